@@ -1,11 +1,15 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::wrong_self_convention)]
 
+use std::collections::BTreeSet;
+
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::update::{DerivedDatabase, DocumentsWriteMode, IndexDocumentsMethod};
 use meilisearch_types::settings::Unchecked;
-use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
+use meilisearch_types::tasks::{
+    Details, DumpAnonymization, IndexSwap, KindWithContent, Status, Task, TaskId,
+};
 use meilisearch_types::InstanceUid;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
@@ -16,7 +20,7 @@ mod reader;
 mod writer;
 
 pub use error::Error;
-pub use reader::{DumpReader, UpdateFile};
+pub use reader::{CompatReport, DumpReader, UpdateFile};
 pub use writer::DumpWriter;
 
 const CURRENT_DUMP_VERSION: Version = Version::V6;
@@ -96,6 +100,8 @@ pub enum KindDump {
         method: IndexDocumentsMethod,
         documents_count: u64,
         allow_index_creation: bool,
+        #[serde(default, skip_serializing_if = "DocumentsWriteMode::is_upsert")]
+        write_mode: DocumentsWriteMode,
     },
     DocumentDeletion {
         documents_ids: Vec<String>,
@@ -121,6 +127,9 @@ pub enum KindDump {
     IndexUpdate {
         primary_key: Option<String>,
     },
+    IndexRebuild {
+        databases: BTreeSet<DerivedDatabase>,
+    },
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
@@ -135,8 +144,15 @@ pub enum KindDump {
     DumpCreation {
         keys: Vec<Key>,
         instance_uid: Option<InstanceUid>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        anonymization: Option<DumpAnonymization>,
     },
     SnapshotCreation,
+    SnapshotComparison {
+        index_uid: String,
+        snapshot: String,
+        other_snapshot: Option<String>,
+    },
 }
 
 impl From<Task> for TaskDump {
@@ -164,12 +180,14 @@ impl From<KindWithContent> for KindDump {
                 method,
                 documents_count,
                 allow_index_creation,
+                write_mode,
                 ..
             } => KindDump::DocumentImport {
                 primary_key,
                 method,
                 documents_count,
                 allow_index_creation,
+                write_mode,
             },
             KindWithContent::DocumentDeletion { documents_ids, .. } => {
                 KindDump::DocumentDeletion { documents_ids }
@@ -194,6 +212,7 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::IndexUpdate { primary_key, .. } => {
                 KindDump::IndexUpdate { primary_key }
             }
+            KindWithContent::IndexRebuild { databases, .. } => KindDump::IndexRebuild { databases },
             KindWithContent::IndexSwap { swaps } => KindDump::IndexSwap { swaps },
             KindWithContent::TaskCancelation { query, tasks } => {
                 KindDump::TaskCancelation { query, tasks }
@@ -201,10 +220,13 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::TaskDeletion { query, tasks } => {
                 KindDump::TasksDeletion { query, tasks }
             }
-            KindWithContent::DumpCreation { keys, instance_uid } => {
-                KindDump::DumpCreation { keys, instance_uid }
+            KindWithContent::DumpCreation { keys, instance_uid, anonymization } => {
+                KindDump::DumpCreation { keys, instance_uid, anonymization }
             }
             KindWithContent::SnapshotCreation => KindDump::SnapshotCreation,
+            KindWithContent::SnapshotComparison { index_uid, snapshot, other_snapshot } => {
+                KindDump::SnapshotComparison { index_uid, snapshot, other_snapshot }
+            }
         }
     }
 }
@@ -265,16 +287,19 @@ pub(crate) mod test {
     pub fn create_test_settings() -> Settings<Checked> {
         let settings = Settings {
             displayed_attributes: Setting::Set(vec![S("race"), S("name")]).into(),
-            searchable_attributes: Setting::Set(vec![S("name"), S("race")]).into(),
+            searchable_attributes: Setting::Set(vec!["name".into(), "race".into()]).into(),
             filterable_attributes: Setting::Set(btreeset! { S("race"), S("age") }),
             sortable_attributes: Setting::Set(btreeset! { S("age") }),
+            dense_sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
+            exact_words: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
+            version_attribute: Setting::NotSet,
             proximity_precision: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::Set(FacetingSettings {
@@ -282,10 +307,32 @@ pub(crate) mod test {
                 sort_facet_values_by: Setting::Set(
                     btreemap! { S("age") => FacetValuesSort::Count },
                 ),
+                value_aliases: Setting::NotSet,
             }),
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
+            prefix_cache_size: Setting::NotSet,
+            long_query_threshold: Setting::NotSet,
+            quote_characters: Setting::NotSet,
+            language_preset: Setting::NotSet,
+            presence_only_attributes: Setting::NotSet,
+            chunking: Setting::NotSet,
+            document_hook: Setting::NotSet,
+            computed_fields: Setting::NotSet,
+            prepared_filters: Setting::NotSet,
+            pii_attributes: Setting::NotSet,
+            query_limits: Setting::NotSet,
+            regex_filter: Setting::NotSet,
+            field_languages: Setting::NotSet,
+            min_prefix_length: Setting::NotSet,
+            blocked_terms: Setting::NotSet,
+            new_fields: Setting::NotSet,
+            query_rewrites: Setting::NotSet,
+            position_decay: Setting::NotSet,
+            batching: Setting::NotSet,
+            collation: Setting::NotSet,
+            tie_breaker: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -303,11 +350,15 @@ pub(crate) mod test {
                         allow_index_creation: true,
                         primary_key: Some(S("bone")),
                         documents_count: 12,
+                        write_mode: milli::update::DocumentsWriteMode::Upsert,
                     },
                     canceled_by: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
+                        skipped_documents: 0,
+                        rejected_documents: 0,
+                        rejected_document_ids: vec![],
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -326,11 +377,15 @@ pub(crate) mod test {
                         allow_index_creation: true,
                         primary_key: None,
                         documents_count: 2,
+                        write_mode: milli::update::DocumentsWriteMode::Upsert,
                     },
                     canceled_by: None,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
+                        skipped_documents: 0,
+                        rejected_documents: 0,
+                        rejected_document_ids: vec![],
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -369,6 +424,8 @@ pub(crate) mod test {
                 actions: vec![Action::DocumentsAll],
                 indexes: vec![IndexUidPattern::from_str("doggos").unwrap()],
                 expires_at: Some(datetime!(4130-03-14 12:21 UTC)),
+                search_parameters: Default::default(),
+                principals: Vec::new(),
                 created_at: datetime!(1960-11-15 0:00 UTC),
                 updated_at: datetime!(2022-11-10 0:00 UTC),
             },
@@ -379,6 +436,8 @@ pub(crate) mod test {
                 actions: vec![Action::All],
                 indexes: vec![IndexUidPattern::all()],
                 expires_at: None,
+                search_parameters: Default::default(),
+                principals: Vec::new(),
                 created_at: datetime!(0000-01-01 00:01 UTC),
                 updated_at: datetime!(1964-05-04 17:25 UTC),
             },
@@ -389,6 +448,8 @@ pub(crate) mod test {
                 actions: vec![],
                 indexes: vec![],
                 expires_at: None,
+                search_parameters: Default::default(),
+                principals: Vec::new(),
                 created_at: datetime!(400-02-29 0:00 UTC),
                 updated_at: datetime!(1024-02-29 0:00 UTC),
             },
@@ -1,3 +1,4 @@
+pub mod report;
 pub mod v1_to_v2;
 pub mod v2_to_v3;
 pub mod v3_to_v4;
@@ -0,0 +1,50 @@
+//! Collects what the compatibility layers converted or left out while reading an old dump,
+//! so that it can be reported once the dump is imported instead of only being logged.
+
+use std::cell::RefCell;
+
+/// What was converted or left out while converting a dump to the current version.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CompatReport {
+    /// What was converted to its equivalent in the current version.
+    pub migrated: Vec<String>,
+    /// What couldn't be converted and was left out.
+    pub dropped: Vec<String>,
+}
+
+thread_local! {
+    // The conversions are implemented with `From`, which can't be given the report,
+    // and a dump is always read from a single thread.
+    static REPORT: RefCell<CompatReport> = RefCell::new(CompatReport::default());
+}
+
+/// Reports something that was converted to its equivalent in the current version.
+pub(crate) fn migrated(message: impl Into<String>) {
+    REPORT.with(|report| report.borrow_mut().migrated.push(message.into()));
+}
+
+/// Reports something that couldn't be converted and was left out.
+pub(crate) fn dropped(message: impl Into<String>) {
+    REPORT.with(|report| report.borrow_mut().dropped.push(message.into()));
+}
+
+/// Returns what was reported since the last call, clearing the report.
+pub(crate) fn take() -> CompatReport {
+    REPORT.with(|report| std::mem::take(&mut *report.borrow_mut()))
+}
+
+/// Prefixes what is reported while running the given function with the uid of the index.
+pub(crate) fn in_index<T>(index_uid: &str, f: impl FnOnce() -> T) -> T {
+    let (migrated, dropped) = REPORT.with(|report| {
+        let report = report.borrow();
+        (report.migrated.len(), report.dropped.len())
+    });
+    let result = f();
+    REPORT.with(|report| {
+        let mut report = report.borrow_mut();
+        let prefix = |message: &mut String| *message = format!("Index `{index_uid}`: {message}");
+        report.migrated[migrated..].iter_mut().for_each(prefix);
+        report.dropped[dropped..].iter_mut().for_each(prefix);
+    });
+    result
+}
@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+use super::report;
 use super::v2_to_v3::CompatV2ToV3;
 use crate::reader::{v1, v2, Document};
 use crate::Result;
@@ -101,6 +102,12 @@ impl CompatIndexV1ToV2 {
 
 impl From<v1::settings::Settings> for v2::Settings<v2::Unchecked> {
     fn from(source: v1::settings::Settings) -> Self {
+        if matches!(&source.attributes_for_faceting, Some(Some(attributes)) if !attributes.is_empty())
+        {
+            report::migrated(
+                "The `attributesForFaceting` setting was converted to the `filterableAttributes` and `sortableAttributes` settings.",
+            );
+        }
         Self {
             displayed_attributes: option_to_setting(source.displayed_attributes)
                 .map(|displayed| displayed.into_iter().collect()),
@@ -124,6 +131,9 @@ impl From<v1::settings::Settings> for v2::Settings<v2::Unchecked> {
                                     "Could not import the following ranking rule: `{}`.",
                                     ranking_rule
                                 );
+                                report::dropped(format!(
+                                    "The ranking rule `{ranking_rule}`, which could not be imported."
+                                ));
                                 None
                             }
                         }
@@ -157,6 +167,10 @@ impl From<v1::update::UpdateStatus> for Option<v2::updates::UpdateStatus> {
                     content.update_id
                 );
                 tracing::warn!("Task will be skipped in the queue of imported tasks.");
+                report::dropped(format!(
+                    "The enqueued task {}, enqueued tasks can't be imported from v1 dumps.",
+                    content.update_id
+                ));
 
                 return None;
             }
@@ -230,6 +244,7 @@ impl From<v1::update::UpdateType> for Option<v2::updates::UpdateMeta> {
             v1::update::UpdateType::ClearAll => v2::updates::UpdateMeta::ClearDocuments,
             v1::update::UpdateType::Customs => {
                 tracing::warn!("Ignoring task with type 'Customs' that is no longer supported");
+                report::dropped("A task of type `customs`, which is no longer supported.");
                 return None;
             }
             v1::update::UpdateType::DocumentsAddition { .. } => {
@@ -297,6 +312,7 @@ impl From<v1::settings::RankingRule> for Option<v2::settings::Criterion> {
             v1::settings::RankingRule::Attribute => Some(v2::settings::Criterion::Attribute),
             v1::settings::RankingRule::WordsPosition => {
                 tracing::warn!("Removing the 'WordsPosition' ranking rule that is no longer supported, please check the resulting ranking rules of your indexes");
+                report::dropped("The `wordsPosition` ranking rule, which is no longer supported.");
                 None
             }
             v1::settings::RankingRule::Exactness => Some(v2::settings::Criterion::Exactness),
@@ -3,6 +3,7 @@ use std::str::FromStr;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
+use super::report;
 use super::v1_to_v2::{CompatIndexV1ToV2, CompatV1ToV2};
 use super::v3_to_v4::CompatV3ToV4;
 use crate::reader::{v2, v3, Document};
@@ -147,6 +148,10 @@ impl From<v2::updates::UpdateStatus> for v3::updates::UpdateStatus {
                     Err(e) => {
                         tracing::warn!("Error with task {}: {}", processing.from.update_id, e);
                         tracing::warn!("Task will be marked as `Failed`.");
+                        report::migrated(format!(
+                            "The task {} could not be read and was marked as failed: {e}.",
+                            processing.from.update_id
+                        ));
                         v3::updates::UpdateStatus::Failed(v3::updates::Failed {
                             from: v3::updates::Processing {
                                 from: v3::updates::Enqueued {
@@ -173,6 +178,10 @@ impl From<v2::updates::UpdateStatus> for v3::updates::UpdateStatus {
                     Err(e) => {
                         tracing::warn!("Error with task {}: {}", enqueued.update_id, e);
                         tracing::warn!("Task will be marked as `Failed`.");
+                        report::migrated(format!(
+                            "The task {} could not be read and was marked as failed: {e}.",
+                            enqueued.update_id
+                        ));
                         v3::updates::UpdateStatus::Failed(v3::updates::Failed {
                             from: v3::updates::Processing {
                                 from: v3::updates::Enqueued {
@@ -353,6 +362,9 @@ impl From<String> for v3::Code {
             "missing_payload" => v3::Code::MissingPayload,
             other => {
                 tracing::warn!("Unknown error code {}", other);
+                report::migrated(format!(
+                    "The unknown error code `{other}` was replaced by an unretrievable error code."
+                ));
                 v3::Code::UnretrievableErrorCode
             }
         }
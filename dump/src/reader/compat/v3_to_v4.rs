@@ -1,3 +1,4 @@
+use super::report;
 use super::v2_to_v3::{CompatIndexV2ToV3, CompatV2ToV3};
 use super::v4_to_v5::CompatV4ToV5;
 use crate::reader::{v3, v4, UpdateFile};
@@ -87,9 +88,17 @@ impl CompatV3ToV4 {
                                 if task.update.is_finished() {
                                     // we're fucking with his history but not his data, that's ok-ish.
                                     tracing::warn!("The index-uuid will be set as `unknown`.");
+                                    report::migrated(format!(
+                                        "The index of the task {} could not be retrieved and was set to `unknown`.",
+                                        task.update.id()
+                                    ));
                                     String::from("unknown")
                                 } else {
                                     tracing::warn!("The task will be ignored.");
+                                    report::dropped(format!(
+                                        "The task {} whose index could not be retrieved.",
+                                        task.update.id()
+                                    ));
                                     return None;
                                 }
                             }
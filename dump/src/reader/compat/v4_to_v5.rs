@@ -1,3 +1,4 @@
+use super::report;
 use super::v3_to_v4::{CompatIndexV3ToV4, CompatV3ToV4};
 use super::v5_to_v6::CompatV5ToV6;
 use crate::reader::{v4, v5, Document};
@@ -306,6 +307,9 @@ impl From<v4::ResponseError> for v5::ResponseError {
             "invalid_api_key_description" => v5::Code::InvalidApiKeyDescription,
             other => {
                 tracing::warn!("Unknown error code {}", other);
+                report::migrated(format!(
+                    "The unknown error code `{other}` was replaced by an unretrievable error code."
+                ));
                 v5::Code::UnretrievableErrorCode
             }
         };
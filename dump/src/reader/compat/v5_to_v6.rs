@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+use super::report;
 use super::v4_to_v5::{CompatIndexV4ToV5, CompatV4ToV5};
 use crate::reader::{v5, v6, Document, UpdateFile};
 use crate::Result;
@@ -103,6 +104,7 @@ impl CompatV5ToV6 {
                                 }
                             },
                             allow_index_creation,
+                            write_mode: v6::milli::update::DocumentsWriteMode::Upsert,
                         },
                         v5::tasks::TaskContent::DocumentDeletion { deletion, .. } => match deletion
                         {
@@ -123,7 +125,11 @@ impl CompatV5ToV6 {
                         },
                         v5::tasks::TaskContent::Dump { uid: _ } => {
                             // in v6 we compute the dump_uid from the started_at processing time
-                            v6::Kind::DumpCreation { keys: keys.clone(), instance_uid }
+                            v6::Kind::DumpCreation {
+                                keys: keys.clone(),
+                                instance_uid,
+                                anonymization: None,
+                            }
                         }
                     },
                     canceled_by: None,
@@ -132,6 +138,9 @@ impl CompatV5ToV6 {
                             v6::Details::DocumentAdditionOrUpdate {
                                 received_documents: received_documents as u64,
                                 indexed_documents,
+                                skipped_documents: 0,
+                                rejected_documents: 0,
+                                rejected_document_ids: vec![],
                             }
                         }
                         v5::Details::Settings { settings } => {
@@ -186,6 +195,8 @@ impl CompatV5ToV6 {
                     })
                     .collect(),
                 expires_at: key.expires_at,
+                search_parameters: Default::default(),
+                principals: Vec::new(),
                 created_at: key.created_at,
                 updated_at: key.updated_at,
             })
@@ -305,6 +316,9 @@ impl From<v5::ResponseError> for v6::ResponseError {
             "api_key_already_exists" => v6::Code::ApiKeyAlreadyExists,
             other => {
                 tracing::warn!("Unknown error code {}", other);
+                report::migrated(format!(
+                    "The unknown error code `{other}` was replaced by an unretrievable error code."
+                ));
                 v6::Code::UnretrievableErrorCode
             }
         };
@@ -316,9 +330,17 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
     fn from(settings: v5::Settings<T>) -> Self {
         v6::Settings {
             displayed_attributes: v6::Setting::from(settings.displayed_attributes).into(),
-            searchable_attributes: v6::Setting::from(settings.searchable_attributes).into(),
+            searchable_attributes: match settings.searchable_attributes {
+                v5::Setting::Set(names) => {
+                    v6::Setting::Set(names.iter().map(|name| name.as_str().into()).collect())
+                }
+                v5::Setting::Reset => v6::Setting::Reset,
+                v5::Setting::NotSet => v6::Setting::NotSet,
+            }
+            .into(),
             filterable_attributes: settings.filterable_attributes.into(),
             sortable_attributes: settings.sortable_attributes.into(),
+            dense_sortable_attributes: v6::Setting::NotSet,
             ranking_rules: {
                 match settings.ranking_rules {
                     v5::settings::Setting::Set(ranking_rules) => {
@@ -329,7 +351,10 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                                     new_ranking_rules.push(new_rule);
                                 }
                                 Err(_) => {
-                                    tracing::warn!("Error while importing settings. The ranking rule `{rule}` does not exist anymore.")
+                                    tracing::warn!("Error while importing settings. The ranking rule `{rule}` does not exist anymore.");
+                                    report::dropped(format!(
+                                        "The ranking rule `{rule}`, which does not exist anymore."
+                                    ));
                                 }
                             }
                         }
@@ -343,8 +368,10 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             non_separator_tokens: v6::Setting::NotSet,
             separator_tokens: v6::Setting::NotSet,
             dictionary: v6::Setting::NotSet,
+            exact_words: v6::Setting::NotSet,
             synonyms: settings.synonyms.into(),
             distinct_attribute: settings.distinct_attribute.into(),
+            version_attribute: v6::Setting::NotSet,
             proximity_precision: v6::Setting::NotSet,
             typo_tolerance: match settings.typo_tolerance {
                 v5::Setting::Set(typo) => v6::Setting::Set(v6::TypoTolerance {
@@ -359,6 +386,11 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                     },
                     disable_on_words: typo.disable_on_words.into(),
                     disable_on_attributes: typo.disable_on_attributes.into(),
+                    half_typos: v6::Setting::NotSet,
+                    disable_on_numbers: v6::Setting::NotSet,
+                    first_letter_typos: v6::Setting::NotSet,
+                    enable_phonetic: v6::Setting::NotSet,
+                    typo_costs: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -367,6 +399,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::Set(faceting) => v6::Setting::Set(v6::FacetingSettings {
                     max_values_per_facet: faceting.max_values_per_facet.into(),
                     sort_facet_values_by: v6::Setting::NotSet,
+                    value_aliases: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -380,6 +413,27 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             },
             embedders: v6::Setting::NotSet,
             search_cutoff_ms: v6::Setting::NotSet,
+            prefix_cache_size: v6::Setting::NotSet,
+            long_query_threshold: v6::Setting::NotSet,
+            quote_characters: v6::Setting::NotSet,
+            language_preset: v6::Setting::NotSet,
+            presence_only_attributes: v6::Setting::NotSet,
+            chunking: v6::Setting::NotSet,
+            document_hook: v6::Setting::NotSet,
+            computed_fields: v6::Setting::NotSet,
+            prepared_filters: v6::Setting::NotSet,
+            pii_attributes: v6::Setting::NotSet,
+            query_limits: v6::Setting::NotSet,
+            regex_filter: v6::Setting::NotSet,
+            field_languages: v6::Setting::NotSet,
+            min_prefix_length: v6::Setting::NotSet,
+            blocked_terms: v6::Setting::NotSet,
+            new_fields: v6::Setting::NotSet,
+            query_rewrites: v6::Setting::NotSet,
+            position_decay: v6::Setting::NotSet,
+            batching: v6::Setting::NotSet,
+            collation: v6::Setting::NotSet,
+            tie_breaker: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
@@ -5,6 +5,8 @@ use flate2::bufread::GzDecoder;
 use serde::Deserialize;
 use tempfile::TempDir;
 
+use self::compat::report;
+pub use self::compat::report::CompatReport;
 use self::compat::v4_to_v5::CompatV4ToV5;
 use self::compat::v5_to_v6::{CompatIndexV5ToV6, CompatV5ToV6};
 use self::v5::V5Reader;
@@ -44,6 +46,9 @@ impl DumpReader {
         let mut meta_file = File::open(path.path().join("metadata.json"))?;
         let MetadataVersion { dump_version } = serde_json::from_reader(&mut meta_file)?;
 
+        // forget what was reported while reading a previous dump
+        report::take();
+
         match dump_version {
             Version::V1 => {
                 Ok(v1::V1Reader::open(path)?.to_v2().to_v3().to_v4().to_v5().to_v6().into())
@@ -114,6 +119,12 @@ impl DumpReader {
             DumpReader::Compat(compat) => compat.features(),
         }
     }
+
+    /// Returns what was converted or left out while reading the dump so far,
+    /// which is always empty for a dump of the current version.
+    pub fn take_compat_report(&mut self) -> CompatReport {
+        report::take()
+    }
 }
 
 impl From<V6Reader> for DumpReader {
@@ -171,7 +182,10 @@ impl DumpIndexReader {
     pub fn settings(&mut self) -> Result<v6::Settings<v6::Checked>> {
         match self {
             DumpIndexReader::Current(v6) => v6.settings(),
-            DumpIndexReader::Compat(compat) => compat.settings(),
+            DumpIndexReader::Compat(compat) => {
+                let index_uid = compat.metadata().uid.clone();
+                report::in_index(&index_uid, || compat.settings())
+            }
         }
     }
 }
@@ -871,5 +885,15 @@ pub(crate) mod test {
         let documents = spells.documents().unwrap().collect::<Result<Vec<_>>>().unwrap();
         assert_eq!(documents.len(), 10);
         meili_snap::snapshot_hash!(format!("{:#?}", documents), @"aa24c0cfc733d66c396237ad44263bed");
+
+        // what the conversion of the settings changed
+        let report = dump.take_compat_report();
+        assert!(report.migrated.contains(&String::from(
+            "Index `movies`: The `attributesForFaceting` setting was converted to the `filterableAttributes` and `sortableAttributes` settings."
+        )));
+        assert!(report.dropped.contains(&String::from(
+            "Index `dnd_spells`: The `wordsPosition` ranking rule, which is no longer supported."
+        )));
+        assert!(dump.take_compat_report().dropped.is_empty());
     }
 }
@@ -184,16 +184,23 @@ pub struct V6IndexReader {
     metadata: IndexMetadata,
     documents: BufReader<File>,
     settings: BufReader<File>,
+    document_hook_module: Option<Vec<u8>>,
 }
 
 impl V6IndexReader {
     pub fn new(_name: String, path: &Path) -> Result<Self> {
         let metadata = File::open(path.join("metadata.json"))?;
+        let document_hook_module = match fs::read(path.join("document_hook.wasm")) {
+            Ok(module) => Some(module),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
 
         let ret = V6IndexReader {
             metadata: serde_json::from_reader(metadata)?,
             documents: BufReader::new(File::open(path.join("documents.jsonl"))?),
             settings: BufReader::new(File::open(path.join("settings.json"))?),
+            document_hook_module,
         };
 
         Ok(ret)
@@ -210,7 +217,10 @@ impl V6IndexReader {
     }
 
     pub fn settings(&mut self) -> Result<Settings<Checked>> {
-        let settings: Settings<Unchecked> = serde_json::from_reader(&mut self.settings)?;
+        let mut settings: Settings<Unchecked> = serde_json::from_reader(&mut self.settings)?;
+        if let Some(module) = &self.document_hook_module {
+            settings.restore_document_hook_module(module);
+        }
         Ok(settings.check())
     }
 }
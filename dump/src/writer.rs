@@ -157,6 +157,7 @@ impl UpdateFile {
 }
 
 pub struct IndexWriter {
+    path: PathBuf,
     documents: BufWriter<File>,
     settings: File,
 }
@@ -171,7 +172,7 @@ impl IndexWriter {
         let documents = File::create(path.join("documents.jsonl"))?;
         let settings = File::create(path.join("settings.json"))?;
 
-        Ok(IndexWriter { documents: BufWriter::new(documents), settings })
+        Ok(IndexWriter { path, documents: BufWriter::new(documents), settings })
     }
 
     pub fn push_document(&mut self, document: &Map<String, Value>) -> Result<()> {
@@ -185,6 +186,10 @@ impl IndexWriter {
         Ok(())
     }
 
+    pub fn document_hook_module(&mut self, module: &[u8]) -> Result<()> {
+        Ok(fs::write(self.path.join("document_hook.wasm"), module)?)
+    }
+
     pub fn settings(mut self, settings: &Settings<Checked>) -> Result<()> {
         self.settings.write_all(&serde_json::to_vec(&settings)?)?;
         Ok(())
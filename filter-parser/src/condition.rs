@@ -1,8 +1,9 @@
 //! BNF grammar:
 //!
 //! ```text
-//! condition      = value ("==" | ">" ...) value
+//! condition      = value ("==" | ">" | "~=" ...) value
 //! to             = value value TO value
+//! matches        = value MATCHES value
 //! ```
 
 use nom::branch::alt;
@@ -25,18 +26,25 @@ pub enum Condition<'a> {
     Exists,
     LowerThan(Token<'a>),
     LowerThanOrEqual(Token<'a>),
-    Between { from: Token<'a>, to: Token<'a> },
+    Between {
+        from: Token<'a>,
+        to: Token<'a>,
+    },
+    Matches(Token<'a>),
+    /// An equality tolerating one typo, e.g. `brand ~= adidsa`.
+    FuzzyEqual(Token<'a>),
 }
 
 /// condition      = value ("==" | ">" ...) value
 pub fn parse_condition(input: Span) -> IResult<FilterCondition> {
-    let operator = alt((tag("<="), tag(">="), tag("!="), tag("<"), tag(">"), tag("=")));
+    let operator = alt((tag("<="), tag(">="), tag("!="), tag("~="), tag("<"), tag(">"), tag("=")));
     let (input, (fid, op, value)) = tuple((parse_value, operator, cut(parse_value)))(input)?;
 
     let condition = match *op.fragment() {
         "<=" => FilterCondition::Condition { fid, op: LowerThanOrEqual(value) },
         ">=" => FilterCondition::Condition { fid, op: GreaterThanOrEqual(value) },
         "!=" => FilterCondition::Condition { fid, op: NotEqual(value) },
+        "~=" => FilterCondition::Condition { fid, op: FuzzyEqual(value) },
         "<" => FilterCondition::Condition { fid, op: LowerThan(value) },
         ">" => FilterCondition::Condition { fid, op: GreaterThan(value) },
         "=" => FilterCondition::Condition { fid, op: Equal(value) },
@@ -92,6 +100,14 @@ pub fn parse_not_exists(input: Span) -> IResult<FilterCondition> {
     Ok((input, FilterCondition::Not(Box::new(FilterCondition::Condition { fid: key, op: Exists }))))
 }
 
+/// matches        = value "MATCHES" WS+ value
+pub fn parse_matches(input: Span) -> IResult<FilterCondition> {
+    let (input, (key, _, _, regex)) =
+        tuple((parse_value, tag("MATCHES"), multispace1, cut(parse_value)))(input)?;
+
+    Ok((input, FilterCondition::Condition { fid: key, op: Matches(regex) }))
+}
+
 /// to             = value value "TO" WS+ value
 pub fn parse_to(input: Span) -> IResult<FilterCondition> {
     let (input, (key, from, _, _, to)) =
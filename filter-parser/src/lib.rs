@@ -6,11 +6,12 @@
 //! or             = and ("OR" WS+ and)*
 //! and            = not ("AND" WS+ not)*
 //! not            = ("NOT" WS+ not) | primary
-//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
+//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | matches | to
 //! in             = value "IN" WS* "[" value_list "]"
-//! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
+//! condition      = value ("=" | "!=" | "~=" | ">" | ">=" | "<" | "<=") value
 //! exists         = value "EXISTS"
 //! not_exists     = value "NOT" WS+ "EXISTS"
+//! matches        = value "MATCHES" WS+ value
 //! to             = value value "TO" WS+ value
 //! value          = WS* ( word | singleQuoted | doubleQuoted) WS+
 //! value_list     = (value ("," value)* ","?)?
@@ -49,7 +50,7 @@ use std::fmt::Debug;
 pub use condition::{parse_condition, parse_to, Condition};
 use condition::{
     parse_exists, parse_is_empty, parse_is_not_empty, parse_is_not_null, parse_is_null,
-    parse_not_exists,
+    parse_matches, parse_not_exists,
 };
 use error::{cut_with_err, ExpectedValueKind, NomErrorExt};
 pub use error::{Error, ErrorKind};
@@ -426,7 +427,7 @@ fn parse_error_reserved_keyword(input: Span) -> IResult<FilterCondition> {
     }
 }
 
-/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | to
+/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | matches | to
 fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
         return Err(nom::Err::Error(Error::new_from_kind(input, ErrorKind::DepthLimitReached)));
@@ -451,6 +452,7 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         parse_is_not_empty,
         parse_exists,
         parse_not_exists,
+        parse_matches,
         parse_to,
         // the next lines are only for error handling and are written at the end to have the less possible performance impact
         parse_geo,
@@ -528,12 +530,14 @@ impl<'a> std::fmt::Display for Condition<'a> {
             Condition::GreaterThanOrEqual(token) => write!(f, ">= {token}"),
             Condition::Equal(token) => write!(f, "= {token}"),
             Condition::NotEqual(token) => write!(f, "!= {token}"),
+            Condition::FuzzyEqual(token) => write!(f, "~= {token}"),
             Condition::Null => write!(f, "IS NULL"),
             Condition::Empty => write!(f, "IS EMPTY"),
             Condition::Exists => write!(f, "EXISTS"),
             Condition::LowerThan(token) => write!(f, "< {token}"),
             Condition::LowerThanOrEqual(token) => write!(f, "<= {token}"),
             Condition::Between { from, to } => write!(f, "{from} TO {to}"),
+            Condition::Matches(token) => write!(f, "MATCHES {token}"),
         }
     }
 }
@@ -607,6 +611,9 @@ pub mod tests {
         // Test conditions
         insta::assert_snapshot!(p("channel != ponce"), @"{channel} != {ponce}");
         insta::assert_snapshot!(p("NOT channel = ponce"), @"NOT ({channel} = {ponce})");
+        insta::assert_snapshot!(p("brand ~= adidsa"), @"{brand} ~= {adidsa}");
+        insta::assert_snapshot!(p("brand~='adidas originals'"), @"{brand} ~= {adidas originals}");
+        insta::assert_snapshot!(p("NOT brand ~= adidsa"), @"NOT ({brand} ~= {adidsa})");
         insta::assert_snapshot!(p("subscribers < 1000"), @"{subscribers} < {1000}");
         insta::assert_snapshot!(p("subscribers > 1000"), @"{subscribers} > {1000}");
         insta::assert_snapshot!(p("subscribers <= 1000"), @"{subscribers} <= {1000}");
@@ -639,6 +646,11 @@ pub mod tests {
         insta::assert_snapshot!(p("NOT subscribers NOT EXISTS"), @"{subscribers} EXISTS");
         insta::assert_snapshot!(p("subscribers NOT   EXISTS"), @"NOT ({subscribers} EXISTS)");
 
+        // Test MATCHES
+        insta::assert_snapshot!(p("title MATCHES '^kef.r$'"), @"{title} MATCHES {^kef.r$}");
+        insta::assert_snapshot!(p("NOT title MATCHES \"[0-9]+\""), @"NOT ({title} MATCHES {[0-9]+})");
+        insta::assert_snapshot!(p("title MATCHES kefir AND id = 1"), @"AND[{title} MATCHES {kefir}, {id} = {1}, ]");
+
         // Test nested NOT
         insta::assert_snapshot!(p("NOT NOT NOT NOT x = 5"), @"{x} = {5}");
         insta::assert_snapshot!(p("NOT NOT (NOT NOT x = 5)"), @"{x} = {5}");
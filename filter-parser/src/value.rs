@@ -196,7 +196,7 @@ fn is_value_component(c: char) -> bool {
 }
 
 fn is_syntax_component(c: char) -> bool {
-    c.is_whitespace() || ['(', ')', '=', '<', '>', '!'].contains(&c)
+    c.is_whitespace() || ['(', ')', '=', '<', '>', '!', '~'].contains(&c)
 }
 
 fn is_keyword(s: &str) -> bool {
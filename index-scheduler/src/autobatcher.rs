@@ -34,6 +34,7 @@ enum AutobatchKind {
     IndexCreation,
     IndexDeletion,
     IndexUpdate,
+    IndexRebuild,
     IndexSwap,
 }
 
@@ -78,11 +79,13 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::IndexDeletion { .. } => AutobatchKind::IndexDeletion,
             KindWithContent::IndexCreation { .. } => AutobatchKind::IndexCreation,
             KindWithContent::IndexUpdate { .. } => AutobatchKind::IndexUpdate,
+            KindWithContent::IndexRebuild { .. } => AutobatchKind::IndexRebuild,
             KindWithContent::IndexSwap { .. } => AutobatchKind::IndexSwap,
             KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
-            | KindWithContent::SnapshotCreation => {
+            | KindWithContent::SnapshotCreation
+            | KindWithContent::SnapshotComparison { .. } => {
                 panic!("The autobatcher should never be called with tasks that don't apply to an index.")
             }
         }
@@ -134,6 +137,9 @@ pub enum BatchKind {
     IndexUpdate {
         id: TaskId,
     },
+    IndexRebuild {
+        id: TaskId,
+    },
     IndexSwap {
         id: TaskId,
     },
@@ -179,6 +185,7 @@ impl BatchKind {
             K::IndexCreation => (Break(BatchKind::IndexCreation { id: task_id }), true),
             K::IndexDeletion => (Break(BatchKind::IndexDeletion { ids: vec![task_id] }), false),
             K::IndexUpdate => (Break(BatchKind::IndexUpdate { id: task_id }), false),
+            K::IndexRebuild => (Break(BatchKind::IndexRebuild { id: task_id }), false),
             K::IndexSwap => (Break(BatchKind::IndexSwap { id: task_id }), false),
             K::DocumentClear => (Continue(BatchKind::DocumentClear { ids: vec![task_id] }), false),
             K::DocumentImport { method, allow_index_creation, primary_key: pk }
@@ -228,7 +235,7 @@ impl BatchKind {
 
         match (self, kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentEdition | K::DocumentDeletionByFilter) => Break(this),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexRebuild | K::IndexSwap | K::DocumentEdition | K::DocumentDeletionByFilter) => Break(this),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break(this)
@@ -524,6 +531,7 @@ impl BatchKind {
                 BatchKind::IndexCreation { .. }
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
+                | BatchKind::IndexRebuild { .. }
                 | BatchKind::IndexSwap { .. }
                 | BatchKind::DocumentEdition { .. }
                 | BatchKind::DocumentDeletionByFilter { .. },
@@ -576,6 +584,7 @@ pub fn autobatch(
 
 #[cfg(test)]
 mod tests {
+    use meilisearch_types::milli::update::DocumentsWriteMode;
     use meilisearch_types::tasks::IndexSwap;
     use uuid::Uuid;
 
@@ -606,6 +615,7 @@ mod tests {
             content_file: Uuid::new_v4(),
             documents_count: 0,
             allow_index_creation,
+            write_mode: DocumentsWriteMode::Upsert,
         }
     }
 
@@ -26,17 +26,22 @@ use std::io::BufWriter;
 use dump::IndexMetadata;
 use meilisearch_types::error::Code;
 use meilisearch_types::heed::{RoTxn, RwTxn};
+use meilisearch_types::milli::batch_limits::BatchLimits;
 use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
 use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::update::{
-    IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig, Settings as MilliSettings,
+    DerivedDatabase, DocumentsWriteMode, IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig,
+    RebuildDatabases, Settings as MilliSettings,
 };
 use meilisearch_types::milli::vector::parsed_vectors::{
     ExplicitVectors, VectorOrArrayOfVectors, RESERVED_VECTORS_FIELD_NAME,
 };
 use meilisearch_types::milli::{self, Filter, Object};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{
+    AnonymizationStrategy, Details, DumpAnonymization, IndexSwap, Kind, KindWithContent, Status,
+    Task,
+};
 use meilisearch_types::{compression, Index, VERSION_FILE_NAME};
 use roaring::RoaringBitmap;
 use time::macros::format_description;
@@ -44,6 +49,7 @@ use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::autobatcher::{self, BatchKind};
+use crate::snapshot_comparison::{compare_indexes, SnapshotIndex};
 use crate::utils::{self, swap_index_uid_in_task};
 use crate::{Error, IndexScheduler, MustStopProcessing, ProcessingTasks, Result, TaskId};
 
@@ -65,6 +71,7 @@ pub(crate) enum Batch {
     TaskDeletions(Vec<Task>),
     SnapshotCreation(Vec<Task>),
     Dump(Task),
+    SnapshotComparison(Task),
     IndexOperation {
         op: IndexOperation,
         must_create_index: bool,
@@ -79,6 +86,11 @@ pub(crate) enum Batch {
         primary_key: Option<String>,
         task: Task,
     },
+    IndexRebuild {
+        index_uid: String,
+        databases: BTreeSet<DerivedDatabase>,
+        task: Task,
+    },
     IndexDeletion {
         index_uid: String,
         tasks: Vec<Task>,
@@ -91,7 +103,7 @@ pub(crate) enum Batch {
 
 #[derive(Debug)]
 pub(crate) enum DocumentOperation {
-    Add(Uuid),
+    Add(Uuid, DocumentsWriteMode),
     Delete(Vec<String>),
 }
 
@@ -153,8 +165,10 @@ impl Batch {
         match self {
             Batch::TaskCancelation { task, .. }
             | Batch::Dump(task)
+            | Batch::SnapshotComparison(task)
             | Batch::IndexCreation { task, .. }
-            | Batch::IndexUpdate { task, .. } => {
+            | Batch::IndexUpdate { task, .. }
+            | Batch::IndexRebuild { task, .. } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
             }
             Batch::SnapshotCreation(tasks)
@@ -197,10 +211,12 @@ impl Batch {
             | TaskDeletions(_)
             | SnapshotCreation(_)
             | Dump(_)
+            | SnapshotComparison(_)
             | IndexSwap { .. } => None,
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexRebuild { index_uid, .. }
             | IndexDeletion { index_uid, .. } => Some(index_uid),
         }
     }
@@ -216,9 +232,11 @@ impl fmt::Display for Batch {
             Batch::TaskDeletions(_) => f.write_str("TaskDeletion")?,
             Batch::SnapshotCreation(_) => f.write_str("SnapshotCreation")?,
             Batch::Dump(_) => f.write_str("Dump")?,
+            Batch::SnapshotComparison(_) => f.write_str("SnapshotComparison")?,
             Batch::IndexOperation { op, .. } => write!(f, "{op}")?,
             Batch::IndexCreation { .. } => f.write_str("IndexCreation")?,
             Batch::IndexUpdate { .. } => f.write_str("IndexUpdate")?,
+            Batch::IndexRebuild { .. } => f.write_str("IndexRebuild")?,
             Batch::IndexDeletion { .. } => f.write_str("IndexDeletion")?,
             Batch::IndexSwap { .. } => f.write_str("IndexSwap")?,
         };
@@ -341,10 +359,11 @@ impl IndexScheduler {
                         KindWithContent::DocumentAdditionOrUpdate {
                             content_file,
                             documents_count,
+                            write_mode,
                             ..
                         } => {
                             documents_counts.push(documents_count);
-                            operations.push(DocumentOperation::Add(content_file));
+                            operations.push(DocumentOperation::Add(content_file, write_mode));
                         }
                         KindWithContent::DocumentDeletion { ref documents_ids, .. } => {
                             documents_counts.push(documents_ids.len() as u64);
@@ -530,6 +549,14 @@ impl IndexScheduler {
                 };
                 Ok(Some(Batch::IndexUpdate { index_uid, primary_key, task }))
             }
+            BatchKind::IndexRebuild { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                let databases = match &task.kind {
+                    KindWithContent::IndexRebuild { databases, .. } => databases.clone(),
+                    _ => unreachable!(),
+                };
+                Ok(Some(Batch::IndexRebuild { index_uid, databases, task }))
+            }
             BatchKind::IndexDeletion { ids } => Ok(Some(Batch::IndexDeletion {
                 index_uid,
                 index_has_been_created: must_create_index,
@@ -547,7 +574,8 @@ impl IndexScheduler {
     /// 2. We get the *next* task to delete.
     /// 3. We get the *next* snapshot to process.
     /// 4. We get the *next* dump to process.
-    /// 5. We get the *next* tasks to process for a specific index.
+    /// 5. We get the *next* snapshot comparison to process.
+    /// 6. We get the *next* tasks to process for a specific index.
     #[tracing::instrument(level = "trace", skip(self, rtxn), target = "indexing::scheduler")]
     pub(crate) fn create_next_batch(&self, rtxn: &RoTxn) -> Result<Option<Batch>> {
         #[cfg(test)]
@@ -590,7 +618,15 @@ impl IndexScheduler {
             )));
         }
 
-        // 5. We make a batch from the unprioritised tasks. Start by taking the next enqueued task.
+        // 5. we batch the snapshot comparisons.
+        let to_compare = self.get_kind(rtxn, Kind::SnapshotComparison)? & enqueued;
+        if let Some(to_compare) = to_compare.min() {
+            return Ok(Some(Batch::SnapshotComparison(
+                self.get_task(rtxn, to_compare)?.ok_or(Error::CorruptedTaskQueue)?,
+            )));
+        }
+
+        // 6. We make a batch from the unprioritised tasks. Start by taking the next enqueued task.
         let task_id = if let Some(task_id) = enqueued.min() { task_id } else { return Ok(None) };
         let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
 
@@ -607,10 +643,12 @@ impl IndexScheduler {
 
         let index_already_exists = self.index_mapper.exists(rtxn, index_name)?;
         let mut primary_key = None;
+        let mut batch_limits = BatchLimits::default();
         if index_already_exists {
             let index = self.index_mapper.index(rtxn, index_name)?;
             let rtxn = index.read_txn()?;
             primary_key = index.primary_key(&rtxn)?.map(|pk| pk.to_string());
+            batch_limits = index.batch_limits(&rtxn)?;
         }
 
         let index_tasks = self.index_tasks(rtxn, index_name)? & enqueued;
@@ -620,7 +658,7 @@ impl IndexScheduler {
         let tasks_limit =
             if self.autobatching_enabled { self.max_number_of_batched_tasks } else { 1 };
 
-        let enqueued = index_tasks
+        let mut enqueued = index_tasks
             .into_iter()
             .take(tasks_limit)
             .map(|task_id| {
@@ -630,6 +668,21 @@ impl IndexScheduler {
             })
             .collect::<Result<Vec<_>>>()?;
 
+        // The index can further limit the number of tasks and the size of the payloads
+        // of its batches. Only the document additions have a payload, stored in the
+        // file store, whose size is only computed when it is limited.
+        let payload_sizes = enqueued.iter().map(|(_, kind)| match kind {
+            KindWithContent::DocumentAdditionOrUpdate { content_file, .. }
+                if batch_limits.max_batch_payload.is_some() =>
+            {
+                // a missing content file fails the task when it is processed
+                self.file_store.compute_size(*content_file).unwrap_or_default()
+            }
+            _ => 0,
+        });
+        let batchable_tasks = batch_limits.batchable_tasks(payload_sizes);
+        enqueued.truncate(batchable_tasks);
+
         if let Some((batchkind, create_index)) =
             autobatcher::autobatch(enqueued, index_already_exists, primary_key.as_deref())
         {
@@ -726,9 +779,20 @@ impl IndexScheduler {
                 }
 
                 let mut wtxn = self.env.write_txn()?;
-                let mut deleted_tasks = self.delete_matched_tasks(&mut wtxn, &matched_tasks)?;
+                let (mut deleted_tasks, retained_content_uuids) =
+                    self.delete_matched_tasks(&mut wtxn, &matched_tasks)?;
                 wtxn.commit()?;
 
+                for content_uuid in retained_content_uuids {
+                    if let Err(error) = self.delete_update_file(content_uuid) {
+                        tracing::error!(
+                            file_content_uuid = %content_uuid,
+                            %error,
+                            "Failed deleting content file"
+                        )
+                    }
+                }
+
                 for task in tasks.iter_mut() {
                     task.status = Status::Succeeded;
                     let KindWithContent::TaskDeletion { tasks, query: _ } = &task.kind else {
@@ -850,13 +914,27 @@ impl IndexScheduler {
             }
             Batch::Dump(mut task) => {
                 let started_at = OffsetDateTime::now_utc();
-                let (keys, instance_uid) =
-                    if let KindWithContent::DumpCreation { keys, instance_uid } = &task.kind {
-                        (keys, instance_uid)
-                    } else {
-                        unreachable!();
-                    };
+                let KindWithContent::DumpCreation { keys, instance_uid, anonymization } =
+                    &task.kind
+                else {
+                    unreachable!();
+                };
                 let dump = dump::DumpWriter::new(*instance_uid)?;
+                // a new salt for every dump, so that the hashes can't be compared between dumps
+                let salt = Uuid::new_v4();
+                let anonymize = |document: &mut Object| {
+                    let Some(DumpAnonymization { attributes, strategy }) = anonymization else {
+                        return;
+                    };
+                    match strategy {
+                        AnonymizationStrategy::Hash => {
+                            milli::pii::hash_attributes(document, attributes, salt.as_bytes())
+                        }
+                        AnonymizationStrategy::Drop => {
+                            milli::pii::remove_pii_attributes(document, attributes)
+                        }
+                    }
+                };
 
                 // 1. dump the keys
                 let mut dump_keys = dump.create_keys()?;
@@ -890,6 +968,12 @@ impl IndexScheduler {
                         t.started_at = Some(started_at);
                         t.finished_at = Some(finished_at);
                     }
+                    // Only the enqueued tasks need the module of the document hook to be processed.
+                    if status != Status::Enqueued {
+                        if let KindWithContent::SettingsUpdate { new_settings, .. } = &mut t.kind {
+                            new_settings.hide_document_hook_module();
+                        }
+                    }
                     let mut dump_content_file = dump_tasks.push_task(&t.into())?;
 
                     // 2.1. Dump the `content_file` associated with the task if there is one and the task is not finished yet.
@@ -909,10 +993,9 @@ impl IndexScheduler {
                             while let Some(doc) =
                                 cursor.next_document().map_err(milli::Error::from)?
                             {
-                                dump_content_file.push_document(&obkv_to_object(
-                                    &doc,
-                                    &documents_batch_index,
-                                )?)?;
+                                let mut document = obkv_to_object(&doc, &documents_batch_index)?;
+                                anonymize(&mut document);
+                                dump_content_file.push_document(&document)?;
                             }
                             dump_content_file.flush()?;
                         }
@@ -934,6 +1017,7 @@ impl IndexScheduler {
                     let fields_ids_map = index.fields_ids_map(&rtxn)?;
                     let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
                     let embedding_configs = index.embedding_configs(&rtxn)?;
+                    let pii_attributes = index.pii_attributes(&rtxn)?;
 
                     // 3.1. Dump the documents
                     for ret in index.all_documents(&rtxn)? {
@@ -944,6 +1028,9 @@ impl IndexScheduler {
                         let (id, doc) = ret?;
 
                         let mut document = milli::obkv_to_json(&all_fields, &fields_ids_map, doc)?;
+                        // the personal information never leaves the index
+                        milli::pii::remove_pii_attributes(&mut document, &pii_attributes);
+                        anonymize(&mut document);
 
                         'inject_vectors: {
                             let embeddings = index.embeddings(&rtxn, id)?;
@@ -998,11 +1085,16 @@ impl IndexScheduler {
                     }
 
                     // 3.2. Dump the settings
-                    let settings = meilisearch_types::settings::settings(
+                    let mut settings = meilisearch_types::settings::settings(
                         index,
                         &rtxn,
                         meilisearch_types::settings::SecretPolicy::RevealSecrets,
                     )?;
+                    // the module of the document hook is dumped as is rather than base64 encoded
+                    if let Some(hook) = index.document_hook(&rtxn)? {
+                        index_dumper.document_hook_module(&hook.module)?;
+                    }
+                    settings.hide_document_hook_module();
                     index_dumper.settings(&settings)?;
                     Ok(())
                 })?;
@@ -1027,6 +1119,43 @@ impl IndexScheduler {
                 task.details = Some(Details::Dump { dump_uid: Some(dump_uid) });
                 Ok(vec![task])
             }
+            Batch::SnapshotComparison(mut task) => {
+                let KindWithContent::SnapshotComparison { index_uid, snapshot, other_snapshot } =
+                    &task.kind
+                else {
+                    unreachable!();
+                };
+
+                let old = SnapshotIndex::open(&self.snapshots_path, snapshot, index_uid)?;
+                // without a second snapshot, the snapshot is compared with the current index
+                let report = match other_snapshot {
+                    Some(other_snapshot) => {
+                        let new =
+                            SnapshotIndex::open(&self.snapshots_path, other_snapshot, index_uid)?;
+                        let report =
+                            compare_indexes(old.index(), new.index(), &self.must_stop_processing);
+                        new.close();
+                        report
+                    }
+                    None => {
+                        let rtxn = self.env.read_txn()?;
+                        let new = self.index_mapper.index(&rtxn, index_uid)?;
+                        drop(rtxn);
+                        compare_indexes(old.index(), &new, &self.must_stop_processing)
+                    }
+                };
+                old.close();
+                let report = report?;
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::SnapshotComparison {
+                    index_uid: index_uid.clone(),
+                    snapshot: snapshot.clone(),
+                    other_snapshot: other_snapshot.clone(),
+                    report: Some(report),
+                });
+                Ok(vec![task])
+            }
             Batch::IndexOperation { op, must_create_index } => {
                 let index_uid = op.index_uid().to_string();
                 let index = if must_create_index {
@@ -1127,6 +1256,29 @@ impl IndexScheduler {
 
                 Ok(vec![task])
             }
+            Batch::IndexRebuild { index_uid, databases, mut task } => {
+                let rtxn = self.env.read_txn()?;
+                let index = self.index_mapper.index(&rtxn, &index_uid)?;
+
+                let mut index_wtxn = index.write_txn()?;
+                let must_stop_processing = self.must_stop_processing.clone();
+                RebuildDatabases::new(
+                    &mut index_wtxn,
+                    &index,
+                    self.index_mapper.indexer_config(),
+                    databases.clone(),
+                )
+                .execute(|| must_stop_processing.get())?;
+                index_wtxn.commit()?;
+
+                // drop rtxn before starting a new wtxn on the same db
+                rtxn.commit()?;
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::IndexRebuild { databases });
+
+                Ok(vec![task])
+            }
             Batch::IndexDeletion { index_uid, index_has_been_created, mut tasks } => {
                 let wtxn = self.env.write_txn()?;
 
@@ -1329,12 +1481,22 @@ impl IndexScheduler {
 
                 for (operation, task) in operations.into_iter().zip(tasks.iter_mut()) {
                     match operation {
-                        DocumentOperation::Add(content_uuid) => {
+                        DocumentOperation::Add(content_uuid, write_mode) => {
                             let content_file = self.file_store.get_update(content_uuid)?;
                             let reader = DocumentsBatchReader::from_reader(content_file)
                                 .map_err(milli::Error::from)?;
+                            let outdated_documents = builder.outdated_documents_count();
+                            let rejected_documents = builder.rejected_documents_count();
+                            let rejected_document_ids = builder.rejected_documents().len();
+                            builder = builder.with_write_mode(write_mode);
                             let (new_builder, user_result) = builder.add_documents(reader)?;
                             builder = new_builder;
+                            let skipped_documents =
+                                builder.outdated_documents_count() - outdated_documents;
+                            let rejected_documents =
+                                builder.rejected_documents_count() - rejected_documents;
+                            let rejected_document_ids =
+                                builder.rejected_documents()[rejected_document_ids..].to_vec();
 
                             builder = builder.with_embedders(embedders.clone());
 
@@ -1356,6 +1518,9 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(count),
+                                        skipped_documents,
+                                        rejected_documents,
+                                        rejected_document_ids,
                                     })
                                 }
                                 Err(e) => {
@@ -1363,6 +1528,9 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(0),
+                                        skipped_documents: 0,
+                                        rejected_documents: 0,
+                                        rejected_document_ids: Vec::new(),
                                     });
                                     task.error = Some(milli::Error::from(e).into());
                                 }
@@ -1521,7 +1689,7 @@ impl IndexScheduler {
 
                 for (task, (_, settings)) in tasks.iter_mut().zip(settings) {
                     let checked_settings = settings.clone().check();
-                    task.details = Some(Details::SettingsUpdate { settings: Box::new(settings) });
+                    task.details = Some(Details::settings_update(settings));
                     apply_settings_to_builder(&checked_settings, &mut builder);
 
                     // We can apply the status right now and if an update fail later
@@ -1604,12 +1772,13 @@ impl IndexScheduler {
 
     /// Delete each given task from all the databases (if it is deleteable).
     ///
-    /// Return the number of tasks that were actually deleted.
+    /// Return the tasks that were actually deleted and the retained content files
+    /// that the transaction owner must delete if the commit is successful.
     fn delete_matched_tasks(
         &self,
         wtxn: &mut RwTxn,
         matched_tasks: &RoaringBitmap,
-    ) -> Result<RoaringBitmap> {
+    ) -> Result<(RoaringBitmap, Vec<Uuid>)> {
         // 1. Remove from this list the tasks that we are not allowed to delete
         let enqueued_tasks = self.get_status(wtxn, Status::Enqueued)?;
         let processing_tasks = &self.processing_tasks.read().unwrap().processing.clone();
@@ -1625,6 +1794,7 @@ impl IndexScheduler {
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
+        let mut to_delete_payloads = Vec::new();
 
         for task_id in to_delete_tasks.iter() {
             let task = self.get_task(wtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
@@ -1635,7 +1805,10 @@ impl IndexScheduler {
             // Note: don't delete the persisted task data since
             // we can only delete succeeded, failed, and canceled tasks.
             // In each of those cases, the persisted data is supposed to
-            // have been deleted already.
+            // have been deleted already, unless it is retained to replay the task.
+            if self.must_retain_task_payload(&task) {
+                to_delete_payloads.extend(task.content_uuid());
+            }
             utils::remove_task_datetime(wtxn, self.enqueued_at, task.enqueued_at, task.uid)?;
             if let Some(started_at) = task.started_at {
                 utils::remove_task_datetime(wtxn, self.started_at, started_at, task.uid)?;
@@ -1674,7 +1847,7 @@ impl IndexScheduler {
             }
         }
 
-        Ok(to_delete_tasks)
+        Ok((to_delete_tasks, to_delete_payloads))
     }
 
     /// Cancel each given task from all the databases (if it is cancelable).
@@ -44,6 +44,10 @@ pub struct IndexMap {
     /// closing request was made, so the reader that "lost the race" has the old generation and will need to wait again for the index
     /// to close.
     generation: usize,
+
+    /// Called with the indexes that are about to be closed or deleted, so that the handles to
+    /// them kept outside of the map are dropped and don't delay the closing.
+    closing_hook: Option<Box<dyn Fn(&Index) + Send + Sync>>,
 }
 
 #[derive(Clone)]
@@ -133,7 +137,23 @@ impl ReopenableIndex {
 
 impl IndexMap {
     pub fn new(cap: usize) -> IndexMap {
-        Self { unavailable: Default::default(), available: LruMap::new(cap), generation: 0 }
+        Self {
+            unavailable: Default::default(),
+            available: LruMap::new(cap),
+            generation: 0,
+            closing_hook: None,
+        }
+    }
+
+    /// Sets the function called with the indexes that are about to be closed or deleted.
+    pub fn set_closing_hook(&mut self, hook: Box<dyn Fn(&Index) + Send + Sync>) {
+        self.closing_hook = Some(hook);
+    }
+
+    fn run_closing_hook(&self, index: &Index) {
+        if let Some(hook) = &self.closing_hook {
+            hook(index);
+        }
     }
 
     /// Gets the current status of an index in the map.
@@ -233,6 +253,7 @@ impl IndexMap {
         map_size_growth: usize,
     ) {
         let map_size = index.map_size() + map_size_growth;
+        self.run_closing_hook(&index);
         let closing_event = index.prepare_for_closing();
         let generation = self.next_generation();
         self.unavailable.insert(
@@ -259,6 +280,7 @@ impl IndexMap {
     ) -> std::result::Result<Option<EnvClosingEvent>, Option<ClosingIndex>> {
         if let Some(index) = self.available.remove(uuid) {
             self.unavailable.insert(*uuid, None);
+            self.run_closing_hook(&index);
             return Ok(Some(index.prepare_for_closing()));
         }
         match self.unavailable.remove(uuid) {
@@ -19,7 +19,7 @@ use crate::{Error, Result};
 
 mod index_map;
 
-const INDEX_MAPPING: &str = "index-mapping";
+pub(crate) const INDEX_MAPPING: &str = "index-mapping";
 const INDEX_STATS: &str = "index-stats";
 
 /// Structure managing meilisearch's indexes.
@@ -488,6 +488,12 @@ impl IndexMapper {
         &self.indexer_config
     }
 
+    /// Sets the function called with the indexes that are about to be closed, because they are
+    /// resized, evicted from the cache, or deleted.
+    pub fn set_index_closing_hook(&self, hook: impl Fn(&Index) + Send + Sync + 'static) {
+        self.index_map.write().unwrap().set_closing_hook(Box::new(hook));
+    }
+
     pub fn set_currently_updating_index(&self, index: Option<(String, Index)>) {
         *self.currently_updating_index.write().unwrap() = index;
     }
@@ -174,8 +174,24 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentAdditionOrUpdate {
             received_documents,
             indexed_documents,
+            skipped_documents,
+            rejected_documents,
+            rejected_document_ids,
         } => {
-            format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
+            let mut details = format!(
+                "{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?}"
+            );
+            if *skipped_documents != 0 {
+                details.push_str(&format!(", skipped_documents: {skipped_documents}"));
+            }
+            if *rejected_documents != 0 {
+                details.push_str(&format!(", rejected_documents: {rejected_documents}"));
+            }
+            if !rejected_document_ids.is_empty() {
+                details.push_str(&format!(", rejected_document_ids: {rejected_document_ids:?}"));
+            }
+            details.push_str(" }");
+            details
         }
         Details::DocumentEdition {
             deleted_documents,
@@ -221,9 +237,15 @@ fn snapshot_details(d: &Details) -> String {
         Details::Dump { dump_uid } => {
             format!("{{ dump_uid: {dump_uid:?} }}")
         },
+        Details::IndexRebuild { databases } => {
+            format!("{{ databases: {databases:?} }}")
+        }
         Details::IndexSwap { swaps } => {
             format!("{{ swaps: {swaps:?} }}")
         }
+        Details::SnapshotComparison { index_uid, snapshot, other_snapshot, report } => {
+            format!("{{ index_uid: {index_uid:?}, snapshot: {snapshot:?}, other_snapshot: {other_snapshot:?}, report: {report:?} }}")
+        }
     }
 }
 
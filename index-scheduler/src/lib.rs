@@ -26,6 +26,8 @@ mod index_mapper;
 #[cfg(test)]
 mod insta_snapshot;
 mod lru;
+mod queue_stats;
+mod snapshot_comparison;
 mod utils;
 pub mod uuid_codec;
 
@@ -37,7 +39,7 @@ use std::io::{self, BufReader, Read};
 use std::ops::{Bound, RangeBounds};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering::{self, Relaxed};
-use std::sync::atomic::{AtomicBool, AtomicU32};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
@@ -59,6 +61,7 @@ use meilisearch_types::milli::vector::{Embedder, EmbedderOptions, EmbeddingConfi
 use meilisearch_types::milli::{self, CboRoaringBitmapCodec, Index, RoaringBitmapCodec, BEU32};
 use meilisearch_types::task_view::TaskView;
 use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+pub use queue_stats::{ProcessingDurations, TaskQueueStats};
 use rayon::current_num_threads;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use roaring::RoaringBitmap;
@@ -274,6 +277,9 @@ pub struct IndexSchedulerOptions {
     /// If the autobatcher is allowed to automatically batch tasks
     /// it will only batch this defined number of tasks at once.
     pub max_number_of_batched_tasks: usize,
+    /// How long the update files of the succeeded document additions are kept
+    /// after being processed so that the tasks can be replayed, if at all.
+    pub task_payload_retention: Option<Duration>,
     /// The experimental features enabled for this instance.
     pub instance_features: InstanceTogglableFeatures,
 }
@@ -338,6 +344,14 @@ pub struct IndexScheduler {
     /// The maximum number of tasks that will be batched together.
     pub(crate) max_number_of_batched_tasks: usize,
 
+    /// How long the update files of the succeeded document additions are kept after processing.
+    pub(crate) task_payload_retention: Option<Duration>,
+    /// The finish date up to which the expired update files have already been deleted.
+    pub(crate) task_payloads_cleaned_until: Arc<RwLock<Option<OffsetDateTime>>>,
+
+    /// The number of bytes of document payloads indexed since the launch.
+    pub(crate) indexed_payload_bytes: Arc<AtomicU64>,
+
     /// The webhook url we should send tasks to after processing every batches.
     pub(crate) webhook_url: Option<String>,
     /// The Authorization header to send to the webhook URL.
@@ -397,6 +411,9 @@ impl IndexScheduler {
             cleanup_enabled: self.cleanup_enabled,
             max_number_of_tasks: self.max_number_of_tasks,
             max_number_of_batched_tasks: self.max_number_of_batched_tasks,
+            task_payload_retention: self.task_payload_retention,
+            task_payloads_cleaned_until: self.task_payloads_cleaned_until.clone(),
+            indexed_payload_bytes: self.indexed_payload_bytes.clone(),
             snapshots_path: self.snapshots_path.clone(),
             dumps_path: self.dumps_path.clone(),
             auth_path: self.auth_path.clone(),
@@ -499,6 +516,9 @@ impl IndexScheduler {
             cleanup_enabled: options.cleanup_enabled,
             max_number_of_tasks: options.max_number_of_tasks,
             max_number_of_batched_tasks: options.max_number_of_batched_tasks,
+            task_payload_retention: options.task_payload_retention,
+            task_payloads_cleaned_until: Default::default(),
+            indexed_payload_bytes: Default::default(),
             dumps_path: options.dumps_path,
             snapshots_path: options.snapshots_path,
             auth_path: options.auth_path,
@@ -635,6 +655,14 @@ impl IndexScheduler {
         &self.index_mapper.indexer_config
     }
 
+    /// Sets the function called with the indexes that are about to be closed, because they are
+    /// resized, evicted from the cache, or deleted.
+    ///
+    /// The index can't be closed while a handle to it is kept, so the hook must drop them.
+    pub fn set_index_closing_hook(&self, hook: impl Fn(&Index) + Send + Sync + 'static) {
+        self.index_mapper.set_index_closing_hook(hook)
+    }
+
     /// Return the real database size (i.e.: The size **with** the free pages)
     pub fn size(&self) -> Result<u64> {
         Ok(self.env.real_disk_size()?)
@@ -1133,6 +1161,10 @@ impl IndexScheduler {
             self.cleanup_task_queue()?;
         }
 
+        if self.task_payload_retention.is_some() {
+            self.delete_expired_task_payloads()?;
+        }
+
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
         let batch =
             match self.create_next_batch(&rtxn).map_err(|e| Error::CreateBatch(Box::new(e)))? {
@@ -1195,7 +1227,16 @@ impl IndexScheduler {
 
                     match task.error {
                         Some(_) => failure += 1,
-                        None => success += 1,
+                        None => {
+                            success += 1;
+                            // the update files are only deleted once the tasks are committed
+                            if let Some(size) = task
+                                .content_uuid()
+                                .and_then(|uuid| self.file_store.compute_size(uuid).ok())
+                            {
+                                self.indexed_payload_bytes.fetch_add(size, Relaxed);
+                            }
+                        }
                     }
 
                     self.update_task(&mut wtxn, &task)
@@ -1284,6 +1325,9 @@ impl IndexScheduler {
                     .get_task(&rtxn, id)
                     .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?
                     .ok_or(Error::CorruptedTaskQueue)?;
+                if self.must_retain_task_payload(&task) {
+                    continue;
+                }
                 if let Err(e) = self.delete_persisted_task_data(&task) {
                     tracing::error!(
                         "Failure to delete the content files associated with task {}. Error: {e}",
@@ -1461,6 +1505,98 @@ impl IndexScheduler {
         }
     }
 
+    /// Whether the update file of the given processed task must be kept so that it can be replayed.
+    pub(crate) fn must_retain_task_payload(&self, task: &Task) -> bool {
+        self.task_payload_retention.is_some()
+            && task.status == Status::Succeeded
+            && matches!(task.kind, KindWithContent::DocumentAdditionOrUpdate { .. })
+    }
+
+    /// Deletes the retained update files of the tasks that finished longer ago than the retention.
+    fn delete_expired_task_payloads(&self) -> Result<()> {
+        let Some(retention) = self.task_payload_retention else { return Ok(()) };
+        let expired_before = OffsetDateTime::now_utc() - retention;
+        let mut cleaned_until = self.task_payloads_cleaned_until.write().unwrap();
+
+        let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
+        let mut expired = self.get_status(&rtxn, Status::Succeeded)?
+            & self.get_kind(&rtxn, Kind::DocumentAdditionOrUpdate)?;
+        keep_tasks_within_datetimes(
+            &rtxn,
+            &mut expired,
+            self.finished_at,
+            *cleaned_until,
+            Some(expired_before),
+        )?;
+
+        for task in self.get_existing_tasks(&rtxn, expired)? {
+            let Some(content_file) = task.content_uuid() else { continue };
+            // the update file may have been deleted by a previous run of the scheduler
+            if self.file_store.get_update_path(content_file).exists() {
+                if let Err(e) = self.delete_update_file(content_file) {
+                    tracing::error!(
+                        "Failure to delete the retained content file of task {}. Error: {e}",
+                        task.uid
+                    );
+                }
+            }
+        }
+
+        *cleaned_until = Some(expired_before);
+        Ok(())
+    }
+
+    /// Registers a new task indexing the retained payload of the given succeeded document addition,
+    /// e.g. to reprocess it against a rebuilt index.
+    ///
+    /// The payload is copied so that both tasks keep their own update file.
+    pub fn replay_task(
+        &self,
+        task: &Task,
+        allow_index_creation: bool,
+        task_id: Option<TaskId>,
+        dry_run: bool,
+    ) -> Result<Task> {
+        let KindWithContent::DocumentAdditionOrUpdate {
+            index_uid,
+            primary_key,
+            method,
+            content_file,
+            documents_count,
+            allow_index_creation: _,
+            write_mode,
+        } = &task.kind
+        else {
+            return Err(Error::TaskNotReplayable(task.uid));
+        };
+        if task.status != Status::Succeeded
+            || !self.file_store.get_update_path(*content_file).exists()
+        {
+            return Err(Error::TaskNotReplayable(task.uid));
+        }
+
+        let (uuid, mut update_file) = self.create_update_file(dry_run)?;
+        let mut payload = self.file_store.get_update(*content_file)?;
+        io::copy(&mut payload, &mut update_file)?;
+        update_file.persist()?;
+
+        let kind = KindWithContent::DocumentAdditionOrUpdate {
+            index_uid: index_uid.clone(),
+            primary_key: primary_key.clone(),
+            method: *method,
+            content_file: uuid,
+            documents_count: *documents_count,
+            allow_index_creation,
+            write_mode: *write_mode,
+        };
+        self.register(kind, task_id, dry_run).map_err(|e| {
+            if !dry_run {
+                let _ = self.delete_update_file(uuid);
+            }
+            e
+        })
+    }
+
     // TODO: consider using a type alias or a struct embedder/template
     pub fn embedders(
         &self,
@@ -1589,6 +1725,7 @@ impl<'a> Dump<'a> {
                     method,
                     documents_count,
                     allow_index_creation,
+                    write_mode,
                 } => KindWithContent::DocumentAdditionOrUpdate {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     primary_key,
@@ -1596,6 +1733,7 @@ impl<'a> Dump<'a> {
                     content_file: content_uuid.ok_or(Error::CorruptedDump)?,
                     documents_count,
                     allow_index_creation,
+                    write_mode,
                 },
                 KindDump::DocumentDeletion { documents_ids } => KindWithContent::DocumentDeletion {
                     documents_ids,
@@ -1637,6 +1775,10 @@ impl<'a> Dump<'a> {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     primary_key,
                 },
+                KindDump::IndexRebuild { databases } => KindWithContent::IndexRebuild {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    databases,
+                },
                 KindDump::IndexSwap { swaps } => KindWithContent::IndexSwap { swaps },
                 KindDump::TaskCancelation { query, tasks } => {
                     KindWithContent::TaskCancelation { query, tasks }
@@ -1644,10 +1786,13 @@ impl<'a> Dump<'a> {
                 KindDump::TasksDeletion { query, tasks } => {
                     KindWithContent::TaskDeletion { query, tasks }
                 }
-                KindDump::DumpCreation { keys, instance_uid } => {
-                    KindWithContent::DumpCreation { keys, instance_uid }
+                KindDump::DumpCreation { keys, instance_uid, anonymization } => {
+                    KindWithContent::DumpCreation { keys, instance_uid, anonymization }
                 }
                 KindDump::SnapshotCreation => KindWithContent::SnapshotCreation,
+                KindDump::SnapshotComparison { index_uid, snapshot, other_snapshot } => {
+                    KindWithContent::SnapshotComparison { index_uid, snapshot, other_snapshot }
+                }
             },
         };
 
@@ -1767,7 +1912,7 @@ mod tests {
     use meilisearch_types::milli::update::IndexDocumentsMethod::{
         ReplaceDocuments, UpdateDocuments,
     };
-    use meilisearch_types::milli::update::Setting;
+    use meilisearch_types::milli::update::{DocumentsWriteMode, Setting};
     use meilisearch_types::milli::vector::settings::EmbeddingSettings;
     use meilisearch_types::settings::Unchecked;
     use meilisearch_types::tasks::IndexSwap;
@@ -1830,6 +1975,7 @@ mod tests {
                 cleanup_enabled: true,
                 max_number_of_tasks: 1_000_000,
                 max_number_of_batched_tasks: usize::MAX,
+                task_payload_retention: None,
                 instance_features: Default::default(),
             };
             configuration(&mut options);
@@ -1898,6 +2044,7 @@ mod tests {
             content_file: Uuid::from_u128(content_file_uuid),
             documents_count,
             allow_index_creation: true,
+            write_mode: DocumentsWriteMode::Upsert,
         }
     }
 
@@ -1910,7 +2057,7 @@ mod tests {
         let mut buffer = BufWriter::new(temp_file.reopen().unwrap());
         buffer.write_all(bytes).unwrap();
         buffer.flush().unwrap();
-        meilisearch_types::document_formats::read_json(temp_file.as_file(), write)
+        meilisearch_types::document_formats::read_json(temp_file.as_file(), write, None)
     }
 
     /// Create an update file with the given file uuid.
@@ -2369,6 +2516,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -2414,6 +2562,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -2454,6 +2603,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -2520,6 +2670,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -2726,6 +2877,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -2900,6 +3052,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: true,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -2951,6 +3104,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: true,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -3004,6 +3158,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: true,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -3096,6 +3251,67 @@ mod tests {
         insta::assert_json_snapshot!(config.embedder_options);
     }
 
+    #[test]
+    fn test_index_batch_limits() {
+        use meilisearch_types::settings::{BatchingSettings, Settings, Unchecked};
+        use milli::update::Setting;
+
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let mut new_settings: Box<Settings<Unchecked>> = Box::default();
+        new_settings.batching = Setting::Set(BatchingSettings {
+            max_batch_size: Setting::Set(2),
+            max_batch_payload: Setting::NotSet,
+        });
+        index_scheduler
+            .register(
+                KindWithContent::SettingsUpdate {
+                    index_uid: S("doggos"),
+                    new_settings,
+                    is_deletion: false,
+                    allow_index_creation: true,
+                },
+                None,
+                false,
+            )
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        for i in 0..5 {
+            let (file, documents_count) = sample_documents(&index_scheduler, i, i as usize);
+            file.persist().unwrap();
+            index_scheduler
+                .register(
+                    replace_document_import_task("doggos", Some("id"), i, documents_count),
+                    None,
+                    false,
+                )
+                .unwrap();
+        }
+
+        let statuses = |index_scheduler: &IndexScheduler| {
+            let rtxn = index_scheduler.read_txn().unwrap();
+            (1..=5)
+                .map(|task_id| index_scheduler.get_task(&rtxn, task_id).unwrap().unwrap().status)
+                .collect::<Vec<_>>()
+        };
+
+        // the document additions are processed two by two
+        handle.advance_one_successful_batch();
+        assert_eq!(
+            statuses(&index_scheduler),
+            [
+                Status::Succeeded,
+                Status::Succeeded,
+                Status::Enqueued,
+                Status::Enqueued,
+                Status::Enqueued
+            ]
+        );
+        handle.advance_n_successful_batches(2);
+        assert_eq!(statuses(&index_scheduler), [Status::Succeeded; 5]);
+    }
+
     #[test]
     fn test_document_replace_without_autobatching() {
         let (index_scheduler, mut handle) = IndexScheduler::test(false, vec![]);
@@ -3121,6 +3337,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: true,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -3176,6 +3393,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: true,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -3678,6 +3896,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -3720,6 +3939,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -3780,6 +4000,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: false,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -3832,6 +4053,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: false,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -3894,6 +4116,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: false,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -3961,6 +4184,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: false,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -4033,6 +4257,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -4090,6 +4315,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -4146,6 +4372,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: true,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -4211,6 +4438,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: true,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -4272,6 +4500,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: true,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -4357,6 +4586,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: true,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -4444,6 +4674,7 @@ mod tests {
                         content_file: uuid,
                         documents_count,
                         allow_index_creation: true,
+                        write_mode: DocumentsWriteMode::Upsert,
                     },
                     None,
                     false,
@@ -4587,6 +4818,84 @@ mod tests {
         handle.advance_one_failed_batch();
     }
 
+    #[test]
+    fn replay_retained_document_addition() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.task_payload_retention = Some(std::time::Duration::from_secs(60 * 60));
+            });
+
+        let content = r#"
+        {
+            "id": 1,
+            "doggo": "bob"
+        }"#;
+
+        let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
+        let documents_count = read_json(content.as_bytes(), &mut file).unwrap();
+        file.persist().unwrap();
+        index_scheduler
+            .register(
+                KindWithContent::DocumentAdditionOrUpdate {
+                    index_uid: S("doggos"),
+                    primary_key: Some(S("id")),
+                    method: ReplaceDocuments,
+                    content_file: uuid,
+                    documents_count,
+                    allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
+                },
+                None,
+                false,
+            )
+            .unwrap();
+        handle.advance_one_successful_batch();
+        // the payload of the succeeded addition is retained
+        assert!(index_scheduler.file_store.get_update_path(uuid).exists());
+
+        index_scheduler
+            .register(KindWithContent::IndexDeletion { index_uid: S("doggos") }, None, false)
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let rtxn = index_scheduler.read_txn().unwrap();
+        let addition = index_scheduler.get_task(&rtxn, 0).unwrap().unwrap();
+        let deletion = index_scheduler.get_task(&rtxn, 1).unwrap().unwrap();
+        drop(rtxn);
+
+        let err = index_scheduler.replay_task(&deletion, true, None, false).unwrap_err();
+        snapshot!(err, @"Task `1` cannot be replayed. Only the succeeded document additions can be replayed, as long as their payload is retained.");
+
+        // the addition is replayed against a new index
+        let replayed = index_scheduler.replay_task(&addition, true, None, false).unwrap();
+        assert_ne!(replayed.content_uuid(), Some(uuid));
+        handle.advance_one_successful_batch();
+        index_scheduler.assert_internally_consistent();
+
+        let index = index_scheduler.index("doggos").unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 1);
+        drop(rtxn);
+
+        // deleting the task deletes its retained payload
+        index_scheduler
+            .register(
+                KindWithContent::TaskDeletion {
+                    query: S("test"),
+                    tasks: RoaringBitmap::from_iter([0]),
+                },
+                None,
+                false,
+            )
+            .unwrap();
+        handle.advance_one_successful_batch();
+        assert!(!index_scheduler.file_store.get_update_path(uuid).exists());
+        assert!(index_scheduler
+            .file_store
+            .get_update_path(replayed.content_uuid().unwrap())
+            .exists());
+    }
+
     #[test]
     fn test_auto_deletion_of_tasks() {
         let (index_scheduler, mut handle) =
@@ -4886,7 +5195,11 @@ mod tests {
     fn cancel_processing_dump() {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
 
-        let dump_creation = KindWithContent::DumpCreation { keys: Vec::new(), instance_uid: None };
+        let dump_creation = KindWithContent::DumpCreation {
+            keys: Vec::new(),
+            instance_uid: None,
+            anonymization: None,
+        };
         let dump_cancellation = KindWithContent::TaskCancelation {
             query: "cancel dump".to_owned(),
             tasks: RoaringBitmap::from_iter([0]),
@@ -5120,6 +5433,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -5194,6 +5508,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -5316,6 +5631,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -5463,6 +5779,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: true,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -5565,6 +5882,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -5732,6 +6050,7 @@ mod tests {
                     content_file: uuid,
                     documents_count,
                     allow_index_creation: false,
+                    write_mode: DocumentsWriteMode::Upsert,
                 },
                 None,
                 false,
@@ -0,0 +1,128 @@
+//! Statistics on how fast the task queue processes the tasks.
+//!
+//! Except for the number of indexed bytes, everything is computed from the tasks stored in the
+//! task queue, so that the statistics survive a restart.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::Ordering;
+
+use meilisearch_types::tasks::Status;
+use serde::Serialize;
+use time::{Duration, OffsetDateTime};
+
+use crate::{IndexScheduler, Query, Result};
+
+/// The window over which the statistics of the finished tasks are computed.
+const STATS_WINDOW: Duration = Duration::HOUR;
+
+/// The statistics of the task queue, computed over the tasks finished during the last hour.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskQueueStats {
+    /// How long the oldest enqueued task has been waiting to be processed, in seconds.
+    pub current_lag_seconds: f64,
+    /// How long the finished tasks waited between being enqueued and being processed, in seconds.
+    pub average_lag_seconds: f64,
+    /// The number of batches processed.
+    pub batches_per_hour: u64,
+    /// The processing durations of the finished tasks, by type of task.
+    pub processing_durations: BTreeMap<String, ProcessingDurations>,
+    /// The number of bytes of document payloads indexed since the launch of the instance,
+    /// for all the indexes.
+    pub indexed_payload_bytes: u64,
+}
+
+/// The percentiles of the processing durations of a type of task.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingDurations {
+    pub count: u64,
+    pub p50_seconds: f64,
+    pub p95_seconds: f64,
+}
+
+impl IndexScheduler {
+    /// Returns the statistics of the tasks the filters give access to.
+    pub fn get_task_queue_stats(
+        &self,
+        filters: &meilisearch_auth::AuthFilter,
+    ) -> Result<TaskQueueStats> {
+        let rtxn = self.read_txn()?;
+        let now = OffsetDateTime::now_utc();
+
+        let query = Query { statuses: Some(vec![Status::Enqueued]), ..Query::default() };
+        let (mut enqueued, _) =
+            self.get_task_ids_from_authorized_indexes(&rtxn, &query, filters)?;
+        enqueued -= &self.processing_tasks.read().unwrap().processing;
+        let current_lag = match enqueued.min() {
+            Some(oldest) => self.get_task(&rtxn, oldest)?.map(|task| now - task.enqueued_at),
+            None => None,
+        };
+
+        let query = Query {
+            statuses: Some(vec![Status::Succeeded, Status::Failed, Status::Canceled]),
+            after_finished_at: Some(now - STATS_WINDOW),
+            ..Query::default()
+        };
+        let (finished, _) = self.get_task_ids_from_authorized_indexes(&rtxn, &query, filters)?;
+
+        let mut lags = Vec::new();
+        // the tasks of a batch all start at the same date
+        let mut batches = BTreeSet::new();
+        let mut durations = BTreeMap::<String, Vec<f64>>::new();
+        for task in self.get_existing_tasks(&rtxn, finished)? {
+            // the tasks canceled before being processed never started
+            let (Some(started_at), Some(finished_at)) = (task.started_at, task.finished_at) else {
+                continue;
+            };
+            lags.push((started_at - task.enqueued_at).as_seconds_f64());
+            batches.insert(started_at);
+            durations
+                .entry(task.kind.as_kind().to_string())
+                .or_default()
+                .push((finished_at - started_at).as_seconds_f64());
+        }
+
+        Ok(TaskQueueStats {
+            current_lag_seconds: current_lag.map_or(0.0, |lag| lag.as_seconds_f64().max(0.0)),
+            average_lag_seconds: if lags.is_empty() {
+                0.0
+            } else {
+                lags.iter().sum::<f64>() / lags.len() as f64
+            },
+            batches_per_hour: batches.len() as u64,
+            processing_durations: durations
+                .into_iter()
+                .map(|(kind, mut durations)| {
+                    durations.sort_by(f64::total_cmp);
+                    let durations = ProcessingDurations {
+                        count: durations.len() as u64,
+                        p50_seconds: percentile(&durations, 0.50),
+                        p95_seconds: percentile(&durations, 0.95),
+                    };
+                    (kind, durations)
+                })
+                .collect(),
+            indexed_payload_bytes: self.indexed_payload_bytes.load(Ordering::Relaxed),
+        })
+    }
+}
+
+/// Returns the nearest-rank percentile of sorted values, which must not be empty.
+fn percentile(sorted: &[f64], percentile: f64) -> f64 {
+    let rank = (percentile * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let durations: Vec<_> = (1..=20).map(f64::from).collect();
+        assert_eq!(percentile(&durations, 0.50), 10.0);
+        assert_eq!(percentile(&durations, 0.95), 19.0);
+        assert_eq!(percentile(&[3.0], 0.95), 3.0);
+    }
+}
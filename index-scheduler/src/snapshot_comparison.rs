@@ -0,0 +1,169 @@
+//! Comparison of an index with its version in a snapshot, to validate a migration or the
+//! correctness of a replication.
+//!
+//! The snapshots are looked up by their file name in the snapshots directory and are extracted
+//! in a temporary directory for the duration of the comparison.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use meilisearch_types::compression;
+use meilisearch_types::heed::types::Str;
+use meilisearch_types::heed::{Database, EnvOpenOptions, RoTxn};
+use meilisearch_types::milli::{self, Index};
+use meilisearch_types::settings::{settings, SecretPolicy};
+use meilisearch_types::tasks::ComparisonReport;
+use tempfile::TempDir;
+
+use crate::index_mapper::INDEX_MAPPING;
+use crate::uuid_codec::UuidCodec;
+use crate::{Error, MustStopProcessing, Result};
+
+/// The map size used to open the environments of a snapshot, they are grown to the size
+/// of their data anyway.
+const SNAPSHOT_MAP_SIZE: usize = 1024 * 1024 * 1024; // 1 GiB
+
+/// An index opened from an extracted snapshot.
+pub(crate) struct SnapshotIndex {
+    index: Index,
+    dir: TempDir,
+}
+
+impl SnapshotIndex {
+    /// Extracts the snapshot with the given file name and opens the given index from it.
+    pub fn open(snapshots_path: &Path, snapshot: &str, index_uid: &str) -> Result<Self> {
+        // only the files of the snapshots directory can be compared
+        let is_file_name = Path::new(snapshot).file_name().map_or(false, |name| name == snapshot);
+        let path = snapshots_path.join(snapshot);
+        if !is_file_name || !path.is_file() {
+            return Err(Error::SnapshotNotFound(snapshot.to_string()));
+        }
+
+        let dir = tempfile::tempdir()?;
+        compression::from_tar_gz(&path, dir.path())?;
+
+        let env = unsafe {
+            EnvOpenOptions::new()
+                .max_dbs(11)
+                .map_size(SNAPSHOT_MAP_SIZE)
+                .open(dir.path().join("tasks"))
+        }?;
+        let rtxn = env.read_txn()?;
+        let index_mapping: Option<Database<Str, UuidCodec>> =
+            env.open_database(&rtxn, Some(INDEX_MAPPING))?;
+        let uuid = match index_mapping {
+            Some(index_mapping) => index_mapping.get(&rtxn, index_uid)?,
+            None => None,
+        };
+        drop(rtxn);
+        env.prepare_for_closing().wait();
+
+        let Some(uuid) = uuid else {
+            return Err(Error::IndexNotFoundInSnapshot {
+                index_uid: index_uid.to_string(),
+                snapshot: snapshot.to_string(),
+            });
+        };
+
+        let mut options = EnvOpenOptions::new();
+        options.map_size(SNAPSHOT_MAP_SIZE);
+        let index = Index::new(options, dir.path().join("indexes").join(uuid.to_string()))?;
+        Ok(SnapshotIndex { index, dir })
+    }
+
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Closes the index before removing the extracted snapshot.
+    pub fn close(self) {
+        let SnapshotIndex { index, dir } = self;
+        index.prepare_for_closing().wait();
+        drop(dir);
+    }
+}
+
+/// Compares the documents and the settings of two versions of an index.
+///
+/// The documents are matched by their external id and compared by their JSON content,
+/// so that the internal ids and the order of the fields don't matter.
+pub(crate) fn compare_indexes(
+    old: &Index,
+    new: &Index,
+    must_stop_processing: &MustStopProcessing,
+) -> Result<ComparisonReport> {
+    let old_rtxn = old.read_txn()?;
+    let new_rtxn = new.read_txn()?;
+    let mut report = ComparisonReport::default();
+
+    let old_fields_ids_map = old.fields_ids_map(&old_rtxn)?;
+    let new_fields_ids_map = new.fields_ids_map(&new_rtxn)?;
+    let old_external_ids = old.external_documents_ids();
+    let new_external_ids = new.external_documents_ids();
+
+    for result in old_external_ids.iter(&old_rtxn)? {
+        if must_stop_processing.get() {
+            return Err(Error::AbortedTask);
+        }
+
+        let (external_id, old_docid) = result?;
+        let Some(new_docid) = new_external_ids.get(&new_rtxn, external_id)? else {
+            report.documents_removed += 1;
+            continue;
+        };
+
+        let old_document = document(old, &old_rtxn, old_docid, &old_fields_ids_map)?;
+        let new_document = document(new, &new_rtxn, new_docid, &new_fields_ids_map)?;
+        if old_document != new_document {
+            report.documents_changed += 1;
+        }
+    }
+
+    for result in new_external_ids.iter(&new_rtxn)? {
+        let (external_id, _) = result?;
+        if old_external_ids.get(&old_rtxn, external_id)?.is_none() {
+            report.documents_added += 1;
+        }
+    }
+
+    report.settings_differences = settings_differences(old, &old_rtxn, new, &new_rtxn)?;
+    Ok(report)
+}
+
+fn document(
+    index: &Index,
+    rtxn: &RoTxn,
+    docid: milli::DocumentId,
+    fields_ids_map: &milli::FieldsIdsMap,
+) -> Result<Option<milli::Object>> {
+    match index.iter_documents(rtxn, Some(docid))?.next().transpose()? {
+        Some((_, obkv)) => Ok(Some(milli::all_obkv_to_json(obkv, fields_ids_map)?)),
+        None => Ok(None),
+    }
+}
+
+/// Returns the names of the settings that are different between the two indexes.
+fn settings_differences(
+    old: &Index,
+    old_rtxn: &RoTxn,
+    new: &Index,
+    new_rtxn: &RoTxn,
+) -> Result<Vec<String>> {
+    let to_object =
+        |index: &Index, rtxn: &RoTxn| -> Result<serde_json::Map<String, serde_json::Value>> {
+            let settings = settings(index, rtxn, SecretPolicy::RevealSecrets)?;
+            match serde_json::to_value(settings) {
+                Ok(serde_json::Value::Object(object)) => Ok(object),
+                _ => Ok(Default::default()),
+            }
+        };
+    let old_settings = to_object(old, old_rtxn)?;
+    let new_settings = to_object(new, new_rtxn)?;
+
+    let names: BTreeSet<_> = old_settings.keys().chain(new_settings.keys()).collect();
+    Ok(names
+        .into_iter()
+        .filter(|name| old_settings.get(*name) != new_settings.get(*name))
+        .cloned()
+        .collect())
+}
@@ -246,6 +246,7 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
         K::IndexCreation { index_uid, .. } => index_uids.push(index_uid),
         K::IndexUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::IndexRebuild { index_uid, .. } => index_uids.push(index_uid),
         K::IndexSwap { swaps } => {
             for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
                 if lhs == swap.0 || lhs == swap.1 {
@@ -259,7 +260,8 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::TaskCancelation { .. }
         | K::TaskDeletion { .. }
         | K::DumpCreation { .. }
-        | K::SnapshotCreation => (),
+        | K::SnapshotCreation
+        | K::SnapshotComparison { .. } => (),
     };
     if let Some(Details::IndexSwap { swaps }) = &mut task.details {
         for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
@@ -398,7 +400,11 @@ impl IndexScheduler {
                             assert_eq!(&sw1, sw2);
                         }
                     }
-                    Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
+                    Details::DocumentAdditionOrUpdate {
+                        received_documents,
+                        indexed_documents,
+                        ..
+                    } => {
                         assert_eq!(kind.as_kind(), Kind::DocumentAdditionOrUpdate);
                         match indexed_documents {
                             Some(indexed_documents) => {
@@ -547,6 +553,29 @@ impl IndexScheduler {
                     Details::Dump { dump_uid: _ } => {
                         assert_eq!(kind.as_kind(), Kind::DumpCreation);
                     }
+                    Details::IndexRebuild { databases } => match &kind {
+                        KindWithContent::IndexRebuild { databases: kind_databases, .. } => {
+                            assert_eq!(&databases, kind_databases);
+                        }
+                        _ => panic!(),
+                    },
+                    Details::SnapshotComparison { index_uid, snapshot, other_snapshot, report } => {
+                        match &kind {
+                            KindWithContent::SnapshotComparison {
+                                index_uid: kind_index_uid,
+                                snapshot: kind_snapshot,
+                                other_snapshot: kind_other_snapshot,
+                            } => {
+                                assert_eq!(&index_uid, kind_index_uid);
+                                assert_eq!(&snapshot, kind_snapshot);
+                                assert_eq!(&other_snapshot, kind_other_snapshot);
+                            }
+                            _ => panic!(),
+                        }
+                        if status != Status::Succeeded {
+                            assert!(report.is_none());
+                        }
+                    }
                 }
             }
 
@@ -565,6 +594,8 @@ impl IndexScheduler {
                             self.file_store.all_uuids().unwrap().collect::<std::result::Result<Vec<_>, file_store::Error>>().unwrap(),
                         );
                     }
+                    // the payload of the succeeded additions is retained to replay them
+                    Status::Succeeded if self.task_payload_retention.is_some() => (),
                     Status::Succeeded | Status::Failed | Status::Canceled => {
                         assert!(self
                             .file_store
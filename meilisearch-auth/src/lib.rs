@@ -9,9 +9,12 @@ use std::sync::Arc;
 use error::{AuthControllerError, Result};
 use maplit::hashset;
 use meilisearch_types::index_uid_pattern::IndexUidPattern;
-use meilisearch_types::keys::{Action, CreateApiKey, Key, PatchApiKey};
+use meilisearch_types::keys::{Action, CreateApiKey, Key, KeySearchParameters, PatchApiKey};
+use meilisearch_types::milli::acl::acl_filter;
 use meilisearch_types::milli::update::Setting;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
 pub use store::open_auth_store_env;
 use store::{generate_key_as_hexa, HeedAuthStore};
 use time::OffsetDateTime;
@@ -91,18 +94,40 @@ impl AuthController {
             .ok_or_else(|| AuthControllerError::ApiKeyNotFound(encoded_key.to_string()))
     }
 
+    /// Returns the filters of a key, along with the search rules and the principals of the
+    /// tenant token generated from it, if any.
     pub fn get_key_filters(
         &self,
         uid: Uuid,
         search_rules: Option<SearchRules>,
+        token_principals: Option<Vec<String>>,
     ) -> Result<AuthFilter> {
         let key = self.get_key(uid)?;
 
         let key_authorized_indexes = SearchRules::Set(key.indexes.into_iter().collect());
 
         let allow_index_creation = self.is_key_authorized(uid, Action::IndexesAdd, None)?;
+        let allow_pii = self.is_key_authorized(uid, Action::DocumentsPii, None)?;
 
-        Ok(AuthFilter { search_rules, key_authorized_indexes, allow_index_creation })
+        // a tenant token can only restrict the principals of its key
+        let principals = match token_principals {
+            Some(principals) if key.principals.is_empty() => Some(principals),
+            Some(principals) => {
+                Some(principals.into_iter().filter(|p| key.principals.contains(p)).collect())
+            }
+            None => (!key.principals.is_empty()).then_some(key.principals),
+        };
+
+        Ok(AuthFilter {
+            key_uid: Some(uid),
+            tenant_token_digest: None,
+            search_rules,
+            key_authorized_indexes,
+            allow_index_creation,
+            allow_pii,
+            search_parameters: key.search_parameters,
+            principals,
+        })
     }
 
     pub fn list_keys(&self) -> Result<Vec<Key>> {
@@ -167,17 +192,30 @@ impl AuthController {
 }
 
 pub struct AuthFilter {
+    /// The uid of the API key used to authenticate, `None` for the master key.
+    key_uid: Option<Uuid>,
+    /// The SHA-256 digest of the tenant token used to authenticate, if any.
+    tenant_token_digest: Option<[u8; 32]>,
     search_rules: Option<SearchRules>,
     key_authorized_indexes: SearchRules,
     allow_index_creation: bool,
+    allow_pii: bool,
+    search_parameters: KeySearchParameters,
+    /// The principals whose documents can be seen, all the documents when `None`.
+    principals: Option<Vec<String>>,
 }
 
 impl Default for AuthFilter {
     fn default() -> Self {
         Self {
+            key_uid: None,
+            tenant_token_digest: None,
             search_rules: None,
             key_authorized_indexes: SearchRules::default(),
             allow_index_creation: true,
+            allow_pii: true,
+            search_parameters: KeySearchParameters::default(),
+            principals: None,
         }
     }
 }
@@ -188,6 +226,35 @@ impl AuthFilter {
         self.allow_index_creation && self.is_index_authorized(index)
     }
 
+    /// Returns true if the key is allowed to see the PII attributes of the documents.
+    #[inline]
+    pub fn allow_pii(&self) -> bool {
+        self.allow_pii
+    }
+
+    /// Returns the uid of the API key used to authenticate, `None` for the master key.
+    #[inline]
+    pub fn key_uid(&self) -> Option<Uuid> {
+        self.key_uid
+    }
+
+    /// Returns the SHA-256 digest of the tenant token used to authenticate, `None` for an API key.
+    #[inline]
+    pub fn tenant_token_digest(&self) -> Option<[u8; 32]> {
+        self.tenant_token_digest
+    }
+
+    /// Records the tenant token used to authenticate, which the tenants of a same key differ by.
+    pub fn set_tenant_token(&mut self, token: &str) {
+        self.tenant_token_digest = Some(Sha256::digest(token.as_bytes()).into());
+    }
+
+    /// Returns the search parameters applied to the search requests made with the key.
+    #[inline]
+    pub fn search_parameters(&self) -> &KeySearchParameters {
+        &self.search_parameters
+    }
+
     #[inline]
     /// Return true if a tenant token was used to generate the search rules.
     pub fn is_tenant_token(&self) -> bool {
@@ -196,9 +263,14 @@ impl AuthFilter {
 
     pub fn with_allowed_indexes(allowed_indexes: HashSet<IndexUidPattern>) -> Self {
         Self {
+            key_uid: None,
+            tenant_token_digest: None,
             search_rules: None,
             key_authorized_indexes: SearchRules::Set(allowed_indexes),
             allow_index_creation: false,
+            allow_pii: false,
+            search_parameters: KeySearchParameters::default(),
+            principals: None,
         }
     }
 
@@ -259,12 +331,36 @@ impl AuthFilter {
         indexes
     }
 
+    /// Returns the rules to apply to the searches on the index, i.e. the filter of the tenant
+    /// token and the one restricting the documents to the ones the principals can see.
     pub fn get_index_search_rules(&self, index: &str) -> Option<IndexSearchRules> {
         if !self.is_index_authorized(index) {
             return None;
         }
         let search_rules = self.search_rules.as_ref().unwrap_or(&self.key_authorized_indexes);
-        search_rules.get_index_search_rules(index)
+        let mut rules = search_rules.get_index_search_rules(index);
+        if self.principals.is_some() {
+            let rules = rules.get_or_insert_with(IndexSearchRules::default);
+            rules.filter = self.restrict_to_principals(rules.filter.take());
+        }
+        rules
+    }
+
+    /// Restricts the filter to the documents the principals of the key can see,
+    /// returns it as is when the key has no principals.
+    pub fn restrict_to_principals(&self, filter: Option<Value>) -> Option<Value> {
+        let Some(principals) = &self.principals else {
+            return filter;
+        };
+        let acl_filter = Value::String(acl_filter(principals));
+        Some(match filter {
+            None => acl_filter,
+            Some(Value::Array(mut filters)) => {
+                filters.push(acl_filter);
+                Value::Array(filters)
+            }
+            Some(filter) => Value::Array(vec![filter, acl_filter]),
+        })
     }
 }
 
@@ -8,8 +8,8 @@ use deserr::{take_cf_content, DeserializeError, IntoValue, MergeWithError, Value
 
 use crate::error::deserr_codes::*;
 use crate::error::{
-    Code, DeserrParseBoolError, DeserrParseIntError, ErrorCode, InvalidTaskDateError,
-    ParseOffsetDateTimeError,
+    Code, DeserrParseBoolError, DeserrParseFloatError, DeserrParseIntError, ErrorCode,
+    InvalidTaskDateError, ParseOffsetDateTimeError,
 };
 use crate::index_uid::IndexUidFormatError;
 use crate::tasks::{ParseTaskKindError, ParseTaskStatusError};
@@ -146,6 +146,10 @@ macro_rules! make_missing_field_convenience_builder {
     };
 }
 make_missing_field_convenience_builder!(MissingIndexUid, missing_index_uid);
+make_missing_field_convenience_builder!(
+    MissingIndexRebuildDatabases,
+    missing_index_rebuild_databases
+);
 make_missing_field_convenience_builder!(MissingApiKeyActions, missing_api_key_actions);
 make_missing_field_convenience_builder!(MissingApiKeyExpiresAt, missing_api_key_expires_at);
 make_missing_field_convenience_builder!(MissingApiKeyIndexes, missing_api_key_indexes);
@@ -159,6 +163,14 @@ make_missing_field_convenience_builder!(
     MissingDocumentEditionFunction,
     missing_document_edition_function
 );
+make_missing_field_convenience_builder!(
+    MissingSnapshotComparisonIndexUid,
+    missing_snapshot_comparison_index_uid
+);
+make_missing_field_convenience_builder!(
+    MissingSnapshotComparisonSnapshot,
+    missing_snapshot_comparison_snapshot
+);
 
 // Integrate a sub-error into a [`DeserrError`] by taking its error message but using
 // the default error code (C) from `Self`
@@ -186,6 +198,7 @@ macro_rules! merge_with_error_impl_take_error_message {
 // All these errors can be merged into a `DeserrError`
 merge_with_error_impl_take_error_message!(DeserrParseIntError);
 merge_with_error_impl_take_error_message!(DeserrParseBoolError);
+merge_with_error_impl_take_error_message!(DeserrParseFloatError);
 merge_with_error_impl_take_error_message!(uuid::Error);
 merge_with_error_impl_take_error_message!(InvalidTaskDateError);
 merge_with_error_impl_take_error_message!(ParseOffsetDateTimeError);
@@ -194,5 +207,6 @@ merge_with_error_impl_take_error_message!(ParseTaskStatusError);
 merge_with_error_impl_take_error_message!(IndexUidFormatError);
 merge_with_error_impl_take_error_message!(InvalidSearchSemanticRatio);
 merge_with_error_impl_take_error_message!(InvalidSearchRankingScoreThreshold);
+merge_with_error_impl_take_error_message!(InvalidSearchDiversify);
 merge_with_error_impl_take_error_message!(InvalidSimilarRankingScoreThreshold);
 merge_with_error_impl_take_error_message!(InvalidSimilarId);
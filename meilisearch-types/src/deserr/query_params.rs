@@ -17,7 +17,7 @@ use std::str::FromStr;
 
 use deserr::{DeserializeError, Deserr, MergeWithError, ValueKind};
 
-use super::{DeserrParseBoolError, DeserrParseIntError};
+use super::{DeserrParseBoolError, DeserrParseFloatError, DeserrParseIntError};
 use crate::index_uid::IndexUid;
 use crate::tasks::{Kind, Status};
 
@@ -105,6 +105,7 @@ macro_rules! impl_from_query_param_wrap_original_value_in_error {
 impl_from_query_param_wrap_original_value_in_error!(usize, DeserrParseIntError);
 impl_from_query_param_wrap_original_value_in_error!(u32, DeserrParseIntError);
 impl_from_query_param_wrap_original_value_in_error!(bool, DeserrParseBoolError);
+impl_from_query_param_wrap_original_value_in_error!(f64, DeserrParseFloatError);
 
 impl FromQueryParameter for String {
     type Err = Infallible;
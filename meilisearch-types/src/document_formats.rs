@@ -11,14 +11,19 @@ use serde::{Deserialize, Deserializer};
 use serde_json::error::Category;
 
 use crate::error::{Code, ErrorCode};
+use crate::text_extraction::{
+    extract_html_text, extract_pdf_text, ContentExtraction, TextExtractionError,
+};
 
 type Result<T> = std::result::Result<T, DocumentFormatError>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum PayloadType {
     Ndjson,
     Json,
     Csv { delimiter: u8 },
+    Pdf,
+    Html,
 }
 
 impl fmt::Display for PayloadType {
@@ -27,6 +32,8 @@ impl fmt::Display for PayloadType {
             PayloadType::Ndjson => f.write_str("ndjson"),
             PayloadType::Json => f.write_str("json"),
             PayloadType::Csv { .. } => f.write_str("csv"),
+            PayloadType::Pdf => f.write_str("pdf"),
+            PayloadType::Html => f.write_str("html"),
         }
     }
 }
@@ -35,6 +42,7 @@ impl fmt::Display for PayloadType {
 pub enum DocumentFormatError {
     Io(io::Error),
     MalformedPayload(Error, PayloadType),
+    TextExtraction(TextExtractionError, PayloadType),
 }
 
 impl Display for DocumentFormatError {
@@ -73,6 +81,9 @@ impl Display for DocumentFormatError {
                 }
                 _ => write!(f, "The `{}` payload provided is malformed: `{}`.", b, me),
             },
+            Self::TextExtraction(e, b) => {
+                write!(f, "The `{}` payload provided is malformed: `{}`.", b, e)
+            }
         }
     }
 }
@@ -99,6 +110,7 @@ impl ErrorCode for DocumentFormatError {
         match self {
             DocumentFormatError::Io(e) => e.error_code(),
             DocumentFormatError::MalformedPayload(_, _) => Code::MalformedPayload,
+            DocumentFormatError::TextExtraction(_, _) => Code::MalformedPayload,
         }
     }
 }
@@ -117,16 +129,32 @@ pub fn read_csv(file: &File, writer: impl Write, delimiter: u8) -> Result<u64> {
 }
 
 /// Reads JSON from temporary file and write an obkv batch to writer.
-pub fn read_json(file: &File, writer: impl Write) -> Result<u64> {
+///
+/// When an extraction is given, the text of the content of each document is extracted.
+pub fn read_json(
+    file: &File,
+    writer: impl Write,
+    extraction: Option<&ContentExtraction>,
+) -> Result<u64> {
     let mut builder = DocumentsBatchBuilder::new(BufWriter::new(writer));
     let mmap = unsafe { MmapOptions::new().map(file)? };
     let mut deserializer = serde_json::Deserializer::from_slice(&mmap);
 
-    match array_each(&mut deserializer, |obj| builder.append_json_object(&obj)) {
+    let mut extracted_len = 0;
+    let mut append = |mut obj: Object| {
+        if let Some(extraction) = extraction {
+            extraction
+                .extract(&mut obj, &mut extracted_len)
+                .map_err(|e| DocumentFormatError::TextExtraction(e, PayloadType::Json))?;
+        }
+        builder.append_json_object(&obj).map_err(DocumentFormatError::Io)
+    };
+
+    match array_each(&mut deserializer, &mut append) {
         // The json data has been deserialized and does not need to be processed again.
         // The data has been transferred to the writer during the deserialization process.
         Ok(Ok(_)) => (),
-        Ok(Err(e)) => return Err(DocumentFormatError::Io(e)),
+        Ok(Err(e)) => return Err(e),
         Err(e) => {
             // Attempt to deserialize a single json string when the cause of the exception is not Category.data
             // Other types of deserialisation exceptions are returned directly to the front-end
@@ -140,7 +168,7 @@ pub fn read_json(file: &File, writer: impl Write) -> Result<u64> {
             let content: Object = serde_json::from_slice(&mmap)
                 .map_err(Error::Json)
                 .map_err(|e| (PayloadType::Json, e))?;
-            builder.append_json_object(&content).map_err(DocumentFormatError::Io)?;
+            append(content)?;
         }
     }
 
@@ -151,12 +179,25 @@ pub fn read_json(file: &File, writer: impl Write) -> Result<u64> {
 }
 
 /// Reads JSON from temporary file  and write an obkv batch to writer.
-pub fn read_ndjson(file: &File, writer: impl Write) -> Result<u64> {
+///
+/// When an extraction is given, the text of the content of each document is extracted.
+pub fn read_ndjson(
+    file: &File,
+    writer: impl Write,
+    extraction: Option<&ContentExtraction>,
+) -> Result<u64> {
     let mut builder = DocumentsBatchBuilder::new(BufWriter::new(writer));
     let mmap = unsafe { MmapOptions::new().map(file)? };
 
+    let mut extracted_len = 0;
     for result in serde_json::Deserializer::from_slice(&mmap).into_iter() {
-        let object = result.map_err(Error::Json).map_err(|e| (PayloadType::Ndjson, e))?;
+        let mut object: Object =
+            result.map_err(Error::Json).map_err(|e| (PayloadType::Ndjson, e))?;
+        if let Some(extraction) = extraction {
+            extraction
+                .extract(&mut object, &mut extracted_len)
+                .map_err(|e| DocumentFormatError::TextExtraction(e, PayloadType::Ndjson))?;
+        }
         builder.append_json_object(&object).map_err(Into::into).map_err(DocumentFormatError::Io)?;
     }
 
@@ -166,26 +207,57 @@ pub fn read_ndjson(file: &File, writer: impl Write) -> Result<u64> {
     Ok(count as u64)
 }
 
+/// Extracts the text of a PDF or HTML payload from temporary file and write it as an obkv
+/// batch of a single document to writer, the text being written in the `text_field` of the
+/// given document.
+pub fn read_extracted_text(
+    file: &File,
+    writer: impl Write,
+    format: PayloadType,
+    mut document: Object,
+    text_field: &str,
+) -> Result<u64> {
+    let mut builder = DocumentsBatchBuilder::new(BufWriter::new(writer));
+    let mmap = unsafe { MmapOptions::new().map(file)? };
+
+    let text = match format {
+        PayloadType::Pdf => {
+            extract_pdf_text(&mmap).map_err(|e| DocumentFormatError::TextExtraction(e, format))?
+        }
+        _ => extract_html_text(&String::from_utf8_lossy(&mmap)),
+    };
+    document.insert(text_field.to_string(), text.into());
+    builder.append_json_object(&document).map_err(DocumentFormatError::Io)?;
+
+    let count = builder.documents_count();
+    let _ = builder.into_inner().map_err(DocumentFormatError::Io)?;
+
+    Ok(count as u64)
+}
+
 /// The actual handling of the deserialization process in serde
 /// avoids storing the deserialized object in memory.
 ///
 /// ## References
 /// <https://serde.rs/stream-array.html>
 /// <https://github.com/serde-rs/json/issues/160>
-fn array_each<'de, D, T, F>(deserializer: D, f: F) -> std::result::Result<io::Result<u64>, D::Error>
+fn array_each<'de, D, T, F, E>(
+    deserializer: D,
+    f: F,
+) -> std::result::Result<std::result::Result<u64, E>, D::Error>
 where
     D: Deserializer<'de>,
     T: Deserialize<'de>,
-    F: FnMut(T) -> io::Result<()>,
+    F: FnMut(T) -> std::result::Result<(), E>,
 {
     struct SeqVisitor<T, F>(F, PhantomData<T>);
 
-    impl<'de, T, F> Visitor<'de> for SeqVisitor<T, F>
+    impl<'de, T, F, E> Visitor<'de> for SeqVisitor<T, F>
     where
         T: Deserialize<'de>,
-        F: FnMut(T) -> io::Result<()>,
+        F: FnMut(T) -> std::result::Result<(), E>,
     {
-        type Value = io::Result<u64>;
+        type Value = std::result::Result<u64, E>;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
             formatter.write_str("a nonempty sequence")
@@ -194,7 +266,7 @@ where
         fn visit_seq<A>(
             mut self,
             mut seq: A,
-        ) -> std::result::Result<io::Result<u64>, <A as SeqAccess<'de>>::Error>
+        ) -> std::result::Result<std::result::Result<u64, E>, <A as SeqAccess<'de>>::Error>
         where
             A: SeqAccess<'de>,
         {
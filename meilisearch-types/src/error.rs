@@ -183,12 +183,19 @@ macro_rules! make_error_codes {
 
 // An exhaustive list of all the error codes used by meilisearch.
 make_error_codes! {
+AclNotIndexed                         , InvalidRequest       , BAD_REQUEST ;
 ApiKeyAlreadyExists                   , InvalidRequest       , CONFLICT ;
 ApiKeyNotFound                        , InvalidRequest       , NOT_FOUND ;
+AttachmentNotFound                    , InvalidRequest       , NOT_FOUND ;
 BadParameter                          , InvalidRequest       , BAD_REQUEST;
 BadRequest                            , InvalidRequest       , BAD_REQUEST;
+BlockedSearchTerm                     , InvalidRequest       , BAD_REQUEST ;
 DatabaseSizeLimitReached              , Internal             , INTERNAL_SERVER_ERROR;
+DocumentHookFailed                    , InvalidRequest       , BAD_REQUEST ;
 DocumentNotFound                      , InvalidRequest       , NOT_FOUND;
+DocumentUploadInProgress              , InvalidRequest       , CONFLICT ;
+DocumentUploadNotFound                , InvalidRequest       , NOT_FOUND ;
+DocumentUploadOffsetMismatch          , InvalidRequest       , CONFLICT ;
 DumpAlreadyProcessing                 , InvalidRequest       , CONFLICT;
 DumpNotFound                          , InvalidRequest       , NOT_FOUND;
 DumpProcessFailed                     , Internal             , INTERNAL_SERVER_ERROR;
@@ -198,6 +205,8 @@ ImmutableApiKeyCreatedAt              , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyExpiresAt              , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyIndexes                , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyKey                    , InvalidRequest       , BAD_REQUEST;
+ImmutableApiKeyPrincipals             , InvalidRequest       , BAD_REQUEST;
+ImmutableApiKeySearchParameters       , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyUid                    , InvalidRequest       , BAD_REQUEST;
 ImmutableApiKeyUpdatedAt              , InvalidRequest       , BAD_REQUEST;
 ImmutableIndexCreatedAt               , InvalidRequest       , BAD_REQUEST;
@@ -218,11 +227,18 @@ InvalidApiKeyIndexes                  , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyLimit                    , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyName                     , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyOffset                   , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeyPrincipals               , InvalidRequest       , BAD_REQUEST ;
+InvalidApiKeySearchParameters         , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyUid                      , InvalidRequest       , BAD_REQUEST ;
 InvalidContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
+InvalidDocumentAttachments            , InvalidRequest       , BAD_REQUEST ;
+InvalidDumpAnonymizedAttributes       , InvalidRequest       , BAD_REQUEST ;
+InvalidDumpAnonymization              , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentContentField           , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentCsvDelimiter           , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFields                 , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentRetrieveVectors        , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentWord                   , InvalidRequest       , BAD_REQUEST ;
 MissingDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 MissingDocumentEditionFunction        , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
@@ -232,12 +248,18 @@ InvalidVectorsType                    , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentId                     , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentLimit                  , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentOffset                 , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentTextField              , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentUploadOffset           , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentWriteMode              , InvalidRequest       , BAD_REQUEST ;
 InvalidEmbedder                       , InvalidRequest       , BAD_REQUEST ;
 InvalidHybridQuery                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexRebuildDatabases          , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexUid                       , InvalidRequest       , BAD_REQUEST ;
+InvalidPartitionRange                 , InvalidRequest       , BAD_REQUEST ;
+InvalidPartitionTimestamp             , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToSearchOn     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToCrop         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToHighlight    , InvalidRequest       , BAD_REQUEST ;
@@ -249,32 +271,49 @@ InvalidSimilarRankingScoreThreshold   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchMaxHighlightFragments    , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFragmentSeparator        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarId                      , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFilter                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchPreparedFilter           , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarFilter                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHighlightPostTag         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHighlightPreTag          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHitsPerPage              , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarLimit                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLimit                    , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchLanguages                , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchMatchingStrategy         , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchPhraseFallback           , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarOffset                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPage                     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchQ                        , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchQuery               , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                , InvalidRequest       , BAD_REQUEST ;
+InvalidFacetValuesFrom                , InvalidRequest       , BAD_REQUEST ;
+InvalidFacetValuesLimit               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchVector                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowMatchesPosition      , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScore         , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScore        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScoreDetails , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowQueryTerms           , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowQueryInterpretation  , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchShowTimings              , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSynonyms                 , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchGeoSortMaxDistance       , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchGeoSortBucketWidth       , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchDistinct                 , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDiversify                , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchCreateSession            , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSessionToken             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsCopySettings           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision     , InvalidRequest       , BAD_REQUEST ;
@@ -282,18 +321,45 @@ InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSearchCutoffMs         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsPrefixCacheSize        , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsLongQueryThreshold     , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsQuoteCharacters        , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsLanguagePreset         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsPresenceOnlyAttributes , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsChunking               , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsDocumentHook           , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsComputedFields         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsPreparedFilters        , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsPiiAttributes          , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsQueryLimits            , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsRegexFilter            , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsFieldLanguages         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsMinPrefixLength        , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsBlockedTerms           , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsNewFields              , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsQueryRewrites          , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsPositionDecay          , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsBatching               , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsCollation              , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsTieBreaker             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsEmbedders              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsRankingRules           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSearchableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSortableAttributes     , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsDenseSortableAttributes, InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsStopWords              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsNonSeparatorTokens     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSeparatorTokens        , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDictionary             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsExactWords             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSynonyms               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsTypoTolerance          , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsVersionAttribute       , InvalidRequest       , BAD_REQUEST ;
 InvalidState                          , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidStoreFile                      , Internal             , INTERNAL_SERVER_ERROR ;
+InvalidSnapshotComparisonIndexUid     , InvalidRequest       , BAD_REQUEST ;
+InvalidSnapshotComparisonOtherSnapshot, InvalidRequest       , BAD_REQUEST ;
+InvalidSnapshotComparisonSnapshot     , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapDuplicateIndexFound        , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterEnqueuedAt            , InvalidRequest       , BAD_REQUEST ;
@@ -310,6 +376,8 @@ InvalidTaskTypes                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskUids                       , InvalidRequest       , BAD_REQUEST  ;
 IoError                               , System               , UNPROCESSABLE_ENTITY;
 FeatureNotEnabled                     , InvalidRequest       , BAD_REQUEST ;
+FederationPiiNotAllowed               , Auth                 , FORBIDDEN ;
+FederationUpstreamError               , System               , BAD_GATEWAY ;
 MalformedPayload                      , InvalidRequest       , BAD_REQUEST ;
 MaxFieldsLimitExceeded                , InvalidRequest       , BAD_REQUEST ;
 MissingApiKeyActions                  , InvalidRequest       , BAD_REQUEST ;
@@ -319,20 +387,33 @@ MissingAuthorizationHeader            , Auth                 , UNAUTHORIZED ;
 MissingContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 MissingDocumentId                     , InvalidRequest       , BAD_REQUEST ;
 MissingFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
+MissingIndexRebuildDatabases          , InvalidRequest       , BAD_REQUEST ;
 MissingIndexUid                       , InvalidRequest       , BAD_REQUEST ;
 MissingMasterKey                      , Auth                 , UNAUTHORIZED ;
 MissingPayload                        , InvalidRequest       , BAD_REQUEST ;
+MissingPiiEncryptionKey               , InvalidRequest       , BAD_REQUEST ;
 MissingSearchHybrid                   , InvalidRequest       , BAD_REQUEST ;
+MissingSnapshotComparisonIndexUid     , InvalidRequest       , BAD_REQUEST ;
+MissingSnapshotComparisonSnapshot     , InvalidRequest       , BAD_REQUEST ;
 MissingSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 MissingTaskFilters                    , InvalidRequest       , BAD_REQUEST ;
 NoSpaceLeftOnDevice                   , System               , UNPROCESSABLE_ENTITY;
+PartitionFamilyNotFound               , InvalidRequest       , NOT_FOUND ;
 PayloadTooLarge                       , InvalidRequest       , PAYLOAD_TOO_LARGE ;
+SearchJobNotFound                     , InvalidRequest       , NOT_FOUND ;
+SearchJobResultTooLarge               , InvalidRequest       , BAD_REQUEST ;
+SearchSessionNotFound                 , InvalidRequest       , NOT_FOUND ;
+SnapshotNotFound                      , InvalidRequest       , NOT_FOUND ;
+TooManyDocumentUploads                , System               , SERVICE_UNAVAILABLE ;
+TooManySearchJobs                     , System               , SERVICE_UNAVAILABLE ;
 TooManySearchRequests                 , System               , SERVICE_UNAVAILABLE ;
 TaskNotFound                          , InvalidRequest       , NOT_FOUND ;
+TaskNotReplayable                     , InvalidRequest       , BAD_REQUEST ;
 TooManyOpenFiles                      , System               , UNPROCESSABLE_ENTITY ;
 TooManyVectors                        , InvalidRequest       , BAD_REQUEST ;
 UnretrievableDocument                 , Internal             , BAD_REQUEST ;
 UnretrievableErrorCode                , InvalidRequest       , BAD_REQUEST ;
+UnknownRankingRulePlugin              , InvalidRequest       , BAD_REQUEST ;
 UnsupportedMediaType                  , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 
 // Experimental features
@@ -390,7 +471,9 @@ impl ErrorCode for milli::Error {
                     }
                     UserError::PrimaryKeyCannotBeChanged(_) => Code::IndexPrimaryKeyAlreadyExists,
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
+                    UserError::InvalidDiversifyAttribute { .. } => Code::InvalidSearchDiversify,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
+                    UserError::UnknownRankingRulePlugin { .. } => Code::UnknownRankingRulePlugin,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
                     UserError::InvalidSearchableAttribute { .. } => {
@@ -404,11 +487,29 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidVectorDimensions { .. } => Code::InvalidVectorDimensions,
                     UserError::InvalidVectorsMapType { .. }
                     | UserError::InvalidVectorsEmbedderConf { .. } => Code::InvalidVectorsType,
+                    UserError::InvalidDocumentAttachments { .. } => {
+                        Code::InvalidDocumentAttachments
+                    }
                     UserError::TooManyVectors(_, _) => Code::TooManyVectors,
                     UserError::SortError(_) => Code::InvalidSearchSort,
                     UserError::InvalidMinTypoWordLenSetting(_, _) => {
                         Code::InvalidSettingsTypoTolerance
                     }
+                    UserError::InvalidTypoCostsSetting(_, _) => Code::InvalidSettingsTypoTolerance,
+                    UserError::InvalidQuoteCharacter(_) => Code::InvalidSettingsQuoteCharacters,
+                    UserError::InvalidQueryRegex { .. } => Code::InvalidSearchQ,
+                    UserError::BlockedSearchTerm(_) => Code::BlockedSearchTerm,
+                    UserError::InvalidChunkingOverlap { .. } => Code::InvalidSettingsChunking,
+                    UserError::InvalidDocumentHook(_) => Code::InvalidSettingsDocumentHook,
+                    UserError::DocumentHookFailed { .. } => Code::DocumentHookFailed,
+                    UserError::InvalidComputedField { .. } => Code::InvalidSettingsComputedFields,
+                    UserError::InvalidPreparedFilter { .. } => Code::InvalidSettingsPreparedFilters,
+                    UserError::InvalidQueryRewriteRule { .. } => Code::InvalidSettingsQueryRewrites,
+                    UserError::InvalidPreparedFilterParameters { .. }
+                    | UserError::UnknownPreparedFilter { .. } => Code::InvalidSearchPreparedFilter,
+                    UserError::MissingPiiEncryptionKey => Code::MissingPiiEncryptionKey,
+                    UserError::InvalidPiiAttribute { .. } => Code::InvalidSettingsPiiAttributes,
+                    UserError::AclNotIndexed => Code::AclNotIndexed,
                     UserError::InvalidEmbedder(_) => Code::InvalidEmbedder,
                     UserError::VectorEmbeddingError(_) => Code::VectorEmbeddingError,
                     UserError::DocumentEditionCannotModifyPrimaryKey
@@ -503,6 +604,16 @@ impl fmt::Display for DeserrParseIntError {
     }
 }
 
+/// Deserialization error when `deserr` cannot parse a String
+/// into a float.
+#[derive(Debug)]
+pub struct DeserrParseFloatError(pub String);
+impl fmt::Display for DeserrParseFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse `{}` as a number", self.0)
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidSearchSemanticRatio {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -532,6 +643,12 @@ impl fmt::Display for deserr_codes::InvalidSearchRankingScoreThreshold {
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidSearchDiversify {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the value of `maxPerValue` is invalid, expected a positive integer.")
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidSimilarRankingScoreThreshold {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         deserr_codes::InvalidSearchRankingScoreThreshold.fmt(f)
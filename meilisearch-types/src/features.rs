@@ -7,6 +7,7 @@ pub struct RuntimeTogglableFeatures {
     pub metrics: bool,
     pub logs_route: bool,
     pub edit_documents_by_function: bool,
+    pub typo_statistics: bool,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
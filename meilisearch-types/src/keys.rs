@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::convert::Infallible;
 use std::hash::Hash;
 use std::str::FromStr;
@@ -6,6 +7,7 @@ use deserr::{DeserializeError, Deserr, MergeWithError, ValuePointerRef};
 use enum_iterator::Sequence;
 use milli::update::Setting;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use time::format_description::well_known::Rfc3339;
 use time::macros::{format_description, time};
 use time::{Date, OffsetDateTime, PrimitiveDateTime};
@@ -47,11 +49,24 @@ pub struct CreateApiKey {
     pub indexes: Vec<IndexUidPattern>,
     #[deserr(error = DeserrJsonError<InvalidApiKeyExpiresAt>, try_from(Option<String>) = parse_expiration_date -> ParseOffsetDateTimeError, missing_field_error = DeserrJsonError::missing_api_key_expires_at)]
     pub expires_at: Option<OffsetDateTime>,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeySearchParameters>)]
+    pub search_parameters: KeySearchParameters,
+    #[deserr(default, error = DeserrJsonError<InvalidApiKeyPrincipals>)]
+    pub principals: Vec<String>,
 }
 
 impl CreateApiKey {
     pub fn to_key(self) -> Key {
-        let CreateApiKey { description, name, uid, actions, indexes, expires_at } = self;
+        let CreateApiKey {
+            description,
+            name,
+            uid,
+            actions,
+            indexes,
+            expires_at,
+            search_parameters,
+            principals,
+        } = self;
         let now = OffsetDateTime::now_utc();
         Key {
             description,
@@ -60,6 +75,8 @@ impl CreateApiKey {
             actions,
             indexes,
             expires_at,
+            search_parameters,
+            principals,
             created_at: now,
             updated_at: now,
         }
@@ -76,6 +93,10 @@ fn deny_immutable_fields_api_key(
         "actions" => immutable_field_error(field, accepted, Code::ImmutableApiKeyActions),
         "indexes" => immutable_field_error(field, accepted, Code::ImmutableApiKeyIndexes),
         "expiresAt" => immutable_field_error(field, accepted, Code::ImmutableApiKeyExpiresAt),
+        "searchParameters" => {
+            immutable_field_error(field, accepted, Code::ImmutableApiKeySearchParameters)
+        }
+        "principals" => immutable_field_error(field, accepted, Code::ImmutableApiKeyPrincipals),
         "createdAt" => immutable_field_error(field, accepted, Code::ImmutableApiKeyCreatedAt),
         "updatedAt" => immutable_field_error(field, accepted, Code::ImmutableApiKeyUpdatedAt),
         _ => deserr::take_cf_content(DeserrJsonError::<BadRequest>::error::<Infallible>(
@@ -106,6 +127,11 @@ pub struct Key {
     pub indexes: Vec<IndexUidPattern>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub expires_at: Option<OffsetDateTime>,
+    #[serde(default, skip_serializing_if = "KeySearchParameters::is_empty")]
+    pub search_parameters: KeySearchParameters,
+    /// The principals whose documents the key can see, all the documents when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub principals: Vec<String>,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
@@ -123,6 +149,8 @@ impl Key {
             actions: vec![Action::All],
             indexes: vec![IndexUidPattern::all()],
             expires_at: None,
+            search_parameters: KeySearchParameters::default(),
+            principals: Vec::new(),
             created_at: now,
             updated_at: now,
         }
@@ -138,12 +166,35 @@ impl Key {
             actions: vec![Action::Search],
             indexes: vec![IndexUidPattern::all()],
             expires_at: None,
+            search_parameters: KeySearchParameters::default(),
+            principals: Vec::new(),
             created_at: now,
             updated_at: now,
         }
     }
 }
 
+/// The search parameters applied to every search request made with a key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidApiKeySearchParameters>, rename_all = camelCase, deny_unknown_fields)]
+pub struct KeySearchParameters {
+    /// The parameters used when the search request leaves them to their default value.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[deserr(default)]
+    pub default: BTreeMap<String, Value>,
+    /// The parameters replacing the ones of the search request, whatever they are.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[deserr(default)]
+    pub forced: BTreeMap<String, Value>,
+}
+
+impl KeySearchParameters {
+    pub fn is_empty(&self) -> bool {
+        self.default.is_empty() && self.forced.is_empty()
+    }
+}
+
 fn parse_expiration_date(
     string: Option<String>,
 ) -> std::result::Result<Option<OffsetDateTime>, ParseOffsetDateTimeError> {
@@ -284,6 +335,9 @@ pub enum Action {
     #[serde(rename = "experimental.update")]
     #[deserr(rename = "experimental.update")]
     ExperimentalFeaturesUpdate,
+    #[serde(rename = "documents.pii")]
+    #[deserr(rename = "documents.pii")]
+    DocumentsPii,
 }
 
 impl Action {
@@ -323,6 +377,7 @@ impl Action {
             KEYS_DELETE => Some(Self::KeysDelete),
             EXPERIMENTAL_FEATURES_GET => Some(Self::ExperimentalFeaturesGet),
             EXPERIMENTAL_FEATURES_UPDATE => Some(Self::ExperimentalFeaturesUpdate),
+            DOCUMENTS_PII => Some(Self::DocumentsPii),
             _otherwise => None,
         }
     }
@@ -368,4 +423,5 @@ pub mod actions {
     pub const KEYS_DELETE: u8 = KeysDelete.repr();
     pub const EXPERIMENTAL_FEATURES_GET: u8 = ExperimentalFeaturesGet.repr();
     pub const EXPERIMENTAL_FEATURES_UPDATE: u8 = ExperimentalFeaturesUpdate.repr();
+    pub const DOCUMENTS_PII: u8 = DocumentsPii.repr();
 }
@@ -11,6 +11,7 @@ pub mod settings;
 pub mod star_or;
 pub mod task_view;
 pub mod tasks;
+pub mod text_extraction;
 pub mod versioning;
 pub use milli::{heed, Index};
 use uuid::Uuid;
@@ -6,12 +6,25 @@ use std::num::NonZeroUsize;
 use std::ops::{ControlFlow, Deref};
 use std::str::FromStr;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValuePointerRef};
 use fst::IntoStreamer;
+use milli::blocked_terms::BlockedTermsMode;
+use milli::collation::{CollationLocale, SortCollation};
 use milli::index::IndexEmbeddingConfig;
+use milli::language_preset::LanguagePreset;
+use milli::position_decay::PositionDecay;
 use milli::proximity::ProximityPrecision;
+use milli::query_limits::DEFAULT_MAX_QUERY_TERMS;
+use milli::query_rewrites::QueryRewriteRule;
+use milli::tie_breaker::TieBreaker;
+use milli::typo::{FirstLetterTypos, KeyboardLayout, TypoCosts};
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{
+    Criterion, CriterionError, DecayFunction, DecayOrigin, DecayScale, FieldId, HalfLife, Index,
+    Weight, DEFAULT_SEARCH_CUTOFF_MS, DEFAULT_VALUES_PER_FACET,
+};
 use serde::{Deserialize, Serialize, Serializer};
 
 use crate::deserr::DeserrJsonError;
@@ -22,14 +35,15 @@ use crate::facet_values_sort::FacetValuesSort;
 /// will be able to return in one search call.
 pub const DEFAULT_PAGINATION_MAX_TOTAL_HITS: usize = 1000;
 
-fn serialize_with_wildcard<S>(
-    field: &Setting<Vec<String>>,
+fn serialize_with_wildcard<S, T>(
+    field: &Setting<Vec<T>>,
     s: S,
 ) -> std::result::Result<S::Ok, S::Error>
 where
     S: Serializer,
+    T: Serialize + for<'a> From<&'a str>,
 {
-    let wildcard = vec!["*".to_string()];
+    let wildcard = vec![T::from("*")];
     match field {
         Setting::Set(value) => Some(value),
         Setting::Reset => Some(&wildcard),
@@ -80,6 +94,34 @@ pub struct MinWordSizeTyposSetting {
     pub two_typos: Setting<u8>,
 }
 
+fn validate_typo_costs_setting<E: DeserializeError>(
+    s: TypoCostsSetting,
+    location: ValuePointerRef,
+) -> Result<TypoCostsSetting, E> {
+    if let (Setting::Set(one), Setting::Set(two)) = (s.one_typo, s.two_typos) {
+        if one > two {
+            return Err(deserr::take_cf_content(E::error::<Infallible>(None, ErrorKind::Unexpected { msg: format!("`typoCosts` setting is invalid. `twoTypos` should be greater or equals to `oneTypo` but found `oneTypo: {one}` and `twoTypos: {two}`.") }, location)));
+        }
+    }
+    Ok(s)
+}
+
+/// The cost of the typo edges, the documents matching the cheapest ones being ranked first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(deny_unknown_fields, rename_all = camelCase, validate = validate_typo_costs_setting -> DeserrJsonError<InvalidSettingsTypoTolerance>)]
+pub struct TypoCostsSetting {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub one_typo: Setting<u32>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub two_typos: Setting<u32>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub split: Setting<u32>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 #[deserr(deny_unknown_fields, rename_all = camelCase, where_predicate = __Deserr_E: deserr::MergeWithError<DeserrJsonError<InvalidSettingsTypoTolerance>>)]
@@ -96,6 +138,33 @@ pub struct TypoSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub disable_on_attributes: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoTolerance>)]
+    pub half_typos: Setting<HalfTyposSetting>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub disable_on_numbers: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoTolerance>)]
+    pub first_letter_typos: Setting<FirstLetterTyposView>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub enable_phonetic: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoTolerance>)]
+    pub typo_costs: Setting<TypoCostsSetting>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(deny_unknown_fields, rename_all = camelCase, where_predicate = __Deserr_E: deserr::MergeWithError<DeserrJsonError<InvalidSettingsTypoTolerance>>)]
+pub struct HalfTyposSetting {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoTolerance>)]
+    pub keyboard_layout: Setting<KeyboardLayoutView>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub accents: Setting<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
@@ -108,6 +177,11 @@ pub struct FacetingSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub sort_facet_values_by: Setting<BTreeMap<String, FacetValuesSort>>,
+    /// By field, the facet values displayed in place of the values merged with them,
+    /// e.g. `{ "country": { "United States": ["US", "USA"] } }`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub value_aliases: Setting<BTreeMap<String, BTreeMap<String, Vec<String>>>>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
@@ -119,6 +193,160 @@ pub struct PaginationSettings {
     pub max_total_hits: Setting<usize>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct ChunkingSettings {
+    /// The number of words of a passage.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub max_words: Setting<usize>,
+    /// The number of words at the end of a passage that are repeated at the start of the next one.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub overlap: Setting<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryLimitsSettings {
+    /// The number of characters of a query beyond which the end of the query is ignored.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub max_length: Setting<usize>,
+    /// The number of words and phrases of a query beyond which the next ones are ignored.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub max_terms: Setting<usize>,
+    /// The number of phrases of a query beyond which the next phrases are ignored.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub max_phrases: Setting<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct BlockedTermsSettings {
+    /// The words that are never searched, nor derived from the other words of the queries.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub terms: Setting<BTreeSet<String>>,
+    /// Whether the blocked words are dropped from the queries or reject them.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub mode: Setting<BlockedTermsModeView>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct NewFieldsSettings {
+    /// Whether the fields discovered by the document additions are searchable
+    /// when the searchable attributes are `*`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub searchable: Setting<bool>,
+    /// Whether the fields discovered by the document additions are made filterable.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub filterable: Setting<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct BatchingSettings {
+    /// The maximum number of tasks merged into a batch, `null` for no limit.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub max_batch_size: Setting<u64>,
+    /// The maximum total size in bytes of the document payloads merged into a batch,
+    /// `null` for no limit.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub max_batch_payload: Setting<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct CollationSettings {
+    /// The locale ordering the strings of all the sortable attributes, `null` to sort
+    /// them by their normalized bytes.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub locale: Setting<CollationLocaleView>,
+    /// The locales of some sortable attributes, overriding the locale of the index.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub attributes: Setting<BTreeMap<String, CollationLocaleView>>,
+}
+
+/// A rule rewriting the queries before they are tokenized.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRewriteRuleView {
+    /// The text to find in the queries, matched case-insensitively as a whole word,
+    /// or a regular expression if `regex` is `true`.
+    pub find: String,
+    /// The replacement, which can refer to the capture groups of a regular expression, e.g. `$1`.
+    pub replace: String,
+    #[serde(default)]
+    #[deserr(default)]
+    pub regex: bool,
+}
+
+impl From<QueryRewriteRule> for QueryRewriteRuleView {
+    fn from(QueryRewriteRule { find, replace, regex }: QueryRewriteRule) -> Self {
+        QueryRewriteRuleView { find, replace, regex }
+    }
+}
+
+impl From<QueryRewriteRuleView> for QueryRewriteRule {
+    fn from(QueryRewriteRuleView { find, replace, regex }: QueryRewriteRuleView) -> Self {
+        QueryRewriteRule { find, replace, regex }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct RegexFilterSettings {
+    /// Whether the `MATCHES` operator can be used in the filters.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub enabled: Setting<bool>,
+    /// The number of distinct values of an attribute beyond which a `MATCHES` condition fails.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub max_candidates: Setting<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentHookSettings {
+    /// The base64 encoded WebAssembly module executed on each document.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub module: Setting<String>,
+    /// The hex encoded SHA-256 digest of the module, shown instead of the module itself.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(skip)]
+    pub module_sha256: Setting<String>,
+    /// The size of the module, in bytes.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(skip)]
+    pub module_size: Setting<u64>,
+    /// The fuel given to the module for each document, roughly its number of executed instructions.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub fuel: Setting<u64>,
+}
+
 impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSettingsRankingRules> {
     fn merge(
         _self_: Option<Self>,
@@ -150,7 +378,7 @@ pub struct Settings<T> {
 
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchableAttributes>)]
-    pub searchable_attributes: WildcardSetting,
+    pub searchable_attributes: WildcardSetting<SearchableAttribute>,
 
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsFilterableAttributes>)]
@@ -158,6 +386,10 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSortableAttributes>)]
     pub sortable_attributes: Setting<BTreeSet<String>>,
+    /// Sortable attributes for which a dense numeric sort index is maintained.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDenseSortableAttributes>)]
+    pub dense_sortable_attributes: Setting<BTreeSet<String>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsRankingRules>)]
     pub ranking_rules: Setting<Vec<RankingRuleView>>,
@@ -174,12 +406,18 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsDictionary>)]
     pub dictionary: Setting<BTreeSet<String>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsExactWords>)]
+    pub exact_words: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonyms>)]
     pub synonyms: Setting<BTreeMap<String, Vec<String>>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsDistinctAttribute>)]
     pub distinct_attribute: Setting<String>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsVersionAttribute>)]
+    pub version_attribute: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsProximityPrecision>)]
     pub proximity_precision: Setting<ProximityPrecisionView>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
@@ -198,6 +436,90 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchCutoffMs>)]
     pub search_cutoff_ms: Setting<u64>,
+    /// Number of hits precomputed for the most popular short prefixes. `0` disables the cache.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixCacheSize>)]
+    pub prefix_cache_size: Setting<usize>,
+    /// Number of query terms above which the proximities from 3 to 7 are bucketed together.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLongQueryThreshold>)]
+    pub long_query_threshold: Setting<usize>,
+    /// Characters delimiting the phrases in the search queries.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsQuoteCharacters>)]
+    pub quote_characters: Setting<BTreeSet<String>>,
+    /// Language whose tokenization, stop words and typo tolerance presets are applied.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLanguagePreset>)]
+    pub language_preset: Setting<LanguagePresetView>,
+    /// Attributes for which the positions of the words and the proximities between them are not stored.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPresenceOnlyAttributes>)]
+    pub presence_only_attributes: Setting<BTreeSet<String>>,
+    /// Cuts the very long searchable fields into overlapping passages at indexing time.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsChunking>)]
+    pub chunking: Setting<ChunkingSettings>,
+    /// Transforms each document with a WebAssembly module at ingestion time.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDocumentHook>)]
+    pub document_hook: Setting<DocumentHookSettings>,
+    /// Fields computed at search time from an expression over the other fields, e.g. `price * (1 - discount)`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsComputedFields>)]
+    pub computed_fields: Setting<BTreeMap<String, String>>,
+    /// Named filters with `{parameter}` placeholders, e.g. `user_id = {uid}`, that the search requests reference.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPreparedFilters>)]
+    pub prepared_filters: Setting<BTreeMap<String, String>>,
+    /// Attributes holding personal information, stored encrypted and only returned to the keys with the `documents.pii` action.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPiiAttributes>)]
+    pub pii_attributes: Setting<BTreeSet<String>>,
+    /// Limits on the length, number of terms and number of phrases of the search queries.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsQueryLimits>)]
+    pub query_limits: Setting<QueryLimitsSettings>,
+    /// Enables the `MATCHES` filter operator evaluating a regular expression on the facet values.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsRegexFilter>)]
+    pub regex_filter: Setting<RegexFilterSettings>,
+    /// Language of the fields whose text is written in a single language, e.g. `{ "title_fr": "fr" }`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsFieldLanguages>)]
+    pub field_languages: Setting<BTreeMap<String, LanguagePresetView>>,
+    /// Number of characters the last word of the query must have to be searched as a prefix.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsMinPrefixLength>)]
+    pub min_prefix_length: Setting<usize>,
+    /// Words that are never searched, either dropped from the queries or rejecting them.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsBlockedTerms>)]
+    pub blocked_terms: Setting<BlockedTermsSettings>,
+    /// How the fields discovered by the document additions are handled.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsNewFields>)]
+    pub new_fields: Setting<NewFieldsSettings>,
+    /// Rules rewriting the queries before they are tokenized, applied in order.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsQueryRewrites>)]
+    pub query_rewrites: Setting<Vec<QueryRewriteRuleView>>,
+    /// How the relevancy of the matched words decays with their position in the attributes.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPositionDecay>)]
+    pub position_decay: Setting<PositionDecayView>,
+    /// The limits of the batches of tasks processed on the index.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsBatching>)]
+    pub batching: Setting<BatchingSettings>,
+    /// The locales ordering the string values of the sortable attributes.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsCollation>)]
+    pub collation: Setting<CollationSettings>,
+    /// The order of the documents that all the ranking rules consider equal.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsTieBreaker>)]
+    pub tie_breaker: Setting<TieBreakerView>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -205,7 +527,159 @@ pub struct Settings<T> {
 }
 
 impl<T> Settings<T> {
+    /// The name of every setting, as they appear in the JSON representation.
+    pub const NAMES: &'static [&'static str] = &[
+        "displayedAttributes",
+        "searchableAttributes",
+        "filterableAttributes",
+        "sortableAttributes",
+        "denseSortableAttributes",
+        "rankingRules",
+        "stopWords",
+        "nonSeparatorTokens",
+        "separatorTokens",
+        "dictionary",
+        "exactWords",
+        "synonyms",
+        "distinctAttribute",
+        "versionAttribute",
+        "proximityPrecision",
+        "typoTolerance",
+        "faceting",
+        "pagination",
+        "embedders",
+        "searchCutoffMs",
+        "prefixCacheSize",
+        "longQueryThreshold",
+        "quoteCharacters",
+        "languagePreset",
+        "presenceOnlyAttributes",
+        "chunking",
+        "documentHook",
+        "computedFields",
+        "preparedFilters",
+        "piiAttributes",
+        "queryLimits",
+        "regexFilter",
+        "fieldLanguages",
+        "minPrefixLength",
+        "blockedTerms",
+        "newFields",
+        "queryRewrites",
+        "positionDecay",
+        "batching",
+        "collation",
+        "tieBreaker",
+    ];
+
+    /// Marks every setting whose name is not in `names` as `NotSet`.
+    ///
+    /// Returns the first name that isn't a known setting, if any.
+    pub fn retain(&mut self, names: &[String]) -> Result<(), String> {
+        if let Some(unknown) = names.iter().find(|name| !Self::NAMES.contains(&name.as_str())) {
+            return Err(unknown.clone());
+        }
+
+        fn retain_setting<S: Default>(setting: &mut S, keep: bool) {
+            if !keep {
+                *setting = S::default();
+            }
+        }
+        let keep = |name: &str| names.iter().any(|n| n == name);
+
+        let Self {
+            displayed_attributes,
+            searchable_attributes,
+            filterable_attributes,
+            sortable_attributes,
+            dense_sortable_attributes,
+            ranking_rules,
+            stop_words,
+            non_separator_tokens,
+            separator_tokens,
+            dictionary,
+            exact_words,
+            synonyms,
+            distinct_attribute,
+            version_attribute,
+            proximity_precision,
+            typo_tolerance,
+            faceting,
+            pagination,
+            embedders,
+            search_cutoff_ms,
+            prefix_cache_size,
+            long_query_threshold,
+            quote_characters,
+            language_preset,
+            presence_only_attributes,
+            chunking,
+            document_hook,
+            computed_fields,
+            prepared_filters,
+            pii_attributes,
+            query_limits,
+            regex_filter,
+            field_languages,
+            min_prefix_length,
+            blocked_terms,
+            new_fields,
+            query_rewrites,
+            position_decay,
+            batching,
+            collation,
+            tie_breaker,
+            _kind: _,
+        } = self;
+
+        retain_setting(displayed_attributes, keep("displayedAttributes"));
+        retain_setting(searchable_attributes, keep("searchableAttributes"));
+        retain_setting(filterable_attributes, keep("filterableAttributes"));
+        retain_setting(sortable_attributes, keep("sortableAttributes"));
+        retain_setting(dense_sortable_attributes, keep("denseSortableAttributes"));
+        retain_setting(ranking_rules, keep("rankingRules"));
+        retain_setting(stop_words, keep("stopWords"));
+        retain_setting(non_separator_tokens, keep("nonSeparatorTokens"));
+        retain_setting(separator_tokens, keep("separatorTokens"));
+        retain_setting(dictionary, keep("dictionary"));
+        retain_setting(exact_words, keep("exactWords"));
+        retain_setting(synonyms, keep("synonyms"));
+        retain_setting(distinct_attribute, keep("distinctAttribute"));
+        retain_setting(version_attribute, keep("versionAttribute"));
+        retain_setting(proximity_precision, keep("proximityPrecision"));
+        retain_setting(typo_tolerance, keep("typoTolerance"));
+        retain_setting(faceting, keep("faceting"));
+        retain_setting(pagination, keep("pagination"));
+        retain_setting(embedders, keep("embedders"));
+        retain_setting(search_cutoff_ms, keep("searchCutoffMs"));
+        retain_setting(prefix_cache_size, keep("prefixCacheSize"));
+        retain_setting(long_query_threshold, keep("longQueryThreshold"));
+        retain_setting(quote_characters, keep("quoteCharacters"));
+        retain_setting(language_preset, keep("languagePreset"));
+        retain_setting(presence_only_attributes, keep("presenceOnlyAttributes"));
+        retain_setting(chunking, keep("chunking"));
+        retain_setting(document_hook, keep("documentHook"));
+        retain_setting(computed_fields, keep("computedFields"));
+        retain_setting(prepared_filters, keep("preparedFilters"));
+        retain_setting(pii_attributes, keep("piiAttributes"));
+        retain_setting(query_limits, keep("queryLimits"));
+        retain_setting(regex_filter, keep("regexFilter"));
+        retain_setting(field_languages, keep("fieldLanguages"));
+        retain_setting(min_prefix_length, keep("minPrefixLength"));
+        retain_setting(blocked_terms, keep("blockedTerms"));
+        retain_setting(new_fields, keep("newFields"));
+        retain_setting(query_rewrites, keep("queryRewrites"));
+        retain_setting(position_decay, keep("positionDecay"));
+        retain_setting(batching, keep("batching"));
+        retain_setting(collation, keep("collation"));
+        retain_setting(tie_breaker, keep("tieBreaker"));
+
+        Ok(())
+    }
+
     pub fn hide_secrets(&mut self) {
+        self.hide_document_hook_module();
+
         let Setting::Set(embedders) = &mut self.embedders else {
             return;
         };
@@ -223,6 +697,33 @@ impl<T> Settings<T> {
         }
     }
 
+    /// Replaces the module of the document hook by its digest and size, so that
+    /// the potentially very large module isn't echoed back or stored twice.
+    pub fn hide_document_hook_module(&mut self) {
+        let Setting::Set(hook) = &mut self.document_hook else {
+            return;
+        };
+
+        let Setting::Set(module) = std::mem::take(&mut hook.module) else {
+            return;
+        };
+
+        if hook.module_sha256.is_not_set() {
+            // an invalid module is rejected by `Settings::validate`
+            if let Ok(module) = BASE64_STANDARD.decode(module) {
+                hook.module_sha256 = Setting::Set(milli::document_hook::module_sha256(&module));
+                hook.module_size = Setting::Set(module.len() as u64);
+            }
+        }
+    }
+
+    /// Puts back the module of the document hook, e.g. when it was stored apart from the settings.
+    pub fn restore_document_hook_module(&mut self, module: &[u8]) {
+        if let Setting::Set(hook) = &mut self.document_hook {
+            hook.module = Setting::Set(BASE64_STANDARD.encode(module));
+        }
+    }
+
     fn hide_secret(secret: &mut String) {
         match secret.len() {
             x if x < 10 => {
@@ -248,19 +749,43 @@ impl Settings<Checked> {
             searchable_attributes: Setting::Reset.into(),
             filterable_attributes: Setting::Reset,
             sortable_attributes: Setting::Reset,
+            dense_sortable_attributes: Setting::Reset,
             ranking_rules: Setting::Reset,
             stop_words: Setting::Reset,
             synonyms: Setting::Reset,
             non_separator_tokens: Setting::Reset,
             separator_tokens: Setting::Reset,
             dictionary: Setting::Reset,
+            exact_words: Setting::Reset,
             distinct_attribute: Setting::Reset,
+            version_attribute: Setting::Reset,
             proximity_precision: Setting::Reset,
             typo_tolerance: Setting::Reset,
             faceting: Setting::Reset,
             pagination: Setting::Reset,
             embedders: Setting::Reset,
             search_cutoff_ms: Setting::Reset,
+            prefix_cache_size: Setting::Reset,
+            long_query_threshold: Setting::Reset,
+            quote_characters: Setting::Reset,
+            language_preset: Setting::Reset,
+            presence_only_attributes: Setting::Reset,
+            chunking: Setting::Reset,
+            document_hook: Setting::Reset,
+            computed_fields: Setting::Reset,
+            prepared_filters: Setting::Reset,
+            pii_attributes: Setting::Reset,
+            query_limits: Setting::Reset,
+            regex_filter: Setting::Reset,
+            field_languages: Setting::Reset,
+            min_prefix_length: Setting::Reset,
+            blocked_terms: Setting::Reset,
+            new_fields: Setting::Reset,
+            query_rewrites: Setting::Reset,
+            position_decay: Setting::Reset,
+            batching: Setting::Reset,
+            collation: Setting::Reset,
+            tie_breaker: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -271,19 +796,43 @@ impl Settings<Checked> {
             searchable_attributes,
             filterable_attributes,
             sortable_attributes,
+            dense_sortable_attributes,
             ranking_rules,
             stop_words,
             non_separator_tokens,
             separator_tokens,
             dictionary,
+            exact_words,
             synonyms,
             distinct_attribute,
+            version_attribute,
             proximity_precision,
             typo_tolerance,
             faceting,
             pagination,
             embedders,
             search_cutoff_ms,
+            prefix_cache_size,
+            long_query_threshold,
+            quote_characters,
+            language_preset,
+            presence_only_attributes,
+            chunking,
+            document_hook,
+            computed_fields,
+            prepared_filters,
+            pii_attributes,
+            query_limits,
+            regex_filter,
+            field_languages,
+            min_prefix_length,
+            blocked_terms,
+            new_fields,
+            query_rewrites,
+            position_decay,
+            batching,
+            collation,
+            tie_breaker,
             ..
         } = self;
 
@@ -292,19 +841,43 @@ impl Settings<Checked> {
             searchable_attributes,
             filterable_attributes,
             sortable_attributes,
+            dense_sortable_attributes,
             ranking_rules,
             stop_words,
             non_separator_tokens,
             separator_tokens,
             dictionary,
+            exact_words,
             synonyms,
             distinct_attribute,
+            version_attribute,
             proximity_precision,
             typo_tolerance,
             faceting,
             pagination,
             embedders,
             search_cutoff_ms,
+            prefix_cache_size,
+            long_query_threshold,
+            quote_characters,
+            language_preset,
+            presence_only_attributes,
+            chunking,
+            document_hook,
+            computed_fields,
+            prepared_filters,
+            pii_attributes,
+            query_limits,
+            regex_filter,
+            field_languages,
+            min_prefix_length,
+            blocked_terms,
+            new_fields,
+            query_rewrites,
+            position_decay,
+            batching,
+            collation,
+            tie_breaker,
             _kind: PhantomData,
         }
     }
@@ -325,7 +898,7 @@ impl Settings<Unchecked> {
 
         let searchable_attributes = match self.searchable_attributes.0 {
             Setting::Set(fields) => {
-                if fields.iter().any(|f| f == "*") {
+                if fields.iter().any(|f| f.attribute() == "*") {
                     Setting::Reset
                 } else {
                     Setting::Set(fields)
@@ -339,25 +912,63 @@ impl Settings<Unchecked> {
             searchable_attributes: searchable_attributes.into(),
             filterable_attributes: self.filterable_attributes,
             sortable_attributes: self.sortable_attributes,
+            dense_sortable_attributes: self.dense_sortable_attributes,
             ranking_rules: self.ranking_rules,
             stop_words: self.stop_words,
             synonyms: self.synonyms,
             non_separator_tokens: self.non_separator_tokens,
             separator_tokens: self.separator_tokens,
             dictionary: self.dictionary,
+            exact_words: self.exact_words,
             distinct_attribute: self.distinct_attribute,
+            version_attribute: self.version_attribute,
             proximity_precision: self.proximity_precision,
             typo_tolerance: self.typo_tolerance,
             faceting: self.faceting,
             pagination: self.pagination,
             embedders: self.embedders,
             search_cutoff_ms: self.search_cutoff_ms,
+            prefix_cache_size: self.prefix_cache_size,
+            long_query_threshold: self.long_query_threshold,
+            quote_characters: self.quote_characters,
+            language_preset: self.language_preset,
+            presence_only_attributes: self.presence_only_attributes,
+            chunking: self.chunking,
+            document_hook: self.document_hook,
+            computed_fields: self.computed_fields,
+            prepared_filters: self.prepared_filters,
+            pii_attributes: self.pii_attributes,
+            query_limits: self.query_limits,
+            regex_filter: self.regex_filter,
+            field_languages: self.field_languages,
+            min_prefix_length: self.min_prefix_length,
+            blocked_terms: self.blocked_terms,
+            new_fields: self.new_fields,
+            query_rewrites: self.query_rewrites,
+            position_decay: self.position_decay,
+            batching: self.batching,
+            collation: self.collation,
+            tie_breaker: self.tie_breaker,
             _kind: PhantomData,
         }
     }
 
     pub fn validate(self) -> Result<Self, milli::Error> {
-        self.validate_embedding_settings()
+        self.validate_embedding_settings()?.validate_document_hook_settings()
+    }
+
+    fn validate_document_hook_settings(self) -> Result<Self, milli::Error> {
+        if let Setting::Set(DocumentHookSettings { module: Setting::Set(module), .. }) =
+            &self.document_hook
+        {
+            if let Err(error) = BASE64_STANDARD.decode(module) {
+                return Err(milli::UserError::InvalidDocumentHook(format!(
+                    "The module must be base64 encoded: {error}"
+                ))
+                .into());
+            }
+        }
+        Ok(self)
     }
 
     fn validate_embedding_settings(mut self) -> Result<Self, milli::Error> {
@@ -389,24 +1000,53 @@ pub fn apply_settings_to_builder(
         searchable_attributes,
         filterable_attributes,
         sortable_attributes,
+        dense_sortable_attributes,
         ranking_rules,
         stop_words,
         non_separator_tokens,
         separator_tokens,
         dictionary,
+        exact_words,
         synonyms,
         distinct_attribute,
+        version_attribute,
         proximity_precision,
         typo_tolerance,
         faceting,
         pagination,
         embedders,
         search_cutoff_ms,
+        prefix_cache_size,
+        long_query_threshold,
+        quote_characters,
+        language_preset,
+        presence_only_attributes,
+        chunking,
+        document_hook,
+        computed_fields,
+        prepared_filters,
+        pii_attributes,
+        query_limits,
+        regex_filter,
+        field_languages,
+        min_prefix_length,
+        blocked_terms,
+        new_fields,
+        query_rewrites,
+        position_decay,
+        batching,
+        collation,
+        tie_breaker,
         _kind,
     } = settings;
 
     match searchable_attributes.deref() {
-        Setting::Set(ref names) => builder.set_searchable_fields(names.clone()),
+        Setting::Set(ref attributes) => builder.set_weighted_searchable_fields(
+            attributes
+                .iter()
+                .map(|attribute| (attribute.attribute().to_string(), attribute.weight()))
+                .collect(),
+        ),
         Setting::Reset => builder.reset_searchable_fields(),
         Setting::NotSet => (),
     }
@@ -431,6 +1071,14 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match dense_sortable_attributes {
+        Setting::Set(ref fields) => {
+            builder.set_dense_sortable_fields(fields.iter().cloned().collect())
+        }
+        Setting::Reset => builder.reset_dense_sortable_fields(),
+        Setting::NotSet => (),
+    }
+
     match ranking_rules {
         Setting::Set(ref criteria) => {
             builder.set_criteria(criteria.iter().map(|c| c.clone().into()).collect())
@@ -467,6 +1115,12 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match exact_words {
+        Setting::Set(ref exact_words) => builder.set_literal_words(exact_words.clone()),
+        Setting::Reset => builder.reset_literal_words(),
+        Setting::NotSet => (),
+    }
+
     match synonyms {
         Setting::Set(ref synonyms) => builder.set_synonyms(synonyms.clone().into_iter().collect()),
         Setting::Reset => builder.reset_synonyms(),
@@ -479,6 +1133,12 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match version_attribute {
+        Setting::Set(ref attr) => builder.set_version_field(attr.clone()),
+        Setting::Reset => builder.reset_version_field(),
+        Setting::NotSet => (),
+    }
+
     match proximity_precision {
         Setting::Set(ref precision) => builder.set_proximity_precision((*precision).into()),
         Setting::Reset => builder.reset_proximity_precision(),
@@ -528,6 +1188,70 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_exact_attributes(),
                 Setting::NotSet => (),
             }
+
+            match value.half_typos {
+                Setting::Set(ref setting) => {
+                    match setting.keyboard_layout {
+                        Setting::Set(val) => builder.set_half_typos_keyboard_layout(val.into()),
+                        Setting::Reset => builder.reset_half_typos_keyboard_layout(),
+                        Setting::NotSet => (),
+                    }
+                    match setting.accents {
+                        Setting::Set(val) => builder.set_half_typos_on_accents(val),
+                        Setting::Reset => builder.reset_half_typos_on_accents(),
+                        Setting::NotSet => (),
+                    }
+                }
+                Setting::Reset => {
+                    builder.reset_half_typos_keyboard_layout();
+                    builder.reset_half_typos_on_accents();
+                }
+                Setting::NotSet => (),
+            }
+
+            match value.disable_on_numbers {
+                Setting::Set(val) => builder.set_disable_typos_on_numbers(val),
+                Setting::Reset => builder.reset_disable_typos_on_numbers(),
+                Setting::NotSet => (),
+            }
+
+            match value.first_letter_typos {
+                Setting::Set(val) => builder.set_first_letter_typos(val.into()),
+                Setting::Reset => builder.reset_first_letter_typos(),
+                Setting::NotSet => (),
+            }
+
+            match value.enable_phonetic {
+                Setting::Set(val) => builder.set_enable_phonetic(val),
+                Setting::Reset => builder.reset_enable_phonetic(),
+                Setting::NotSet => (),
+            }
+
+            match value.typo_costs {
+                Setting::Set(ref setting) => {
+                    match setting.one_typo {
+                        Setting::Set(val) => builder.set_typo_cost_one_typo(val),
+                        Setting::Reset => builder.reset_typo_cost_one_typo(),
+                        Setting::NotSet => (),
+                    }
+                    match setting.two_typos {
+                        Setting::Set(val) => builder.set_typo_cost_two_typos(val),
+                        Setting::Reset => builder.reset_typo_cost_two_typos(),
+                        Setting::NotSet => (),
+                    }
+                    match setting.split {
+                        Setting::Set(val) => builder.set_typo_cost_split(val),
+                        Setting::Reset => builder.reset_typo_cost_split(),
+                        Setting::NotSet => (),
+                    }
+                }
+                Setting::Reset => {
+                    builder.reset_typo_cost_one_typo();
+                    builder.reset_typo_cost_two_typos();
+                    builder.reset_typo_cost_split();
+                }
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             // all typo settings need to be reset here.
@@ -536,12 +1260,24 @@ pub fn apply_settings_to_builder(
             builder.reset_min_word_len_two_typos();
             builder.reset_exact_words();
             builder.reset_exact_attributes();
+            builder.reset_half_typos_keyboard_layout();
+            builder.reset_half_typos_on_accents();
+            builder.reset_disable_typos_on_numbers();
+            builder.reset_first_letter_typos();
+            builder.reset_enable_phonetic();
+            builder.reset_typo_cost_one_typo();
+            builder.reset_typo_cost_two_typos();
+            builder.reset_typo_cost_split();
         }
         Setting::NotSet => (),
     }
 
     match faceting {
-        Setting::Set(FacetingSettings { max_values_per_facet, sort_facet_values_by }) => {
+        Setting::Set(FacetingSettings {
+            max_values_per_facet,
+            sort_facet_values_by,
+            value_aliases,
+        }) => {
             match max_values_per_facet {
                 Setting::Set(val) => builder.set_max_values_per_facet(*val),
                 Setting::Reset => builder.reset_max_values_per_facet(),
@@ -554,10 +1290,16 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_sort_facet_values_by(),
                 Setting::NotSet => (),
             }
+            match value_aliases {
+                Setting::Set(val) => builder.set_facet_value_aliases(val.clone()),
+                Setting::Reset => builder.reset_facet_value_aliases(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             builder.reset_max_values_per_facet();
             builder.reset_sort_facet_values_by();
+            builder.reset_facet_value_aliases();
         }
         Setting::NotSet => (),
     }
@@ -583,12 +1325,271 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_search_cutoff(),
         Setting::NotSet => (),
     }
-}
 
-pub enum SecretPolicy {
-    RevealSecrets,
-    HideSecrets,
-}
+    match prefix_cache_size {
+        Setting::Set(size) => builder.set_prefix_cache_size(*size),
+        Setting::Reset => builder.reset_prefix_cache_size(),
+        Setting::NotSet => (),
+    }
+
+    match long_query_threshold {
+        Setting::Set(threshold) => builder.set_long_query_threshold(*threshold),
+        Setting::Reset => builder.reset_long_query_threshold(),
+        Setting::NotSet => (),
+    }
+
+    match quote_characters {
+        Setting::Set(quotes) => builder.set_quote_characters(quotes.clone()),
+        Setting::Reset => builder.reset_quote_characters(),
+        Setting::NotSet => (),
+    }
+
+    match language_preset {
+        Setting::Set(preset) => builder.set_language_preset((*preset).into()),
+        Setting::Reset => builder.reset_language_preset(),
+        Setting::NotSet => (),
+    }
+
+    match presence_only_attributes {
+        Setting::Set(attrs) => {
+            builder.set_presence_only_attributes(attrs.iter().cloned().collect())
+        }
+        Setting::Reset => builder.reset_presence_only_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match chunking {
+        Setting::Set(value) => {
+            match value.max_words {
+                Setting::Set(max_words) => builder.set_chunking_max_words(max_words),
+                Setting::Reset => builder.reset_chunking_max_words(),
+                Setting::NotSet => (),
+            }
+            match value.overlap {
+                Setting::Set(overlap) => builder.set_chunking_overlap(overlap),
+                Setting::Reset => builder.reset_chunking_overlap(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_chunking_max_words();
+            builder.reset_chunking_overlap();
+        }
+        Setting::NotSet => (),
+    }
+
+    match document_hook {
+        Setting::Set(value) => {
+            match &value.module {
+                // an invalid module is rejected by `Settings::validate`
+                Setting::Set(module) => builder
+                    .set_document_hook_module(BASE64_STANDARD.decode(module).unwrap_or_default()),
+                Setting::Reset => builder.reset_document_hook_module(),
+                Setting::NotSet => (),
+            }
+            match value.fuel {
+                Setting::Set(fuel) => builder.set_document_hook_fuel(fuel),
+                Setting::Reset => builder.reset_document_hook_fuel(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_document_hook_module();
+            builder.reset_document_hook_fuel();
+        }
+        Setting::NotSet => (),
+    }
+
+    match computed_fields {
+        Setting::Set(computed_fields) => builder.set_computed_fields(computed_fields.clone()),
+        Setting::Reset => builder.reset_computed_fields(),
+        Setting::NotSet => (),
+    }
+
+    match prepared_filters {
+        Setting::Set(prepared_filters) => builder.set_prepared_filters(prepared_filters.clone()),
+        Setting::Reset => builder.reset_prepared_filters(),
+        Setting::NotSet => (),
+    }
+
+    match pii_attributes {
+        Setting::Set(pii_attributes) => builder.set_pii_attributes(pii_attributes.clone()),
+        Setting::Reset => builder.reset_pii_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match query_limits {
+        Setting::Set(value) => {
+            match value.max_length {
+                Setting::Set(max_length) => builder.set_query_max_length(max_length),
+                Setting::Reset => builder.reset_query_max_length(),
+                Setting::NotSet => (),
+            }
+            match value.max_terms {
+                Setting::Set(max_terms) => builder.set_query_max_terms(max_terms),
+                Setting::Reset => builder.reset_query_max_terms(),
+                Setting::NotSet => (),
+            }
+            match value.max_phrases {
+                Setting::Set(max_phrases) => builder.set_query_max_phrases(max_phrases),
+                Setting::Reset => builder.reset_query_max_phrases(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_query_max_length();
+            builder.reset_query_max_terms();
+            builder.reset_query_max_phrases();
+        }
+        Setting::NotSet => (),
+    }
+
+    match regex_filter {
+        Setting::Set(value) => {
+            match value.enabled {
+                Setting::Set(enabled) => builder.set_regex_filter_enabled(enabled),
+                Setting::Reset => builder.reset_regex_filter_enabled(),
+                Setting::NotSet => (),
+            }
+            match value.max_candidates {
+                Setting::Set(max_candidates) => {
+                    builder.set_regex_filter_max_candidates(max_candidates)
+                }
+                Setting::Reset => builder.reset_regex_filter_max_candidates(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_regex_filter_enabled();
+            builder.reset_regex_filter_max_candidates();
+        }
+        Setting::NotSet => (),
+    }
+
+    match field_languages {
+        Setting::Set(field_languages) => builder.set_field_languages(
+            field_languages
+                .iter()
+                .map(|(field, language)| (field.clone(), (*language).into()))
+                .collect(),
+        ),
+        Setting::Reset => builder.reset_field_languages(),
+        Setting::NotSet => (),
+    }
+
+    match min_prefix_length {
+        Setting::Set(length) => builder.set_min_prefix_length(*length),
+        Setting::Reset => builder.reset_min_prefix_length(),
+        Setting::NotSet => (),
+    }
+
+    match blocked_terms {
+        Setting::Set(value) => {
+            match &value.terms {
+                Setting::Set(terms) => builder.set_blocked_terms(terms.clone()),
+                Setting::Reset => builder.reset_blocked_terms(),
+                Setting::NotSet => (),
+            }
+            match value.mode {
+                Setting::Set(mode) => builder.set_blocked_terms_mode(mode.into()),
+                Setting::Reset => builder.reset_blocked_terms_mode(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_blocked_terms();
+            builder.reset_blocked_terms_mode();
+        }
+        Setting::NotSet => (),
+    }
+
+    match new_fields {
+        Setting::Set(value) => {
+            match value.searchable {
+                Setting::Set(searchable) => builder.set_new_fields_searchable(searchable),
+                Setting::Reset => builder.reset_new_fields_searchable(),
+                Setting::NotSet => (),
+            }
+            match value.filterable {
+                Setting::Set(filterable) => builder.set_new_fields_filterable(filterable),
+                Setting::Reset => builder.reset_new_fields_filterable(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_new_fields_searchable();
+            builder.reset_new_fields_filterable();
+        }
+        Setting::NotSet => (),
+    }
+
+    match query_rewrites {
+        Setting::Set(rules) => {
+            builder.set_query_rewrites(rules.iter().cloned().map(Into::into).collect())
+        }
+        Setting::Reset => builder.reset_query_rewrites(),
+        Setting::NotSet => (),
+    }
+
+    match position_decay {
+        Setting::Set(decay) => builder.set_position_decay(decay.into()),
+        Setting::Reset => builder.reset_position_decay(),
+        Setting::NotSet => (),
+    }
+
+    match batching {
+        Setting::Set(value) => {
+            match value.max_batch_size {
+                Setting::Set(size) => builder.set_max_batch_size(size),
+                Setting::Reset => builder.reset_max_batch_size(),
+                Setting::NotSet => (),
+            }
+            match value.max_batch_payload {
+                Setting::Set(payload) => builder.set_max_batch_payload(payload),
+                Setting::Reset => builder.reset_max_batch_payload(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_max_batch_size();
+            builder.reset_max_batch_payload();
+        }
+        Setting::NotSet => (),
+    }
+
+    match collation {
+        Setting::Set(value) => {
+            match value.locale {
+                Setting::Set(locale) => builder.set_collation_locale(locale.into()),
+                Setting::Reset => builder.reset_collation_locale(),
+                Setting::NotSet => (),
+            }
+            match value.attributes {
+                Setting::Set(attributes) => builder.set_collation_attributes(
+                    attributes.into_iter().map(|(name, locale)| (name, locale.into())).collect(),
+                ),
+                Setting::Reset => builder.reset_collation_attributes(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_collation_locale();
+            builder.reset_collation_attributes();
+        }
+        Setting::NotSet => (),
+    }
+
+    match tie_breaker {
+        Setting::Set(value) => builder.set_tie_breaker(value.into()),
+        Setting::Reset => builder.reset_tie_breaker(),
+        Setting::NotSet => (),
+    }
+}
+
+pub enum SecretPolicy {
+    RevealSecrets,
+    HideSecrets,
+}
 
 pub fn settings(
     index: &Index,
@@ -598,14 +1599,26 @@ pub fn settings(
     let displayed_attributes =
         index.displayed_fields(rtxn)?.map(|fields| fields.into_iter().map(String::from).collect());
 
-    let searchable_attributes = index
-        .user_defined_searchable_fields(rtxn)?
-        .map(|fields| fields.into_iter().map(String::from).collect());
+    let searchable_weights = index.searchable_weights(rtxn)?;
+    let searchable_attributes = index.user_defined_searchable_fields(rtxn)?.map(|fields| {
+        fields
+            .into_iter()
+            .map(|field| match searchable_weights.get(field) {
+                Some(&weight) => SearchableAttribute::Weighted(WeightedSearchableAttribute {
+                    attribute: field.to_string(),
+                    weight,
+                }),
+                None => SearchableAttribute::Name(field.to_string()),
+            })
+            .collect()
+    });
 
     let filterable_attributes = index.filterable_fields(rtxn)?.into_iter().collect();
 
     let sortable_attributes = index.sortable_fields(rtxn)?.into_iter().collect();
 
+    let dense_sortable_attributes = index.dense_sortable_fields(rtxn)?.into_iter().collect();
+
     let criteria = index.criteria(rtxn)?;
 
     let stop_words = index
@@ -619,9 +1632,12 @@ pub fn settings(
     let non_separator_tokens = index.non_separator_tokens(rtxn)?.unwrap_or_default();
     let separator_tokens = index.separator_tokens(rtxn)?.unwrap_or_default();
     let dictionary = index.dictionary(rtxn)?.unwrap_or_default();
+    let exact_words = index.literal_words(rtxn)?.unwrap_or_default();
 
     let distinct_field = index.distinct_field(rtxn)?.map(String::from);
 
+    let version_field = index.version_field(rtxn)?.map(String::from);
+
     let proximity_precision = index.proximity_precision(rtxn)?.map(ProximityPrecisionView::from);
 
     let synonyms = index.user_defined_synonyms(rtxn)?;
@@ -638,11 +1654,51 @@ pub fn settings(
 
     let disabled_attributes = index.exact_attributes(rtxn)?.into_iter().map(String::from).collect();
 
+    let half_typos = match index.half_typos(rtxn)? {
+        Some(half_typos) => Setting::Set(HalfTyposSetting {
+            keyboard_layout: match half_typos.keyboard_layout {
+                Some(layout) => Setting::Set(layout.into()),
+                None => Setting::Reset,
+            },
+            accents: Setting::Set(half_typos.accents),
+        }),
+        None => Setting::NotSet,
+    };
+
+    let disable_on_numbers = match index.disable_typos_on_numbers(rtxn)? {
+        true => Setting::Set(true),
+        false => Setting::NotSet,
+    };
+
+    let first_letter_typos = match index.first_letter_typos(rtxn)? {
+        FirstLetterTypos::CountAsTwo => Setting::NotSet,
+        first_letter_typos => Setting::Set(first_letter_typos.into()),
+    };
+
+    let enable_phonetic = match index.enable_phonetic(rtxn)? {
+        true => Setting::Set(true),
+        false => Setting::NotSet,
+    };
+
+    let typo_costs = match index.typo_costs(rtxn)? {
+        typo_costs if typo_costs == TypoCosts::default() => Setting::NotSet,
+        typo_costs => Setting::Set(TypoCostsSetting {
+            one_typo: Setting::Set(typo_costs.one_typo),
+            two_typos: Setting::Set(typo_costs.two_typos),
+            split: Setting::Set(typo_costs.split),
+        }),
+    };
+
     let typo_tolerance = TypoSettings {
         enabled: Setting::Set(index.authorize_typos(rtxn)?),
         min_word_size_for_typos: Setting::Set(min_typo_word_len),
         disable_on_words: Setting::Set(disabled_words),
         disable_on_attributes: Setting::Set(disabled_attributes),
+        half_typos,
+        disable_on_numbers,
+        first_letter_typos,
+        enable_phonetic,
+        typo_costs,
     };
 
     let faceting = FacetingSettings {
@@ -659,6 +1715,10 @@ pub fn settings(
                 .map(|(name, sort)| (name, sort.into()))
                 .collect(),
         ),
+        value_aliases: match index.facet_value_aliases(rtxn)? {
+            aliases if aliases.is_empty() => Setting::NotSet,
+            aliases => Setting::Set(aliases),
+        },
     };
 
     let pagination = PaginationSettings {
@@ -679,6 +1739,52 @@ pub fn settings(
 
     let search_cutoff_ms = index.search_cutoff(rtxn)?;
 
+    let prefix_cache_size = index.prefix_cache_size(rtxn)?.unwrap_or(0) as usize;
+
+    let long_query_threshold = index.long_query_threshold(rtxn)?;
+
+    let quote_characters = index.quote_characters(rtxn)?;
+
+    let language_preset = index.language_preset(rtxn)?;
+
+    let presence_only_attributes =
+        index.presence_only_attributes(rtxn)?.into_iter().map(String::from).collect();
+
+    let chunking = index.chunking(rtxn)?;
+
+    let document_hook = index.document_hook(rtxn)?;
+
+    let computed_fields = index.computed_fields(rtxn)?;
+
+    let prepared_filters = index.prepared_filters(rtxn)?;
+
+    let pii_attributes = index.pii_attributes(rtxn)?;
+
+    let query_limits = index.query_limits(rtxn)?;
+
+    let regex_filter = index.regex_filter(rtxn)?;
+
+    let field_languages = index
+        .field_languages(rtxn)?
+        .into_iter()
+        .map(|(field, language)| (field, language.into()))
+        .collect();
+
+    let min_prefix_length = index.min_prefix_length(rtxn)?;
+
+    let blocked_terms = index.blocked_terms(rtxn)?;
+
+    let new_fields = index.new_fields_policy(rtxn)?;
+
+    let query_rewrites = index.query_rewrites(rtxn)?.into_iter().map(Into::into).collect();
+
+    let position_decay = index.position_decay(rtxn)?.into();
+
+    let batch_limits = index.batch_limits(rtxn)?;
+
+    let SortCollation { locale: collation_locale, attributes: collation_attributes } =
+        index.sort_collation(rtxn)?;
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -692,15 +1798,21 @@ pub fn settings(
         .into(),
         filterable_attributes: Setting::Set(filterable_attributes),
         sortable_attributes: Setting::Set(sortable_attributes),
+        dense_sortable_attributes: Setting::Set(dense_sortable_attributes),
         ranking_rules: Setting::Set(criteria.iter().map(|c| c.clone().into()).collect()),
         stop_words: Setting::Set(stop_words),
         non_separator_tokens: Setting::Set(non_separator_tokens),
         separator_tokens: Setting::Set(separator_tokens),
         dictionary: Setting::Set(dictionary),
+        exact_words: Setting::Set(exact_words),
         distinct_attribute: match distinct_field {
             Some(field) => Setting::Set(field),
             None => Setting::Reset,
         },
+        version_attribute: match version_field {
+            Some(field) => Setting::Set(field),
+            None => Setting::Reset,
+        },
         proximity_precision: Setting::Set(proximity_precision.unwrap_or_default()),
         synonyms: Setting::Set(synonyms),
         typo_tolerance: Setting::Set(typo_tolerance),
@@ -711,6 +1823,83 @@ pub fn settings(
             Some(cutoff) => Setting::Set(cutoff),
             None => Setting::Reset,
         },
+        prefix_cache_size: Setting::Set(prefix_cache_size),
+        long_query_threshold: match long_query_threshold {
+            Some(threshold) => Setting::Set(threshold as usize),
+            None => Setting::Reset,
+        },
+        quote_characters: Setting::Set(quote_characters),
+        language_preset: match language_preset {
+            Some(preset) => Setting::Set(preset.into()),
+            None => Setting::Reset,
+        },
+        presence_only_attributes: Setting::Set(presence_only_attributes),
+        chunking: match chunking {
+            Some(chunking) => Setting::Set(ChunkingSettings {
+                max_words: Setting::Set(chunking.max_words),
+                overlap: Setting::Set(chunking.overlap),
+            }),
+            None => Setting::Reset,
+        },
+        document_hook: match document_hook {
+            Some(hook) => Setting::Set(DocumentHookSettings {
+                module_sha256: Setting::Set(milli::document_hook::module_sha256(&hook.module)),
+                module_size: Setting::Set(hook.module.len() as u64),
+                module: Setting::Set(BASE64_STANDARD.encode(hook.module)),
+                fuel: Setting::Set(hook.fuel),
+            }),
+            None => Setting::Reset,
+        },
+        computed_fields: Setting::Set(computed_fields),
+        prepared_filters: Setting::Set(prepared_filters),
+        pii_attributes: Setting::Set(pii_attributes),
+        query_limits: Setting::Set(QueryLimitsSettings {
+            max_length: query_limits.max_length.map_or(Setting::Reset, Setting::Set),
+            max_terms: Setting::Set(query_limits.max_terms.unwrap_or(DEFAULT_MAX_QUERY_TERMS)),
+            max_phrases: query_limits.max_phrases.map_or(Setting::Reset, Setting::Set),
+        }),
+        regex_filter: Setting::Set(RegexFilterSettings {
+            enabled: Setting::Set(regex_filter.enabled),
+            max_candidates: Setting::Set(regex_filter.max_candidates()),
+        }),
+        field_languages: Setting::Set(field_languages),
+        min_prefix_length: Setting::Set(min_prefix_length as usize),
+        blocked_terms: Setting::Set(BlockedTermsSettings {
+            terms: Setting::Set(blocked_terms.terms),
+            mode: Setting::Set(blocked_terms.mode.into()),
+        }),
+        new_fields: Setting::Set(NewFieldsSettings {
+            searchable: Setting::Set(new_fields.searchable),
+            filterable: Setting::Set(new_fields.filterable),
+        }),
+        query_rewrites: Setting::Set(query_rewrites),
+        position_decay: Setting::Set(position_decay),
+        batching: Setting::Set(BatchingSettings {
+            max_batch_size: match batch_limits.max_batch_size {
+                Some(size) => Setting::Set(size),
+                None => Setting::Reset,
+            },
+            max_batch_payload: match batch_limits.max_batch_payload {
+                Some(payload) => Setting::Set(payload),
+                None => Setting::Reset,
+            },
+        }),
+        collation: Setting::Set(CollationSettings {
+            locale: match collation_locale {
+                Some(locale) => Setting::Set(locale.into()),
+                None => Setting::Reset,
+            },
+            attributes: Setting::Set(
+                collation_attributes
+                    .into_iter()
+                    .map(|(name, locale)| (name, locale.into()))
+                    .collect(),
+            ),
+        }),
+        tie_breaker: match index.tie_breaker(rtxn)? {
+            Some(tie_breaker) => Setting::Set(tie_breaker.into()),
+            None => Setting::Reset,
+        },
         _kind: PhantomData,
     };
 
@@ -721,6 +1910,113 @@ pub fn settings(
     Ok(settings)
 }
 
+/// The settings of an index as they are applied by the engine.
+///
+/// Unlike [`settings`], every default is materialized and the values derived from the
+/// settings and the documents, like the searchable fields ids, are exposed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveSettings {
+    #[serde(flatten)]
+    pub settings: Settings<Checked>,
+    /// The fields that are searched, in order of importance.
+    pub searchable_fields: Vec<EffectiveSearchableField>,
+    /// The fields stored in the facet databases, that is, the filterable and sortable fields.
+    pub faceted_fields: BTreeSet<String>,
+    pub geo: EffectiveGeoSettings,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveSearchableField {
+    pub name: String,
+    pub field_id: FieldId,
+    pub weight: Weight,
+}
+
+/// The geosearch features enabled by having `_geo` in the filterable or sortable attributes.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveGeoSettings {
+    /// `_geoRadius` and `_geoBoundingBox` can be used in filters.
+    pub filterable: bool,
+    /// `_geoPoint` can be used in sort.
+    pub sortable: bool,
+}
+
+pub fn effective_settings(
+    index: &Index,
+    rtxn: &crate::heed::RoTxn,
+) -> Result<EffectiveSettings, milli::Error> {
+    let mut settings = settings(index, rtxn, SecretPolicy::HideSecrets)?;
+
+    let searchable_fields: Vec<_> = index
+        .searchable_fields_and_weights(rtxn)?
+        .into_iter()
+        .map(|(name, field_id, weight)| EffectiveSearchableField {
+            name: name.into_owned(),
+            field_id,
+            weight,
+        })
+        .collect();
+
+    if let Setting::Reset = *settings.displayed_attributes {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        settings.displayed_attributes =
+            Setting::Set(fields_ids_map.names().map(String::from).collect()).into();
+    }
+    if let Setting::Reset = *settings.searchable_attributes {
+        settings.searchable_attributes = Setting::Set(
+            searchable_fields.iter().map(|field| field.name.as_str().into()).collect(),
+        )
+        .into();
+    }
+    if let Setting::Set(typo_tolerance) = &mut settings.typo_tolerance {
+        if let Setting::NotSet = typo_tolerance.half_typos {
+            typo_tolerance.half_typos = Setting::Set(HalfTyposSetting {
+                keyboard_layout: Setting::Reset,
+                accents: Setting::Set(false),
+            });
+        }
+        if let Setting::NotSet = typo_tolerance.disable_on_numbers {
+            typo_tolerance.disable_on_numbers = Setting::Set(false);
+        }
+        if let Setting::NotSet = typo_tolerance.first_letter_typos {
+            typo_tolerance.first_letter_typos = Setting::Set(FirstLetterTyposView::CountAsTwo);
+        }
+        if let Setting::NotSet = typo_tolerance.enable_phonetic {
+            typo_tolerance.enable_phonetic = Setting::Set(false);
+        }
+        if let Setting::NotSet = typo_tolerance.typo_costs {
+            let default = TypoCosts::default();
+            typo_tolerance.typo_costs = Setting::Set(TypoCostsSetting {
+                one_typo: Setting::Set(default.one_typo),
+                two_typos: Setting::Set(default.two_typos),
+                split: Setting::Set(default.split),
+            });
+        }
+    }
+    if let Setting::Set(faceting) = &mut settings.faceting {
+        if let Setting::NotSet = faceting.value_aliases {
+            faceting.value_aliases = Setting::Set(BTreeMap::new());
+        }
+    }
+    if let Setting::NotSet = settings.embedders {
+        settings.embedders = Setting::Set(BTreeMap::new());
+    }
+    if let Setting::Reset = settings.search_cutoff_ms {
+        settings.search_cutoff_ms = Setting::Set(DEFAULT_SEARCH_CUTOFF_MS);
+    }
+
+    let faceted_fields = index.faceted_fields(rtxn)?.into_iter().collect();
+    let geo = EffectiveGeoSettings {
+        filterable: index.filterable_fields(rtxn)?.contains("_geo"),
+        sortable: index.sortable_fields(rtxn)?.contains("_geo"),
+    };
+
+    Ok(EffectiveSettings { settings, searchable_fields, faceted_fields, geo })
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserr)]
 #[deserr(try_from(&String) = FromStr::from_str -> CriterionError)]
 pub enum RankingRuleView {
@@ -743,6 +2039,14 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the buckets of the ranking rule plugin registered under the name specified.
+    Plugin(String),
+    /// Sorted by the decreasing recency of the date field specified, decaying with the half-life.
+    Recency { field: String, half_life: HalfLife },
+    /// Sorted by the decreasing boost of the field specified, decaying with the distance from the origin.
+    Decay { field: String, scale: DecayScale, function: DecayFunction, origin: DecayOrigin },
+    /// Sorted by the decreasing BM25 score of the query terms.
+    Bm25,
 }
 impl Serialize for RankingRuleView {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -799,6 +2103,14 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::Plugin(x) => RankingRuleView::Plugin(x),
+            Criterion::Recency { field, half_life } => {
+                RankingRuleView::Recency { field, half_life }
+            }
+            Criterion::Decay { field, scale, function, origin } => {
+                RankingRuleView::Decay { field, scale, function, origin }
+            }
+            Criterion::Bm25 => RankingRuleView::Bm25,
         }
     }
 }
@@ -813,6 +2125,14 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::Plugin(x) => Criterion::Plugin(x),
+            RankingRuleView::Recency { field, half_life } => {
+                Criterion::Recency { field, half_life }
+            }
+            RankingRuleView::Decay { field, scale, function, origin } => {
+                Criterion::Decay { field, scale, function, origin }
+            }
+            RankingRuleView::Bm25 => Criterion::Bm25,
         }
     }
 }
@@ -843,16 +2163,423 @@ impl From<ProximityPrecisionView> for ProximityPrecision {
     }
 }
 
-#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
-pub struct WildcardSetting(Setting<Vec<String>>);
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsPositionDecay>, rename_all = camelCase, deny_unknown_fields)]
+pub enum PositionDecayView {
+    #[default]
+    Log,
+    Linear,
+    None,
+}
+
+impl From<PositionDecay> for PositionDecayView {
+    fn from(value: PositionDecay) -> Self {
+        match value {
+            PositionDecay::Log => PositionDecayView::Log,
+            PositionDecay::Linear => PositionDecayView::Linear,
+            PositionDecay::None => PositionDecayView::None,
+        }
+    }
+}
+impl From<PositionDecayView> for PositionDecay {
+    fn from(value: PositionDecayView) -> Self {
+        match value {
+            PositionDecayView::Log => PositionDecay::Log,
+            PositionDecayView::Linear => PositionDecay::Linear,
+            PositionDecayView::None => PositionDecay::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+pub enum LanguagePresetView {
+    #[serde(rename = "en")]
+    #[deserr(rename = "en")]
+    English,
+    #[serde(rename = "fr")]
+    #[deserr(rename = "fr")]
+    French,
+    #[serde(rename = "de")]
+    #[deserr(rename = "de")]
+    German,
+    #[serde(rename = "es")]
+    #[deserr(rename = "es")]
+    Spanish,
+    #[serde(rename = "ja")]
+    #[deserr(rename = "ja")]
+    Japanese,
+    #[serde(rename = "zh")]
+    #[deserr(rename = "zh")]
+    Chinese,
+    #[serde(rename = "ko")]
+    #[deserr(rename = "ko")]
+    Korean,
+    #[serde(rename = "he")]
+    #[deserr(rename = "he")]
+    Hebrew,
+    #[serde(rename = "th")]
+    #[deserr(rename = "th")]
+    Thai,
+}
+
+impl From<LanguagePreset> for LanguagePresetView {
+    fn from(value: LanguagePreset) -> Self {
+        match value {
+            LanguagePreset::English => LanguagePresetView::English,
+            LanguagePreset::French => LanguagePresetView::French,
+            LanguagePreset::German => LanguagePresetView::German,
+            LanguagePreset::Spanish => LanguagePresetView::Spanish,
+            LanguagePreset::Japanese => LanguagePresetView::Japanese,
+            LanguagePreset::Chinese => LanguagePresetView::Chinese,
+            LanguagePreset::Korean => LanguagePresetView::Korean,
+            LanguagePreset::Hebrew => LanguagePresetView::Hebrew,
+            LanguagePreset::Thai => LanguagePresetView::Thai,
+        }
+    }
+}
+impl From<LanguagePresetView> for LanguagePreset {
+    fn from(value: LanguagePresetView) -> Self {
+        match value {
+            LanguagePresetView::English => LanguagePreset::English,
+            LanguagePresetView::French => LanguagePreset::French,
+            LanguagePresetView::German => LanguagePreset::German,
+            LanguagePresetView::Spanish => LanguagePreset::Spanish,
+            LanguagePresetView::Japanese => LanguagePreset::Japanese,
+            LanguagePresetView::Chinese => LanguagePreset::Chinese,
+            LanguagePresetView::Korean => LanguagePreset::Korean,
+            LanguagePresetView::Hebrew => LanguagePreset::Hebrew,
+            LanguagePresetView::Thai => LanguagePreset::Thai,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+pub enum CollationLocaleView {
+    #[serde(rename = "en")]
+    #[deserr(rename = "en")]
+    English,
+    #[serde(rename = "fr")]
+    #[deserr(rename = "fr")]
+    French,
+    #[serde(rename = "de")]
+    #[deserr(rename = "de")]
+    German,
+    #[serde(rename = "es")]
+    #[deserr(rename = "es")]
+    Spanish,
+    #[serde(rename = "sv")]
+    #[deserr(rename = "sv")]
+    Swedish,
+    #[serde(rename = "fi")]
+    #[deserr(rename = "fi")]
+    Finnish,
+    #[serde(rename = "da")]
+    #[deserr(rename = "da")]
+    Danish,
+    #[serde(rename = "nb")]
+    #[deserr(rename = "nb")]
+    Norwegian,
+}
+
+impl From<CollationLocale> for CollationLocaleView {
+    fn from(value: CollationLocale) -> Self {
+        match value {
+            CollationLocale::English => CollationLocaleView::English,
+            CollationLocale::French => CollationLocaleView::French,
+            CollationLocale::German => CollationLocaleView::German,
+            CollationLocale::Spanish => CollationLocaleView::Spanish,
+            CollationLocale::Swedish => CollationLocaleView::Swedish,
+            CollationLocale::Finnish => CollationLocaleView::Finnish,
+            CollationLocale::Danish => CollationLocaleView::Danish,
+            CollationLocale::Norwegian => CollationLocaleView::Norwegian,
+        }
+    }
+}
+impl From<CollationLocaleView> for CollationLocale {
+    fn from(value: CollationLocaleView) -> Self {
+        match value {
+            CollationLocaleView::English => CollationLocale::English,
+            CollationLocaleView::French => CollationLocale::French,
+            CollationLocaleView::German => CollationLocale::German,
+            CollationLocaleView::Spanish => CollationLocale::Spanish,
+            CollationLocaleView::Swedish => CollationLocale::Swedish,
+            CollationLocaleView::Finnish => CollationLocale::Finnish,
+            CollationLocaleView::Danish => CollationLocale::Danish,
+            CollationLocaleView::Norwegian => CollationLocale::Norwegian,
+        }
+    }
+}
+
+/// The order of the documents that all the ranking rules consider equal, either
+/// `"primaryKeyAsc"`, `"primaryKeyDesc"` or a sortable attribute along with its order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TieBreakerView {
+    PrimaryKey(PrimaryKeyTieBreakerView),
+    Field(FieldTieBreakerView),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+pub enum PrimaryKeyTieBreakerView {
+    #[serde(rename = "primaryKeyAsc")]
+    #[deserr(rename = "primaryKeyAsc")]
+    Asc,
+    #[serde(rename = "primaryKeyDesc")]
+    #[deserr(rename = "primaryKeyDesc")]
+    Desc,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(deny_unknown_fields, rename_all = camelCase)]
+pub struct FieldTieBreakerView {
+    pub field: String,
+    #[serde(default)]
+    #[deserr(default)]
+    pub order: TieBreakerOrderView,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum TieBreakerOrderView {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl<E: DeserializeError> Deserr<E> for TieBreakerView {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::String(_) => {
+                PrimaryKeyTieBreakerView::deserialize_from_value(value, location)
+                    .map(TieBreakerView::PrimaryKey)
+            }
+            deserr::Value::Map(_) => FieldTieBreakerView::deserialize_from_value(value, location)
+                .map(TieBreakerView::Field),
+            _ => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[deserr::ValueKind::String, deserr::ValueKind::Map],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+impl From<TieBreaker> for TieBreakerView {
+    fn from(value: TieBreaker) -> Self {
+        match value {
+            TieBreaker::PrimaryKey { ascending: true } => {
+                TieBreakerView::PrimaryKey(PrimaryKeyTieBreakerView::Asc)
+            }
+            TieBreaker::PrimaryKey { ascending: false } => {
+                TieBreakerView::PrimaryKey(PrimaryKeyTieBreakerView::Desc)
+            }
+            TieBreaker::Field { field, ascending } => {
+                let order =
+                    if ascending { TieBreakerOrderView::Asc } else { TieBreakerOrderView::Desc };
+                TieBreakerView::Field(FieldTieBreakerView { field, order })
+            }
+        }
+    }
+}
+impl From<TieBreakerView> for TieBreaker {
+    fn from(value: TieBreakerView) -> Self {
+        match value {
+            TieBreakerView::PrimaryKey(order) => {
+                TieBreaker::PrimaryKey { ascending: order == PrimaryKeyTieBreakerView::Asc }
+            }
+            TieBreakerView::Field(FieldTieBreakerView { field, order }) => {
+                TieBreaker::Field { field, ascending: order == TieBreakerOrderView::Asc }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsTypoTolerance>, rename_all = camelCase, deny_unknown_fields)]
+pub enum KeyboardLayoutView {
+    Qwerty,
+    Azerty,
+    Qwertz,
+}
+
+impl From<KeyboardLayout> for KeyboardLayoutView {
+    fn from(value: KeyboardLayout) -> Self {
+        match value {
+            KeyboardLayout::Qwerty => KeyboardLayoutView::Qwerty,
+            KeyboardLayout::Azerty => KeyboardLayoutView::Azerty,
+            KeyboardLayout::Qwertz => KeyboardLayoutView::Qwertz,
+        }
+    }
+}
+impl From<KeyboardLayoutView> for KeyboardLayout {
+    fn from(value: KeyboardLayoutView) -> Self {
+        match value {
+            KeyboardLayoutView::Qwerty => KeyboardLayout::Qwerty,
+            KeyboardLayoutView::Azerty => KeyboardLayout::Azerty,
+            KeyboardLayoutView::Qwertz => KeyboardLayout::Qwertz,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsTypoTolerance>, rename_all = camelCase, deny_unknown_fields)]
+pub enum FirstLetterTyposView {
+    CountAsTwo,
+    Penalized,
+    Disallowed,
+}
+
+impl From<FirstLetterTypos> for FirstLetterTyposView {
+    fn from(value: FirstLetterTypos) -> Self {
+        match value {
+            FirstLetterTypos::CountAsTwo => FirstLetterTyposView::CountAsTwo,
+            FirstLetterTypos::Penalized => FirstLetterTyposView::Penalized,
+            FirstLetterTypos::Disallowed => FirstLetterTyposView::Disallowed,
+        }
+    }
+}
+impl From<FirstLetterTyposView> for FirstLetterTypos {
+    fn from(value: FirstLetterTyposView) -> Self {
+        match value {
+            FirstLetterTyposView::CountAsTwo => FirstLetterTypos::CountAsTwo,
+            FirstLetterTyposView::Penalized => FirstLetterTypos::Penalized,
+            FirstLetterTyposView::Disallowed => FirstLetterTypos::Disallowed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserr, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsBlockedTerms>, rename_all = camelCase, deny_unknown_fields)]
+pub enum BlockedTermsModeView {
+    Drop,
+    Reject,
+}
+
+impl From<BlockedTermsMode> for BlockedTermsModeView {
+    fn from(value: BlockedTermsMode) -> Self {
+        match value {
+            BlockedTermsMode::Drop => BlockedTermsModeView::Drop,
+            BlockedTermsMode::Reject => BlockedTermsModeView::Reject,
+        }
+    }
+}
+impl From<BlockedTermsModeView> for BlockedTermsMode {
+    fn from(value: BlockedTermsModeView) -> Self {
+        match value {
+            BlockedTermsModeView::Drop => BlockedTermsMode::Drop,
+            BlockedTermsModeView::Reject => BlockedTermsMode::Reject,
+        }
+    }
+}
+
+/// The maximum weight of a searchable attribute.
+pub const MAX_SEARCHABLE_ATTRIBUTE_WEIGHT: Weight = 100;
+
+/// A searchable attribute, either its name alone or along with the weight scaling
+/// its importance in the attribute ranking rule.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SearchableAttribute {
+    Name(String),
+    Weighted(WeightedSearchableAttribute),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(deny_unknown_fields, rename_all = camelCase)]
+pub struct WeightedSearchableAttribute {
+    pub attribute: String,
+    /// The cost of matching in the attribute is divided by its weight,
+    /// the attributes without any weight have a weight of 1.
+    pub weight: Weight,
+}
+
+impl SearchableAttribute {
+    pub fn attribute(&self) -> &str {
+        match self {
+            SearchableAttribute::Name(name) => name,
+            SearchableAttribute::Weighted(weighted) => &weighted.attribute,
+        }
+    }
+
+    pub fn weight(&self) -> Option<Weight> {
+        match self {
+            SearchableAttribute::Name(_) => None,
+            SearchableAttribute::Weighted(weighted) => Some(weighted.weight),
+        }
+    }
+}
+
+impl From<&str> for SearchableAttribute {
+    fn from(name: &str) -> Self {
+        SearchableAttribute::Name(name.to_string())
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for SearchableAttribute {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::String(name) => Ok(SearchableAttribute::Name(name)),
+            deserr::Value::Map(_) => {
+                let weighted =
+                    WeightedSearchableAttribute::deserialize_from_value(value, location)?;
+                if !(1..=MAX_SEARCHABLE_ATTRIBUTE_WEIGHT).contains(&weighted.weight) {
+                    return Err(deserr::take_cf_content(E::error::<Infallible>(
+                        None,
+                        ErrorKind::Unexpected {
+                            msg: format!(
+                                "the weight of `{}` should be between `1` and `{MAX_SEARCHABLE_ATTRIBUTE_WEIGHT}` but found `{}`.",
+                                weighted.attribute, weighted.weight
+                            ),
+                        },
+                        location,
+                    )));
+                }
+                Ok(SearchableAttribute::Weighted(weighted))
+            }
+            _ => Err(deserr::take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[deserr::ValueKind::String, deserr::ValueKind::Map],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct WildcardSetting<T = String>(Setting<Vec<T>>);
+
+impl<T> Default for WildcardSetting<T> {
+    fn default() -> Self {
+        Self(Setting::NotSet)
+    }
+}
 
-impl From<Setting<Vec<String>>> for WildcardSetting {
-    fn from(setting: Setting<Vec<String>>) -> Self {
+impl<T> From<Setting<Vec<T>>> for WildcardSetting<T> {
+    fn from(setting: Setting<Vec<T>>) -> Self {
         Self(setting)
     }
 }
 
-impl Serialize for WildcardSetting {
+impl<T: Serialize + for<'a> From<&'a str>> Serialize for WildcardSetting<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -861,7 +2588,7 @@ impl Serialize for WildcardSetting {
     }
 }
 
-impl<E: deserr::DeserializeError> Deserr<E> for WildcardSetting {
+impl<T: Deserr<E>, E: deserr::DeserializeError> Deserr<E> for WildcardSetting<T> {
     fn deserialize_from_value<V: deserr::IntoValue>(
         value: deserr::Value<V>,
         location: ValuePointerRef<'_>,
@@ -870,8 +2597,8 @@ impl<E: deserr::DeserializeError> Deserr<E> for WildcardSetting {
     }
 }
 
-impl std::ops::Deref for WildcardSetting {
-    type Target = Setting<Vec<String>>;
+impl<T> std::ops::Deref for WildcardSetting<T> {
+    type Target = Setting<Vec<T>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -887,22 +2614,46 @@ pub(crate) mod test {
         // test no changes
         let settings = Settings {
             displayed_attributes: Setting::Set(vec![String::from("hello")]).into(),
-            searchable_attributes: Setting::Set(vec![String::from("hello")]).into(),
+            searchable_attributes: Setting::Set(vec!["hello".into()]).into(),
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
+            dense_sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
+            exact_words: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
+            version_attribute: Setting::NotSet,
             proximity_precision: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
+            prefix_cache_size: Setting::NotSet,
+            long_query_threshold: Setting::NotSet,
+            quote_characters: Setting::NotSet,
+            language_preset: Setting::NotSet,
+            presence_only_attributes: Setting::NotSet,
+            chunking: Setting::NotSet,
+            document_hook: Setting::NotSet,
+            computed_fields: Setting::NotSet,
+            prepared_filters: Setting::NotSet,
+            pii_attributes: Setting::NotSet,
+            query_limits: Setting::NotSet,
+            regex_filter: Setting::NotSet,
+            field_languages: Setting::NotSet,
+            min_prefix_length: Setting::NotSet,
+            blocked_terms: Setting::NotSet,
+            new_fields: Setting::NotSet,
+            query_rewrites: Setting::NotSet,
+            position_decay: Setting::NotSet,
+            batching: Setting::NotSet,
+            collation: Setting::NotSet,
+            tie_breaker: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -914,23 +2665,46 @@ pub(crate) mod test {
         // test no changes
         let settings = Settings {
             displayed_attributes: Setting::Set(vec![String::from("*")]).into(),
-            searchable_attributes: Setting::Set(vec![String::from("hello"), String::from("*")])
-                .into(),
+            searchable_attributes: Setting::Set(vec!["hello".into(), "*".into()]).into(),
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
+            dense_sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
+            exact_words: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
+            version_attribute: Setting::NotSet,
             proximity_precision: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
+            prefix_cache_size: Setting::NotSet,
+            long_query_threshold: Setting::NotSet,
+            quote_characters: Setting::NotSet,
+            language_preset: Setting::NotSet,
+            presence_only_attributes: Setting::NotSet,
+            chunking: Setting::NotSet,
+            document_hook: Setting::NotSet,
+            computed_fields: Setting::NotSet,
+            prepared_filters: Setting::NotSet,
+            pii_attributes: Setting::NotSet,
+            query_limits: Setting::NotSet,
+            regex_filter: Setting::NotSet,
+            field_languages: Setting::NotSet,
+            min_prefix_length: Setting::NotSet,
+            blocked_terms: Setting::NotSet,
+            new_fields: Setting::NotSet,
+            query_rewrites: Setting::NotSet,
+            position_decay: Setting::NotSet,
+            batching: Setting::NotSet,
+            collation: Setting::NotSet,
+            tie_breaker: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -1,10 +1,15 @@
+use std::collections::BTreeSet;
+
+use milli::update::DerivedDatabase;
 use milli::Object;
 use serde::Serialize;
 use time::{Duration, OffsetDateTime};
 
 use crate::error::ResponseError;
 use crate::settings::{Settings, Unchecked};
-use crate::tasks::{serialize_duration, Details, IndexSwap, Kind, Status, Task, TaskId};
+use crate::tasks::{
+    serialize_duration, ComparisonReport, Details, IndexSwap, Kind, Status, Task, TaskId,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -55,6 +60,12 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped_documents: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejected_documents: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejected_document_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub edited_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub primary_key: Option<Option<String>>,
@@ -80,19 +91,39 @@ pub struct DetailsView {
     #[serde(flatten)]
     pub settings: Option<Box<Settings<Unchecked>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub databases: Option<BTreeSet<DerivedDatabase>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub swaps: Option<Vec<IndexSwap>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_uid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other_snapshot: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<Option<ComparisonReport>>,
 }
 
 impl From<Details> for DetailsView {
     fn from(details: Details) -> Self {
         match details {
-            Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
-                DetailsView {
-                    received_documents: Some(received_documents),
-                    indexed_documents: Some(indexed_documents),
-                    ..DetailsView::default()
-                }
-            }
+            Details::DocumentAdditionOrUpdate {
+                received_documents,
+                indexed_documents,
+                skipped_documents,
+                rejected_documents,
+                rejected_document_ids,
+            } => DetailsView {
+                received_documents: Some(received_documents),
+                indexed_documents: Some(indexed_documents),
+                // only shown when a version field is used to skip outdated documents
+                skipped_documents: (skipped_documents != 0).then_some(skipped_documents),
+                // only shown when a write mode rejected some documents
+                rejected_documents: (rejected_documents != 0).then_some(rejected_documents),
+                rejected_document_ids: (!rejected_document_ids.is_empty())
+                    .then_some(rejected_document_ids),
+                ..DetailsView::default()
+            },
             Details::DocumentEdition {
                 deleted_documents,
                 edited_documents,
@@ -153,9 +184,21 @@ impl From<Details> for DetailsView {
             Details::Dump { dump_uid } => {
                 DetailsView { dump_uid: Some(dump_uid), ..DetailsView::default() }
             }
+            Details::IndexRebuild { databases } => {
+                DetailsView { databases: Some(databases), ..DetailsView::default() }
+            }
             Details::IndexSwap { swaps } => {
                 DetailsView { swaps: Some(swaps), ..Default::default() }
             }
+            Details::SnapshotComparison { index_uid, snapshot, other_snapshot, report } => {
+                DetailsView {
+                    index_uid: Some(index_uid),
+                    snapshot: Some(snapshot),
+                    other_snapshot: Some(other_snapshot),
+                    report: Some(report),
+                    ..DetailsView::default()
+                }
+            }
         }
     }
 }
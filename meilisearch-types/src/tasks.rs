@@ -1,10 +1,11 @@
 use core::fmt;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fmt::{Display, Write};
 use std::str::FromStr;
 
+use deserr::Deserr;
 use enum_iterator::Sequence;
-use milli::update::IndexDocumentsMethod;
+use milli::update::{DerivedDatabase, DocumentsWriteMode, IndexDocumentsMethod};
 use milli::Object;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize, Serializer};
@@ -45,6 +46,7 @@ impl Task {
         match &self.kind {
             DumpCreation { .. }
             | SnapshotCreation
+            | SnapshotComparison { .. }
             | TaskCancelation { .. }
             | TaskDeletion { .. }
             | IndexSwap { .. } => None,
@@ -56,6 +58,7 @@ impl Task {
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexRebuild { index_uid, .. }
             | IndexDeletion { index_uid } => Some(index_uid),
         }
     }
@@ -77,11 +80,13 @@ impl Task {
             | KindWithContent::IndexDeletion { .. }
             | KindWithContent::IndexCreation { .. }
             | KindWithContent::IndexUpdate { .. }
+            | KindWithContent::IndexRebuild { .. }
             | KindWithContent::IndexSwap { .. }
             | KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
-            | KindWithContent::SnapshotCreation => None,
+            | KindWithContent::SnapshotCreation
+            | KindWithContent::SnapshotComparison { .. } => None,
         }
     }
 }
@@ -96,6 +101,8 @@ pub enum KindWithContent {
         content_file: Uuid,
         documents_count: u64,
         allow_index_creation: bool,
+        #[serde(default, skip_serializing_if = "DocumentsWriteMode::is_upsert")]
+        write_mode: DocumentsWriteMode,
     },
     DocumentDeletion {
         index_uid: String,
@@ -131,6 +138,11 @@ pub enum KindWithContent {
         index_uid: String,
         primary_key: Option<String>,
     },
+    /// Rebuilds the databases of the index derived from the other ones.
+    IndexRebuild {
+        index_uid: String,
+        databases: BTreeSet<DerivedDatabase>,
+    },
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
@@ -145,8 +157,17 @@ pub enum KindWithContent {
     DumpCreation {
         keys: Vec<Key>,
         instance_uid: Option<InstanceUid>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        anonymization: Option<DumpAnonymization>,
     },
     SnapshotCreation,
+    /// Compares an index in a snapshot with the same index in another snapshot, or with the
+    /// live index when `other_snapshot` is `None`.
+    SnapshotComparison {
+        index_uid: String,
+        snapshot: String,
+        other_snapshot: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -155,6 +176,27 @@ pub struct IndexSwap {
     pub indexes: (String, String),
 }
 
+/// The attributes anonymized in the documents of a dump, so that a production dump can be
+/// shared to debug the relevancy without leaking personal information.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAnonymization {
+    /// The attributes anonymized in the documents of every index.
+    pub attributes: BTreeSet<String>,
+    pub strategy: AnonymizationStrategy,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum AnonymizationStrategy {
+    /// Every word of the attributes is replaced by a hash, which keeps the structure of the values.
+    #[default]
+    Hash,
+    /// The attributes are removed from the documents.
+    Drop,
+}
+
 impl KindWithContent {
     pub fn as_kind(&self) -> Kind {
         match self {
@@ -167,11 +209,13 @@ impl KindWithContent {
             KindWithContent::IndexCreation { .. } => Kind::IndexCreation,
             KindWithContent::IndexDeletion { .. } => Kind::IndexDeletion,
             KindWithContent::IndexUpdate { .. } => Kind::IndexUpdate,
+            KindWithContent::IndexRebuild { .. } => Kind::IndexRebuild,
             KindWithContent::IndexSwap { .. } => Kind::IndexSwap,
             KindWithContent::TaskCancelation { .. } => Kind::TaskCancelation,
             KindWithContent::TaskDeletion { .. } => Kind::TaskDeletion,
             KindWithContent::DumpCreation { .. } => Kind::DumpCreation,
             KindWithContent::SnapshotCreation => Kind::SnapshotCreation,
+            KindWithContent::SnapshotComparison { .. } => Kind::SnapshotComparison,
         }
     }
 
@@ -181,6 +225,7 @@ impl KindWithContent {
         match self {
             DumpCreation { .. }
             | SnapshotCreation
+            | SnapshotComparison { .. }
             | TaskCancelation { .. }
             | TaskDeletion { .. } => vec![],
             DocumentAdditionOrUpdate { index_uid, .. }
@@ -191,6 +236,7 @@ impl KindWithContent {
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexRebuild { index_uid, .. }
             | IndexDeletion { index_uid } => vec![index_uid],
             IndexSwap { swaps } => {
                 let mut indexes = HashSet::<&str>::default();
@@ -211,6 +257,9 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    skipped_documents: 0,
+                    rejected_documents: 0,
+                    rejected_document_ids: Vec::new(),
                 })
             }
             KindWithContent::DocumentEdition { index_uid: _, filter_expr, context, function } => {
@@ -238,12 +287,15 @@ impl KindWithContent {
                 Some(Details::ClearAll { deleted_documents: None })
             }
             KindWithContent::SettingsUpdate { new_settings, .. } => {
-                Some(Details::SettingsUpdate { settings: new_settings.clone() })
+                Some(Details::settings_update(new_settings.as_ref().clone()))
             }
             KindWithContent::IndexCreation { primary_key, .. }
             | KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexRebuild { databases, .. } => {
+                Some(Details::IndexRebuild { databases: databases.clone() })
+            }
             KindWithContent::IndexSwap { swaps } => {
                 Some(Details::IndexSwap { swaps: swaps.clone() })
             }
@@ -259,6 +311,14 @@ impl KindWithContent {
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
             KindWithContent::SnapshotCreation => None,
+            KindWithContent::SnapshotComparison { index_uid, snapshot, other_snapshot } => {
+                Some(Details::SnapshotComparison {
+                    index_uid: index_uid.clone(),
+                    snapshot: snapshot.clone(),
+                    other_snapshot: other_snapshot.clone(),
+                    report: None,
+                })
+            }
         }
     }
 
@@ -268,6 +328,9 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: Some(0),
+                    skipped_documents: 0,
+                    rejected_documents: 0,
+                    rejected_document_ids: Vec::new(),
                 })
             }
             KindWithContent::DocumentEdition { index_uid: _, filter_expr, context, function } => {
@@ -295,13 +358,16 @@ impl KindWithContent {
                 Some(Details::ClearAll { deleted_documents: None })
             }
             KindWithContent::SettingsUpdate { new_settings, .. } => {
-                Some(Details::SettingsUpdate { settings: new_settings.clone() })
+                Some(Details::settings_update(new_settings.as_ref().clone()))
             }
             KindWithContent::IndexDeletion { .. } => None,
             KindWithContent::IndexCreation { primary_key, .. }
             | KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexRebuild { databases, .. } => {
+                Some(Details::IndexRebuild { databases: databases.clone() })
+            }
             KindWithContent::IndexSwap { .. } => {
                 todo!()
             }
@@ -317,6 +383,14 @@ impl KindWithContent {
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
             KindWithContent::SnapshotCreation => None,
+            KindWithContent::SnapshotComparison { index_uid, snapshot, other_snapshot } => {
+                Some(Details::SnapshotComparison {
+                    index_uid: index_uid.clone(),
+                    snapshot: snapshot.clone(),
+                    other_snapshot: other_snapshot.clone(),
+                    report: None,
+                })
+            }
         }
     }
 }
@@ -328,6 +402,9 @@ impl From<&KindWithContent> for Option<Details> {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    skipped_documents: 0,
+                    rejected_documents: 0,
+                    rejected_document_ids: Vec::new(),
                 })
             }
             KindWithContent::DocumentEdition { .. } => None,
@@ -335,7 +412,7 @@ impl From<&KindWithContent> for Option<Details> {
             KindWithContent::DocumentDeletionByFilter { .. } => None,
             KindWithContent::DocumentClear { .. } => None,
             KindWithContent::SettingsUpdate { new_settings, .. } => {
-                Some(Details::SettingsUpdate { settings: new_settings.clone() })
+                Some(Details::settings_update(new_settings.as_ref().clone()))
             }
             KindWithContent::IndexDeletion { .. } => None,
             KindWithContent::IndexCreation { primary_key, .. } => {
@@ -344,6 +421,9 @@ impl From<&KindWithContent> for Option<Details> {
             KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexRebuild { databases, .. } => {
+                Some(Details::IndexRebuild { databases: databases.clone() })
+            }
             KindWithContent::IndexSwap { .. } => None,
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
@@ -357,6 +437,14 @@ impl From<&KindWithContent> for Option<Details> {
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
             KindWithContent::SnapshotCreation => None,
+            KindWithContent::SnapshotComparison { index_uid, snapshot, other_snapshot } => {
+                Some(Details::SnapshotComparison {
+                    index_uid: index_uid.clone(),
+                    snapshot: snapshot.clone(),
+                    other_snapshot: other_snapshot.clone(),
+                    report: None,
+                })
+            }
         }
     }
 }
@@ -430,11 +518,13 @@ pub enum Kind {
     IndexCreation,
     IndexDeletion,
     IndexUpdate,
+    IndexRebuild,
     IndexSwap,
     TaskCancelation,
     TaskDeletion,
     DumpCreation,
     SnapshotCreation,
+    SnapshotComparison,
 }
 
 impl Kind {
@@ -446,12 +536,14 @@ impl Kind {
             | Kind::SettingsUpdate
             | Kind::IndexCreation
             | Kind::IndexDeletion
-            | Kind::IndexUpdate => true,
+            | Kind::IndexUpdate
+            | Kind::IndexRebuild => true,
             Kind::IndexSwap
             | Kind::TaskCancelation
             | Kind::TaskDeletion
             | Kind::DumpCreation
-            | Kind::SnapshotCreation => false,
+            | Kind::SnapshotCreation
+            | Kind::SnapshotComparison => false,
         }
     }
 }
@@ -465,11 +557,13 @@ impl Display for Kind {
             Kind::IndexCreation => write!(f, "indexCreation"),
             Kind::IndexDeletion => write!(f, "indexDeletion"),
             Kind::IndexUpdate => write!(f, "indexUpdate"),
+            Kind::IndexRebuild => write!(f, "indexRebuild"),
             Kind::IndexSwap => write!(f, "indexSwap"),
             Kind::TaskCancelation => write!(f, "taskCancelation"),
             Kind::TaskDeletion => write!(f, "taskDeletion"),
             Kind::DumpCreation => write!(f, "dumpCreation"),
             Kind::SnapshotCreation => write!(f, "snapshotCreation"),
+            Kind::SnapshotComparison => write!(f, "snapshotComparison"),
         }
     }
 }
@@ -481,6 +575,8 @@ impl FromStr for Kind {
             Ok(Kind::IndexCreation)
         } else if kind.eq_ignore_ascii_case("indexUpdate") {
             Ok(Kind::IndexUpdate)
+        } else if kind.eq_ignore_ascii_case("indexRebuild") {
+            Ok(Kind::IndexRebuild)
         } else if kind.eq_ignore_ascii_case("indexSwap") {
             Ok(Kind::IndexSwap)
         } else if kind.eq_ignore_ascii_case("indexDeletion") {
@@ -501,6 +597,8 @@ impl FromStr for Kind {
             Ok(Kind::DumpCreation)
         } else if kind.eq_ignore_ascii_case("snapshotCreation") {
             Ok(Kind::SnapshotCreation)
+        } else if kind.eq_ignore_ascii_case("snapshotComparison") {
+            Ok(Kind::SnapshotComparison)
         } else {
             Err(ParseTaskKindError(kind.to_owned()))
         }
@@ -533,6 +631,15 @@ pub enum Details {
     DocumentAdditionOrUpdate {
         received_documents: u64,
         indexed_documents: Option<u64>,
+        /// The documents that were not added because a newer version of them is already stored.
+        #[serde(default, skip_serializing_if = "is_zero")]
+        skipped_documents: u64,
+        /// The number of documents that were rejected by the write mode of the addition.
+        #[serde(default, skip_serializing_if = "is_zero")]
+        rejected_documents: u64,
+        /// The ids of the first documents that were rejected by the write mode of the addition.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        rejected_document_ids: Vec<String>,
     },
     SettingsUpdate {
         settings: Box<Settings<Unchecked>>,
@@ -571,12 +678,42 @@ pub enum Details {
     Dump {
         dump_uid: Option<String>,
     },
+    IndexRebuild {
+        databases: BTreeSet<DerivedDatabase>,
+    },
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
+    SnapshotComparison {
+        index_uid: String,
+        snapshot: String,
+        other_snapshot: Option<String>,
+        report: Option<ComparisonReport>,
+    },
+}
+
+/// The differences between two versions of an index.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonReport {
+    /// The number of documents only present in the new version.
+    pub documents_added: u64,
+    /// The number of documents only present in the old version.
+    pub documents_removed: u64,
+    /// The number of documents present in both versions with a different content.
+    pub documents_changed: u64,
+    /// The names of the settings that are different between the versions.
+    pub settings_differences: Vec<String>,
 }
 
 impl Details {
+    /// The details of a settings update, which keep the digest of the module of the
+    /// document hook rather than the module itself.
+    pub fn settings_update(mut settings: Settings<Unchecked>) -> Self {
+        settings.hide_document_hook_module();
+        Self::SettingsUpdate { settings: Box::new(settings) }
+    }
+
     pub fn to_failed(&self) -> Self {
         let mut details = self.clone();
         match &mut details {
@@ -594,13 +731,19 @@ impl Details {
             Self::SettingsUpdate { .. }
             | Self::IndexInfo { .. }
             | Self::Dump { .. }
-            | Self::IndexSwap { .. } => (),
+            | Self::IndexRebuild { .. }
+            | Self::IndexSwap { .. }
+            | Self::SnapshotComparison { .. } => (),
         }
 
         details
     }
 }
 
+fn is_zero(n: &u64) -> bool {
+    *n == 0
+}
+
 /// Serialize a `time::Duration` as a best effort ISO 8601 while waiting for
 /// https://github.com/time-rs/time/issues/378.
 /// This code is a port of the old code of time that was removed in 0.2.
@@ -0,0 +1,569 @@
+//! Extraction of the text of simple PDF and HTML documents, so that they can be indexed
+//! without going through a separate extraction service.
+//!
+//! Only the text drawn with the standard text operators of uncompressed or deflated PDF
+//! content streams is extracted, fonts with custom encodings are not supported.
+
+use std::io::Read;
+
+use base64::Engine;
+use flate2::read::ZlibDecoder;
+use milli::Object;
+use serde_json::Value;
+
+/// The field in which the text is written when none is specified.
+pub const DEFAULT_TEXT_FIELD: &str = "content";
+/// The maximum number of bytes decoded from a single stream of a PDF document, the rest of
+/// the stream is ignored.
+pub const MAX_DECODED_STREAM_LEN: u64 = 64 * 1024 * 1024; // 64 MiB
+/// The maximum length of the text extracted from the documents of a payload.
+pub const MAX_EXTRACTED_TEXT_LEN: usize = 256 * 1024 * 1024; // 256 MiB
+
+#[derive(Debug, thiserror::Error)]
+pub enum TextExtractionError {
+    #[error("the payload is not a PDF document")]
+    NotAPdf,
+    #[error(
+        "the `{field}` field must be a string containing base64 encoded content, found `{value}`"
+    )]
+    NotAString { field: String, value: Value },
+    #[error("the `{field}` field does not contain valid base64 encoded content")]
+    InvalidBase64 { field: String },
+    #[error("the extracted text exceeds the limit of {} bytes", MAX_EXTRACTED_TEXT_LEN)]
+    TooMuchText,
+}
+
+/// Replaces the base64 encoded PDF or HTML content of a field of the documents by its text.
+#[derive(Debug, Clone)]
+pub struct ContentExtraction {
+    /// The field containing the base64 encoded content, removed from the documents.
+    pub content_field: String,
+    /// The field in which the extracted text is written.
+    pub text_field: String,
+}
+
+impl ContentExtraction {
+    /// Extracts the text of the content of the document, if there is one.
+    ///
+    /// The content is considered to be a PDF document if it starts with the PDF header,
+    /// and an HTML document otherwise. The `extracted_len` is the length of the text already
+    /// extracted from the other documents of the payload, the payload is rejected once it
+    /// exceeds [`MAX_EXTRACTED_TEXT_LEN`].
+    pub fn extract(
+        &self,
+        document: &mut Object,
+        extracted_len: &mut usize,
+    ) -> Result<(), TextExtractionError> {
+        let content = match document.remove(&self.content_field) {
+            None | Some(Value::Null) => return Ok(()),
+            Some(Value::String(content)) => content,
+            Some(value) => {
+                return Err(TextExtractionError::NotAString {
+                    field: self.content_field.clone(),
+                    value,
+                })
+            }
+        };
+
+        let bytes =
+            base64::engine::general_purpose::STANDARD.decode(content.trim()).map_err(|_| {
+                TextExtractionError::InvalidBase64 { field: self.content_field.clone() }
+            })?;
+        let text = if is_pdf(&bytes) {
+            extract_pdf_text(&bytes)?
+        } else {
+            extract_html_text(&String::from_utf8_lossy(&bytes))
+        };
+        *extracted_len += text.len();
+        if *extracted_len > MAX_EXTRACTED_TEXT_LEN {
+            return Err(TextExtractionError::TooMuchText);
+        }
+        document.insert(self.text_field.clone(), Value::String(text));
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if the bytes start with the PDF header, which may be
+/// preceded by some garbage in the first kilobyte.
+pub fn is_pdf(bytes: &[u8]) -> bool {
+    find(&bytes[..bytes.len().min(1024)], b"%PDF-").is_some()
+}
+
+/// Returns the text of the HTML document, without the tags, scripts and styles.
+pub fn extract_html_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&decode_html_entities(&rest[..start]));
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let end = match rest.find('>') {
+            Some(end) => end,
+            // an unclosed tag, we consider the rest of the document to be in it.
+            None => break,
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        if !tag.starts_with('/') && !tag.ends_with('/') && is_raw_text_element(&name) {
+            // the content of these elements is not text to display, we skip it up to the closing tag.
+            let closing = format!("</{name}");
+            rest = match find_ignore_ascii_case(rest, &closing) {
+                Some(start) => rest[start..].find('>').map_or("", |end| &rest[start + end + 1..]),
+                None => "",
+            };
+        }
+
+        if !is_inline_element(&name) {
+            text.push(' ');
+        }
+    }
+    text.push_str(&decode_html_entities(rest));
+
+    collapse_whitespaces(&text)
+}
+
+fn is_raw_text_element(name: &str) -> bool {
+    matches!(name, "script" | "style" | "noscript" | "template")
+}
+
+/// The elements that are rendered inside a line and must not separate the words.
+fn is_inline_element(name: &str) -> bool {
+    matches!(
+        name,
+        "a" | "abbr"
+            | "b"
+            | "code"
+            | "em"
+            | "i"
+            | "mark"
+            | "q"
+            | "s"
+            | "small"
+            | "span"
+            | "strong"
+            | "sub"
+            | "sup"
+            | "u"
+    )
+}
+
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+fn decode_html_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((decode_html_entity(&rest[1..end + 1])?, end + 2)));
+        match entity {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+fn decode_html_entity(entity: &str) -> Option<char> {
+    let code = if let Some(hex) = entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+        u32::from_str_radix(hex, 16).ok()?
+    } else if let Some(decimal) = entity.strip_prefix('#') {
+        decimal.parse().ok()?
+    } else {
+        return match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => None,
+        };
+    };
+    char::from_u32(code)
+}
+
+/// Returns the text drawn in the content streams of the PDF document.
+pub fn extract_pdf_text(pdf: &[u8]) -> Result<String, TextExtractionError> {
+    if !is_pdf(pdf) {
+        return Err(TextExtractionError::NotAPdf);
+    }
+
+    let mut text = String::new();
+    let mut offset = 0;
+    while let Some(start) = find(&pdf[offset..], b"stream").map(|start| offset + start) {
+        offset = start + b"stream".len();
+        if pdf[..start].ends_with(b"end") {
+            continue;
+        }
+
+        // the dictionary of the stream is between the beginning of its object and the keyword.
+        let dictionary_start = rfind(&pdf[..start], b"obj").unwrap_or(0);
+        let dictionary = &pdf[dictionary_start..start];
+
+        let data_start = match &pdf[offset..] {
+            [b'\r', b'\n', ..] => offset + 2,
+            [b'\n', ..] | [b'\r', ..] => offset + 1,
+            _ => offset,
+        };
+        let data_end = match find(&pdf[data_start..], b"endstream") {
+            Some(end) => data_start + end,
+            None => break,
+        };
+        offset = data_end + b"endstream".len();
+
+        if let Some(content) = decode_stream(dictionary, &pdf[data_start..data_end]) {
+            extract_content_stream_text(&content, &mut text);
+        }
+        if text.len() > MAX_EXTRACTED_TEXT_LEN {
+            return Err(TextExtractionError::TooMuchText);
+        }
+    }
+
+    Ok(collapse_whitespaces(&text))
+}
+
+/// Returns the decoded data of the stream, or `None` if it can't contain text.
+fn decode_stream(dictionary: &[u8], data: &[u8]) -> Option<Vec<u8>> {
+    // the images and the font programs.
+    let not_content = [&b"/Image"[..], b"/Length1", b"/Length2", b"/Length3", b"/XRef"];
+    if not_content.iter().any(|key| find(dictionary, key).is_some()) {
+        return None;
+    }
+
+    if find(dictionary, b"/Filter").is_none() {
+        return Some(data.to_vec());
+    }
+    if find(dictionary, b"/FlateDecode").is_none() {
+        return None;
+    }
+
+    // the decoded stream is truncated so that a small stream can't exhaust the memory.
+    let mut content = Vec::new();
+    match ZlibDecoder::new(data).take(MAX_DECODED_STREAM_LEN).read_to_end(&mut content) {
+        Ok(_) => Some(content),
+        // the stream may be truncated, we keep what could be decoded.
+        Err(_) if !content.is_empty() => Some(content),
+        Err(_) => None,
+    }
+}
+
+enum Operand {
+    String(Vec<u8>),
+    Number(f64),
+    Array(Vec<Operand>),
+    Other,
+}
+
+/// Appends the text shown by the text operators of the content stream.
+fn extract_content_stream_text(content: &[u8], text: &mut String) {
+    let mut operands: Vec<Operand> = Vec::new();
+    let mut array: Option<Vec<Operand>> = None;
+    let mut i = 0;
+
+    while i < content.len() {
+        let operand = match content[i] {
+            c if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'%' => {
+                i += content[i..]
+                    .iter()
+                    .position(|&c| c == b'\n' || c == b'\r')
+                    .unwrap_or(content.len() - i);
+                continue;
+            }
+            b'(' => {
+                let (string, len) = parse_literal_string(&content[i..]);
+                i += len;
+                Operand::String(string)
+            }
+            b'<' if content.get(i + 1) == Some(&b'<') => {
+                i += 2;
+                Operand::Other
+            }
+            b'<' => {
+                // an unterminated hex string extends to the end of the stream.
+                let end = content[i + 1..]
+                    .iter()
+                    .position(|&c| c == b'>')
+                    .map_or(content.len(), |end| i + 1 + end);
+                let string = parse_hex_string(&content[i + 1..end]);
+                i = end + 1;
+                Operand::String(string)
+            }
+            b'>' => {
+                i += 1;
+                Operand::Other
+            }
+            b'[' => {
+                i += 1;
+                array = Some(Vec::new());
+                continue;
+            }
+            b']' => {
+                i += 1;
+                Operand::Array(array.take().unwrap_or_default())
+            }
+            _ => {
+                // the first character is never a delimiter, except for the names and the braces.
+                let len = content[i + 1..]
+                    .iter()
+                    .position(|&c| c.is_ascii_whitespace() || b"()<>[]{}/%".contains(&c))
+                    .map_or(content.len() - i, |len| len + 1);
+                let token = &content[i..i + len];
+                i += len;
+
+                if let Some(number) = std::str::from_utf8(token).ok().and_then(|n| n.parse().ok()) {
+                    Operand::Number(number)
+                } else if token[0] == b'/' || token[0] == b'{' || token[0] == b'}' {
+                    Operand::Other
+                } else {
+                    apply_text_operator(token, &operands, text);
+                    operands.clear();
+                    continue;
+                }
+            }
+        };
+
+        match (&mut array, operand) {
+            (_, Operand::Array(elements)) => operands.push(Operand::Array(elements)),
+            (Some(array), operand) => array.push(operand),
+            (None, operand) => operands.push(operand),
+        }
+    }
+}
+
+fn apply_text_operator(operator: &[u8], operands: &[Operand], text: &mut String) {
+    match operator {
+        b"Tj" | b"'" | b"\"" => {
+            if operator != b"Tj" {
+                text.push('\n');
+            }
+            if let Some(Operand::String(string)) = operands.last() {
+                text.push_str(&decode_pdf_string(string));
+            }
+        }
+        b"TJ" => {
+            if let Some(Operand::Array(array)) = operands.last() {
+                show_text_array(array, text);
+            }
+        }
+        b"Td" | b"TD" | b"Tm" | b"T*" | b"BT" | b"ET" => text.push(' '),
+        _ => (),
+    }
+}
+
+/// Shows the strings of the array of a `TJ` operator, the big negative
+/// adjustments between them being considered as word separators.
+fn show_text_array(array: &[Operand], text: &mut String) {
+    for operand in array {
+        match operand {
+            Operand::String(string) => text.push_str(&decode_pdf_string(string)),
+            Operand::Number(adjustment) if *adjustment < -200.0 => text.push(' '),
+            _ => (),
+        }
+    }
+}
+
+/// Parses the literal string at the beginning of the content and returns it with its length.
+fn parse_literal_string(content: &[u8]) -> (Vec<u8>, usize) {
+    let mut string = Vec::new();
+    let mut depth = 0;
+    let mut i = 1;
+
+    while i < content.len() {
+        match content[i] {
+            b'\\' => {
+                i += 1;
+                match content.get(i) {
+                    Some(b'n') => string.push(b'\n'),
+                    Some(b'r') => string.push(b'\r'),
+                    Some(b't') => string.push(b'\t'),
+                    Some(b'b') => string.push(0x08),
+                    Some(b'f') => string.push(0x0c),
+                    Some(b'0'..=b'7') => {
+                        let digits = content[i..]
+                            .iter()
+                            .take(3)
+                            .take_while(|c| (b'0'..=b'7').contains(c))
+                            .count();
+                        let code = content[i..i + digits]
+                            .iter()
+                            .fold(0u32, |code, c| code * 8 + (c - b'0') as u32);
+                        string.push(code as u8);
+                        i += digits - 1;
+                    }
+                    // a backslash at the end of a line continues the string on the next line.
+                    Some(b'\r') => {
+                        if content.get(i + 1) == Some(&b'\n') {
+                            i += 1;
+                        }
+                    }
+                    Some(b'\n') => (),
+                    Some(&c) => string.push(c),
+                    None => break,
+                }
+            }
+            b'(' => {
+                depth += 1;
+                string.push(b'(');
+            }
+            b')' if depth == 0 => return (string, i + 1),
+            b')' => {
+                depth -= 1;
+                string.push(b')');
+            }
+            c => string.push(c),
+        }
+        i += 1;
+    }
+
+    (string, content.len())
+}
+
+fn parse_hex_string(hex: &[u8]) -> Vec<u8> {
+    let digits: Vec<u8> =
+        hex.iter().filter_map(|&c| (c as char).to_digit(16)).map(|digit| digit as u8).collect();
+    // a missing last digit is considered to be a zero.
+    digits.chunks(2).map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0)).collect()
+}
+
+/// Decodes a string as UTF-16 if it starts with its byte order mark, as Latin-1 otherwise.
+fn decode_pdf_string(string: &[u8]) -> String {
+    match string.strip_prefix(&[0xfe, 0xff]) {
+        Some(utf16) => {
+            let units = utf16.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]]));
+            char::decode_utf16(units).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)).collect()
+        }
+        None => string.iter().map(|&c| c as char).collect(),
+    }
+}
+
+fn collapse_whitespaces(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn html_text() {
+        let html = r#"<!DOCTYPE html>
+<html>
+  <head><title>Kefir &amp; Intel</title><style>p { color: red; }</style></head>
+  <body>
+    <!-- <p>hidden</p> -->
+    <h1>The <b>good</b> do<i>g</i>gos</h1>
+    <p>Kefir&nbsp;is 3&#x20;years old &lt;3</p><p>Intel&unknown;</p>
+    <script type="text/javascript">document.write("<p>bark</p>")</SCRIPT>
+  </body>
+</html>"#;
+        assert_eq!(
+            extract_html_text(html),
+            "Kefir & Intel The good doggos Kefir is 3 years old <3 Intel&unknown;"
+        );
+    }
+
+    #[test]
+    fn pdf_text() {
+        let pdf = b"%PDF-1.4
+1 0 obj << /Type /Page /Contents 2 0 R >> endobj
+2 0 obj << /Length 104 >>
+stream
+BT /F1 12 Tf 72 712 Td (The good \\(doggos\\)) Tj T* [(Ke) 20 (fir) -300 (Intel)] TJ 0 -14 Td <4865> Tj ET
+endstream
+endobj
+3 0 obj << /Subtype /Image /Length 12 >>
+stream
+(hidden) Tj
+endstream
+endobj
+%%EOF";
+        assert_eq!(extract_pdf_text(pdf).unwrap(), "The good (doggos) Kefir Intel He");
+        assert!(matches!(extract_pdf_text(b"<p>doggo</p>"), Err(TextExtractionError::NotAPdf)));
+    }
+
+    #[test]
+    fn unterminated_pdf_strings() {
+        let mut text = String::new();
+        extract_content_stream_text(b"(x) Tj <", &mut text);
+        assert_eq!(text, "x");
+
+        let mut text = String::new();
+        extract_content_stream_text(b"<4865 Tj", &mut text);
+        assert_eq!(text, "");
+    }
+
+    #[test]
+    fn content_extraction() {
+        let extraction =
+            ContentExtraction { content_field: "file".into(), text_field: "text".into() };
+        let mut document = Object::new();
+        document.insert("id".into(), Value::from(1));
+        document.insert("file".into(), Value::from("PGgxPktlZmlyPC9oMT4="));
+        let mut extracted_len = 0;
+        extraction.extract(&mut document, &mut extracted_len).unwrap();
+        assert_eq!(extracted_len, 5);
+        assert_eq!(document.get("text"), Some(&Value::from("Kefir")));
+        assert_eq!(document.get("file"), None);
+
+        // the documents without any content are left untouched.
+        extraction.extract(&mut document, &mut extracted_len).unwrap();
+        assert_eq!(document.get("text"), Some(&Value::from("Kefir")));
+
+        document.insert("file".into(), Value::from("not base64!"));
+        assert!(extraction.extract(&mut document, &mut extracted_len).is_err());
+
+        // the payload is rejected once its extracted text exceeds the limit.
+        document.insert("file".into(), Value::from("PGgxPktlZmlyPC9oMT4="));
+        let mut extracted_len = MAX_EXTRACTED_TEXT_LEN - 4;
+        assert!(matches!(
+            extraction.extract(&mut document, &mut extracted_len),
+            Err(TextExtractionError::TooMuchText)
+        ));
+    }
+}
@@ -74,8 +74,16 @@ pub enum DocumentDeletionKind {
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DocumentFetchKind {
-    PerDocumentId { retrieve_vectors: bool },
-    Normal { with_filter: bool, limit: usize, offset: usize, retrieve_vectors: bool },
+    PerDocumentId {
+        retrieve_vectors: bool,
+    },
+    Normal {
+        with_filter: bool,
+        with_ids: bool,
+        limit: usize,
+        offset: usize,
+        retrieve_vectors: bool,
+    },
 }
 
 pub trait Analytics: Sync + Send {
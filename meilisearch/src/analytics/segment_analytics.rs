@@ -39,6 +39,7 @@ use crate::search::{
     DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
     DEFAULT_SEMANTIC_RATIO,
 };
+use crate::typo_statistics::TypoUsage;
 use crate::Opt;
 
 const ANALYTICS_HEADER: &str = "X-Meilisearch-Client";
@@ -263,11 +264,15 @@ struct Infos {
     env: String,
     experimental_enable_metrics: bool,
     experimental_search_queue_size: usize,
+    experimental_search_threads: Option<usize>,
     experimental_logs_mode: LogMode,
     experimental_replication_parameters: bool,
     experimental_enable_logs_route: bool,
     experimental_reduce_indexing_memory_usage: bool,
     experimental_max_number_of_batched_tasks: usize,
+    experimental_task_payload_retention_days: Option<u64>,
+    experimental_federation_upstreams: usize,
+    experimental_index_partitions: usize,
     gpu_enabled: bool,
     db_path: bool,
     import_dump: bool,
@@ -286,6 +291,9 @@ struct Infos {
     log_level: String,
     max_indexing_memory: MaxMemory,
     max_indexing_threads: MaxThreads,
+    max_attachment_size: Byte,
+    max_document_attachments_size: Byte,
+    pii_encryption_key: bool,
     with_configuration_file: bool,
     ssl_auth_path: bool,
     ssl_cert_path: bool,
@@ -305,11 +313,15 @@ impl From<Opt> for Infos {
             db_path,
             experimental_enable_metrics,
             experimental_search_queue_size,
+            experimental_search_threads,
             experimental_logs_mode,
             experimental_replication_parameters,
             experimental_enable_logs_route,
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
+            experimental_task_payload_retention_days,
+            experimental_federation_upstreams,
+            experimental_index_partitions,
             http_addr,
             master_key: _,
             env,
@@ -326,6 +338,7 @@ impl From<Opt> for Infos {
             ssl_resumption,
             ssl_tickets,
             import_snapshot,
+            import_snapshot_sha256: _,
             ignore_missing_snapshot,
             ignore_snapshot_if_db_exists,
             snapshot_dir,
@@ -346,8 +359,14 @@ impl From<Opt> for Infos {
             ScheduleSnapshot::Enabled(interval) => Some(interval),
         };
 
-        let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } =
-            indexer_options;
+        let IndexerOpts {
+            max_indexing_memory,
+            max_indexing_threads,
+            max_attachment_size,
+            max_document_attachments_size,
+            pii_encryption_key,
+            skip_index_budget: _,
+        } = indexer_options;
 
         // We're going to override every sensible information.
         // We consider information sensible if it contains a path, an address, or a key.
@@ -355,6 +374,7 @@ impl From<Opt> for Infos {
             env,
             experimental_enable_metrics,
             experimental_search_queue_size,
+            experimental_search_threads,
             experimental_logs_mode,
             experimental_replication_parameters,
             experimental_enable_logs_route,
@@ -373,11 +393,19 @@ impl From<Opt> for Infos {
             http_addr: http_addr != default_http_addr(),
             http_payload_size_limit,
             experimental_max_number_of_batched_tasks,
+            experimental_task_payload_retention_days,
+            experimental_federation_upstreams: experimental_federation_upstreams
+                .map_or(0, |upstreams| upstreams.0.len()),
+            experimental_index_partitions: experimental_index_partitions
+                .map_or(0, |partitions| partitions.0.len()),
             task_queue_webhook: task_webhook_url.is_some(),
             task_webhook_authorization_header: task_webhook_authorization_header.is_some(),
             log_level: log_level.to_string(),
             max_indexing_memory,
             max_indexing_threads,
+            max_attachment_size,
+            max_document_attachments_size,
+            pii_encryption_key: pii_encryption_key.is_some(),
             with_configuration_file: config_file_path.is_some(),
             ssl_auth_path: ssl_auth_path.is_some(),
             ssl_cert_path: ssl_cert_path.is_some(),
@@ -610,16 +638,25 @@ pub struct SearchAggregator {
     total_used_negative_operator: usize,
     time_spent: BinaryHeap<usize>,
 
+    // typos
+    // every time a search counts the typos of its hits, they are added here
+    typo_usage: TypoUsage,
+
     // sort
     sort_with_geo_point: bool,
     // every time a request has a filter, this field must be incremented by the number of terms it contains
     sort_sum_of_criteria_terms: usize,
     // every time a request has a filter, this field must be incremented by one
     sort_total_number_of_criteria: usize,
+    sort_with_geo_max_distance: bool,
+    sort_with_geo_bucket_width: bool,
 
     // distinct
     distinct: bool,
 
+    // diversify
+    diversify: bool,
+
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
@@ -628,11 +665,20 @@ pub struct SearchAggregator {
     // every time a request has a filter, this field must be incremented by one
     filter_total_number_of_criteria: usize,
     used_syntax: HashMap<String, usize>,
+    filter_with_prepared_filter: bool,
 
     // attributes_to_search_on
     // every time a search is done using attributes_to_search_on
     attributes_to_search_on_total_number_of_uses: usize,
 
+    // languages
+    // every time a search is restricted to some languages
+    languages_total_number_of_uses: usize,
+
+    // synonyms
+    // every time a search is done with its own synonyms
+    synonyms_total_number_of_uses: usize,
+
     // q
     // The maximum number of terms in a q request
     max_terms_number: usize,
@@ -650,10 +696,16 @@ pub struct SearchAggregator {
     // every time a search is done, we increment the counter linked to the used settings
     matching_strategy: HashMap<String, usize>,
 
+    // phrase fallback
+    phrase_fallback: bool,
+
     // pagination
     max_limit: usize,
     max_offset: usize,
     finite_pagination: usize,
+    // every time a search creates or resumes a search session
+    sessions_created: usize,
+    sessions_resumed: usize,
 
     // formatting
     max_attributes_to_retrieve: usize,
@@ -662,7 +714,11 @@ pub struct SearchAggregator {
     highlight_post_tag: bool,
     max_attributes_to_crop: usize,
     crop_marker: bool,
+    max_highlight_fragments: bool,
     show_matches_position: bool,
+    show_query_terms: bool,
+    show_query_interpretation: bool,
+    show_timings: bool,
     crop_length: bool,
 
     // facets
@@ -691,19 +747,33 @@ impl SearchAggregator {
             crop_length,
             attributes_to_highlight: _,
             show_matches_position,
+            show_query_terms,
+            show_query_interpretation,
+            show_timings,
             show_ranking_score,
             show_ranking_score_details,
             filter,
+            prepared_filter,
             sort,
             distinct,
+            diversify,
             facets: _,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
+            max_highlight_fragments,
+            fragment_separator: _,
             matching_strategy,
+            phrase_fallback,
             attributes_to_search_on,
+            languages,
+            synonyms,
+            geo_sort_max_distance,
+            geo_sort_bucket_width,
             hybrid,
             ranking_score_threshold,
+            create_session,
+            session_token,
         } = query;
 
         let mut ret = Self::default();
@@ -717,8 +787,11 @@ impl SearchAggregator {
             ret.sort_with_geo_point = sort.iter().any(|s| s.contains("_geoPoint("));
             ret.sort_sum_of_criteria_terms = sort.len();
         }
+        ret.sort_with_geo_max_distance = geo_sort_max_distance.is_some();
+        ret.sort_with_geo_bucket_width = geo_sort_bucket_width.is_some();
 
         ret.distinct = distinct.is_some();
+        ret.diversify = diversify.is_some();
 
         if let Some(ref filter) = filter {
             static RE: Lazy<Regex> = Lazy::new(|| Regex::new("AND | OR").unwrap());
@@ -743,12 +816,23 @@ impl SearchAggregator {
             ret.filter_with_geo_bounding_box = stringified_filters.contains("_geoBoundingBox(");
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
+        ret.filter_with_prepared_filter = prepared_filter.is_some();
 
         // attributes_to_search_on
         if attributes_to_search_on.is_some() {
             ret.attributes_to_search_on_total_number_of_uses = 1;
         }
 
+        // languages
+        if languages.is_some() {
+            ret.languages_total_number_of_uses = 1;
+        }
+
+        // synonyms
+        if synonyms.is_some() {
+            ret.synonyms_total_number_of_uses = 1;
+        }
+
         if let Some(ref q) = q {
             ret.max_terms_number = q.split_whitespace().count();
         }
@@ -768,14 +852,24 @@ impl SearchAggregator {
             ret.max_offset = *offset;
             ret.finite_pagination = 0;
         }
+        if session_token.is_some() {
+            ret.sessions_resumed = 1;
+        } else if *create_session {
+            ret.sessions_created = 1;
+        }
 
         ret.matching_strategy.insert(format!("{:?}", matching_strategy), 1);
+        ret.phrase_fallback = *phrase_fallback;
 
         ret.highlight_pre_tag = *highlight_pre_tag != DEFAULT_HIGHLIGHT_PRE_TAG();
         ret.highlight_post_tag = *highlight_post_tag != DEFAULT_HIGHLIGHT_POST_TAG();
         ret.crop_marker = *crop_marker != DEFAULT_CROP_MARKER();
+        ret.max_highlight_fragments = max_highlight_fragments.is_some();
         ret.crop_length = *crop_length != DEFAULT_CROP_LENGTH();
         ret.show_matches_position = *show_matches_position;
+        ret.show_query_terms = *show_query_terms;
+        ret.show_query_interpretation = *show_query_interpretation;
+        ret.show_timings = *show_timings;
 
         ret.show_ranking_score = *show_ranking_score;
         ret.show_ranking_score_details = *show_ranking_score_details;
@@ -799,8 +893,16 @@ impl SearchAggregator {
             semantic_hit_count: _,
             facet_distribution: _,
             facet_stats: _,
+            formatting_time_ms: _,
+            timings: _,
+            query_truncated: _,
+            relaxed_phrases: _,
+            session_token: _,
             degraded,
             used_negative_operator,
+            query_terms: _,
+            query_interpretation: _,
+            typo_usage,
         } = result;
 
         self.total_succeeded = self.total_succeeded.saturating_add(1);
@@ -811,6 +913,9 @@ impl SearchAggregator {
             self.total_used_negative_operator = self.total_used_negative_operator.saturating_add(1);
         }
         self.time_spent.push(*processing_time_ms as usize);
+        if let Some(typo_usage) = typo_usage {
+            self.typo_usage.merge(typo_usage);
+        }
     }
 
     /// Aggregate one [SearchAggregator] into another.
@@ -824,27 +929,40 @@ impl SearchAggregator {
             sort_with_geo_point,
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
+            sort_with_geo_max_distance,
+            sort_with_geo_bucket_width,
             distinct,
+            diversify,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
+            filter_with_prepared_filter,
             attributes_to_search_on_total_number_of_uses,
+            languages_total_number_of_uses,
+            synonyms_total_number_of_uses,
             max_terms_number,
             max_vector_size,
             retrieve_vectors,
             matching_strategy,
+            phrase_fallback,
             max_limit,
             max_offset,
             finite_pagination,
+            sessions_created,
+            sessions_resumed,
             max_attributes_to_retrieve,
             max_attributes_to_highlight,
             highlight_pre_tag,
             highlight_post_tag,
             max_attributes_to_crop,
             crop_marker,
+            max_highlight_fragments,
             show_matches_position,
+            show_query_terms,
+            show_query_interpretation,
+            show_timings,
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
@@ -856,6 +974,7 @@ impl SearchAggregator {
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
+            typo_usage,
         } = other;
 
         if self.timestamp.is_none() {
@@ -875,16 +994,24 @@ impl SearchAggregator {
             self.total_used_negative_operator.saturating_add(total_used_negative_operator);
         self.time_spent.append(time_spent);
 
+        // typos
+        self.typo_usage.merge(&typo_usage);
+
         // sort
         self.sort_with_geo_point |= sort_with_geo_point;
         self.sort_sum_of_criteria_terms =
             self.sort_sum_of_criteria_terms.saturating_add(sort_sum_of_criteria_terms);
         self.sort_total_number_of_criteria =
             self.sort_total_number_of_criteria.saturating_add(sort_total_number_of_criteria);
+        self.sort_with_geo_max_distance |= sort_with_geo_max_distance;
+        self.sort_with_geo_bucket_width |= sort_with_geo_bucket_width;
 
         // distinct
         self.distinct |= distinct;
 
+        // diversify
+        self.diversify |= diversify;
+
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
@@ -896,12 +1023,21 @@ impl SearchAggregator {
             let used_syntax = self.used_syntax.entry(key).or_insert(0);
             *used_syntax = used_syntax.saturating_add(value);
         }
+        self.filter_with_prepared_filter |= filter_with_prepared_filter;
 
         // attributes_to_search_on
         self.attributes_to_search_on_total_number_of_uses = self
             .attributes_to_search_on_total_number_of_uses
             .saturating_add(attributes_to_search_on_total_number_of_uses);
 
+        // languages
+        self.languages_total_number_of_uses =
+            self.languages_total_number_of_uses.saturating_add(languages_total_number_of_uses);
+
+        // synonyms
+        self.synonyms_total_number_of_uses =
+            self.synonyms_total_number_of_uses.saturating_add(synonyms_total_number_of_uses);
+
         // q
         self.max_terms_number = self.max_terms_number.max(max_terms_number);
 
@@ -916,6 +1052,8 @@ impl SearchAggregator {
         self.max_limit = self.max_limit.max(max_limit);
         self.max_offset = self.max_offset.max(max_offset);
         self.finite_pagination += finite_pagination;
+        self.sessions_created = self.sessions_created.saturating_add(sessions_created);
+        self.sessions_resumed = self.sessions_resumed.saturating_add(sessions_resumed);
 
         // formatting
         self.max_attributes_to_retrieve =
@@ -926,7 +1064,11 @@ impl SearchAggregator {
         self.highlight_post_tag |= highlight_post_tag;
         self.max_attributes_to_crop = self.max_attributes_to_crop.max(max_attributes_to_crop);
         self.crop_marker |= crop_marker;
+        self.max_highlight_fragments |= max_highlight_fragments;
         self.show_matches_position |= show_matches_position;
+        self.show_query_terms |= show_query_terms;
+        self.show_query_interpretation |= show_query_interpretation;
+        self.show_timings |= show_timings;
         self.crop_length |= crop_length;
 
         // facets
@@ -940,6 +1082,9 @@ impl SearchAggregator {
             *matching_strategy = matching_strategy.saturating_add(value);
         }
 
+        // phrase fallback
+        self.phrase_fallback |= phrase_fallback;
+
         // scoring
         self.show_ranking_score |= show_ranking_score;
         self.show_ranking_score_details |= show_ranking_score_details;
@@ -956,27 +1101,40 @@ impl SearchAggregator {
             sort_with_geo_point,
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
+            sort_with_geo_max_distance,
+            sort_with_geo_bucket_width,
             distinct,
+            diversify,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
+            filter_with_prepared_filter,
             attributes_to_search_on_total_number_of_uses,
+            languages_total_number_of_uses,
+            synonyms_total_number_of_uses,
             max_terms_number,
             max_vector_size,
             retrieve_vectors,
             matching_strategy,
+            phrase_fallback,
             max_limit,
             max_offset,
             finite_pagination,
+            sessions_created,
+            sessions_resumed,
             max_attributes_to_retrieve,
             max_attributes_to_highlight,
             highlight_pre_tag,
             highlight_post_tag,
             max_attributes_to_crop,
             crop_marker,
+            max_highlight_fragments,
             show_matches_position,
+            show_query_terms,
+            show_query_interpretation,
+            show_timings,
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
@@ -988,6 +1146,7 @@ impl SearchAggregator {
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
+            typo_usage,
         } = self;
 
         if total_received == 0 {
@@ -1012,18 +1171,28 @@ impl SearchAggregator {
                 },
                 "sort": {
                     "with_geoPoint": sort_with_geo_point,
+                    "with_geoSortMaxDistance": sort_with_geo_max_distance,
+                    "with_geoSortBucketWidth": sort_with_geo_bucket_width,
                     "avg_criteria_number": format!("{:.2}", sort_sum_of_criteria_terms as f64 / sort_total_number_of_criteria as f64),
                 },
                 "distinct": distinct,
+                "diversify": diversify,
                 "filter": {
                    "with_geoRadius": filter_with_geo_radius,
                    "with_geoBoundingBox": filter_with_geo_bounding_box,
                    "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                    "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
+                   "with_prepared_filter": filter_with_prepared_filter,
                 },
                 "attributes_to_search_on": {
                    "total_number_of_uses": attributes_to_search_on_total_number_of_uses,
                 },
+                "languages": {
+                   "total_number_of_uses": languages_total_number_of_uses,
+                },
+                "synonyms": {
+                   "total_number_of_uses": synonyms_total_number_of_uses,
+                },
                 "q": {
                    "max_terms_number": max_terms_number,
                 },
@@ -1040,6 +1209,8 @@ impl SearchAggregator {
                    "max_limit": max_limit,
                    "max_offset": max_offset,
                    "most_used_navigation": if finite_pagination > (total_received / 2) { "exhaustive" } else { "estimated" },
+                   "sessions_created": sessions_created,
+                   "sessions_resumed": sessions_resumed,
                 },
                 "formatting": {
                     "max_attributes_to_retrieve": max_attributes_to_retrieve,
@@ -1048,7 +1219,11 @@ impl SearchAggregator {
                     "highlight_post_tag": highlight_post_tag,
                     "max_attributes_to_crop": max_attributes_to_crop,
                     "crop_marker": crop_marker,
+                    "max_highlight_fragments": max_highlight_fragments,
                     "show_matches_position": show_matches_position,
+                    "show_query_terms": show_query_terms,
+                    "show_query_interpretation": show_query_interpretation,
+                    "show_timings": show_timings,
                     "crop_length": crop_length,
                 },
                 "facets": {
@@ -1057,6 +1232,14 @@ impl SearchAggregator {
                 "matching_strategy": {
                     "most_used_strategy": matching_strategy.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
                 },
+                "phrase_fallback": phrase_fallback,
+                "typos": {
+                    "total_hits": typo_usage.hits,
+                    "zero_typo_hits": typo_usage.zero_typo_hits,
+                    "one_typo_hits": typo_usage.one_typo_hits,
+                    "two_typos_hits": typo_usage.two_typos_hits,
+                    "prefix_hits": typo_usage.prefix_hits,
+                },
                 "scoring": {
                     "show_ranking_score": show_ranking_score,
                     "show_ranking_score_details": show_ranking_score_details,
@@ -1125,15 +1308,27 @@ impl MultiSearchAggregator {
                     show_ranking_score: _,
                     show_ranking_score_details: _,
                     show_matches_position: _,
+                    show_query_terms: _,
+                    show_query_interpretation: _,
+                    show_timings: _,
                     filter: _,
+                    prepared_filter: _,
                     sort: _,
                     distinct: _,
+                    diversify: _,
                     facets: _,
                     highlight_pre_tag: _,
                     highlight_post_tag: _,
                     crop_marker: _,
+                    max_highlight_fragments: _,
+                    fragment_separator: _,
                     matching_strategy: _,
+                    phrase_fallback: _,
                     attributes_to_search_on: _,
+                    languages: _,
+                    synonyms: _,
+                    geo_sort_max_distance: _,
+                    geo_sort_bucket_width: _,
                     hybrid: _,
                     ranking_score_threshold: _,
                 } = query;
@@ -1280,6 +1475,7 @@ impl FacetSearchAggregator {
             vector,
             q,
             filter,
+            prepared_filter,
             matching_strategy,
             attributes_to_search_on,
             hybrid,
@@ -1296,6 +1492,7 @@ impl FacetSearchAggregator {
         ret.additional_search_parameters_provided = q.is_some()
             || vector.is_some()
             || filter.is_some()
+            || prepared_filter.is_some()
             || *matching_strategy != MatchingStrategy::default()
             || attributes_to_search_on.is_some()
             || hybrid.is_some()
@@ -1412,7 +1609,14 @@ impl DocumentsAggregator {
         index_creation: bool,
         request: &HttpRequest,
     ) -> Self {
-        let UpdateDocumentsQuery { primary_key, csv_delimiter: _ } = documents_query;
+        let UpdateDocumentsQuery {
+            primary_key,
+            csv_delimiter: _,
+            write_mode: _,
+            content_field: _,
+            text_field: _,
+            document_id: _,
+        } = documents_query;
 
         let mut primary_keys = HashSet::new();
         if let Some(primary_key) = primary_key.clone() {
@@ -1641,6 +1845,8 @@ pub struct DocumentsFetchAggregator {
 
     // a call on ../documents/:doc_id
     per_document_id: bool,
+    // a call on ../documents/hydrate or on ../documents/fetch with ids
+    per_document_ids: bool,
     // if a filter was used
     per_filter: bool,
 
@@ -1667,6 +1873,7 @@ impl DocumentsFetchAggregator {
             user_agents: extract_user_agents(request).into_iter().collect(),
             total_received: 1,
             per_document_id: matches!(query, DocumentFetchKind::PerDocumentId { .. }),
+            per_document_ids: matches!(query, DocumentFetchKind::Normal { with_ids: true, .. }),
             per_filter: matches!(query, DocumentFetchKind::Normal { with_filter, .. } if *with_filter),
             max_limit: limit,
             max_offset: offset,
@@ -1681,6 +1888,7 @@ impl DocumentsFetchAggregator {
             user_agents,
             total_received,
             per_document_id,
+            per_document_ids,
             per_filter,
             max_limit,
             max_offset,
@@ -1696,6 +1904,7 @@ impl DocumentsFetchAggregator {
 
         self.total_received = self.total_received.saturating_add(total_received);
         self.per_document_id |= per_document_id;
+        self.per_document_ids |= per_document_ids;
         self.per_filter |= per_filter;
 
         self.max_limit = self.max_limit.max(max_limit);
@@ -0,0 +1,270 @@
+//! Resumable uploads of the huge document payloads, so that a flaky connection doesn't force
+//! the whole payload to be sent again.
+//!
+//! An upload is created empty, then its parts are appended one after the other, each part
+//! giving the offset at which it starts. A part is either appended entirely or not at all, so
+//! that after a failure the client can read the offset of the upload and resume from there.
+//! Completing the upload registers a document addition with the assembled payload.
+//!
+//! The uploads are stored in the `uploads` directory of the database and are lost when the
+//! instance restarts, the directory is emptied at startup. The uploads expire a day after their
+//! last part.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tempfile::NamedTempFile;
+use time::OffsetDateTime;
+use tokio::fs::OpenOptions;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use uuid::Uuid;
+
+use crate::error::MeilisearchHttpError;
+use crate::error::PayloadError::ReceivePayload;
+use crate::extractors::payload::Payload;
+
+/// The directory of the database in which the payloads of the uploads are stored.
+pub const UPLOADS_DIR: &str = "uploads";
+/// How long an upload is kept after its last part.
+const UPLOAD_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// The maximum number of uploads kept at the same time.
+const MAX_DOCUMENT_UPLOADS: usize = 100;
+
+pub static DOCUMENT_UPLOADS: Lazy<DocumentUploads> = Lazy::new(DocumentUploads::default);
+
+#[derive(Default)]
+pub struct DocumentUploads {
+    uploads: Mutex<HashMap<Uuid, DocumentUpload>>,
+}
+
+struct DocumentUpload {
+    view: DocumentUploadView,
+    file: Arc<NamedTempFile>,
+    /// Whether a part is being appended to the upload, or the upload is being completed.
+    locked: bool,
+}
+
+/// Keeps an upload locked until it is dropped, so that the upload is unlocked even when the
+/// request handling it is cancelled.
+pub struct UploadLock {
+    uploads: &'static DocumentUploads,
+    uid: Uuid,
+    file: Arc<NamedTempFile>,
+    /// The length the payload is truncated to when the lock is released,
+    /// to remove a part that wasn't appended entirely.
+    truncate_to: Option<u64>,
+    /// Whether the upload is removed when the lock is released.
+    remove: bool,
+}
+
+impl UploadLock {
+    /// Returns the file of the payload of the upload.
+    pub fn file(&self) -> &NamedTempFile {
+        &self.file
+    }
+
+    /// Releases the lock and removes the upload, once its payload was read.
+    pub fn complete(mut self) {
+        self.remove = true;
+    }
+}
+
+impl Drop for UploadLock {
+    fn drop(&mut self) {
+        if let Some(len) = self.truncate_to {
+            if let Err(error) = self.file.as_file().set_len(len) {
+                tracing::error!(%error, upload = %self.uid, "Could not truncate an upload");
+            }
+        }
+        let mut uploads = self.uploads.uploads.lock().unwrap();
+        if self.remove {
+            uploads.remove(&self.uid);
+        } else if let Some(upload) = uploads.get_mut(&self.uid) {
+            upload.locked = false;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentUploadView {
+    pub uid: Uuid,
+    pub index_uid: String,
+    /// The number of bytes received, at which the next part must start.
+    pub offset: u64,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl DocumentUploads {
+    /// Creates an empty upload in the given directory.
+    pub fn create(
+        &self,
+        uploads_path: &Path,
+        index_uid: &str,
+    ) -> Result<DocumentUploadView, MeilisearchHttpError> {
+        let now = OffsetDateTime::now_utc();
+        let mut uploads = self.uploads.lock().unwrap();
+        remove_expired_uploads(&mut uploads, now);
+        if uploads.len() >= MAX_DOCUMENT_UPLOADS {
+            return Err(MeilisearchHttpError::TooManyDocumentUploads(MAX_DOCUMENT_UPLOADS));
+        }
+
+        let file = std::fs::create_dir_all(uploads_path)
+            .and_then(|()| NamedTempFile::new_in(uploads_path))
+            .map_err(receive_error)?;
+        let view = DocumentUploadView {
+            uid: Uuid::new_v4(),
+            index_uid: index_uid.to_string(),
+            offset: 0,
+            created_at: now,
+            updated_at: now,
+        };
+        let upload = DocumentUpload { view: view.clone(), file: Arc::new(file), locked: false };
+        uploads.insert(view.uid, upload);
+        Ok(view)
+    }
+
+    /// Returns the upload, if it was created on this index and didn't expire.
+    pub fn get(
+        &self,
+        index_uid: &str,
+        uid: &str,
+    ) -> Result<DocumentUploadView, MeilisearchHttpError> {
+        let mut uploads = self.uploads.lock().unwrap();
+        Ok(get_upload(&mut uploads, index_uid, uid)?.view.clone())
+    }
+
+    /// Appends a part starting at the given offset, which must be the offset of the upload.
+    ///
+    /// The part is removed if it can't be received entirely.
+    pub async fn append(
+        &'static self,
+        index_uid: &str,
+        uid: &str,
+        offset: u64,
+        mut body: Payload,
+    ) -> Result<DocumentUploadView, MeilisearchHttpError> {
+        let mut lock = {
+            let mut uploads = self.uploads.lock().unwrap();
+            let upload = get_unlocked_upload(&mut uploads, index_uid, uid)?;
+            if upload.view.offset != offset {
+                return Err(MeilisearchHttpError::DocumentUploadOffsetMismatch {
+                    expected: upload.view.offset,
+                    received: offset,
+                });
+            }
+            // the upload must keep on matching its offset until the part is appended entirely
+            self.lock_upload(upload, Some(offset))
+        };
+
+        let mut received = 0;
+        let result = async {
+            let part = OpenOptions::new().append(true).open(lock.file().path()).await;
+            let mut buffer = BufWriter::new(part.map_err(receive_error)?);
+            while let Some(bytes) = body.next().await {
+                let bytes = bytes?;
+                buffer.write_all(&bytes).await.map_err(receive_error)?;
+                received += bytes.len() as u64;
+            }
+            buffer.flush().await.map_err(receive_error)
+        }
+        .await;
+
+        match result {
+            Ok(()) => lock.truncate_to = None,
+            Err(_) => received = 0,
+        }
+
+        let view = {
+            let mut uploads = self.uploads.lock().unwrap();
+            let upload = get_upload(&mut uploads, index_uid, uid)?;
+            upload.view.offset += received;
+            upload.view.updated_at = OffsetDateTime::now_utc();
+            upload.view.clone()
+        };
+        drop(lock);
+        result.map(|()| view)
+    }
+
+    /// Locks the upload while its payload is read, until the returned lock is dropped.
+    pub fn lock(
+        &'static self,
+        index_uid: &str,
+        uid: &str,
+    ) -> Result<UploadLock, MeilisearchHttpError> {
+        let mut uploads = self.uploads.lock().unwrap();
+        let upload = get_unlocked_upload(&mut uploads, index_uid, uid)?;
+        Ok(self.lock_upload(upload, None))
+    }
+
+    fn lock_upload(
+        &'static self,
+        upload: &mut DocumentUpload,
+        truncate_to: Option<u64>,
+    ) -> UploadLock {
+        upload.locked = true;
+        UploadLock {
+            uploads: self,
+            uid: upload.view.uid,
+            file: upload.file.clone(),
+            truncate_to,
+            remove: false,
+        }
+    }
+
+    /// Removes the upload along with the file of its payload.
+    pub fn remove(&self, index_uid: &str, uid: &str) -> Result<(), MeilisearchHttpError> {
+        let mut uploads = self.uploads.lock().unwrap();
+        let uid = get_unlocked_upload(&mut uploads, index_uid, uid)?.view.uid;
+        uploads.remove(&uid);
+        Ok(())
+    }
+}
+
+fn get_upload<'a>(
+    uploads: &'a mut HashMap<Uuid, DocumentUpload>,
+    index_uid: &str,
+    uid: &str,
+) -> Result<&'a mut DocumentUpload, MeilisearchHttpError> {
+    let not_found = || MeilisearchHttpError::DocumentUploadNotFound(uid.to_string());
+    let uid = Uuid::parse_str(uid).map_err(|_| not_found())?;
+    remove_expired_uploads(uploads, OffsetDateTime::now_utc());
+    uploads.get_mut(&uid).filter(|upload| upload.view.index_uid == index_uid).ok_or_else(not_found)
+}
+
+fn get_unlocked_upload<'a>(
+    uploads: &'a mut HashMap<Uuid, DocumentUpload>,
+    index_uid: &str,
+    uid: &str,
+) -> Result<&'a mut DocumentUpload, MeilisearchHttpError> {
+    let upload = get_upload(uploads, index_uid, uid)?;
+    if upload.locked {
+        return Err(MeilisearchHttpError::DocumentUploadInProgress(uid.to_string()));
+    }
+    Ok(upload)
+}
+
+/// Removes the payloads of the uploads of a previous run of the instance, which are lost.
+pub fn remove_previous_uploads(uploads_path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_dir_all(uploads_path) {
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+fn receive_error(error: std::io::Error) -> MeilisearchHttpError {
+    MeilisearchHttpError::Payload(ReceivePayload(Box::new(error)))
+}
+
+fn remove_expired_uploads(uploads: &mut HashMap<Uuid, DocumentUpload>, now: OffsetDateTime) {
+    uploads.retain(|_, upload| upload.locked || now - upload.view.updated_at < UPLOAD_TTL);
+}
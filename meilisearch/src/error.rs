@@ -16,6 +16,14 @@ pub enum MeilisearchHttpError {
     AlreadyUsedLogRoute,
     #[error("The Content-Type `{0}` does not support the use of a csv delimiter. The csv delimiter can only be used with the Content-Type `text/csv`.")]
     CsvDelimiterWithWrongContentType(String),
+    #[error("The Content-Type `{0}` does not support the use of a `contentField`. The `contentField` can only be used with the Content-Type `application/json` or `application/x-ndjson`.")]
+    ContentFieldWithWrongContentType(String),
+    #[error("The Content-Type `{0}` does not support the use of a `documentId`. The `documentId` can only be used with the Content-Type `application/pdf` or `text/html`.")]
+    DocumentIdWithWrongContentType(String),
+    #[error("The `textField` can only be used with the Content-Type `application/pdf` or `text/html`, or along with a `contentField`.")]
+    TextFieldWithoutExtraction,
+    #[error("A `documentId` is missing. It is required to add the document of a {0} payload.")]
+    MissingDocumentIdForExtraction(PayloadType),
     #[error(
         "The Content-Type `{0}` is invalid. Accepted values for the Content-Type header are: {}",
         .1.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
@@ -23,6 +31,8 @@ pub enum MeilisearchHttpError {
     InvalidContentType(String, Vec<String>),
     #[error("Document `{0}` not found.")]
     DocumentNotFound(String),
+    #[error("Attachment `{0}` of the document `{1}` not found.")]
+    AttachmentNotFound(String, String),
     #[error("Sending an empty filter is forbidden.")]
     EmptyFilter,
     #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
@@ -33,12 +43,18 @@ pub enum MeilisearchHttpError {
     TooManySearchRequests(usize),
     #[error("Internal error: Search limiter is down.")]
     SearchLimiterIsDown,
+    #[error("Internal error: A search panicked.")]
+    SearchPanicked,
     #[error("The provided payload reached the size limit. The maximum accepted payload size is {}.",  Byte::from_u64(*.0 as u64).get_appropriate_unit(UnitType::Binary))]
     PayloadTooLarge(usize),
     #[error("Two indexes must be given for each swap. The list `[{}]` contains {} indexes.",
         .0.iter().map(|uid| format!("\"{uid}\"")).collect::<Vec<_>>().join(", "), .0.len()
     )]
     SwapIndexPayloadWrongLength(Vec<IndexUid>),
+    #[error("Unknown setting `{0}` in the settings to copy. Expected one of: {}.",
+        .1.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", ")
+    )]
+    UnknownSettingToCopy(String, &'static [&'static str]),
     #[error(transparent)]
     IndexUid(#[from] IndexUidFormatError),
     #[error(transparent)]
@@ -59,6 +75,32 @@ pub enum MeilisearchHttpError {
     Join(#[from] JoinError),
     #[error("Invalid request: missing `hybrid` parameter when both `q` and `vector` are present.")]
     MissingSearchHybrid,
+    #[error("The upstream `{0}` failed to process the queries: {1}")]
+    FederationUpstream(String, String),
+    #[error("The index `{0}` is held by an upstream that can't hide its PII attributes, only the keys with the `documents.pii` action can search it.")]
+    FederationPiiNotAllowed(String),
+    #[error("Partition family `{0}` not found.")]
+    PartitionFamilyNotFound(String),
+    #[error("Search session `{0}` not found. It may have expired, the sessions expire five minutes after their last search.")]
+    SearchSessionNotFound(String),
+    #[error("Search job `{0}` not found. Its result may have expired, the results are kept for an hour after the end of the job.")]
+    SearchJobNotFound(String),
+    #[error("The result of the search job is bigger than the {0} bytes kept for the results of all the search jobs. Retrieve fewer hits or fewer attributes.")]
+    SearchJobResultTooLarge(usize),
+    #[error("Too many search jobs are waiting or running at the same time: {0}. Retry after a running job is finished.")]
+    TooManySearchJobs(usize),
+    #[error("Document upload `{0}` not found. It may have expired, the uploads expire a day after their last part.")]
+    DocumentUploadNotFound(String),
+    #[error("The document upload `{0}` is already receiving a part or being completed. Retry after the end of this operation.")]
+    DocumentUploadInProgress(String),
+    #[error("The part starts at the offset {received}, but the document upload is at the offset {expected}. Resume the upload from the offset {expected}.")]
+    DocumentUploadOffsetMismatch { expected: u64, received: u64 },
+    #[error("Too many document uploads are in progress at the same time: {0}. Retry after completing or deleting an upload.")]
+    TooManyDocumentUploads(usize),
+    #[error("The document at position {0} has no valid timestamp in its `{1}` field. Expected an RFC 3339 date, a `YYYY-MM-DD` date, or a number of seconds since the epoch.")]
+    InvalidPartitionTimestamp(usize, String),
+    #[error("Invalid value for `{0}`: expected an RFC 3339 date, a `YYYY-MM-DD` date, or a number of seconds since the epoch, but found `{1}`.")]
+    InvalidPartitionRange(&'static str, Value),
 }
 
 impl ErrorCode for MeilisearchHttpError {
@@ -67,15 +109,24 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::MissingContentType(_) => Code::MissingContentType,
             MeilisearchHttpError::AlreadyUsedLogRoute => Code::BadRequest,
             MeilisearchHttpError::CsvDelimiterWithWrongContentType(_) => Code::InvalidContentType,
+            MeilisearchHttpError::ContentFieldWithWrongContentType(_) => {
+                Code::InvalidDocumentContentField
+            }
+            MeilisearchHttpError::DocumentIdWithWrongContentType(_) => Code::InvalidDocumentId,
+            MeilisearchHttpError::TextFieldWithoutExtraction => Code::InvalidDocumentTextField,
+            MeilisearchHttpError::MissingDocumentIdForExtraction(_) => Code::MissingDocumentId,
             MeilisearchHttpError::MissingPayload(_) => Code::MissingPayload,
             MeilisearchHttpError::InvalidContentType(_, _) => Code::InvalidContentType,
             MeilisearchHttpError::DocumentNotFound(_) => Code::DocumentNotFound,
+            MeilisearchHttpError::AttachmentNotFound(..) => Code::AttachmentNotFound,
             MeilisearchHttpError::EmptyFilter => Code::InvalidDocumentFilter,
             MeilisearchHttpError::InvalidExpression(_, _) => Code::InvalidSearchFilter,
             MeilisearchHttpError::PayloadTooLarge(_) => Code::PayloadTooLarge,
             MeilisearchHttpError::TooManySearchRequests(_) => Code::TooManySearchRequests,
             MeilisearchHttpError::SearchLimiterIsDown => Code::Internal,
+            MeilisearchHttpError::SearchPanicked => Code::Internal,
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::InvalidSwapIndexes,
+            MeilisearchHttpError::UnknownSettingToCopy(_, _) => Code::InvalidSettingsCopySettings,
             MeilisearchHttpError::IndexUid(e) => e.error_code(),
             MeilisearchHttpError::SerdeJson(_) => Code::Internal,
             MeilisearchHttpError::HeedError(_) => Code::Internal,
@@ -86,6 +137,23 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
             MeilisearchHttpError::Join(_) => Code::Internal,
             MeilisearchHttpError::MissingSearchHybrid => Code::MissingSearchHybrid,
+            MeilisearchHttpError::FederationUpstream(_, _) => Code::FederationUpstreamError,
+            MeilisearchHttpError::FederationPiiNotAllowed(_) => Code::FederationPiiNotAllowed,
+            MeilisearchHttpError::PartitionFamilyNotFound(_) => Code::PartitionFamilyNotFound,
+            MeilisearchHttpError::SearchSessionNotFound(_) => Code::SearchSessionNotFound,
+            MeilisearchHttpError::SearchJobNotFound(_) => Code::SearchJobNotFound,
+            MeilisearchHttpError::SearchJobResultTooLarge(_) => Code::SearchJobResultTooLarge,
+            MeilisearchHttpError::TooManySearchJobs(_) => Code::TooManySearchJobs,
+            MeilisearchHttpError::DocumentUploadNotFound(_) => Code::DocumentUploadNotFound,
+            MeilisearchHttpError::DocumentUploadInProgress(_) => Code::DocumentUploadInProgress,
+            MeilisearchHttpError::DocumentUploadOffsetMismatch { .. } => {
+                Code::DocumentUploadOffsetMismatch
+            }
+            MeilisearchHttpError::TooManyDocumentUploads(_) => Code::TooManyDocumentUploads,
+            MeilisearchHttpError::InvalidPartitionTimestamp(_, _) => {
+                Code::InvalidPartitionTimestamp
+            }
+            MeilisearchHttpError::InvalidPartitionRange(_, _) => Code::InvalidPartitionRange,
         }
     }
 }
@@ -147,7 +147,7 @@ pub mod policies {
 
     enum TenantTokenOutcome {
         NotATenantToken,
-        Valid(Uuid, SearchRules),
+        Valid(Uuid, SearchRules, Option<Vec<String>>),
     }
 
     #[derive(thiserror::Error, Debug)]
@@ -239,10 +239,10 @@ pub mod policies {
                 return Ok(AuthFilter::default());
             }
 
-            let (key_uuid, search_rules) =
+            let (key_uuid, search_rules, principals) =
                 match ActionPolicy::<A>::authenticate_tenant_token(&auth, token) {
-                    Ok(TenantTokenOutcome::Valid(key_uuid, search_rules)) => {
-                        (key_uuid, Some(search_rules))
+                    Ok(TenantTokenOutcome::Valid(key_uuid, search_rules, principals)) => {
+                        (key_uuid, Some(search_rules), principals)
                     }
                     Ok(TenantTokenOutcome::NotATenantToken)
                     | Err(AuthError::InvalidTenantToken) => (
@@ -250,15 +250,20 @@ pub mod policies {
                             .map_err(|_e| AuthError::InvalidApiKey)?
                             .ok_or(AuthError::InvalidApiKey)?,
                         None,
+                        None,
                     ),
                     Err(e) => return Err(e),
                 };
 
             // check that the indexes are allowed
             let action = Action::from_repr(A).ok_or(AuthError::InternalInvalidAction(A))?;
-            let auth_filter = auth
-                .get_key_filters(key_uuid, search_rules)
+            let is_tenant_token = search_rules.is_some();
+            let mut auth_filter = auth
+                .get_key_filters(key_uuid, search_rules, principals)
                 .map_err(|_e| AuthError::InvalidApiKey)?;
+            if is_tenant_token {
+                auth_filter.set_tenant_token(token);
+            }
 
             // First check if the index is authorized in the tenant token, this is a public
             // information, we can return a nice error message.
@@ -327,7 +332,7 @@ pub mod policies {
                 }
             }
 
-            Ok(TenantTokenOutcome::Valid(uid, data.claims.search_rules))
+            Ok(TenantTokenOutcome::Valid(uid, data.claims.search_rules, data.claims.principals))
         }
     }
 
@@ -337,5 +342,7 @@ pub mod policies {
         search_rules: SearchRules,
         exp: Option<i64>,
         api_key_uid: Uuid,
+        #[serde(default)]
+        principals: Option<Vec<String>>,
     }
 }
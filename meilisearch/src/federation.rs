@@ -0,0 +1,183 @@
+//! The federation proxy mode lets an instance route the queries of a `/multi-search` request
+//! to the remote instances holding the queried indexes, so that very large deployments can split
+//! their indexes across several machines behind a single endpoint.
+//!
+//! The upstreams are configured with the `--experimental-federation-upstreams` option, each one
+//! declaring the indexes it holds. The queries on the other indexes are processed locally.
+//! An upstream that doesn't answer in time fails the queries forwarded to it.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::error::MeilisearchHttpError;
+
+/// The maximum time to establish a connection with an upstream, when it doesn't specify one.
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// The maximum time to wait for the results of an upstream, when it doesn't specify one.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A remote instance to which the queries on some of the indexes are forwarded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Upstream {
+    /// The address of the remote instance, e.g. `http://node-2:7700`.
+    pub url: Url,
+    /// The key sent to the remote instance, it must be allowed to search the forwarded indexes.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// The patterns of the indexes held by the remote instance.
+    pub indexes: Vec<IndexUidPattern>,
+    /// The maximum time to establish a connection with the remote instance, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_ms: Option<u64>,
+    /// The maximum time to wait for the results of the remote instance, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl Upstream {
+    fn connect_timeout(&self) -> Duration {
+        self.connect_timeout_ms.map_or(DEFAULT_CONNECT_TIMEOUT, Duration::from_millis)
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout_ms.map_or(DEFAULT_TIMEOUT, Duration::from_millis)
+    }
+}
+
+/// The list of upstreams, given as a JSON array on the command line.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Upstreams(pub Vec<Upstream>);
+
+impl FromStr for Upstreams {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+impl fmt::Display for Upstreams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+/// Forwards the queries to the upstreams holding their indexes.
+pub struct Federation {
+    /// The clients sending the queries, one per upstream as the timeouts differ.
+    clients: Vec<reqwest::Client>,
+    upstreams: Vec<Upstream>,
+}
+
+impl Federation {
+    pub fn new(upstreams: Upstreams) -> Self {
+        let clients = upstreams
+            .0
+            .iter()
+            .map(|upstream| {
+                reqwest::Client::builder()
+                    .connect_timeout(upstream.connect_timeout())
+                    .timeout(upstream.timeout())
+                    .build()
+                    .expect("could not initialize the HTTP client of the federation")
+            })
+            .collect();
+        Federation { clients, upstreams: upstreams.0 }
+    }
+
+    /// Whether some upstreams are configured.
+    pub fn is_enabled(&self) -> bool {
+        !self.upstreams.is_empty()
+    }
+
+    /// Returns the upstream holding the given index, or `None` if the index is a local one.
+    pub fn upstream_of(&self, index_uid: &str) -> Option<usize> {
+        self.upstreams.iter().position(|upstream| {
+            upstream.indexes.iter().any(|pattern| pattern.matches_str(index_uid))
+        })
+    }
+
+    /// Sends the queries to the given upstream in a single `/multi-search` request
+    /// and returns its results, in the same order as the queries.
+    pub async fn multi_search(
+        &self,
+        upstream: usize,
+        queries: Vec<Value>,
+    ) -> Result<Vec<Value>, ResponseError> {
+        let client = &self.clients[upstream];
+        let upstream = &self.upstreams[upstream];
+        let url = format!("{}/multi-search", upstream.url.as_str().trim_end_matches('/'));
+        let error = |message: String| {
+            ResponseError::from(MeilisearchHttpError::FederationUpstream(
+                upstream.url.to_string(),
+                message,
+            ))
+        };
+
+        let request_error = |e: reqwest::Error| {
+            if e.is_timeout() {
+                error(format!("no response after {}ms", upstream.timeout().as_millis()))
+            } else if e.is_connect() {
+                error(format!("could not connect: {e}"))
+            } else {
+                error(e.to_string())
+            }
+        };
+
+        let queries_count = queries.len();
+        let mut request = client.post(url).json(&json!({ "queries": queries }));
+        if let Some(api_key) = &upstream.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request.send().await.map_err(request_error)?;
+        let status = response.status();
+        let mut body: Value = response.json().await.map_err(request_error)?;
+
+        if !status.is_success() {
+            let message = body["message"].as_str().unwrap_or("unknown error").to_string();
+            return Err(error(message));
+        }
+
+        match body["results"].take() {
+            Value::Array(results) if results.len() == queries_count => Ok(results),
+            _ => Err(error(String::from("unexpected response"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upstream_of_matches_the_index_patterns() {
+        let upstreams: Upstreams = r#"[
+            { "url": "http://node-2:7700", "api_key": "key", "indexes": ["movies", "logs-*"] },
+            { "url": "http://node-3:7700", "indexes": ["logs-2024-*"], "timeout_ms": 500 }
+        ]"#
+        .parse()
+        .unwrap();
+        assert_eq!(upstreams.0[0].api_key.as_deref(), Some("key"));
+        assert_eq!(upstreams.0[0].timeout(), DEFAULT_TIMEOUT);
+        assert_eq!(upstreams.0[1].timeout(), Duration::from_millis(500));
+        assert_eq!(upstreams.0[1].connect_timeout(), DEFAULT_CONNECT_TIMEOUT);
+
+        let federation = Federation::new(upstreams);
+        assert!(federation.is_enabled());
+        assert_eq!(federation.upstream_of("movies"), Some(0));
+        // the first matching upstream is used
+        assert_eq!(federation.upstream_of("logs-2024-01"), Some(0));
+        assert_eq!(federation.upstream_of("products"), None);
+        assert!(!Federation::new(Upstreams::default()).is_enabled());
+    }
+}
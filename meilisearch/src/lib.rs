@@ -2,14 +2,22 @@
 #[macro_use]
 pub mod error;
 pub mod analytics;
+pub mod document_uploads;
 #[macro_use]
 pub mod extractors;
+pub mod federation;
 pub mod metrics;
 pub mod middleware;
 pub mod option;
+pub mod partitions;
+pub mod remote_snapshot;
 pub mod routes;
 pub mod search;
+pub mod search_activity;
+pub mod search_jobs;
 pub mod search_queue;
+pub mod search_session;
+pub mod typo_statistics;
 
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
@@ -31,6 +39,7 @@ use analytics::Analytics;
 use anyhow::bail;
 use error::PayloadError;
 use extractors::payload::PayloadConfig;
+use federation::Federation;
 use index_scheduler::{IndexScheduler, IndexSchedulerOptions};
 use meilisearch_auth::AuthController;
 use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
@@ -42,6 +51,7 @@ use meilisearch_types::{compression, milli, VERSION_FILE_NAME};
 pub use option::Opt;
 use option::ScheduleSnapshot;
 use search_queue::SearchQueue;
+use search_session::SEARCH_SESSIONS;
 use tracing::{error, info_span};
 use tracing_subscriber::filter::Targets;
 
@@ -205,7 +215,9 @@ enum OnFailure {
 pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<AuthController>)> {
     let empty_db = is_empty_db(&opt.db_path);
     let (index_scheduler, auth_controller) = if let Some(ref snapshot_path) = opt.import_snapshot {
-        let snapshot_path_exists = snapshot_path.exists();
+        // a remote snapshot is only downloaded when the database is empty
+        let snapshot_path_exists =
+            snapshot_path.exists() || remote_snapshot::is_remote(snapshot_path);
         // the db is empty and the snapshot exists, import it
         if empty_db && snapshot_path_exists {
             match compression::from_tar_gz(snapshot_path, &opt.db_path) {
@@ -278,6 +290,13 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
             .unwrap();
     }
 
+    // The uploads are lost when the instance restarts
+    document_uploads::remove_previous_uploads(&opt.db_path.join(document_uploads::UPLOADS_DIR))?;
+
+    // The search sessions keep their index open, they are dropped once expired or before it is closed
+    SEARCH_SESSIONS.spawn_reaper();
+    index_scheduler.set_index_closing_hook(|index| SEARCH_SESSIONS.remove_index_sessions(index));
+
     Ok((index_scheduler, auth_controller))
 }
 
@@ -309,6 +328,9 @@ fn open_or_create_database_unchecked(
             cleanup_enabled: !opt.experimental_replication_parameters,
             max_number_of_tasks: 1_000_000,
             max_number_of_batched_tasks: opt.experimental_max_number_of_batched_tasks,
+            task_payload_retention: opt
+                .experimental_task_payload_retention_days
+                .map(|days| Duration::from_secs(days * 24 * 60 * 60)),
             index_growth_amount: byte_unit::Byte::from_str("10GiB").unwrap().as_u64() as usize,
             index_count: DEFAULT_INDEX_COUNT,
             instance_features,
@@ -391,10 +413,12 @@ fn import_dump(
     // try to process tasks while we're trying to import the indexes.
 
     // 4. Import the indexes.
+    let mut reindexed = Vec::new();
     for index_reader in dump_reader.indexes()? {
         let mut index_reader = index_reader?;
         let metadata = index_reader.metadata();
         tracing::info!("Importing index `{}`.", metadata.uid);
+        reindexed.push(metadata.uid.clone());
 
         let date = Some((metadata.created_at, metadata.updated_at));
         let index = index_scheduler.create_raw_index(&metadata.uid, date)?;
@@ -462,6 +486,21 @@ fn import_dump(
         let (task, file) = ret?;
         index_scheduler_dump.register_dumped_task(task, file)?;
     }
+
+    // 6. Report what was converted or left out while reading an old dump.
+    let dump::CompatReport { migrated, dropped } = dump_reader.take_compat_report();
+    if dropped.is_empty() {
+        tracing::info!(version = ?dump_reader.version(), ?migrated, ?reindexed, "Dump imported");
+    } else {
+        tracing::warn!(
+            version = ?dump_reader.version(),
+            ?migrated,
+            ?dropped,
+            ?reindexed,
+            "Dump imported, but some of its content could not be converted to the current version"
+        );
+    }
+
     Ok(index_scheduler_dump.finish()?)
 }
 
@@ -473,15 +512,21 @@ pub fn configure_data(
     (logs_route, logs_stderr): (LogRouteHandle, LogStderrHandle),
     analytics: Arc<dyn Analytics>,
 ) {
+    let search_threads = opt.experimental_search_threads.and_then(NonZeroUsize::new);
     let search_queue = SearchQueue::new(
         opt.experimental_search_queue_size,
-        available_parallelism().unwrap_or(NonZeroUsize::new(2).unwrap()),
+        search_threads
+            .unwrap_or_else(|| available_parallelism().unwrap_or(NonZeroUsize::new(2).unwrap())),
     );
     let http_payload_size_limit = opt.http_payload_size_limit.as_u64() as usize;
     config
         .app_data(index_scheduler)
         .app_data(auth)
         .app_data(web::Data::new(search_queue))
+        .app_data(web::Data::new(Federation::new(
+            opt.experimental_federation_upstreams.clone().unwrap_or_default(),
+        )))
+        .app_data(web::Data::new(opt.experimental_index_partitions.clone().unwrap_or_default()))
         .app_data(web::Data::from(analytics))
         .app_data(web::Data::new(logs_route))
         .app_data(web::Data::new(logs_stderr))
@@ -12,8 +12,8 @@ use is_terminal::IsTerminal;
 use meilisearch::analytics::Analytics;
 use meilisearch::option::LogMode;
 use meilisearch::{
-    analytics, create_app, setup_meilisearch, LogRouteHandle, LogRouteType, LogStderrHandle,
-    LogStderrType, Opt, SubscriberForSecondLayer,
+    analytics, create_app, remote_snapshot, setup_meilisearch, LogRouteHandle, LogRouteType,
+    LogStderrHandle, LogStderrType, Opt, SubscriberForSecondLayer,
 };
 use meilisearch_auth::{generate_master_key, AuthController, MASTER_KEY_MIN_SIZE};
 use mimalloc::MiMalloc;
@@ -72,7 +72,7 @@ fn on_panic(info: &std::panic::PanicInfo) {
 
 #[actix_web::main]
 async fn main() -> anyhow::Result<()> {
-    let (opt, config_read_from) = Opt::try_build()?;
+    let (mut opt, config_read_from) = Opt::try_build()?;
 
     std::panic::set_hook(Box::new(on_panic));
 
@@ -105,7 +105,9 @@ async fn main() -> anyhow::Result<()> {
         _ => (),
     }
 
+    let downloaded_snapshot = remote_snapshot::download_remote_snapshot(&mut opt).await?;
     let (index_scheduler, auth_controller) = setup_meilisearch(&opt)?;
+    drop(downloaded_snapshot);
 
     #[cfg(all(not(debug_assertions), feature = "analytics"))]
     let analytics = if !opt.no_analytics {
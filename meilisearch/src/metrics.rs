@@ -1,7 +1,8 @@
 use lazy_static::lazy_static;
 use prometheus::{
-    opts, register_histogram_vec, register_int_counter_vec, register_int_gauge,
-    register_int_gauge_vec, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
+    opts, register_gauge, register_gauge_vec, register_histogram_vec, register_int_counter_vec,
+    register_int_gauge, register_int_gauge_vec, Gauge, GaugeVec, HistogramVec, IntCounterVec,
+    IntGauge, IntGaugeVec,
 };
 
 lazy_static! {
@@ -46,7 +47,45 @@ lazy_static! {
     pub static ref MEILISEARCH_LAST_UPDATE: IntGauge =
         register_int_gauge!(opts!("meilisearch_last_update", "Meilisearch Last Update"))
             .expect("Can't create a metric");
+    pub static ref MEILISEARCH_SEARCH_THREADS: IntGauge = register_int_gauge!(opts!(
+        "meilisearch_search_threads",
+        "Meilisearch number of threads dedicated to the searches"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEXING_THREADS: IntGauge = register_int_gauge!(opts!(
+        "meilisearch_indexing_threads",
+        "Meilisearch number of threads dedicated to the indexing"
+    ))
+    .expect("Can't create a metric");
     pub static ref MEILISEARCH_IS_INDEXING: IntGauge =
         register_int_gauge!(opts!("meilisearch_is_indexing", "Meilisearch Is Indexing"))
             .expect("Can't create a metric");
+    pub static ref MEILISEARCH_TASK_QUEUE_LAG_SECONDS: Gauge = register_gauge!(opts!(
+        "meilisearch_task_queue_lag_seconds",
+        "Meilisearch time the oldest enqueued task has been waiting for"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_TASK_QUEUE_AVERAGE_LAG_SECONDS: Gauge = register_gauge!(opts!(
+        "meilisearch_task_queue_average_lag_seconds",
+        "Meilisearch average time the tasks finished during the last hour waited for"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_BATCHES_PER_HOUR: IntGauge = register_int_gauge!(opts!(
+        "meilisearch_batches_per_hour",
+        "Meilisearch number of batches finished during the last hour"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_TASK_PROCESSING_DURATION_SECONDS: GaugeVec = register_gauge_vec!(
+        opts!(
+            "meilisearch_task_processing_duration_seconds",
+            "Meilisearch processing durations of the tasks finished during the last hour"
+        ),
+        &["kind", "quantile"]
+    )
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEXED_PAYLOAD_BYTES: IntGauge = register_int_gauge!(opts!(
+        "meilisearch_indexed_payload_bytes",
+        "Meilisearch number of bytes of document payloads indexed since the launch"
+    ))
+    .expect("Can't create a metric");
 }
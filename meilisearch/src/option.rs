@@ -12,6 +12,10 @@ use std::{env, fmt, fs};
 use byte_unit::{Byte, ParseError, UnitType};
 use clap::Parser;
 use meilisearch_types::features::InstanceTogglableFeatures;
+use meilisearch_types::milli::attachments::{
+    AttachmentsQuotas, DEFAULT_MAX_ATTACHMENT_SIZE, DEFAULT_MAX_DOCUMENT_ATTACHMENTS_SIZE,
+};
+use meilisearch_types::milli::pii::PiiCipher;
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::ThreadPoolNoAbortBuilder;
 use rustls::server::{
@@ -23,6 +27,9 @@ use serde::{Deserialize, Serialize};
 use sysinfo::{MemoryRefreshKind, RefreshKind, System};
 use url::Url;
 
+use crate::federation::Upstreams;
+use crate::partitions::PartitionFamilies;
+
 const POSSIBLE_ENV: [&str; 2] = ["development", "production"];
 
 const MEILI_DB_PATH: &str = "MEILI_DB_PATH";
@@ -42,6 +49,7 @@ const MEILI_SSL_REQUIRE_AUTH: &str = "MEILI_SSL_REQUIRE_AUTH";
 const MEILI_SSL_RESUMPTION: &str = "MEILI_SSL_RESUMPTION";
 const MEILI_SSL_TICKETS: &str = "MEILI_SSL_TICKETS";
 const MEILI_IMPORT_SNAPSHOT: &str = "MEILI_IMPORT_SNAPSHOT";
+const MEILI_IMPORT_SNAPSHOT_SHA256: &str = "MEILI_IMPORT_SNAPSHOT_SHA256";
 const MEILI_IGNORE_MISSING_SNAPSHOT: &str = "MEILI_IGNORE_MISSING_SNAPSHOT";
 const MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS: &str = "MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS";
 const MEILI_SNAPSHOT_DIR: &str = "MEILI_SNAPSHOT_DIR";
@@ -56,10 +64,15 @@ const MEILI_EXPERIMENTAL_REPLICATION_PARAMETERS: &str = "MEILI_EXPERIMENTAL_REPL
 const MEILI_EXPERIMENTAL_ENABLE_LOGS_ROUTE: &str = "MEILI_EXPERIMENTAL_ENABLE_LOGS_ROUTE";
 const MEILI_EXPERIMENTAL_ENABLE_METRICS: &str = "MEILI_EXPERIMENTAL_ENABLE_METRICS";
 const MEILI_EXPERIMENTAL_SEARCH_QUEUE_SIZE: &str = "MEILI_EXPERIMENTAL_SEARCH_QUEUE_SIZE";
+const MEILI_EXPERIMENTAL_SEARCH_THREADS: &str = "MEILI_EXPERIMENTAL_SEARCH_THREADS";
 const MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE: &str =
     "MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE";
 const MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS: &str =
     "MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS";
+const MEILI_EXPERIMENTAL_TASK_PAYLOAD_RETENTION_DAYS: &str =
+    "MEILI_EXPERIMENTAL_TASK_PAYLOAD_RETENTION_DAYS";
+const MEILI_EXPERIMENTAL_FEDERATION_UPSTREAMS: &str = "MEILI_EXPERIMENTAL_FEDERATION_UPSTREAMS";
+const MEILI_EXPERIMENTAL_INDEX_PARTITIONS: &str = "MEILI_EXPERIMENTAL_INDEX_PARTITIONS";
 
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
 const DEFAULT_DB_PATH: &str = "./data.ms";
@@ -73,6 +86,9 @@ const DEFAULT_DUMP_DIR: &str = "dumps/";
 
 const MEILI_MAX_INDEXING_MEMORY: &str = "MEILI_MAX_INDEXING_MEMORY";
 const MEILI_MAX_INDEXING_THREADS: &str = "MEILI_MAX_INDEXING_THREADS";
+const MEILI_MAX_ATTACHMENT_SIZE: &str = "MEILI_MAX_ATTACHMENT_SIZE";
+const MEILI_MAX_DOCUMENT_ATTACHMENTS_SIZE: &str = "MEILI_MAX_DOCUMENT_ATTACHMENTS_SIZE";
+const MEILI_PII_ENCRYPTION_KEY: &str = "MEILI_PII_ENCRYPTION_KEY";
 const DEFAULT_LOG_EVERY_N: usize = 100_000;
 
 // Each environment (index and task-db) is taking space in the virtual address space.
@@ -265,9 +281,17 @@ pub struct Opt {
     pub ssl_tickets: bool,
 
     /// Launches Meilisearch after importing a previously-generated snapshot at the given filepath.
+    ///
+    /// The snapshot can also be an `https://` or `s3://` URL, in which case it is downloaded
+    /// before being imported, and its checksum must be given with `--import-snapshot-sha256`.
     #[clap(long, env = MEILI_IMPORT_SNAPSHOT)]
     pub import_snapshot: Option<PathBuf>,
 
+    /// The SHA-256 checksum, in hexadecimal, of the remote snapshot given to `--import-snapshot`.
+    /// The downloaded snapshot is only imported if its checksum matches.
+    #[clap(long, env = MEILI_IMPORT_SNAPSHOT_SHA256, requires = "import_snapshot")]
+    pub import_snapshot_sha256: Option<String>,
+
     /// Prevents a Meilisearch instance from throwing an error when `--import-snapshot`
     /// does not point to a valid snapshot file.
     ///
@@ -355,6 +379,15 @@ pub struct Opt {
     #[serde(default)]
     pub experimental_search_queue_size: usize,
 
+    /// Experimental search threads.
+    ///
+    /// Sets the number of threads dedicated to the search requests. They are distinct from the indexing
+    /// threads, so that a heavy indexing batch can't starve the searches.
+    /// By default, the number of available cores is used.
+    #[clap(long, env = MEILI_EXPERIMENTAL_SEARCH_THREADS)]
+    #[serde(default)]
+    pub experimental_search_threads: Option<usize>,
+
     /// Experimental logs mode feature. For more information, see: <https://github.com/orgs/meilisearch/discussions/723>
     ///
     /// Change the mode of the logs on the console.
@@ -389,6 +422,25 @@ pub struct Opt {
     #[serde(default = "default_limit_batched_tasks")]
     pub experimental_max_number_of_batched_tasks: usize,
 
+    /// Experimentally keeps the payloads of the succeeded document additions for the given number of days,
+    /// so that they can be replayed with the `POST /tasks/{uid}/replay` route.
+    #[clap(long, env = MEILI_EXPERIMENTAL_TASK_PAYLOAD_RETENTION_DAYS)]
+    #[serde(default)]
+    pub experimental_task_payload_retention_days: Option<u64>,
+
+    /// Experimentally forwards the `/multi-search` queries on some indexes to the remote instances holding them,
+    /// given as a JSON array, e.g. `[{ "url": "http://node-2:7700", "api_key": "<key>", "indexes": ["movies*"] }]`.
+    /// Each upstream can set its `connect_timeout_ms` and `timeout_ms`, 5 and 30 seconds by default.
+    #[clap(long, env = MEILI_EXPERIMENTAL_FEDERATION_UPSTREAMS)]
+    #[serde(default)]
+    pub experimental_federation_upstreams: Option<Upstreams>,
+
+    /// Experimentally partitions the documents of some index families across one index per period of time,
+    /// given as a JSON array, e.g. `[{ "family": "logs", "timestamp_field": "timestamp", "granularity": "month", "retention": 12 }]`.
+    #[clap(long, env = MEILI_EXPERIMENTAL_INDEX_PARTITIONS)]
+    #[serde(default)]
+    pub experimental_index_partitions: Option<PartitionFamilies>,
+
     #[serde(flatten)]
     #[clap(flatten)]
     pub indexer_options: IndexerOpts,
@@ -462,6 +514,9 @@ impl Opt {
             max_task_db_size: _,
             http_payload_size_limit,
             experimental_max_number_of_batched_tasks,
+            experimental_task_payload_retention_days,
+            experimental_federation_upstreams,
+            experimental_index_partitions,
             ssl_cert_path,
             ssl_key_path,
             ssl_auth_path,
@@ -475,6 +530,7 @@ impl Opt {
             log_level,
             indexer_options,
             import_snapshot: _,
+            import_snapshot_sha256: _,
             ignore_missing_snapshot: _,
             ignore_snapshot_if_db_exists: _,
             import_dump: _,
@@ -485,6 +541,7 @@ impl Opt {
             no_analytics,
             experimental_enable_metrics,
             experimental_search_queue_size,
+            experimental_search_threads,
             experimental_logs_mode,
             experimental_enable_logs_route,
             experimental_replication_parameters,
@@ -518,6 +575,24 @@ impl Opt {
             MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS,
             experimental_max_number_of_batched_tasks.to_string(),
         );
+        if let Some(retention_days) = experimental_task_payload_retention_days {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_TASK_PAYLOAD_RETENTION_DAYS,
+                retention_days.to_string(),
+            );
+        }
+        if let Some(upstreams) = experimental_federation_upstreams {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_FEDERATION_UPSTREAMS,
+                upstreams.to_string(),
+            );
+        }
+        if let Some(partitions) = experimental_index_partitions {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_INDEX_PARTITIONS,
+                partitions.to_string(),
+            );
+        }
         if let Some(ssl_cert_path) = ssl_cert_path {
             export_to_env_if_not_present(MEILI_SSL_CERT_PATH, ssl_cert_path);
         }
@@ -548,6 +623,12 @@ impl Opt {
             MEILI_EXPERIMENTAL_SEARCH_QUEUE_SIZE,
             experimental_search_queue_size.to_string(),
         );
+        if let Some(experimental_search_threads) = experimental_search_threads {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_SEARCH_THREADS,
+                experimental_search_threads.to_string(),
+            );
+        }
         export_to_env_if_not_present(
             MEILI_EXPERIMENTAL_LOGS_MODE,
             experimental_logs_mode.to_string(),
@@ -636,6 +717,21 @@ pub struct IndexerOpts {
     #[serde(default)]
     pub max_indexing_threads: MaxThreads,
 
+    /// Sets the maximum size of an attachment given in the `_attachments` field of a document.
+    #[clap(long, env = MEILI_MAX_ATTACHMENT_SIZE, default_value_t = default_max_attachment_size())]
+    #[serde(default = "default_max_attachment_size")]
+    pub max_attachment_size: Byte,
+
+    /// Sets the maximum size of all the attachments given in the `_attachments` field of a document.
+    #[clap(long, env = MEILI_MAX_DOCUMENT_ATTACHMENTS_SIZE, default_value_t = default_max_document_attachments_size())]
+    #[serde(default = "default_max_document_attachments_size")]
+    pub max_document_attachments_size: Byte,
+
+    /// Sets the secret from which the key encrypting the values of the PII attributes is derived.
+    /// It is required to add documents to an index with PII attributes, and must not change afterward.
+    #[clap(long, env = MEILI_PII_ENCRYPTION_KEY)]
+    pub pii_encryption_key: Option<String>,
+
     /// Whether or not we want to determine the budget of virtual memory address space we have available dynamically
     /// (the default), or statically.
     ///
@@ -649,7 +745,14 @@ pub struct IndexerOpts {
 impl IndexerOpts {
     /// Exports the values to their corresponding env vars if they are not set.
     pub fn export_to_env(self) {
-        let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } = self;
+        let IndexerOpts {
+            max_indexing_memory,
+            max_indexing_threads,
+            max_attachment_size,
+            max_document_attachments_size,
+            pii_encryption_key,
+            skip_index_budget: _,
+        } = self;
         if let Some(max_indexing_memory) = max_indexing_memory.0 {
             export_to_env_if_not_present(
                 MEILI_MAX_INDEXING_MEMORY,
@@ -660,6 +763,14 @@ impl IndexerOpts {
             MEILI_MAX_INDEXING_THREADS,
             max_indexing_threads.0.to_string(),
         );
+        export_to_env_if_not_present(MEILI_MAX_ATTACHMENT_SIZE, max_attachment_size.to_string());
+        export_to_env_if_not_present(
+            MEILI_MAX_DOCUMENT_ATTACHMENTS_SIZE,
+            max_document_attachments_size.to_string(),
+        );
+        if let Some(pii_encryption_key) = pii_encryption_key {
+            export_to_env_if_not_present(MEILI_PII_ENCRYPTION_KEY, pii_encryption_key);
+        }
     }
 }
 
@@ -678,6 +789,12 @@ impl TryFrom<&IndexerOpts> for IndexerConfig {
             thread_pool: Some(thread_pool),
             max_positions_per_attributes: None,
             skip_index_budget: other.skip_index_budget,
+            attachments_quotas: AttachmentsQuotas {
+                max_attachment_size: other.max_attachment_size.as_u64() as usize,
+                max_document_attachments_size: other.max_document_attachments_size.as_u64()
+                    as usize,
+            },
+            pii_cipher: other.pii_encryption_key.as_deref().map(PiiCipher::new),
             ..Default::default()
         })
     }
@@ -857,6 +974,14 @@ fn default_http_payload_size_limit() -> Byte {
     Byte::from_str(DEFAULT_HTTP_PAYLOAD_SIZE_LIMIT).unwrap()
 }
 
+fn default_max_attachment_size() -> Byte {
+    Byte::from_u64(DEFAULT_MAX_ATTACHMENT_SIZE as u64)
+}
+
+fn default_max_document_attachments_size() -> Byte {
+    Byte::from_u64(DEFAULT_MAX_DOCUMENT_ATTACHMENTS_SIZE as u64)
+}
+
 fn default_limit_batched_tasks() -> usize {
     usize::MAX
 }
@@ -0,0 +1,271 @@
+//! Time-partitioned index families spread the documents of a family, e.g. `logs`, across one
+//! index per period, e.g. `logs-2024-06`, according to the value of a timestamp field.
+//!
+//! The families are configured with the `--experimental-index-partitions` option. The documents
+//! written to a family are routed to the index of their period, the partitions older than the
+//! retention of the family are deleted when new documents are written to it, and a search on a
+//! family only queries the partitions overlapping the requested range of dates.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
+use time::format_description::FormatItem;
+use time::macros::format_description;
+use time::{Date, Duration, Month, OffsetDateTime, UtcOffset};
+
+const DATE_FORMAT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day]");
+
+/// The period covered by each partition of a family.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Granularity {
+    /// One partition per day, e.g. `logs-2024-06-15`.
+    Day,
+    /// One partition per month, e.g. `logs-2024-06`.
+    #[default]
+    Month,
+}
+
+impl Granularity {
+    /// Returns the first day of the period containing the given date.
+    fn period_start(self, date: Date) -> Date {
+        match self {
+            Granularity::Day => date,
+            Granularity::Month => date.replace_day(1).unwrap(),
+        }
+    }
+
+    /// Returns the first day of the period `count` periods before the one of the given date.
+    fn periods_before(self, date: Date, count: u32) -> Option<Date> {
+        match self {
+            Granularity::Day => date.checked_sub(Duration::days(count.into())),
+            Granularity::Month => {
+                let months = date.year() * 12 + date.month() as i32 - 1 - count as i32;
+                let month = Month::try_from((months.rem_euclid(12) + 1) as u8).ok()?;
+                Date::from_calendar_date(months.div_euclid(12), month, 1).ok()
+            }
+        }
+    }
+
+    fn format(self, date: Date) -> String {
+        match self {
+            Granularity::Day => {
+                format!("{:04}-{:02}-{:02}", date.year(), date.month() as u8, date.day())
+            }
+            Granularity::Month => format!("{:04}-{:02}", date.year(), date.month() as u8),
+        }
+    }
+
+    fn parse(self, period: &str) -> Option<Date> {
+        match self {
+            Granularity::Day => Date::parse(period, DATE_FORMAT).ok(),
+            Granularity::Month => Date::parse(&format!("{period}-01"), DATE_FORMAT).ok(),
+        }
+    }
+}
+
+/// A family of indexes partitioned by the value of a timestamp field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PartitionFamily {
+    /// The name of the family, used as the prefix of the uids of its partitions.
+    pub family: String,
+    /// The field holding the timestamp of the documents, either an RFC 3339 date,
+    /// a `YYYY-MM-DD` date, or a number of seconds since the epoch.
+    pub timestamp_field: String,
+    /// The period covered by each partition, a month by default.
+    #[serde(default)]
+    pub granularity: Granularity,
+    /// The number of partitions kept, counting the current one. All of them are kept by default.
+    #[serde(default)]
+    pub retention: Option<u32>,
+}
+
+impl PartitionFamily {
+    /// Returns the uid of the partition holding the documents of the given date.
+    pub fn partition_uid(&self, date: OffsetDateTime) -> String {
+        format!("{}-{}", self.family, self.granularity.format(utc_date(date)))
+    }
+
+    /// Returns the first day of the given partition, or `None` if the index doesn't
+    /// belong to the family.
+    fn partition_start(&self, index_uid: &str) -> Option<Date> {
+        let period = index_uid.strip_prefix(&self.family)?.strip_prefix('-')?;
+        let start = self.granularity.parse(period)?;
+        // the uids are only recognized in their canonical form
+        (self.granularity.format(start) == period).then_some(start)
+    }
+
+    /// Returns the first day of the oldest partition kept by the retention, if any.
+    fn retention_start(&self, now: OffsetDateTime) -> Option<Date> {
+        let retention = self.retention?;
+        let current = self.granularity.period_start(utc_date(now));
+        self.granularity.periods_before(current, retention.saturating_sub(1))
+    }
+
+    /// Whether the partition of the given date is older than the retention of the family.
+    pub fn is_expired(&self, date: OffsetDateTime, now: OffsetDateTime) -> bool {
+        self.retention_start(now)
+            .map_or(false, |start| self.granularity.period_start(utc_date(date)) < start)
+    }
+
+    /// Returns the partitions among the given indexes that are older than the retention.
+    pub fn expired_partitions<'a>(
+        &self,
+        index_uids: impl IntoIterator<Item = &'a str>,
+        now: OffsetDateTime,
+    ) -> Vec<String> {
+        let Some(retention_start) = self.retention_start(now) else { return Vec::new() };
+        index_uids
+            .into_iter()
+            .filter(|uid| self.partition_start(uid).map_or(false, |start| start < retention_start))
+            .map(String::from)
+            .collect()
+    }
+
+    /// Returns the partitions among the given indexes overlapping the range of dates,
+    /// from the most recent to the oldest.
+    pub fn partitions_in_range<'a>(
+        &self,
+        index_uids: impl IntoIterator<Item = &'a str>,
+        from: Option<OffsetDateTime>,
+        to: Option<OffsetDateTime>,
+    ) -> Vec<String> {
+        let from = from.map(|from| self.granularity.period_start(utc_date(from)));
+        let to = to.map(|to| self.granularity.period_start(utc_date(to)));
+        let mut partitions: Vec<_> = index_uids
+            .into_iter()
+            .filter_map(|uid| Some((self.partition_start(uid)?, uid)))
+            .filter(|(start, _)| from.map_or(true, |from| *start >= from))
+            .filter(|(start, _)| to.map_or(true, |to| *start <= to))
+            .collect();
+        partitions.sort_unstable_by(|a, b| b.cmp(a));
+        partitions.into_iter().map(|(_, uid)| uid.to_string()).collect()
+    }
+}
+
+/// The partitions are always cut at midnight UTC.
+fn utc_date(date: OffsetDateTime) -> Date {
+    date.to_offset(UtcOffset::UTC).date()
+}
+
+/// Parses an RFC 3339 date, a `YYYY-MM-DD` date, or a number of seconds since the epoch.
+pub fn parse_timestamp(value: &Value) -> Option<OffsetDateTime> {
+    match value {
+        Value::Number(number) => {
+            OffsetDateTime::from_unix_timestamp(number.as_f64()?.floor() as i64).ok()
+        }
+        Value::String(string) => OffsetDateTime::parse(string, &Rfc3339).ok().or_else(|| {
+            let date = Date::parse(string, DATE_FORMAT).ok()?;
+            Some(date.midnight().assume_utc())
+        }),
+        _ => None,
+    }
+}
+
+/// The list of partition families, given as a JSON array on the command line.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PartitionFamilies(pub Vec<PartitionFamily>);
+
+impl PartitionFamilies {
+    /// Returns the family of the given name, if it is configured.
+    pub fn family(&self, name: &str) -> Option<&PartitionFamily> {
+        self.0.iter().find(|family| family.family == name)
+    }
+}
+
+impl FromStr for PartitionFamilies {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+impl fmt::Display for PartitionFamilies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn families() -> PartitionFamilies {
+        r#"[
+            { "family": "logs", "timestamp_field": "timestamp", "retention": 3 },
+            { "family": "events", "timestamp_field": "at", "granularity": "day" }
+        ]"#
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn route_documents_by_timestamp() {
+        let families = families();
+        let logs = families.family("logs").unwrap();
+        let events = families.family("events").unwrap();
+        assert!(families.family("logs-2024-06").is_none());
+
+        let partition = |family: &PartitionFamily, value| {
+            parse_timestamp(&value).map(|timestamp| family.partition_uid(timestamp))
+        };
+        assert_eq!(partition(logs, json!("2024-06-15T10:00:00Z")).unwrap(), "logs-2024-06");
+        assert_eq!(partition(logs, json!("2024-06-30T23:30:00-02:00")).unwrap(), "logs-2024-07");
+        assert_eq!(partition(logs, json!("2024-06-15")).unwrap(), "logs-2024-06");
+        assert_eq!(partition(logs, json!(1718445600)).unwrap(), "logs-2024-06");
+        assert_eq!(partition(events, json!(1718445600.5)).unwrap(), "events-2024-06-15");
+        assert_eq!(partition(logs, json!("yesterday")), None);
+        assert_eq!(partition(logs, json!(null)), None);
+    }
+
+    #[test]
+    fn retention() {
+        let families = families();
+        let logs = families.family("logs").unwrap();
+        let events = families.family("events").unwrap();
+        let now = datetime!(2024-02-10 12:00 UTC);
+
+        let uids =
+            ["logs-2023-11", "logs-2023-12", "logs-2024-01", "logs-2024-02", "logs", "logs-x"];
+        assert_eq!(logs.expired_partitions(uids, now), vec!["logs-2023-11"]);
+        assert!(logs.is_expired(datetime!(2023-11-30 23:59 UTC), now));
+        assert!(!logs.is_expired(datetime!(2023-12-01 00:00 UTC), now));
+        // the families without retention keep all their partitions
+        assert!(events.expired_partitions(["events-2001-01-01"], now).is_empty());
+    }
+
+    #[test]
+    fn partitions_in_range() {
+        let families = families();
+        let logs = families.family("logs").unwrap();
+        let events = families.family("events").unwrap();
+
+        let uids = ["logs-2024-01", "logs-2024-03", "logs-2024-02", "movies", "events-2024-02-01"];
+        assert_eq!(
+            logs.partitions_in_range(uids, None, None),
+            vec!["logs-2024-03", "logs-2024-02", "logs-2024-01"]
+        );
+        assert_eq!(
+            logs.partitions_in_range(
+                uids,
+                Some(datetime!(2024-02-20 00:00 UTC)),
+                Some(datetime!(2024-03-01 00:00 UTC))
+            ),
+            vec!["logs-2024-03", "logs-2024-02"]
+        );
+        assert_eq!(
+            events.partitions_in_range(uids, Some(datetime!(2024-02-02 00:00 UTC)), None),
+            Vec::<String>::new()
+        );
+    }
+}
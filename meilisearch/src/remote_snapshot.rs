@@ -0,0 +1,119 @@
+//! Downloads the snapshot given to `--import-snapshot` when it is an `https://` or `s3://` URL,
+//! so that a fresh replica can start from a snapshot without sharing a filesystem with the
+//! instance that created it.
+//!
+//! The snapshot is streamed to a temporary file next to the database, and its SHA-256 checksum,
+//! given with `--import-snapshot-sha256`, is verified before the snapshot is imported.
+
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use sha2::{Digest, Sha256};
+use tempfile::TempPath;
+use url::Url;
+
+use crate::option::Opt;
+
+/// Whether the snapshot to import must be downloaded.
+pub fn is_remote(snapshot: &Path) -> bool {
+    remote_url(snapshot).is_some()
+}
+
+/// Returns the URL to download the snapshot from, if it is a remote one.
+///
+/// An `s3://bucket/key` URL is downloaded from the public endpoint of the bucket,
+/// the snapshots of a private bucket must be given as presigned `https://` URLs.
+fn remote_url(snapshot: &Path) -> Option<Url> {
+    let url = Url::parse(snapshot.to_str()?).ok()?;
+    match url.scheme() {
+        "https" => Some(url),
+        "s3" => {
+            let bucket = url.host_str()?;
+            Url::parse(&format!("https://{bucket}.s3.amazonaws.com{}", url.path())).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Downloads the remote snapshot to import, if any, and makes `--import-snapshot` point to it.
+///
+/// Nothing is downloaded when the database already exists, as the snapshot wouldn't be imported.
+/// The downloaded snapshot is removed when the returned path is dropped, it must be kept until
+/// the snapshot is imported.
+pub async fn download_remote_snapshot(opt: &mut Opt) -> anyhow::Result<Option<TempPath>> {
+    let Some(url) = opt.import_snapshot.as_deref().and_then(remote_url) else { return Ok(None) };
+    if !crate::is_empty_db(&opt.db_path) {
+        return Ok(None);
+    }
+    let Some(expected_checksum) = opt.import_snapshot_sha256.as_deref() else {
+        bail!("the SHA-256 checksum of a remote snapshot must be given with `--import-snapshot-sha256`")
+    };
+
+    // the query of a presigned URL contains credentials, it must not be logged
+    let mut displayed_url = url.clone();
+    displayed_url.set_query(None);
+
+    tracing::info!(url = %displayed_url, "Downloading the snapshot");
+    let mut response = reqwest::get(url)
+        .await
+        .with_context(|| format!("could not download the snapshot at {displayed_url}"))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        if opt.ignore_missing_snapshot {
+            opt.import_snapshot = None;
+            return Ok(None);
+        }
+        bail!("snapshot doesn't exist at {displayed_url}")
+    }
+    if !response.status().is_success() {
+        bail!("could not download the snapshot at {displayed_url}: {}", response.status())
+    }
+
+    let dir = match opt.db_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut file = tempfile::Builder::new().prefix(".snapshot-").tempfile_in(dir)?;
+    let mut hasher = Sha256::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .with_context(|| format!("could not download the snapshot at {displayed_url}"))?
+    {
+        hasher.update(&chunk);
+        file.write_all(&chunk)?;
+    }
+    file.flush()?;
+
+    let checksum: String = hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect();
+    if !checksum.eq_ignore_ascii_case(expected_checksum.trim()) {
+        bail!(
+            "the checksum of the snapshot downloaded from {displayed_url} is {checksum}, while {} was expected",
+            expected_checksum.trim()
+        )
+    }
+
+    let path = file.into_temp_path();
+    opt.import_snapshot = Some(path.to_path_buf());
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_urls() {
+        let url = |snapshot: &str| remote_url(Path::new(snapshot)).map(|url| url.to_string());
+        assert_eq!(url("./snapshots/data.ms.snapshot"), None);
+        assert_eq!(url("http://example.com/data.ms.snapshot"), None);
+        assert_eq!(
+            url("https://example.com/data.ms.snapshot?signature=abc").as_deref(),
+            Some("https://example.com/data.ms.snapshot?signature=abc")
+        );
+        assert_eq!(
+            url("s3://my-bucket/prod/data.ms.snapshot").as_deref(),
+            Some("https://my-bucket.s3.amazonaws.com/prod/data.ms.snapshot")
+        );
+    }
+}
@@ -10,7 +10,7 @@ use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
-use meilisearch_types::keys::{CreateApiKey, Key, PatchApiKey};
+use meilisearch_types::keys::{CreateApiKey, Key, KeySearchParameters, PatchApiKey};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use uuid::Uuid;
@@ -20,6 +20,7 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::Pagination;
+use crate::search::validate_key_search_parameters;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -41,6 +42,7 @@ pub async fn create_api_key(
     _req: HttpRequest,
 ) -> Result<HttpResponse, ResponseError> {
     let v = body.into_inner();
+    validate_key_search_parameters(&v.search_parameters)?;
     let res = tokio::task::spawn_blocking(move || -> Result<_, AuthControllerError> {
         let key = auth_controller.create_key(v)?;
         Ok(KeyView::from_key(key, &auth_controller))
@@ -155,6 +157,10 @@ struct KeyView {
     indexes: Vec<String>,
     #[serde(serialize_with = "time::serde::rfc3339::option::serialize")]
     expires_at: Option<OffsetDateTime>,
+    #[serde(skip_serializing_if = "KeySearchParameters::is_empty")]
+    search_parameters: KeySearchParameters,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    principals: Vec<String>,
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
     created_at: OffsetDateTime,
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
@@ -173,6 +179,8 @@ impl KeyView {
             actions: key.actions,
             indexes: key.indexes.into_iter().map(|x| x.to_string()).collect(),
             expires_at: key.expires_at,
+            search_parameters: key.search_parameters,
+            principals: key.principals,
             created_at: key.created_at,
             updated_at: key.updated_at,
         }
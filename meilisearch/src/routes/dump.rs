@@ -1,9 +1,14 @@
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::AuthController;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
-use meilisearch_types::tasks::KindWithContent;
+use meilisearch_types::serde_cs::vec::CS;
+use meilisearch_types::tasks::{AnonymizationStrategy, DumpAnonymization, KindWithContent};
 use serde_json::json;
 use tracing::debug;
 
@@ -18,18 +23,42 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("").route(web::post().to(SeqHandler(create_dump))));
 }
 
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct CreateDump {
+    /// The attributes to anonymize in the documents of every index.
+    #[deserr(default, error = DeserrQueryParamError<InvalidDumpAnonymizedAttributes>)]
+    anonymized_attributes: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDumpAnonymization>)]
+    anonymization: AnonymizationStrategy,
+}
+
 pub async fn create_dump(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<IndexScheduler>>,
     auth_controller: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<AuthController>>,
+    params: AwebQueryParameter<CreateDump, DeserrQueryParamError>,
     req: HttpRequest,
     opt: web::Data<Opt>,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    analytics.publish("Dump Created".to_string(), json!({}), Some(&req));
+    let CreateDump { anonymized_attributes, anonymization } = params.into_inner();
+    let anonymization = anonymized_attributes
+        .map(|attributes| DumpAnonymization {
+            attributes: attributes.into_iter().collect(),
+            strategy: anonymization,
+        })
+        .filter(|anonymization| !anonymization.attributes.is_empty());
+
+    analytics.publish(
+        "Dump Created".to_string(),
+        json!({ "anonymization": anonymization.as_ref().map(|a| a.strategy) }),
+        Some(&req),
+    );
 
     let task = KindWithContent::DumpCreation {
         keys: auth_controller.list_keys()?,
         instance_uid: analytics.instance_uid().cloned(),
+        anonymization,
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
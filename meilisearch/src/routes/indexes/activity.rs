@@ -0,0 +1,140 @@
+use actix_web::web::{self, Data};
+use actix_web::HttpResponse;
+use index_scheduler::{IndexScheduler, Query};
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use meilisearch_types::task_view::TaskView;
+use meilisearch_types::tasks::{Details, Status, TaskId};
+use serde::Serialize;
+use serde_json::Value;
+use time::{Duration, OffsetDateTime};
+use tracing::debug;
+
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search_activity::{SearchVolume, SEARCH_ACTIVITY};
+use crate::typo_statistics::{TypoUsage, TYPO_STATISTICS};
+
+/// The number of tasks listed by the activity of an index.
+const RECENT_TASKS_LIMIT: u32 = 20;
+/// The window over which the writes on an index are summarized.
+const WRITES_WINDOW: Duration = Duration::DAY;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_index_activity))));
+}
+
+/// What recently changed in an index, and how much it was searched.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexActivity {
+    /// The most recent tasks of the index, the most recent first.
+    pub recent_tasks: Vec<TaskView>,
+    /// The writes finished during the last 24 hours.
+    pub writes: WritesSummary,
+    pub searches: SearchVolume,
+    /// The typos needed by the hits of the searches, counted while the
+    /// `typoStatistics` experimental feature is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typos: Option<TypoUsage>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WritesSummary {
+    pub succeeded_tasks: u64,
+    pub failed_tasks: u64,
+    /// The number of documents added, updated or edited.
+    pub indexed_documents: u64,
+    pub deleted_documents: u64,
+    /// The settings updates, the most recent first.
+    pub settings_changes: Vec<SettingsChange>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsChange {
+    pub task_uid: TaskId,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub finished_at: Option<OffsetDateTime>,
+    /// The names of the updated settings.
+    pub settings: Vec<String>,
+}
+
+pub async fn get_index_activity(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::STATS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    // returns an error if the index doesn't exist
+    index_scheduler.index(&index_uid)?;
+    let filters = index_scheduler.filters();
+
+    let query = Query {
+        limit: Some(RECENT_TASKS_LIMIT),
+        index_uids: Some(vec![index_uid.to_string()]),
+        ..Query::default()
+    };
+    let (recent_tasks, _) = index_scheduler.get_tasks_from_authorized_indexes(query, filters)?;
+
+    let query = Query {
+        statuses: Some(vec![Status::Succeeded, Status::Failed]),
+        index_uids: Some(vec![index_uid.to_string()]),
+        after_finished_at: Some(OffsetDateTime::now_utc() - WRITES_WINDOW),
+        ..Query::default()
+    };
+    let (finished_tasks, _) = index_scheduler.get_tasks_from_authorized_indexes(query, filters)?;
+
+    let mut writes = WritesSummary::default();
+    for task in finished_tasks {
+        if task.status == Status::Failed {
+            writes.failed_tasks += 1;
+            continue;
+        }
+        writes.succeeded_tasks += 1;
+
+        match task.details {
+            Some(Details::DocumentAdditionOrUpdate { indexed_documents, .. }) => {
+                writes.indexed_documents += indexed_documents.unwrap_or(0);
+            }
+            Some(Details::DocumentEdition { edited_documents, deleted_documents, .. }) => {
+                writes.indexed_documents += edited_documents.unwrap_or(0);
+                writes.deleted_documents += deleted_documents.unwrap_or(0);
+            }
+            Some(
+                Details::DocumentDeletion { deleted_documents, .. }
+                | Details::DocumentDeletionByFilter { deleted_documents, .. }
+                | Details::ClearAll { deleted_documents },
+            ) => writes.deleted_documents += deleted_documents.unwrap_or(0),
+            Some(Details::SettingsUpdate { settings }) => {
+                // the settings that weren't updated aren't serialized
+                let settings = match serde_json::to_value(&settings) {
+                    Ok(Value::Object(settings)) => settings.into_iter().map(|(k, _)| k).collect(),
+                    _ => Vec::new(),
+                };
+                writes.settings_changes.push(SettingsChange {
+                    task_uid: task.uid,
+                    finished_at: task.finished_at,
+                    settings,
+                });
+            }
+            _ => (),
+        }
+    }
+
+    let activity = IndexActivity {
+        recent_tasks: recent_tasks.iter().map(TaskView::from_task).collect(),
+        writes,
+        searches: SEARCH_ACTIVITY.volume(&index_uid),
+        typos: index_scheduler
+            .features()
+            .runtime_features()
+            .typo_statistics
+            .then(|| TYPO_STATISTICS.usage(&index_uid)),
+    };
+
+    debug!(returns = ?activity, "Get index activity");
+    Ok(HttpResponse::Ok().json(activity))
+}
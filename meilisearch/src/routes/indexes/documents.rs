@@ -8,22 +8,28 @@ use deserr::actix_web::{AwebJson, AwebQueryParameter};
 use deserr::Deserr;
 use futures::StreamExt;
 use index_scheduler::{IndexScheduler, TaskId};
+use meilisearch_auth::AuthFilter;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
-use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
+use meilisearch_types::document_formats::{
+    read_csv, read_extracted_text, read_json, read_ndjson, PayloadType,
+};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::index::WordPositions;
+use meilisearch_types::milli::tokenizer::Normalize;
+use meilisearch_types::milli::update::{DocumentsWriteMode, IndexDocumentsMethod};
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
-use meilisearch_types::milli::DocumentId;
+use meilisearch_types::milli::{DocumentId, Object};
 use meilisearch_types::star_or::OptionStarOrList;
 use meilisearch_types::tasks::KindWithContent;
+use meilisearch_types::text_extraction::{ContentExtraction, DEFAULT_TEXT_FIELD};
 use meilisearch_types::{milli, Document, Index};
 use mime::Mime;
 use once_cell::sync::Lazy;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tempfile::tempfile;
 use tokio::fs::File;
@@ -31,6 +37,7 @@ use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter};
 use tracing::debug;
 
 use crate::analytics::{Analytics, DocumentDeletionKind, DocumentFetchKind};
+use crate::document_uploads::{DOCUMENT_UPLOADS, UPLOADS_DIR};
 use crate::error::MeilisearchHttpError;
 use crate::error::PayloadError::ReceivePayload;
 use crate::extractors::authentication::policies::*;
@@ -40,11 +47,17 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::{
     get_task_id, is_dry_run, PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT,
 };
-use crate::search::{parse_filter, RetrieveVectors};
+use crate::search::{parse_filter, PiiProtection, RetrieveVectors};
 use crate::Opt;
 
 static ACCEPTED_CONTENT_TYPE: Lazy<Vec<String>> = Lazy::new(|| {
-    vec!["application/json".to_string(), "application/x-ndjson".to_string(), "text/csv".to_string()]
+    vec![
+        "application/json".to_string(),
+        "application/x-ndjson".to_string(),
+        "text/csv".to_string(),
+        "application/pdf".to_string(),
+        "text/html".to_string(),
+    ]
 });
 
 /// Extracts the mime type from the content type and return
@@ -69,6 +82,13 @@ pub struct DocumentParam {
     document_id: String,
 }
 
+#[derive(Deserialize)]
+pub struct AttachmentParam {
+    index_uid: String,
+    document_id: String,
+    name: String,
+}
+
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::resource("")
@@ -84,10 +104,31 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     .service(web::resource("/delete").route(web::post().to(SeqHandler(delete_documents_by_filter))))
     .service(web::resource("/edit").route(web::post().to(SeqHandler(edit_documents_by_function))))
     .service(web::resource("/fetch").route(web::post().to(SeqHandler(documents_by_query_post))))
+    .service(web::resource("/hydrate").route(web::post().to(SeqHandler(hydrate_documents))))
+    .service(web::resource("/uploads").route(web::post().to(SeqHandler(create_document_upload))))
+    .service(
+        web::resource("/uploads/{upload_uid}")
+            .route(web::get().to(SeqHandler(get_document_upload)))
+            .route(web::patch().to(SeqHandler(append_document_upload_part)))
+            .route(web::delete().to(SeqHandler(delete_document_upload))),
+    )
+    .service(
+        web::resource("/uploads/{upload_uid}/complete")
+            .route(web::post().to(SeqHandler(complete_document_upload)))
+            .route(web::put().to(SeqHandler(complete_document_upload))),
+    )
     .service(
         web::resource("/{document_id}")
             .route(web::get().to(SeqHandler(get_document)))
             .route(web::delete().to(SeqHandler(delete_document))),
+    )
+    .service(
+        web::resource("/{document_id}/attachments/{name}")
+            .route(web::get().to(SeqHandler(get_document_attachment))),
+    )
+    .service(
+        web::resource("/{document_id}/word-positions")
+            .route(web::get().to(SeqHandler(get_document_word_positions))),
     );
 }
 
@@ -123,12 +164,121 @@ pub async fn get_document(
     );
 
     let index = index_scheduler.index(&index_uid)?;
-    let document =
-        retrieve_document(&index, &document_id, attributes_to_retrieve, retrieve_vectors)?;
+    let mut document = retrieve_document(
+        &index,
+        index_scheduler.filters(),
+        &document_id,
+        attributes_to_retrieve,
+        retrieve_vectors,
+    )?;
+    PiiProtection::new(&index, index_scheduler.filters(), index_scheduler.indexer_config())?
+        .protect_document(&mut document);
     debug!(returns = ?document, "Get document");
     Ok(HttpResponse::Ok().json(document))
 }
 
+pub async fn get_document_attachment(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    attachment_param: web::Path<AttachmentParam>,
+) -> Result<HttpResponse, ResponseError> {
+    let AttachmentParam { index_uid, document_id, name } = attachment_param.into_inner();
+    debug!(document_id, name, "Get document attachment");
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let txn = index.read_txn()?;
+    let internal_id = index
+        .external_documents_ids()
+        .get(&txn, &document_id)?
+        .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(document_id.clone()))?;
+    ensure_document_visible(index_scheduler.filters(), &index, &txn, internal_id, &document_id)?;
+    let content = index
+        .attachment(&txn, internal_id, &name)?
+        .ok_or_else(|| MeilisearchHttpError::AttachmentNotFound(name.clone(), document_id))?;
+
+    Ok(HttpResponse::Ok().content_type(attachment_content_type(&name)).body(content.to_vec()))
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct GetDocumentWordPositions {
+    #[deserr(error = DeserrQueryParamError<InvalidDocumentWord>)]
+    word: String,
+}
+
+/// Where a word is indexed for a document.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentWordPositions {
+    /// The word, normalized like the indexed words.
+    word: String,
+    /// The attributes containing the word.
+    attributes: Vec<String>,
+    /// The positions of the word in these attributes, bucketed like for the attribute ranking rules.
+    positions: Vec<u16>,
+}
+
+/// Returns where a word is indexed for a document, by reading the position databases,
+/// to understand why a document matches a query or not.
+pub async fn get_document_word_positions(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    document_param: web::Path<DocumentParam>,
+    params: AwebQueryParameter<GetDocumentWordPositions, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let DocumentParam { index_uid, document_id } = document_param.into_inner();
+    debug!(parameters = ?params, "Get document word positions");
+    let index_uid = IndexUid::try_from(index_uid)?;
+    let GetDocumentWordPositions { word } = params.into_inner();
+
+    let index = index_scheduler.index(&index_uid)?;
+    let txn = index.read_txn()?;
+    let internal_id = index
+        .external_documents_ids()
+        .get(&txn, &document_id)?
+        .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(document_id.clone()))?;
+    ensure_document_visible(index_scheduler.filters(), &index, &txn, internal_id, &document_id)?;
+
+    let word = word.as_str().normalize(&Default::default()).into_owned();
+    let WordPositions { fields_ids, mut positions } =
+        index.word_positions(&txn, &word, internal_id)?;
+    let fields_ids_map = index.fields_ids_map(&txn)?;
+    // the keys that can't see the personal data must not learn what they contain
+    let hidden = if index_scheduler.filters().allow_pii() {
+        Default::default()
+    } else {
+        index.pii_attributes(&txn)?
+    };
+    let attributes: Vec<_> =
+        fields_ids.into_iter().filter_map(|fid| fields_ids_map.name(fid)).collect();
+    // the positions aren't stored by attribute
+    if attributes.iter().any(|name| hidden.contains(*name)) {
+        positions.clear();
+    }
+    let attributes =
+        attributes.into_iter().filter(|name| !hidden.contains(*name)).map(String::from).collect();
+
+    let word_positions = DocumentWordPositions { word, attributes, positions };
+    debug!(returns = ?word_positions, "Get document word positions");
+    Ok(HttpResponse::Ok().json(word_positions))
+}
+
+/// Guesses the content type of an attachment from the extension of its name.
+fn attachment_content_type(name: &str) -> &'static str {
+    let extension = name.rsplit_once('.').map(|(_, extension)| extension.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        Some("ico") => "image/x-icon",
+        Some("pdf") => "application/pdf",
+        Some("json") => "application/json",
+        Some("txt") => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
 pub async fn delete_document(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     path: web::Path<DocumentParam>,
@@ -183,6 +333,9 @@ pub struct BrowseQuery {
     retrieve_vectors: bool,
     #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
     filter: Option<Value>,
+    /// The primary keys of the documents to return, in this order.
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentId>)]
+    ids: Option<Vec<Value>>,
 }
 
 pub async fn documents_by_query_post(
@@ -198,6 +351,7 @@ pub async fn documents_by_query_post(
     analytics.post_fetch_documents(
         &DocumentFetchKind::Normal {
             with_filter: body.filter.is_some(),
+            with_ids: body.ids.is_some(),
             limit: body.limit,
             offset: body.offset,
             retrieve_vectors: body.retrieve_vectors,
@@ -208,6 +362,63 @@ pub async fn documents_by_query_post(
     documents_by_query(&index_scheduler, index_uid, body)
 }
 
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct HydrateQuery {
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentId>)]
+    ids: Vec<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFields>)]
+    fields: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentRetrieveVectors>)]
+    retrieve_vectors: bool,
+}
+
+/// Returns the documents of the given primary keys, in the same order.
+///
+/// This is the companion of the searches retrieving no attribute, that only return the
+/// primary keys of the hits. It is a fetch of all the given ids, without the pagination.
+pub async fn hydrate_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<HydrateQuery, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let HydrateQuery { ids, fields, retrieve_vectors } = body.into_inner();
+    debug!(parameters = ?ids, "Hydrate documents");
+
+    let query = BrowseQuery {
+        offset: 0,
+        limit: ids.len(),
+        fields,
+        retrieve_vectors,
+        filter: None,
+        ids: Some(ids),
+    };
+
+    analytics.post_fetch_documents(
+        &DocumentFetchKind::Normal {
+            with_filter: false,
+            with_ids: true,
+            limit: query.limit,
+            offset: query.offset,
+            retrieve_vectors: query.retrieve_vectors,
+        },
+        &req,
+    );
+
+    let (_, results) = fetch_documents(&index_scheduler, index_uid, query)?;
+
+    let ret = serde_json::json!({ "results": results });
+    debug!(returns = ?ret, "Hydrate documents");
+    Ok(HttpResponse::Ok().json(ret))
+}
+
+/// Returns the primary key given as a JSON string or number as a string.
+fn external_document_id(id: &Value) -> String {
+    id.as_str().map(String::from).unwrap_or_else(|| id.to_string())
+}
+
 pub async fn get_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -233,11 +444,13 @@ pub async fn get_documents(
         fields: fields.merge_star_and_none(),
         retrieve_vectors: retrieve_vectors.0,
         filter,
+        ids: None,
     };
 
     analytics.get_fetch_documents(
         &DocumentFetchKind::Normal {
             with_filter: query.filter.is_some(),
+            with_ids: false,
             limit: query.limit,
             offset: query.offset,
             retrieve_vectors: query.retrieve_vectors,
@@ -249,24 +462,42 @@ pub async fn get_documents(
 }
 
 fn documents_by_query(
-    index_scheduler: &IndexScheduler,
+    index_scheduler: &GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
     query: BrowseQuery,
 ) -> Result<HttpResponse, ResponseError> {
+    let (offset, limit) = (query.offset, query.limit);
+    let (total, documents) = fetch_documents(index_scheduler, index_uid, query)?;
+
+    let ret = PaginationView::new(offset, limit, total as usize, documents);
+
+    debug!(returns = ?ret, "Get documents");
+    Ok(HttpResponse::Ok().json(ret))
+}
+
+/// Returns the total number of documents matching the query and the page of documents asked.
+fn fetch_documents(
+    index_scheduler: &GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    query: BrowseQuery,
+) -> Result<(u64, Vec<Document>), ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
-    let BrowseQuery { offset, limit, fields, retrieve_vectors, filter } = query;
+    let BrowseQuery { offset, limit, fields, retrieve_vectors, filter, ids } = query;
 
     let features = index_scheduler.features();
     let retrieve_vectors = RetrieveVectors::new(retrieve_vectors, features)?;
+    let ids: Option<Vec<_>> = ids.map(|ids| ids.iter().map(external_document_id).collect());
+    // the keys with principals only see the documents they are allowed to
+    let filter = index_scheduler.filters().restrict_to_principals(filter);
 
     let index = index_scheduler.index(&index_uid)?;
-    let (total, documents) =
-        retrieve_documents(&index, offset, limit, filter, fields, retrieve_vectors)?;
-
-    let ret = PaginationView::new(offset, limit, total as usize, documents);
+    let (total, mut documents) =
+        retrieve_documents(&index, offset, limit, filter, ids, fields, retrieve_vectors)?;
+    let pii_protection =
+        PiiProtection::new(&index, index_scheduler.filters(), index_scheduler.indexer_config())?;
+    documents.iter_mut().for_each(|document| pii_protection.protect_document(document));
 
-    debug!(returns = ?ret, "Get documents");
-    Ok(HttpResponse::Ok().json(ret))
+    Ok((total, documents))
 }
 
 #[derive(Deserialize, Debug, Deserr)]
@@ -276,6 +507,40 @@ pub struct UpdateDocumentsQuery {
     pub primary_key: Option<String>,
     #[deserr(default, try_from(char) = from_char_csv_delimiter -> DeserrQueryParamError<InvalidDocumentCsvDelimiter>, error = DeserrQueryParamError<InvalidDocumentCsvDelimiter>)]
     pub csv_delimiter: Option<u8>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentWriteMode>)]
+    pub write_mode: WriteMode,
+    /// The field of the JSON or NDJSON documents containing a base64 encoded PDF or HTML
+    /// document of which the text is extracted.
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentContentField>)]
+    pub content_field: Option<String>,
+    /// The field in which the extracted text is written.
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentTextField>)]
+    pub text_field: Option<String>,
+    /// The id of the document made from a PDF or HTML payload.
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentId>)]
+    pub document_id: Option<String>,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserr)]
+#[deserr(rename_all = camelCase)]
+pub enum WriteMode {
+    /// Create the missing documents and update the existing ones
+    #[default]
+    Upsert,
+    /// Reject the documents that already exist in the index
+    FailIfExists,
+    /// Reject the documents that do not exist in the index yet
+    FailIfMissing,
+}
+
+impl From<WriteMode> for DocumentsWriteMode {
+    fn from(other: WriteMode) -> Self {
+        match other {
+            WriteMode::Upsert => Self::Upsert,
+            WriteMode::FailIfExists => Self::FailIfExists,
+            WriteMode::FailIfMissing => Self::FailIfMissing,
+        }
+    }
 }
 
 fn from_char_csv_delimiter(
@@ -318,9 +583,8 @@ pub async fn replace_documents(
         extract_mime_type(&req)?,
         index_scheduler,
         index_uid,
-        params.primary_key,
-        params.csv_delimiter,
-        body,
+        params,
+        DocumentsBody::Payload(body),
         IndexDocumentsMethod::ReplaceDocuments,
         uid,
         dry_run,
@@ -359,9 +623,8 @@ pub async fn update_documents(
         extract_mime_type(&req)?,
         index_scheduler,
         index_uid,
-        params.primary_key,
-        params.csv_delimiter,
-        body,
+        params,
+        DocumentsBody::Payload(body),
         IndexDocumentsMethod::UpdateDocuments,
         uid,
         dry_run,
@@ -373,19 +636,161 @@ pub async fn update_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Deserialize)]
+pub struct DocumentUploadParam {
+    index_uid: String,
+    upload_uid: String,
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct UploadPartQuery {
+    #[deserr(error = DeserrQueryParamError<InvalidDocumentUploadOffset>)]
+    offset: Param<u64>,
+}
+
+pub async fn create_document_upload(
+    _index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    opt: web::Data<Opt>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let upload = DOCUMENT_UPLOADS.create(&opt.db_path.join(UPLOADS_DIR), &index_uid)?;
+
+    debug!(returns = ?upload, "Create document upload");
+    Ok(HttpResponse::Created().json(upload))
+}
+
+pub async fn get_document_upload(
+    _index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    path: web::Path<DocumentUploadParam>,
+) -> Result<HttpResponse, ResponseError> {
+    let DocumentUploadParam { index_uid, upload_uid } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+    let upload = DOCUMENT_UPLOADS.get(&index_uid, &upload_uid)?;
+
+    debug!(returns = ?upload, "Get document upload");
+    Ok(HttpResponse::Ok().json(upload))
+}
+
+/// Appends a part to the upload. The parts can be compressed with the `Content-Encoding`
+/// header, they are decompressed before being appended.
+pub async fn append_document_upload_part(
+    _index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    path: web::Path<DocumentUploadParam>,
+    params: AwebQueryParameter<UploadPartQuery, DeserrQueryParamError>,
+    body: Payload,
+) -> Result<HttpResponse, ResponseError> {
+    let DocumentUploadParam { index_uid, upload_uid } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+    let offset = params.into_inner().offset.0;
+    let upload = DOCUMENT_UPLOADS.append(&index_uid, &upload_uid, offset, body).await?;
+
+    debug!(returns = ?upload, "Append document upload part");
+    Ok(HttpResponse::Ok().json(upload))
+}
+
+pub async fn delete_document_upload(
+    _index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    path: web::Path<DocumentUploadParam>,
+) -> Result<HttpResponse, ResponseError> {
+    let DocumentUploadParam { index_uid, upload_uid } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+    DOCUMENT_UPLOADS.remove(&index_uid, &upload_uid)?;
+
+    debug!("Delete document upload");
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Completes the upload with a document addition of the assembled payload, replacing the
+/// documents on `POST` and updating them on `PUT`, like the document additions.
+pub async fn complete_document_upload(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    path: web::Path<DocumentUploadParam>,
+    params: AwebQueryParameter<UpdateDocumentsQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let DocumentUploadParam { index_uid, upload_uid } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+
+    let params = params.into_inner();
+    debug!(parameters = ?params, "Complete document upload");
+
+    let method = if req.method() == actix_web::http::Method::PUT {
+        IndexDocumentsMethod::UpdateDocuments
+    } else {
+        IndexDocumentsMethod::ReplaceDocuments
+    };
+    analytics.add_documents(
+        &params,
+        index_scheduler.index_exists(&index_uid).map_or(true, |x| !x),
+        &req,
+    );
+
+    let mime_type = extract_mime_type(&req)?;
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let upload = DOCUMENT_UPLOADS.lock(&index_uid, &upload_uid)?;
+    let task = match upload.file().reopen() {
+        Ok(file) => {
+            document_addition(
+                mime_type,
+                index_scheduler,
+                index_uid.clone(),
+                params,
+                DocumentsBody::Upload(file),
+                method,
+                uid,
+                dry_run,
+                allow_index_creation,
+            )
+            .await
+        }
+        Err(e) => Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
+    };
+    // an invalid payload can still be fixed by resuming the upload, a valid one is removed
+    // as its documents were written in the update file of the task
+    if task.is_ok() {
+        upload.complete();
+    }
+    let task = task?;
+    debug!(returns = ?task, "Complete document upload");
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// The documents of a document addition.
+enum DocumentsBody {
+    /// The payload of the request.
+    Payload(Payload),
+    /// The payload assembled by a document upload.
+    Upload(std::fs::File),
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn document_addition(
     mime_type: Option<Mime>,
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
     index_uid: IndexUid,
-    primary_key: Option<String>,
-    csv_delimiter: Option<u8>,
-    mut body: Payload,
+    params: UpdateDocumentsQuery,
+    body: DocumentsBody,
     method: IndexDocumentsMethod,
     task_id: Option<TaskId>,
     dry_run: bool,
     allow_index_creation: bool,
 ) -> Result<SummarizedTaskView, MeilisearchHttpError> {
+    let UpdateDocumentsQuery {
+        primary_key,
+        csv_delimiter,
+        write_mode,
+        content_field,
+        text_field,
+        document_id,
+    } = params;
+
     let format = match (
         mime_type.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str())),
         csv_delimiter,
@@ -394,6 +799,8 @@ async fn document_addition(
         (Some(("application", "x-ndjson")), None) => PayloadType::Ndjson,
         (Some(("text", "csv")), None) => PayloadType::Csv { delimiter: b',' },
         (Some(("text", "csv")), Some(delimiter)) => PayloadType::Csv { delimiter },
+        (Some(("application", "pdf")), None) => PayloadType::Pdf,
+        (Some(("text", "html")), None) => PayloadType::Html,
 
         (Some(("application", "json")), Some(_)) => {
             return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
@@ -405,6 +812,16 @@ async fn document_addition(
                 "application/x-ndjson",
             )))
         }
+        (Some(("application", "pdf")), Some(_)) => {
+            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
+                "application/pdf",
+            )))
+        }
+        (Some(("text", "html")), Some(_)) => {
+            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
+                "text/html",
+            )))
+        }
         (Some((type_, subtype)), _) => {
             return Err(MeilisearchHttpError::InvalidContentType(
                 format!("{}/{}", type_, subtype),
@@ -416,48 +833,76 @@ async fn document_addition(
         }
     };
 
-    let (uuid, mut update_file) = index_scheduler.create_update_file(dry_run)?;
-
-    let temp_file = match tempfile() {
-        Ok(file) => file,
-        Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
+    let content_type = mime_type.as_ref().map_or("", |m| m.essence_str());
+    let content_extraction = match (format, content_field) {
+        (PayloadType::Json | PayloadType::Ndjson, Some(content_field)) => Some(ContentExtraction {
+            text_field: text_field.clone().unwrap_or_else(|| content_field.clone()),
+            content_field,
+        }),
+        (_, Some(_)) => {
+            return Err(MeilisearchHttpError::ContentFieldWithWrongContentType(
+                content_type.to_string(),
+            ))
+        }
+        (_, None) => None,
     };
 
-    let async_file = File::from_std(temp_file);
-    let mut buffer = BufWriter::new(async_file);
-
-    let mut buffer_write_size: usize = 0;
-    while let Some(result) = body.next().await {
-        let byte = result?;
-
-        if byte.is_empty() && buffer_write_size == 0 {
-            return Err(MeilisearchHttpError::MissingPayload(format));
+    // the PDF and HTML payloads are a single document made of its id and of the extracted text.
+    let extracted_document = match (format, document_id) {
+        (PayloadType::Pdf | PayloadType::Html, Some(document_id)) => {
+            let primary_key = match primary_key.clone() {
+                Some(primary_key) => primary_key,
+                None => index_primary_key(&index_scheduler, &index_uid)?
+                    .unwrap_or_else(|| String::from("id")),
+            };
+            let mut document = Object::new();
+            document.insert(primary_key, Value::String(document_id));
+            let text_field = text_field.unwrap_or_else(|| DEFAULT_TEXT_FIELD.to_string());
+            Some((document, text_field))
         }
-
-        match buffer.write_all(&byte).await {
-            Ok(()) => buffer_write_size += 1,
-            Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
+        (PayloadType::Pdf | PayloadType::Html, None) => {
+            return Err(MeilisearchHttpError::MissingDocumentIdForExtraction(format))
         }
-    }
-
-    if let Err(e) = buffer.flush().await {
-        return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))));
-    }
-
-    if buffer_write_size == 0 {
-        return Err(MeilisearchHttpError::MissingPayload(format));
-    }
+        (_, Some(_)) => {
+            return Err(MeilisearchHttpError::DocumentIdWithWrongContentType(
+                content_type.to_string(),
+            ))
+        }
+        (_, None) if text_field.is_some() && content_extraction.is_none() => {
+            return Err(MeilisearchHttpError::TextFieldWithoutExtraction)
+        }
+        (_, None) => None,
+    };
 
-    if let Err(e) = buffer.seek(std::io::SeekFrom::Start(0)).await {
-        return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))));
-    }
+    let (uuid, mut update_file) = index_scheduler.create_update_file(dry_run)?;
 
-    let read_file = buffer.into_inner().into_std().await;
+    let read_file = match body {
+        DocumentsBody::Payload(body) => receive_payload(body, format).await?,
+        DocumentsBody::Upload(file) => match file.metadata() {
+            Ok(metadata) if metadata.len() == 0 => {
+                return Err(MeilisearchHttpError::MissingPayload(format))
+            }
+            Ok(_) => file,
+            Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
+        },
+    };
     let documents_count = tokio::task::spawn_blocking(move || {
-        let documents_count = match format {
-            PayloadType::Json => read_json(&read_file, &mut update_file)?,
-            PayloadType::Csv { delimiter } => read_csv(&read_file, &mut update_file, delimiter)?,
-            PayloadType::Ndjson => read_ndjson(&read_file, &mut update_file)?,
+        let documents_count = match (format, extracted_document) {
+            (PayloadType::Json, _) => {
+                read_json(&read_file, &mut update_file, content_extraction.as_ref())?
+            }
+            (PayloadType::Csv { delimiter }, _) => {
+                read_csv(&read_file, &mut update_file, delimiter)?
+            }
+            (PayloadType::Ndjson, _) => {
+                read_ndjson(&read_file, &mut update_file, content_extraction.as_ref())?
+            }
+            (PayloadType::Pdf | PayloadType::Html, Some((document, text_field))) => {
+                read_extracted_text(&read_file, &mut update_file, format, document, &text_field)?
+            }
+            (PayloadType::Pdf | PayloadType::Html, None) => {
+                unreachable!("the document of a PDF or HTML payload is always built")
+            }
         };
         // we NEED to persist the file here because we moved the `udpate_file` in another task.
         update_file.persist()?;
@@ -494,6 +939,7 @@ async fn document_addition(
         documents_count,
         primary_key,
         allow_index_creation,
+        write_mode: write_mode.into(),
         index_uid: index_uid.to_string(),
     };
 
@@ -511,6 +957,63 @@ async fn document_addition(
     Ok(task.into())
 }
 
+/// Receives the payload of the request in a temporary file.
+async fn receive_payload(
+    mut body: Payload,
+    format: PayloadType,
+) -> Result<std::fs::File, MeilisearchHttpError> {
+    let temp_file = match tempfile() {
+        Ok(file) => file,
+        Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
+    };
+
+    let async_file = File::from_std(temp_file);
+    let mut buffer = BufWriter::new(async_file);
+
+    let mut buffer_write_size: usize = 0;
+    while let Some(result) = body.next().await {
+        let byte = result?;
+
+        if byte.is_empty() && buffer_write_size == 0 {
+            return Err(MeilisearchHttpError::MissingPayload(format));
+        }
+
+        match buffer.write_all(&byte).await {
+            Ok(()) => buffer_write_size += 1,
+            Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e)))),
+        }
+    }
+
+    if let Err(e) = buffer.flush().await {
+        return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))));
+    }
+
+    if buffer_write_size == 0 {
+        return Err(MeilisearchHttpError::MissingPayload(format));
+    }
+
+    if let Err(e) = buffer.seek(std::io::SeekFrom::Start(0)).await {
+        return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))));
+    }
+
+    Ok(buffer.into_inner().into_std().await)
+}
+
+/// Returns the primary key of the index, or `None` if it doesn't exist or has none yet.
+fn index_primary_key(
+    index_scheduler: &IndexScheduler,
+    index_uid: &IndexUid,
+) -> Result<Option<String>, MeilisearchHttpError> {
+    let index = match index_scheduler.index(index_uid) {
+        Ok(index) => index,
+        Err(index_scheduler::Error::IndexNotFound(_)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let rtxn = index.read_txn()?;
+    let primary_key = index.primary_key(&rtxn)?.map(String::from);
+    Ok(primary_key)
+}
+
 pub async fn delete_documents_batch(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -729,11 +1232,13 @@ fn some_documents<'a, 't: 'a>(
     }))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn retrieve_documents<S: AsRef<str>>(
     index: &Index,
     offset: usize,
     limit: usize,
     filter: Option<Value>,
+    ids: Option<Vec<String>>,
     attributes_to_retrieve: Option<Vec<S>>,
     retrieve_vectors: RetrieveVectors,
 ) -> Result<(u64, Vec<Document>), ResponseError> {
@@ -757,18 +1262,23 @@ fn retrieve_documents<S: AsRef<str>>(
         index.documents_ids(&rtxn)?
     };
 
-    let (it, number_of_documents) = {
-        let number_of_documents = candidates.len();
-        (
-            some_documents(
-                index,
-                &rtxn,
-                candidates.into_iter().skip(offset).take(limit),
-                retrieve_vectors,
-            )?,
-            number_of_documents,
-        )
+    let (docids, number_of_documents): (Vec<_>, _) = match ids {
+        // the documents are returned in the order of the ids, the unknown ids are ignored
+        Some(ids) => {
+            let external_documents_ids = index.external_documents_ids();
+            let mut docids = Vec::with_capacity(ids.len());
+            for id in ids {
+                match external_documents_ids.get(&rtxn, &id)? {
+                    Some(docid) if candidates.contains(docid) => docids.push(docid),
+                    _ => (),
+                }
+            }
+            let number_of_documents = docids.len() as u64;
+            (docids.into_iter().skip(offset).take(limit).collect(), number_of_documents)
+        }
+        None => (candidates.iter().skip(offset).take(limit).collect(), candidates.len()),
     };
+    let it = some_documents(index, &rtxn, docids, retrieve_vectors)?;
 
     let documents: Vec<_> = it
         .map(|document| {
@@ -789,6 +1299,7 @@ fn retrieve_documents<S: AsRef<str>>(
 
 fn retrieve_document<S: AsRef<str>>(
     index: &Index,
+    filters: &AuthFilter,
     doc_id: &str,
     attributes_to_retrieve: Option<Vec<S>>,
     retrieve_vectors: RetrieveVectors,
@@ -799,6 +1310,7 @@ fn retrieve_document<S: AsRef<str>>(
         .external_documents_ids()
         .get(&txn, doc_id)?
         .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))?;
+    ensure_document_visible(filters, index, &txn, internal_id, doc_id)?;
 
     let document = some_documents(index, &txn, Some(internal_id), retrieve_vectors)?
         .next()
@@ -817,3 +1329,23 @@ fn retrieve_document<S: AsRef<str>>(
 
     Ok(document)
 }
+
+/// Returns a not found error if the principals of the key can't see the document,
+/// so that they don't learn it exists.
+fn ensure_document_visible(
+    filters: &AuthFilter,
+    index: &Index,
+    rtxn: &RoTxn,
+    internal_id: DocumentId,
+    doc_id: &str,
+) -> Result<(), ResponseError> {
+    let Some(filter) = filters.restrict_to_principals(None) else {
+        return Ok(());
+    };
+    if let Some(filter) = parse_filter(&filter)? {
+        if !filter.evaluate(rtxn, index)?.contains(internal_id) {
+            return Err(MeilisearchHttpError::DocumentNotFound(doc_id.to_string()).into());
+        }
+    }
+    Ok(())
+}
@@ -14,9 +14,10 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::routes::indexes::search::search_kind;
 use crate::search::{
-    add_search_rules, perform_facet_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    SearchQuery, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
-    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    add_search_rules, perform_facet_search, HybridQuery, MatchingStrategy, PreparedFilterQuery,
+    RankingScoreThreshold, SearchQuery, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SEARCH_OFFSET,
 };
 use crate::search_queue::SearchQueue;
 
@@ -42,6 +43,8 @@ pub struct FacetSearchQuery {
     pub hybrid: Option<HybridQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchPreparedFilter>)]
+    pub prepared_filter: Option<PreparedFilterQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
@@ -78,10 +81,11 @@ pub async fn search(
     let features = index_scheduler.features();
     let search_kind = search_kind(&search_query, &index_scheduler, &index, features)?;
     let _permit = search_queue.try_get_search_permit().await?;
-    let search_result = tokio::task::spawn_blocking(move || {
-        perform_facet_search(&index, search_query, facet_query, facet_name, search_kind)
-    })
-    .await?;
+    let search_result = search_queue
+        .run(move || {
+            perform_facet_search(&index, search_query, facet_query, facet_name, search_kind)
+        })
+        .await?;
 
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
@@ -102,6 +106,7 @@ impl From<FacetSearchQuery> for SearchQuery {
             q,
             vector,
             filter,
+            prepared_filter,
             matching_strategy,
             attributes_to_search_on,
             hybrid,
@@ -122,18 +127,32 @@ impl From<FacetSearchQuery> for SearchQuery {
             show_matches_position: false,
             show_ranking_score: false,
             show_ranking_score_details: false,
+            show_query_terms: false,
+            show_query_interpretation: false,
+            show_timings: false,
             filter,
+            prepared_filter,
             sort: None,
             distinct: None,
+            diversify: None,
             facets: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
+            max_highlight_fragments: None,
+            fragment_separator: None,
             matching_strategy,
+            phrase_fallback: false,
             vector,
             attributes_to_search_on,
+            languages: None,
+            synonyms: None,
+            geo_sort_max_distance: None,
+            geo_sort_bucket_width: None,
             hybrid,
             ranking_score_threshold,
+            create_session: false,
+            session_token: None,
         }
     }
 }
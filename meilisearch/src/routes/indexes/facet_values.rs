@@ -0,0 +1,74 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use deserr::actix_web::AwebQueryParameter;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::{FacetValueHit, FacetValues, DEFAULT_FACET_VALUES_PAGE_SIZE};
+use serde::Serialize;
+use tracing::debug;
+
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::{add_search_rules, parse_filter};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/{field}/values").route(web::get().to(SeqHandler(get_facet_values))),
+    );
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetValuesQuery {
+    #[deserr(default, error = DeserrQueryParamError<InvalidFacetValuesFrom>)]
+    pub from: Option<String>,
+    #[deserr(default = Param(DEFAULT_FACET_VALUES_PAGE_SIZE), error = DeserrQueryParamError<InvalidFacetValuesLimit>)]
+    pub limit: Param<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FacetValuesView {
+    pub results: Vec<FacetValueHit>,
+    pub limit: usize,
+    pub from: Option<String>,
+    pub next: Option<String>,
+}
+
+pub async fn get_facet_values(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    path: web::Path<(String, String)>,
+    params: AwebQueryParameter<FacetValuesQuery, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let (index_uid, field) = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+    let FacetValuesQuery { from, limit: Param(limit) } = params.into_inner();
+    debug!(field, from, limit, "Get facet values");
+
+    // Tenant token search_rules.
+    let mut filter = None;
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut filter, search_rules);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let mut facet_values = FacetValues::new(&rtxn, &index, field);
+    facet_values.limit(limit);
+    if let Some(from) = &from {
+        facet_values.from(from.clone());
+    }
+    if let Some(filter) = filter.as_ref().map(parse_filter).transpose()?.flatten() {
+        facet_values.candidates(filter.evaluate(&rtxn, &index)?);
+    }
+    let page = facet_values.execute()?;
+
+    let view = FacetValuesView { results: page.values, limit, from, next: page.next };
+    debug!(returns = ?view, "Get facet values");
+    Ok(HttpResponse::Ok().json(view))
+}
@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::Infallible;
 
 use actix_web::web::Data;
@@ -10,6 +11,8 @@ use meilisearch_types::deserr::{immutable_field_error, DeserrJsonError, DeserrQu
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::fields_schema::{FieldSchema, ValueType};
+use meilisearch_types::milli::update::DerivedDatabase;
 use meilisearch_types::milli::{self, FieldDistribution, Index};
 use meilisearch_types::tasks::KindWithContent;
 use serde::Serialize;
@@ -25,8 +28,10 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::is_dry_run;
 use crate::Opt;
 
+pub mod activity;
 pub mod documents;
 pub mod facet_search;
+pub mod facet_values;
 pub mod search;
 pub mod settings;
 pub mod similar;
@@ -45,10 +50,18 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route(web::patch().to(SeqHandler(update_index)))
                     .route(web::delete().to(SeqHandler(delete_index))),
             )
+            .service(web::resource("/rebuild").route(web::post().to(SeqHandler(rebuild_index))))
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
+            .service(
+                web::resource("/stats/fields")
+                    .route(web::get().to(SeqHandler(get_index_fields_stats))),
+            )
+            .service(web::resource("/schema").route(web::get().to(SeqHandler(get_index_schema))))
+            .service(web::scope("/activity").configure(activity::configure))
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
+            .service(web::scope("/facets").configure(facet_values::configure))
             .service(web::scope("/similar").configure(similar::configure))
             .service(web::scope("/settings").configure(settings::configure)),
     );
@@ -227,6 +240,48 @@ pub async fn update_index(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Deserr, Debug)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct RebuildIndexRequest {
+    #[deserr(error = DeserrJsonError<InvalidIndexRebuildDatabases>, missing_field_error = DeserrJsonError::missing_index_rebuild_databases)]
+    databases: BTreeSet<DerivedDatabase>,
+}
+
+/// Enqueues a task rebuilding some of the databases derived from the other ones,
+/// e.g. the prefix databases, without reindexing the documents.
+pub async fn rebuild_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<RebuildIndexRequest, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!(parameters = ?body, "Rebuild index");
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let body = body.into_inner();
+    analytics.publish(
+        "Index Rebuilt".to_string(),
+        json!({ "databases": body.databases }),
+        Some(&req),
+    );
+
+    let task = KindWithContent::IndexRebuild {
+        index_uid: index_uid.into_inner(),
+        databases: body.databases,
+    };
+
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Rebuild index");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 pub async fn delete_index(
     index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_DELETE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -278,3 +333,51 @@ pub async fn get_index_stats(
     debug!(returns = ?stats, "Get index stats");
     Ok(HttpResponse::Ok().json(stats))
 }
+
+pub async fn get_index_fields_stats(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::STATS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let stats = index.fields_stats(&rtxn)?;
+
+    debug!(returns = ?stats, "Get index fields stats");
+    Ok(HttpResponse::Ok().json(stats))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldSchemaView {
+    /// The number of documents containing a value of each type for this field.
+    pub types: BTreeMap<ValueType, u64>,
+    pub examples: Vec<serde_json::Value>,
+    /// The proportion of documents containing this field, between 0 and 1.
+    pub fill_rate: f64,
+}
+
+pub async fn get_index_schema(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::STATS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let number_of_documents = index.number_of_documents(&rtxn)?;
+    let schema: BTreeMap<_, _> = index
+        .fields_schema(&rtxn)?
+        .into_iter()
+        .map(|(field, FieldSchema { documents, types, examples })| {
+            let fill_rate = if number_of_documents == 0 {
+                0.0
+            } else {
+                documents as f64 / number_of_documents as f64
+            };
+            (field, FieldSchemaView { types, examples, fill_rate })
+        })
+        .collect();
+
+    debug!(returns = ?schema, "Get index schema");
+    Ok(HttpResponse::Ok().json(schema))
+}
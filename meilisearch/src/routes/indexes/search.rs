@@ -9,6 +9,7 @@ use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli;
 use meilisearch_types::serde_cs::vec::CS;
+use serde::Deserialize;
 use serde_json::Value;
 use tracing::debug;
 
@@ -19,19 +20,31 @@ use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    add_search_rules, perform_search, HybridQuery, MatchingStrategy, PiiProtection,
+    RankingScoreThreshold, RetrieveVectors, SearchKind, SearchQuery, SemanticRatio,
+    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
 };
+use crate::search_activity::SEARCH_ACTIVITY;
+use crate::search_jobs::{SearchJobOwner, MAX_SEARCH_JOB_HITS, SEARCH_JOBS};
 use crate::search_queue::SearchQueue;
+use crate::search_session::search_session;
+use crate::typo_statistics::TYPO_STATISTICS;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::resource("")
             .route(web::get().to(SeqHandler(search_with_url_query)))
             .route(web::post().to(SeqHandler(search_with_post))),
-    );
+    )
+    .service(web::resource("/jobs").route(web::post().to(SeqHandler(create_search_job))))
+    .service(web::resource("/jobs/{job_uid}").route(web::get().to(SeqHandler(get_search_job))));
+}
+
+#[derive(Deserialize)]
+pub struct SearchJobParam {
+    index_uid: String,
+    job_uid: String,
 }
 
 #[derive(Debug, deserr::Deserr)]
@@ -71,6 +84,12 @@ pub struct SearchQueryGet {
     show_ranking_score: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowRankingScoreDetails>)]
     show_ranking_score_details: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowQueryTerms>)]
+    show_query_terms: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowQueryInterpretation>)]
+    show_query_interpretation: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowTimings>)]
+    show_timings: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     facets: Option<CS<String>>,
     #[deserr( default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPreTag>)]
@@ -79,16 +98,30 @@ pub struct SearchQueryGet {
     highlight_post_tag: String,
     #[deserr(default = DEFAULT_CROP_MARKER(), error = DeserrQueryParamError<InvalidSearchCropMarker>)]
     crop_marker: String,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchMaxHighlightFragments>)]
+    max_highlight_fragments: Option<Param<usize>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchFragmentSeparator>)]
+    fragment_separator: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchMatchingStrategy>)]
     matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchPhraseFallback>)]
+    phrase_fallback: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToSearchOn>)]
     pub attributes_to_search_on: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchGeoSortMaxDistance>)]
+    pub geo_sort_max_distance: Option<Param<f64>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchGeoSortBucketWidth>)]
+    pub geo_sort_bucket_width: Option<Param<f64>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidEmbedder>)]
     pub hybrid_embedder: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSemanticRatio>)]
     pub hybrid_semantic_ratio: Option<SemanticRatioGet>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingScoreThreshold>)]
     pub ranking_score_threshold: Option<RankingScoreThresholdGet>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchCreateSession>)]
+    pub create_session: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSessionToken>)]
+    pub session_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
@@ -162,19 +195,33 @@ impl From<SearchQueryGet> for SearchQuery {
             crop_length: other.crop_length.0,
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             filter,
+            prepared_filter: None,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
             distinct: other.distinct,
+            diversify: None,
             show_matches_position: other.show_matches_position.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
+            show_query_terms: other.show_query_terms.0,
+            show_query_interpretation: other.show_query_interpretation.0,
+            show_timings: other.show_timings.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
+            max_highlight_fragments: other.max_highlight_fragments.map(|o| o.0),
+            fragment_separator: other.fragment_separator,
             matching_strategy: other.matching_strategy,
+            phrase_fallback: other.phrase_fallback.0,
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
+            languages: None,
+            synonyms: None,
+            geo_sort_max_distance: other.geo_sort_max_distance.map(|o| o.0),
+            geo_sort_bucket_width: other.geo_sort_bucket_width.map(|o| o.0),
             hybrid,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
+            create_session: other.create_session.0,
+            session_token: other.session_token,
         }
     }
 }
@@ -221,25 +268,44 @@ pub async fn search_with_url_query(
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
         add_search_rules(&mut query.filter, search_rules);
     }
+    query.apply_key_search_parameters(index_scheduler.filters().search_parameters())?;
 
     let mut aggregate = SearchAggregator::from_query(&query, &req);
 
     let index = index_scheduler.index(&index_uid)?;
+    SEARCH_ACTIVITY.record(&index_uid);
     let features = index_scheduler.features();
 
     let search_kind = search_kind(&query, index_scheduler.get_ref(), &index, features)?;
     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors, features)?;
+    let session = search_session(&index_uid, &index, &query)?;
+    let pii_protection =
+        PiiProtection::new(&index, index_scheduler.filters(), index_scheduler.indexer_config())?;
+    let typo_statistics = features.runtime_features().typo_statistics;
     let _permit = search_queue.try_get_search_permit().await?;
-    let search_result = tokio::task::spawn_blocking(move || {
-        perform_search(&index, query, search_kind, retrieve_vector)
-    })
-    .await?;
+    let search_result = search_queue
+        .run(move || {
+            perform_search(
+                &index,
+                query,
+                search_kind,
+                retrieve_vector,
+                session,
+                typo_statistics,
+                false,
+            )
+        })
+        .await?;
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
+        if let Some(typo_usage) = &search_result.typo_usage {
+            TYPO_STATISTICS.record(&index_uid, typo_usage);
+        }
     }
     analytics.get_search(aggregate);
 
-    let search_result = search_result?;
+    let mut search_result = search_result?;
+    pii_protection.protect_hits(&mut search_result.hits);
 
     debug!(returns = ?search_result, "Search get");
     Ok(HttpResponse::Ok().json(search_result))
@@ -262,35 +328,107 @@ pub async fn search_with_post(
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
         add_search_rules(&mut query.filter, search_rules);
     }
+    query.apply_key_search_parameters(index_scheduler.filters().search_parameters())?;
 
     let mut aggregate = SearchAggregator::from_query(&query, &req);
 
     let index = index_scheduler.index(&index_uid)?;
+    SEARCH_ACTIVITY.record(&index_uid);
 
     let features = index_scheduler.features();
 
     let search_kind = search_kind(&query, index_scheduler.get_ref(), &index, features)?;
     let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors, features)?;
+    let session = search_session(&index_uid, &index, &query)?;
+    let pii_protection =
+        PiiProtection::new(&index, index_scheduler.filters(), index_scheduler.indexer_config())?;
+    let typo_statistics = features.runtime_features().typo_statistics;
 
     let _permit = search_queue.try_get_search_permit().await?;
-    let search_result = tokio::task::spawn_blocking(move || {
-        perform_search(&index, query, search_kind, retrieve_vectors)
-    })
-    .await?;
+    let search_result = search_queue
+        .run(move || {
+            perform_search(
+                &index,
+                query,
+                search_kind,
+                retrieve_vectors,
+                session,
+                typo_statistics,
+                false,
+            )
+        })
+        .await?;
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
+        if let Some(typo_usage) = &search_result.typo_usage {
+            TYPO_STATISTICS.record(&index_uid, typo_usage);
+        }
         if search_result.degraded {
             MEILISEARCH_DEGRADED_SEARCH_REQUESTS.inc();
         }
     }
     analytics.post_search(aggregate);
 
-    let search_result = search_result?;
+    let mut search_result = search_result?;
+    pii_protection.protect_hits(&mut search_result.hits);
 
     debug!(returns = ?search_result, "Search post");
     Ok(HttpResponse::Ok().json(search_result))
 }
 
+pub async fn create_search_job(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebJson<SearchQuery, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let mut query = params.into_inner();
+    debug!(parameters = ?query, "Create search job");
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut query.filter, search_rules);
+    }
+    query.apply_key_search_parameters(index_scheduler.filters().search_parameters())?;
+    // the jobs count all the hits, but only return a bounded number of them
+    query.limit = query.limit.min(MAX_SEARCH_JOB_HITS);
+    query.hits_per_page = query.hits_per_page.map(|hits| hits.min(MAX_SEARCH_JOB_HITS));
+
+    let index = index_scheduler.index(&index_uid)?;
+    let features = index_scheduler.features();
+
+    let search_kind = search_kind(&query, index_scheduler.get_ref(), &index, features)?;
+    let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors, features)?;
+    let pii_protection =
+        PiiProtection::new(&index, index_scheduler.filters(), index_scheduler.indexer_config())?;
+
+    // the jobs always read the latest version of the index, they don't resume the search sessions
+    let owner = SearchJobOwner::new(index_scheduler.filters());
+    let job = SEARCH_JOBS.enqueue(&index_uid, owner, move || {
+        let mut search_result =
+            perform_search(&index, query, search_kind, retrieve_vectors, None, false, true)?;
+        pii_protection.protect_hits(&mut search_result.hits);
+        Ok(search_result)
+    })?;
+
+    debug!(returns = ?job, "Create search job");
+    Ok(HttpResponse::Accepted().json(job))
+}
+
+pub async fn get_search_job(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    path: web::Path<SearchJobParam>,
+) -> Result<HttpResponse, ResponseError> {
+    let SearchJobParam { index_uid, job_uid } = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+    let owner = SearchJobOwner::new(index_scheduler.filters());
+    let job = SEARCH_JOBS.get(&index_uid, &job_uid, owner)?;
+
+    debug!(returns = ?job, "Get search job");
+    Ok(HttpResponse::Ok().json(job))
+}
+
 pub fn search_kind(
     query: &SearchQuery,
     index_scheduler: &IndexScheduler,
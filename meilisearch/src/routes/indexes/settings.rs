@@ -1,20 +1,33 @@
+use actix_web::http::header::CONTENT_TYPE;
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
-use deserr::actix_web::AwebJson;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use deserr::Deserr;
+use futures::StreamExt;
 use index_scheduler::IndexScheduler;
-use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
+use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli::update::Setting;
-use meilisearch_types::settings::{settings, RankingRuleView, SecretPolicy, Settings, Unchecked};
+use meilisearch_types::settings::{
+    effective_settings, settings, Checked, DocumentHookSettings, LanguagePresetView,
+    RankingRuleView, SecretPolicy, Settings, Unchecked,
+};
+use meilisearch_types::star_or::OptionStarOrList;
 use meilisearch_types::tasks::KindWithContent;
 use serde_json::json;
 use tracing::debug;
 
 use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::*;
-use crate::extractors::authentication::GuardedData;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::payload::Payload;
 use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
 use crate::Opt;
 
@@ -203,6 +216,31 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/dense-sortable-attributes",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDenseSortableAttributes,
+    >,
+    dense_sortable_attributes,
+    "denseSortableAttributes",
+    analytics,
+    |setting: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "DenseSortableAttributes Updated".to_string(),
+            json!({
+                "dense_sortable_attributes": {
+                    "total": setting.as_ref().map(|sort| sort.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/displayed-attributes",
     put,
@@ -267,6 +305,21 @@ make_setting_route!(
                             .set()
                             .map(|s| s.two_typos.set()))
                         .flatten(),
+                    "half_typos": setting
+                        .as_ref()
+                        .map(|s| s.half_typos.as_ref().set().is_some()),
+                    "disable_on_numbers": setting
+                        .as_ref()
+                        .and_then(|s| s.disable_on_numbers.as_ref().set().copied()),
+                    "first_letter_typos": setting
+                        .as_ref()
+                        .and_then(|s| s.first_letter_typos.as_ref().set().copied()),
+                    "enable_phonetic": setting
+                        .as_ref()
+                        .and_then(|s| s.enable_phonetic.as_ref().set().copied()),
+                    "typo_costs": setting
+                        .as_ref()
+                        .map(|s| s.typo_costs.as_ref().set().is_some()),
                 },
             }),
             Some(req),
@@ -277,14 +330,14 @@ make_setting_route!(
 make_setting_route!(
     "/searchable-attributes",
     put,
-    Vec<String>,
+    Vec<meilisearch_types::settings::SearchableAttribute>,
     meilisearch_types::deserr::DeserrJsonError<
         meilisearch_types::error::deserr_codes::InvalidSettingsSearchableAttributes,
     >,
     searchable_attributes,
     "searchableAttributes",
     analytics,
-    |setting: &Option<Vec<String>>, req: &HttpRequest| {
+    |setting: &Option<Vec<meilisearch_types::settings::SearchableAttribute>>, req: &HttpRequest| {
         use serde_json::json;
 
         analytics.publish(
@@ -292,7 +345,8 @@ make_setting_route!(
             json!({
                 "searchable_attributes": {
                     "total": setting.as_ref().map(|searchable| searchable.len()),
-                    "with_wildcard": setting.as_ref().map(|searchable| searchable.iter().any(|searchable| searchable == "*")),
+                    "with_wildcard": setting.as_ref().map(|searchable| searchable.iter().any(|searchable| searchable.attribute() == "*")),
+                    "with_weights": setting.as_ref().map(|searchable| searchable.iter().any(|searchable| searchable.weight().is_some())),
                 },
             }),
             Some(req),
@@ -400,6 +454,31 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/exact-words",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsExactWords,
+    >,
+    exact_words,
+    "exactWords",
+    analytics,
+    |exact_words: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "ExactWords Updated".to_string(),
+            json!({
+                "exact_words": {
+                    "total": exact_words.as_ref().map(|exact_words| exact_words.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/synonyms",
     put,
@@ -449,6 +528,30 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/version-attribute",
+    put,
+    String,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsVersionAttribute,
+    >,
+    version_attribute,
+    "versionAttribute",
+    analytics,
+    |version: &Option<String>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "VersionAttribute Updated".to_string(),
+            json!({
+                "version_attribute": {
+                    "set": version.is_some(),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/proximity-precision",
     put,
@@ -497,6 +600,10 @@ make_setting_route!(
                     "attribute_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Attribute))),
                     "sort_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Sort))),
                     "exactness_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Exactness))),
+                    "plugins": setting.as_ref().map(|rr| rr.iter().filter(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Plugin(_))).count()),
+                    "recency": setting.as_ref().map(|rr| rr.iter().filter(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Recency { .. })).count()),
+                    "decay": setting.as_ref().map(|rr| rr.iter().filter(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Decay { .. })).count()),
+                    "bm25_position": setting.as_ref().map(|rr| rr.iter().position(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Bm25))),
                     "values": setting.as_ref().map(|rr| rr.iter().filter(|s| matches!(s, meilisearch_types::settings::RankingRuleView::Asc(_) | meilisearch_types::settings::RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
                 }
             }),
@@ -528,6 +635,7 @@ make_setting_route!(
                         s.sort_facet_values_by.as_ref().set().map(|s| s.iter().any(|(k, v)| k == "*" && v == &FacetValuesSort::Count))
                     }),
                     "sort_facet_values_by_total": setting.as_ref().and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
+                    "value_aliases_total": setting.as_ref().and_then(|s| s.value_aliases.as_ref().set().map(|s| s.len())),
                 },
             }),
             Some(req),
@@ -642,6 +750,525 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/prefix-cache-size",
+    put,
+    usize,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPrefixCacheSize,
+    >,
+    prefix_cache_size,
+    "prefixCacheSize",
+    analytics,
+    |setting: &Option<usize>, req: &HttpRequest| {
+        analytics.publish(
+            "Prefix Cache Size Updated".to_string(),
+            serde_json::json!({"prefix_cache_size": setting }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/long-query-threshold",
+    put,
+    usize,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLongQueryThreshold,
+    >,
+    long_query_threshold,
+    "longQueryThreshold",
+    analytics,
+    |setting: &Option<usize>, req: &HttpRequest| {
+        analytics.publish(
+            "Long Query Threshold Updated".to_string(),
+            serde_json::json!({"long_query_threshold": setting }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/quote-characters",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsQuoteCharacters,
+    >,
+    quote_characters,
+    "quoteCharacters",
+    analytics,
+    |quote_characters: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "quoteCharacters Updated".to_string(),
+            json!({
+                "quote_characters": {
+                    "total": quote_characters.as_ref().map(|quote_characters| quote_characters.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/language-preset",
+    put,
+    meilisearch_types::settings::LanguagePresetView,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLanguagePreset,
+    >,
+    language_preset,
+    "languagePreset",
+    analytics,
+    |preset: &Option<meilisearch_types::settings::LanguagePresetView>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "LanguagePreset Updated".to_string(),
+            json!({
+                "language_preset": {
+                    "set": preset.is_some(),
+                    "value": preset,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/presence-only-attributes",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPresenceOnlyAttributes,
+    >,
+    presence_only_attributes,
+    "presenceOnlyAttributes",
+    analytics,
+    |attributes: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "PresenceOnlyAttributes Updated".to_string(),
+            json!({
+                "presence_only_attributes": {
+                    "total": attributes.as_ref().map(|attributes| attributes.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/chunking",
+    patch,
+    meilisearch_types::settings::ChunkingSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsChunking,
+    >,
+    chunking,
+    "chunking",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::ChunkingSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Chunking Updated".to_string(),
+            json!({
+                "chunking": {
+                    "max_words": setting.as_ref().and_then(|s| s.max_words.set()),
+                    "overlap": setting.as_ref().and_then(|s| s.overlap.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/document-hook",
+    patch,
+    meilisearch_types::settings::DocumentHookSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDocumentHook,
+    >,
+    document_hook,
+    "documentHook",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::DocumentHookSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "DocumentHook Updated".to_string(),
+            json!({
+                "document_hook": {
+                    "module": setting.as_ref().map(|s| s.module.as_ref().set().is_some()),
+                    "fuel": setting.as_ref().and_then(|s| s.fuel.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/computed-fields",
+    put,
+    std::collections::BTreeMap<String, String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsComputedFields,
+    >,
+    computed_fields,
+    "computedFields",
+    analytics,
+    |setting: &Option<std::collections::BTreeMap<String, String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "ComputedFields Updated".to_string(),
+            json!({
+                "computed_fields": {
+                    "total": setting.as_ref().map(|fields| fields.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/prepared-filters",
+    put,
+    std::collections::BTreeMap<String, String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPreparedFilters,
+    >,
+    prepared_filters,
+    "preparedFilters",
+    analytics,
+    |setting: &Option<std::collections::BTreeMap<String, String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "PreparedFilters Updated".to_string(),
+            json!({
+                "prepared_filters": {
+                    "total": setting.as_ref().map(|filters| filters.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/pii-attributes",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPiiAttributes,
+    >,
+    pii_attributes,
+    "piiAttributes",
+    analytics,
+    |setting: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "PiiAttributes Updated".to_string(),
+            json!({
+                "pii_attributes": {
+                    "total": setting.as_ref().map(|attributes| attributes.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/query-limits",
+    patch,
+    meilisearch_types::settings::QueryLimitsSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsQueryLimits,
+    >,
+    query_limits,
+    "queryLimits",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::QueryLimitsSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "QueryLimits Updated".to_string(),
+            json!({
+                "query_limits": {
+                    "max_length": setting.as_ref().and_then(|s| s.max_length.set()),
+                    "max_terms": setting.as_ref().and_then(|s| s.max_terms.set()),
+                    "max_phrases": setting.as_ref().and_then(|s| s.max_phrases.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/regex-filter",
+    patch,
+    meilisearch_types::settings::RegexFilterSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsRegexFilter,
+    >,
+    regex_filter,
+    "regexFilter",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::RegexFilterSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "RegexFilter Updated".to_string(),
+            json!({
+                "regex_filter": {
+                    "enabled": setting.as_ref().and_then(|s| s.enabled.set()),
+                    "max_candidates": setting.as_ref().and_then(|s| s.max_candidates.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/field-languages",
+    put,
+    std::collections::BTreeMap<String, LanguagePresetView>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsFieldLanguages,
+    >,
+    field_languages,
+    "fieldLanguages",
+    analytics,
+    |setting: &Option<std::collections::BTreeMap<String, LanguagePresetView>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "FieldLanguages Updated".to_string(),
+            json!({
+                "field_languages": {
+                    "total": setting.as_ref().map(|fields| fields.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/min-prefix-length",
+    put,
+    usize,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsMinPrefixLength,
+    >,
+    min_prefix_length,
+    "minPrefixLength",
+    analytics,
+    |setting: &Option<usize>, req: &HttpRequest| {
+        analytics.publish(
+            "Min Prefix Length Updated".to_string(),
+            serde_json::json!({"min_prefix_length": setting }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/blocked-terms",
+    patch,
+    meilisearch_types::settings::BlockedTermsSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsBlockedTerms,
+    >,
+    blocked_terms,
+    "blockedTerms",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::BlockedTermsSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "BlockedTerms Updated".to_string(),
+            json!({
+                "blocked_terms": {
+                    "total": setting.as_ref().and_then(|s| s.terms.as_ref().set()).map(|terms| terms.len()),
+                    "mode": setting.as_ref().and_then(|s| s.mode.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/new-fields",
+    patch,
+    meilisearch_types::settings::NewFieldsSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsNewFields,
+    >,
+    new_fields,
+    "newFields",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::NewFieldsSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "NewFields Updated".to_string(),
+            json!({
+                "new_fields": {
+                    "searchable": setting.as_ref().and_then(|s| s.searchable.set()),
+                    "filterable": setting.as_ref().and_then(|s| s.filterable.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/query-rewrites",
+    put,
+    Vec<meilisearch_types::settings::QueryRewriteRuleView>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsQueryRewrites,
+    >,
+    query_rewrites,
+    "queryRewrites",
+    analytics,
+    |setting: &Option<Vec<meilisearch_types::settings::QueryRewriteRuleView>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "QueryRewrites Updated".to_string(),
+            json!({
+                "query_rewrites": {
+                    "total": setting.as_ref().map(|rules| rules.len()),
+                    "regex": setting.as_ref().map(|rules| rules.iter().filter(|rule| rule.regex).count()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/position-decay",
+    put,
+    meilisearch_types::settings::PositionDecayView,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPositionDecay,
+    >,
+    position_decay,
+    "positionDecay",
+    analytics,
+    |decay: &Option<meilisearch_types::settings::PositionDecayView>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "PositionDecay Updated".to_string(),
+            json!({
+                "position_decay": {
+                    "set": decay.is_some(),
+                    "value": decay.unwrap_or_default(),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/batching",
+    patch,
+    meilisearch_types::settings::BatchingSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsBatching,
+    >,
+    batching,
+    "batching",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::BatchingSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Batching Updated".to_string(),
+            json!({
+                "batching": {
+                    "max_batch_size": setting.as_ref().and_then(|s| s.max_batch_size.set()),
+                    "max_batch_payload": setting.as_ref().and_then(|s| s.max_batch_payload.set()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/collation",
+    patch,
+    meilisearch_types::settings::CollationSettings,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsCollation,
+    >,
+    collation,
+    "collation",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::CollationSettings>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Collation Updated".to_string(),
+            json!({
+                "collation": {
+                    "locale": setting.as_ref().and_then(|s| s.locale.as_ref().set().copied()),
+                    "total_attributes": setting.as_ref().and_then(|s| s.attributes.as_ref().set()).map(|attributes| attributes.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/tie-breaker",
+    put,
+    meilisearch_types::settings::TieBreakerView,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsTieBreaker,
+    >,
+    tie_breaker,
+    "tieBreaker",
+    analytics,
+    |tie_breaker: &Option<meilisearch_types::settings::TieBreakerView>, req: &HttpRequest| {
+        use meilisearch_types::settings::TieBreakerView;
+        use serde_json::json;
+        analytics.publish(
+            "TieBreaker Updated".to_string(),
+            json!({
+                "tie_breaker": {
+                    "set": tie_breaker.is_some(),
+                    "primary_key": matches!(tie_breaker, Some(TieBreakerView::PrimaryKey(_))),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 macro_rules! generate_configure {
     ($($mod:ident),*) => {
         pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -651,6 +1278,9 @@ macro_rules! generate_configure {
                 .route(web::patch().to(SeqHandler(update_all)))
                 .route(web::get().to(SeqHandler(get_all)))
                 .route(web::delete().to(SeqHandler(delete_all))))
+                .service(web::resource("/effective").route(web::get().to(SeqHandler(get_effective))))
+                .service(web::resource("/copy-to/{other_index_uid}").route(web::post().to(SeqHandler(copy_to))))
+                .service(web::resource("/document-hook/module").route(web::put().to(SeqHandler(update_document_hook_module))))
                 $(.service($mod::resources()))*;
         }
     };
@@ -659,21 +1289,45 @@ macro_rules! generate_configure {
 generate_configure!(
     filterable_attributes,
     sortable_attributes,
+    dense_sortable_attributes,
     displayed_attributes,
     searchable_attributes,
     distinct_attribute,
+    version_attribute,
     proximity_precision,
     stop_words,
     separator_tokens,
     non_separator_tokens,
     dictionary,
+    exact_words,
     synonyms,
     ranking_rules,
     typo_tolerance,
     pagination,
     faceting,
     embedders,
-    search_cutoff_ms
+    search_cutoff_ms,
+    prefix_cache_size,
+    long_query_threshold,
+    quote_characters,
+    language_preset,
+    presence_only_attributes,
+    chunking,
+    document_hook,
+    computed_fields,
+    prepared_filters,
+    pii_attributes,
+    query_limits,
+    regex_filter,
+    field_languages,
+    min_prefix_length,
+    blocked_terms,
+    new_fields,
+    query_rewrites,
+    position_decay,
+    batching,
+    collation,
+    tie_breaker
 );
 
 pub async fn update_all(
@@ -700,11 +1354,16 @@ pub async fn update_all(
                 "attribute_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Attribute))),
                 "sort_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Sort))),
                 "exactness_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Exactness))),
+                "plugins": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| matches!(s, RankingRuleView::Plugin(_))).count()),
+                "recency": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| matches!(s, RankingRuleView::Recency { .. })).count()),
+                "decay": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| matches!(s, RankingRuleView::Decay { .. })).count()),
+                "bm25_position": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().position(|s| matches!(s, RankingRuleView::Bm25))),
                 "values": new_settings.ranking_rules.as_ref().set().map(|rr| rr.iter().filter(|s| !matches!(s, RankingRuleView::Asc(_) | RankingRuleView::Desc(_)) ).map(|x| x.to_string()).collect::<Vec<_>>().join(", ")),
             },
             "searchable_attributes": {
                 "total": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.len()),
-                "with_wildcard": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.iter().any(|searchable| searchable == "*")),
+                "with_wildcard": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.iter().any(|searchable| searchable.attribute() == "*")),
+                "with_weights": new_settings.searchable_attributes.as_ref().set().map(|searchable| searchable.iter().any(|searchable| searchable.weight().is_some())),
             },
             "displayed_attributes": {
                 "total": new_settings.displayed_attributes.as_ref().set().map(|displayed| displayed.len()),
@@ -714,6 +1373,9 @@ pub async fn update_all(
                 "total": new_settings.sortable_attributes.as_ref().set().map(|sort| sort.len()),
                 "has_geo": new_settings.sortable_attributes.as_ref().set().map(|sort| sort.iter().any(|s| s == "_geo")),
             },
+           "dense_sortable_attributes": {
+                "total": new_settings.dense_sortable_attributes.as_ref().set().map(|sort| sort.len()),
+            },
            "filterable_attributes": {
                 "total": new_settings.filterable_attributes.as_ref().set().map(|filter| filter.len()),
                 "has_geo": new_settings.filterable_attributes.as_ref().set().map(|filter| filter.iter().any(|s| s == "_geo")),
@@ -721,6 +1383,9 @@ pub async fn update_all(
             "distinct_attribute": {
                 "set": new_settings.distinct_attribute.as_ref().set().is_some()
             },
+            "version_attribute": {
+                "set": new_settings.version_attribute.as_ref().set().is_some()
+            },
             "proximity_precision": {
                 "set": new_settings.proximity_precision.as_ref().set().is_some(),
                 "value": new_settings.proximity_precision.as_ref().set().copied().unwrap_or_default()
@@ -755,6 +1420,26 @@ pub async fn update_all(
                         .set()
                         .map(|s| s.two_typos.set()))
                     .flatten(),
+                "half_typos": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .map(|s| s.half_typos.as_ref().set().is_some()),
+                "disable_on_numbers": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.disable_on_numbers.as_ref().set().copied()),
+                "first_letter_typos": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.first_letter_typos.as_ref().set().copied()),
+                "enable_phonetic": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.enable_phonetic.as_ref().set().copied()),
+                "typo_costs": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .map(|s| s.typo_costs.as_ref().set().is_some()),
             },
             "faceting": {
                 "max_values_per_facet": new_settings.faceting
@@ -771,6 +1456,10 @@ pub async fn update_all(
                     .as_ref()
                     .set()
                     .and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
+                "value_aliases_total": new_settings.faceting
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.value_aliases.as_ref().set().map(|s| s.len())),
             },
             "pagination": {
                 "max_total_hits": new_settings.pagination
@@ -786,6 +1475,76 @@ pub async fn update_all(
             },
             "embedders": crate::routes::indexes::settings::embedder_analytics(new_settings.embedders.as_ref().set()),
             "search_cutoff_ms": new_settings.search_cutoff_ms.as_ref().set(),
+            "prefix_cache_size": new_settings.prefix_cache_size.as_ref().set(),
+            "long_query_threshold": new_settings.long_query_threshold.as_ref().set(),
+            "quote_characters": {
+                "total": new_settings.quote_characters.as_ref().set().map(|quote_characters| quote_characters.len()),
+            },
+            "language_preset": {
+                "set": new_settings.language_preset.as_ref().set().is_some(),
+                "value": new_settings.language_preset.as_ref().set(),
+            },
+            "presence_only_attributes": {
+                "total": new_settings.presence_only_attributes.as_ref().set().map(|attributes| attributes.len()),
+            },
+            "chunking": {
+                "max_words": new_settings.chunking.as_ref().set().and_then(|s| s.max_words.as_ref().set()),
+                "overlap": new_settings.chunking.as_ref().set().and_then(|s| s.overlap.as_ref().set()),
+            },
+            "document_hook": {
+                "module": new_settings.document_hook.as_ref().set().map(|s| s.module.as_ref().set().is_some()),
+                "fuel": new_settings.document_hook.as_ref().set().and_then(|s| s.fuel.as_ref().set()),
+            },
+            "computed_fields": {
+                "total": new_settings.computed_fields.as_ref().set().map(|fields| fields.len()),
+            },
+            "prepared_filters": {
+                "total": new_settings.prepared_filters.as_ref().set().map(|filters| filters.len()),
+            },
+            "pii_attributes": {
+                "total": new_settings.pii_attributes.as_ref().set().map(|attributes| attributes.len()),
+            },
+            "query_limits": {
+                "max_length": new_settings.query_limits.as_ref().set().and_then(|s| s.max_length.as_ref().set()),
+                "max_terms": new_settings.query_limits.as_ref().set().and_then(|s| s.max_terms.as_ref().set()),
+                "max_phrases": new_settings.query_limits.as_ref().set().and_then(|s| s.max_phrases.as_ref().set()),
+            },
+            "regex_filter": {
+                "enabled": new_settings.regex_filter.as_ref().set().and_then(|s| s.enabled.as_ref().set()),
+                "max_candidates": new_settings.regex_filter.as_ref().set().and_then(|s| s.max_candidates.as_ref().set()),
+            },
+            "field_languages": {
+                "total": new_settings.field_languages.as_ref().set().map(|fields| fields.len()),
+            },
+            "min_prefix_length": new_settings.min_prefix_length.as_ref().set(),
+            "blocked_terms": {
+                "total": new_settings.blocked_terms.as_ref().set().and_then(|s| s.terms.as_ref().set()).map(|terms| terms.len()),
+                "mode": new_settings.blocked_terms.as_ref().set().and_then(|s| s.mode.as_ref().set()),
+            },
+            "new_fields": {
+                "searchable": new_settings.new_fields.as_ref().set().and_then(|s| s.searchable.as_ref().set()),
+                "filterable": new_settings.new_fields.as_ref().set().and_then(|s| s.filterable.as_ref().set()),
+            },
+            "query_rewrites": {
+                "total": new_settings.query_rewrites.as_ref().set().map(|rules| rules.len()),
+                "regex": new_settings.query_rewrites.as_ref().set().map(|rules| rules.iter().filter(|rule| rule.regex).count()),
+            },
+            "position_decay": {
+                "set": new_settings.position_decay.as_ref().set().is_some(),
+                "value": new_settings.position_decay.as_ref().set().copied().unwrap_or_default(),
+            },
+            "batching": {
+                "max_batch_size": new_settings.batching.as_ref().set().and_then(|s| s.max_batch_size.as_ref().set()),
+                "max_batch_payload": new_settings.batching.as_ref().set().and_then(|s| s.max_batch_payload.as_ref().set()),
+            },
+            "collation": {
+                "locale": new_settings.collation.as_ref().set().and_then(|s| s.locale.as_ref().set()),
+                "total_attributes": new_settings.collation.as_ref().set().and_then(|s| s.attributes.as_ref().set()).map(|attributes| attributes.len()),
+            },
+            "tie_breaker": {
+                "set": new_settings.tie_breaker.as_ref().set().is_some(),
+                "primary_key": matches!(new_settings.tie_breaker.as_ref().set(), Some(meilisearch_types::settings::TieBreakerView::PrimaryKey(_))),
+            },
         }),
         Some(&req),
     );
@@ -822,6 +1581,160 @@ pub async fn get_all(
     Ok(HttpResponse::Ok().json(new_settings))
 }
 
+pub async fn get_effective(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let effective_settings = effective_settings(&index, &rtxn)?;
+    debug!(returns = ?effective_settings, "Get effective settings");
+    Ok(HttpResponse::Ok().json(effective_settings))
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct CopySettingsQuery {
+    #[deserr(default, error = DeserrQueryParamError<InvalidSettingsCopySettings>)]
+    settings: OptionStarOrList<String>,
+}
+
+pub async fn copy_to(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    path: web::Path<(String, String)>,
+    params: AwebQueryParameter<CopySettingsQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let (index_uid, other_index_uid) = path.into_inner();
+    let index_uid = IndexUid::try_from(index_uid)?;
+    let other_index_uid = IndexUid::try_from(other_index_uid)?;
+    if !index_scheduler.filters().is_index_authorized(&other_index_uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    // the secrets must be copied too, otherwise the embedders of the target would be unusable
+    let mut new_settings = settings(&index, &rtxn, SecretPolicy::RevealSecrets)?;
+    drop(rtxn);
+
+    let copied_settings = params.into_inner().settings.merge_star_and_none();
+    if let Some(names) = &copied_settings {
+        new_settings.retain(names).map_err(|name| {
+            MeilisearchHttpError::UnknownSettingToCopy(name, Settings::<Checked>::NAMES)
+        })?;
+    }
+    debug!(from = %index_uid, to = %other_index_uid, "Copy settings");
+    let new_settings = validate_settings(new_settings.into_unchecked(), &index_scheduler)?;
+
+    analytics.publish(
+        "Settings Copied".to_string(),
+        json!({ "settings": copied_settings }),
+        Some(&req),
+    );
+
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&other_index_uid);
+    let task = KindWithContent::SettingsUpdate {
+        index_uid: other_index_uid.into_inner(),
+        new_settings: Box::new(new_settings),
+        is_deletion: false,
+        allow_index_creation,
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Copy settings");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentHookModuleQuery {
+    #[deserr(default, error = DeserrQueryParamError<InvalidSettingsDocumentHook>)]
+    fuel: Option<Param<u64>>,
+}
+
+/// Uploads the raw WebAssembly module of the document hook, which is
+/// much lighter than sending it base64 encoded in the settings.
+pub async fn update_document_hook_module(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<DocumentHookModuleQuery, DeserrQueryParamError>,
+    mut body: Payload,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let fuel = params.into_inner().fuel.map(|fuel| fuel.0);
+
+    let accepted_content_type = vec!["application/wasm".to_string()];
+    match req.headers().get(CONTENT_TYPE).map(|content_type| content_type.to_str()) {
+        Some(Ok("application/wasm")) => (),
+        Some(content_type) => {
+            let content_type = content_type.unwrap_or_default().to_string();
+            return Err(MeilisearchHttpError::InvalidContentType(
+                content_type,
+                accepted_content_type,
+            )
+            .into());
+        }
+        None => return Err(MeilisearchHttpError::MissingContentType(accepted_content_type).into()),
+    }
+
+    let mut module = Vec::new();
+    while let Some(bytes) = body.next().await {
+        module.extend_from_slice(&bytes?);
+    }
+    debug!(module_size = module.len(), ?fuel, "Update document hook module");
+
+    analytics.publish(
+        "DocumentHook Updated".to_string(),
+        json!({
+            "document_hook": {
+                "module": true,
+                "fuel": fuel,
+            },
+        }),
+        Some(&req),
+    );
+
+    let new_settings = Settings {
+        document_hook: Setting::Set(DocumentHookSettings {
+            module: Setting::Set(BASE64_STANDARD.encode(module)),
+            fuel: fuel.map_or(Setting::NotSet, Setting::Set),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let new_settings = validate_settings(new_settings, &index_scheduler)?;
+
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let task = KindWithContent::SettingsUpdate {
+        index_uid: index_uid.into_inner(),
+        new_settings: Box::new(new_settings),
+        is_deletion: false,
+        allow_index_creation,
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Update document hook module");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 pub async fn delete_all(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -17,8 +17,9 @@ use crate::analytics::{Analytics, SimilarAggregator};
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::search::{
-    add_search_rules, perform_similar, RankingScoreThresholdSimilar, RetrieveVectors, SearchKind,
-    SimilarQuery, SimilarResult, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    add_search_rules, perform_similar, PiiProtection, RankingScoreThresholdSimilar,
+    RetrieveVectors, SearchKind, SimilarQuery, SimilarResult, DEFAULT_SEARCH_LIMIT,
+    DEFAULT_SEARCH_OFFSET,
 };
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -104,11 +105,16 @@ async fn similar(
 
     let (embedder_name, embedder) =
         SearchKind::embedder(&index_scheduler, &index, query.embedder.as_deref(), None)?;
+    let pii_protection =
+        PiiProtection::new(&index, index_scheduler.filters(), index_scheduler.indexer_config())?;
 
-    tokio::task::spawn_blocking(move || {
+    let mut similar_result = tokio::task::spawn_blocking(move || {
         perform_similar(&index, query, embedder_name, embedder, retrieve_vectors)
     })
-    .await?
+    .await??;
+    pii_protection.protect_hits(&mut similar_result.hits);
+
+    Ok(similar_result)
 }
 
 #[derive(Debug, deserr::Deserr)]
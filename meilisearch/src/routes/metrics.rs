@@ -10,6 +10,8 @@ use prometheus::{Encoder, TextEncoder};
 use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::routes::create_all_stats;
+use crate::search_queue::SearchQueue;
+use crate::Opt;
 
 pub fn configure(config: &mut web::ServiceConfig) {
     config.service(web::resource("").route(web::get().to(get_metrics)));
@@ -18,6 +20,8 @@ pub fn configure(config: &mut web::ServiceConfig) {
 pub async fn get_metrics(
     index_scheduler: GuardedData<ActionPolicy<{ actions::METRICS_GET }>, Data<IndexScheduler>>,
     auth_controller: Data<AuthController>,
+    search_queue: Data<SearchQueue>,
+    opt: Data<Opt>,
 ) -> Result<HttpResponse, ResponseError> {
     index_scheduler.features().check_metrics()?;
     let auth_filters = index_scheduler.filters();
@@ -52,8 +56,27 @@ pub async fn get_metrics(
     if let Some(last_update) = response.last_update {
         crate::metrics::MEILISEARCH_LAST_UPDATE.set(last_update.unix_timestamp());
     }
+    crate::metrics::MEILISEARCH_SEARCH_THREADS.set(search_queue.threads() as i64);
+    crate::metrics::MEILISEARCH_INDEXING_THREADS
+        .set(*opt.indexer_options.max_indexing_threads as i64);
     crate::metrics::MEILISEARCH_IS_INDEXING.set(index_scheduler.is_task_processing()? as i64);
 
+    let queue_stats = index_scheduler.get_task_queue_stats(auth_filters)?;
+    crate::metrics::MEILISEARCH_TASK_QUEUE_LAG_SECONDS.set(queue_stats.current_lag_seconds);
+    crate::metrics::MEILISEARCH_TASK_QUEUE_AVERAGE_LAG_SECONDS.set(queue_stats.average_lag_seconds);
+    crate::metrics::MEILISEARCH_BATCHES_PER_HOUR.set(queue_stats.batches_per_hour as i64);
+    // the types without any task finished during the last hour must disappear
+    crate::metrics::MEILISEARCH_TASK_PROCESSING_DURATION_SECONDS.reset();
+    for (kind, durations) in queue_stats.processing_durations {
+        crate::metrics::MEILISEARCH_TASK_PROCESSING_DURATION_SECONDS
+            .with_label_values(&[&kind, "0.5"])
+            .set(durations.p50_seconds);
+        crate::metrics::MEILISEARCH_TASK_PROCESSING_DURATION_SECONDS
+            .with_label_values(&[&kind, "0.95"])
+            .set(durations.p95_seconds);
+    }
+    crate::metrics::MEILISEARCH_INDEXED_PAYLOAD_BYTES.set(queue_stats.indexed_payload_bytes as i64);
+
     let encoder = TextEncoder::new();
     let mut buffer = vec![];
     encoder.encode(&prometheus::gather(), &mut buffer).expect("Failed to encode metrics");
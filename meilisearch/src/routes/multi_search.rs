@@ -1,23 +1,31 @@
+use std::collections::BTreeMap;
+
 use actix_http::StatusCode;
 use actix_web::web::{self, Data};
 use actix_web::{HttpRequest, HttpResponse};
-use deserr::actix_web::AwebJson;
+use futures::future;
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::actions;
 use serde::Serialize;
+use serde_json::Value;
 use tracing::debug;
 
 use crate::analytics::{Analytics, MultiSearchAggregator};
+use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
+use crate::federation::Federation;
 use crate::routes::indexes::search::search_kind;
 use crate::search::{
-    add_search_rules, perform_search, RetrieveVectors, SearchQueryWithIndex, SearchResultWithIndex,
+    add_search_rules, perform_search, PiiProtection, RetrieveVectors, SearchQueryWithIndex,
+    SearchResultWithIndex,
 };
+use crate::search_activity::SEARCH_ACTIVITY;
 use crate::search_queue::SearchQueue;
+use crate::typo_statistics::TYPO_STATISTICS;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("").route(web::post().to(SeqHandler(multi_search_with_post))));
@@ -25,7 +33,15 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
 
 #[derive(Serialize)]
 struct SearchResults {
-    results: Vec<SearchResultWithIndex>,
+    results: Vec<MultiSearchResult>,
+}
+
+/// The result of a query, computed locally or by the upstream holding its index.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum MultiSearchResult {
+    Local(SearchResultWithIndex),
+    Remote(Value),
 }
 
 #[derive(Debug, deserr::Deserr)]
@@ -37,11 +53,18 @@ pub struct SearchQueries {
 pub async fn multi_search_with_post(
     index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
     search_queue: Data<SearchQueue>,
-    params: AwebJson<SearchQueries, DeserrJsonError>,
+    federation: Data<Federation>,
+    params: web::Json<Value>,
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    let queries = params.into_inner().queries;
+    let params = params.into_inner();
+    // The queries forwarded to the upstreams are sent as they were received.
+    let raw_queries = match params.get("queries") {
+        Some(Value::Array(queries)) if federation.is_enabled() => queries.clone(),
+        _ => Vec::new(),
+    };
+    let queries = deserr::deserialize::<SearchQueries, _, DeserrJsonError>(params)?.queries;
 
     let mut multi_aggregate = MultiSearchAggregator::from_queries(&queries, &req);
     let features = index_scheduler.features();
@@ -55,6 +78,7 @@ pub async fn multi_search_with_post(
     // changes.
     let search_results: Result<_, (ResponseError, usize)> = async {
         let mut search_results = Vec::with_capacity(queries.len());
+        let mut remote_queries: BTreeMap<usize, Vec<(usize, Value)>> = BTreeMap::new();
         for (query_index, (index_uid, mut query)) in
             queries.into_iter().map(SearchQueryWithIndex::into_index_query).enumerate()
         {
@@ -69,6 +93,30 @@ pub async fn multi_search_with_post(
             {
                 add_search_rules(&mut query.filter, search_rules);
             }
+            query
+                .apply_key_search_parameters(index_scheduler.filters().search_parameters())
+                .with_index(query_index)?;
+
+            if let Some(upstream) = federation.upstream_of(&index_uid) {
+                // the PII attributes of the remote indexes aren't known, their hits are only
+                // returned to the keys allowed to see the PII attributes
+                if !index_scheduler.filters().allow_pii() {
+                    return Err(MeilisearchHttpError::FederationPiiNotAllowed(
+                        index_uid.into_inner(),
+                    ))
+                    .with_index(query_index);
+                }
+                let mut raw_query = raw_queries[query_index].clone();
+                query
+                    .write_key_search_parameters(
+                        &mut raw_query,
+                        index_scheduler.filters().search_parameters(),
+                    )
+                    .with_index(query_index)?;
+                remote_queries.entry(upstream).or_default().push((query_index, raw_query));
+                search_results.push(None);
+                continue;
+            }
 
             let index = index_scheduler
                 .index(&index_uid)
@@ -80,24 +128,66 @@ pub async fn multi_search_with_post(
                     err
                 })
                 .with_index(query_index)?;
+            SEARCH_ACTIVITY.record(&index_uid);
 
             let search_kind = search_kind(&query, index_scheduler.get_ref(), &index, features)
                 .with_index(query_index)?;
             let retrieve_vector =
                 RetrieveVectors::new(query.retrieve_vectors, features).with_index(query_index)?;
-
-            let search_result = tokio::task::spawn_blocking(move || {
-                perform_search(&index, query, search_kind, retrieve_vector)
-            })
-            .await
+            let pii_protection = PiiProtection::new(
+                &index,
+                index_scheduler.filters(),
+                index_scheduler.indexer_config(),
+            )
             .with_index(query_index)?;
 
-            search_results.push(SearchResultWithIndex {
+            let typo_statistics = features.runtime_features().typo_statistics;
+            let search_result = search_queue
+                .run(move || {
+                    perform_search(
+                        &index,
+                        query,
+                        search_kind,
+                        retrieve_vector,
+                        None,
+                        typo_statistics,
+                        false,
+                    )
+                })
+                .await
+                .with_index(query_index)?;
+
+            let mut search_result = search_result.with_index(query_index)?;
+            if let Some(typo_usage) = &search_result.typo_usage {
+                TYPO_STATISTICS.record(&index_uid, typo_usage);
+            }
+            pii_protection.protect_hits(&mut search_result.hits);
+
+            search_results.push(Some(MultiSearchResult::Local(SearchResultWithIndex {
                 index_uid: index_uid.into_inner(),
-                result: search_result.with_index(query_index)?,
-            });
+                result: search_result,
+            })));
         }
-        Ok(search_results)
+
+        // Each upstream receives all its queries at once, and all the upstreams are queried concurrently.
+        let federation = &federation;
+        let remote_results =
+            future::join_all(remote_queries.into_iter().map(|(upstream, queries)| async move {
+                let (query_indexes, queries): (Vec<_>, Vec<_>) = queries.into_iter().unzip();
+                let results = federation
+                    .multi_search(upstream, queries)
+                    .await
+                    .with_index(query_indexes[0])?;
+                Ok::<_, (ResponseError, usize)>(query_indexes.into_iter().zip(results))
+            }))
+            .await;
+        for results in remote_results {
+            for (query_index, result) in results? {
+                search_results[query_index] = Some(MultiSearchResult::Remote(result));
+            }
+        }
+
+        Ok(search_results.into_iter().flatten().collect::<Vec<_>>())
     }
     .await;
 
@@ -0,0 +1,311 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::document_formats::DocumentFormatError;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use meilisearch_types::milli::documents::DocumentsBatchBuilder;
+use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::Object;
+use meilisearch_types::tasks::KindWithContent;
+use serde::Serialize;
+use serde_json::Value;
+use time::OffsetDateTime;
+use tracing::debug;
+
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::ActionPolicy;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::partitions::{parse_timestamp, PartitionFamilies, PartitionFamily};
+use crate::routes::indexes::search::search_kind;
+use crate::routes::{is_dry_run, SummarizedTaskView};
+use crate::search::{
+    add_search_rules, perform_search, HitsInfo, PiiProtection, RetrieveVectors, SearchHit,
+    SearchQuery, DEFAULT_SEARCH_LIMIT,
+};
+use crate::search_activity::SEARCH_ACTIVITY;
+use crate::search_queue::SearchQueue;
+use crate::typo_statistics::TYPO_STATISTICS;
+use crate::Opt;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/{family}/documents").route(web::post().to(SeqHandler(add_documents))),
+    )
+    .service(web::resource("/{family}/search").route(web::post().to(SeqHandler(search))));
+}
+
+fn partition_family<'a>(
+    partitions: &'a PartitionFamilies,
+    family: &str,
+) -> Result<&'a PartitionFamily, MeilisearchHttpError> {
+    partitions
+        .family(family)
+        .ok_or_else(|| MeilisearchHttpError::PartitionFamilyNotFound(family.to_string()))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PartitionedDocumentsView {
+    /// The document additions of the partitions, followed by the deletions of the expired ones.
+    tasks: Vec<SummarizedTaskView>,
+    /// The number of documents ignored because their partition is already expired.
+    ignored_documents: usize,
+}
+
+pub async fn add_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    partitions: Data<PartitionFamilies>,
+    family: web::Path<String>,
+    body: web::Json<Vec<Object>>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+) -> Result<HttpResponse, ResponseError> {
+    let family = partition_family(&partitions, &family)?;
+    let documents = body.into_inner();
+    debug!(family = %family.family, documents = documents.len(), "Add partitioned documents");
+    let dry_run = is_dry_run(&req, &opt)?;
+    let now = OffsetDateTime::now_utc();
+
+    let mut partitioned_documents = BTreeMap::<String, Vec<Object>>::new();
+    let mut ignored_documents = 0;
+    for (position, document) in documents.into_iter().enumerate() {
+        let Some(timestamp) = document.get(&family.timestamp_field).and_then(parse_timestamp)
+        else {
+            return Err(MeilisearchHttpError::InvalidPartitionTimestamp(
+                position,
+                family.timestamp_field.clone(),
+            )
+            .into());
+        };
+        if family.is_expired(timestamp, now) {
+            ignored_documents += 1;
+            continue;
+        }
+        partitioned_documents.entry(family.partition_uid(timestamp)).or_default().push(document);
+    }
+
+    // all the partitions are checked before any task is registered
+    for index_uid in partitioned_documents.keys() {
+        IndexUid::try_from(index_uid.clone())?;
+        if !index_scheduler.filters().is_index_authorized(index_uid) {
+            return Err(AuthenticationError::InvalidToken.into());
+        }
+    }
+
+    let mut tasks = Vec::new();
+    for (index_uid, documents) in partitioned_documents {
+        let (uuid, mut update_file) = index_scheduler.create_update_file(dry_run)?;
+        let documents_count = tokio::task::spawn_blocking(move || {
+            let mut builder = DocumentsBatchBuilder::new(&mut update_file);
+            for document in &documents {
+                builder.append_json_object(document).map_err(DocumentFormatError::Io)?;
+            }
+            let documents_count = builder.documents_count() as u64;
+            builder.into_inner().map_err(DocumentFormatError::Io)?;
+            update_file.persist()?;
+            Ok::<_, MeilisearchHttpError>(documents_count)
+        })
+        .await??;
+
+        let task = KindWithContent::DocumentAdditionOrUpdate {
+            method: IndexDocumentsMethod::ReplaceDocuments,
+            content_file: uuid,
+            documents_count,
+            primary_key: None,
+            allow_index_creation: index_scheduler.filters().allow_index_creation(&index_uid),
+            write_mode: Default::default(),
+            index_uid,
+        };
+        let scheduler = index_scheduler.clone();
+        let task =
+            match tokio::task::spawn_blocking(move || scheduler.register(task, None, dry_run))
+                .await?
+            {
+                Ok(task) => task,
+                Err(e) => {
+                    index_scheduler.delete_update_file(uuid)?;
+                    return Err(e.into());
+                }
+            };
+        tasks.push(SummarizedTaskView::from(task));
+    }
+
+    // the retention is a policy of the instance, it applies whatever the key used to write
+    let index_uids = index_scheduler.index_names()?;
+    for index_uid in family.expired_partitions(index_uids.iter().map(String::as_str), now) {
+        let task = KindWithContent::IndexDeletion { index_uid };
+        let scheduler = index_scheduler.clone();
+        let task =
+            tokio::task::spawn_blocking(move || scheduler.register(task, None, dry_run)).await??;
+        tasks.push(task.into());
+    }
+
+    let view = PartitionedDocumentsView { tasks, ignored_documents };
+    debug!(returns = ?view, "Add partitioned documents");
+    Ok(HttpResponse::Accepted().json(view))
+}
+
+#[derive(Debug, Serialize)]
+struct PartitionedHit {
+    #[serde(flatten)]
+    hit: SearchHit,
+    #[serde(rename = "_partition")]
+    partition: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PartitionedSearchResult {
+    hits: Vec<PartitionedHit>,
+    query: String,
+    processing_time_ms: u128,
+    limit: usize,
+    offset: usize,
+    estimated_total_hits: usize,
+    /// The partitions searched, from the most recent to the oldest.
+    partitions: Vec<String>,
+}
+
+/// Removes the given bound of the range of dates from the search parameters.
+fn take_range_bound(
+    params: &mut Value,
+    bound: &'static str,
+) -> Result<Option<OffsetDateTime>, MeilisearchHttpError> {
+    match params.as_object_mut().and_then(|params| params.remove(bound)) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => parse_timestamp(&value)
+            .map(Some)
+            .ok_or(MeilisearchHttpError::InvalidPartitionRange(bound, value)),
+    }
+}
+
+/// Searches the partitions of the family overlapping the `from` and `to` dates,
+/// and merges their hits by ranking score.
+///
+/// The facet distribution of the partitions isn't merged, and the pages are always
+/// returned with an offset and a limit.
+pub async fn search(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    search_queue: Data<SearchQueue>,
+    partitions: Data<PartitionFamilies>,
+    family: web::Path<String>,
+    params: web::Json<Value>,
+) -> Result<HttpResponse, ResponseError> {
+    let before_search = Instant::now();
+    let family = partition_family(&partitions, &family)?;
+    let mut params = params.into_inner();
+    let from = take_range_bound(&mut params, "from")?;
+    let to = take_range_bound(&mut params, "to")?;
+    let query = deserr::deserialize::<SearchQuery, _, DeserrJsonError>(params)?;
+    debug!(family = %family.family, ?from, ?to, parameters = ?query, "Partitioned search");
+
+    let (offset, limit) = match (query.page, query.hits_per_page) {
+        (None, None) => (query.offset, query.limit),
+        (page, hits_per_page) => {
+            let hits_per_page = hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
+            (page.unwrap_or(1).saturating_sub(1) * hits_per_page, hits_per_page)
+        }
+    };
+
+    let index_uids = index_scheduler.index_names()?;
+    let partition_uids =
+        family.partitions_in_range(index_uids.iter().map(String::as_str), from, to);
+
+    let filters = index_scheduler.filters();
+    let features = index_scheduler.features();
+    let _permit = search_queue.try_get_search_permit().await?;
+
+    let mut hits = Vec::new();
+    let mut estimated_total_hits = 0;
+    let mut searched_partitions = Vec::new();
+    for index_uid in partition_uids {
+        // the keys restricted to some of the partitions only search those
+        if !filters.is_index_authorized(&index_uid) {
+            continue;
+        }
+        let index = match index_scheduler.index(&index_uid) {
+            Ok(index) => index,
+            // the partition was deleted since the list of indexes was read
+            Err(index_scheduler::Error::IndexNotFound(_)) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        SEARCH_ACTIVITY.record(&index_uid);
+
+        // each partition returns enough hits to fill the requested page on its own
+        let mut query = query.clone();
+        query.offset = 0;
+        query.limit = offset.saturating_add(limit);
+        query.page = None;
+        query.hits_per_page = None;
+        query.show_ranking_score = true;
+        if let Some(search_rules) = filters.get_index_search_rules(&index_uid) {
+            add_search_rules(&mut query.filter, search_rules);
+        }
+        query.apply_key_search_parameters(filters.search_parameters())?;
+
+        let search_kind = search_kind(&query, index_scheduler.get_ref(), &index, features)?;
+        let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors, features)?;
+        let pii_protection = PiiProtection::new(&index, filters, index_scheduler.indexer_config())?;
+        let typo_statistics = features.runtime_features().typo_statistics;
+        let mut result = search_queue
+            .run(move || {
+                perform_search(
+                    &index,
+                    query,
+                    search_kind,
+                    retrieve_vectors,
+                    None,
+                    typo_statistics,
+                    false,
+                )
+            })
+            .await??;
+        if let Some(typo_usage) = &result.typo_usage {
+            TYPO_STATISTICS.record(&index_uid, typo_usage);
+        }
+        pii_protection.protect_hits(&mut result.hits);
+
+        if let HitsInfo::OffsetLimit { estimated_total_hits: partition_hits, .. } = result.hits_info
+        {
+            estimated_total_hits += partition_hits;
+        }
+        hits.extend(result.hits.into_iter().map(|hit| (index_uid.clone(), hit)));
+        searched_partitions.push(index_uid);
+    }
+
+    // the sort is stable, the most recent partitions come first on equal scores
+    hits.sort_by(|(_, a), (_, b)| {
+        let score = |hit: &SearchHit| hit.ranking_score.unwrap_or_default();
+        score(b).total_cmp(&score(a))
+    });
+    let hits = hits
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|(partition, mut hit)| {
+            if !query.show_ranking_score {
+                hit.ranking_score = None;
+            }
+            PartitionedHit { hit, partition }
+        })
+        .collect();
+
+    let result = PartitionedSearchResult {
+        hits,
+        query: query.q.unwrap_or_default(),
+        processing_time_ms: before_search.elapsed().as_millis(),
+        limit,
+        offset,
+        estimated_total_hits,
+        partitions: searched_partitions,
+    };
+    debug!(returns = ?result, "Partitioned search");
+    Ok(HttpResponse::Ok().json(result))
+}
@@ -1,20 +1,26 @@
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
 use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::tasks::KindWithContent;
 use serde_json::json;
 use tracing::debug;
 
 use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
-use crate::extractors::authentication::GuardedData;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
 use crate::Opt;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_snapshot))));
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_snapshot))))
+        .service(web::resource("/compare").route(web::post().to(SeqHandler(compare_snapshots))));
 }
 
 pub async fn create_snapshot(
@@ -36,3 +42,48 @@ pub async fn create_snapshot(
     debug!(returns = ?task, "Create snapshot");
     Ok(HttpResponse::Accepted().json(task))
 }
+
+#[derive(Deserr, Debug, Clone, PartialEq, Eq)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SnapshotComparisonPayload {
+    #[deserr(error = DeserrJsonError<InvalidSnapshotComparisonIndexUid>, missing_field_error = DeserrJsonError::missing_snapshot_comparison_index_uid)]
+    index_uid: IndexUid,
+    #[deserr(error = DeserrJsonError<InvalidSnapshotComparisonSnapshot>, missing_field_error = DeserrJsonError::missing_snapshot_comparison_snapshot)]
+    snapshot: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSnapshotComparisonOtherSnapshot>)]
+    other_snapshot: Option<String>,
+}
+
+pub async fn compare_snapshots(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SNAPSHOTS_CREATE }>, Data<IndexScheduler>>,
+    params: AwebJson<SnapshotComparisonPayload, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let SnapshotComparisonPayload { index_uid, snapshot, other_snapshot } = params.into_inner();
+    analytics.publish(
+        "Snapshots Compared".to_string(),
+        json!({ "with_current_index": other_snapshot.is_none() }),
+        Some(&req),
+    );
+
+    if !index_scheduler.filters().is_index_authorized(&index_uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    let task = KindWithContent::SnapshotComparison {
+        index_uid: index_uid.into_inner(),
+        snapshot,
+        other_snapshot,
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Compare snapshots");
+    Ok(HttpResponse::Accepted().json(task))
+}
@@ -34,7 +34,9 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::delete().to(SeqHandler(delete_tasks))),
     )
     .service(web::resource("/cancel").route(web::post().to(SeqHandler(cancel_tasks))))
-    .service(web::resource("/{task_id}").route(web::get().to(SeqHandler(get_task))));
+    .service(web::resource("/stats").route(web::get().to(SeqHandler(get_tasks_stats))))
+    .service(web::resource("/{task_id}").route(web::get().to(SeqHandler(get_task))))
+    .service(web::resource("/{task_id}/replay").route(web::post().to(SeqHandler(replay_task))));
 }
 #[derive(Debug, Deserr)]
 #[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
@@ -291,6 +293,14 @@ async fn get_tasks(
     Ok(HttpResponse::Ok().json(tasks))
 }
 
+async fn get_tasks_stats(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let filters = index_scheduler.filters();
+    let stats = index_scheduler.get_task_queue_stats(filters)?;
+    Ok(HttpResponse::Ok().json(stats))
+}
+
 async fn get_task(
     index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
     task_uid: web::Path<String>,
@@ -316,6 +326,42 @@ async fn get_task(
     }
 }
 
+async fn replay_task(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    task_uid: web::Path<String>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let task_uid_string = task_uid.into_inner();
+
+    let task_uid: TaskId = match task_uid_string.parse() {
+        Ok(id) => id,
+        Err(_e) => {
+            return Err(index_scheduler::Error::InvalidTaskUids { task_uid: task_uid_string }.into())
+        }
+    };
+
+    analytics.publish("Task Replayed".to_string(), json!({}), Some(&req));
+
+    let query = index_scheduler::Query { uids: Some(vec![task_uid]), ..Query::default() };
+    let filters = index_scheduler.filters();
+    let (tasks, _) = index_scheduler.get_tasks_from_authorized_indexes(query, filters)?;
+    let task = tasks.into_iter().next().ok_or(index_scheduler::Error::TaskNotFound(task_uid))?;
+    let allow_index_creation =
+        task.index_uid().map_or(false, |index_uid| filters.allow_index_creation(index_uid));
+
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task = task::spawn_blocking(move || {
+        index_scheduler.replay_task(&task, allow_index_creation, uid, dry_run)
+    })
+    .await??;
+    let task: SummarizedTaskView = task.into();
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 pub enum DeserializeDateOption {
     Before,
     After,
@@ -591,7 +637,7 @@ mod tests {
             let err = deserr_query_params::<TaskDeletionOrCancelationQuery>(params).unwrap_err();
             snapshot!(meili_snap::json_string!(err), @r###"
             {
-              "message": "Invalid value in parameter `types`: `createIndex` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentEdition`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`.",
+              "message": "Invalid value in parameter `types`: `createIndex` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentEdition`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexRebuild`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`, `snapshotComparison`.",
               "code": "invalid_task_types",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_task_types"
@@ -1,6 +1,7 @@
 use core::fmt;
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ops::Range;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -8,28 +9,38 @@ use std::time::{Duration, Instant};
 use deserr::Deserr;
 use either::Either;
 use indexmap::IndexMap;
-use meilisearch_auth::IndexSearchRules;
+use meilisearch_auth::{AuthFilter, IndexSearchRules};
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::KeySearchParameters;
+use meilisearch_types::milli::computed_fields::ComputedFields;
+use meilisearch_types::milli::documents::PrimaryKey;
+use meilisearch_types::milli::pii::{remove_pii_attributes, PiiCipher};
+use meilisearch_types::milli::prepared_filter::render_prepared_filter;
+use meilisearch_types::milli::query_rewrites::AppliedQueryRewrite;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
+use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
 use meilisearch_types::milli::vector::Embedder;
 use meilisearch_types::milli::{FacetValueHit, OrderBy, SearchForFacetValues, TimeBudget};
-use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
+use meilisearch_types::settings::{LanguagePresetView, DEFAULT_PAGINATION_MAX_TOTAL_HITS};
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::TokenizerBuilder;
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, InterpretedQueryTerm,
+    MatchBounds, MatcherBuilder, QueryInterpretation, QueryTermKind, QueryTermLocation,
+    SearchTimings, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::Serialize;
 use serde_json::{json, Value};
 
 use crate::error::MeilisearchHttpError;
+use crate::search_session::SearchSession;
+use crate::typo_statistics::TypoUsage;
 
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
 
@@ -74,12 +85,22 @@ pub struct SearchQuery {
     pub show_ranking_score: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScoreDetails>, default)]
     pub show_ranking_score_details: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowQueryTerms>, default)]
+    pub show_query_terms: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowQueryInterpretation>, default)]
+    pub show_query_interpretation: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowTimings>, default)]
+    pub show_timings: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchPreparedFilter>)]
+    pub prepared_filter: Option<PreparedFilterQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDiversify>)]
+    pub diversify: Option<DiversifyQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
@@ -88,12 +109,30 @@ pub struct SearchQuery {
     pub highlight_post_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchCropMarker>, default = DEFAULT_CROP_MARKER())]
     pub crop_marker: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchMaxHighlightFragments>)]
+    pub max_highlight_fragments: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFragmentSeparator>)]
+    pub fragment_separator: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchPhraseFallback>, default)]
+    pub phrase_fallback: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLanguages>, default)]
+    pub languages: Option<Vec<LanguagePresetView>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSynonyms>, default)]
+    pub synonyms: Option<BTreeMap<String, Vec<String>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGeoSortMaxDistance>, default)]
+    pub geo_sort_max_distance: Option<f64>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGeoSortBucketWidth>, default)]
+    pub geo_sort_bucket_width: Option<f64>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCreateSession>, default)]
+    pub create_session: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSessionToken>, default)]
+    pub session_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Deserr)]
@@ -153,16 +192,30 @@ impl fmt::Debug for SearchQuery {
             show_matches_position,
             show_ranking_score,
             show_ranking_score_details,
+            show_query_terms,
+            show_query_interpretation,
+            show_timings,
             filter,
+            prepared_filter,
             sort,
             distinct,
+            diversify,
             facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
+            max_highlight_fragments,
+            fragment_separator,
             matching_strategy,
+            phrase_fallback,
             attributes_to_search_on,
+            languages,
+            synonyms,
+            geo_sort_max_distance,
+            geo_sort_bucket_width,
             ranking_score_threshold,
+            create_session,
+            session_token,
         } = self;
 
         let mut debug = f.debug_struct("SearchQuery");
@@ -175,6 +228,12 @@ impl fmt::Debug for SearchQuery {
         if let Some(hits_per_page) = hits_per_page {
             debug.field("hits_per_page", &hits_per_page);
         }
+        if *create_session {
+            debug.field("create_session", &create_session);
+        }
+        if let Some(session_token) = session_token {
+            debug.field("session_token", &session_token);
+        }
 
         // Then, everything related to the queries
         if let Some(q) = q {
@@ -199,19 +258,40 @@ impl fmt::Debug for SearchQuery {
         if let Some(attributes_to_search_on) = attributes_to_search_on {
             debug.field("attributes_to_search_on", &attributes_to_search_on);
         }
+        if let Some(languages) = languages {
+            debug.field("languages", &languages);
+        }
+        if let Some(synonyms) = synonyms {
+            debug.field("synonyms", &synonyms);
+        }
+        if let Some(geo_sort_max_distance) = geo_sort_max_distance {
+            debug.field("geo_sort_max_distance", &geo_sort_max_distance);
+        }
+        if let Some(geo_sort_bucket_width) = geo_sort_bucket_width {
+            debug.field("geo_sort_bucket_width", &geo_sort_bucket_width);
+        }
         if let Some(filter) = filter {
             debug.field("filter", &filter);
         }
+        if let Some(prepared_filter) = prepared_filter {
+            debug.field("prepared_filter", &prepared_filter);
+        }
         if let Some(sort) = sort {
             debug.field("sort", &sort);
         }
         if let Some(distinct) = distinct {
             debug.field("distinct", &distinct);
         }
+        if let Some(diversify) = diversify {
+            debug.field("diversify", &diversify);
+        }
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
         debug.field("matching_strategy", &matching_strategy);
+        if *phrase_fallback {
+            debug.field("phrase_fallback", phrase_fallback);
+        }
 
         // Then everything related to the formatting
         debug.field("crop_length", &crop_length);
@@ -224,6 +304,15 @@ impl fmt::Debug for SearchQuery {
         if *show_ranking_score_details {
             debug.field("self.show_ranking_score_details", show_ranking_score_details);
         }
+        if *show_query_terms {
+            debug.field("show_query_terms", show_query_terms);
+        }
+        if *show_query_interpretation {
+            debug.field("show_query_interpretation", show_query_interpretation);
+        }
+        if *show_timings {
+            debug.field("show_timings", show_timings);
+        }
         debug.field("crop_length", &crop_length);
         if let Some(facets) = facets {
             debug.field("facets", &facets);
@@ -240,6 +329,12 @@ impl fmt::Debug for SearchQuery {
         debug.field("highlight_pre_tag", &highlight_pre_tag);
         debug.field("highlight_post_tag", &highlight_post_tag);
         debug.field("crop_marker", &crop_marker);
+        if let Some(max_highlight_fragments) = max_highlight_fragments {
+            debug.field("max_highlight_fragments", &max_highlight_fragments);
+        }
+        if let Some(fragment_separator) = fragment_separator {
+            debug.field("fragment_separator", &fragment_separator);
+        }
         if let Some(ranking_score_threshold) = ranking_score_threshold {
             debug.field("ranking_score_threshold", &ranking_score_threshold);
         }
@@ -248,6 +343,42 @@ impl fmt::Debug for SearchQuery {
     }
 }
 
+/// A reference to one of the prepared filters of the index, along with the values of its parameters.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchPreparedFilter>, rename_all = camelCase, deny_unknown_fields)]
+pub struct PreparedFilterQuery {
+    #[deserr(error = DeserrJsonError<InvalidSearchPreparedFilter>)]
+    pub name: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchPreparedFilter>)]
+    pub params: BTreeMap<String, Value>,
+}
+
+/// Limits the number of hits sharing the same value of a filterable attribute.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchDiversify>, rename_all = camelCase, deny_unknown_fields)]
+pub struct DiversifyQuery {
+    #[deserr(error = DeserrJsonError<InvalidSearchDiversify>)]
+    pub attribute: String,
+    #[deserr(error = DeserrJsonError<InvalidSearchDiversify>)]
+    pub max_per_value: MaxPerValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(try_from(usize) = TryFrom::try_from -> InvalidSearchDiversify)]
+pub struct MaxPerValue(usize);
+
+impl std::convert::TryFrom<usize> for MaxPerValue {
+    type Error = InvalidSearchDiversify;
+
+    fn try_from(n: usize) -> Result<Self, Self::Error> {
+        if n == 0 {
+            Err(InvalidSearchDiversify)
+        } else {
+            Ok(MaxPerValue(n))
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Deserr)]
 #[deserr(error = DeserrJsonError<InvalidHybridQuery>, rename_all = camelCase, deny_unknown_fields)]
 pub struct HybridQuery {
@@ -356,6 +487,150 @@ impl SearchQuery {
     pub fn is_finite_pagination(&self) -> bool {
         self.page.or(self.hits_per_page).is_some()
     }
+
+    /// Applies the search parameters of the API key used to make the request.
+    ///
+    /// The default parameters replace the ones the request left to their default value, and the
+    /// forced parameters replace the ones of the request whatever they are. The forced filter is
+    /// the exception: the documents must match both the filter of the request and the forced one.
+    pub fn apply_key_search_parameters(
+        &mut self,
+        parameters: &KeySearchParameters,
+    ) -> Result<(), ResponseError> {
+        if parameters.is_empty() {
+            return Ok(());
+        }
+
+        let unset = search_query_from_parameters(&BTreeMap::new(), "default")?;
+        let default = search_query_from_parameters(&parameters.default, "default")?;
+        for name in parameters.default.keys() {
+            if same_search_parameter(self, &unset, name) {
+                copy_search_parameter(self, &default, name);
+            }
+        }
+
+        let forced = search_query_from_parameters(&parameters.forced, "forced")?;
+        for name in parameters.forced.keys() {
+            match name.as_str() {
+                "filter" => self.filter = merge_filters(self.filter.take(), forced.filter.clone()),
+                name => copy_search_parameter(self, &forced, name),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the search parameters of the API key, as [`Self::apply_key_search_parameters`]
+    /// applied them to this query, into the JSON the query was deserialized from, so that the
+    /// query is forwarded with the same parameters.
+    pub fn write_key_search_parameters(
+        &self,
+        raw_query: &mut Value,
+        parameters: &KeySearchParameters,
+    ) -> Result<(), ResponseError> {
+        let default = search_query_from_parameters(&parameters.default, "default")?;
+        for (name, value) in &parameters.default {
+            if same_search_parameter(self, &default, name) {
+                raw_query[name.as_str()] = value.clone();
+            }
+        }
+        for (name, value) in &parameters.forced {
+            raw_query[name.as_str()] = value.clone();
+        }
+        // the filter also holds the forced filter merged with the one of the request
+        // and the rules of the tenant token
+        if let Some(filter) = &self.filter {
+            raw_query["filter"] = filter.clone();
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks that the search parameters of an API key are valid search parameters.
+pub fn validate_key_search_parameters(
+    parameters: &KeySearchParameters,
+) -> Result<(), ResponseError> {
+    search_query_from_parameters(&parameters.default, "default")?;
+    search_query_from_parameters(&parameters.forced, "forced")?;
+    Ok(())
+}
+
+fn search_query_from_parameters(
+    parameters: &BTreeMap<String, Value>,
+    kind: &str,
+) -> Result<SearchQuery, ResponseError> {
+    let parameters = Value::Object(parameters.clone().into_iter().collect());
+    deserr::deserialize::<SearchQuery, _, DeserrJsonError>(parameters).map_err(|error| {
+        ResponseError::from_msg(
+            format!("Invalid value at `.searchParameters.{kind}`: {error}"),
+            Code::InvalidApiKeySearchParameters,
+        )
+    })
+}
+
+macro_rules! search_parameters {
+    ($($name:literal => $field:ident),* $(,)?) => {
+        /// Copies a search parameter, designated by its name in the API, from a query to another.
+        #[allow(clippy::clone_on_copy)]
+        fn copy_search_parameter(target: &mut SearchQuery, source: &SearchQuery, name: &str) {
+            match name {
+                $($name => target.$field = source.$field.clone(),)*
+                _ => (),
+            }
+        }
+
+        /// Returns true if a search parameter, designated by its name in the API, is the same in
+        /// both queries.
+        fn same_search_parameter(left: &SearchQuery, right: &SearchQuery, name: &str) -> bool {
+            match name {
+                $($name => left.$field == right.$field,)*
+                _ => true,
+            }
+        }
+    };
+}
+
+search_parameters! {
+    "q" => q,
+    "vector" => vector,
+    "hybrid" => hybrid,
+    "offset" => offset,
+    "limit" => limit,
+    "page" => page,
+    "hitsPerPage" => hits_per_page,
+    "attributesToRetrieve" => attributes_to_retrieve,
+    "retrieveVectors" => retrieve_vectors,
+    "attributesToCrop" => attributes_to_crop,
+    "cropLength" => crop_length,
+    "attributesToHighlight" => attributes_to_highlight,
+    "showMatchesPosition" => show_matches_position,
+    "showRankingScore" => show_ranking_score,
+    "showRankingScoreDetails" => show_ranking_score_details,
+    "showQueryTerms" => show_query_terms,
+    "showQueryInterpretation" => show_query_interpretation,
+    "showTimings" => show_timings,
+    "filter" => filter,
+    "preparedFilter" => prepared_filter,
+    "sort" => sort,
+    "distinct" => distinct,
+    "diversify" => diversify,
+    "facets" => facets,
+    "highlightPreTag" => highlight_pre_tag,
+    "highlightPostTag" => highlight_post_tag,
+    "cropMarker" => crop_marker,
+    "maxHighlightFragments" => max_highlight_fragments,
+    "fragmentSeparator" => fragment_separator,
+    "matchingStrategy" => matching_strategy,
+    "phraseFallback" => phrase_fallback,
+    "attributesToSearchOn" => attributes_to_search_on,
+    "languages" => languages,
+    "synonyms" => synonyms,
+    "geoSortMaxDistance" => geo_sort_max_distance,
+    "geoSortBucketWidth" => geo_sort_bucket_width,
+    "rankingScoreThreshold" => ranking_score_threshold,
+    "createSession" => create_session,
+    "sessionToken" => session_token,
 }
 
 /// A `SearchQuery` + an index UID.
@@ -395,14 +670,24 @@ pub struct SearchQueryWithIndex {
     pub show_ranking_score: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScoreDetails>, default)]
     pub show_ranking_score_details: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowQueryTerms>, default)]
+    pub show_query_terms: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowQueryInterpretation>, default)]
+    pub show_query_interpretation: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowTimings>, default)]
+    pub show_timings: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>, default)]
     pub show_matches_position: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchPreparedFilter>)]
+    pub prepared_filter: Option<PreparedFilterQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDiversify>)]
+    pub diversify: Option<DiversifyQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
@@ -411,10 +696,24 @@ pub struct SearchQueryWithIndex {
     pub highlight_post_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchCropMarker>, default = DEFAULT_CROP_MARKER())]
     pub crop_marker: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchMaxHighlightFragments>)]
+    pub max_highlight_fragments: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFragmentSeparator>)]
+    pub fragment_separator: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchPhraseFallback>, default)]
+    pub phrase_fallback: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>, default)]
     pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLanguages>, default)]
+    pub languages: Option<Vec<LanguagePresetView>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSynonyms>, default)]
+    pub synonyms: Option<BTreeMap<String, Vec<String>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGeoSortMaxDistance>, default)]
+    pub geo_sort_max_distance: Option<f64>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGeoSortBucketWidth>, default)]
+    pub geo_sort_bucket_width: Option<f64>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
 }
@@ -436,16 +735,28 @@ impl SearchQueryWithIndex {
             attributes_to_highlight,
             show_ranking_score,
             show_ranking_score_details,
+            show_query_terms,
+            show_query_interpretation,
+            show_timings,
             show_matches_position,
             filter,
+            prepared_filter,
             sort,
             distinct,
+            diversify,
             facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
+            max_highlight_fragments,
+            fragment_separator,
             matching_strategy,
+            phrase_fallback,
             attributes_to_search_on,
+            languages,
+            synonyms,
+            geo_sort_max_distance,
+            geo_sort_bucket_width,
             hybrid,
             ranking_score_threshold,
         } = self;
@@ -465,18 +776,33 @@ impl SearchQueryWithIndex {
                 attributes_to_highlight,
                 show_ranking_score,
                 show_ranking_score_details,
+                show_query_terms,
+                show_query_interpretation,
+                show_timings,
                 show_matches_position,
                 filter,
+                prepared_filter,
                 sort,
                 distinct,
+                diversify,
                 facets,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
+                max_highlight_fragments,
+                fragment_separator,
                 matching_strategy,
+                phrase_fallback,
                 attributes_to_search_on,
+                languages,
+                synonyms,
+                geo_sort_max_distance,
+                geo_sort_bucket_width,
                 hybrid,
                 ranking_score_threshold,
+                // the search sessions are only available on the search route
+                create_session: false,
+                session_token: None,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
@@ -541,8 +867,9 @@ impl TryFrom<Value> for ExternalDocumentId {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserr)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserr, Serialize)]
 #[deserr(rename_all = camelCase)]
+#[serde(rename_all = "camelCase")]
 pub enum MatchingStrategy {
     /// Remove query words from last to first
     Last,
@@ -550,6 +877,9 @@ pub enum MatchingStrategy {
     All,
     /// Remove query words from the most frequent to the least
     Frequency,
+    /// Interpret the quoted parts of the query as regular expressions matched against the
+    /// words of the index, and remove query words from last to first
+    Regex,
 }
 
 impl Default for MatchingStrategy {
@@ -564,6 +894,7 @@ impl From<MatchingStrategy> for TermsMatchingStrategy {
             MatchingStrategy::Last => Self::Last,
             MatchingStrategy::All => Self::All,
             MatchingStrategy::Frequency => Self::Frequency,
+            MatchingStrategy::Regex => Self::Last,
         }
     }
 }
@@ -602,6 +933,188 @@ pub struct SearchHit {
     pub ranking_score_details: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
+/// The location of a term of the query, in bytes, as returned when `showQueryTerms` is set.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQueryTerm {
+    pub start: usize,
+    pub end: usize,
+    pub kind: SearchQueryTermKind,
+    /// Whether at least one of the returned hits contains the term.
+    pub matched: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchQueryTermKind {
+    Word,
+    Prefix,
+    Phrase,
+    NegativeWord,
+    NegativePhrase,
+    Wildcard,
+    Regex,
+}
+
+impl From<QueryTermKind> for SearchQueryTermKind {
+    fn from(kind: QueryTermKind) -> Self {
+        match kind {
+            QueryTermKind::Word => SearchQueryTermKind::Word,
+            QueryTermKind::Prefix => SearchQueryTermKind::Prefix,
+            QueryTermKind::Phrase => SearchQueryTermKind::Phrase,
+            QueryTermKind::NegativeWord => SearchQueryTermKind::NegativeWord,
+            QueryTermKind::NegativePhrase => SearchQueryTermKind::NegativePhrase,
+            QueryTermKind::Wildcard => SearchQueryTermKind::Wildcard,
+            QueryTermKind::Regex => SearchQueryTermKind::Regex,
+        }
+    }
+}
+
+impl From<QueryTermLocation> for SearchQueryTerm {
+    fn from(location: QueryTermLocation) -> Self {
+        let QueryTermLocation { byte_range, kind, matched } = location;
+        SearchQueryTerm { start: byte_range.start, end: byte_range.end, kind: kind.into(), matched }
+    }
+}
+
+/// How the engine interpreted the query, as returned when `showQueryInterpretation` is set.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQueryInterpretation {
+    pub terms: Vec<SearchInterpretedTerm>,
+    pub removed_stop_words: Vec<SearchRemovedStopWord>,
+    pub matching_strategy: MatchingStrategy,
+    /// The query rewritten by the `queryRewrites` setting, the offsets of the terms
+    /// and stop words are then given in this query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rewritten_query: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rewrites: Vec<SearchQueryRewrite>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchInterpretedTerm {
+    pub original: String,
+    pub start: usize,
+    pub end: usize,
+    pub kind: SearchQueryTermKind,
+    /// The maximum number of typos allowed on the term.
+    pub max_typos: u8,
+    /// The synonyms of the term that were searched for along with it.
+    pub synonyms: Vec<String>,
+    /// Whether the matching strategy made the term optional for some of the returned hits.
+    pub dropped: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQueryRewrite {
+    pub original: String,
+    pub rewritten: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchRemovedStopWord {
+    pub original: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl SearchQueryInterpretation {
+    fn new(
+        query: &str,
+        interpretation: QueryInterpretation,
+        matching_strategy: MatchingStrategy,
+    ) -> Self {
+        let QueryInterpretation { terms, removed_stop_words, rewritten_query, rewrites } =
+            interpretation;
+        let query = rewritten_query.as_deref().unwrap_or(query);
+        let original =
+            |range: &Range<usize>| query.get(range.clone()).unwrap_or_default().to_string();
+
+        let terms = terms
+            .into_iter()
+            .map(|term| {
+                let InterpretedQueryTerm { byte_range, kind, max_typos, synonyms, dropped } = term;
+                SearchInterpretedTerm {
+                    original: original(&byte_range),
+                    start: byte_range.start,
+                    end: byte_range.end,
+                    kind: kind.into(),
+                    max_typos,
+                    synonyms,
+                    dropped,
+                }
+            })
+            .collect();
+        let removed_stop_words = removed_stop_words
+            .into_iter()
+            .map(|range| SearchRemovedStopWord {
+                original: original(&range),
+                start: range.start,
+                end: range.end,
+            })
+            .collect();
+
+        let rewrites = rewrites
+            .into_iter()
+            .map(|AppliedQueryRewrite { original, rewritten }| SearchQueryRewrite {
+                original,
+                rewritten,
+            })
+            .collect();
+
+        SearchQueryInterpretation {
+            terms,
+            removed_stop_words,
+            matching_strategy,
+            rewritten_query,
+            rewrites,
+        }
+    }
+}
+
+/// The time spent in each stage of the search, as returned when `showTimings` is set.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchTimingsView {
+    pub filter_ms: f64,
+    pub query_parsing_ms: f64,
+    pub graph_building_ms: f64,
+    /// The time spent in each ranking rule, in the order they are applied.
+    pub ranking_rules: Vec<RankingRuleTimingView>,
+    pub facets_ms: f64,
+    pub formatting_ms: f64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RankingRuleTimingView {
+    pub rule: String,
+    pub ms: f64,
+}
+
+impl SearchTimingsView {
+    fn new(timings: SearchTimings, facets: Duration, formatting: Duration) -> Self {
+        // the stages often take less than a millisecond, they are given to the microsecond
+        let ms = |duration: Duration| duration.as_micros() as f64 / 1000.0;
+        let SearchTimings { filter, query_parsing, graph_building, ranking_rules } = timings;
+        SearchTimingsView {
+            filter_ms: ms(filter),
+            query_parsing_ms: ms(query_parsing),
+            graph_building_ms: ms(graph_building),
+            ranking_rules: ranking_rules
+                .into_iter()
+                .map(|(rule, duration)| RankingRuleTimingView { rule, ms: ms(duration) })
+                .collect(),
+            facets_ms: ms(facets),
+            formatting_ms: ms(formatting),
+        }
+    }
+}
+
 #[derive(Serialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchResult {
@@ -618,11 +1131,37 @@ pub struct SearchResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_terms: Option<Vec<SearchQueryTerm>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_interpretation: Option<SearchQueryInterpretation>,
+
+    /// Time spent highlighting, cropping and computing the matches position of the hits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formatting_time_ms: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<SearchTimingsView>,
+
+    /// Whether some parts of the query were ignored because of the query limits of the index.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub query_truncated: bool,
+
+    /// Whether the phrases of the query were relaxed because they didn't match any document.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub relaxed_phrases: bool,
+
+    /// The token of the search session the results were taken from, to get the next pages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_token: Option<String>,
+
     // These fields are only used for analytics purposes
     #[serde(skip)]
     pub degraded: bool,
     #[serde(skip)]
     pub used_negative_operator: bool,
+    /// The typos of the hits, counted when the `typoStatistics` feature is enabled.
+    #[serde(skip)]
+    pub typo_usage: Option<TypoUsage>,
 }
 
 impl fmt::Debug for SearchResult {
@@ -635,8 +1174,16 @@ impl fmt::Debug for SearchResult {
             facet_distribution,
             facet_stats,
             semantic_hit_count,
+            formatting_time_ms,
+            timings,
+            query_terms,
+            query_interpretation,
+            query_truncated,
+            relaxed_phrases,
+            session_token,
             degraded,
             used_negative_operator,
+            typo_usage,
         } = self;
 
         let mut debug = f.debug_struct("SearchResult");
@@ -651,6 +1198,15 @@ impl fmt::Debug for SearchResult {
         if *degraded {
             debug.field("degraded", degraded);
         }
+        if *query_truncated {
+            debug.field("query_truncated", query_truncated);
+        }
+        if *relaxed_phrases {
+            debug.field("relaxed_phrases", relaxed_phrases);
+        }
+        if let Some(session_token) = session_token {
+            debug.field("session_token", &session_token);
+        }
         if let Some(facet_distribution) = facet_distribution {
             debug.field("facet_distribution", &facet_distribution);
         }
@@ -660,6 +1216,21 @@ impl fmt::Debug for SearchResult {
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
+        if let Some(formatting_time_ms) = formatting_time_ms {
+            debug.field("formatting_time_ms", &formatting_time_ms);
+        }
+        if let Some(timings) = timings {
+            debug.field("timings", &timings);
+        }
+        if let Some(query_terms) = query_terms {
+            debug.field("query_terms", &query_terms);
+        }
+        if let Some(query_interpretation) = query_interpretation {
+            debug.field("query_interpretation", &query_interpretation);
+        }
+        if let Some(typo_usage) = typo_usage {
+            debug.field("typo_usage", &typo_usage);
+        }
 
         debug.finish()
     }
@@ -708,20 +1279,103 @@ pub struct FacetSearchResult {
 
 /// Incorporate search rules in search query
 pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
-    *filter = match (filter.take(), rules.filter) {
-        (None, rules_filter) => rules_filter,
+    *filter = merge_filters(filter.take(), rules.filter);
+}
+
+/// Combines two filters so that the documents must match both of them.
+fn merge_filters(filter: Option<Value>, other: Option<Value>) -> Option<Value> {
+    match (filter, other) {
+        (None, other) => other,
         (filter, None) => filter,
-        (Some(filter), Some(rules_filter)) => {
+        (Some(filter), Some(other)) => {
             let filter = match filter {
                 Value::Array(filter) => filter,
                 filter => vec![filter],
             };
-            let rules_filter = match rules_filter {
-                Value::Array(rules_filter) => rules_filter,
-                rules_filter => vec![rules_filter],
+            let other = match other {
+                Value::Array(other) => other,
+                other => vec![other],
             };
 
-            Some(Value::Array([filter, rules_filter].concat()))
+            Some(Value::Array([filter, other].concat()))
+        }
+    }
+}
+
+/// Replaces the prepared filter of the query by its rendering, merged into the filter of the query.
+fn resolve_prepared_filter(
+    index: &Index,
+    rtxn: &RoTxn,
+    query: &mut SearchQuery,
+) -> Result<(), MeilisearchHttpError> {
+    let Some(PreparedFilterQuery { name, params }) = query.prepared_filter.take() else {
+        return Ok(());
+    };
+
+    let prepared_filters = index.prepared_filters(rtxn)?;
+    let template = prepared_filters.get(&name).ok_or_else(|| {
+        milli::Error::from(milli::UserError::UnknownPreparedFilter { name: name.clone() })
+    })?;
+    let filter = render_prepared_filter(&name, template, &params).map_err(milli::Error::from)?;
+    query.filter = merge_filters(query.filter.take(), Some(Value::String(filter)));
+
+    Ok(())
+}
+
+/// Hides the PII attributes of the documents to the keys without the `documents.pii` action,
+/// and decrypts them for the other keys.
+pub struct PiiProtection {
+    pii_attributes: BTreeSet<String>,
+    reveal: bool,
+    cipher: Option<PiiCipher>,
+}
+
+impl PiiProtection {
+    pub fn new(
+        index: &Index,
+        filters: &AuthFilter,
+        indexer_config: &IndexerConfig,
+    ) -> Result<Self, MeilisearchHttpError> {
+        let rtxn = index.read_txn()?;
+        let pii_attributes = index.pii_attributes(&rtxn)?;
+        Ok(Self {
+            pii_attributes,
+            reveal: filters.allow_pii(),
+            cipher: indexer_config.pii_cipher.clone(),
+        })
+    }
+
+    pub fn protect_document(&self, document: &mut Document) {
+        if self.pii_attributes.is_empty() {
+            return;
+        }
+        if !self.reveal {
+            remove_pii_attributes(document, &self.pii_attributes);
+        } else if let Some(cipher) = &self.cipher {
+            cipher.decrypt_document(document, &self.pii_attributes);
+        }
+    }
+
+    pub fn protect_hits(&self, hits: &mut [SearchHit]) {
+        if self.pii_attributes.is_empty() {
+            return;
+        }
+        for hit in hits {
+            self.protect_document(&mut hit.document);
+
+            // the formatted values and the matches were computed on the encrypted values
+            let SearchHit { document, formatted, matches_position, .. } = hit;
+            let pii_attributes = &self.pii_attributes;
+            formatted
+                .retain(|field, _| !pii_attributes.contains(field) || document.contains_key(field));
+            for (field, value) in formatted.iter_mut() {
+                if pii_attributes.contains(field) {
+                    value.clone_from(&document[field]);
+                }
+            }
+            if let Some(matches_position) = matches_position {
+                matches_position.retain(|field, _| !pii_attributes.contains(field));
+            }
         }
     }
 }
@@ -732,7 +1386,8 @@ fn prepare_search<'t>(
     query: &'t SearchQuery,
     search_kind: &SearchKind,
     time_budget: TimeBudget,
-) -> Result<(milli::Search<'t>, bool, usize, usize), MeilisearchHttpError> {
+    exhaustive: bool,
+) -> Result<(milli::Search<'t>, bool, usize, usize, usize), MeilisearchHttpError> {
     let mut search = index.search(rtxn);
     search.time_budget(time_budget);
     if let Some(ranking_score_threshold) = query.ranking_score_threshold {
@@ -743,6 +1398,10 @@ fn prepare_search<'t>(
         search.distinct(distinct.clone());
     }
 
+    if let Some(DiversifyQuery { attribute, max_per_value }) = &query.diversify {
+        search.diversify(attribute.clone(), max_per_value.0);
+    }
+
     match search_kind {
         SearchKind::KeywordOnly => {
             if let Some(q) = &query.q {
@@ -778,16 +1437,37 @@ fn prepare_search<'t>(
         search.searchable_attributes(searchable);
     }
 
+    if let Some(ref languages) = query.languages {
+        search.languages(languages.iter().map(|language| (*language).into()).collect());
+    }
+
+    if let Some(ref synonyms) = query.synonyms {
+        search.synonyms(synonyms);
+    }
+
+    search.geo_max_distance(query.geo_sort_max_distance);
+    search.geo_bucket_width(query.geo_sort_bucket_width);
+
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
+    search.phrase_fallback(query.phrase_fallback);
+    search.regex_terms(query.matching_strategy == MatchingStrategy::Regex);
 
-    let max_total_hits = index
-        .pagination_max_total_hits(rtxn)
-        .map_err(milli::Error::from)?
-        .map(|x| x as usize)
-        .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
+    let max_total_hits = if exhaustive {
+        usize::MAX
+    } else {
+        index
+            .pagination_max_total_hits(rtxn)
+            .map_err(milli::Error::from)?
+            .map(|x| x as usize)
+            .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS)
+    };
 
-    search.exhaustive_number_hits(is_finite_pagination);
+    search.exhaustive_number_hits(is_finite_pagination || exhaustive);
+    search.query_term_locations(query.show_query_terms);
+    search.query_interpretation(query.show_query_interpretation);
+    search.timings(query.show_timings);
+    search.term_typos(query.show_ranking_score_details);
     search.scoring_strategy(
         if query.show_ranking_score
             || query.show_ranking_score_details
@@ -835,24 +1515,57 @@ fn prepare_search<'t>(
         search.sort_criteria(sort);
     }
 
-    Ok((search, is_finite_pagination, max_total_hits, offset))
+    Ok((search, is_finite_pagination, max_total_hits, offset, limit))
 }
 
 pub fn perform_search(
     index: &Index,
-    query: SearchQuery,
+    mut query: SearchQuery,
     search_kind: SearchKind,
     retrieve_vectors: RetrieveVectors,
+    session: Option<Arc<SearchSession>>,
+    typo_statistics: bool,
+    exhaustive: bool,
 ) -> Result<SearchResult, MeilisearchHttpError> {
     let before_search = Instant::now();
-    let rtxn = index.read_txn()?;
-    let time_budget = match index.search_cutoff(&rtxn)? {
+    // the searches of a session read the snapshot of the index it was created with
+    let index = session.as_ref().map_or(index, |session| session.index());
+    let session_rtxn = session.as_ref().map(|session| session.rtxn());
+    let fresh_rtxn;
+    let rtxn: &RoTxn = match &session_rtxn {
+        Some(rtxn) => &**rtxn,
+        None => {
+            fresh_rtxn = index.read_txn()?;
+            &fresh_rtxn
+        }
+    };
+    // the search jobs count all the hits, whatever the time it takes
+    let time_budget = match index.search_cutoff(rtxn)? {
+        _ if exhaustive => TimeBudget::max(),
         Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
         None => TimeBudget::default(),
     };
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
-        prepare_search(index, &rtxn, &query, &search_kind, time_budget)?;
+    resolve_prepared_filter(index, rtxn, &mut query)?;
+    let (mut search, is_finite_pagination, max_total_hits, offset, page_limit) =
+        prepare_search(index, rtxn, &query, &search_kind, time_budget, exhaustive)?;
+    if typo_statistics {
+        // the typos of the hits are read from their detailed scores
+        search.scoring_strategy(ScoringStrategy::Detailed);
+        search.term_typos(true);
+    }
+
+    let execute = |search: &milli::Search| -> Result<_, MeilisearchHttpError> {
+        Ok(match &search_kind {
+            SearchKind::KeywordOnly => (search.execute()?, None),
+            SearchKind::SemanticOnly { .. } => {
+                let results = search.execute()?;
+                let semantic_hit_count = results.document_scores.len() as u32;
+                (results, Some(semantic_hit_count))
+            }
+            SearchKind::Hybrid { semantic_ratio, .. } => search.execute_hybrid(*semantic_ratio)?,
+        })
+    };
 
     let (
         milli::SearchResult {
@@ -862,16 +1575,35 @@ pub fn perform_search(
             document_scores,
             degraded,
             used_negative_operator,
+            query_truncated,
+            query_term_locations,
+            query_interpretation,
+            relaxed_phrases,
+            timings,
         },
         semantic_hit_count,
-    ) = match &search_kind {
-        SearchKind::KeywordOnly => (search.execute()?, None),
-        SearchKind::SemanticOnly { .. } => {
-            let results = search.execute()?;
-            let semantic_hit_count = results.document_scores.len() as u32;
-            (results, Some(semantic_hit_count))
-        }
-        SearchKind::Hybrid { semantic_ratio, .. } => search.execute_hybrid(*semantic_ratio)?,
+    ) = match &session {
+        None => execute(&search)?,
+        Some(session) => {
+            // the pages of a session are cut from the ranking of all the documents before them,
+            // which stays the same from one page to the next
+            let end = offset + page_limit;
+            let (mut result, semantic_hit_count) = match session.cached_ranking(&query, end) {
+                Some(ranking) => ranking,
+                None => {
+                    search.offset(0);
+                    search.limit(end);
+                    let (result, semantic_hit_count) = execute(&search)?;
+                    session.cache_ranking(&query, end, &result, semantic_hit_count);
+                    (result, semantic_hit_count)
+                }
+            };
+            result.documents_ids =
+                result.documents_ids.into_iter().skip(offset).take(page_limit).collect();
+            result.document_scores =
+                result.document_scores.into_iter().skip(offset).take(page_limit).collect();
+            (result, semantic_hit_count)
+        }
     };
 
     let SearchQuery {
@@ -888,20 +1620,33 @@ pub fn perform_search(
         show_matches_position,
         show_ranking_score,
         show_ranking_score_details,
+        show_query_terms,
+        // already used in prepare_search
+        show_query_interpretation: _,
+        show_timings: _,
         sort,
         facets,
         highlight_pre_tag,
         highlight_post_tag,
         crop_marker,
+        max_highlight_fragments,
+        fragment_separator,
         // already used in prepare_search
         vector: _,
         hybrid: _,
         offset: _,
         ranking_score_threshold: _,
-        matching_strategy: _,
+        matching_strategy,
+        phrase_fallback: _,
         attributes_to_search_on: _,
+        languages: _,
+        synonyms: _,
+        geo_sort_max_distance: _,
+        geo_sort_bucket_width: _,
         filter: _,
+        prepared_filter: _,
         distinct: _,
+        diversify: _,
     } = query;
 
     let format = AttributesFormat {
@@ -911,6 +1656,8 @@ pub fn perform_search(
         attributes_to_crop,
         crop_length,
         crop_marker,
+        max_highlight_fragments,
+        fragment_separator,
         highlight_pre_tag,
         highlight_post_tag,
         show_matches_position,
@@ -919,8 +1666,9 @@ pub fn perform_search(
         show_ranking_score_details,
     };
 
-    let documents =
-        make_hits(index, &rtxn, format, matching_words, documents_ids, document_scores)?;
+    let typo_usage = typo_statistics.then(|| TypoUsage::from_scores(&document_scores));
+    let (documents, formatting_time) =
+        make_hits(index, rtxn, format, matching_words, documents_ids, document_scores)?;
 
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
     let hits_info = if is_finite_pagination {
@@ -940,19 +1688,24 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits }
     };
 
+    let before_facets = Instant::now();
     let (facet_distribution, facet_stats) = match facets {
         Some(ref fields) => {
-            let mut facet_distribution = index.facets_distribution(&rtxn);
+            let mut facet_distribution = index.facets_distribution(rtxn);
 
-            let max_values_by_facet = index
-                .max_values_per_facet(&rtxn)
-                .map_err(milli::Error::from)?
-                .map(|x| x as usize)
-                .unwrap_or(DEFAULT_VALUES_PER_FACET);
+            let max_values_by_facet = if exhaustive {
+                usize::MAX
+            } else {
+                index
+                    .max_values_per_facet(rtxn)
+                    .map_err(milli::Error::from)?
+                    .map(|x| x as usize)
+                    .unwrap_or(DEFAULT_VALUES_PER_FACET)
+            };
             facet_distribution.max_values_per_facet(max_values_by_facet);
 
             let sort_facet_values_by =
-                index.sort_facet_values_by(&rtxn).map_err(milli::Error::from)?;
+                index.sort_facet_values_by(rtxn).map_err(milli::Error::from)?;
 
             if fields.iter().all(|f| f != "*") {
                 let fields: Vec<_> =
@@ -973,17 +1726,40 @@ pub fn perform_search(
     let facet_stats = facet_stats.map(|stats| {
         stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect()
     });
+    let timings = timings.map(|timings| {
+        SearchTimingsView::new(
+            timings,
+            before_facets.elapsed(),
+            formatting_time.unwrap_or_default(),
+        )
+    });
+
+    let query = q.unwrap_or_default();
+    // the relaxed phrases are searched with all their words required
+    let matching_strategy = if relaxed_phrases { MatchingStrategy::All } else { matching_strategy };
+    let query_interpretation = query_interpretation.map(|interpretation| {
+        SearchQueryInterpretation::new(&query, interpretation, matching_strategy)
+    });
 
     let result = SearchResult {
         hits: documents,
         hits_info,
-        query: q.unwrap_or_default(),
+        query,
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
         degraded,
         used_negative_operator,
+        query_truncated,
+        relaxed_phrases,
         semantic_hit_count,
+        formatting_time_ms: formatting_time.map(|time| time.as_millis()),
+        query_terms: show_query_terms
+            .then(|| query_term_locations.into_iter().map(SearchQueryTerm::from).collect()),
+        query_interpretation,
+        timings,
+        session_token: session.as_ref().map(|session| session.token()),
+        typo_usage,
     };
     Ok(result)
 }
@@ -995,6 +1771,8 @@ struct AttributesFormat {
     attributes_to_crop: Option<Vec<String>>,
     crop_length: usize,
     crop_marker: String,
+    max_highlight_fragments: Option<usize>,
+    fragment_separator: Option<String>,
     highlight_pre_tag: String,
     highlight_post_tag: String,
     show_matches_position: bool,
@@ -1033,6 +1811,11 @@ impl RetrieveVectors {
     }
 }
 
+/// Makes the hits of the requested page, along with the time spent formatting them
+/// if any formatting was requested.
+///
+/// It must only be called with the documents of the page, as the formatting is
+/// by far the most expensive operation on long documents.
 fn make_hits(
     index: &Index,
     rtxn: &RoTxn<'_>,
@@ -1040,7 +1823,7 @@ fn make_hits(
     matching_words: milli::MatchingWords,
     documents_ids: Vec<u32>,
     document_scores: Vec<Vec<ScoreDetails>>,
-) -> Result<Vec<SearchHit>, MeilisearchHttpError> {
+) -> Result<(Vec<SearchHit>, Option<Duration>), MeilisearchHttpError> {
     let fields_ids_map = index.fields_ids_map(rtxn).unwrap();
     let displayed_ids =
         index.displayed_fields_ids(rtxn)?.map(|fields| fields.into_iter().collect::<BTreeSet<_>>());
@@ -1068,6 +1851,25 @@ fn make_hits(
 
     let displayed_ids =
         displayed_ids.unwrap_or_else(|| fields_ids_map.iter().map(|(id, _)| id).collect());
+
+    let ids_only = format.attributes_to_retrieve.as_ref().is_some_and(BTreeSet::is_empty)
+        && format.attributes_to_highlight.as_ref().map_or(true, HashSet::is_empty)
+        && format.attributes_to_crop.as_ref().map_or(true, Vec::is_empty)
+        && !format.show_matches_position
+        && retrieve_vectors != RetrieveVectors::Retrieve;
+    if ids_only {
+        let hits = make_ids_only_hits(
+            index,
+            rtxn,
+            &format,
+            &fields_ids_map,
+            &displayed_ids,
+            documents_ids,
+            document_scores,
+        )?;
+        return Ok((hits, None));
+    }
+
     let fids = |attrs: &BTreeSet<String>| {
         let mut ids = BTreeSet::new();
         for attr in attrs {
@@ -1103,7 +1905,10 @@ fn make_hits(
     );
     let mut tokenizer_builder = TokenizerBuilder::default();
     tokenizer_builder.create_char_map(true);
-    let script_lang_map = index.script_language(rtxn)?;
+    let script_lang_map = match index.language_preset(rtxn)? {
+        Some(preset) => preset.allow_list(),
+        None => index.script_language(rtxn)?,
+    };
     if !script_lang_map.is_empty() {
         tokenizer_builder.allow_list(&script_lang_map);
     }
@@ -1113,7 +1918,7 @@ fn make_hits(
     if let Some(ref separators) = separators {
         tokenizer_builder.separators(separators);
     }
-    let dictionary = index.dictionary(rtxn)?;
+    let dictionary = index.tokenizer_dictionary(rtxn)?;
     let dictionary: Option<Vec<_>> =
         dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
     if let Some(ref dictionary) = dictionary {
@@ -1121,10 +1926,20 @@ fn make_hits(
     }
     let mut formatter_builder = MatcherBuilder::new(matching_words, tokenizer_builder.build());
     formatter_builder.crop_marker(format.crop_marker);
+    if let Some(max_highlight_fragments) = format.max_highlight_fragments {
+        formatter_builder.max_fragments(max_highlight_fragments);
+    }
+    if let Some(fragment_separator) = format.fragment_separator {
+        formatter_builder.fragment_separator(fragment_separator);
+    }
     formatter_builder.highlight_prefix(format.highlight_pre_tag);
     formatter_builder.highlight_suffix(format.highlight_post_tag);
+    let should_format = !formatted_options.is_empty() || format.show_matches_position;
+    let mut formatting_time = Duration::ZERO;
     let mut documents = Vec::new();
     let embedding_configs = index.embedding_configs(rtxn)?;
+    let computed_fields =
+        ComputedFields::new(&index.computed_fields(rtxn)?).map_err(milli::Error::from)?;
     let documents_iter = index.documents(rtxn, documents_ids)?;
     for ((id, obkv), score) in documents_iter.into_iter().zip(document_scores.into_iter()) {
         // First generate a document with all the displayed fields
@@ -1168,19 +1983,39 @@ fn make_hits(
             document.insert("_vectors".into(), vectors.into());
         }
 
-        let (matches_position, formatted) = format_fields(
-            &displayed_document,
-            &fields_ids_map,
-            &formatter_builder,
-            &formatted_options,
-            format.show_matches_position,
-            &displayed_ids,
-        )?;
+        let (matches_position, formatted) = if should_format {
+            let before_formatting = Instant::now();
+            let formatted = format_fields(
+                &displayed_document,
+                &fields_ids_map,
+                &formatter_builder,
+                &formatted_options,
+                format.show_matches_position,
+                &displayed_ids,
+            )?;
+            formatting_time += before_formatting.elapsed();
+            formatted
+        } else {
+            (None, Document::new())
+        };
 
         if let Some(sort) = format.sort.as_ref() {
             insert_geo_distance(sort, &mut document);
         }
 
+        if !computed_fields.is_empty() {
+            // the expressions may use the fields that aren't displayed
+            let full_document = milli::all_obkv_to_json(obkv, &fields_ids_map)?;
+            for (name, value) in computed_fields.compute_all(&full_document) {
+                let retrieved = format.attributes_to_retrieve.as_ref().map_or(true, |attributes| {
+                    attributes.contains("*") || attributes.contains(&name)
+                });
+                if retrieved {
+                    document.insert(name, value);
+                }
+            }
+        }
+
         let ranking_score =
             format.show_ranking_score.then(|| ScoreDetails::global_score(score.iter()));
         let ranking_score_details =
@@ -1195,12 +2030,57 @@ fn make_hits(
         };
         documents.push(hit);
     }
+    Ok((documents, should_format.then_some(formatting_time)))
+}
+
+/// Makes the hits of a search that retrieves no attribute and doesn't format anything.
+///
+/// Only the primary key of the documents is decoded and returned, with its original JSON type,
+/// along with their scores, so that the caller can join the results with its own database or
+/// hydrate them later.
+fn make_ids_only_hits(
+    index: &Index,
+    rtxn: &RoTxn<'_>,
+    format: &AttributesFormat,
+    fields_ids_map: &FieldsIdsMap,
+    displayed_ids: &BTreeSet<FieldId>,
+    documents_ids: Vec<u32>,
+    document_scores: Vec<Vec<ScoreDetails>>,
+) -> Result<Vec<SearchHit>, MeilisearchHttpError> {
+    let primary_key = index
+        .primary_key(rtxn)?
+        .filter(|name| fields_ids_map.id(name).map_or(false, |fid| displayed_ids.contains(&fid)))
+        .and_then(|name| PrimaryKey::new(name, fields_ids_map));
+
+    let mut documents = Vec::with_capacity(documents_ids.len());
+    let documents_iter = index.documents(rtxn, documents_ids)?;
+    for ((_id, obkv), score) in documents_iter.into_iter().zip(document_scores) {
+        let mut document = Document::new();
+        if let Some(primary_key) = primary_key {
+            if let Some(value) = primary_key.document_id_value(&obkv, fields_ids_map)? {
+                document.insert(primary_key.name().to_string(), value);
+            }
+        }
+
+        let ranking_score =
+            format.show_ranking_score.then(|| ScoreDetails::global_score(score.iter()));
+        let ranking_score_details =
+            format.show_ranking_score_details.then(|| ScoreDetails::to_json_map(score.iter()));
+
+        documents.push(SearchHit {
+            document,
+            formatted: Document::new(),
+            matches_position: None,
+            ranking_score_details,
+            ranking_score,
+        });
+    }
     Ok(documents)
 }
 
 pub fn perform_facet_search(
     index: &Index,
-    search_query: SearchQuery,
+    mut search_query: SearchQuery,
     facet_query: Option<String>,
     facet_name: String,
     search_kind: SearchKind,
@@ -1212,7 +2092,9 @@ pub fn perform_facet_search(
         None => TimeBudget::default(),
     };
 
-    let (search, _, _, _) = prepare_search(index, &rtxn, &search_query, &search_kind, time_budget)?;
+    resolve_prepared_filter(index, &rtxn, &mut search_query)?;
+    let (search, _, _, _, _) =
+        prepare_search(index, &rtxn, &search_query, &search_kind, time_budget, false)?;
     let mut facet_search = SearchForFacetValues::new(
         facet_name,
         search,
@@ -1287,6 +2169,11 @@ pub fn perform_similar(
         document_scores,
         degraded: _,
         used_negative_operator: _,
+        query_truncated: _,
+        query_term_locations: _,
+        query_interpretation: _,
+        relaxed_phrases: _,
+        timings: _,
     } = similar.execute().map_err(|err| match err {
         milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
             ResponseError::from_msg(err.to_string(), Code::InvalidSimilarFilter)
@@ -1301,6 +2188,8 @@ pub fn perform_similar(
         attributes_to_crop: None,
         crop_length: DEFAULT_CROP_LENGTH(),
         crop_marker: DEFAULT_CROP_MARKER(),
+        max_highlight_fragments: None,
+        fragment_separator: None,
         highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
         highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
         show_matches_position: false,
@@ -1309,7 +2198,8 @@ pub fn perform_similar(
         show_ranking_score_details,
     };
 
-    let hits = make_hits(index, &rtxn, format, Default::default(), documents_ids, document_scores)?;
+    let (hits, _formatting_time) =
+        make_hits(index, &rtxn, format, Default::default(), documents_ids, document_scores)?;
 
     let max_total_hits = index
         .pagination_max_total_hits(&rtxn)
@@ -1491,7 +2381,21 @@ fn format_fields<'a>(
     displayable_ids: &BTreeSet<FieldId>,
 ) -> Result<(Option<MatchesPosition>, Document), MeilisearchHttpError> {
     let mut matches_position = compute_matches.then(BTreeMap::new);
-    let mut document = document.clone();
+
+    let selectors: Vec<_> = formatted_options
+        .keys()
+        // This unwrap must be safe since we got the ids from the fields_ids_map just
+        // before.
+        .map(|&fid| field_ids_map.name(fid).unwrap())
+        .collect();
+
+    // the matches position are computed on all the displayed attributes, otherwise
+    // only the attributes returned in `_formatted` need to be visited
+    let mut document = if compute_matches {
+        document.clone()
+    } else {
+        permissive_json_pointer::select_values(document, selectors.iter().copied())
+    };
 
     // reduce the formatted option list to the attributes that should be formatted,
     // instead of all the attributes to display.
@@ -1530,11 +2434,6 @@ fn format_fields<'a>(
         }
     });
 
-    let selectors = formatted_options
-        .keys()
-        // This unwrap must be safe since we got the ids from the fields_ids_map just
-        // before.
-        .map(|&fid| field_ids_map.name(fid).unwrap());
     let document = permissive_json_pointer::select_values(&document, selectors);
 
     Ok((matches_position, document))
@@ -1668,6 +2567,35 @@ fn parse_filter_array(arr: &[Value]) -> Result<Option<Filter>, MeilisearchHttpEr
 mod test {
     use super::*;
 
+    #[test]
+    fn write_key_search_parameters_as_applied() {
+        let parameters = KeySearchParameters {
+            default: BTreeMap::from([
+                ("limit".to_string(), json!(1)),
+                ("sort".to_string(), json!(["price:asc"])),
+            ]),
+            forced: BTreeMap::from([
+                ("attributesToRetrieve".to_string(), json!(["title"])),
+                ("filter".to_string(), json!("tenant = acme")),
+            ]),
+        };
+        let mut raw_query = json!({ "q": "kefir", "limit": 5, "filter": "color = red" });
+        let mut query: SearchQuery =
+            deserr::deserialize::<_, _, DeserrJsonError>(raw_query.clone()).unwrap();
+        query.apply_key_search_parameters(&parameters).unwrap();
+        query.write_key_search_parameters(&mut raw_query, &parameters).unwrap();
+
+        // the limit of the request is kept, the other parameters are the applied ones
+        assert_eq!(raw_query["q"], json!("kefir"));
+        assert_eq!(raw_query["limit"], json!(5));
+        assert_eq!(raw_query["sort"], json!(["price:asc"]));
+        assert_eq!(raw_query["attributesToRetrieve"], json!(["title"]));
+        assert_eq!(Some(&raw_query["filter"]), query.filter.as_ref());
+        let forwarded: SearchQuery =
+            deserr::deserialize::<_, _, DeserrJsonError>(raw_query).unwrap();
+        assert_eq!(forwarded, query);
+    }
+
     #[test]
     fn test_insert_geo_distance() {
         let value: Document = serde_json::from_str(
@@ -0,0 +1,101 @@
+//! Counts the searches made on each index over the last hour, so that the activity
+//! of an index can report its recent search volume.
+//!
+//! The counts are kept in memory, by minute, and are lost when the instance restarts.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+/// The number of minutes the searches are counted for.
+const WINDOW_MINUTES: i64 = 60;
+
+pub static SEARCH_ACTIVITY: Lazy<SearchActivity> = Lazy::new(SearchActivity::default);
+
+#[derive(Debug, Default)]
+pub struct SearchActivity {
+    /// The number of searches of each index by minute, the oldest minute first.
+    searches: Mutex<HashMap<String, VecDeque<(i64, u64)>>>,
+}
+
+/// The number of searches made on an index recently.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchVolume {
+    pub last_minute: u64,
+    pub last_hour: u64,
+}
+
+impl SearchActivity {
+    /// Counts a search made on the given index.
+    pub fn record(&self, index_uid: &str) {
+        self.record_at(index_uid, OffsetDateTime::now_utc());
+    }
+
+    /// Returns the number of searches made on the given index recently.
+    pub fn volume(&self, index_uid: &str) -> SearchVolume {
+        self.volume_at(index_uid, OffsetDateTime::now_utc())
+    }
+
+    fn record_at(&self, index_uid: &str, now: OffsetDateTime) {
+        let minute = now.unix_timestamp().div_euclid(60);
+        let mut searches = self.searches.lock().unwrap();
+        let minutes = searches.entry(index_uid.to_string()).or_default();
+
+        match minutes.back_mut() {
+            Some((last, count)) if *last == minute => *count += 1,
+            _ => minutes.push_back((minute, 1)),
+        }
+        while minutes.front().map_or(false, |(oldest, _)| *oldest <= minute - WINDOW_MINUTES) {
+            minutes.pop_front();
+        }
+    }
+
+    fn volume_at(&self, index_uid: &str, now: OffsetDateTime) -> SearchVolume {
+        let minute = now.unix_timestamp().div_euclid(60);
+        let searches = self.searches.lock().unwrap();
+        let Some(minutes) = searches.get(index_uid) else {
+            return SearchVolume::default();
+        };
+
+        let mut volume = SearchVolume::default();
+        for &(searched_at, count) in minutes {
+            if searched_at > minute - WINDOW_MINUTES {
+                volume.last_hour += count;
+            }
+            if searched_at == minute {
+                volume.last_minute += count;
+            }
+        }
+        volume
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn volume_over_the_last_hour() {
+        let activity = SearchActivity::default();
+        let now = OffsetDateTime::now_utc();
+
+        activity.record_at("movies", now - Duration::minutes(90));
+        activity.record_at("movies", now - Duration::minutes(30));
+        activity.record_at("movies", now);
+        activity.record_at("movies", now);
+        activity.record_at("books", now);
+
+        assert_eq!(
+            activity.volume_at("movies", now),
+            SearchVolume { last_minute: 2, last_hour: 3 }
+        );
+        assert_eq!(activity.volume_at("books", now), SearchVolume { last_minute: 1, last_hour: 1 });
+        assert_eq!(activity.volume_at("shows", now), SearchVolume::default());
+    }
+}
@@ -0,0 +1,254 @@
+//! Search jobs, running the expensive searches in the background so that they don't slow down
+//! the synchronous searches.
+//!
+//! A job is created with the query to run and is processed as soon as one of the few job slots
+//! is free. Its searches ignore the limits keeping the synchronous searches fast: all the hits
+//! are counted, the pagination limit and the search cutoff don't apply, and the facet
+//! distributions return all the facet values.
+//!
+//! A job can only be retrieved with the API key that created it, or with the same tenant token
+//! when it was created with a tenant token. Its searches return at most `MAX_SEARCH_JOB_HITS`
+//! hits.
+//!
+//! The jobs and their results are kept in memory and are lost when the instance restarts.
+//! The results expire an hour after the end of their job, and the oldest ones are dropped
+//! when the results kept exceed a total size.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use meilisearch_auth::AuthFilter;
+use meilisearch_types::error::ResponseError;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use time::OffsetDateTime;
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+
+use crate::error::MeilisearchHttpError;
+use crate::search::SearchResult;
+
+/// How long the result of a job is kept after the end of the job.
+const RESULT_TTL: Duration = Duration::from_secs(60 * 60);
+/// The maximum number of jobs kept at the same time, whether they are finished or not.
+const MAX_SEARCH_JOBS: usize = 100;
+/// The maximum number of jobs processed at the same time.
+const MAX_RUNNING_SEARCH_JOBS: usize = 2;
+/// The maximum total size, in bytes of JSON, of the results kept at the same time.
+const MAX_RESULTS_SIZE: usize = 100 * 1024 * 1024;
+/// The maximum number of hits returned by the search of a job, which counts all of them.
+pub const MAX_SEARCH_JOB_HITS: usize = 10_000;
+
+pub static SEARCH_JOBS: Lazy<SearchJobs> = Lazy::new(SearchJobs::default);
+
+pub struct SearchJobs {
+    jobs: Mutex<HashMap<Uuid, SearchJobView>>,
+    slots: Semaphore,
+}
+
+impl Default for SearchJobs {
+    fn default() -> Self {
+        Self { jobs: Mutex::default(), slots: Semaphore::new(MAX_RUNNING_SEARCH_JOBS) }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchJobStatus {
+    Enqueued,
+    Processing,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchJobView {
+    pub uid: Uuid,
+    pub index_uid: String,
+    pub status: SearchJobStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<SearchResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ResponseError>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub enqueued_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub started_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub finished_at: Option<OffsetDateTime>,
+    /// The API key and the tenant token that created the job.
+    #[serde(skip)]
+    owner: SearchJobOwner,
+    /// The size of the result once serialized in JSON.
+    #[serde(skip)]
+    result_size: usize,
+}
+
+/// The API key, and the tenant token if any, a job was created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchJobOwner {
+    /// The uid of the API key, `None` for the master key.
+    key_uid: Option<Uuid>,
+    /// The digest of the tenant token, `None` when the API key was used directly.
+    tenant_token_digest: Option<[u8; 32]>,
+}
+
+impl SearchJobOwner {
+    pub fn new(filters: &AuthFilter) -> Self {
+        Self { key_uid: filters.key_uid(), tenant_token_digest: filters.tenant_token_digest() }
+    }
+}
+
+impl SearchJobs {
+    /// Creates a job running the given search in the background, once a job slot is free.
+    ///
+    /// The oldest finished job is dropped if there are too many of them.
+    pub fn enqueue(
+        &'static self,
+        index_uid: &str,
+        owner: SearchJobOwner,
+        search: impl FnOnce() -> Result<SearchResult, ResponseError> + Send + 'static,
+    ) -> Result<SearchJobView, MeilisearchHttpError> {
+        let now = OffsetDateTime::now_utc();
+        let job = SearchJobView {
+            uid: Uuid::new_v4(),
+            index_uid: index_uid.to_string(),
+            status: SearchJobStatus::Enqueued,
+            result: None,
+            error: None,
+            enqueued_at: now,
+            started_at: None,
+            finished_at: None,
+            owner,
+            result_size: 0,
+        };
+
+        {
+            let mut jobs = self.jobs.lock().unwrap();
+            remove_expired_jobs(&mut jobs, now);
+            if jobs.len() >= MAX_SEARCH_JOBS {
+                let oldest_finished = jobs
+                    .values()
+                    .filter_map(|job| job.finished_at.map(|finished_at| (finished_at, job.uid)))
+                    .min();
+                match oldest_finished {
+                    Some((_, uid)) => jobs.remove(&uid),
+                    None => return Err(MeilisearchHttpError::TooManySearchJobs(MAX_SEARCH_JOBS)),
+                };
+            }
+            jobs.insert(job.uid, job.clone());
+        }
+
+        let uid = job.uid;
+        tokio::spawn(async move {
+            // the semaphore is never closed
+            let _slot = self.slots.acquire().await;
+            self.update(uid, |job| {
+                job.status = SearchJobStatus::Processing;
+                job.started_at = Some(OffsetDateTime::now_utc());
+            });
+
+            let result = match tokio::task::spawn_blocking(search).await {
+                Ok(result) => result,
+                Err(error) => Err(MeilisearchHttpError::from(error).into()),
+            };
+            let result = result.and_then(|result| match json_size(&result) {
+                Ok(size) if size <= MAX_RESULTS_SIZE => Ok((result, size)),
+                Ok(_) => {
+                    Err(MeilisearchHttpError::SearchJobResultTooLarge(MAX_RESULTS_SIZE).into())
+                }
+                Err(error) => Err(error.into()),
+            });
+
+            self.finish(uid, result);
+        });
+
+        Ok(job)
+    }
+
+    /// Returns the job, if it was created on this index with the same API key and tenant token,
+    /// and its result didn't expire.
+    pub fn get(
+        &self,
+        index_uid: &str,
+        uid: &str,
+        owner: SearchJobOwner,
+    ) -> Result<SearchJobView, MeilisearchHttpError> {
+        let not_found = || MeilisearchHttpError::SearchJobNotFound(uid.to_string());
+        let uid = Uuid::parse_str(uid).map_err(|_| not_found())?;
+
+        let mut jobs = self.jobs.lock().unwrap();
+        remove_expired_jobs(&mut jobs, OffsetDateTime::now_utc());
+        jobs.get(&uid)
+            .filter(|job| job.index_uid == index_uid && job.owner == owner)
+            .cloned()
+            .ok_or_else(not_found)
+    }
+
+    /// Stores the outcome of the job, then drops the oldest finished jobs until the results
+    /// kept fit in the maximum total size.
+    fn finish(&self, uid: Uuid, result: Result<(SearchResult, usize), ResponseError>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        let Some(job) = jobs.get_mut(&uid) else { return };
+        match result {
+            Ok((result, size)) => {
+                job.status = SearchJobStatus::Succeeded;
+                job.result = Some(result);
+                job.result_size = size;
+            }
+            Err(error) => {
+                job.status = SearchJobStatus::Failed;
+                job.error = Some(error);
+            }
+        }
+        job.finished_at = Some(OffsetDateTime::now_utc());
+
+        let mut total_size: usize = jobs.values().map(|job| job.result_size).sum();
+        while total_size > MAX_RESULTS_SIZE {
+            let oldest_finished = jobs
+                .values()
+                .filter(|job| job.uid != uid)
+                .filter_map(|job| job.finished_at.map(|finished_at| (finished_at, job.uid)))
+                .min();
+            let Some((_, oldest_uid)) = oldest_finished else { break };
+            if let Some(job) = jobs.remove(&oldest_uid) {
+                total_size -= job.result_size;
+            }
+        }
+    }
+
+    fn update(&self, uid: Uuid, f: impl FnOnce(&mut SearchJobView)) {
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(&uid) {
+            f(job);
+        }
+    }
+}
+
+/// Returns the number of bytes of the result once serialized in JSON.
+fn json_size(result: &SearchResult) -> Result<usize, MeilisearchHttpError> {
+    struct ByteCounter(usize);
+
+    impl io::Write for ByteCounter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = ByteCounter(0);
+    serde_json::to_writer(&mut counter, result)?;
+    Ok(counter.0)
+}
+
+fn remove_expired_jobs(jobs: &mut HashMap<Uuid, SearchJobView>, now: OffsetDateTime) {
+    jobs.retain(|_, job| {
+        job.finished_at.map_or(true, |finished_at| now - finished_at < RESULT_TTL)
+    });
+}
@@ -16,11 +16,17 @@
 //!                                You should exit and free all the RAM you use ASAP.
 //! - Sends you a Permit => that will unlock the method, and you will be able to process your search.
 //!                         And should drop the Permit only once you have freed all the RAM consumed by the method.
+//!
+//! While holding the permit, the search itself should be run with the `SearchQueue::run` method.
+//! It executes it on a thread pool dedicated to the searches, distinct from the indexing one,
+//! so that a heavy indexing batch can't take the threads the searches need.
 
 use std::num::NonZeroUsize;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use tokio::sync::{mpsc, oneshot};
 
 use crate::error::MeilisearchHttpError;
@@ -29,6 +35,7 @@ use crate::error::MeilisearchHttpError;
 pub struct SearchQueue {
     sender: mpsc::Sender<oneshot::Sender<Permit>>,
     capacity: usize,
+    thread_pool: ThreadPool,
 }
 
 /// You should only run search requests while holding this permit.
@@ -52,15 +59,22 @@ impl SearchQueue {
         // so let's not allocate any RAM and keep a capacity of 1.
         let (sender, receiver) = mpsc::channel(1);
 
-        tokio::task::spawn(Self::run(capacity, paralellism, receiver));
-        Self { sender, capacity }
+        // There is one thread per search allowed to run at the same time.
+        let thread_pool = ThreadPoolBuilder::new()
+            .thread_name(|index| format!("search-thread:{index}"))
+            .num_threads(paralellism.into())
+            .build()
+            .expect("Could not create the search thread pool");
+
+        tokio::task::spawn(Self::schedule(capacity, paralellism, receiver));
+        Self { sender, capacity, thread_pool }
     }
 
     /// This function is the main loop, it's in charge on scheduling which search request should execute first and
     /// how many should executes at the same time.
     ///
     /// It **must never** panic or exit.
-    async fn run(
+    async fn schedule(
         capacity: usize,
         parallelism: NonZeroUsize,
         mut receive_new_searches: mpsc::Receiver<oneshot::Sender<Permit>>,
@@ -124,6 +138,31 @@ impl SearchQueue {
         receiver.await.map_err(|_| MeilisearchHttpError::TooManySearchRequests(self.capacity))
     }
 
+    /// Runs the search on the search thread pool and returns its result.
+    /// You should hold a search `Permit` for the whole duration of the search.
+    pub async fn run<F, R>(&self, search: F) -> Result<R, MeilisearchHttpError>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+        self.thread_pool.spawn(move || {
+            // A panic must not abort the pool, the search is reported as failed instead.
+            let _ = sender.send(catch_unwind(AssertUnwindSafe(search)));
+        });
+
+        match receiver.await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(_panic)) => Err(MeilisearchHttpError::SearchPanicked),
+            Err(_) => Err(MeilisearchHttpError::SearchLimiterIsDown),
+        }
+    }
+
+    /// Returns the number of threads dedicated to the searches.
+    pub fn threads(&self) -> usize {
+        self.thread_pool.current_num_threads()
+    }
+
     /// Returns `Ok(())` if everything seems normal.
     /// Returns `Err(MeilisearchHttpError::SearchLimiterIsDown)` if the search limiter seems down.
     pub fn health(&self) -> Result<(), MeilisearchHttpError> {
@@ -0,0 +1,219 @@
+//! Short-lived search sessions, so that paginating a result set isn't disturbed by the
+//! documents added, updated, or deleted between two pages.
+//!
+//! A session is created by a search with `createSession` and its token is returned along with
+//! the results. The searches made with this token read the index as it was when the session
+//! was created, and take their hits from the ranking the session computed for the same query.
+//!
+//! The sessions are kept in memory and expire a few minutes after their last search. Each one
+//! keeps a read transaction open, which prevents LMDB from reusing the pages freed by the
+//! updates of the index, so only a limited number of them are kept at the same time, the
+//! expired ones are regularly dropped, and the sessions of an index are dropped before it is
+//! closed.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use meilisearch_types::heed::RoTxn;
+use meilisearch_types::milli::{self, Index};
+use once_cell::sync::Lazy;
+use uuid::Uuid;
+
+use crate::error::MeilisearchHttpError;
+use crate::search::SearchQuery;
+
+/// How long a session is kept after its last search.
+const SESSION_TTL: Duration = Duration::from_secs(5 * 60);
+/// The maximum number of sessions kept at the same time.
+const MAX_SESSIONS: usize = 100;
+/// How often the expired sessions are dropped.
+const REAPER_INTERVAL: Duration = Duration::from_secs(SESSION_TTL.as_secs() / 10);
+
+pub static SEARCH_SESSIONS: Lazy<SearchSessions> = Lazy::new(SearchSessions::default);
+
+#[derive(Default)]
+pub struct SearchSessions {
+    sessions: Mutex<HashMap<Uuid, Arc<SearchSession>>>,
+}
+
+pub struct SearchSession {
+    token: Uuid,
+    index_uid: String,
+    /// The index the snapshot was taken from, whose databases must be read with it.
+    index: Index,
+    /// The snapshot of the index read by the searches of the session.
+    rtxn: Mutex<RoTxn<'static>>,
+    /// The ranking computed for the last query of the session.
+    ranking: Mutex<Option<CachedRanking>>,
+    last_used: Mutex<Instant>,
+}
+
+struct CachedRanking {
+    /// The query that was ranked, without its pagination parameters.
+    query: SearchQuery,
+    /// The number of documents that were asked to the ranking.
+    limit: usize,
+    result: milli::SearchResult,
+    semantic_hit_count: Option<u32>,
+}
+
+impl SearchSessions {
+    /// Creates a new session reading a snapshot of the current state of the index.
+    ///
+    /// The least recently used session is dropped if there are too many of them.
+    pub fn create(
+        &self,
+        index_uid: &str,
+        index: &Index,
+    ) -> Result<Arc<SearchSession>, MeilisearchHttpError> {
+        let now = Instant::now();
+        let session = Arc::new(SearchSession {
+            token: Uuid::new_v4(),
+            index_uid: index_uid.to_string(),
+            index: index.clone(),
+            rtxn: Mutex::new(index.static_read_txn()?),
+            ranking: Mutex::new(None),
+            last_used: Mutex::new(now),
+        });
+
+        let mut sessions = self.sessions.lock().unwrap();
+        remove_expired_sessions(&mut sessions, now);
+        if sessions.len() >= MAX_SESSIONS {
+            let least_recently_used =
+                sessions.values().min_by_key(|session| session.last_used()).map(|s| s.token);
+            if let Some(token) = least_recently_used {
+                sessions.remove(&token);
+            }
+        }
+        sessions.insert(session.token, session.clone());
+
+        Ok(session)
+    }
+
+    /// Returns the session of the token, if it was created on this index and didn't expire.
+    pub fn get(
+        &self,
+        index_uid: &str,
+        token: &str,
+    ) -> Result<Arc<SearchSession>, MeilisearchHttpError> {
+        let not_found = || MeilisearchHttpError::SearchSessionNotFound(token.to_string());
+        let uuid = Uuid::parse_str(token).map_err(|_| not_found())?;
+
+        let now = Instant::now();
+        let mut sessions = self.sessions.lock().unwrap();
+        remove_expired_sessions(&mut sessions, now);
+        let session = sessions
+            .get(&uuid)
+            .filter(|session| session.index_uid == index_uid)
+            .ok_or_else(not_found)?;
+        *session.last_used.lock().unwrap() = now;
+
+        Ok(session.clone())
+    }
+
+    /// Drops the sessions reading the given index, which can't be closed while they are kept.
+    pub fn remove_index_sessions(&self, index: &Index) {
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|_, session| session.index.path() != index.path());
+    }
+
+    /// Drops the expired sessions in a background thread, every few seconds.
+    ///
+    /// The thread is only spawned once, whatever the number of calls.
+    pub fn spawn_reaper(&'static self) {
+        static REAPER: Once = Once::new();
+        REAPER.call_once(|| {
+            thread::Builder::new()
+                .name(String::from("search-sessions-reaper"))
+                .spawn(move || loop {
+                    thread::sleep(REAPER_INTERVAL);
+                    let mut sessions = self.sessions.lock().unwrap();
+                    remove_expired_sessions(&mut sessions, Instant::now());
+                })
+                .unwrap();
+        });
+    }
+}
+
+fn remove_expired_sessions(sessions: &mut HashMap<Uuid, Arc<SearchSession>>, now: Instant) {
+    sessions.retain(|_, session| now.duration_since(session.last_used()) < SESSION_TTL);
+}
+
+impl SearchSession {
+    pub fn token(&self) -> String {
+        self.token.to_string()
+    }
+
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Returns the snapshot of the index, which can only be read by one search at a time.
+    pub fn rtxn(&self) -> MutexGuard<'_, RoTxn<'static>> {
+        self.rtxn.lock().unwrap()
+    }
+
+    fn last_used(&self) -> Instant {
+        *self.last_used.lock().unwrap()
+    }
+
+    /// Returns the ranking of at least the `limit` first documents of the query,
+    /// if the session already computed it.
+    pub fn cached_ranking(
+        &self,
+        query: &SearchQuery,
+        limit: usize,
+    ) -> Option<(milli::SearchResult, Option<u32>)> {
+        let ranking = self.ranking.lock().unwrap();
+        let ranking = ranking.as_ref().filter(|ranking| ranking.query == ranking_query(query))?;
+        // a ranking with less documents than asked contains all the matching documents
+        let is_exhaustive = ranking.result.documents_ids.len() < ranking.limit;
+        (ranking.limit >= limit || is_exhaustive)
+            .then(|| (ranking.result.clone(), ranking.semantic_hit_count))
+    }
+
+    /// Keeps the ranking of the `limit` first documents of the query for the next pages.
+    pub fn cache_ranking(
+        &self,
+        query: &SearchQuery,
+        limit: usize,
+        result: &milli::SearchResult,
+        semantic_hit_count: Option<u32>,
+    ) {
+        *self.ranking.lock().unwrap() = Some(CachedRanking {
+            query: ranking_query(query),
+            limit,
+            result: result.clone(),
+            semantic_hit_count,
+        });
+    }
+}
+
+/// Returns the query without the parameters selecting the page of results,
+/// which is the same for all the pages of a result set.
+fn ranking_query(query: &SearchQuery) -> SearchQuery {
+    SearchQuery {
+        offset: 0,
+        limit: 0,
+        page: None,
+        hits_per_page: None,
+        create_session: false,
+        session_token: None,
+        ..query.clone()
+    }
+}
+
+/// Returns the session the query must be run in, if it resumes a session or creates one.
+pub fn search_session(
+    index_uid: &str,
+    index: &Index,
+    query: &SearchQuery,
+) -> Result<Option<Arc<SearchSession>>, MeilisearchHttpError> {
+    match (&query.session_token, query.create_session) {
+        (Some(token), _) => SEARCH_SESSIONS.get(index_uid, token).map(Some),
+        (None, true) => SEARCH_SESSIONS.create(index_uid, index).map(Some),
+        (None, false) => Ok(None),
+    }
+}
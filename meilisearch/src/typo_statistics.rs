@@ -0,0 +1,138 @@
+//! Counts how many typos the hits returned by the searches on each index needed to match,
+//! so that the typo tolerance of an index can be tuned from its real traffic.
+//!
+//! The hits are only counted while the `typoStatistics` experimental feature is enabled.
+//! The counts are kept in memory and are lost when the instance restarts.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use meilisearch_types::milli::score_details::ScoreDetails;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+pub static TYPO_STATISTICS: Lazy<TypoStatistics> = Lazy::new(TypoStatistics::default);
+
+#[derive(Debug, Default)]
+pub struct TypoStatistics {
+    usages: Mutex<HashMap<String, TypoUsage>>,
+}
+
+/// The number of hits that matched the query with each number of typos.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypoUsage {
+    /// The number of hits whose typos were counted.
+    pub hits: u64,
+    pub zero_typo_hits: u64,
+    pub one_typo_hits: u64,
+    pub two_typos_hits: u64,
+    /// The number of hits where a term only matched longer words it is a prefix of.
+    pub prefix_hits: u64,
+}
+
+impl TypoUsage {
+    /// Counts the typos of the hits from their detailed scores.
+    ///
+    /// A hit is counted with the most typos any of its terms needed, and the hits that
+    /// weren't ranked by the `typo` ranking rule, e.g. the semantic hits, are ignored.
+    pub fn from_scores(document_scores: &[Vec<ScoreDetails>]) -> Self {
+        let mut usage = TypoUsage::default();
+        for scores in document_scores {
+            let Some(typo) = scores.iter().find_map(|details| match details {
+                ScoreDetails::Typo(typo) => Some(typo),
+                _ => None,
+            }) else {
+                continue;
+            };
+
+            usage.hits += 1;
+            match typo.terms.iter().map(|term| term.typos).max().unwrap_or(0) {
+                0 => usage.zero_typo_hits += 1,
+                1 => usage.one_typo_hits += 1,
+                _ => usage.two_typos_hits += 1,
+            }
+            if typo.terms.iter().any(|term| term.prefix) {
+                usage.prefix_hits += 1;
+            }
+        }
+        usage
+    }
+
+    pub fn merge(&mut self, other: &TypoUsage) {
+        let TypoUsage { hits, zero_typo_hits, one_typo_hits, two_typos_hits, prefix_hits } = other;
+        self.hits = self.hits.saturating_add(*hits);
+        self.zero_typo_hits = self.zero_typo_hits.saturating_add(*zero_typo_hits);
+        self.one_typo_hits = self.one_typo_hits.saturating_add(*one_typo_hits);
+        self.two_typos_hits = self.two_typos_hits.saturating_add(*two_typos_hits);
+        self.prefix_hits = self.prefix_hits.saturating_add(*prefix_hits);
+    }
+}
+
+impl TypoStatistics {
+    /// Adds the typos of the hits of a search made on the given index.
+    pub fn record(&self, index_uid: &str, usage: &TypoUsage) {
+        let mut usages = self.usages.lock().unwrap();
+        usages.entry(index_uid.to_string()).or_default().merge(usage);
+    }
+
+    /// Returns the typos counted for the given index since the instance started.
+    pub fn usage(&self, index_uid: &str) -> TypoUsage {
+        self.usages.lock().unwrap().get(index_uid).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use meilisearch_types::milli::score_details::{Rank, TermTypos, Typo, Words};
+
+    use super::*;
+
+    fn scores(terms: &[(u8, bool)]) -> Vec<ScoreDetails> {
+        let terms = terms
+            .iter()
+            .map(|&(typos, prefix)| TermTypos {
+                term: String::from("term"),
+                typos,
+                split: false,
+                prefix,
+            })
+            .collect();
+        vec![
+            ScoreDetails::Words(Words { matching_words: 1, max_matching_words: 1 }),
+            ScoreDetails::Typo(Typo {
+                typo_count: 0,
+                max_typo_count: 2,
+                rank: Rank { rank: 3, max_rank: 3 },
+                terms,
+            }),
+        ]
+    }
+
+    #[test]
+    fn count_the_typos_of_the_hits() {
+        let statistics = TypoStatistics::default();
+
+        let usage = TypoUsage::from_scores(&[
+            scores(&[(0, false), (0, false)]),
+            scores(&[(0, false), (1, false)]),
+            scores(&[(2, false), (0, true)]),
+            // a hit that wasn't ranked by the typo ranking rule
+            vec![],
+        ]);
+        statistics.record("movies", &usage);
+        statistics.record("movies", &TypoUsage::from_scores(&[scores(&[(0, true)])]));
+
+        assert_eq!(
+            statistics.usage("movies"),
+            TypoUsage {
+                hits: 4,
+                zero_typo_hits: 2,
+                one_typo_hits: 1,
+                two_typos_hits: 1,
+                prefix_hits: 2,
+            }
+        );
+        assert_eq!(statistics.usage("books"), TypoUsage::default());
+    }
+}
@@ -421,7 +421,7 @@ async fn error_add_api_key_invalid_parameters_actions() {
     meili_snap::snapshot!(code, @"400 Bad Request");
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
-      "message": "Unknown value `doc.add` at `.actions[0]`: expected one of `*`, `search`, `documents.*`, `documents.add`, `documents.get`, `documents.delete`, `indexes.*`, `indexes.create`, `indexes.get`, `indexes.update`, `indexes.delete`, `indexes.swap`, `tasks.*`, `tasks.cancel`, `tasks.delete`, `tasks.get`, `settings.*`, `settings.get`, `settings.update`, `stats.*`, `stats.get`, `metrics.*`, `metrics.get`, `dumps.*`, `dumps.create`, `snapshots.*`, `snapshots.create`, `version`, `keys.create`, `keys.get`, `keys.update`, `keys.delete`, `experimental.get`, `experimental.update`",
+      "message": "Unknown value `doc.add` at `.actions[0]`: expected one of `*`, `search`, `documents.*`, `documents.add`, `documents.get`, `documents.delete`, `indexes.*`, `indexes.create`, `indexes.get`, `indexes.update`, `indexes.delete`, `indexes.swap`, `tasks.*`, `tasks.cancel`, `tasks.delete`, `tasks.get`, `settings.*`, `settings.get`, `settings.update`, `stats.*`, `stats.get`, `metrics.*`, `metrics.get`, `dumps.*`, `dumps.create`, `snapshots.*`, `snapshots.create`, `version`, `keys.create`, `keys.get`, `keys.update`, `keys.delete`, `experimental.get`, `experimental.update`, `documents.pii`",
       "code": "invalid_api_key_actions",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_api_key_actions"
@@ -514,7 +514,7 @@ async fn error_add_api_key_parameters_uid_already_exist() {
     });
 
     // first creation is valid.
-    let (response, code) = server.add_api_key(content.clone()).await;
+    let (response, code) = server.add_api_key(content).await;
     meili_snap::snapshot!(meili_snap::json_string!(response, { ".createdAt" => "[ignored]", ".updatedAt" => "[ignored]" }), @r###"
     {
       "name": null,
@@ -1779,3 +1779,146 @@ async fn error_access_api_key_routes_no_master_key_set() {
     "###);
     meili_snap::snapshot!(code, @"401 Unauthorized");
 }
+
+#[actix_rt::test]
+async fn search_with_key_search_parameters() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let index = server.index("products");
+    let (task, _) = index.update_settings(json!({ "filterableAttributes": ["tenant"] })).await;
+    index.wait_task(task.uid()).await;
+    let documents = json!([
+        { "id": 1, "tenant": "acme", "name": "kefir" },
+        { "id": 2, "tenant": "acme", "name": "intel" },
+        { "id": 3, "tenant": "globex", "name": "kefir" },
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let content = json!({
+        "indexes": ["products"],
+        "actions": ["search"],
+        "expiresAt": null,
+        "searchParameters": { "default": { "limit": 1 }, "forced": { "filter": "tenant = acme" } },
+    });
+    let (response, code) = server.add_api_key(content.clone()).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    meili_snap::snapshot!(meili_snap::json_string!(response["searchParameters"]), @r###"
+    {
+      "default": {
+        "limit": 1
+      },
+      "forced": {
+        "filter": "tenant = acme"
+      }
+    }
+    "###);
+    server.use_api_key(response["key"].as_str().unwrap());
+
+    // the default limit applies and the documents of the other tenants are never returned
+    let index = server.index("products");
+    let (response, code) = index.search_post(json!({ "q": "kefir" })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "tenant": "acme",
+        "name": "kefir"
+      }
+    ]
+    "###);
+
+    // the default limit can be overridden, but not the forced filter
+    let (response, code) =
+        index.search_post(json!({ "limit": 10, "filter": "tenant = globex" })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @"[]");
+
+    server.use_api_key("MASTER_KEY");
+    let content = json!({
+        "indexes": ["products"],
+        "actions": ["search"],
+        "expiresAt": null,
+        "searchParameters": { "forced": { "doggo": true } },
+    });
+    let (response, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.searchParameters.forced`: Unknown field `doggo`: expected one of `q`, `vector`, `hybrid`, `offset`, `limit`, `page`, `hitsPerPage`, `attributesToRetrieve`, `retrieveVectors`, `attributesToCrop`, `cropLength`, `attributesToHighlight`, `showMatchesPosition`, `showRankingScore`, `showRankingScoreDetails`, `showQueryTerms`, `showQueryInterpretation`, `showTimings`, `filter`, `preparedFilter`, `sort`, `distinct`, `diversify`, `facets`, `highlightPreTag`, `highlightPostTag`, `cropMarker`, `maxHighlightFragments`, `fragmentSeparator`, `matchingStrategy`, `phraseFallback`, `attributesToSearchOn`, `languages`, `synonyms`, `geoSortMaxDistance`, `geoSortBucketWidth`, `rankingScoreThreshold`, `createSession`, `sessionToken`",
+      "code": "invalid_api_key_search_parameters",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_api_key_search_parameters"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_key_principals() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let index = server.index("docs");
+    let documents = json!([
+        { "id": 1, "title": "public handbook" },
+        { "id": 2, "title": "sales handbook", "_acl": ["group:sales"] },
+        { "id": 3, "title": "legal handbook", "_acl": ["group:legal", "alice"] },
+        { "id": 4, "title": "secret handbook", "_acl": [] },
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let content = json!({
+        "indexes": ["docs"],
+        "actions": ["search"],
+        "expiresAt": null,
+        "principals": ["alice", "group:sales"],
+    });
+    let (response, code) = server.add_api_key(content).await;
+    meili_snap::snapshot!(code, @"201 Created");
+    meili_snap::snapshot!(meili_snap::json_string!(response["principals"]), @r###"
+    [
+      "alice",
+      "group:sales"
+    ]
+    "###);
+    let key = response["key"].as_str().unwrap().to_string();
+    let uid = response["uid"].as_str().unwrap().to_string();
+
+    // the `_acl` field isn't searchable, and the documents without it are public
+    server.use_api_key(&key);
+    let index = server.index("docs");
+    let (response, code) =
+        index.search_post(json!({ "q": "handbook", "attributesToRetrieve": ["id"] })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 3
+      }
+    ]
+    "###);
+    let (response, code) = index.search_post(json!({ "q": "group" })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @"[]");
+
+    // the principals of a key can't be updated
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.patch_api_key(&uid, json!({ "principals": ["bob"] })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(response["code"], @r###""immutable_api_key_principals""###);
+
+    // the master key sees every document
+    let index = server.index("docs");
+    let (response, code) = index.search_post(json!({ "q": "handbook" })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(response["estimatedTotalHits"], @"4");
+}
@@ -14,11 +14,22 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
     Lazy::new(|| {
         let authorizations = hashmap! {
             ("POST",    "/multi-search") =>                                    hashset!{"search", "*"},
+            ("POST",    "/partitions/logs/search") =>                          hashset!{"search", "*"},
+            ("POST",    "/partitions/logs/documents") =>                       hashset!{"documents.add", "documents.*", "*"},
             ("POST",    "/indexes/products/search") =>                         hashset!{"search", "*"},
             ("GET",     "/indexes/products/search") =>                         hashset!{"search", "*"},
+            ("POST",    "/indexes/products/search/jobs") =>                    hashset!{"search", "*"},
+            ("GET",     "/indexes/products/search/jobs/0") =>                  hashset!{"search", "*"},
+            ("GET",     "/indexes/products/facets/color/values") =>            hashset!{"search", "*"},
             ("POST",    "/indexes/products/documents") =>                      hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/indexes/products/documents") =>                      hashset!{"documents.get", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/fetch") =>                hashset!{"documents.get", "documents.*", "*"},
+            ("POST",    "/indexes/products/documents/uploads") =>              hashset!{"documents.add", "documents.*", "*"},
+            ("GET",     "/indexes/products/documents/uploads/0") =>            hashset!{"documents.add", "documents.*", "*"},
+            ("PATCH",   "/indexes/products/documents/uploads/0") =>            hashset!{"documents.add", "documents.*", "*"},
+            ("DELETE",  "/indexes/products/documents/uploads/0") =>            hashset!{"documents.add", "documents.*", "*"},
+            ("POST",    "/indexes/products/documents/uploads/0/complete") =>   hashset!{"documents.add", "documents.*", "*"},
+            ("PUT",     "/indexes/products/documents/uploads/0/complete") =>   hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/indexes/products/documents/0") =>                    hashset!{"documents.get", "documents.*", "*"},
             ("DELETE",  "/indexes/products/documents/0") =>                    hashset!{"documents.delete", "documents.*", "*"},
             ("POST",    "/indexes/products/documents/delete-batch") =>         hashset!{"documents.delete", "documents.*", "*"},
@@ -27,7 +38,10 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("DELETE",  "/tasks") =>                                           hashset!{"tasks.delete", "tasks.*", "*"},
             ("GET",     "/tasks?indexUid=products") =>                         hashset!{"tasks.get", "tasks.*", "*"},
             ("GET",     "/tasks/0") =>                                         hashset!{"tasks.get", "tasks.*", "*"},
+            ("GET",     "/tasks/stats") =>                                     hashset!{"tasks.get", "tasks.*", "*"},
+            ("POST",    "/tasks/0/replay") =>                                  hashset!{"documents.add", "documents.*", "*"},
             ("PATCH",   "/indexes/products/") =>                               hashset!{"indexes.update", "indexes.*", "*"},
+            ("POST",    "/indexes/products/rebuild") =>                        hashset!{"indexes.update", "indexes.*", "*"},
             ("GET",     "/indexes/products/") =>                               hashset!{"indexes.get", "indexes.*", "*"},
             ("DELETE",  "/indexes/products/") =>                               hashset!{"indexes.delete", "indexes.*", "*"},
             ("POST",    "/indexes") =>                                         hashset!{"indexes.create", "indexes.*", "*"},
@@ -54,9 +68,12 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("PUT",     "/indexes/products/settings/stop-words") =>            hashset!{"settings.update", "settings.*", "*"},
             ("PUT",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.update", "settings.*", "*"},
             ("GET",     "/indexes/products/stats") =>                          hashset!{"stats.get", "stats.*", "*"},
+            ("GET",     "/indexes/products/activity") =>                       hashset!{"stats.get", "stats.*", "*"},
+            ("GET",     "/indexes/products/schema") =>                         hashset!{"stats.get", "stats.*", "*"},
             ("GET",     "/stats") =>                                           hashset!{"stats.get", "stats.*", "*"},
             ("POST",    "/dumps") =>                                           hashset!{"dumps.create", "dumps.*", "*"},
             ("POST",    "/snapshots") =>                                       hashset!{"snapshots.create", "snapshots.*", "*"},
+            ("POST",    "/snapshots/compare") =>                               hashset!{"snapshots.create", "snapshots.*", "*"},
             ("GET",     "/version") =>                                         hashset!{"version", "*"},
             ("GET",     "/metrics") =>                                         hashset!{"metrics.get", "metrics.*", "*"},
             ("POST",    "/logs/stream") =>                                     hashset!{"metrics.get", "metrics.*", "*"},
@@ -1038,3 +1055,106 @@ async fn error_creating_index_without_index() {
     let (response, code) = index.create(None).await;
     assert_eq!(403, code, "{:?}", &response);
 }
+
+#[actix_rt::test]
+async fn pii_attributes_only_returned_to_allowed_keys() {
+    let dir = TempDir::new().unwrap();
+    let mut options = default_settings(dir.path());
+    options.indexer_options.pii_encryption_key = Some("kefir".to_string());
+    let mut server = Server::new_auth_with_options(options, dir).await;
+    server.use_api_key(MASTER_KEY);
+
+    let index = server.index("products");
+    let (task, _) = index.update_settings(json!({ "piiAttributes": ["email"] })).await;
+    index.wait_task(task.uid()).await;
+    let documents = json!([{ "id": 1, "name": "kefir", "email": "kefir@meilisearch.com" }]);
+    let (task, _) = index.add_documents(documents, None).await;
+    let response = index.wait_task(task.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+
+    for (actions, email) in [
+        (json!(["search", "documents.get"]), json!(null)),
+        (json!(["search", "documents.get", "documents.pii"]), json!("kefir@meilisearch.com")),
+    ] {
+        server.use_api_key(MASTER_KEY);
+        let content = json!({ "indexes": ["products"], "actions": actions, "expiresAt": null });
+        let (response, code) = server.add_api_key(content).await;
+        assert_eq!(201, code, "{:?}", &response);
+        server.use_api_key(response["key"].as_str().unwrap());
+
+        let index = server.index("products");
+        let (response, code) = index.search_post(json!({ "q": "kefir" })).await;
+        assert_eq!(200, code, "{:?}", &response);
+        assert_eq!(response["hits"][0]["name"], json!("kefir"));
+        assert_eq!(response["hits"][0]["email"], email);
+
+        let (response, code) = index.get_document(1, None).await;
+        assert_eq!(200, code, "{:?}", &response);
+        assert_eq!(response["email"], email);
+    }
+}
+
+#[actix_rt::test]
+async fn federated_queries_only_allowed_to_pii_keys() {
+    let dir = TempDir::new().unwrap();
+    let mut options = default_settings(dir.path());
+    options.experimental_federation_upstreams =
+        Some(r#"[{ "url": "http://127.0.0.1:1", "indexes": ["remote-*"] }]"#.parse().unwrap());
+    let mut server = Server::new_auth_with_options(options, dir).await;
+
+    for (actions, expected_code, error_code) in [
+        (json!(["search"]), 403, "federation_pii_not_allowed"),
+        (json!(["search", "documents.pii"]), 502, "federation_upstream_error"),
+    ] {
+        server.use_api_key(MASTER_KEY);
+        let content = json!({ "indexes": ["*"], "actions": actions, "expiresAt": null });
+        let (response, code) = server.add_api_key(content).await;
+        assert_eq!(201, code, "{:?}", &response);
+        server.use_api_key(response["key"].as_str().unwrap());
+
+        let (response, code) = server
+            .multi_search(json!({ "queries": [{ "indexUid": "remote-movies", "q": "glass" }] }))
+            .await;
+        assert_eq!(expected_code, code, "{:?}", &response);
+        assert_eq!(response["code"], error_code);
+    }
+}
+
+#[actix_rt::test]
+async fn search_jobs_only_retrieved_by_their_key() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key(MASTER_KEY);
+
+    let index = server.index("products");
+    let (task, _) = index.add_documents(json!([{ "id": 1, "name": "kefir" }]), None).await;
+    let response = index.wait_task(task.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+
+    let mut keys = Vec::new();
+    for _ in 0..2 {
+        server.use_api_key(MASTER_KEY);
+        let content = json!({ "indexes": ["products"], "actions": ["search"], "expiresAt": null });
+        let (response, code) = server.add_api_key(content).await;
+        assert_eq!(201, code, "{:?}", &response);
+        keys.push(response["key"].as_str().unwrap().to_string());
+    }
+
+    server.use_api_key(&keys[0]);
+    let (job, code) = server.index("products").create_search_job(json!({ "q": "kefir" })).await;
+    assert_eq!(202, code, "{:?}", &job);
+    let job_uid = job["uid"].as_str().unwrap();
+
+    server.use_api_key(&keys[1]);
+    let (response, code) = server.index("products").search_job(job_uid).await;
+    assert_eq!(404, code, "{:?}", &response);
+    assert_eq!(response["code"], "search_job_not_found");
+
+    server.use_api_key(MASTER_KEY);
+    let (response, code) = server.index("products").search_job(job_uid).await;
+    assert_eq!(404, code, "{:?}", &response);
+
+    server.use_api_key(&keys[0]);
+    let job = server.index("products").wait_search_job(job_uid).await;
+    assert_eq!(job["status"], "succeeded", "{job}");
+    assert_eq!(job["result"]["hits"][0]["name"], json!("kefir"));
+}
@@ -596,3 +596,127 @@ async fn error_access_modified_token() {
     assert_eq!(response, INVALID_RESPONSE.clone());
     assert_eq!(code, 403);
 }
+
+#[actix_rt::test]
+async fn search_with_tenant_token_principals() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let index = server.index("docs");
+    let documents = json!([
+        { "id": 1, "title": "public handbook" },
+        { "id": 2, "title": "sales handbook", "_acl": ["group:sales"] },
+        { "id": 3, "title": "legal handbook", "_acl": ["group:legal"] },
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let content = json!({
+        "indexes": ["docs"],
+        "actions": ["search"],
+        "expiresAt": null,
+        "principals": ["group:sales", "group:legal"],
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(code, 201);
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+
+    // a tenant token can only restrict the principals of its parent key
+    let tenant_token = hashmap! {
+        "searchRules" => json!(["docs"]),
+        "principals" => json!(["group:sales", "group:marketing"]),
+    };
+    let web_token = generate_tenant_token(uid, key, tenant_token);
+    server.use_api_key(&web_token);
+    let index = server.index("docs");
+    let (response, code) = index.search_post(json!({ "attributesToRetrieve": ["id"] })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["hits"], json!([{ "id": 1 }, { "id": 2 }]));
+}
+
+#[actix_rt::test]
+async fn get_documents_with_principals() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let index = server.index("docs");
+    let documents = json!([
+        { "id": 1, "title": "public handbook" },
+        { "id": 2, "title": "sales handbook", "_acl": ["group:sales"] },
+        { "id": 3, "title": "legal handbook", "_acl": ["group:legal"] },
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+
+    let content = json!({
+        "indexes": ["docs"],
+        "actions": ["documents.get"],
+        "expiresAt": null,
+        "principals": ["group:sales"],
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(code, 201);
+    server.use_api_key(response["key"].as_str().unwrap());
+
+    let index = server.index("docs");
+    let (response, code) = index.get_document_by_filter(json!({ "fields": ["id"] })).await;
+    assert_eq!(code, 200, "{response}");
+    assert_eq!(response["results"], json!([{ "id": 1 }, { "id": 2 }]));
+    assert_eq!(response["total"], json!(2));
+
+    let (response, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200, "{response}");
+    let (response, code) = index.get_document(3, None).await;
+    assert_eq!(code, 404, "{response}");
+    assert_eq!(response["code"], json!("document_not_found"));
+}
+
+#[actix_rt::test]
+async fn search_jobs_only_retrieved_by_their_tenant_token() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let index = server.index("products");
+    let (task, _) = index.add_documents(json!([{ "id": 1, "name": "kefir" }]), None).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = index.update_settings(json!({ "filterableAttributes": ["name"] })).await;
+    index.wait_task(task.uid()).await;
+
+    let content = json!({ "indexes": ["products"], "actions": ["search"], "expiresAt": null });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(code, 201);
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+
+    let tenant_tokens: Vec<_> = ["name = kefir", "name = milk"]
+        .into_iter()
+        .map(|filter| {
+            let tenant_token = hashmap! {
+                "searchRules" => json!({ "products": { "filter": filter } }),
+            };
+            generate_tenant_token(uid, key, tenant_token)
+        })
+        .collect();
+
+    server.use_api_key(&tenant_tokens[0]);
+    let (job, code) = server.index("products").create_search_job(json!({ "q": "kefir" })).await;
+    assert_eq!(code, 202, "{job}");
+    let job_uid = job["uid"].as_str().unwrap();
+
+    // a tenant token of the same key can't see the job
+    server.use_api_key(&tenant_tokens[1]);
+    let (response, code) = server.index("products").search_job(job_uid).await;
+    assert_eq!(code, 404, "{response}");
+    assert_eq!(response["code"], "search_job_not_found");
+
+    // nor the key itself
+    server.use_api_key(key);
+    let (response, code) = server.index("products").search_job(job_uid).await;
+    assert_eq!(code, 404, "{response}");
+
+    server.use_api_key(&tenant_tokens[0]);
+    let job = server.index("products").wait_search_job(job_uid).await;
+    assert_eq!(job["status"], "succeeded", "{job}");
+    assert_eq!(job["result"]["hits"][0]["name"], json!("kefir"));
+}
@@ -78,6 +78,11 @@ impl Index<'_> {
         self.service.patch_encoded(url, body, self.encoder).await
     }
 
+    pub async fn rebuild(&self, body: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/rebuild", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, body, self.encoder).await
+    }
+
     pub async fn delete(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}", urlencode(self.uid.as_ref()));
         self.service.delete(url).await
@@ -195,6 +200,11 @@ impl Index<'_> {
         self.service.post(url, payload).await
     }
 
+    pub async fn hydrate_documents(&self, payload: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/hydrate", urlencode(self.uid.as_ref()));
+        self.service.post(url, payload).await
+    }
+
     pub async fn get_all_documents_raw(&self, options: &str) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/documents{}", urlencode(self.uid.as_ref()), options);
         self.service.get(url).await
@@ -242,6 +252,27 @@ impl Index<'_> {
         self.service.get(url).await
     }
 
+    pub async fn effective_settings(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/settings/effective", urlencode(self.uid.as_ref()));
+        self.service.get(url).await
+    }
+
+    pub async fn copy_settings_to(
+        &self,
+        other_uid: &str,
+        settings: Option<&str>,
+    ) -> (Value, StatusCode) {
+        let mut url = format!(
+            "/indexes/{}/settings/copy-to/{}",
+            urlencode(self.uid.as_ref()),
+            urlencode(other_uid)
+        );
+        if let Some(settings) = settings {
+            url.push_str(&format!("?settings={settings}"));
+        }
+        self.service.post(url, json!(null)).await
+    }
+
     pub async fn update_settings(&self, settings: Value) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/settings", urlencode(self.uid.as_ref()));
         self.service.patch_encoded(url, settings, self.encoder).await
@@ -332,6 +363,47 @@ impl Index<'_> {
         self.service.get(url).await
     }
 
+    pub async fn activity(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/activity", urlencode(self.uid.as_ref()));
+        self.service.get(url).await
+    }
+
+    pub async fn fields_stats(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/stats/fields", urlencode(self.uid.as_ref()));
+        self.service.get(url).await
+    }
+
+    pub async fn create_search_job(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/search/jobs", urlencode(self.uid.as_ref()));
+        self.service.post(url, query).await
+    }
+
+    /// Waits for the search job to be finished and returns it.
+    pub async fn wait_search_job(&self, job_uid: &str) -> Value {
+        let url = format!("/indexes/{}/search/jobs/{}", urlencode(self.uid.as_ref()), job_uid);
+        for _ in 0..100 {
+            let (response, status_code) = self.service.get(&url).await;
+            assert_eq!(200, status_code, "response: {}", response);
+
+            if response["status"] == "succeeded" || response["status"] == "failed" {
+                return response;
+            }
+
+            sleep(Duration::from_millis(100)).await;
+        }
+        panic!("Timeout waiting for search job {job_uid}")
+    }
+
+    pub async fn search_job(&self, job_uid: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/search/jobs/{}", urlencode(self.uid.as_ref()), job_uid);
+        self.service.get(url).await
+    }
+
+    pub async fn schema(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/schema", urlencode(self.uid.as_ref()));
+        self.service.get(url).await
+    }
+
     /// Performs both GET and POST search queries
     pub async fn search(
         &self,
@@ -411,6 +483,12 @@ impl Index<'_> {
         self.service.post_encoded(url, query, self.encoder).await
     }
 
+    pub async fn facet_values(&self, field: &str, query: &str) -> (Value, StatusCode) {
+        let url =
+            format!("/indexes/{}/facets/{}/values{}", urlencode(self.uid.as_ref()), field, query);
+        self.service.get(url).await
+    }
+
     pub async fn update_distinct_attribute(&self, value: Value) -> (Value, StatusCode) {
         let url =
             format!("/indexes/{}/settings/{}", urlencode(self.uid.as_ref()), "distinct-attribute");
@@ -165,6 +165,10 @@ impl Server {
         self.service.get(format!("/tasks?{}", filter)).await
     }
 
+    pub async fn tasks_stats(&self) -> (Value, StatusCode) {
+        self.service.get("/tasks/stats").await
+    }
+
     pub async fn get_dump_status(&self, uid: &str) -> (Value, StatusCode) {
         self.service.get(format!("/dumps/{}/status", uid)).await
     }
@@ -177,6 +181,10 @@ impl Server {
         self.service.post("/snapshots", json!(null)).await
     }
 
+    pub async fn compare_snapshots(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/snapshots/compare", value).await
+    }
+
     pub async fn index_swap(&self, value: Value) -> (Value, StatusCode) {
         self.service.post("/swap-indexes", value).await
     }
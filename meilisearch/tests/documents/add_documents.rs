@@ -505,6 +505,110 @@ async fn add_csv_document_with_types_error() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_html_document() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let document = r#"<html>
+  <head><title>Kefir</title><script>alert("woof")</script></head>
+  <body><h1>The good doggo</h1><p>Kefir&nbsp;is a <b>good</b> boy</p></body>
+</html>"#;
+
+    let (response, code) =
+        index.raw_update_documents(document, Some("text/html"), "?documentId=1").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": "1",
+      "content": "Kefir The good doggo Kefir is a good boy"
+    }
+    "###);
+
+    let (response, code) = index.raw_update_documents(document, Some("text/html"), "").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "A `documentId` is missing. It is required to add the document of a html payload.",
+      "code": "missing_document_id",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_document_id"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_documents_with_content_field() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    // `<h1>Kefir</h1>` encoded in base64
+    let documents = json!([
+        { "id": 1, "file": "PGgxPktlZmlyPC9oMT4=" },
+        { "id": 2, "text": "Intel" },
+    ]);
+    let (response, code) = index
+        .raw_update_documents(
+            &documents.to_string(),
+            Some("application/json"),
+            "?contentField=file&textField=text",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (response, _) = index.get_document(1, None).await;
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "text": "Kefir"
+    }
+    "###);
+    let (response, _) = index.get_document(2, None).await;
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 2,
+      "text": "Intel"
+    }
+    "###);
+
+    let documents = json!([{ "id": 3, "file": 42 }]);
+    let (response, code) = index
+        .raw_update_documents(
+            &documents.to_string(),
+            Some("application/json"),
+            "?contentField=file",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `json` payload provided is malformed: `the `file` field must be a string containing base64 encoded content, found `42``.",
+      "code": "malformed_payload",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#malformed_payload"
+    }
+    "###);
+
+    let (response, code) =
+        index.raw_update_documents("id\n1", Some("text/csv"), "?contentField=file").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The Content-Type `text/csv` does not support the use of a `contentField`. The `contentField` can only be used with the Content-Type `application/json` or `application/x-ndjson`.",
+      "code": "invalid_document_content_field",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_content_field"
+    }
+    "###);
+}
+
 /// any other content-type is must be refused
 #[actix_rt::test]
 async fn error_add_documents_test_bad_content_types() {
@@ -532,7 +636,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/pdf`, `text/html`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -553,7 +657,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/pdf`, `text/html`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -587,7 +691,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/pdf`, `text/html`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -607,7 +711,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/pdf`, `text/html`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -1210,6 +1314,112 @@ async fn replace_document() {
     "###);
 }
 
+#[actix_rt::test]
+async fn skip_outdated_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _code) = index.update_settings(json!({ "versionAttribute": "version" })).await;
+    index.wait_task(response.uid()).await;
+
+    let documents = json!([
+        { "id": 1, "content": "new", "version": 2 },
+        { "id": 2, "content": "new", "version": 2 },
+    ]);
+    let (response, _code) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await;
+
+    let documents = json!([
+        { "id": 1, "content": "old", "version": 1 },
+        { "id": 2, "content": "newer", "version": 3 },
+    ]);
+    let (response, _code) = index.add_documents(documents, None).await;
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 2,
+      "indexedDocuments": 1,
+      "skippedDocuments": 1
+    }
+    "###);
+
+    let (response, code) = index.get_all_documents(Default::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "content": "new",
+        "version": 2
+      },
+      {
+        "id": 2,
+        "content": "newer",
+        "version": 3
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_documents_with_write_mode() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _code) =
+        index.add_documents(json!([{ "id": 1, "content": "kefir" }]), None).await;
+    index.wait_task(response.uid()).await;
+
+    let headers = vec![("Content-Type", "application/json")];
+    let documents = r#"[{ "id": 1, "content": "intel" }, { "id": 2, "content": "bob" }]"#;
+    let (response, code) =
+        index.raw_add_documents(documents, headers.clone(), "?writeMode=failIfExists").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 2,
+      "indexedDocuments": 1,
+      "rejectedDocuments": 1,
+      "rejectedDocumentIds": [
+        "1"
+      ]
+    }
+    "###);
+
+    let documents = r#"[{ "id": 2, "content": "jean" }, { "id": 3, "content": "michel" }]"#;
+    let (response, code) =
+        index.raw_add_documents(documents, headers, "?writeMode=failIfMissing").await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 2,
+      "indexedDocuments": 1,
+      "rejectedDocuments": 1,
+      "rejectedDocumentIds": [
+        "3"
+      ]
+    }
+    "###);
+
+    let (response, code) = index.get_all_documents(Default::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "content": "kefir"
+      },
+      {
+        "id": 2,
+        "content": "jean"
+      }
+    ]
+    "###);
+}
+
 #[actix_rt::test]
 async fn add_no_documents() {
     let server = Server::new().await;
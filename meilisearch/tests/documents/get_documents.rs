@@ -1,4 +1,4 @@
-use actix_web::http::header::ACCEPT_ENCODING;
+use actix_web::http::header::{ACCEPT_ENCODING, CONTENT_TYPE};
 use actix_web::test;
 use meili_snap::*;
 use urlencoding::encode as urlencode;
@@ -536,7 +536,8 @@ async fn get_document_with_vectors() {
       "vectorStore": true,
       "metrics": false,
       "logsRoute": false,
-      "editDocumentsByFunction": false
+      "editDocumentsByFunction": false,
+      "typoStatistics": false
     }
     "###);
 
@@ -729,3 +730,257 @@ async fn get_document_with_vectors() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn get_documents_by_ids() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index.update_settings_filterable_attributes(json!(["color"])).await;
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "kefir", "color": "red" },
+                { "id": 1, "name": "echo", "color": "blue" },
+                { "id": 2, "name": "intel", "color": "blue" },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(1).await;
+
+    // the documents are returned in the requested order and the unknown ids are ignored
+    let (response, code) =
+        index.get_document_by_filter(json!({ "ids": [2, "0", 42], "fields": ["name"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "name": "intel"
+        },
+        {
+          "name": "kefir"
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 2
+    }
+    "###);
+
+    // the ids are paginated and filtered
+    let (response, code) = index
+        .get_document_by_filter(
+            json!({ "ids": [2, 0, 1], "filter": "color = blue", "offset": 1, "limit": 1 }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 1,
+          "name": "echo",
+          "color": "blue"
+        }
+      ],
+      "offset": 1,
+      "limit": 1,
+      "total": 2
+    }
+    "###);
+
+    let (response, code) = index.get_document_by_filter(json!({ "ids": 2 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.ids`: expected an array, but found a positive integer: `2`",
+      "code": "invalid_document_id",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_id"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn hydrate_documents() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "kefir", "color": "red" },
+                { "id": 1, "name": "echo", "color": "blue" },
+                { "id": 2, "name": "intel", "color": "blue" },
+            ]),
+            Some("id"),
+        )
+        .await;
+    index.wait_task(0).await;
+
+    // the documents are returned in the requested order and the unknown ids are ignored
+    let (response, code) = index.hydrate_documents(json!({ "ids": [2, "0", 42] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 2,
+          "name": "intel",
+          "color": "blue"
+        },
+        {
+          "id": 0,
+          "name": "kefir",
+          "color": "red"
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) =
+        index.hydrate_documents(json!({ "ids": [1, 0], "fields": ["name"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "name": "echo"
+        },
+        {
+          "name": "kefir"
+        }
+      ]
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn get_document_attachment() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    // `kefir` encoded in base64
+    let (task, _code) = index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "kefir", "_attachments": { "kefir.txt": "a2VmaXI=" } },
+                { "id": 1, "name": "echo" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(task.uid()).await;
+
+    // the attachments are not part of the document
+    let (response, code) = index.get_document(0, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 0,
+      "name": "kefir"
+    }
+    "###);
+
+    let app = server.init_web_app().await;
+    let req = test::TestRequest::get()
+        .uri("/indexes/test/documents/0/attachments/kefir.txt")
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    snapshot!(res.status(), @"200 OK");
+    snapshot!(res.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap(), @"text/plain");
+    let bytes = test::read_body(res).await;
+    snapshot!(String::from_utf8_lossy(&bytes), @"kefir");
+
+    let (response, code) =
+        server.service.get("/indexes/test/documents/1/attachments/kefir.txt").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attachment `kefir.txt` of the document `1` not found.",
+      "code": "attachment_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#attachment_not_found"
+    }
+    "###);
+
+    // `kefir ` encoded in base64, the attachment is too big
+    let big = "a2VmaXIg".repeat(20_000);
+    let (task, _code) =
+        index.add_documents(json!([{ "id": 2, "_attachments": { "kefir.txt": big } }]), None).await;
+    let response = index.wait_task(task.uid()).await;
+    snapshot!(response["error"], @r###"
+    {
+      "message": "Invalid `_attachments` field in the document with id: `2`. The attachment `kefir.txt` weighs 120000 bytes, while the maximum size of an attachment is 102400 bytes.",
+      "code": "invalid_document_attachments",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_attachments"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn get_document_word_positions() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, _code) = index
+        .add_documents(
+            json!([
+                { "id": 0, "title": "Hello World", "overview": "a big world" },
+                { "id": 1, "title": "hello" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) =
+        server.service.get("/indexes/test/documents/0/word-positions?word=World").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "word": "world",
+      "attributes": [
+        "title",
+        "overview"
+      ],
+      "positions": [
+        1,
+        2
+      ]
+    }
+    "###);
+
+    let (response, code) =
+        server.service.get("/indexes/test/documents/1/word-positions?word=world").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "word": "world",
+      "attributes": [],
+      "positions": []
+    }
+    "###);
+
+    let (response, code) =
+        server.service.get("/indexes/test/documents/2/word-positions?word=world").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Document `2` not found.",
+      "code": "document_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#document_not_found"
+    }
+    "###);
+
+    let (response, code) = server.service.get("/indexes/test/documents/0/word-positions").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing parameter `word`",
+      "code": "invalid_document_word",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_word"
+    }
+    "###);
+}
@@ -3,3 +3,4 @@ mod delete_documents;
 mod errors;
 mod get_documents;
 mod update_documents;
+mod upload_documents;
@@ -0,0 +1,82 @@
+use actix_web::test;
+use meili_snap::{json_string, snapshot};
+
+use crate::common::encoder::Encoder;
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn resumable_document_upload() {
+    let server = Server::new().await;
+    let index = server.index("dog");
+
+    let (upload, code) = server.service.post("/indexes/dog/documents/uploads", json!(null)).await;
+    snapshot!(code, @"201 Created");
+    snapshot!(json_string!(upload, { ".uid" => "[uuid]", ".createdAt" => "[date]", ".updatedAt" => "[date]" }), @r###"
+    {
+      "uid": "[uuid]",
+      "indexUid": "dog",
+      "offset": 0,
+      "createdAt": "[date]",
+      "updatedAt": "[date]"
+    }
+    "###);
+    let url = format!("/indexes/dog/documents/uploads/{}", upload["uid"].as_str().unwrap());
+
+    let first_part = "{ \"id\": 1, \"name\": \"kefir\" }\n";
+    let req = test::TestRequest::patch().uri(&format!("{url}?offset=0")).set_payload(first_part);
+    let (upload, code) = server.service.request(req).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(upload["offset"], @"29");
+
+    // the same part can't be sent twice
+    let req = test::TestRequest::patch().uri(&format!("{url}?offset=0")).set_payload(first_part);
+    let (response, code) = server.service.request(req).await;
+    snapshot!(code, @"409 Conflict");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The part starts at the offset 0, but the document upload is at the offset 29. Resume the upload from the offset 29.",
+      "code": "document_upload_offset_mismatch",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#document_upload_offset_mismatch"
+    }
+    "###);
+
+    // the parts can be compressed
+    let encoder = Encoder::Gzip;
+    let second_part = "{ \"id\": 2, \"name\": \"intel\" }\n";
+    let req = test::TestRequest::patch()
+        .uri(&format!("{url}?offset=29"))
+        .set_payload(encoder.encode(second_part))
+        .insert_header(encoder.header().unwrap());
+    let (upload, code) = server.service.request(req).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(upload["offset"], @"58");
+
+    let req = test::TestRequest::post()
+        .uri(&format!("{url}/complete?primaryKey=id"))
+        .insert_header(("content-type", "application/x-ndjson"));
+    let (task, code) = server.service.request(req).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (documents, _) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "name": "kefir"
+      },
+      {
+        "id": 2,
+        "name": "intel"
+      }
+    ]
+    "###);
+
+    // the completed uploads are removed
+    let (response, code) = server.service.get(&url).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""document_upload_not_found""###);
+}
@@ -46,6 +46,7 @@ async fn import_dump_v1_movie_raw() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "typo",
         "words",
@@ -57,8 +58,10 @@ async fn import_dump_v1_movie_raw() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -78,7 +81,49 @@ async fn import_dump_v1_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -205,6 +250,7 @@ async fn import_dump_v1_movie_with_settings() {
       "sortableAttributes": [
         "genres"
       ],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "typo",
         "words",
@@ -219,8 +265,10 @@ async fn import_dump_v1_movie_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -240,7 +288,49 @@ async fn import_dump_v1_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -354,6 +444,7 @@ async fn import_dump_v1_rubygems_with_settings() {
       "sortableAttributes": [
         "version"
       ],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "typo",
         "words",
@@ -367,8 +458,10 @@ async fn import_dump_v1_rubygems_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -388,7 +481,49 @@ async fn import_dump_v1_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -490,6 +625,7 @@ async fn import_dump_v2_movie_raw() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "words",
         "typo",
@@ -501,8 +637,10 @@ async fn import_dump_v2_movie_raw() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -522,7 +660,49 @@ async fn import_dump_v2_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -633,6 +813,7 @@ async fn import_dump_v2_movie_with_settings() {
         "genres"
       ],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "words",
         "typo",
@@ -647,8 +828,10 @@ async fn import_dump_v2_movie_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -668,7 +851,49 @@ async fn import_dump_v2_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -779,6 +1004,7 @@ async fn import_dump_v2_rubygems_with_settings() {
         "version"
       ],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "typo",
         "words",
@@ -792,8 +1018,10 @@ async fn import_dump_v2_rubygems_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -813,7 +1041,49 @@ async fn import_dump_v2_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -915,6 +1185,7 @@ async fn import_dump_v3_movie_raw() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "words",
         "typo",
@@ -926,8 +1197,10 @@ async fn import_dump_v3_movie_raw() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -947,7 +1220,49 @@ async fn import_dump_v3_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -1058,6 +1373,7 @@ async fn import_dump_v3_movie_with_settings() {
         "genres"
       ],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "words",
         "typo",
@@ -1072,8 +1388,10 @@ async fn import_dump_v3_movie_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -1093,7 +1411,49 @@ async fn import_dump_v3_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -1204,6 +1564,7 @@ async fn import_dump_v3_rubygems_with_settings() {
         "version"
       ],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "typo",
         "words",
@@ -1217,8 +1578,10 @@ async fn import_dump_v3_rubygems_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -1238,7 +1601,49 @@ async fn import_dump_v3_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -1340,6 +1745,7 @@ async fn import_dump_v4_movie_raw() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "words",
         "typo",
@@ -1351,8 +1757,10 @@ async fn import_dump_v4_movie_raw() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -1372,7 +1780,49 @@ async fn import_dump_v4_movie_raw() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -1483,6 +1933,7 @@ async fn import_dump_v4_movie_with_settings() {
         "genres"
       ],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "words",
         "typo",
@@ -1497,8 +1948,10 @@ async fn import_dump_v4_movie_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -1518,7 +1971,49 @@ async fn import_dump_v4_movie_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -1629,6 +2124,7 @@ async fn import_dump_v4_rubygems_with_settings() {
         "version"
       ],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "typo",
         "words",
@@ -1642,8 +2138,10 @@ async fn import_dump_v4_rubygems_with_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -1663,7 +2161,49 @@ async fn import_dump_v4_rubygems_with_settings() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###
     );
@@ -1860,7 +2400,8 @@ async fn import_dump_v6_containing_experimental_features() {
       "vectorStore": false,
       "metrics": false,
       "logsRoute": false,
-      "editDocumentsByFunction": false
+      "editDocumentsByFunction": false,
+      "typoStatistics": false
     }
     "###);
 
@@ -1878,6 +2419,7 @@ async fn import_dump_v6_containing_experimental_features() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "words",
         "typo",
@@ -1887,8 +2429,10 @@ async fn import_dump_v6_containing_experimental_features() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byAttribute",
       "typoTolerance": {
         "enabled": true,
@@ -1908,7 +2452,49 @@ async fn import_dump_v6_containing_experimental_features() {
       "pagination": {
         "maxTotalHits": 1000
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###);
 
@@ -1954,7 +2540,8 @@ async fn generate_and_import_dump_containing_vectors() {
       "vectorStore": true,
       "metrics": false,
       "logsRoute": false,
-      "editDocumentsByFunction": false
+      "editDocumentsByFunction": false,
+      "typoStatistics": false
     }
     "###);
     let index = server.index("pets");
@@ -2025,7 +2612,8 @@ async fn generate_and_import_dump_containing_vectors() {
       "vectorStore": true,
       "metrics": false,
       "logsRoute": false,
-      "editDocumentsByFunction": false
+      "editDocumentsByFunction": false,
+      "typoStatistics": false
     }
     "###);
 
@@ -2043,6 +2631,7 @@ async fn generate_and_import_dump_containing_vectors() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "words",
         "typo",
@@ -2055,8 +2644,10 @@ async fn generate_and_import_dump_containing_vectors() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -2084,7 +2675,49 @@ async fn generate_and_import_dump_containing_vectors() {
           "documentTemplate": "{{doc.doggo}}"
         }
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###);
 
@@ -2148,3 +2781,59 @@ async fn generate_and_import_dump_containing_vectors() {
         })
         .await;
 }
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn generate_and_import_anonymized_dump() {
+    let temp = tempfile::tempdir().unwrap();
+    let mut opt = default_settings(temp.path());
+    let server = Server::new_with_options(opt.clone()).await.unwrap();
+    let index = server.index("people");
+    let (response, _code) = index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "Kefir Dog", "email": "kefir@meilisearch.com", "bio": "a good dog" },
+                { "id": 1, "name": "Intel Kefir", "email": "intel@meilisearch.com", "bio": "a cat" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = server
+        .service
+        .post("/dumps?anonymizedAttributes=name,email&anonymization=hash", json!(null))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    let dump_name = format!("{}.dump", response["details"]["dumpUid"].as_str().unwrap());
+
+    let (response, code) = server.service.post("/dumps?anonymization=scramble", json!(null)).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_dump_anonymization""###);
+
+    drop(server);
+    tokio::fs::remove_dir_all(&opt.db_path).await.unwrap();
+    opt.import_dump = Some(opt.dump_dir.join(dump_name));
+    let server = Server::new_with_options(opt).await.unwrap();
+    let index = server.index("people");
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    let documents = documents["results"].as_array().unwrap();
+    // the other attributes are kept as they are
+    assert_eq!(documents[0]["bio"], json!("a good dog"));
+    assert_eq!(documents[1]["bio"], json!("a cat"));
+
+    // every word is hashed, the same words having the same hashes
+    let name = |i: usize| -> Vec<String> {
+        documents[i]["name"].as_str().unwrap().split(' ').map(String::from).collect()
+    };
+    let (kefir_dog, intel_kefir) = (name(0), name(1));
+    assert_eq!(kefir_dog.len(), 2);
+    assert_eq!(kefir_dog[0], intel_kefir[1]);
+    assert_ne!(kefir_dog[0], "Kefir");
+    let email = documents[0]["email"].as_str().unwrap();
+    assert!(!email.contains("kefir") && email.contains('@'));
+}
@@ -2,5 +2,6 @@ mod create_index;
 mod delete_index;
 mod errors;
 mod get_index;
+mod rebuild_index;
 mod stats;
 mod update_index;
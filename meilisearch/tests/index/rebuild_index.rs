@@ -0,0 +1,88 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn rebuild_some_databases() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _) = index.update_settings(json!({ "filterableAttributes": ["genre"] })).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = index
+        .add_documents(
+            json!([
+                { "id": 1, "title": "Shazam!", "genre": "action" },
+                { "id": 2, "title": "Captain Marvel", "genre": "action" },
+                { "id": 3, "title": "Escape Room", "genre": "horror" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(task.uid()).await;
+
+    let (task, code) = index.rebuild(json!({ "databases": ["facets", "prefixes"] })).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(task.uid()).await;
+    snapshot!(json_string!(task, { ".uid" => "[uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" }), @r###"
+    {
+      "uid": "[uid]",
+      "indexUid": "test",
+      "status": "succeeded",
+      "type": "indexRebuild",
+      "canceledBy": null,
+      "details": {
+        "databases": [
+          "prefixes",
+          "facets"
+        ]
+      },
+      "error": null,
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "q": "capt", "filter": "genre = action" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0]["id"], @"2");
+}
+
+#[actix_rt::test]
+async fn rebuild_errors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.rebuild(json!({})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `databases`",
+      "code": "missing_index_rebuild_databases",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_index_rebuild_databases"
+    }
+    "###);
+
+    let (response, code) = index.rebuild(json!({ "databases": ["doggo"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.databases[0]`: expected one of `prefixes`, `facets`, `sortIndexes`, `phonetic`, `prefixCache`",
+      "code": "invalid_index_rebuild_databases",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_rebuild_databases"
+    }
+    "###);
+
+    // the index must exist
+    let (task, code) = index.rebuild(json!({ "databases": ["prefixes"] })).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""failed""###);
+    snapshot!(task["error"]["code"], @r###""index_not_found""###);
+}
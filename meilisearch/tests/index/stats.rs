@@ -1,3 +1,5 @@
+use meili_snap::{json_string, snapshot};
+
 use crate::common::Server;
 use crate::json;
 
@@ -45,6 +47,76 @@ async fn stats() {
     assert_eq!(response["fieldDistribution"]["age"], 1);
 }
 
+#[actix_rt::test]
+async fn fields_stats() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "name": "Alexey",
+        },
+        {
+            "id": 2,
+            "age": 45,
+        }
+    ]);
+
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.fields_stats().await;
+
+    assert_eq!(code, 200);
+    assert_eq!(
+        response,
+        json!({
+            "age": { "distinctTerms": 1, "termDocumentPairs": 1, "totalTokens": 1, "facetCardinality": 0, "documentsRatio": 0.5 },
+            "id": { "distinctTerms": 2, "termDocumentPairs": 2, "totalTokens": 2, "facetCardinality": 0, "documentsRatio": 1.0 },
+            "name": { "distinctTerms": 1, "termDocumentPairs": 1, "totalTokens": 1, "facetCardinality": 0, "documentsRatio": 0.5 },
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn schema() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "name": "Alexey", "tags": ["a"] },
+        { "id": 2, "name": null, "age": 45 },
+        { "id": 3, "age": "unknown" },
+        { "id": 4, "age": 45 },
+    ]);
+
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.schema().await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response,
+        json!({
+            "age": { "types": { "number": 2, "string": 1 }, "examples": [45, "unknown"], "fillRate": 0.75 },
+            "id": { "types": { "number": 4 }, "examples": [1, 2, 3], "fillRate": 1.0 },
+            "name": { "types": { "null": 1, "string": 1 }, "examples": ["Alexey", null], "fillRate": 0.5 },
+            "tags": { "types": { "array": 1 }, "examples": [["a"]], "fillRate": 0.25 },
+        })
+    );
+
+    let (response, _) = index.delete_document(1).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.schema().await;
+    assert_eq!(code, 200);
+    assert!(response.get("tags").is_none());
+    assert_eq!(response["name"]["types"], json!({ "null": 1 }));
+}
+
 #[actix_rt::test]
 async fn error_get_stats_unexisting_index() {
     let server = Server::new().await;
@@ -60,3 +132,85 @@ async fn error_get_stats_unexisting_index() {
     assert_eq!(response, expected_response);
     assert_eq!(code, 404);
 }
+
+#[actix_rt::test]
+async fn activity() {
+    let server = Server::new().await;
+    // the searches are counted for the whole process, the name of the index must be unique
+    let index = server.index("activity");
+
+    index.add_documents(json!([{ "id": 1 }, { "id": 2 }, { "id": 3 }]), Some("id")).await;
+    index.wait_task(0).await;
+    index.update_settings(json!({ "rankingRules": ["words"], "stopWords": ["the"] })).await;
+    index.wait_task(1).await;
+    index.delete_document(2).await;
+    index.wait_task(2).await;
+    index.search_post(json!({ "q": "hello" })).await;
+    index.search_get("q=hello").await;
+
+    let (response, code) = index.activity().await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["recentTasks"].as_array().unwrap().len(), 3);
+    assert_eq!(response["recentTasks"][0]["uid"], 2);
+    snapshot!(json_string!(response["writes"], { ".settingsChanges[].finishedAt" => "[date]" }), @r###"
+    {
+      "succeededTasks": 3,
+      "failedTasks": 0,
+      "indexedDocuments": 3,
+      "deletedDocuments": 1,
+      "settingsChanges": [
+        {
+          "taskUid": 1,
+          "finishedAt": "[date]",
+          "settings": [
+            "rankingRules",
+            "stopWords"
+          ]
+        }
+      ]
+    }
+    "###);
+    // the searches may not have been made during the same minute
+    assert_eq!(response["searches"]["lastHour"], 2);
+
+    let (response, code) = server.index("unknown").activity().await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+}
+
+#[actix_rt::test]
+async fn activity_typo_statistics() {
+    let server = Server::new().await;
+    // the typos are counted for the whole process, the name of the index must be unique
+    let index = server.index("activity_typo_statistics");
+
+    let documents = json!([
+        { "id": 1, "title": "black horse" },
+        { "id": 2, "title": "black horses" },
+        { "id": 3, "title": "black horsa" },
+    ]);
+    index.add_documents(documents, Some("id")).await;
+    index.wait_task(0).await;
+
+    // the typos are only counted while the feature is enabled
+    index.search_post(json!({ "q": "black horse" })).await;
+    let (response, code) = index.activity().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["typos"], @"null");
+
+    let (_, code) = server.set_features(json!({ "typoStatistics": true })).await;
+    snapshot!(code, @"200 OK");
+    index.search_post(json!({ "q": "black horse" })).await;
+
+    let (response, code) = index.activity().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["typos"]), @r###"
+    {
+      "hits": 3,
+      "zeroTypoHits": 2,
+      "oneTypoHits": 1,
+      "twoTyposHits": 0,
+      "prefixHits": 1
+    }
+    "###);
+}
@@ -6,6 +6,7 @@ mod dumps;
 mod features;
 mod index;
 mod logs;
+mod partitions;
 mod search;
 mod settings;
 mod similar;
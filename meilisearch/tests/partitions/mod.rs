@@ -0,0 +1,134 @@
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use tempfile::TempDir;
+use time::{Date, OffsetDateTime};
+
+use crate::common::{default_settings, Server};
+use crate::json;
+
+async fn server(temp: &TempDir) -> Server {
+    let partitions = r#"[{ "family": "logs", "timestamp_field": "timestamp", "retention": 2 }]"#;
+    Server::new_with_options(Opt {
+        experimental_index_partitions: Some(partitions.parse().unwrap()),
+        ..default_settings(temp.path())
+    })
+    .await
+    .unwrap()
+}
+
+fn partition(date: Date) -> String {
+    format!("logs-{:04}-{:02}", date.year(), date.month() as u8)
+}
+
+#[actix_rt::test]
+async fn route_documents_and_search_over_range() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = server(&temp).await;
+
+    // a partition older than the retention
+    let (task, _) = server.index("logs-2001-01").create(None).await;
+    server.wait_task(task.uid()).await;
+
+    let current = OffsetDateTime::now_utc().date().replace_day(1).unwrap();
+    let previous = current.previous_day().unwrap();
+    let (response, code) = server
+        .service
+        .post(
+            "/partitions/logs/documents",
+            json!([
+                { "id": 1, "timestamp": format!("{current}T10:00:00Z"), "message": "disk full on node-1" },
+                { "id": 2, "timestamp": format!("{previous}T10:00:00Z"), "message": "disk full on node-2" },
+                { "id": 3, "timestamp": previous.to_string(), "message": "node-3 rebooted" },
+                { "id": 4, "timestamp": "2001-01-15", "message": "disk full on node-4" },
+            ]),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(response["ignoredDocuments"], @"1");
+    // the additions to both partitions and the deletion of the expired one
+    let tasks = response["tasks"].as_array().unwrap();
+    assert_eq!(tasks.len(), 3);
+    for task in tasks {
+        let task = server.wait_task(task["taskUid"].as_u64().unwrap()).await;
+        assert_eq!(task["status"], "succeeded", "{task}");
+    }
+    let (_, code) = server.index("logs-2001-01").get().await;
+    snapshot!(code, @"404 Not Found");
+
+    let (response, code) =
+        server.service.post("/partitions/logs/search", json!({ "q": "disk full" })).await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["partitions"], json!([partition(current), partition(previous)]));
+    assert_eq!(response["estimatedTotalHits"], 2);
+    let mut hits: Vec<_> = response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| (hit["id"].as_u64().unwrap(), hit["_partition"].as_str().unwrap().to_string()))
+        .collect();
+    hits.sort();
+    assert_eq!(hits, vec![(1, partition(current)), (2, partition(previous))]);
+
+    // only the partitions overlapping the range are searched
+    let (response, code) = server
+        .service
+        .post(
+            "/partitions/logs/search",
+            json!({ "q": "disk full", "from": current.to_string(), "limit": 1 }),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    assert_eq!(response["partitions"], json!([partition(current)]));
+    assert_eq!(response["hits"][0]["id"], 1);
+    assert!(response["hits"][0].get("_rankingScore").is_none());
+}
+
+#[actix_rt::test]
+async fn partition_errors() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = server(&temp).await;
+
+    let (response, code) =
+        server.service.post("/partitions/movies/search", json!({ "q": "glass" })).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Partition family `movies` not found.",
+      "code": "partition_family_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#partition_family_not_found"
+    }
+    "###);
+
+    let (response, code) = server
+        .service
+        .post(
+            "/partitions/logs/documents",
+            json!([
+                { "id": 1, "timestamp": "2024-06-15" },
+                { "id": 2, "timestamp": "yesterday" },
+            ]),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The document at position 1 has no valid timestamp in its `timestamp` field. Expected an RFC 3339 date, a `YYYY-MM-DD` date, or a number of seconds since the epoch.",
+      "code": "invalid_partition_timestamp",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_partition_timestamp"
+    }
+    "###);
+
+    let (response, code) =
+        server.service.post("/partitions/logs/search", json!({ "from": "last week" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value for `from`: expected an RFC 3339 date, a `YYYY-MM-DD` date, or a number of seconds since the epoch, but found `\"last week\"`.",
+      "code": "invalid_partition_range",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_partition_range"
+    }
+    "###);
+}
@@ -389,6 +389,51 @@ async fn search_bad_facets() {
     // Can't make the `attributes_to_highlight` fail with a get search since it'll accept anything as an array of strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_synonyms() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"synonyms": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.synonyms`: expected an object, but found an array: `[\"doggo\"]`",
+      "code": "invalid_search_synonyms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_synonyms"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_geo_sort_max_distance() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"geoSortMaxDistance": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.geoSortMaxDistance`: expected a number, but found a string: `\"doggo\"`",
+      "code": "invalid_search_geo_sort_max_distance",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_sort_max_distance"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?geoSortMaxDistance=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `geoSortMaxDistance`: could not parse `doggo` as a number",
+      "code": "invalid_search_geo_sort_max_distance",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_geo_sort_max_distance"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_threshold() {
     let server = Server::new().await;
@@ -598,6 +643,34 @@ async fn search_bad_crop_marker() {
     // Can't make the `crop_marker` fail with a get search since it'll accept anything as a strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_max_highlight_fragments() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"maxHighlightFragments": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.maxHighlightFragments`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_search_max_highlight_fragments",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_max_highlight_fragments"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?maxHighlightFragments=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `maxHighlightFragments`: could not parse `doggo` as a positive integer",
+      "code": "invalid_search_max_highlight_fragments",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_max_highlight_fragments"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_matching_strategy() {
     let server = Server::new().await;
@@ -607,7 +680,7 @@ async fn search_bad_matching_strategy() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown value `doggo` at `.matchingStrategy`: expected one of `last`, `all`, `frequency`",
+      "message": "Unknown value `doggo` at `.matchingStrategy`: expected one of `last`, `all`, `frequency`, `regex`",
       "code": "invalid_search_matching_strategy",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_search_matching_strategy"
@@ -629,7 +702,7 @@ async fn search_bad_matching_strategy() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown value `doggo` for parameter `matchingStrategy`: expected one of `last`, `all`, `frequency`",
+      "message": "Unknown value `doggo` for parameter `matchingStrategy`: expected one of `last`, `all`, `frequency`, `regex`",
       "code": "invalid_search_matching_strategy",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_search_matching_strategy"
@@ -637,6 +710,34 @@ async fn search_bad_matching_strategy() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_phrase_fallback() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"phraseFallback": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.phraseFallback`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_phrase_fallback",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_phrase_fallback"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?phraseFallback=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `phraseFallback`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_phrase_fallback",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_phrase_fallback"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn filter_invalid_syntax_object() {
     let server = Server::new().await;
@@ -1066,6 +1167,31 @@ async fn sort_unset_ranking_rule() {
         .await;
 }
 
+#[actix_rt::test]
+async fn unknown_ranking_rule_plugin() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"rankingRules": ["words", "plugin:boost"]})).await;
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let expected_response = json!({
+        "message": "The `plugin:boost` ranking rule is not available, no ranking rule plugin is registered under the name `boost`.",
+        "code": "unknown_ranking_rule_plugin",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#unknown_ranking_rule_plugin"
+    });
+    index
+        .search(json!({ "q": "glass" }), |response, code| {
+            assert_eq!(response, expected_response);
+            assert_eq!(code, 400);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn search_on_unknown_field() {
     let server = Server::new().await;
@@ -1203,3 +1329,68 @@ async fn distinct_at_search_time() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn diversify_at_search_time() {
+    let server = Server::new().await;
+    let index = server.index("tamo");
+    let (task, _) = index.create(None).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index
+        .search_post(json!({"diversify": { "attribute": "doggo.truc", "maxPerValue": 2 }}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Attribute `doggo.truc` is not filterable and thus, cannot be used to diversify the results. This index does not have configured filterable attributes.",
+      "code": "invalid_search_diversify",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_diversify"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"diversify": { "attribute": "color", "maxPerValue": 0 }})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value at `.diversify.maxPerValue`: the value of `maxPerValue` is invalid, expected a positive integer.",
+      "code": "invalid_search_diversify",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_diversify"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"diversify": "color"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value type at `.diversify`: expected an object, but found a string: `\"color\"`",
+      "code": "invalid_search_diversify",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_diversify"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn blocked_term_in_query() {
+    let server = Server::new().await;
+    let index = server.index("tamo");
+    let (task, _) = index
+        .update_settings(json!({"blockedTerms": { "terms": ["doggo"], "mode": "reject" }}))
+        .await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index.search_post(json!({"q": "Doggo pictures"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "The query contains the blocked term `doggo`.",
+      "code": "blocked_search_term",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#blocked_search_term"
+    }
+    "###);
+}
@@ -200,3 +200,85 @@ async fn simple_facet_search_with_sort_by_count() {
     assert_eq!(hits[0], json!({ "value": "Action", "count": 3 }));
     assert_eq!(hits[1], json!({ "value": "Adventure", "count": 2 }));
 }
+
+#[actix_rt::test]
+async fn facet_values_are_paginated_with_a_cursor() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["genres"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.facet_values("genres", "?limit=2").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r###"
+    {
+      "results": [
+        {
+          "value": "Action",
+          "count": 3
+        },
+        {
+          "value": "Adventure",
+          "count": 2
+        }
+      ],
+      "limit": 2,
+      "from": null,
+      "next": "comedy"
+    }
+    "###);
+
+    let (response, code) = index.facet_values("genres", "?limit=2&from=comedy").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"], @r###"
+    [
+      {
+        "value": "Comedy",
+        "count": 1
+      },
+      {
+        "value": "Horror",
+        "count": 1
+      }
+    ]
+    "###);
+    snapshot!(response["next"], @r###""multiple words""###);
+
+    let (response, code) = index.facet_values("genres", "?from=multiple%20words").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"], @r###"
+    [
+      {
+        "value": "Multiple Words",
+        "count": 1
+      },
+      {
+        "value": "Thriller",
+        "count": 2
+      }
+    ]
+    "###);
+    snapshot!(response["next"], @"null");
+}
+
+#[actix_rt::test]
+async fn facet_values_of_a_non_filterable_field() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["genres"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.facet_values("title", "").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_facet_search_facet_name""###);
+
+    let (response, code) = index.facet_values("genres", "?limit=two").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_facet_values_limit""###);
+}
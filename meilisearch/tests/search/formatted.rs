@@ -568,3 +568,65 @@ async fn test_cjk_highlight() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn formatting_time_is_only_returned_when_formatting() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = NESTED_DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({ "q": "pésti" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response.get("formattingTimeMs").is_none(), "{}", response);
+        })
+        .await;
+
+    index
+        .search(json!({ "q": "pésti", "attributesToHighlight": ["*"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response["formattingTimeMs"].is_u64(), "{}", response);
+        })
+        .await;
+
+    index
+        .search(json!({ "q": "pésti", "showMatchesPosition": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response["formattingTimeMs"].is_u64(), "{}", response);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn crop_to_highlight_fragments() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "text": "one world two three four five six world world seven eight nine ten world eleven" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(
+            json!({
+                "q": "world",
+                "attributesToCrop": ["text"],
+                "cropLength": 3,
+                "attributesToHighlight": ["text"],
+                "maxHighlightFragments": 2,
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(
+                    response["hits"][0]["_formatted"]["text"],
+                    json!("one <em>world</em> two…six <em>world</em> <em>world</em>…")
+                );
+            },
+        )
+        .await;
+}
@@ -184,3 +184,70 @@ async fn bug_4640() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn geo_sort_with_max_distance() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_sortable_attributes(json!(["_geo", "rating"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "sort": ["_geoPoint(45.4777599, 9.1967508):asc", "rating:desc"],
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2,
+                    "_geoDistance": 0
+                  },
+                  {
+                    "id": 1,
+                    "_geoDistance": 9714063
+                  },
+                  {
+                    "id": 3
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the documents farther than 10km are sorted with the ones without a geo point
+    index
+        .search(
+            json!({
+                "sort": ["_geoPoint(45.4777599, 9.1967508):asc", "rating:desc"],
+                "attributesToRetrieve": ["id"],
+                "geoSortMaxDistance": 10000,
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2,
+                    "_geoDistance": 0
+                  },
+                  {
+                    "id": 3
+                  },
+                  {
+                    "id": 1,
+                    "_geoDistance": 9714063
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
@@ -0,0 +1,95 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn search_job_returns_all_the_hits() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "Shazam!", "genre": "action" },
+        { "id": 2, "title": "Captain Marvel", "genre": "action" },
+        { "id": 3, "title": "Escape Room", "genre": "horror" },
+    ]);
+    let (task, _) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = index
+        .update_settings(json!({
+            "filterableAttributes": ["genre"],
+            "pagination": { "maxTotalHits": 1 },
+            "faceting": { "maxValuesPerFacet": 1 },
+        }))
+        .await;
+    index.wait_task(task.uid()).await;
+
+    let query = json!({ "limit": 10, "facets": ["genre"], "attributesToRetrieve": ["id"] });
+
+    // the synchronous searches are limited by the settings of the index
+    let (response, code) = index.search_post(query.clone()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      }
+    ]
+    "###);
+    snapshot!(json_string!(response["facetDistribution"]), @r###"
+    {
+      "genre": {
+        "action": 2
+      }
+    }
+    "###);
+
+    let (job, code) = index.create_search_job(query).await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(job["status"], @r###""enqueued""###);
+
+    let job = index.wait_search_job(job["uid"].as_str().unwrap()).await;
+    snapshot!(job["status"], @r###""succeeded""###);
+    snapshot!(json_string!(job["result"]["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 3
+      }
+    ]
+    "###);
+    snapshot!(job["result"]["estimatedTotalHits"], @"3");
+    snapshot!(json_string!(job["result"]["facetDistribution"]), @r###"
+    {
+      "genre": {
+        "action": 2,
+        "horror": 1
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_job_errors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _) = index.add_documents(json!([{ "id": 1 }]), None).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index.search_job("c6b2a3a0-6c5b-4c5e-8a8e-5d4f0b3f8d11").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""search_job_not_found""###);
+
+    // the errors of the search are returned in the job
+    let (job, code) = index.create_search_job(json!({ "filter": "genre = action" })).await;
+    snapshot!(code, @"202 Accepted");
+    let job = index.wait_search_job(job["uid"].as_str().unwrap()).await;
+    snapshot!(job["status"], @r###""failed""###);
+    snapshot!(job["error"]["code"], @r###""invalid_search_filter""###);
+}
@@ -7,6 +7,7 @@ mod facet_search;
 mod formatted;
 mod geo;
 mod hybrid;
+mod jobs;
 mod matching_strategy;
 mod multi;
 mod pagination;
@@ -292,6 +293,570 @@ async fn non_negative_search() {
         .await;
 }
 
+#[actix_rt::test]
+async fn query_terms_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(
+            json!({"q": "\"train your\" dragon kefir", "showQueryTerms": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let hits = response["hits"].as_array().unwrap();
+                assert_eq!(hits.len(), 1);
+                assert_eq!(hits[0]["id"], "166428");
+                assert_eq!(
+                    response["queryTerms"],
+                    json!([
+                        { "start": 1, "end": 11, "kind": "phrase", "matched": true },
+                        { "start": 13, "end": 19, "kind": "word", "matched": true },
+                        { "start": 20, "end": 25, "kind": "prefix", "matched": false },
+                    ])
+                );
+            },
+        )
+        .await;
+
+    // the query terms are only returned when requested
+    index
+        .search(json!({"q": "\"train your\" dragon kefir" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response.get("queryTerms").is_none(), "{}", response);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn query_interpretation_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .update_settings(json!({"stopWords": ["the"], "synonyms": { "captain": ["shazam"] }}))
+        .await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({"q": "the captain room", "showQueryInterpretation": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+                meili_snap::snapshot!(meili_snap::json_string!(response["queryInterpretation"]), @r###"
+                {
+                  "terms": [
+                    {
+                      "original": "captain",
+                      "start": 4,
+                      "end": 11,
+                      "kind": "word",
+                      "maxTypos": 1,
+                      "synonyms": [
+                        "shazam"
+                      ],
+                      "dropped": false
+                    },
+                    {
+                      "original": "room",
+                      "start": 12,
+                      "end": 16,
+                      "kind": "prefix",
+                      "maxTypos": 0,
+                      "synonyms": [],
+                      "dropped": true
+                    }
+                  ],
+                  "removedStopWords": [
+                    {
+                      "original": "the",
+                      "start": 0,
+                      "end": 3
+                    }
+                  ],
+                  "matchingStrategy": "last"
+                }
+                "###);
+            },
+        )
+        .await;
+
+    // the interpretation of the query is only returned when requested
+    index
+        .search(json!({"q": "the captain room" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response.get("queryInterpretation").is_none(), "{}", response);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn query_rewrites_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .update_settings(json!({"queryRewrites": [{ "find": "cpt", "replace": "captain" }]}))
+        .await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({"q": "cpt marvel", "showQueryInterpretation": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+            assert_eq!(response["hits"][0]["title"], "Captain Marvel");
+            meili_snap::snapshot!(meili_snap::json_string!(response["queryInterpretation"]), @r###"
+                {
+                  "terms": [
+                    {
+                      "original": "captain",
+                      "start": 0,
+                      "end": 7,
+                      "kind": "word",
+                      "maxTypos": 1,
+                      "synonyms": [],
+                      "dropped": false
+                    },
+                    {
+                      "original": "marvel",
+                      "start": 8,
+                      "end": 14,
+                      "kind": "prefix",
+                      "maxTypos": 1,
+                      "synonyms": [],
+                      "dropped": false
+                    }
+                  ],
+                  "removedStopWords": [],
+                  "matchingStrategy": "last",
+                  "rewrittenQuery": "captain marvel",
+                  "rewrites": [
+                    {
+                      "original": "cpt",
+                      "rewritten": "captain"
+                    }
+                  ]
+                }
+                "###);
+        })
+        .await;
+
+    let (response, code) = index
+        .update_settings(json!({"queryRewrites": [{ "find": "(", "replace": "", "regex": true }]}))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(response.uid()).await;
+    assert_eq!(response["error"]["code"], "invalid_settings_query_rewrites", "{}", response);
+}
+
+#[actix_rt::test]
+async fn timings_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index.update_settings(json!({"filterableAttributes": ["id"]})).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({"q": "captain", "filter": "id != 1", "facets": ["id"], "showTimings": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let timings = response["timings"].as_object().unwrap();
+                let mut stages: Vec<_> = timings.keys().map(String::as_str).collect();
+                stages.sort_unstable();
+                meili_snap::snapshot!(format!("{stages:?}"), @r###"["facetsMs", "filterMs", "formattingMs", "graphBuildingMs", "queryParsingMs", "rankingRules"]"###);
+                let rules: Vec<_> = timings["rankingRules"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|rule| {
+                        assert!(rule["ms"].as_f64().unwrap() >= 0.0, "{}", rule);
+                        rule["rule"].as_str().unwrap()
+                    })
+                    .collect();
+                meili_snap::snapshot!(format!("{rules:?}"), @r###"["words", "typo", "proximity", "fid", "position", "exact_attribute", "exactness"]"###);
+            },
+        )
+        .await;
+
+    // the timings are only returned when requested
+    index
+        .search(json!({"q": "captain" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert!(response.get("timings").is_none(), "{}", response);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn computed_fields_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "price": 100, "discount": 0.5 },
+        { "id": 1, "price": 80, "discount": 0 },
+        { "id": 2, "price": 40 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, _code) = index
+        .update_settings(json!({
+            "displayedAttributes": ["id"],
+            "computedFields": { "finalPrice": "price * (1 - discount)" },
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(json!({ "sort": ["finalPrice:asc"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 0,
+                "finalPrice": 50.0
+              },
+              {
+                "id": 1,
+                "finalPrice": 80
+              },
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(
+            json!({ "sort": ["finalPrice:desc"], "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  },
+                  {
+                    "id": 0
+                  },
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn prepared_filters_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "owner": "kefir", "color": "red" },
+        { "id": 1, "owner": "intel", "color": "red" },
+        { "id": 2, "owner": "kefir", "color": "blue" },
+        { "id": 3, "owner": "kefir\" OR owner = \"intel", "color": "green" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, _code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["owner", "color"],
+            "preparedFilters": { "mine": "owner = {owner} AND color IN {colors}" },
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(
+            json!({
+                "preparedFilter": {
+                    "name": "mine",
+                    "params": { "owner": "kefir", "colors": ["red", "green"] },
+                },
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 0
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the parameters can't alter the structure of the filter
+    index
+        .search(
+            json!({
+                "preparedFilter": {
+                    "name": "mine",
+                    "params": { "owner": "kefir\" OR owner = \"intel", "colors": ["red", "green"] },
+                },
+                "filter": "color = green",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 3
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({ "preparedFilter": { "name": "mine", "params": { "owner": "kefir" } } }),
+            |response, code| {
+                assert_eq!(code, 400, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+                {
+                  "message": "The parameters of the `mine` prepared filter are invalid. Missing the `colors` parameter.",
+                  "code": "invalid_search_prepared_filter",
+                  "type": "invalid_request",
+                  "link": "https://docs.meilisearch.com/errors#invalid_search_prepared_filter"
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(json!({ "preparedFilter": { "name": "theirs" } }), |response, code| {
+            assert_eq!(code, 400, "{}", response);
+            meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+            {
+              "message": "The `theirs` prepared filter doesn't exist. Prepared filters are defined in the `preparedFilters` setting of the index.",
+              "code": "invalid_search_prepared_filter",
+              "type": "invalid_request",
+              "link": "https://docs.meilisearch.com/errors#invalid_search_prepared_filter"
+            }
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_query_limits() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "text": "the quick brown fox jumps over the lazy dog" },
+        { "id": 1, "text": "the quick brown cat" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, _code) =
+        index.update_settings(json!({ "queryLimits": { "maxTerms": 2 } })).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(
+            json!({
+                "q": "quick brown fox",
+                "matchingStrategy": "all",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 0
+                  },
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+                meili_snap::snapshot!(response["queryTruncated"], @"true");
+            },
+        )
+        .await;
+
+    // the flag is omitted when the whole query was searched
+    index
+        .search(json!({ "q": "quick brown", "matchingStrategy": "all" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            meili_snap::snapshot!(response["queryTruncated"], @"null");
+        })
+        .await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    meili_snap::snapshot!(meili_snap::json_string!(response["queryLimits"]), @r###"
+    {
+      "maxLength": null,
+      "maxTerms": 2,
+      "maxPhrases": null
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_languages() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _code) = index
+        .update_settings(json!({ "fieldLanguages": { "title_en": "en", "title_fr": "fr" } }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let documents = json!([
+        { "id": 0, "title_en": "the best of the city", "title_fr": "le meilleur de la ville" },
+        { "id": 1, "title_en": "le mans race", "title_fr": "la course du mans" },
+    ]);
+    let (response, _code) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(
+            json!({ "q": "ville", "languages": ["en"], "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @"[]");
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({ "q": "ville", "languages": ["fr"], "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 0
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    meili_snap::snapshot!(meili_snap::json_string!(response["fieldLanguages"]), @r###"
+    {
+      "title_en": "en",
+      "title_fr": "fr"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn query_synonyms_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index.update_settings(json!({"synonyms": { "captain": ["shazam"] }})).await;
+    index.wait_task(1).await;
+
+    // the synonyms of the query are added to the ones of the index
+    index
+        .search(
+            json!({"q": "captain", "synonyms": { "Captain": ["Escape Room"] } }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let mut ids: Vec<_> =
+                    response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+                ids.sort_by_key(|id| id.as_str().unwrap().to_string());
+                assert_eq!(ids, vec!["287947", "299537", "522681"]);
+            },
+        )
+        .await;
+
+    // but they are only used by this query
+    index
+        .search(json!({"q": "captain"}), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2, "{}", response);
+        })
+        .await;
+    let (response, _) = index.settings().await;
+    assert_eq!(response["synonyms"], json!({ "captain": ["shazam"] }));
+}
+
+#[actix_rt::test]
+async fn facet_value_aliases_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "country": "United States" },
+        { "id": 2, "country": "USA" },
+        { "id": 3, "country": "us" },
+        { "id": 4, "country": "France" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index.update_settings_filterable_attributes(json!(["country"])).await;
+    index
+        .update_settings_faceting(json!({
+            "valueAliases": { "country": { "United States": ["US", "USA"] } }
+        }))
+        .await;
+    index.wait_task(2).await;
+
+    index
+        .search(json!({"filter": "country = usa", "facets": ["country"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 3, "{}", response);
+            assert_eq!(response["facetDistribution"], json!({ "country": { "United States": 3 } }));
+        })
+        .await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["faceting"]["valueAliases"],
+        json!({ "country": { "United States": ["US", "USA"] } })
+    );
+}
+
 #[actix_rt::test]
 async fn negative_special_cases_search() {
     let server = Server::new().await;
@@ -745,6 +1310,52 @@ async fn displayed_attributes() {
     assert!(response["hits"][0].get("title").is_some());
 }
 
+#[actix_rt::test]
+async fn retrieve_ids_only() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(
+            json!({ "q": "glass", "attributesToRetrieve": [], "showRankingScore": true }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(meili_snap::json_string!(response["hits"], { "[]._rankingScore" => "[score]" }), @r###"
+                [
+                  {
+                    "id": "450465",
+                    "_rankingScore": "[score]"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the primary keys are returned with their original type
+    let index = server.index("numbers");
+    let documents = json!([{ "id": 1, "text": "glass" }, { "id": 2, "text": "bottle" }]);
+    let (response, _code) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(json!({ "q": "glass", "attributesToRetrieve": [] }), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              }
+            ]
+            "###);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn placeholder_search_is_hard_limited() {
     let server = Server::new().await;
@@ -957,7 +1568,21 @@ async fn test_score_details() {
                         "order": 1,
                         "typoCount": 0,
                         "maxTypoCount": 2,
-                        "score": 1.0
+                        "score": 1.0,
+                        "terms": [
+                          {
+                            "term": "train",
+                            "typos": 0,
+                            "split": false,
+                            "prefix": false
+                          },
+                          {
+                            "term": "dragon",
+                            "typos": 0,
+                            "split": false,
+                            "prefix": false
+                          }
+                        ]
                       },
                       "proximity": {
                         "order": 2,
@@ -1,7 +1,8 @@
 use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
 
 use super::{DOCUMENTS, NESTED_DOCUMENTS};
-use crate::common::Server;
+use crate::common::{default_settings, Server};
 use crate::json;
 
 #[actix_rt::test]
@@ -376,3 +377,50 @@ async fn search_multiple_query_errors() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn federation_upstream_unreachable() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = Server::new_with_options(Opt {
+        experimental_federation_upstreams: Some(
+            r#"[{ "url": "http://127.0.0.1:1", "indexes": ["remote-*"] }]"#.parse().unwrap(),
+        ),
+        ..default_settings(temp.path())
+    })
+    .await
+    .unwrap();
+
+    let index = server.index("test");
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    // the local queries are still processed locally
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"indexUid" : "test", "q": "glass"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"][0]["hits"][0]["id"], @r###""450465""###);
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "remote-movies", "q": "glass"},
+        ]}))
+        .await;
+    snapshot!(code, @"502 Bad Gateway");
+    snapshot!(json_string!(response, { ".message" => "[message]" }), @r###"
+    {
+      "message": "[message]",
+      "code": "federation_upstream_error",
+      "type": "system",
+      "link": "https://docs.meilisearch.com/errors#federation_upstream_error"
+    }
+    "###);
+    assert!(response["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Inside `.queries[1]`: The upstream `http://127.0.0.1:1/` failed"));
+}
@@ -1,4 +1,6 @@
-use crate::common::Server;
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{Server, Value};
 use crate::json;
 use crate::search::DOCUMENTS;
 
@@ -163,3 +165,56 @@ async fn ensure_placeholder_search_hit_count_valid() {
             .await;
     }
 }
+
+#[actix_rt::test]
+async fn search_session_pagination() {
+    let server = Server::new().await;
+    let index = server.index("basic");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    fn get_ids(response: &Value) -> Vec<&str> {
+        response["hits"].as_array().unwrap().iter().map(|h| h["id"].as_str().unwrap()).collect()
+    }
+
+    let (response, code) = index.search_post(json!({"limit": 2, "createSession": true})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", get_ids(&response)), @r###"["287947", "299537"]"###);
+    let token = response["sessionToken"].as_str().unwrap().to_string();
+
+    // the documents deleted after the creation of the session are still paginated
+    index.clear_all_documents().await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({"offset": 2, "limit": 2})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", get_ids(&response)), @"[]");
+
+    let (response, code) =
+        index.search_post(json!({"offset": 2, "limit": 2, "sessionToken": token})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", get_ids(&response)), @r###"["522681", "166428"]"###);
+    snapshot!(response["estimatedTotalHits"], @"5");
+    assert_eq!(response["sessionToken"], json!(token));
+
+    // the sessions are bound to their index
+    let other = server.index("other");
+    other.create(None).await;
+    other.wait_task(2).await;
+    let (response, code) = other.search_post(json!({"sessionToken": token})).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""search_session_not_found""###);
+
+    let (response, code) = index.search_post(json!({"sessionToken": "doggo"})).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Search session `doggo` not found. It may have expired, the sessions expire five minutes after their last search.",
+      "code": "search_session_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#search_session_not_found"
+    }
+    "###);
+}
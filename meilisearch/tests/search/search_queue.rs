@@ -182,3 +182,21 @@ async fn works_with_capacity_of_zero() {
         .expect("I should get a permit straight away")
         .unwrap();
 }
+
+#[actix_rt::test]
+async fn search_runs_on_the_search_threads() {
+    let queue = SearchQueue::new(1, NonZeroUsize::new(2).unwrap());
+    assert_eq!(queue.threads(), 2);
+
+    let _permit = queue.try_get_search_permit().await.unwrap();
+    let thread_name =
+        queue.run(|| std::thread::current().name().map(ToOwned::to_owned)).await.unwrap();
+    assert!(thread_name.unwrap().starts_with("search-thread:"));
+
+    // a panicking search must not bring the pool down
+    let err = queue.run(|| -> usize { panic!("oops an unexpected crash happened") }).await;
+    let err = meilisearch_types::error::ResponseError::from(err.unwrap_err());
+    snapshot!(err.message, @"Internal error: A search panicked.");
+
+    snapshot!(queue.run(|| 42).await.unwrap(), @"42");
+}
@@ -0,0 +1,84 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn copy_all_settings() {
+    let server = Server::new().await;
+    let source = server.index("source");
+    let target = server.index("target");
+
+    let (response, _code) = source
+        .update_settings(json!({
+            "searchableAttributes": ["title"],
+            "stopWords": ["the"],
+            "rankingRules": ["words", "exactness"],
+        }))
+        .await;
+    source.wait_task(response.uid()).await;
+
+    let (response, code) = source.copy_settings_to("target", None).await;
+    assert_eq!(code, 202, "{response}");
+    let response = target.wait_task(response.uid()).await;
+    assert_eq!(response["status"], "succeeded", "{response}");
+    assert_eq!(response["indexUid"], "target");
+
+    let (source_settings, _) = source.settings().await;
+    let (target_settings, _) = target.settings().await;
+    assert_eq!(source_settings, target_settings);
+}
+
+#[actix_rt::test]
+async fn copy_a_subset_of_the_settings() {
+    let server = Server::new().await;
+    let source = server.index("source");
+    let target = server.index("target");
+
+    let (response, _code) = source
+        .update_settings(json!({
+            "searchableAttributes": ["title"],
+            "stopWords": ["the"],
+            "rankingRules": ["words", "exactness"],
+        }))
+        .await;
+    source.wait_task(response.uid()).await;
+
+    let (response, code) = source.copy_settings_to("target", Some("stopWords,rankingRules")).await;
+    assert_eq!(code, 202, "{response}");
+    target.wait_task(response.uid()).await;
+
+    let (response, _) = target.settings().await;
+    assert_eq!(response["stopWords"], json!(["the"]));
+    assert_eq!(response["rankingRules"], json!(["words", "exactness"]));
+    assert_eq!(response["searchableAttributes"], json!(["*"]));
+}
+
+#[actix_rt::test]
+async fn error_copy_unknown_setting() {
+    let server = Server::new().await;
+    let source = server.index("source");
+    let (response, _code) = source.create(None).await;
+    source.wait_task(response.uid()).await;
+
+    let (response, code) = source.copy_settings_to("target", Some("stopWords,doggo")).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown setting `doggo` in the settings to copy. Expected one of: `displayedAttributes`, `searchableAttributes`, `filterableAttributes`, `sortableAttributes`, `denseSortableAttributes`, `rankingRules`, `stopWords`, `nonSeparatorTokens`, `separatorTokens`, `dictionary`, `exactWords`, `synonyms`, `distinctAttribute`, `versionAttribute`, `proximityPrecision`, `typoTolerance`, `faceting`, `pagination`, `embedders`, `searchCutoffMs`, `prefixCacheSize`, `longQueryThreshold`, `quoteCharacters`, `languagePreset`, `presenceOnlyAttributes`, `chunking`, `documentHook`, `computedFields`, `preparedFilters`, `piiAttributes`, `queryLimits`, `regexFilter`, `fieldLanguages`, `minPrefixLength`, `blockedTerms`, `newFields`, `queryRewrites`, `positionDecay`, `batching`, `collation`, `tieBreaker`.",
+      "code": "invalid_settings_copy_settings",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_copy_settings"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_copy_from_unexisting_index() {
+    let server = Server::new().await;
+    let source = server.index("source");
+
+    let (response, code) = source.copy_settings_to("target", None).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+}
@@ -57,6 +57,19 @@ async fn settings_bad_searchable_attributes() {
       "link": "https://docs.meilisearch.com/errors#invalid_settings_searchable_attributes"
     }
     "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "searchableAttributes": [{ "attribute": "doggo", "weight": 0 }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.searchableAttributes[0]`: the weight of `doggo` should be between `1` and `100` but found `0`.",
+      "code": "invalid_settings_searchable_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_searchable_attributes"
+    }
+    "###);
 }
 
 #[actix_rt::test]
@@ -365,3 +378,142 @@ async fn settings_bad_search_cutoff_ms() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn settings_bad_quote_characters() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "quoteCharacters": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.quoteCharacters`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_quote_characters",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_quote_characters"
+    }
+    "###);
+
+    let (response, code) = index.update_settings(json!({ "quoteCharacters": ["“", "ab"] })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "`ab` is not a valid quote character. A quote character must be a single character that is neither alphanumeric nor a whitespace.",
+      "code": "invalid_settings_quote_characters",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_quote_characters"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_language_preset() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "languagePreset": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.languagePreset`: expected one of `en`, `fr`, `de`, `es`, `ja`, `zh`, `ko`, `he`, `th`",
+      "code": "invalid_settings_language_preset",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_language_preset"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_document_hook() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.update_settings(json!({ "documentHook": { "module": "not base64" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`documentHook` setting is invalid. The module must be base64 encoded: Invalid symbol 32, offset 3.",
+      "code": "invalid_settings_document_hook",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_document_hook"
+    }
+    "###);
+
+    // "not wasm", base64 encoded
+    let (response, code) =
+        index.update_settings(json!({ "documentHook": { "module": "bm90IHdhc20=" } })).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(response["error"]["code"], @r###""invalid_settings_document_hook""###);
+    // the module isn't echoed back, only its digest and size
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "documentHook": {
+        "moduleSha256": "dc0cbbe931352caad7a936203e1d540bb96efc8aaafbf760fb94774364aeac06",
+        "moduleSize": 8
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .service
+        .put_str(
+            "/indexes/test/settings/document-hook/module",
+            "not wasm",
+            vec![("Content-Type", "application/json")],
+        )
+        .await;
+    snapshot!(code, @"415 Unsupported Media Type");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The Content-Type `application/json` is invalid. Accepted values for the Content-Type header are: `application/wasm`",
+      "code": "invalid_content_type",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_content_type"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_tie_breaker() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "tieBreaker": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.tieBreaker`: expected one of `primaryKeyAsc`, `primaryKeyDesc`",
+      "code": "invalid_settings_tie_breaker",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_tie_breaker"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "tieBreaker": { "field": "rank", "order": "up" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `up` at `.tieBreaker.order`: expected one of `asc`, `desc`",
+      "code": "invalid_settings_tie_breaker",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_tie_breaker"
+    }
+    "###);
+
+    let (response, code) = index.update_settings(json!({ "tieBreaker": 42 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.tieBreaker`: expected a string or an object, but found a positive integer: `42`",
+      "code": "invalid_settings_tie_breaker",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_tie_breaker"
+    }
+    "###);
+}
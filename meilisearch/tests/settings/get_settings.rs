@@ -10,7 +10,9 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
     map.insert("displayed_attributes", json!(["*"]));
     map.insert("searchable_attributes", json!(["*"]));
     map.insert("filterable_attributes", json!([]));
+    map.insert("dense_sortable_attributes", json!([]));
     map.insert("distinct_attribute", json!(null));
+    map.insert("version_attribute", json!(null));
     map.insert(
         "ranking_rules",
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"]),
@@ -19,6 +21,7 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
     map.insert("non_separator_tokens", json!([]));
     map.insert("separator_tokens", json!([]));
     map.insert("dictionary", json!([]));
+    map.insert("exact_words", json!([]));
     map.insert("synonyms", json!({}));
     map.insert(
         "faceting",
@@ -36,6 +39,27 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
         }),
     );
     map.insert("search_cutoff_ms", json!(null));
+    map.insert("prefix_cache_size", json!(0));
+    map.insert("long_query_threshold", json!(null));
+    map.insert("quote_characters", json!(["\""]));
+    map.insert("language_preset", json!(null));
+    map.insert("presence_only_attributes", json!([]));
+    map.insert("chunking", json!(null));
+    map.insert("document_hook", json!(null));
+    map.insert("computed_fields", json!({}));
+    map.insert("prepared_filters", json!({}));
+    map.insert("pii_attributes", json!([]));
+    map.insert("query_limits", json!({ "maxLength": null, "maxTerms": 10, "maxPhrases": null }));
+    map.insert("regex_filter", json!({ "enabled": false, "maxCandidates": 10000 }));
+    map.insert("field_languages", json!({}));
+    map.insert("min_prefix_length", json!(1));
+    map.insert("blocked_terms", json!({ "terms": [], "mode": "drop" }));
+    map.insert("new_fields", json!({ "searchable": true, "filterable": false }));
+    map.insert("query_rewrites", json!([]));
+    map.insert("position_decay", json!("log"));
+    map.insert("batching", json!({ "maxBatchSize": null, "maxBatchPayload": null }));
+    map.insert("collation", json!({ "locale": null, "attributes": {} }));
+    map.insert("tie_breaker", json!(null));
     map
 });
 
@@ -55,12 +79,14 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 16);
+    assert_eq!(settings.keys().len(), 40);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
     assert_eq!(settings["sortableAttributes"], json!([]));
+    assert_eq!(settings["denseSortableAttributes"], json!([]));
     assert_eq!(settings["distinctAttribute"], json!(null));
+    assert_eq!(settings["versionAttribute"], json!(null));
     assert_eq!(
         settings["rankingRules"],
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"])
@@ -69,6 +95,7 @@ async fn get_settings() {
     assert_eq!(settings["nonSeparatorTokens"], json!([]));
     assert_eq!(settings["separatorTokens"], json!([]));
     assert_eq!(settings["dictionary"], json!([]));
+    assert_eq!(settings["exactWords"], json!([]));
     assert_eq!(
         settings["faceting"],
         json!({
@@ -86,6 +113,30 @@ async fn get_settings() {
     );
     assert_eq!(settings["proximityPrecision"], json!("byWord"));
     assert_eq!(settings["searchCutoffMs"], json!(null));
+    assert_eq!(settings["prefixCacheSize"], json!(0));
+    assert_eq!(settings["longQueryThreshold"], json!(null));
+    assert_eq!(settings["quoteCharacters"], json!(["\""]));
+    assert_eq!(settings["languagePreset"], json!(null));
+    assert_eq!(settings["presenceOnlyAttributes"], json!([]));
+    assert_eq!(settings["chunking"], json!(null));
+    assert_eq!(settings["documentHook"], json!(null));
+    assert_eq!(settings["computedFields"], json!({}));
+    assert_eq!(settings["preparedFilters"], json!({}));
+    assert_eq!(settings["piiAttributes"], json!([]));
+    assert_eq!(
+        settings["queryLimits"],
+        json!({ "maxLength": null, "maxTerms": 10, "maxPhrases": null })
+    );
+    assert_eq!(settings["regexFilter"], json!({ "enabled": false, "maxCandidates": 10000 }));
+    assert_eq!(settings["fieldLanguages"], json!({}));
+    assert_eq!(settings["minPrefixLength"], json!(1));
+    assert_eq!(settings["blockedTerms"], json!({ "terms": [], "mode": "drop" }));
+    assert_eq!(settings["newFields"], json!({ "searchable": true, "filterable": false }));
+    assert_eq!(settings["queryRewrites"], json!([]));
+    assert_eq!(settings["positionDecay"], json!("log"));
+    assert_eq!(settings["batching"], json!({ "maxBatchSize": null, "maxBatchPayload": null }));
+    assert_eq!(settings["collation"], json!({ "locale": null, "attributes": {} }));
+    assert_eq!(settings["tieBreaker"], json!(null));
 }
 
 #[actix_rt::test]
@@ -99,7 +150,8 @@ async fn secrets_are_hidden_in_settings() {
       "vectorStore": true,
       "metrics": false,
       "logsRoute": false,
-      "editDocumentsByFunction": false
+      "editDocumentsByFunction": false,
+      "typoStatistics": false
     }
     "###);
 
@@ -148,6 +200,7 @@ async fn secrets_are_hidden_in_settings() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "denseSortableAttributes": [],
       "rankingRules": [
         "words",
         "typo",
@@ -160,8 +213,10 @@ async fn secrets_are_hidden_in_settings() {
       "nonSeparatorTokens": [],
       "separatorTokens": [],
       "dictionary": [],
+      "exactWords": [],
       "synonyms": {},
       "distinctAttribute": null,
+      "versionAttribute": null,
       "proximityPrecision": "byWord",
       "typoTolerance": {
         "enabled": true,
@@ -201,7 +256,49 @@ async fn secrets_are_hidden_in_settings() {
           "inputType": "text"
         }
       },
-      "searchCutoffMs": null
+      "searchCutoffMs": null,
+      "prefixCacheSize": 0,
+      "longQueryThreshold": null,
+      "quoteCharacters": [
+        "\""
+      ],
+      "languagePreset": null,
+      "presenceOnlyAttributes": [],
+      "chunking": null,
+      "documentHook": null,
+      "computedFields": {},
+      "preparedFilters": {},
+      "piiAttributes": [],
+      "queryLimits": {
+        "maxLength": null,
+        "maxTerms": 10,
+        "maxPhrases": null
+      },
+      "regexFilter": {
+        "enabled": false,
+        "maxCandidates": 10000
+      },
+      "fieldLanguages": {},
+      "minPrefixLength": 1,
+      "blockedTerms": {
+        "terms": [],
+        "mode": "drop"
+      },
+      "newFields": {
+        "searchable": true,
+        "filterable": false
+      },
+      "queryRewrites": [],
+      "positionDecay": "log",
+      "batching": {
+        "maxBatchSize": null,
+        "maxBatchPayload": null
+      },
+      "collation": {
+        "locale": null,
+        "attributes": {}
+      },
+      "tieBreaker": null
     }
     "###);
 
@@ -249,6 +346,94 @@ async fn test_partial_update() {
     assert_eq!(response["searchableAttributes"], json!(["bar"]));
 }
 
+#[actix_rt::test]
+async fn weighted_searchable_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let searchable_attributes = json!(["title", { "attribute": "overview", "weight": 3 }]);
+    let (_response, code) =
+        index.update_settings(json!({ "searchableAttributes": searchable_attributes })).await;
+    assert_eq!(code, 202);
+    index.wait_task(0).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["searchableAttributes"], searchable_attributes);
+
+    let (_response, code) = index.update_settings_searchable_attributes(json!(["title"])).await;
+    assert_eq!(code, 202);
+    index.wait_task(1).await;
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["searchableAttributes"], json!(["title"]));
+}
+
+#[actix_rt::test]
+async fn get_effective_settings() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "name": "curqui",
+            "age": 99
+        }
+    ]);
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.effective_settings().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["displayedAttributes"], json!(["id", "name", "age"]));
+    assert_eq!(response["searchableAttributes"], json!(["id", "name", "age"]));
+    assert_eq!(response["searchCutoffMs"], json!(1500));
+    assert_eq!(response["embedders"], json!({}));
+    assert_eq!(
+        response["typoTolerance"]["halfTypos"],
+        json!({ "keyboardLayout": null, "accents": false })
+    );
+    assert_eq!(response["typoTolerance"]["disableOnNumbers"], json!(false));
+    assert_eq!(response["typoTolerance"]["firstLetterTypos"], json!("countAsTwo"));
+    assert_eq!(response["typoTolerance"]["enablePhonetic"], json!(false));
+    assert_eq!(
+        response["typoTolerance"]["typoCosts"],
+        json!({ "oneTypo": 1, "twoTypos": 2, "split": 1 })
+    );
+    assert_eq!(response["faceting"]["valueAliases"], json!({}));
+    assert_eq!(response["geo"], json!({ "filterable": false, "sortable": false }));
+
+    let (response, _code) = index
+        .update_settings(json!({
+            "searchableAttributes": ["age", "name"],
+            "filterableAttributes": ["_geo"],
+            "sortableAttributes": ["age"],
+        }))
+        .await;
+    index.wait_task(response.uid()).await;
+
+    let (response, code) = index.effective_settings().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["searchableAttributes"], json!(["age", "name"]));
+    assert_eq!(
+        response["searchableFields"],
+        json!([
+            { "name": "age", "fieldId": 2, "weight": 0 },
+            { "name": "name", "fieldId": 1, "weight": 1 },
+        ])
+    );
+    assert_eq!(response["facetedFields"], json!(["_geo", "age"]));
+    assert_eq!(response["geo"], json!({ "filterable": true, "sortable": false }));
+
+    // the regular route still doesn't materialize the defaults
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["displayedAttributes"], json!(["*"]));
+    assert_eq!(response["searchCutoffMs"], json!(null));
+}
+
 #[actix_rt::test]
 async fn error_delete_settings_unexisting_index() {
     let server = Server::new().await;
@@ -413,15 +598,39 @@ test_setting_routes!(
     displayed_attributes put,
     searchable_attributes put,
     distinct_attribute put,
+    version_attribute put,
+    dense_sortable_attributes put,
     stop_words put,
     separator_tokens put,
     non_separator_tokens put,
     dictionary put,
+    exact_words put,
     ranking_rules put,
     synonyms put,
     pagination patch,
     faceting patch,
-    search_cutoff_ms put
+    search_cutoff_ms put,
+    prefix_cache_size put,
+    long_query_threshold put,
+    quote_characters put,
+    language_preset put,
+    presence_only_attributes put,
+    chunking patch,
+    document_hook patch,
+    computed_fields put,
+    prepared_filters put,
+    pii_attributes put,
+    query_limits patch,
+    regex_filter patch,
+    field_languages put,
+    min_prefix_length put,
+    blocked_terms patch,
+    new_fields patch,
+    query_rewrites put,
+    position_decay put,
+    batching patch,
+    collation patch,
+    tie_breaker put
 );
 
 #[actix_rt::test]
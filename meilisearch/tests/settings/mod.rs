@@ -1,3 +1,4 @@
+mod copy_settings;
 mod distinct;
 mod errors;
 mod get_settings;
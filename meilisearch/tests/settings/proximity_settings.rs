@@ -350,3 +350,49 @@ async fn attribute_scale_default_ranking_rules() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn presence_only_attributes_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .update_settings(json!({
+            "presenceOnlyAttributes": ["b"],
+            "rankingRules": ["words", "typo", "proximity"],
+        }))
+        .await;
+    assert_eq!("202", code.as_str(), "{:?}", response);
+    index.wait_task(1).await;
+
+    // the expected order is [1, 2, 3] instead of [1, 3, 2]
+    // because the proximities between the words of `b` are not stored.
+    index
+        .search(json!({"q": "many the fish", "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 2
+              },
+              {
+                "id": 3
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["presenceOnlyAttributes"]), @r###"
+    [
+      "b"
+    ]
+    "###);
+}
@@ -465,3 +465,42 @@ async fn advanced_synergies() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn language_preset() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, _code) = index.update_settings(json!({ "languagePreset": "he" })).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["languagePreset"]), @r###""he""###);
+    snapshot!(json_string!(response["stopWords"]), @r###"
+    [
+      "את",
+      "על",
+      "עם",
+      "של"
+    ]
+    "###);
+    snapshot!(json_string!(response["typoTolerance"]["minWordSizeForTypos"]), @r###"
+    {
+      "oneTypo": 4,
+      "twoTypos": 8
+    }
+    "###);
+
+    let (response, _code) = index.update_settings(json!({ "languagePreset": null })).await;
+    index.wait_task(response.uid()).await;
+
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["languagePreset"]), @"null");
+    snapshot!(json_string!(response["stopWords"]), @"[]");
+    snapshot!(json_string!(response["typoTolerance"]["minWordSizeForTypos"]), @r###"
+    {
+      "oneTypo": 5,
+      "twoTypos": 9
+    }
+    "###);
+}
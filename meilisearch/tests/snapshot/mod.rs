@@ -202,3 +202,71 @@ async fn perform_on_demand_snapshot() {
         server.index("doggo").settings(),
     );
 }
+
+#[actix_rt::test]
+async fn compare_snapshot_with_current_index() {
+    let temp = tempfile::tempdir().unwrap();
+    let snapshot_dir = tempfile::tempdir().unwrap();
+
+    let options =
+        Opt { snapshot_dir: snapshot_dir.path().to_owned(), ..default_settings(temp.path()) };
+
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let index = server.index("doggo");
+    let (task, _) = index
+        .add_documents(
+            json!([
+                { "id": 1, "name": "kefir" },
+                { "id": 2, "name": "intel" },
+                { "id": 3, "name": "bouvier" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(task.uid()).await;
+
+    let (task, _) = server.create_snapshot().await;
+    index.wait_task(task.uid()).await;
+
+    let (task, _) = index
+        .add_documents(
+            json!([{ "id": 2, "name": "intel le chien" }, { "id": 4, "name": "bob" }]),
+            None,
+        )
+        .await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = index.delete_document(3).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = index.update_settings(json!({ "filterableAttributes": ["name"] })).await;
+    index.wait_task(task.uid()).await;
+
+    let (task, code) =
+        server.compare_snapshots(json!({ "indexUid": "doggo", "snapshot": "db.snapshot" })).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "indexUid": "doggo",
+      "snapshot": "db.snapshot",
+      "otherSnapshot": null,
+      "report": {
+        "documentsAdded": 1,
+        "documentsRemoved": 1,
+        "documentsChanged": 1,
+        "settingsDifferences": [
+          "filterableAttributes"
+        ]
+      }
+    }
+    "###);
+
+    // only the files of the snapshots directory can be compared
+    let (task, _) = server
+        .compare_snapshots(json!({ "indexUid": "doggo", "snapshot": "../db.snapshot" }))
+        .await;
+    let task = index.wait_task(task.uid()).await;
+    snapshot!(task["status"], @r###""failed""###);
+    snapshot!(task["error"]["code"], @r###""snapshot_not_found""###);
+}
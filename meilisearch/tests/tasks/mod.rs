@@ -2,11 +2,12 @@ mod errors;
 mod webhook;
 
 use meili_snap::insta::assert_json_snapshot;
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
-use crate::common::Server;
+use crate::common::{default_settings, Server};
 use crate::json;
 
 #[actix_rt::test]
@@ -62,6 +63,46 @@ async fn list_tasks() {
     assert_eq!(response["results"].as_array().unwrap().len(), 2);
 }
 
+#[actix_rt::test]
+async fn tasks_stats() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+    index.add_documents(json!([{ "id": 1, "content": "foobar" }]), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server.tasks_stats().await;
+    snapshot!(code, @"200 OK");
+    assert!(response["indexedPayloadBytes"].as_u64().unwrap() > 0);
+    snapshot!(json_string!(response, {
+        ".currentLagSeconds" => "[lag]",
+        ".averageLagSeconds" => "[lag]",
+        ".processingDurations.*.p50Seconds" => "[duration]",
+        ".processingDurations.*.p95Seconds" => "[duration]",
+        ".indexedPayloadBytes" => "[bytes]",
+    }), @r###"
+    {
+      "currentLagSeconds": "[lag]",
+      "averageLagSeconds": "[lag]",
+      "batchesPerHour": 2,
+      "processingDurations": {
+        "documentAdditionOrUpdate": {
+          "count": 1,
+          "p50Seconds": "[duration]",
+          "p95Seconds": "[duration]"
+        },
+        "indexCreation": {
+          "count": 1,
+          "p50Seconds": "[duration]",
+          "p95Seconds": "[duration]"
+        }
+      },
+      "indexedPayloadBytes": "[bytes]"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn list_tasks_with_star_filters() {
     let server = Server::new().await;
@@ -1122,3 +1163,73 @@ async fn test_summarized_dump_creation() {
     }
     "###);
 }
+
+#[actix_web::test]
+async fn replay_document_addition() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = Server::new_with_options(Opt {
+        experimental_task_payload_retention_days: Some(1),
+        ..default_settings(temp.path())
+    })
+    .await
+    .unwrap();
+    let index = server.index("test");
+
+    let (task, _) = index.add_documents(json!([{ "id": 1, "doggo": "bob" }]), None).await;
+    index.wait_task(task.uid()).await;
+    let (task, _) = index.delete().await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = server.service.post("/tasks/0/replay", json!(null)).await;
+    snapshot!(code, @"202 Accepted");
+    assert_valid_summarized_task!(response, "documentAdditionOrUpdate", "test");
+    let task = index.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (response, code) = index.get_all_documents_raw("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "doggo": "bob"
+      }
+    ]
+    "###);
+
+    // only the document additions can be replayed
+    let (response, code) = server.service.post("/tasks/1/replay", json!(null)).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Task `1` cannot be replayed. Only the succeeded document additions can be replayed, as long as their payload is retained.",
+      "code": "task_not_replayable",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#task_not_replayable"
+    }
+    "###);
+}
+
+#[actix_web::test]
+async fn replay_document_addition_without_retention() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _) = index.add_documents(json!([{ "id": 1, "doggo": "bob" }]), None).await;
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = server.service.post("/tasks/0/replay", json!(null)).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Task `0` cannot be replayed. Only the succeeded document additions can be replayed, as long as their payload is retained.",
+      "code": "task_not_replayable",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#task_not_replayable"
+    }
+    "###);
+
+    let (response, code) = server.service.post("/tasks/12/replay", json!(null)).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""task_not_found""###);
+}
@@ -1,18 +1,19 @@
-use std::fs::{read_dir, read_to_string, remove_file, File};
+use std::fs::{metadata, read_dir, read_to_string, remove_file, File};
 use std::io::BufWriter;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 use clap::{Parser, Subcommand};
 use dump::{DumpWriter, IndexMetadata};
 use file_store::FileStore;
 use meilisearch_auth::AuthController;
-use meilisearch_types::heed::types::{SerdeJson, Str};
-use meilisearch_types::heed::{Database, Env, EnvOpenOptions, RoTxn, RwTxn, Unspecified};
+use meilisearch_types::heed::types::{DecodeIgnore, SerdeJson, Str};
+use meilisearch_types::heed::{Database, Env, EnvFlags, EnvOpenOptions, RoTxn, RwTxn, Unspecified};
 use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
-use meilisearch_types::milli::{obkv_to_json, BEU32};
+use meilisearch_types::milli::index::{db_name, main_key};
+use meilisearch_types::milli::{obkv_to_json, RoaringBitmapLenCodec, BEU32};
 use meilisearch_types::tasks::{Status, Task};
-use meilisearch_types::versioning::check_version_file;
+use meilisearch_types::versioning::{check_version_file, get_version};
 use meilisearch_types::Index;
 use time::macros::format_description;
 use time::OffsetDateTime;
@@ -20,6 +21,12 @@ use uuid_codec::UuidCodec;
 
 mod uuid_codec;
 
+/// A conservative number of documents indexed per second, used to estimate the time of a migration.
+///
+/// The actual time mostly depends on the size of the documents, the settings of the indexes
+/// and the hardware.
+const ESTIMATED_DOCUMENTS_PER_SECOND: u64 = 5_000;
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
@@ -62,18 +69,30 @@ enum Command {
         #[arg(long)]
         skip_enqueued_tasks: bool,
     },
+
+    /// Reports what upgrading the database to the version of this tool implies, without
+    /// modifying anything.
+    ///
+    /// Prints the version of the database, the internal databases that would need to be rebuilt,
+    /// and an estimation of the time the migration would take. The databases are only rebuilt
+    /// by exporting a dump with the version of the database and importing it with the new one.
+    CheckUpgrade,
 }
 
 fn main() -> anyhow::Result<()> {
     let Cli { db_path, command } = Cli::parse();
 
-    check_version_file(&db_path).context("While checking the version file")?;
+    // the upgrade check is precisely about the databases of another version
+    if !matches!(command, Command::CheckUpgrade) {
+        check_version_file(&db_path).context("While checking the version file")?;
+    }
 
     match command {
         Command::ClearTaskQueue => clear_task_queue(db_path),
         Command::ExportADump { dump_dir, skip_enqueued_tasks } => {
             export_a_dump(db_path, dump_dir, skip_enqueued_tasks)
         }
+        Command::CheckUpgrade => check_upgrade(db_path),
     }
 }
 
@@ -312,3 +331,71 @@ fn export_a_dump(
 
     Ok(())
 }
+
+/// Reports the databases of `db_path` that must be rebuilt to be used by this version.
+fn check_upgrade(db_path: PathBuf) -> anyhow::Result<()> {
+    let current_version = env!("CARGO_PKG_VERSION");
+    let (major, minor, patch) = get_version(&db_path).context("While reading the version file")?;
+    println!("Database version: v{major}.{minor}.{patch}");
+    println!("Current version: v{current_version}");
+
+    if check_version_file(&db_path).is_ok() {
+        println!(
+            "The database is compatible with the current version, nothing needs to be rebuilt."
+        );
+        return Ok(());
+    }
+
+    let index_scheduler_path = db_path.join("tasks");
+    let env = open_read_only_env(&index_scheduler_path)?;
+    let rtxn = env.read_txn()?;
+    let all_tasks: Database<BEU32, DecodeIgnore> = try_opening_database(&env, &rtxn, "all-tasks")?;
+    let index_mapping: Database<Str, UuidCodec> =
+        try_opening_database(&env, &rtxn, "index-mapping")?;
+
+    println!("The following databases must be rebuilt:");
+    println!(
+        "  - the task queue: {} tasks, {}",
+        all_tasks.len(&rtxn)?,
+        human_size(&index_scheduler_path),
+    );
+    println!("  - the API keys: {}", human_size(&db_path.join("auth")));
+
+    let mut total_documents = 0;
+    for result in index_mapping.iter(&rtxn)? {
+        let (uid, uuid) = result?;
+        let index_path = db_path.join("indexes").join(uuid.to_string());
+        let index_env = open_read_only_env(&index_path)?;
+        let index_rtxn = index_env.read_txn()?;
+        let main: Database<Str, RoaringBitmapLenCodec> =
+            try_opening_database(&index_env, &index_rtxn, db_name::MAIN)?;
+        let documents = main.get(&index_rtxn, main_key::DOCUMENTS_IDS_KEY)?.unwrap_or_default();
+        total_documents += documents;
+        println!("  - the `{uid}` index: {documents} documents, {}", human_size(&index_path));
+    }
+
+    let seconds = total_documents / ESTIMATED_DOCUMENTS_PER_SECOND;
+    println!(
+        "Estimated migration time: {}, to export a dump with v{major}.{minor}.{patch} and import it with v{current_version}.",
+        time::Duration::seconds(seconds.max(1) as i64),
+    );
+
+    Ok(())
+}
+
+/// Opens the LMDB environment at `path` without ever writing to it.
+fn open_read_only_env(path: &Path) -> anyhow::Result<Env> {
+    let mut options = EnvOpenOptions::new();
+    options.max_dbs(100);
+    unsafe { options.flags(EnvFlags::READ_ONLY) };
+    unsafe { options.open(path) }
+        .with_context(|| format!("While trying to open {:?}", path.display()))
+}
+
+/// Returns the size of the LMDB environment at `path`, in a human readable form.
+fn human_size(path: &Path) -> String {
+    match metadata(path.join("data.mdb")) {
+        Ok(metadata) => format!("{:.1} MiB", metadata.len() as f64 / (1024.0 * 1024.0)),
+        Err(_) => "unknown size".to_string(),
+    }
+}
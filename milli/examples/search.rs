@@ -5,7 +5,7 @@ use std::time::Instant;
 
 use heed::EnvOpenOptions;
 use milli::{
-    execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, Index, SearchContext,
+    execute_search, filtered_universe, DefaultSearchLogger, GeoSortParameter, Index, SearchContext,
     SearchLogger, TermsMatchingStrategy, TimeBudget,
 };
 
@@ -60,7 +60,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 universe,
                 &None,
                 &None,
-                GeoSortStrategy::default(),
+                GeoSortParameter::default(),
                 0,
                 20,
                 None,
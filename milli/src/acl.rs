@@ -0,0 +1,107 @@
+//! Document-level access control lists.
+//!
+//! A document can list the principals allowed to see it, e.g. users or groups, in its `_acl`
+//! array. The `_acl` field is always faceted, so that the documents visible to a list of
+//! principals are found with the facet bitmaps, and never searchable. The documents without
+//! any `_acl` field are visible to everyone.
+//!
+//! The `_acl` field of the documents added before it was faceted has no facets, so these
+//! documents would be visible to everyone. Such indexes refuse the filters on `_acl` until
+//! the next settings update indexes the facets of the field for all the documents.
+
+/// The reserved field listing the principals allowed to see a document.
+pub const RESERVED_ACL_FIELD_NAME: &str = "_acl";
+
+/// Returns the filter selecting the documents visible to any of the given principals.
+pub fn acl_filter(principals: &[String]) -> String {
+    if principals.is_empty() {
+        return format!("{RESERVED_ACL_FIELD_NAME} NOT EXISTS");
+    }
+    let principals = principals.iter().map(|principal| quote(principal)).collect::<Vec<_>>();
+    format!(
+        "{RESERVED_ACL_FIELD_NAME} NOT EXISTS OR {RESERVED_ACL_FIELD_NAME} IN [{}]",
+        principals.join(", ")
+    )
+}
+
+fn quote(string: &str) -> String {
+    format!("\"{}\"", string.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use heed::types::{Str, U8};
+
+    use super::*;
+    use crate::error::{Error, UserError};
+    use crate::index::main_key;
+    use crate::index::tests::TempIndex;
+    use crate::Filter;
+
+    #[test]
+    fn filter() {
+        insta::assert_snapshot!(acl_filter(&[]), @"_acl NOT EXISTS");
+        insta::assert_snapshot!(
+            acl_filter(&["group:sales".to_string(), "bob\" OR \"".to_string()]),
+            @r###"_acl NOT EXISTS OR _acl IN ["group:sales", "bob\" OR \""]"###
+        );
+    }
+
+    #[test]
+    fn visible_documents() {
+        let index = TempIndex::new();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "public" },
+                { "id": 1, "title": "sales", "_acl": ["group:sales"] },
+                { "id": 2, "title": "legal", "_acl": ["group:legal", "alice"] },
+                { "id": 3, "title": "nobody", "_acl": [] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        // the `_acl` field is never searchable but always filterable
+        let searchable = index.searchable_fields(&rtxn).unwrap();
+        assert!(!searchable.iter().any(|field| field == RESERVED_ACL_FIELD_NAME));
+
+        let visible = |principals: &[&str]| {
+            let principals: Vec<_> = principals.iter().map(|p| p.to_string()).collect();
+            let filter = acl_filter(&principals);
+            let filter = Filter::from_str(&filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>()
+        };
+        assert_eq!(visible(&[]), vec![0]);
+        assert_eq!(visible(&["alice"]), vec![0, 2]);
+        assert_eq!(visible(&["group:sales", "alice"]), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn acl_not_indexed() {
+        let index = TempIndex::new();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "public" },
+                { "id": 1, "title": "sales", "_acl": ["group:sales"] },
+            ]))
+            .unwrap();
+
+        // an index whose documents were added before the `_acl` field was faceted
+        let mut wtxn = index.write_txn().unwrap();
+        index.main.remap_types::<Str, U8>().delete(&mut wtxn, main_key::ACL_INDEXED).unwrap();
+        wtxn.commit().unwrap();
+
+        let filter = acl_filter(&["group:sales".to_string()]);
+        let filter = Filter::from_str(&filter).unwrap().unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let result = filter.evaluate(&rtxn, &index);
+        assert!(matches!(result, Err(Error::UserError(UserError::AclNotIndexed))));
+        drop(rtxn);
+
+        // any settings update indexes the `_acl` field
+        index.update_settings(|_| {}).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.acl_indexed(&rtxn).unwrap());
+        let visible = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(visible.into_iter().collect::<Vec<_>>(), vec![0, 1]);
+    }
+}
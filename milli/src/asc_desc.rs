@@ -8,6 +8,7 @@ use thiserror::Error;
 
 use crate::error::is_reserved_keyword;
 use crate::search::facet::BadGeoError;
+use crate::sort_expression::{SortExpression, SortExpressionError};
 use crate::{CriterionError, Error, UserError};
 
 /// This error type is never supposed to be shown to the end user.
@@ -20,6 +21,8 @@ pub enum AscDescError {
     InvalidSyntax { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a asc/desc rule.")]
     ReservedKeyword { name: String },
+    #[error("Invalid sort expression `{name}`: {error}.")]
+    InvalidExpression { name: String, error: SortExpressionError },
 }
 
 impl From<BadGeoError> for AscDescError {
@@ -34,7 +37,9 @@ impl From<AscDescError> for CriterionError {
             AscDescError::GeoError(_) => {
                 CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() }
             }
-            AscDescError::InvalidSyntax { name } => CriterionError::InvalidName { name },
+            AscDescError::InvalidSyntax { name } | AscDescError::InvalidExpression { name, .. } => {
+                CriterionError::InvalidName { name }
+            }
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoPoint") => {
                 CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() }
             }
@@ -53,6 +58,7 @@ impl From<AscDescError> for CriterionError {
 pub enum Member {
     Field(String),
     Geo([f64; 2]),
+    Expression(SortExpression),
 }
 
 impl FromStr for Member {
@@ -77,6 +83,9 @@ impl FromStr for Member {
                 }
                 Ok(Member::Geo([lat, lng]))
             }
+            None if SortExpression::is_expression(text) => SortExpression::parse(text)
+                .map(Member::Expression)
+                .map_err(|error| AscDescError::InvalidExpression { name: text.to_string(), error }),
             None => {
                 if is_reserved_keyword(text)
                     || text.starts_with("_geoRadius(")
@@ -97,6 +106,7 @@ impl fmt::Display for Member {
         match self {
             Member::Field(name) => f.write_str(name),
             Member::Geo([lat, lng]) => write!(f, "_geoPoint({}, {})", lat, lng),
+            Member::Expression(expression) => write!(f, "{expression}"),
         }
     }
 }
@@ -105,14 +115,21 @@ impl Member {
     pub fn field(&self) -> Option<&str> {
         match self {
             Member::Field(field) => Some(field),
-            Member::Geo(_) => None,
+            Member::Geo(_) | Member::Expression(_) => None,
         }
     }
 
     pub fn geo_point(&self) -> Option<&[f64; 2]> {
         match self {
             Member::Geo(point) => Some(point),
-            Member::Field(_) => None,
+            Member::Field(_) | Member::Expression(_) => None,
+        }
+    }
+
+    pub fn expression(&self) -> Option<&SortExpression> {
+        match self {
+            Member::Expression(expression) => Some(expression),
+            Member::Field(_) | Member::Geo(_) => None,
         }
     }
 }
@@ -159,6 +176,8 @@ pub enum SortError {
     InvalidName { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a sort expression.")]
     ReservedName { name: String },
+    #[error("Invalid sort expression `{name}`: {error}.")]
+    InvalidExpression { name: String, error: SortExpressionError },
     #[error("`{name}` is a reserved keyword and thus can't be used as a sort expression. \
                     Use the _geoPoint(latitude, longitude) built-in rule to sort on _geo field coordinates.")]
     ReservedNameForSettings { name: String },
@@ -172,6 +191,9 @@ impl From<AscDescError> for SortError {
         match error {
             AscDescError::GeoError(error) => SortError::ParseGeoError { error },
             AscDescError::InvalidSyntax { name } => SortError::InvalidName { name },
+            AscDescError::InvalidExpression { name, error } => {
+                SortError::InvalidExpression { name, error }
+            }
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoPoint") => {
                 SortError::BadGeoPointUsage { name }
             }
@@ -203,6 +225,7 @@ mod tests {
     use Member::*;
 
     use super::*;
+    use crate::sort_expression::Operator;
 
     #[test]
     fn parse_asc_desc() {
@@ -222,6 +245,14 @@ mod tests {
             ("_geoPoint(42.0002, 59.895):desc", Desc(Geo([42.0002, 59.895]))),
             ("_geoPoint(42., 59.):desc", Desc(Geo([42., 59.]))),
             ("truc(12, 13):desc", Desc(Field(S("truc(12, 13)")))),
+            (
+                "price / weight:asc",
+                Asc(Expression(SortExpression::Operation {
+                    operator: Operator::Div,
+                    left: Box::new(SortExpression::Field(S("price"))),
+                    right: Box::new(SortExpression::Field(S("weight"))),
+                })),
+            ),
         ];
 
         for (req, expected) in valid_req {
@@ -274,6 +305,13 @@ mod tests {
                 "_geoDistance(12, -2021):desc",
                 ReservedKeyword { name: S("_geoDistance(12, -2021)") },
             ),
+            (
+                "price / :asc",
+                InvalidExpression {
+                    name: S("price / "),
+                    error: SortExpressionError::UnexpectedEnd,
+                },
+            ),
         ];
 
         for (req, expected_error) in invalid_req {
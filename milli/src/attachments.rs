@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use serde_json::Value;
+
+use crate::error::UserError;
+
+/// The reserved field of the documents under which their attachments are given.
+///
+/// It maps the names of the attachments to their base64 encoded content, or to `null`
+/// to remove an attachment. The attachments are stored apart from the documents.
+pub const RESERVED_ATTACHMENTS_FIELD_NAME: &str = "_attachments";
+
+pub const DEFAULT_MAX_ATTACHMENT_SIZE: usize = 100 * 1024; // 100 KiB
+pub const DEFAULT_MAX_DOCUMENT_ATTACHMENTS_SIZE: usize = 1024 * 1024; // 1 MiB
+
+/// The maximum sizes of the attachments given in a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttachmentsQuotas {
+    /// The maximum decoded size of an attachment.
+    pub max_attachment_size: usize,
+    /// The maximum decoded size of all the attachments given in a single document.
+    pub max_document_attachments_size: usize,
+}
+
+impl Default for AttachmentsQuotas {
+    fn default() -> Self {
+        AttachmentsQuotas {
+            max_attachment_size: DEFAULT_MAX_ATTACHMENT_SIZE,
+            max_document_attachments_size: DEFAULT_MAX_DOCUMENT_ATTACHMENTS_SIZE,
+        }
+    }
+}
+
+/// The attachments given in a document, a `None` content meaning that the attachment
+/// must be removed.
+pub type ParsedAttachments = BTreeMap<String, Option<Vec<u8>>>;
+
+/// Decodes the attachments given in a document and checks that they fit in the quotas.
+pub fn parse_attachments(
+    document_id: &str,
+    value: &Value,
+    quotas: AttachmentsQuotas,
+) -> Result<ParsedAttachments, UserError> {
+    let invalid = |error: String| UserError::InvalidDocumentAttachments {
+        document_id: document_id.to_string(),
+        error,
+    };
+
+    let attachments = match value {
+        Value::Object(attachments) => attachments,
+        value => {
+            return Err(invalid(format!(
+                "The `{RESERVED_ATTACHMENTS_FIELD_NAME}` field must be an object mapping the names of the attachments to their base64 encoded content, found `{value}`."
+            )))
+        }
+    };
+
+    let mut parsed = ParsedAttachments::new();
+    let mut total_size = 0;
+    for (name, content) in attachments {
+        if name.is_empty() {
+            return Err(invalid(String::from("The name of an attachment cannot be empty.")));
+        }
+
+        let content = match content {
+            Value::Null => None,
+            Value::String(content) => {
+                let content = base64::engine::general_purpose::STANDARD.decode(content).map_err(
+                    |_| invalid(format!("The content of the attachment `{name}` is not valid base64.")),
+                )?;
+                if content.len() > quotas.max_attachment_size {
+                    return Err(invalid(format!(
+                        "The attachment `{name}` weighs {} bytes, while the maximum size of an attachment is {} bytes.",
+                        content.len(),
+                        quotas.max_attachment_size,
+                    )));
+                }
+                total_size += content.len();
+                Some(content)
+            }
+            value => {
+                return Err(invalid(format!(
+                    "The content of the attachment `{name}` must be a base64 encoded string or `null`, found `{value}`."
+                )))
+            }
+        };
+        parsed.insert(name.clone(), content);
+    }
+
+    if total_size > quotas.max_document_attachments_size {
+        return Err(invalid(format!(
+            "The attachments weigh {total_size} bytes, while the maximum size of the attachments of a document is {} bytes.",
+            quotas.max_document_attachments_size,
+        )));
+    }
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn attachments_quotas() {
+        let quotas = AttachmentsQuotas { max_attachment_size: 4, max_document_attachments_size: 6 };
+
+        // `kefir` and `doggo` encoded in base64
+        let attachments =
+            parse_attachments("1", &json!({ "a.txt": "a2VmaXI=", "b.txt": null }), quotas);
+        insta::assert_snapshot!(attachments.unwrap_err(), @"Invalid `_attachments` field in the document with id: `1`. The attachment `a.txt` weighs 5 bytes, while the maximum size of an attachment is 4 bytes.");
+
+        let quotas = AttachmentsQuotas { max_attachment_size: 5, max_document_attachments_size: 6 };
+        let attachments =
+            parse_attachments("1", &json!({ "a.txt": "a2VmaXI=", "b.txt": null }), quotas).unwrap();
+        assert_eq!(attachments.get("a.txt"), Some(&Some(b"kefir".to_vec())));
+        assert_eq!(attachments.get("b.txt"), Some(&None));
+
+        let attachments =
+            parse_attachments("1", &json!({ "a.txt": "a2VmaXI=", "b.txt": "ZG9nZ28=" }), quotas);
+        insta::assert_snapshot!(attachments.unwrap_err(), @"Invalid `_attachments` field in the document with id: `1`. The attachments weigh 10 bytes, while the maximum size of the attachments of a document is 6 bytes.");
+
+        let attachments = parse_attachments("1", &json!(["a2VmaXI="]), quotas);
+        insta::assert_snapshot!(attachments.unwrap_err(), @r###"Invalid `_attachments` field in the document with id: `1`. The `_attachments` field must be an object mapping the names of the attachments to their base64 encoded content, found `["a2VmaXI="]`."###);
+    }
+}
@@ -0,0 +1,59 @@
+//! The limits of the batches of tasks processed on an index.
+//!
+//! The task scheduler merges the enqueued tasks of an index into a single batch, which speeds
+//! up the indexing but raises its peak memory usage with the size of the batch. An index can
+//! bound the number of tasks, and the total size of the document payloads, merged into one
+//! batch, trading some throughput for a steadier memory usage.
+//!
+//! The first enqueued task is always processed, even when its payload alone exceeds the limit.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchLimits {
+    /// The maximum number of tasks merged into a batch, unlimited when `None`.
+    pub max_batch_size: Option<u64>,
+    /// The maximum total size in bytes of the document payloads merged into a batch,
+    /// unlimited when `None`.
+    pub max_batch_payload: Option<u64>,
+}
+
+impl BatchLimits {
+    /// Returns how many of the enqueued tasks, whose payload sizes are given in order,
+    /// can be merged into the next batch.
+    pub fn batchable_tasks(&self, payload_sizes: impl IntoIterator<Item = u64>) -> usize {
+        let max_size = self.max_batch_size.map_or(usize::MAX, |size| size.max(1) as usize);
+        let mut total_payload = 0u64;
+        let mut count = 0;
+        for size in payload_sizes.into_iter().take(max_size) {
+            total_payload = total_payload.saturating_add(size);
+            if count > 0 && self.max_batch_payload.is_some_and(|max| total_payload > max) {
+                break;
+            }
+            count += 1;
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batchable_tasks() {
+        let limits = BatchLimits::default();
+        assert_eq!(limits.batchable_tasks([10, 20, 30]), 3);
+
+        let limits = BatchLimits { max_batch_size: Some(2), max_batch_payload: None };
+        assert_eq!(limits.batchable_tasks([10, 20, 30]), 2);
+
+        let limits = BatchLimits { max_batch_size: None, max_batch_payload: Some(30) };
+        assert_eq!(limits.batchable_tasks([10, 20, 30]), 2);
+        // the first task is always batched
+        assert_eq!(limits.batchable_tasks([50, 20]), 1);
+        // the tasks without payload are not limited by it
+        assert_eq!(limits.batchable_tasks([30, 0, 0, 1]), 3);
+    }
+}
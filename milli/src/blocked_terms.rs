@@ -0,0 +1,42 @@
+//! Terms that can never be searched, e.g. profanity.
+//!
+//! The blocked terms are checked while the query is parsed, before the derivations of its
+//! words are computed, and they are also removed from the derivations of the other words,
+//! so that the documents containing them can't be found through a typo, a prefix, a synonym
+//! or a split of another word.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+/// What happens to the queries containing a blocked term.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockedTermsMode {
+    /// The blocked terms are removed from the query, the rest of the query is searched.
+    #[default]
+    Drop,
+    /// The whole query is rejected with a `blocked_search_term` error.
+    Reject,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedTerms {
+    /// The blocked terms, normalized like the words of the documents.
+    #[serde(default)]
+    pub terms: BTreeSet<String>,
+    #[serde(default)]
+    pub mode: BlockedTermsMode,
+}
+
+impl BlockedTerms {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether the given normalized word is blocked.
+    pub fn contains(&self, word: &str) -> bool {
+        self.terms.contains(word)
+    }
+}
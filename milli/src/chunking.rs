@@ -0,0 +1,97 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+/// Splits the very long searchable fields into overlapping passages at indexing time.
+///
+/// The positions of the words restart at the beginning of each passage, so that the tail
+/// of a field longer than the maximum number of positions by attribute is still indexed.
+/// The embedders only receive the first passage of the rendered document template.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Chunking {
+    /// The number of words of a passage.
+    pub max_words: usize,
+    /// The number of words at the end of a passage that are repeated at the start of the next one,
+    /// so that the words close to a passage boundary are still considered close to each other.
+    #[serde(default)]
+    pub overlap: usize,
+}
+
+impl Chunking {
+    /// Returns the index and the range of words of each passage of a field made of `len` words.
+    ///
+    /// A field that isn't longer than a passage is made of a single passage.
+    pub fn passages(&self, len: usize) -> impl Iterator<Item = (u16, Range<usize>)> + '_ {
+        let stride = self.max_words.saturating_sub(self.overlap).max(1);
+        (0..=u16::MAX)
+            .map(move |passage| (passage, passage as usize * stride))
+            // a passage exists as long as the previous one didn't reach the end of the field
+            .take_while(move |&(passage, start)| passage == 0 || start + self.overlap < len)
+            .map(move |(passage, start)| (passage, start..len.min(start + self.max_words)))
+    }
+
+    /// Returns the first passage of the text, the words being separated by whitespaces.
+    pub fn first_passage<'t>(&self, text: &'t str) -> &'t str {
+        let mut words = 0;
+        let mut in_word = false;
+        for (i, c) in text.char_indices() {
+            if !c.is_whitespace() {
+                in_word = true;
+            } else if in_word {
+                in_word = false;
+                words += 1;
+                if words == self.max_words {
+                    return &text[..i];
+                }
+            }
+        }
+        text
+    }
+}
+
+/// Returns the position of a word extracted from a document, made of the index of its passage
+/// and of its relative position in the passage.
+pub fn passage_position(passage: u16, position: u16) -> u32 {
+    (passage as u32) << 16 | position as u32
+}
+
+/// Returns the index of the passage of a word extracted from a document.
+pub fn passage_of(position: u32) -> u16 {
+    (position >> 16) as u16
+}
+
+/// Returns the relative position of a word extracted from a document in its passage.
+pub fn position_in_passage(position: u32) -> u16 {
+    position as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passages_overlap() {
+        let chunking = Chunking { max_words: 4, overlap: 1 };
+        let passages: Vec<_> = chunking.passages(9).collect();
+        assert_eq!(passages, vec![(0, 0..4), (1, 3..7), (2, 6..9)]);
+
+        // the last passage ends exactly at the end of the field
+        let passages: Vec<_> = chunking.passages(7).collect();
+        assert_eq!(passages, vec![(0, 0..4), (1, 3..7)]);
+
+        let passages: Vec<_> = chunking.passages(2).collect();
+        assert_eq!(passages, vec![(0, 0..2)]);
+
+        let position = passage_position(2, 5);
+        assert_eq!((passage_of(position), position_in_passage(position)), (2, 5));
+    }
+
+    #[test]
+    fn first_passage() {
+        let chunking = Chunking { max_words: 3, overlap: 0 };
+        assert_eq!(chunking.first_passage("the quick  brown fox jumps"), "the quick  brown");
+        assert_eq!(chunking.first_passage("the quick brown "), "the quick brown");
+        assert_eq!(chunking.first_passage("the quick"), "the quick");
+    }
+}
@@ -0,0 +1,203 @@
+//! Locale-aware ordering of the string values for the sort.
+//!
+//! The facet strings are stored lowercased and decomposed, and the sort follows the order of
+//! their bytes: the case of the values is lost and the accented letters sort after all the
+//! unaccented ones, e.g. `éa` sorts after `ez`. A collation instead compares the strings on
+//! three levels, as the Unicode collation algorithm does:
+//!
+//! 1. the letters, without their accents and their case, in the alphabet of the locale;
+//! 2. the accents, an unaccented letter sorting before the accented ones;
+//! 3. the case, the lowercase letters sorting first unless the locale sorts the uppercase first.
+//!
+//! The locales that don't tailor the alphabet, like `en`, `fr` or `de`, share the same root
+//! collation.
+
+use std::collections::BTreeMap;
+
+use charabia::normalizer::{CharNormalizer, CompatibilityDecompositionNormalizer};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum CollationLocale {
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "sv")]
+    Swedish,
+    #[serde(rename = "fi")]
+    Finnish,
+    #[serde(rename = "da")]
+    Danish,
+    #[serde(rename = "nb")]
+    Norwegian,
+}
+
+/// The locales ordering the string values of the sortable attributes.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SortCollation {
+    /// The locale of all the sortable attributes, the strings are sorted by the bytes
+    /// of their normalized values when `None`.
+    pub locale: Option<CollationLocale>,
+    /// The locales of some sortable attributes, overriding the locale of the index.
+    pub attributes: BTreeMap<String, CollationLocale>,
+}
+
+impl SortCollation {
+    /// Returns the locale ordering the strings of the given attribute, if any.
+    pub fn locale(&self, field_name: &str) -> Option<CollationLocale> {
+        self.attributes.get(field_name).copied().or(self.locale)
+    }
+}
+
+impl CollationLocale {
+    /// Returns the key ordering the string in this locale, the strings being compared
+    /// by comparing their keys.
+    pub fn sort_key(&self, value: &str) -> Vec<u32> {
+        let mut primary = Vec::new();
+        let mut secondary = Vec::new();
+        let mut tertiary = Vec::new();
+
+        for c in value.trim().chars() {
+            let case = u32::from(c.is_uppercase() != self.uppercase_first());
+            let lower = c.to_lowercase().next().unwrap_or(c);
+
+            if let Some(weight) = self.tailoring(lower) {
+                primary.push(weight);
+                secondary.push(0);
+                tertiary.push(case);
+                continue;
+            }
+
+            let expansion = match lower {
+                'ß' => "ss",
+                'æ' => "ae",
+                'œ' => "oe",
+                _ => "",
+            };
+            let mut buffer = [0; 4];
+            let letters = if expansion.is_empty() {
+                CompatibilityDecompositionNormalizer
+                    .normalize_str(lower.encode_utf8(&mut buffer))
+                    .to_string()
+            } else {
+                expansion.to_string()
+            };
+
+            for letter in letters.chars() {
+                if is_combining_mark(letter) {
+                    // only the first accent of a letter is compared
+                    if let Some(accent) = secondary.last_mut().filter(|accent| **accent == 0) {
+                        *accent = letter as u32;
+                    }
+                } else {
+                    primary.push(primary_weight(letter));
+                    secondary.push(0);
+                    tertiary.push(case);
+                }
+            }
+        }
+
+        let mut key = primary;
+        key.push(0);
+        key.extend(secondary);
+        key.push(0);
+        key.extend(tertiary);
+        key
+    }
+
+    /// Returns the weight of the letters sorted as distinct letters of the alphabet of the locale.
+    fn tailoring(&self, lower: char) -> Option<u32> {
+        let after_z = |rank| primary_weight('z') + rank;
+        match self {
+            CollationLocale::Swedish | CollationLocale::Finnish => match lower {
+                'å' => Some(after_z(1)),
+                'ä' | 'æ' => Some(after_z(2)),
+                'ö' | 'ø' => Some(after_z(3)),
+                _ => None,
+            },
+            CollationLocale::Danish | CollationLocale::Norwegian => match lower {
+                'æ' | 'ä' => Some(after_z(1)),
+                'ø' | 'ö' => Some(after_z(2)),
+                'å' => Some(after_z(3)),
+                _ => None,
+            },
+            CollationLocale::Spanish => match lower {
+                'ñ' => Some(primary_weight('n') + 1),
+                _ => None,
+            },
+            CollationLocale::English | CollationLocale::French | CollationLocale::German => None,
+        }
+    }
+
+    fn uppercase_first(&self) -> bool {
+        matches!(self, CollationLocale::Danish)
+    }
+}
+
+/// The weights of the letters leave room for three tailored letters after each of them.
+fn primary_weight(letter: char) -> u32 {
+    (letter as u32 + 1) << 2
+}
+
+fn is_combining_mark(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE20}'..='\u{FE2F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(locale: CollationLocale, words: &[&str]) -> Vec<String> {
+        let mut words: Vec<_> = words.iter().map(|word| word.to_string()).collect();
+        words.sort_by_key(|word| locale.sort_key(word));
+        words
+    }
+
+    #[test]
+    fn accents_sort_next_to_their_letter() {
+        let words = ["ez", "éa", "eb", "f", "ea"];
+        assert_eq!(sorted(CollationLocale::French, &words), ["ea", "éa", "eb", "ez", "f"]);
+    }
+
+    #[test]
+    fn case_follows_the_locale() {
+        let words = ["Apple", "apple", "banana", "Banana"];
+        assert_eq!(
+            sorted(CollationLocale::English, &words),
+            ["apple", "Apple", "banana", "Banana"]
+        );
+        assert_eq!(sorted(CollationLocale::Danish, &words), ["Apple", "apple", "Banana", "banana"]);
+    }
+
+    #[test]
+    fn tailored_alphabets() {
+        let words = ["ångström", "zebra", "ärlig", "apa", "öga"];
+        assert_eq!(
+            sorted(CollationLocale::Swedish, &words),
+            ["apa", "zebra", "ångström", "ärlig", "öga"]
+        );
+        assert_eq!(
+            sorted(CollationLocale::German, &words),
+            ["ångström", "apa", "ärlig", "öga", "zebra"]
+        );
+
+        let words = ["nube", "ñu", "oso"];
+        assert_eq!(sorted(CollationLocale::Spanish, &words), ["nube", "ñu", "oso"]);
+        assert_eq!(sorted(CollationLocale::English, &words), ["ñu", "nube", "oso"]);
+
+        assert_eq!(
+            sorted(CollationLocale::German, &["strasse", "straße", "strase"]),
+            ["strase", "strasse", "straße"]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Fields computed at search time from an expression over the other fields of the documents,
+//! e.g. `price * (1 - discount)`.
+//!
+//! They are returned in the hits and can be used to sort, so changing their expression
+//! doesn't require any reindexing. The fields of the documents are available as variables
+//! in the expressions, as long as their name is a valid identifier.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rhai::{Dynamic, Engine, OptimizationLevel, Scope, AST};
+use serde_json::Value;
+
+use crate::error::UserError;
+use crate::Object;
+
+/// The compiled expressions of the computed fields of an index.
+pub struct ComputedFields {
+    engine: Engine,
+    fields: BTreeMap<String, ComputedField>,
+}
+
+struct ComputedField {
+    ast: AST,
+    /// The identifiers of the expression, a superset of the fields it reads.
+    variables: BTreeSet<String>,
+}
+
+impl ComputedFields {
+    /// Compiles the expressions of the computed fields, mapped by the name of the fields.
+    pub fn new(expressions: &BTreeMap<String, String>) -> Result<Self, UserError> {
+        // The expressions are evaluated on every hit, they must stay lightweight.
+        let mut engine = Engine::new();
+        engine.set_optimization_level(OptimizationLevel::Full);
+        engine.set_max_call_levels(10);
+        engine.set_max_operations(10_000);
+        engine.set_max_expr_depths(100, 100);
+        engine.set_max_string_size(1024 * 1024); // 1 MiB
+        engine.set_max_array_size(10_000);
+        engine.set_max_map_size(10_000);
+
+        let fields = expressions
+            .iter()
+            .map(|(name, expression)| match engine.compile_expression(expression) {
+                Ok(ast) => {
+                    Ok((name.clone(), ComputedField { ast, variables: identifiers(expression) }))
+                }
+                Err(error) => Err(UserError::InvalidComputedField {
+                    name: name.clone(),
+                    error: error.to_string(),
+                }),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(ComputedFields { engine, fields })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.fields.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.fields.keys().map(String::as_str)
+    }
+
+    /// Returns the names of the fields of the documents the expression of a computed field
+    /// may read, so that only these fields need to be decoded to evaluate it.
+    pub fn variables(&self, name: &str) -> impl Iterator<Item = &str> {
+        self.fields
+            .get(name)
+            .into_iter()
+            .flat_map(|field| field.variables.iter().map(String::as_str))
+    }
+
+    /// Evaluates the expression of a computed field on a document.
+    ///
+    /// Returns `None` if the field isn't computed or if its evaluation failed,
+    /// e.g. on a document missing one of the fields of the expression.
+    pub fn compute(&self, name: &str, document: &Object) -> Option<Value> {
+        let field = self.fields.get(name)?;
+        self.evaluate(&mut document_scope(document), &field.ast)
+    }
+
+    /// Evaluates all the computed fields on a document, skipping the ones that failed.
+    pub fn compute_all(&self, document: &Object) -> Vec<(String, Value)> {
+        let mut scope = document_scope(document);
+        self.fields
+            .iter()
+            .filter_map(|(name, field)| {
+                Some((name.clone(), self.evaluate(&mut scope, &field.ast)?))
+            })
+            .collect()
+    }
+
+    fn evaluate(&self, scope: &mut Scope<'_>, ast: &AST) -> Option<Value> {
+        let result: Dynamic = self.engine.eval_ast_with_scope(scope, ast).ok()?;
+        if result.is_unit() {
+            return None;
+        }
+        rhai::serde::from_dynamic(&result).ok()
+    }
+}
+
+/// Returns the words of an expression that could be variables, including its keywords,
+/// functions and the words of its string literals.
+fn identifiers(expression: &str) -> BTreeSet<String> {
+    expression
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| word.chars().next().map_or(false, |c| !c.is_ascii_digit()))
+        .map(str::to_string)
+        .collect()
+}
+
+fn document_scope(document: &Object) -> Scope<'static> {
+    let mut scope = Scope::new();
+    for (field, value) in document {
+        if let Ok(value) = rhai::serde::to_dynamic(value) {
+            scope.push_constant_dynamic(field.as_str(), value);
+        }
+    }
+    scope
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn compute_fields() {
+        let expressions = BTreeMap::from([
+            ("finalPrice".to_string(), "price * (1 - discount)".to_string()),
+            ("label".to_string(), r#"name + " (" + brand + ")""#.to_string()),
+        ]);
+        let computed_fields = ComputedFields::new(&expressions).unwrap();
+
+        let document = json!({ "price": 100, "discount": 0.25, "name": "shoe", "brand": "kefir" });
+        let document = document.as_object().unwrap();
+        assert_eq!(computed_fields.compute("finalPrice", document), Some(json!(75.0)));
+        assert_eq!(computed_fields.compute("label", document), Some(json!("shoe (kefir)")));
+        assert_eq!(computed_fields.compute("unknown", document), None);
+        let variables: Vec<_> = computed_fields.variables("finalPrice").collect();
+        assert_eq!(variables, ["discount", "price"]);
+
+        // the document misses the `discount` and `brand` fields
+        let document = json!({ "price": 100, "name": "shoe" });
+        assert_eq!(computed_fields.compute_all(document.as_object().unwrap()), vec![]);
+
+        let expressions = BTreeMap::from([("broken".to_string(), "price *".to_string())]);
+        let error = ComputedFields::new(&expressions).err().unwrap();
+        assert!(matches!(error, UserError::InvalidComputedField { name, .. } if name == "broken"));
+    }
+}
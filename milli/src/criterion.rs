@@ -22,6 +22,166 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error(
+        "`{name}` ranking rule is invalid. The recency ranking rule must be written as \
+`recency(field, half_life)`, with a half-life made of a number and a unit among `s`, `m`, `h` and `d`, e.g. `recency(published_at, 7d)`"
+    )]
+    InvalidRecency { name: String },
+    #[error(
+        "`{name}` ranking rule is invalid. The decay ranking rule must be written as \
+`decay(field, scale=scale, type=type, origin=origin)`, with a scale made of a positive number, optionally followed by a unit among `s`, `m`, `h` and `d`, \
+a type among `exponential`, `linear` and `gauss`, and an origin made of `now` or an integer, e.g. `decay(published_at, scale=30d, type=exponential)`"
+    )]
+    InvalidDecay { name: String },
+}
+
+/// The time after which the boost of the recency ranking rule is divided by two.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct HalfLife {
+    pub seconds: u64,
+}
+
+impl FromStr for HalfLife {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<HalfLife, Self::Err> {
+        let unit_position = text.find(|c: char| !c.is_ascii_digit()).ok_or(())?;
+        let (value, unit) = text.split_at(unit_position);
+        let value: u64 = value.parse().map_err(|_| ())?;
+        let unit = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            _ => return Err(()),
+        };
+        match value.checked_mul(unit) {
+            Some(seconds) if seconds != 0 => Ok(HalfLife { seconds }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for HalfLife {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let units = [(24 * 60 * 60, "d"), (60 * 60, "h"), (60, "m")];
+        match units.iter().find(|(seconds, _)| self.seconds % seconds == 0) {
+            Some((seconds, unit)) => write!(f, "{}{unit}", self.seconds / seconds),
+            None => write!(f, "{}s", self.seconds),
+        }
+    }
+}
+
+/// The function with which the boost of the decay ranking rule decreases with the distance
+/// between the value of the field and the origin.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DecayFunction {
+    Exponential,
+    Linear,
+    Gauss,
+}
+
+impl DecayFunction {
+    /// Returns the boost, between 0 and 1, of a value at the given distance from the origin,
+    /// expressed in number of scales. The boost is always 0.5 at one scale from the origin.
+    pub fn boost(&self, distance: f64) -> f64 {
+        match self {
+            DecayFunction::Exponential => 0.5f64.powf(distance),
+            DecayFunction::Linear => (1.0 - distance / 2.0).max(0.0),
+            DecayFunction::Gauss => 0.5f64.powf(distance * distance),
+        }
+    }
+}
+
+impl FromStr for DecayFunction {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<DecayFunction, Self::Err> {
+        match text {
+            "exponential" => Ok(DecayFunction::Exponential),
+            "linear" => Ok(DecayFunction::Linear),
+            "gauss" => Ok(DecayFunction::Gauss),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for DecayFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecayFunction::Exponential => f.write_str("exponential"),
+            DecayFunction::Linear => f.write_str("linear"),
+            DecayFunction::Gauss => f.write_str("gauss"),
+        }
+    }
+}
+
+/// The distance from the origin at which the boost of the decay ranking rule is divided by two,
+/// either a duration for the dates or a number for the other numeric fields.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DecayScale {
+    Duration(HalfLife),
+    Number(u64),
+}
+
+impl DecayScale {
+    /// Returns the scale as a number, the durations being converted to seconds.
+    pub fn value(&self) -> f64 {
+        match self {
+            DecayScale::Duration(duration) => duration.seconds as f64,
+            DecayScale::Number(number) => *number as f64,
+        }
+    }
+}
+
+impl FromStr for DecayScale {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<DecayScale, Self::Err> {
+        match text.parse() {
+            Ok(0) => Err(()),
+            Ok(number) => Ok(DecayScale::Number(number)),
+            Err(_) => text.parse().map(DecayScale::Duration),
+        }
+    }
+}
+
+impl fmt::Display for DecayScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecayScale::Duration(duration) => write!(f, "{duration}"),
+            DecayScale::Number(number) => write!(f, "{number}"),
+        }
+    }
+}
+
+/// The value of the field getting the highest boost from the decay ranking rule.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DecayOrigin {
+    /// The current date, as a Unix timestamp in seconds.
+    #[default]
+    Now,
+    Number(i64),
+}
+
+impl FromStr for DecayOrigin {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<DecayOrigin, Self::Err> {
+        match text {
+            "now" => Ok(DecayOrigin::Now),
+            text => text.parse().map(DecayOrigin::Number).map_err(drop),
+        }
+    }
+}
+
+impl fmt::Display for DecayOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecayOrigin::Now => f.write_str("now"),
+            DecayOrigin::Number(number) => write!(f, "{number}"),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -45,6 +205,16 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the buckets of the ranking rule plugin registered under the name specified.
+    Plugin(String),
+    /// Sorted by a boost decaying exponentially with the age of the date of the field specified.
+    Recency { field: String, half_life: HalfLife },
+    /// Sorted by a boost decaying with the distance between the value of the field specified
+    /// and the origin, such as a recency or a popularity boost.
+    Decay { field: String, scale: DecayScale, function: DecayFunction, origin: DecayOrigin },
+    /// Sorted by the decreasing BM25 score of the query terms, computed from the number of
+    /// times they appear in the documents.
+    Bm25,
 }
 
 impl Criterion {
@@ -52,6 +222,7 @@ impl Criterion {
     pub fn field_name(&self) -> Option<&str> {
         match self {
             Criterion::Asc(name) | Criterion::Desc(name) => Some(name),
+            Criterion::Recency { field, .. } | Criterion::Decay { field, .. } => Some(field),
             _otherwise => None,
         }
     }
@@ -68,17 +239,82 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
-            text => match AscDesc::from_str(text)? {
-                AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
-                AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
-                AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
+            "relevancy:bm25" => Ok(Criterion::Bm25),
+            text => match AscDesc::from_str(text) {
+                Ok(AscDesc::Asc(Member::Field(field))) => Ok(Criterion::Asc(field)),
+                Ok(AscDesc::Desc(Member::Field(field))) => Ok(Criterion::Desc(field)),
+                Ok(AscDesc::Asc(Member::Geo(_))) | Ok(AscDesc::Desc(Member::Geo(_))) => {
                     Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
                 }
+                Ok(AscDesc::Asc(Member::Expression(_)))
+                | Ok(AscDesc::Desc(Member::Expression(_))) => {
+                    Err(CriterionError::InvalidName { name: text.to_string() })
+                }
+                Err(error) => {
+                    if let Some(name) = text.strip_prefix("plugin:").filter(|n| !n.is_empty()) {
+                        return Ok(Criterion::Plugin(name.to_string()));
+                    }
+                    if let Some(parameters) =
+                        text.strip_prefix("decay(").and_then(|s| s.strip_suffix(')'))
+                    {
+                        return parse_decay(parameters).ok_or_else(|| {
+                            CriterionError::InvalidDecay { name: text.to_string() }
+                        });
+                    }
+                    match text.strip_prefix("recency(").and_then(|s| s.strip_suffix(')')) {
+                        Some(parameters) => parse_recency(parameters).ok_or_else(|| {
+                            CriterionError::InvalidRecency { name: text.to_string() }
+                        }),
+                        None => Err(error)?,
+                    }
+                }
             },
         }
     }
 }
 
+/// Parses the `field, half_life` parameters of a recency ranking rule.
+fn parse_recency(parameters: &str) -> Option<Criterion> {
+    let (field, half_life) = parameters.rsplit_once(',')?;
+    let field = field.trim();
+    if field.is_empty() {
+        return None;
+    }
+    let half_life = half_life.trim().parse().ok()?;
+    Some(Criterion::Recency { field: field.to_string(), half_life })
+}
+
+/// Parses the `field, scale=scale, type=type, origin=origin` parameters of a decay ranking rule.
+///
+/// The scale is required, the type defaults to `exponential` and the origin to `now`.
+fn parse_decay(parameters: &str) -> Option<Criterion> {
+    let (mut scale, mut function, mut origin) = (None, None, None);
+    // the named parameters follow the field, whose name may contain commas
+    let mut field = parameters;
+    while let Some((rest, parameter)) = field.rsplit_once(',') {
+        let Some((name, value)) = parameter.split_once('=') else { break };
+        let value = value.trim();
+        match name.trim() {
+            "scale" if scale.is_none() => scale = Some(value.parse().ok()?),
+            "type" if function.is_none() => function = Some(value.parse().ok()?),
+            "origin" if origin.is_none() => origin = Some(value.parse().ok()?),
+            _ => return None,
+        }
+        field = rest;
+    }
+
+    let field = field.trim();
+    if field.is_empty() {
+        return None;
+    }
+    Some(Criterion::Decay {
+        field: field.to_string(),
+        scale: scale?,
+        function: function.unwrap_or(DecayFunction::Exponential),
+        origin: origin.unwrap_or_default(),
+    })
+}
+
 pub fn default_criteria() -> Vec<Criterion> {
     vec![
         Criterion::Words,
@@ -103,6 +339,15 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            Plugin(name) => write!(f, "plugin:{}", name),
+            Recency { field, half_life } => write!(f, "recency({field}, {half_life})"),
+            Decay { field, scale, function, origin: DecayOrigin::Now } => {
+                write!(f, "decay({field}, scale={scale}, type={function})")
+            }
+            Decay { field, scale, function, origin } => {
+                write!(f, "decay({field}, scale={scale}, type={function}, origin={origin})")
+            }
+            Bm25 => f.write_str("relevancy:bm25"),
         }
     }
 }
@@ -129,6 +374,48 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            ("plugin:boost", Criterion::Plugin(S("boost"))),
+            ("plugin:asc", Criterion::Asc(S("plugin"))),
+            (
+                "recency(published_at, 7d)",
+                Criterion::Recency {
+                    field: S("published_at"),
+                    half_life: HalfLife { seconds: 7 * 24 * 60 * 60 },
+                },
+            ),
+            (
+                "recency(a,b, 90m)",
+                Criterion::Recency { field: S("a,b"), half_life: HalfLife { seconds: 90 * 60 } },
+            ),
+            ("relevancy:bm25", Criterion::Bm25),
+            ("relevancy:asc", Criterion::Asc(S("relevancy"))),
+            (
+                "decay(published_at, scale=30d, type=exponential)",
+                Criterion::Decay {
+                    field: S("published_at"),
+                    scale: DecayScale::Duration(HalfLife { seconds: 30 * 24 * 60 * 60 }),
+                    function: DecayFunction::Exponential,
+                    origin: DecayOrigin::Now,
+                },
+            ),
+            (
+                "decay(a,b, origin=1000, scale=50)",
+                Criterion::Decay {
+                    field: S("a,b"),
+                    scale: DecayScale::Number(50),
+                    function: DecayFunction::Exponential,
+                    origin: DecayOrigin::Number(1000),
+                },
+            ),
+            (
+                "decay(views, scale=100, type=gauss, origin=-5)",
+                Criterion::Decay {
+                    field: S("views"),
+                    scale: DecayScale::Number(100),
+                    function: DecayFunction::Gauss,
+                    origin: DecayOrigin::Number(-5),
+                },
+            ),
         ];
 
         for (input, expected) in valid_criteria {
@@ -153,6 +440,27 @@ mod tests {
             ("price:aasc", InvalidName { name: S("price:aasc") }),
             ("price:asc and desc", InvalidName { name: S("price:asc and desc") }),
             ("price:asc:truc", InvalidName { name: S("price:asc:truc") }),
+            ("plugin:", InvalidName { name: S("plugin:") }),
+            ("relevancy:bm26", InvalidName { name: S("relevancy:bm26") }),
+            ("price / weight:asc", InvalidName { name: S("price / weight:asc") }),
+            ("recency(published_at)", InvalidRecency { name: S("recency(published_at)") }),
+            ("recency(published_at, 7y)", InvalidRecency { name: S("recency(published_at, 7y)") }),
+            ("recency(published_at, 0d)", InvalidRecency { name: S("recency(published_at, 0d)") }),
+            ("recency(, 7d)", InvalidRecency { name: S("recency(, 7d)") }),
+            ("decay(published_at)", InvalidDecay { name: S("decay(published_at)") }),
+            (
+                "decay(published_at, scale=0)",
+                InvalidDecay { name: S("decay(published_at, scale=0)") },
+            ),
+            (
+                "decay(published_at, scale=7d, type=cubic)",
+                InvalidDecay { name: S("decay(published_at, scale=7d, type=cubic)") },
+            ),
+            (
+                "decay(published_at, scale=7d, scale=8d)",
+                InvalidDecay { name: S("decay(published_at, scale=7d, scale=8d)") },
+            ),
+            ("decay(scale=7d)", InvalidDecay { name: S("decay(scale=7d)") }),
             ("_geo:asc", ReservedName { name: S("_geo") }),
             ("_geoDistance:asc", ReservedName { name: S("_geoDistance") }),
             ("_geoPoint:asc", ReservedNameForSort { name: S("_geoPoint") }),
@@ -0,0 +1,149 @@
+//! A WebAssembly module executed on each document at ingestion time, e.g. to derive fields,
+//! redact personal information or normalize the values.
+//!
+//! The module is sandboxed: it can't import anything from the host, its memory is limited to
+//! [`MAX_DOCUMENT_HOOK_MEMORY`] and its execution is limited by an amount of fuel, roughly one
+//! unit of fuel by executed instruction, that is refilled for every document. It must export:
+//!  - a `memory`,
+//!  - an `alloc(len: i32) -> i32` function returning the address of `len` free bytes in the memory,
+//!  - a `transform(ptr: i32, len: i32) -> i64` function receiving the address and length of
+//!    the JSON object of a document, and returning the address and length of the JSON object
+//!    of the transformed document packed as `(ptr << 32) | len`.
+
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::result::Result as StdResult;
+
+use sha2::{Digest, Sha256};
+use wasmi::{Config, Engine, Instance, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::documents::{obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader};
+use crate::error::UserError;
+use crate::{Object, Result};
+
+/// The fuel given to the module for each document when none is configured.
+pub const DEFAULT_DOCUMENT_HOOK_FUEL: u64 = 10_000_000;
+/// The maximum size of the memory of the module, in bytes.
+pub const MAX_DOCUMENT_HOOK_MEMORY: usize = 256 * 1024 * 1024;
+
+/// The WebAssembly module of a document hook and the fuel given to it for each document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentHook {
+    pub module: Vec<u8>,
+    pub fuel: u64,
+}
+
+/// The hex encoded SHA-256 digest of a module, which identifies it without exposing it.
+pub fn module_sha256(module: &[u8]) -> String {
+    Sha256::digest(module).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// A compiled document hook, ready to transform documents.
+pub struct DocumentHookRunner {
+    engine: Engine,
+    module: Module,
+    fuel: u64,
+}
+
+impl DocumentHookRunner {
+    /// Compiles the module of the hook and checks that it exports what is expected.
+    pub fn new(hook: &DocumentHook) -> StdResult<Self, String> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &hook.module[..]).map_err(|e| e.to_string())?;
+
+        if let Some(import) = module.imports().next() {
+            return Err(format!(
+                "The module must not import anything but imports `{}::{}`.",
+                import.module(),
+                import.name()
+            ));
+        }
+
+        let runner = DocumentHookRunner { engine, module, fuel: hook.fuel };
+        let (mut store, instance) = runner.instantiate()?;
+        if instance.get_memory(&store, "memory").is_none() {
+            return Err("The module must export a `memory`.".to_string());
+        }
+        instance
+            .get_typed_func::<i32, i32>(&mut store, "alloc")
+            .map_err(|e| format!("The module must export an `alloc(i32) -> i32` function: {e}"))?;
+        instance.get_typed_func::<(i32, i32), i64>(&mut store, "transform").map_err(|e| {
+            format!("The module must export a `transform(i32, i32) -> i64` function: {e}")
+        })?;
+
+        Ok(runner)
+    }
+
+    /// Instantiates the module in a new store, filled with the fuel of a single document.
+    fn instantiate(&self) -> StdResult<(Store<StoreLimits>, Instance), String> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_DOCUMENT_HOOK_MEMORY)
+            .memories(1)
+            .tables(1)
+            .instances(1)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.add_fuel(self.fuel).map_err(|e| e.to_string())?;
+        let instance = Linker::<StoreLimits>::new(&self.engine)
+            .instantiate(&mut store, &self.module)
+            .and_then(|instance| instance.start(&mut store))
+            .map_err(|e| e.to_string())?;
+        Ok((store, instance))
+    }
+
+    /// Executes the hook on a document, in a fresh instance of the module.
+    pub fn transform(&self, document: &Object) -> StdResult<Object, String> {
+        let (mut store, instance) = self.instantiate()?;
+        let memory = instance.get_memory(&store, "memory").ok_or("missing `memory` export")?;
+        let alloc =
+            instance.get_typed_func::<i32, i32>(&mut store, "alloc").map_err(|e| e.to_string())?;
+        let transform = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, "transform")
+            .map_err(|e| e.to_string())?;
+
+        let input = serde_json::to_vec(document).map_err(|e| e.to_string())?;
+        let len = i32::try_from(input.len()).map_err(|_| "the document is too large")?;
+        let ptr = alloc.call(&mut store, len).map_err(|e| e.to_string())?;
+        memory.write(&mut store, ptr as u32 as usize, &input).map_err(|e| e.to_string())?;
+
+        let packed = transform.call(&mut store, (ptr, len)).map_err(|e| e.to_string())? as u64;
+        let (ptr, len) = ((packed >> 32) as usize, (packed & u32::MAX as u64) as usize);
+        // the output is read in place, its bounds are checked against the memory of the module
+        let output = ptr
+            .checked_add(len)
+            .and_then(|end| memory.data(&store).get(ptr..end))
+            .ok_or("The module returned an output out of the bounds of its memory.")?;
+
+        serde_json::from_slice(output)
+            .map_err(|e| format!("The module must return a JSON object: {e}"))
+    }
+}
+
+/// Executes the hook on every document of the batch and returns a batch of the transformed documents.
+pub(crate) fn apply_document_hook<R: Read + Seek>(
+    hook: &DocumentHook,
+    reader: DocumentsBatchReader<R>,
+) -> Result<StdResult<DocumentsBatchReader<File>, UserError>> {
+    let runner = match DocumentHookRunner::new(hook) {
+        Ok(runner) => runner,
+        Err(error) => return Ok(Err(UserError::InvalidDocumentHook(error))),
+    };
+
+    let (mut cursor, fields_index) = reader.into_cursor_and_fields_index();
+    let mut builder = tempfile::tempfile().map(DocumentsBatchBuilder::new)?;
+    let mut position = 0;
+    while let Some(document) = cursor.next_document()? {
+        let document = obkv_to_object(&document, &fields_index)?;
+        match runner.transform(&document) {
+            Ok(document) => builder.append_json_object(&document)?,
+            Err(error) => return Ok(Err(UserError::DocumentHookFailed { position, error })),
+        }
+        position += 1;
+    }
+
+    let file = builder.into_inner()?;
+    Ok(Ok(DocumentsBatchReader::from_reader(file)?))
+}
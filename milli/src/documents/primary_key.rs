@@ -100,6 +100,36 @@ impl<'a> PrimaryKey<'a> {
         }
     }
 
+    /// Returns the value of the primary key of the document with its original JSON type,
+    /// without validating it, e.g. to return the id of a document that is already indexed.
+    pub fn document_id_value(
+        &self,
+        document: &obkv::KvReader<'_, FieldId>,
+        fields: &impl FieldIdMapper,
+    ) -> Result<Option<Value>> {
+        match self {
+            PrimaryKey::Flat { name: _, field_id } => match document.get(*field_id) {
+                Some(document_id_bytes) => Ok(Some(
+                    serde_json::from_slice(document_id_bytes).map_err(InternalError::SerdeJson)?,
+                )),
+                None => Ok(None),
+            },
+            nested @ PrimaryKey::Nested { .. } => {
+                let mut matching_documents_ids = Vec::new();
+                for (first_level_name, right) in nested.possible_level_names() {
+                    if let Some(field_id) = fields.id(first_level_name) {
+                        if let Some(value_bytes) = document.get(field_id) {
+                            let object = serde_json::from_slice(value_bytes)
+                                .map_err(InternalError::SerdeJson)?;
+                            fetch_matching_values(object, right, &mut matching_documents_ids);
+                        }
+                    }
+                }
+                Ok(matching_documents_ids.pop())
+            }
+        }
+    }
+
     /// Returns an `Iterator` that gives all the possible fields names the primary key
     /// can have depending of the first level name and depth of the objects.
     pub fn possible_level_names(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
@@ -65,6 +65,10 @@ pub enum InternalError {
     AbortedIndexation,
     #[error("The matching words list contains at least one invalid member")]
     InvalidMatchingWords,
+    #[error("The encryption of a PII attribute failed")]
+    PiiEncryption,
+    #[error("The `{0}` float cannot be represented as a JSON number")]
+    NonFiniteFloat(f64),
     #[error(transparent)]
     ArroyError(#[from] arroy::Error),
     #[error(transparent)]
@@ -122,6 +126,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidVectorsMapType { document_id: String, value: Value },
     #[error("Bad embedder configuration in the document with id: `{document_id}`. {error}")]
     InvalidVectorsEmbedderConf { document_id: String, error: deserr::errors::JsonError },
+    #[error("Invalid `_attachments` field in the document with id: `{document_id}`. {error}")]
+    InvalidDocumentAttachments { document_id: String, error: String },
     #[error("{0}")]
     InvalidFilter(String),
     #[error("Invalid type for filter subexpression: expected: {}, found: {1}.", .0.join(", "))]
@@ -148,6 +154,17 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         }
     )]
     InvalidDistinctAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Attribute `{}` is not filterable and thus, cannot be used to diversify the results. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured filterable attributes.".to_string(),
+            false => format!("Available filterable attributes are: `{}{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidDiversifyAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
     #[error("Attribute `{}` is not facet-searchable. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -177,6 +194,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidLmdbOpenOptions,
     #[error("You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.")]
     SortRankingRuleMissing,
+    #[error("The `plugin:{name}` ranking rule is not available, no ranking rule plugin is registered under the name `{name}`.")]
+    UnknownRankingRulePlugin { name: String },
     #[error("The database file is in an invalid state.")]
     InvalidStoreFile,
     #[error("Maximum database size has been reached.")]
@@ -201,6 +220,36 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     UnknownInternalDocumentId { document_id: DocumentId },
     #[error("`minWordSizeForTypos` setting is invalid. `oneTypo` and `twoTypos` fields should be between `0` and `255`, and `twoTypos` should be greater or equals to `oneTypo` but found `oneTypo: {0}` and twoTypos: {1}`.")]
     InvalidMinTypoWordLenSetting(u8, u8),
+    #[error("`typoCosts` setting is invalid. `twoTypos` should be greater or equals to `oneTypo` but found `oneTypo: {0}` and `twoTypos: {1}`.")]
+    InvalidTypoCostsSetting(u32, u32),
+    #[error("`chunking` setting is invalid. `overlap` should be smaller than `maxWords` but found `maxWords: {max_words}` and `overlap: {overlap}`.")]
+    InvalidChunkingOverlap { max_words: usize, overlap: usize },
+    #[error("`documentHook` setting is invalid. {0}")]
+    InvalidDocumentHook(String),
+    #[error("The document hook failed on the document at position {position}: {error}")]
+    DocumentHookFailed { position: usize, error: String },
+    #[error("`computedFields` setting is invalid. The expression of the `{name}` computed field is invalid: {error}")]
+    InvalidComputedField { name: String, error: String },
+    #[error("`preparedFilters` setting is invalid. The `{name}` prepared filter is not a valid filter once its parameters are given: {error}")]
+    InvalidPreparedFilter { name: String, error: String },
+    #[error("The PII attributes of the documents can't be encrypted or decrypted because no PII encryption key was given to the engine at startup.")]
+    MissingPiiEncryptionKey,
+    #[error("`piiAttributes` setting is invalid. The `{name}` attribute is nested in another one, only the top-level attributes can be PII: make the whole `{parent}` attribute PII instead.")]
+    InvalidPiiAttribute { name: String, parent: String },
+    #[error("The `_acl` field of the documents of this index isn't indexed yet, the searches restricted to principals can't be answered. Update any setting of the index to index it.")]
+    AclNotIndexed,
+    #[error("The parameters of the `{name}` prepared filter are invalid. {error}")]
+    InvalidPreparedFilterParameters { name: String, error: String },
+    #[error("The `{name}` prepared filter doesn't exist. Prepared filters are defined in the `preparedFilters` setting of the index.")]
+    UnknownPreparedFilter { name: String },
+    #[error("`queryRewrites` setting is invalid. The rule finding `{find}` is invalid: {error}")]
+    InvalidQueryRewriteRule { find: String, error: String },
+    #[error("`{0}` is not a valid quote character. A quote character must be a single character that is neither alphanumeric nor a whitespace.")]
+    InvalidQuoteCharacter(String),
+    #[error("The `{pattern}` regular expression of the query is invalid: {error}")]
+    InvalidQueryRegex { pattern: String, error: String },
+    #[error("The query contains the blocked term `{0}`.")]
+    BlockedSearchTerm(String),
     #[error(transparent)]
     VectorEmbeddingError(#[from] crate::vector::Error),
     #[error(transparent)]
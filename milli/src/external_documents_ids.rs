@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use heed::types::Str;
-use heed::{Database, RoIter, RoTxn, RwTxn};
+use heed::{Database, RoIter, RoRevIter, RoTxn, RwTxn};
 
 use crate::{DocumentId, BEU32};
 
@@ -80,4 +80,9 @@ impl ExternalDocumentsIds {
     pub fn iter<'t>(&self, rtxn: &'t RoTxn<'_>) -> heed::Result<RoIter<'t, Str, BEU32>> {
         self.0.iter(rtxn)
     }
+
+    /// Returns an iterator over all the external ids, in reverse order.
+    pub fn rev_iter<'t>(&self, rtxn: &'t RoTxn<'_>) -> heed::Result<RoRevIter<'t, Str, BEU32>> {
+        self.0.rev_iter(rtxn)
+    }
 }
@@ -0,0 +1,100 @@
+use std::collections::{BTreeMap, HashMap};
+use std::iter;
+
+use heed::RoTxn;
+
+use crate::{normalize_facet, FieldId, Index, Result};
+
+/// The string values of a facet that are merged under a single displayed value,
+/// at search time, both in the facet distribution and in the filters.
+#[derive(Debug, Default)]
+pub struct FacetValueAliases {
+    merged: Vec<MergedValues>,
+    /// The position in `merged` of each normalized merged value.
+    positions: HashMap<String, usize>,
+}
+
+#[derive(Debug)]
+struct MergedValues {
+    displayed: String,
+    normalized: String,
+    /// The normalized values, including the displayed one.
+    values: Vec<String>,
+}
+
+impl FacetValueAliases {
+    /// Builds the aliases from the values merged under each displayed value.
+    pub fn new(aliases: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut this = FacetValueAliases::default();
+        for (displayed, values) in aliases {
+            let position = this.merged.len();
+            let mut normalized_values: Vec<_> =
+                iter::once(displayed).chain(values).map(|value| normalize_facet(value)).collect();
+            normalized_values.sort_unstable();
+            normalized_values.dedup();
+
+            for value in &normalized_values {
+                this.positions.insert(value.clone(), position);
+            }
+            this.merged.push(MergedValues {
+                displayed: displayed.clone(),
+                normalized: normalize_facet(displayed),
+                values: normalized_values,
+            });
+        }
+        this
+    }
+
+    /// Returns the aliases defined for the given field.
+    pub fn of_field(index: &Index, rtxn: &RoTxn<'_>, field_id: FieldId) -> Result<Self> {
+        let mut aliases = index.facet_value_aliases(rtxn)?;
+        if aliases.is_empty() {
+            return Ok(FacetValueAliases::default());
+        }
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_aliases = fields_ids_map.name(field_id).and_then(|name| aliases.remove(name));
+        Ok(field_aliases.as_ref().map(FacetValueAliases::new).unwrap_or_default())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.merged.is_empty()
+    }
+
+    /// Returns the normalized and original displayed value of the given
+    /// normalized value, or `None` if the value isn't merged with others.
+    pub fn displayed_value(&self, normalized: &str) -> Option<(&str, &str)> {
+        let merged = &self.merged[*self.positions.get(normalized)?];
+        Some((&merged.normalized, &merged.displayed))
+    }
+
+    /// Returns all the normalized values merged with the given normalized value,
+    /// or `None` if the value isn't merged with others.
+    pub fn merged_values(&self, normalized: &str) -> Option<&[String]> {
+        let merged = &self.merged[*self.positions.get(normalized)?];
+        Some(&merged.values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+    use maplit::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn merged_values_are_normalized() {
+        let aliases = FacetValueAliases::new(&btreemap! {
+            S("United States") => vec![S("US"), S(" usa "), S("united states")],
+        });
+
+        assert_eq!(aliases.displayed_value("usa"), Some(("united states", "United States")));
+        assert_eq!(aliases.displayed_value("united states").unwrap().1, "United States");
+        assert_eq!(aliases.displayed_value("france"), None);
+        assert_eq!(
+            aliases.merged_values("us").unwrap(),
+            &[S("united states"), S("us"), S("usa")][..]
+        );
+        assert!(FacetValueAliases::new(&BTreeMap::new()).is_empty());
+    }
+}
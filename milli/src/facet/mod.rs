@@ -1,6 +1,8 @@
 mod facet_type;
 mod facet_value;
+mod facet_value_aliases;
 pub mod value_encoding;
 
 pub use self::facet_type::FacetType;
 pub use self::facet_value::FacetValue;
+pub use self::facet_value_aliases::FacetValueAliases;
@@ -0,0 +1,135 @@
+//! The schema of the documents of an index, inferred from the values of their fields.
+//!
+//! The schema is updated incrementally when the documents are written, by counting the type of
+//! the values of the fields of the added documents and discounting the ones of the deleted
+//! documents, so that it doesn't require reading all the documents again.
+//!
+//! A few values of every field are kept as examples. They are not removed when the documents
+//! they come from are deleted, only when the field disappears from all the documents.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The number of example values kept for every field.
+pub const MAX_EXAMPLES: usize = 3;
+/// The maximum size of the JSON of an example value, the larger values are never kept.
+const MAX_EXAMPLE_SIZE: usize = 256;
+
+/// Associates every top-level field name with its schema.
+pub type FieldsSchema = BTreeMap<String, FieldSchema>;
+
+/// The type of a JSON value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValueType {
+    Null,
+    Boolean,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl ValueType {
+    /// Returns the type of a JSON value from its serialized form, without parsing it.
+    pub fn from_json(bytes: &[u8]) -> Option<Self> {
+        let first = bytes.iter().find(|b| !b.is_ascii_whitespace())?;
+        Some(match first {
+            b'n' => ValueType::Null,
+            b't' | b'f' => ValueType::Boolean,
+            b'"' => ValueType::String,
+            b'[' => ValueType::Array,
+            b'{' => ValueType::Object,
+            _ => ValueType::Number,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldSchema {
+    /// The number of documents containing this field.
+    pub documents: u64,
+    /// The number of documents containing a value of each type for this field.
+    pub types: BTreeMap<ValueType, u64>,
+    /// A few distinct values of this field.
+    pub examples: Vec<Value>,
+}
+
+/// Updates the schema of a field with the value it had before and after a document was written.
+///
+/// The values are the serialized JSON of the field, `None` when the document didn't or doesn't
+/// contain the field anymore.
+pub fn update_field_schema(
+    schema: &mut FieldsSchema,
+    field_name: &str,
+    deletion: Option<&[u8]>,
+    addition: Option<&[u8]>,
+) {
+    if let Some(value_type) = deletion.and_then(ValueType::from_json) {
+        if let Some(field) = schema.get_mut(field_name) {
+            field.documents = field.documents.saturating_sub(1);
+            if let Some(count) = field.types.get_mut(&value_type) {
+                *count = count.saturating_sub(1);
+                if *count == 0 {
+                    field.types.remove(&value_type);
+                }
+            }
+            if field.documents == 0 {
+                schema.remove(field_name);
+            }
+        }
+    }
+
+    if let Some((value, value_type)) =
+        addition.and_then(|value| ValueType::from_json(value).map(|t| (value, t)))
+    {
+        let field = schema.entry(field_name.to_string()).or_default();
+        field.documents += 1;
+        *field.types.entry(value_type).or_default() += 1;
+        if field.examples.len() < MAX_EXAMPLES && value.len() <= MAX_EXAMPLE_SIZE {
+            if let Ok(value) = serde_json::from_slice::<Value>(value) {
+                if !field.examples.contains(&value) {
+                    field.examples.push(value);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreemap;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn incremental_schema() {
+        let mut schema = FieldsSchema::new();
+        update_field_schema(&mut schema, "age", None, Some(b"20"));
+        update_field_schema(&mut schema, "age", None, Some(b"\"unknown\""));
+        update_field_schema(&mut schema, "age", None, Some(b"20"));
+        update_field_schema(&mut schema, "tags", None, Some(b"[\"a\"]"));
+
+        assert_eq!(
+            schema["age"],
+            FieldSchema {
+                documents: 3,
+                types: btreemap! { ValueType::Number => 2, ValueType::String => 1 },
+                examples: vec![json!(20), json!("unknown")],
+            }
+        );
+
+        // a document updated from a string to a number
+        update_field_schema(&mut schema, "age", Some(b"\"unknown\""), Some(b"35"));
+        assert_eq!(schema["age"].documents, 3);
+        assert_eq!(schema["age"].types, btreemap! { ValueType::Number => 3 });
+
+        // the field is removed with the last document containing it
+        update_field_schema(&mut schema, "tags", Some(b"[\"a\"]"), None);
+        assert!(!schema.contains_key("tags"));
+    }
+}
@@ -0,0 +1,30 @@
+use std::borrow::Cow;
+
+use heed::BoxedError;
+
+use super::SliceTooShortError;
+use crate::{try_split_array_at, FieldId};
+
+pub struct FieldIdBoolCodec;
+
+impl<'a> heed::BytesDecode<'a> for FieldIdBoolCodec {
+    type DItem = (FieldId, bool);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let (field_id_bytes, bytes) = try_split_array_at(bytes).ok_or(SliceTooShortError)?;
+        let field_id = u16::from_be_bytes(field_id_bytes);
+        let ([value], _nothing) = try_split_array_at(bytes).ok_or(SliceTooShortError)?;
+        Ok((field_id, value != 0))
+    }
+}
+
+impl<'a> heed::BytesEncode<'a> for FieldIdBoolCodec {
+    type EItem = (FieldId, bool);
+
+    fn bytes_encode((field_id, value): &Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let mut bytes = Vec::with_capacity(2 + 1);
+        bytes.extend_from_slice(&field_id.to_be_bytes());
+        bytes.push(u8::from(*value));
+        Ok(Cow::Owned(bytes))
+    }
+}
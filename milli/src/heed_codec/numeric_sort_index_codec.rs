@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+use std::mem::size_of;
+
+use heed::{BoxedError, BytesDecode, BytesEncode};
+
+use super::SliceTooShortError;
+use crate::DocumentId;
+
+/// A dense structure that associates every document id with the ordinal
+/// of its numeric facet value for a given field.
+///
+/// The ordinals are the positions of the values in `values`, which are
+/// sorted in ascending order. A document with several values is associated
+/// with its smallest value in `min_ordinals` and its biggest one in `max_ordinals`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NumericSortIndex {
+    pub values: Vec<f64>,
+    pub min_ordinals: Vec<u32>,
+    pub max_ordinals: Vec<u32>,
+}
+
+impl NumericSortIndex {
+    /// The ordinal of the documents that don't have any numeric value.
+    pub const MISSING: u32 = u32::MAX;
+
+    /// Returns the ordinal of the smallest value of the document, if any.
+    pub fn min_ordinal(&self, docid: DocumentId) -> Option<u32> {
+        self.min_ordinals.get(docid as usize).copied().filter(|&o| o != Self::MISSING)
+    }
+
+    /// Returns the ordinal of the biggest value of the document, if any.
+    pub fn max_ordinal(&self, docid: DocumentId) -> Option<u32> {
+        self.max_ordinals.get(docid as usize).copied().filter(|&o| o != Self::MISSING)
+    }
+}
+
+/// A [`NumericSortIndex`] read directly from the bytes of the database,
+/// so that the ordinals of a few documents can be read without decoding the whole index.
+#[derive(Debug, Clone, Copy)]
+pub struct NumericSortIndexView<'a> {
+    values: &'a [u8],
+    min_ordinals: &'a [u8],
+    max_ordinals: &'a [u8],
+}
+
+impl<'a> NumericSortIndexView<'a> {
+    /// Returns the value of the ordinal.
+    pub fn value(&self, ordinal: u32) -> Option<f64> {
+        read_le_bytes(self.values, ordinal as usize).map(f64::from_le_bytes)
+    }
+
+    /// Returns the ordinal of the smallest value of the document, if any.
+    pub fn min_ordinal(&self, docid: DocumentId) -> Option<u32> {
+        read_le_bytes(self.min_ordinals, docid as usize)
+            .map(u32::from_le_bytes)
+            .filter(|&o| o != NumericSortIndex::MISSING)
+    }
+
+    /// Returns the ordinal of the biggest value of the document, if any.
+    pub fn max_ordinal(&self, docid: DocumentId) -> Option<u32> {
+        read_le_bytes(self.max_ordinals, docid as usize)
+            .map(u32::from_le_bytes)
+            .filter(|&o| o != NumericSortIndex::MISSING)
+    }
+}
+
+/// Returns the `N` bytes of the element at the position in an array of elements of `N` bytes.
+fn read_le_bytes<const N: usize>(bytes: &[u8], position: usize) -> Option<[u8; N]> {
+    let start = position.checked_mul(N)?;
+    bytes.get(start..start.checked_add(N)?).map(|chunk| chunk.try_into().unwrap())
+}
+
+/// Encodes a [`NumericSortIndex`] as the number of values, the values and the two ordinals
+/// arrays, all in little endian.
+pub struct NumericSortIndexCodec;
+
+/// Decodes the bytes of a [`NumericSortIndexCodec`] as a [`NumericSortIndexView`].
+pub struct NumericSortIndexViewCodec;
+
+impl<'a> BytesDecode<'a> for NumericSortIndexViewCodec {
+    type DItem = NumericSortIndexView<'a>;
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let (values, ordinals) = split_values(bytes)?;
+        // both ordinals arrays have the same length
+        let (min_ordinals, max_ordinals) = ordinals.split_at(ordinals.len() / 2);
+        Ok(NumericSortIndexView { values, min_ordinals, max_ordinals })
+    }
+}
+
+/// Splits the bytes of a [`NumericSortIndexCodec`] into the values and the ordinals.
+fn split_values(bytes: &[u8]) -> Result<(&[u8], &[u8]), BoxedError> {
+    if bytes.len() < size_of::<u32>() {
+        return Err(SliceTooShortError.into());
+    }
+    let (len, bytes) = bytes.split_at(size_of::<u32>());
+    let len = u32::from_le_bytes(len.try_into()?) as usize;
+    if bytes.len() < len * size_of::<f64>() {
+        return Err(SliceTooShortError.into());
+    }
+    Ok(bytes.split_at(len * size_of::<f64>()))
+}
+
+impl<'a> BytesEncode<'a> for NumericSortIndexCodec {
+    type EItem = NumericSortIndex;
+
+    fn bytes_encode(item: &'a Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let NumericSortIndex { values, min_ordinals, max_ordinals } = item;
+        let mut bytes = Vec::with_capacity(
+            size_of::<u32>()
+                + values.len() * size_of::<f64>()
+                + (min_ordinals.len() + max_ordinals.len()) * size_of::<u32>(),
+        );
+        bytes.extend_from_slice(&(values.len() as u32).to_le_bytes());
+        values.iter().for_each(|value| bytes.extend_from_slice(&value.to_le_bytes()));
+        min_ordinals.iter().for_each(|ordinal| bytes.extend_from_slice(&ordinal.to_le_bytes()));
+        max_ordinals.iter().for_each(|ordinal| bytes.extend_from_slice(&ordinal.to_le_bytes()));
+        Ok(Cow::Owned(bytes))
+    }
+}
+
+impl<'a> BytesDecode<'a> for NumericSortIndexCodec {
+    type DItem = NumericSortIndex;
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let (values, ordinals) = split_values(bytes)?;
+        let values = values
+            .chunks_exact(size_of::<f64>())
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        // both ordinals arrays have the same length
+        let (min_ordinals, max_ordinals) = ordinals.split_at(ordinals.len() / 2);
+        let decode_ordinals = |bytes: &[u8]| {
+            bytes
+                .chunks_exact(size_of::<u32>())
+                .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+                .collect()
+        };
+
+        Ok(NumericSortIndex {
+            values,
+            min_ordinals: decode_ordinals(min_ordinals),
+            max_ordinals: decode_ordinals(max_ordinals),
+        })
+    }
+}
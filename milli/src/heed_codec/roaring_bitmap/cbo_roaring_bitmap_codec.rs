@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use std::io::{self, Cursor};
 use std::mem::size_of;
 
-use byteorder::{NativeEndian, ReadBytesExt, WriteBytesExt};
+use byteorder::{LittleEndian, NativeEndian, ReadBytesExt, WriteBytesExt};
 use heed::BoxedError;
 use roaring::RoaringBitmap;
 
@@ -14,6 +14,10 @@ use crate::update::del_add::{DelAdd, KvReaderDelAdd};
 /// to determine the encoding used only by using the array of bytes length.
 pub const THRESHOLD: usize = 7;
 
+/// The cookies at the start of a serialized RoaringBitmap, with and without run containers.
+pub(super) const SERIAL_COOKIE_NO_RUNCONTAINER: u32 = 12346;
+pub(super) const SERIAL_COOKIE: u16 = 12347;
+
 /// A conditionnal codec that either use the RoaringBitmap
 /// or a lighter ByteOrder en/decoding method.
 pub struct CboRoaringBitmapCodec;
@@ -75,6 +79,49 @@ impl CboRoaringBitmapCodec {
         }
     }
 
+    /// Returns `true` if the serialized bitmap is guaranteed not to share any integer with `other`.
+    ///
+    /// Only the header of a serialized RoaringBitmap is read: a container is considered
+    /// disjoint when `other` doesn't have any integer in its range, without being decoded.
+    /// It can therefore return `false` for bitmaps that are actually disjoint.
+    pub fn is_disjoint_with_serialized(
+        mut bytes: &[u8],
+        other: &RoaringBitmap,
+    ) -> io::Result<bool> {
+        // See above `deserialize_from` method for implementation details.
+        if bytes.len() <= THRESHOLD * size_of::<u32>() {
+            while let Ok(integer) = bytes.read_u32::<NativeEndian>() {
+                if other.contains(integer) {
+                    return Ok(false);
+                }
+            }
+            return Ok(true);
+        }
+
+        let cookie = bytes.read_u32::<LittleEndian>()?;
+        let containers = if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+            bytes.read_u32::<LittleEndian>()? as usize
+        } else if cookie as u16 == SERIAL_COOKIE {
+            let containers = (cookie >> 16) as usize + 1;
+            // skip the bitset marking the run containers
+            bytes = bytes.get((containers + 7) / 8..).ok_or(io::ErrorKind::UnexpectedEof)?;
+            containers
+        } else {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "unknown cookie value"));
+        };
+
+        for _ in 0..containers {
+            let start = (bytes.read_u16::<LittleEndian>()? as u32) << 16;
+            let _cardinality = bytes.read_u16::<LittleEndian>()?;
+            let before = if start == 0 { 0 } else { other.rank(start - 1) };
+            if other.rank(start | 0xFFFF) > before {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
     /// Merge serialized CboRoaringBitmaps in a buffer.
     ///
     /// if the merged values length is under the threshold, values are directly
@@ -209,6 +256,29 @@ mod tests {
         assert!(roaring_size > bo_size);
     }
 
+    #[test]
+    fn disjoint_with_serialized() {
+        let universe = RoaringBitmap::from_sorted_iter(200_000..200_010).unwrap();
+
+        for input in [
+            RoaringBitmap::from_sorted_iter(0..3).unwrap(),
+            RoaringBitmap::from_sorted_iter(0..1000).unwrap(),
+            RoaringBitmap::from_sorted_iter(0..100_000).unwrap(),
+        ] {
+            let bytes = CboRoaringBitmapCodec::bytes_encode(&input).unwrap();
+            assert!(CboRoaringBitmapCodec::is_disjoint_with_serialized(&bytes, &universe).unwrap());
+        }
+
+        let mut large = RoaringBitmap::from_sorted_iter(0..100_000).unwrap();
+        large.insert(200_005);
+        let bytes = CboRoaringBitmapCodec::bytes_encode(&large).unwrap();
+        assert!(!CboRoaringBitmapCodec::is_disjoint_with_serialized(&bytes, &universe).unwrap());
+
+        let small = RoaringBitmap::from_sorted_iter([3, 200_005]).unwrap();
+        let bytes = CboRoaringBitmapCodec::bytes_encode(&small).unwrap();
+        assert!(!CboRoaringBitmapCodec::is_disjoint_with_serialized(&bytes, &universe).unwrap());
+    }
+
     #[test]
     fn merge_cbo_roaring_bitmaps() {
         let mut buffer = Vec::new();
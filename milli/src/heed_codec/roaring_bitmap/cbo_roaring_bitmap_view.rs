@@ -0,0 +1,237 @@
+use std::io::{self, Cursor};
+use std::mem::size_of;
+
+use byteorder::{ByteOrder, LittleEndian, NativeEndian};
+use roaring::RoaringBitmap;
+
+use super::cbo_roaring_bitmap_codec::{SERIAL_COOKIE, SERIAL_COOKIE_NO_RUNCONTAINER, THRESHOLD};
+
+/// The maximum cardinality of an array container, above it a bitmap container is used.
+const ARRAY_CONTAINER_MAX_LEN: u32 = 4096;
+
+/// The size in bytes of a bitmap container.
+const BITMAP_CONTAINER_SIZE: usize = 8192;
+
+/// When there are less containers than that and some of them are run containers,
+/// the serialized bitmap doesn't contain the offsets header.
+const NO_OFFSET_THRESHOLD: usize = 4;
+
+/// A zero-copy view over a serialized CboRoaringBitmap.
+///
+/// Only the headers of the bitmap are read when creating the view, the containers
+/// are read directly from the bytes when needed. It is useful when only the
+/// cardinality of the bitmap, or of a small intersection with it, is needed.
+pub struct CboRoaringBitmapView<'a> {
+    bytes: &'a [u8],
+    kind: ViewKind<'a>,
+}
+
+enum ViewKind<'a> {
+    /// At most `THRESHOLD` integers encoded with byteorder.
+    Small,
+    /// The containers of a RoaringBitmap, sorted by key.
+    Roaring(Vec<Container<'a>>),
+}
+
+struct Container<'a> {
+    key: u16,
+    len: u32,
+    store: Store<'a>,
+}
+
+enum Store<'a> {
+    /// The sorted values, as little endian u16s.
+    Array(&'a [u8]),
+    /// The bits of the values, as little endian u64s.
+    Bitmap(&'a [u8]),
+    /// The starts and lengths minus one of the runs, as little endian u16s.
+    Run(&'a [u8]),
+}
+
+impl<'a> CboRoaringBitmapView<'a> {
+    pub fn new(bytes: &'a [u8]) -> io::Result<Self> {
+        // See the `CboRoaringBitmapCodec::deserialize_from` method for implementation details.
+        if bytes.len() <= THRESHOLD * size_of::<u32>() {
+            return Ok(CboRoaringBitmapView { bytes, kind: ViewKind::Small });
+        }
+
+        let (cookie, mut rest) = split_at(bytes, size_of::<u32>())?;
+        let cookie = LittleEndian::read_u32(cookie);
+        let (containers_len, run_bitset) = if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+            let (len, tail) = split_at(rest, size_of::<u32>())?;
+            rest = tail;
+            (LittleEndian::read_u32(len) as usize, None)
+        } else if cookie as u16 == SERIAL_COOKIE {
+            let len = (cookie >> 16) as usize + 1;
+            let (run_bitset, tail) = split_at(rest, (len + 7) / 8)?;
+            rest = tail;
+            (len, Some(run_bitset))
+        } else {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "unknown cookie value"));
+        };
+
+        let (descriptions, tail) = split_at(rest, containers_len * 2 * size_of::<u16>())?;
+        rest = tail;
+        if run_bitset.is_none() || containers_len >= NO_OFFSET_THRESHOLD {
+            let (_offsets, tail) = split_at(rest, containers_len * size_of::<u32>())?;
+            rest = tail;
+        }
+
+        let mut containers = Vec::with_capacity(containers_len);
+        for (i, description) in descriptions.chunks_exact(2 * size_of::<u16>()).enumerate() {
+            let key = LittleEndian::read_u16(&description[..2]);
+            let len = LittleEndian::read_u16(&description[2..]) as u32 + 1;
+            let is_run = run_bitset.map_or(false, |bitset| bitset[i / 8] & (1 << (i % 8)) != 0);
+
+            let store = if is_run {
+                let (runs_len, tail) = split_at(rest, size_of::<u16>())?;
+                let runs_len = LittleEndian::read_u16(runs_len) as usize;
+                let (runs, tail) = split_at(tail, runs_len * 2 * size_of::<u16>())?;
+                rest = tail;
+                Store::Run(runs)
+            } else if len <= ARRAY_CONTAINER_MAX_LEN {
+                let (values, tail) = split_at(rest, len as usize * size_of::<u16>())?;
+                rest = tail;
+                Store::Array(values)
+            } else {
+                let (bits, tail) = split_at(rest, BITMAP_CONTAINER_SIZE)?;
+                rest = tail;
+                Store::Bitmap(bits)
+            };
+
+            containers.push(Container { key, len, store });
+        }
+
+        Ok(CboRoaringBitmapView { bytes, kind: ViewKind::Roaring(containers) })
+    }
+
+    /// Returns the number of integers in the bitmap.
+    pub fn len(&self) -> u64 {
+        match &self.kind {
+            ViewKind::Small => (self.bytes.len() / size_of::<u32>()) as u64,
+            ViewKind::Roaring(containers) => containers.iter().map(|c| c.len as u64).sum(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the bitmap contains the given integer.
+    pub fn contains(&self, value: u32) -> bool {
+        match &self.kind {
+            ViewKind::Small => self.small_values().any(|v| v == value),
+            ViewKind::Roaring(containers) => {
+                let key = (value >> 16) as u16;
+                match containers.binary_search_by_key(&key, |c| c.key) {
+                    Ok(index) => containers[index].contains(value as u16),
+                    Err(_) => false,
+                }
+            }
+        }
+    }
+
+    /// Returns the number of integers in common with the other bitmap.
+    ///
+    /// The containers are only decoded if `other` is bigger than this bitmap.
+    pub fn intersection_len(&self, other: &RoaringBitmap) -> io::Result<u64> {
+        match &self.kind {
+            ViewKind::Small => {
+                Ok(self.small_values().filter(|&v| other.contains(v)).count() as u64)
+            }
+            ViewKind::Roaring(_) if other.len() > self.len() => {
+                let intersection =
+                    other.intersection_with_serialized_unchecked(Cursor::new(self.bytes))?;
+                Ok(intersection.len())
+            }
+            ViewKind::Roaring(_) => Ok(other.iter().filter(|&v| self.contains(v)).count() as u64),
+        }
+    }
+
+    /// Returns `true` if the bitmap doesn't have any integer in common with the other bitmap.
+    pub fn is_disjoint(&self, other: &RoaringBitmap) -> io::Result<bool> {
+        match &self.kind {
+            ViewKind::Small => Ok(!self.small_values().any(|v| other.contains(v))),
+            ViewKind::Roaring(_) if other.len() > self.len() => {
+                Ok(self.intersection_len(other)? == 0)
+            }
+            ViewKind::Roaring(_) => Ok(!other.iter().any(|v| self.contains(v))),
+        }
+    }
+
+    fn small_values(&self) -> impl Iterator<Item = u32> + '_ {
+        self.bytes.chunks_exact(size_of::<u32>()).map(NativeEndian::read_u32)
+    }
+}
+
+impl Container<'_> {
+    fn contains(&self, value: u16) -> bool {
+        match self.store {
+            Store::Array(values) => {
+                let (mut low, mut high) = (0, values.len() / size_of::<u16>());
+                while low < high {
+                    let middle = low + (high - low) / 2;
+                    let current = LittleEndian::read_u16(&values[middle * 2..]);
+                    match current.cmp(&value) {
+                        std::cmp::Ordering::Less => low = middle + 1,
+                        std::cmp::Ordering::Greater => high = middle,
+                        std::cmp::Ordering::Equal => return true,
+                    }
+                }
+                false
+            }
+            // The u64 words are little endian, the n-th bit is thus in the n/8-th byte.
+            Store::Bitmap(bits) => bits[value as usize / 8] & (1 << (value % 8)) != 0,
+            Store::Run(runs) => runs.chunks_exact(2 * size_of::<u16>()).any(|run| {
+                let start = LittleEndian::read_u16(&run[..2]);
+                let len = LittleEndian::read_u16(&run[2..]);
+                value >= start && (value - start) <= len
+            }),
+        }
+    }
+}
+
+fn split_at(bytes: &[u8], mid: usize) -> io::Result<(&[u8], &[u8])> {
+    if bytes.len() < mid {
+        Err(io::ErrorKind::UnexpectedEof.into())
+    } else {
+        Ok(bytes.split_at(mid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heed::BytesEncode;
+
+    use super::*;
+    use crate::CboRoaringBitmapCodec;
+
+    #[test]
+    fn view_matches_the_bitmap() {
+        let mut input = RoaringBitmap::from_sorted_iter(0..10).unwrap();
+        // a bitmap container
+        input.insert_range(100_000..110_000);
+        input.insert(300_000);
+
+        let other = RoaringBitmap::from_iter([5, 12, 105_000, 200_000, 300_000]);
+
+        for input in [RoaringBitmap::from_iter([3, 5, 300_000]), input] {
+            let bytes = CboRoaringBitmapCodec::bytes_encode(&input).unwrap();
+            let view = CboRoaringBitmapView::new(&bytes).unwrap();
+
+            assert_eq!(view.len(), input.len());
+            for value in [0, 3, 5, 9, 10, 99_999, 100_000, 109_999, 110_000, 300_000] {
+                assert_eq!(view.contains(value), input.contains(value), "{value}");
+            }
+            assert_eq!(view.intersection_len(&other).unwrap(), input.intersection_len(&other));
+            assert!(view.is_disjoint(&RoaringBitmap::from_iter([1_000_000])).unwrap());
+
+            // a universe bigger than the bitmap is intersected with the serialized bitmap
+            let universe = RoaringBitmap::from_sorted_iter(0..200_000).unwrap();
+            assert_eq!(
+                view.intersection_len(&universe).unwrap(),
+                input.intersection_len(&universe)
+            );
+        }
+    }
+}
@@ -1,7 +1,9 @@
 mod bo_roaring_bitmap_codec;
 pub mod cbo_roaring_bitmap_codec;
+mod cbo_roaring_bitmap_view;
 mod roaring_bitmap_codec;
 
 pub use self::bo_roaring_bitmap_codec::BoRoaringBitmapCodec;
 pub use self::cbo_roaring_bitmap_codec::CboRoaringBitmapCodec;
+pub use self::cbo_roaring_bitmap_view::CboRoaringBitmapView;
 pub use self::roaring_bitmap_codec::RoaringBitmapCodec;
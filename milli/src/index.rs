@@ -12,18 +12,35 @@ use rstar::RTree;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
+use crate::acl::RESERVED_ACL_FIELD_NAME;
+use crate::batch_limits::BatchLimits;
+use crate::blocked_terms::BlockedTerms;
+use crate::chunking::Chunking;
+use crate::collation::SortCollation;
+use crate::document_hook::{DocumentHook, DEFAULT_DOCUMENT_HOOK_FUEL};
 use crate::documents::PrimaryKey;
 use crate::error::{InternalError, UserError};
 use crate::fields_ids_map::FieldsIdsMap;
+use crate::fields_schema::FieldsSchema;
 use crate::heed_codec::facet::{
     FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec,
     FieldIdCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{
-    BEU16StrCodec, FstSetCodec, ScriptLanguageCodec, StrBEU16Codec, StrRefCodec,
+    BEU16StrCodec, BEU32StrCodec, FieldIdBoolCodec, FstSetCodec, NumericSortIndex,
+    NumericSortIndexCodec, NumericSortIndexView, NumericSortIndexViewCodec, ScriptLanguageCodec,
+    StrBEU16Codec, StrRefCodec,
 };
+use crate::language_preset::LanguagePreset;
+use crate::new_fields::NewFieldsPolicy;
 use crate::order_by_map::OrderByMap;
+use crate::position_decay::PositionDecay;
 use crate::proximity::ProximityPrecision;
+use crate::query_limits::QueryLimits;
+use crate::query_rewrites::QueryRewriteRule;
+use crate::regex_filter::RegexFilter;
+use crate::tie_breaker::TieBreaker;
+use crate::typo::{FirstLetterTypos, HalfTypos, TypoCosts};
 use crate::vector::parsed_vectors::RESERVED_VECTORS_FIELD_NAME;
 use crate::vector::{Embedding, EmbeddingConfig};
 use crate::{
@@ -35,27 +52,39 @@ use crate::{
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
 pub const DEFAULT_MIN_WORD_LEN_TWO_TYPOS: u8 = 9;
+/// The characters delimiting the phrases when they haven't been customized.
+pub const DEFAULT_QUOTE_CHARACTERS: &[&str] = &["\""];
+/// The number of characters of the shortest words searched as prefixes by default.
+pub const DEFAULT_MIN_PREFIX_LENGTH: u64 = 1;
 
 pub mod main_key {
     pub const CRITERIA_KEY: &str = "criteria";
     pub const DISPLAYED_FIELDS_KEY: &str = "displayed-fields";
     pub const DISTINCT_FIELD_KEY: &str = "distinct-field-key";
+    pub const VERSION_FIELD_KEY: &str = "version-field";
     pub const DOCUMENTS_IDS_KEY: &str = "documents-ids";
     pub const HIDDEN_FACETED_FIELDS_KEY: &str = "hidden-faceted-fields";
     pub const FILTERABLE_FIELDS_KEY: &str = "filterable-fields";
     pub const SORTABLE_FIELDS_KEY: &str = "sortable-fields";
+    pub const DENSE_SORTABLE_FIELDS_KEY: &str = "dense-sortable-fields";
+    pub const NUMERIC_SORT_INDEX_PREFIX: &str = "numeric-sort-index-";
     pub const FIELD_DISTRIBUTION_KEY: &str = "fields-distribution";
+    pub const FIELDS_STATS_KEY: &str = "fields-stats";
+    pub const FIELDS_SCHEMA_KEY: &str = "fields-schema";
     pub const FIELDS_IDS_MAP_KEY: &str = "fields-ids-map";
     pub const FIELDIDS_WEIGHTS_MAP_KEY: &str = "fieldids-weights-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
+    pub const SEARCHABLE_WEIGHTS_KEY: &str = "searchable-weights";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
     pub const STOP_WORDS_KEY: &str = "stop-words";
     pub const NON_SEPARATOR_TOKENS_KEY: &str = "non-separator-tokens";
     pub const SEPARATOR_TOKENS_KEY: &str = "separator-tokens";
     pub const DICTIONARY_KEY: &str = "dictionary";
+    pub const LITERAL_WORDS_KEY: &str = "literal-words";
+    pub const LITERAL_WORDS_FST_KEY: &str = "literal-words-fst";
     pub const SYNONYMS_KEY: &str = "synonyms";
     pub const USER_DEFINED_SYNONYMS_KEY: &str = "user-defined-synonyms";
     pub const WORDS_FST_KEY: &str = "words-fst";
@@ -63,16 +92,47 @@ pub mod main_key {
     pub const CREATED_AT_KEY: &str = "created-at";
     pub const UPDATED_AT_KEY: &str = "updated-at";
     pub const AUTHORIZE_TYPOS: &str = "authorize-typos";
+    pub const ACL_INDEXED: &str = "acl-indexed";
     pub const ONE_TYPO_WORD_LEN: &str = "one-typo-word-len";
     pub const TWO_TYPOS_WORD_LEN: &str = "two-typos-word-len";
     pub const EXACT_WORDS: &str = "exact-words";
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
+    pub const PRESENCE_ONLY_ATTRIBUTES: &str = "presence-only-attributes";
+    pub const CHUNKING: &str = "chunking";
+    pub const DOCUMENT_HOOK_MODULE: &str = "document-hook-module";
+    pub const DOCUMENT_HOOK_FUEL: &str = "document-hook-fuel";
+    pub const COMPUTED_FIELDS: &str = "computed-fields";
+    pub const PREPARED_FILTERS: &str = "prepared-filters";
+    pub const PII_ATTRIBUTES: &str = "pii-attributes";
+    pub const QUERY_LIMITS: &str = "query-limits";
+    pub const REGEX_FILTER: &str = "regex-filter";
+    pub const HALF_TYPOS: &str = "half-typos";
+    pub const DISABLE_TYPOS_ON_NUMBERS: &str = "disable-typos-on-numbers";
+    pub const ENABLE_PHONETIC: &str = "enable-phonetic";
+    pub const PHONETIC_WORDS_FST_KEY: &str = "phonetic-words-fst";
+    pub const FIRST_LETTER_TYPOS: &str = "first-letter-typos";
+    pub const TYPO_COSTS: &str = "typo-costs";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
+    pub const FACET_VALUE_ALIASES: &str = "facet-value-aliases";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
     pub const PROXIMITY_PRECISION: &str = "proximity-precision";
     pub const EMBEDDING_CONFIGS: &str = "embedding_configs";
     pub const SEARCH_CUTOFF: &str = "search_cutoff";
+    pub const PREFIX_CACHE_SIZE: &str = "prefix-cache-size";
+    pub const PREFIX_CACHE_PREFIX: &str = "prefix-cache-";
+    pub const LONG_QUERY_THRESHOLD: &str = "long-query-threshold";
+    pub const QUOTE_CHARACTERS: &str = "quote-characters";
+    pub const LANGUAGE_PRESET: &str = "language-preset";
+    pub const FIELD_LANGUAGES: &str = "field-languages";
+    pub const MIN_PREFIX_LENGTH: &str = "min-prefix-length";
+    pub const BLOCKED_TERMS: &str = "blocked-terms";
+    pub const NEW_FIELDS_POLICY: &str = "new-fields-policy";
+    pub const QUERY_REWRITES: &str = "query-rewrites";
+    pub const POSITION_DECAY: &str = "position-decay";
+    pub const BATCH_LIMITS: &str = "batch-limits";
+    pub const SORT_COLLATION: &str = "sort-collation";
+    pub const TIE_BREAKER: &str = "tie-breaker";
 }
 
 pub mod db_name {
@@ -89,10 +149,13 @@ pub mod db_name {
     pub const WORD_PREFIX_POSITION_DOCIDS: &str = "word-prefix-position-docids";
     pub const WORD_PREFIX_FIELD_ID_DOCIDS: &str = "word-prefix-field-id-docids";
     pub const FIELD_ID_WORD_COUNT_DOCIDS: &str = "field-id-word-count-docids";
+    pub const WORD_FREQUENCY_DOCIDS: &str = "word-frequency-docids";
+    pub const DOCUMENT_LENGTH_DOCIDS: &str = "document-length-docids";
     pub const FACET_ID_F64_DOCIDS: &str = "facet-id-f64-docids";
     pub const FACET_ID_EXISTS_DOCIDS: &str = "facet-id-exists-docids";
     pub const FACET_ID_IS_NULL_DOCIDS: &str = "facet-id-is-null-docids";
     pub const FACET_ID_IS_EMPTY_DOCIDS: &str = "facet-id-is-empty-docids";
+    pub const FACET_ID_BOOL_DOCIDS: &str = "facet-id-bool-docids";
     pub const FACET_ID_STRING_DOCIDS: &str = "facet-id-string-docids";
     pub const FACET_ID_NORMALIZED_STRING_STRINGS: &str = "facet-id-normalized-string-strings";
     pub const FACET_ID_STRING_FST: &str = "facet-id-string-fst";
@@ -101,6 +164,7 @@ pub mod db_name {
     pub const VECTOR_EMBEDDER_CATEGORY_ID: &str = "vector-embedder-category-id";
     pub const VECTOR_ARROY: &str = "vector-arroy";
     pub const DOCUMENTS: &str = "documents";
+    pub const ATTACHMENTS: &str = "attachments";
     pub const SCRIPT_LANGUAGE_DOCIDS: &str = "script_language_docids";
 }
 
@@ -137,6 +201,12 @@ pub struct Index {
 
     /// Maps the field id and the word count with the docids that corresponds to it.
     pub field_id_word_count_docids: Database<FieldIdWordCountCodec, CboRoaringBitmapCodec>,
+    /// Maps the word and the number of times it appears in a document with the docids that
+    /// corresponds to it, only filled when the ranking rules contain `relevancy:bm25`.
+    pub word_frequency_docids: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
+    /// Maps the number of words of a document with the docids that corresponds to it,
+    /// only filled when the ranking rules contain `relevancy:bm25`.
+    pub document_length_docids: Database<BEU32, CboRoaringBitmapCodec>,
     /// Maps the word prefix and a position with all the docids where the prefix appears at the position.
     pub word_prefix_position_docids: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
     /// Maps the word prefix and a field id with all the docids where the prefix appears inside the field
@@ -151,6 +221,8 @@ pub struct Index {
     pub facet_id_is_null_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
     /// Maps the facet field id and the docids for which this field is considered empty
     pub facet_id_is_empty_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
+    /// Maps the facet field id and a boolean with the docids for which this field contains the boolean
+    pub facet_id_bool_docids: Database<FieldIdBoolCodec, CboRoaringBitmapCodec>,
 
     /// Maps the facet field id and ranges of numbers with the docids that corresponds to them.
     pub facet_id_f64_docids: Database<FacetGroupKeyCodec<OrderedF64Codec>, FacetGroupValueCodec>,
@@ -173,6 +245,8 @@ pub struct Index {
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<BEU32, ObkvCodec>,
+    /// Maps the document id and the name of an attachment to its content.
+    pub attachments: Database<BEU32StrCodec, Bytes>,
 }
 
 impl Index {
@@ -184,7 +258,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(25);
+        options.max_dbs(29);
 
         let env = unsafe { options.open(path) }?;
         let mut wtxn = env.write_txn()?;
@@ -204,6 +278,9 @@ impl Index {
         let word_fid_docids = env.create_database(&mut wtxn, Some(WORD_FIELD_ID_DOCIDS))?;
         let field_id_word_count_docids =
             env.create_database(&mut wtxn, Some(FIELD_ID_WORD_COUNT_DOCIDS))?;
+        let word_frequency_docids = env.create_database(&mut wtxn, Some(WORD_FREQUENCY_DOCIDS))?;
+        let document_length_docids =
+            env.create_database(&mut wtxn, Some(DOCUMENT_LENGTH_DOCIDS))?;
         let word_prefix_position_docids =
             env.create_database(&mut wtxn, Some(WORD_PREFIX_POSITION_DOCIDS))?;
         let word_prefix_fid_docids =
@@ -220,6 +297,7 @@ impl Index {
             env.create_database(&mut wtxn, Some(FACET_ID_IS_NULL_DOCIDS))?;
         let facet_id_is_empty_docids =
             env.create_database(&mut wtxn, Some(FACET_ID_IS_EMPTY_DOCIDS))?;
+        let facet_id_bool_docids = env.create_database(&mut wtxn, Some(FACET_ID_BOOL_DOCIDS))?;
         let field_id_docid_facet_f64s =
             env.create_database(&mut wtxn, Some(FIELD_ID_DOCID_FACET_F64S))?;
         let field_id_docid_facet_strings =
@@ -230,6 +308,7 @@ impl Index {
         let vector_arroy = env.create_database(&mut wtxn, Some(VECTOR_ARROY))?;
 
         let documents = env.create_database(&mut wtxn, Some(DOCUMENTS))?;
+        let attachments = env.create_database(&mut wtxn, Some(ATTACHMENTS))?;
         wtxn.commit()?;
 
         Index::set_creation_dates(&env, main, created_at, updated_at)?;
@@ -249,6 +328,8 @@ impl Index {
             word_prefix_position_docids,
             word_prefix_fid_docids,
             field_id_word_count_docids,
+            word_frequency_docids,
+            document_length_docids,
             facet_id_f64_docids,
             facet_id_string_docids,
             facet_id_normalized_string_strings,
@@ -256,11 +337,13 @@ impl Index {
             facet_id_exists_docids,
             facet_id_is_null_docids,
             facet_id_is_empty_docids,
+            facet_id_bool_docids,
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
             embedder_category_id,
             documents,
+            attachments,
         })
     }
 
@@ -281,6 +364,8 @@ impl Index {
         if main.get(&txn, main_key::CREATED_AT_KEY)?.is_none() {
             main.put(&mut txn, main_key::UPDATED_AT_KEY, &updated_at)?;
             main.put(&mut txn, main_key::CREATED_AT_KEY, &created_at)?;
+            // A new index can't contain documents whose `_acl` field wasn't faceted.
+            main.remap_types::<Str, U8>().put(&mut txn, main_key::ACL_INDEXED, &1)?;
             txn.commit()?;
         }
         Ok(())
@@ -430,7 +515,8 @@ impl Index {
 
     /* fieldids weights map */
     // This maps the fields ids to their weights.
-    // Their weights is defined by the ordering of the searchable attributes.
+    // Their weights is defined by the ordering of the searchable attributes,
+    // scaled by the weights given to the searchable attributes.
 
     /// Writes the fieldids weights map which associates the field ids to their weights
     pub(crate) fn put_fieldids_weights_map(
@@ -579,6 +665,58 @@ impl Index {
             .unwrap_or_default())
     }
 
+    /* fields stats */
+
+    /// Writes the statistics of every field, updated at the end of the indexing.
+    pub(crate) fn put_fields_stats(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        stats: &FieldsStats,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<FieldsStats>>().put(
+            wtxn,
+            main_key::FIELDS_STATS_KEY,
+            stats,
+        )
+    }
+
+    /// Returns the statistics of every field, updated at the end of the indexing.
+    pub fn fields_stats(&self, rtxn: &RoTxn<'_>) -> heed::Result<FieldsStats> {
+        Ok(self.stored_fields_stats(rtxn)?.unwrap_or_default())
+    }
+
+    /// Returns the statistics of every field, `None` if they were never computed for this index.
+    pub(crate) fn stored_fields_stats(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<Option<FieldsStats>> {
+        self.main.remap_types::<Str, SerdeJson<FieldsStats>>().get(rtxn, main_key::FIELDS_STATS_KEY)
+    }
+
+    /* fields schema */
+
+    /// Writes the schema of the documents, updated incrementally when the documents are written.
+    pub(crate) fn put_fields_schema(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        schema: &FieldsSchema,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<FieldsSchema>>().put(
+            wtxn,
+            main_key::FIELDS_SCHEMA_KEY,
+            schema,
+        )
+    }
+
+    /// Returns the schema of the documents, inferred from the values of their top-level fields.
+    pub fn fields_schema(&self, rtxn: &RoTxn<'_>) -> heed::Result<FieldsSchema> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<FieldsSchema>>()
+            .get(rtxn, main_key::FIELDS_SCHEMA_KEY)?
+            .unwrap_or_default())
+    }
+
     /* displayed fields */
 
     /// Writes the fields that must be displayed in the defined order.
@@ -661,6 +799,21 @@ impl Index {
 
         let mut weights = FieldidsWeightsMap::default();
 
+        // The weight of a field in the map is its cost in the attribute ranking rule: the rank of
+        // the field in the searchable fields divided by the weight the user gave it. The costs are
+        // scaled to stay integers and shifted to start at zero, so that without any user weight
+        // the cost of a field is its position in the searchable fields.
+        let user_weights = self.searchable_weights(wtxn)?;
+        let user_weight = |field: &str| user_weights.get(field).copied().unwrap_or(1).max(1) as u64;
+        let max_user_weight =
+            user_fields.iter().map(|field| user_weight(*field)).max().unwrap_or(1);
+        let costs: Vec<u64> = user_fields
+            .iter()
+            .enumerate()
+            .map(|(position, field)| (position as u64 + 1) * max_user_weight / user_weight(*field))
+            .collect();
+        let min_cost = costs.iter().copied().min().unwrap_or_default();
+
         // Now we generate the real searchable fields:
         // 1. Take the user defined searchable fields as-is to keep the priority defined by the attributes criterion.
         // 2. Iterate over the user defined searchable fields.
@@ -669,15 +822,16 @@ impl Index {
         let mut real_fields = Vec::new();
 
         for (id, field_from_map) in fields_ids_map.iter() {
-            for (weight, user_field) in user_fields.iter().enumerate() {
+            for (position, user_field) in user_fields.iter().enumerate() {
                 if crate::is_faceted_by(field_from_map, user_field)
                     && !real_fields.contains(&field_from_map)
                     && !non_searchable_fields_ids.contains(&id)
                 {
                     real_fields.push(field_from_map);
 
-                    let weight: u16 =
-                        weight.try_into().map_err(|_| UserError::AttributeLimitReached)?;
+                    let weight: u16 = (costs[position] - min_cost)
+                        .try_into()
+                        .map_err(|_| UserError::AttributeLimitReached)?;
                     weights.insert(id, weight);
                 }
             }
@@ -691,6 +845,7 @@ impl Index {
     pub(crate) fn delete_all_searchable_fields(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
         let did_delete_searchable = self.delete_searchable_fields(wtxn)?;
         let did_delete_user_defined = self.delete_user_defined_searchable_fields(wtxn)?;
+        self.delete_searchable_weights(wtxn)?;
         self.delete_fieldids_weights_map(wtxn)?;
         Ok(did_delete_searchable || did_delete_user_defined)
     }
@@ -720,6 +875,7 @@ impl Index {
                     .fields_ids_map(rtxn)?
                     .names()
                     .filter(|name| !crate::is_faceted_by(name, RESERVED_VECTORS_FIELD_NAME))
+                    .filter(|name| !crate::is_faceted_by(name, RESERVED_ACL_FIELD_NAME))
                     .map(|field| Cow::Owned(field.to_string()))
                     .collect())
             })
@@ -769,6 +925,34 @@ impl Index {
             .get(rtxn, main_key::USER_DEFINED_SEARCHABLE_FIELDS_KEY)
     }
 
+    /// Writes the weights given to the user defined searchable fields.
+    pub(crate) fn put_searchable_weights(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        weights: &BTreeMap<String, Weight>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            wtxn,
+            main_key::SEARCHABLE_WEIGHTS_KEY,
+            weights,
+        )
+    }
+
+    /// Deletes the weights given to the user defined searchable fields.
+    pub(crate) fn delete_searchable_weights(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::SEARCHABLE_WEIGHTS_KEY)
+    }
+
+    /// Returns the weights given to the user defined searchable fields,
+    /// the fields without any weight have a weight of 1.
+    pub fn searchable_weights(&self, rtxn: &RoTxn<'_>) -> heed::Result<BTreeMap<String, Weight>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(rtxn, main_key::SEARCHABLE_WEIGHTS_KEY)?
+            .unwrap_or_default())
+    }
+
     /// Identical to `user_defined_searchable_fields`, but returns ids instead.
     pub fn user_defined_searchable_fields_ids(
         &self,
@@ -869,6 +1053,90 @@ impl Index {
         Ok(fields.into_iter().filter_map(|name| fields_ids_map.id(&name)).collect())
     }
 
+    /* dense sortable fields */
+
+    /// Writes the fields for which a dense numeric sort index must be maintained.
+    pub(crate) fn put_dense_sortable_fields(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        fields: &HashSet<String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            wtxn,
+            main_key::DENSE_SORTABLE_FIELDS_KEY,
+            fields,
+        )
+    }
+
+    /// Deletes the dense sortable fields in the database.
+    pub(crate) fn delete_dense_sortable_fields(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::DENSE_SORTABLE_FIELDS_KEY)
+    }
+
+    /// Returns the fields for which a dense numeric sort index is maintained.
+    pub fn dense_sortable_fields(&self, rtxn: &RoTxn<'_>) -> heed::Result<HashSet<String>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(rtxn, main_key::DENSE_SORTABLE_FIELDS_KEY)?
+            .unwrap_or_default())
+    }
+
+    /* numeric sort indexes */
+
+    pub(crate) fn put_numeric_sort_index(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        field_id: FieldId,
+        sort_index: &NumericSortIndex,
+    ) -> heed::Result<()> {
+        let key = format!("{}{field_id}", main_key::NUMERIC_SORT_INDEX_PREFIX);
+        self.main.remap_types::<Str, NumericSortIndexCodec>().put(wtxn, &key, sort_index)
+    }
+
+    /// Returns the dense numeric sort index of the field, if it is maintained.
+    pub fn numeric_sort_index(
+        &self,
+        rtxn: &RoTxn<'_>,
+        field_id: FieldId,
+    ) -> heed::Result<Option<NumericSortIndex>> {
+        let key = format!("{}{field_id}", main_key::NUMERIC_SORT_INDEX_PREFIX);
+        self.main.remap_types::<Str, NumericSortIndexCodec>().get(rtxn, &key)
+    }
+
+    /// Returns the dense numeric sort index of the field without decoding it, if it is maintained.
+    pub fn numeric_sort_index_view<'t>(
+        &self,
+        rtxn: &'t RoTxn<'t>,
+        field_id: FieldId,
+    ) -> heed::Result<Option<NumericSortIndexView<'t>>> {
+        let key = format!("{}{field_id}", main_key::NUMERIC_SORT_INDEX_PREFIX);
+        self.main.remap_types::<Str, NumericSortIndexViewCodec>().get(rtxn, &key)
+    }
+
+    /// Deletes the numeric sort index of the field.
+    pub(crate) fn delete_numeric_sort_index(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        field_id: FieldId,
+    ) -> heed::Result<bool> {
+        let key = format!("{}{field_id}", main_key::NUMERIC_SORT_INDEX_PREFIX);
+        self.main.remap_key_type::<Str>().delete(wtxn, &key)
+    }
+
+    /// Deletes all the numeric sort indexes.
+    pub(crate) fn delete_numeric_sort_indexes(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<()> {
+        let mut iter = self
+            .main
+            .remap_types::<Str, DecodeIgnore>()
+            .prefix_iter_mut(wtxn, main_key::NUMERIC_SORT_INDEX_PREFIX)?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we don't keep any reference to the database entries.
+            unsafe { iter.del_current()? };
+        }
+        Ok(())
+    }
+
     /* faceted fields */
 
     /// Writes the faceted fields in the database.
@@ -912,14 +1180,18 @@ impl Index {
 
     /// Returns the user defined faceted fields names.
     ///
-    /// The user faceted fields are the union of all the filterable, sortable, distinct, and Asc/Desc fields.
+    /// The user faceted fields are the union of all the filterable, sortable, distinct, Asc/Desc, recency and decay fields,
+    /// along with the reserved `_acl` field.
     pub fn user_defined_faceted_fields(&self, rtxn: &RoTxn<'_>) -> Result<HashSet<String>> {
         let filterable_fields = self.filterable_fields(rtxn)?;
         let sortable_fields = self.sortable_fields(rtxn)?;
         let distinct_field = self.distinct_field(rtxn)?;
         let asc_desc_fields =
             self.criteria(rtxn)?.into_iter().filter_map(|criterion| match criterion {
-                Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                Criterion::Asc(field)
+                | Criterion::Desc(field)
+                | Criterion::Recency { field, .. }
+                | Criterion::Decay { field, .. } => Some(field),
                 _otherwise => None,
             });
 
@@ -929,6 +1201,7 @@ impl Index {
         if let Some(field) = distinct_field {
             faceted_fields.insert(field.to_owned());
         }
+        faceted_fields.insert(RESERVED_ACL_FIELD_NAME.to_string());
 
         Ok(faceted_fields)
     }
@@ -974,6 +1247,19 @@ impl Index {
         }
     }
 
+    /// Retrieve all the documents which contain this field id with the given boolean value
+    pub fn bool_faceted_documents_ids(
+        &self,
+        rtxn: &RoTxn<'_>,
+        field_id: FieldId,
+        value: bool,
+    ) -> heed::Result<RoaringBitmap> {
+        match self.facet_id_bool_docids.get(rtxn, &(field_id, value))? {
+            Some(docids) => Ok(docids),
+            None => Ok(RoaringBitmap::new()),
+        }
+    }
+
     /// Retrieve all the documents which contain this field id
     pub fn exists_faceted_documents_ids(
         &self,
@@ -1004,6 +1290,28 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(wtxn, main_key::DISTINCT_FIELD_KEY)
     }
 
+    /* version field */
+
+    pub(crate) fn put_version_field(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        version_field: &str,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, Str>().put(wtxn, main_key::VERSION_FIELD_KEY, version_field)
+    }
+
+    /// Returns the field holding the version of the documents.
+    ///
+    /// When a document is added with a version lower than the one of the stored document,
+    /// the addition is skipped.
+    pub fn version_field<'a>(&self, rtxn: &'a RoTxn<'_>) -> heed::Result<Option<&'a str>> {
+        self.main.remap_types::<Str, Str>().get(rtxn, main_key::VERSION_FIELD_KEY)
+    }
+
+    pub(crate) fn delete_version_field(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::VERSION_FIELD_KEY)
+    }
+
     /* criteria */
 
     pub(crate) fn put_criteria(
@@ -1172,6 +1480,72 @@ impl Index {
             .get(rtxn, main_key::DICTIONARY_KEY)?)
     }
 
+    /* literal words */
+
+    pub(crate) fn put_literal_words(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        set: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<_>>().put(wtxn, main_key::LITERAL_WORDS_KEY, set)
+    }
+
+    pub(crate) fn delete_literal_words(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::LITERAL_WORDS_KEY)
+    }
+
+    /// Returns the literal words as they were defined by the user.
+    ///
+    /// Literal words are never split by the tokenizer and only ever match themselves:
+    /// they are excluded from the typo, prefix and split words derivations.
+    pub fn literal_words(&self, rtxn: &RoTxn<'_>) -> Result<Option<BTreeSet<String>>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<BTreeSet<String>>>()
+            .get(rtxn, main_key::LITERAL_WORDS_KEY)?)
+    }
+
+    pub(crate) fn put_literal_words_fst<A: AsRef<[u8]>>(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        fst: &fst::Set<A>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, Bytes>().put(
+            wtxn,
+            main_key::LITERAL_WORDS_FST_KEY,
+            fst.as_fst().as_bytes(),
+        )
+    }
+
+    pub(crate) fn delete_literal_words_fst(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::LITERAL_WORDS_FST_KEY)
+    }
+
+    /// Returns the normalized literal words.
+    pub fn literal_words_fst<'t>(
+        &self,
+        rtxn: &'t RoTxn<'t>,
+    ) -> Result<Option<fst::Set<Cow<'t, [u8]>>>> {
+        match self.main.remap_types::<Str, Bytes>().get(rtxn, main_key::LITERAL_WORDS_FST_KEY)? {
+            Some(bytes) => Ok(Some(fst::Set::new(bytes)?.map_data(Cow::Borrowed)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the words that the tokenizer must keep as a single token,
+    /// the user dictionary and the literal words.
+    pub fn tokenizer_dictionary(&self, rtxn: &RoTxn<'_>) -> Result<Option<BTreeSet<String>>> {
+        let dictionary = self.dictionary(rtxn)?;
+        let literal_words = self.literal_words(rtxn)?;
+        Ok(match (dictionary, literal_words) {
+            (Some(mut dictionary), Some(literal_words)) => {
+                dictionary.extend(literal_words);
+                Some(dictionary)
+            }
+            (dictionary, literal_words) => dictionary.or(literal_words),
+        })
+    }
+
     /* synonyms */
 
     pub(crate) fn put_synonyms(
@@ -1259,6 +1633,38 @@ impl Index {
         self.word_docids.remap_data_type::<RoaringBitmapLenCodec>().get(rtxn, word)
     }
 
+    /* word positions */
+
+    /// Returns the attributes and the bucketed positions where the given normalized word
+    /// is indexed for a document, by reading the position databases directly.
+    pub fn word_positions(
+        &self,
+        rtxn: &RoTxn<'_>,
+        word: &str,
+        docid: DocumentId,
+    ) -> Result<WordPositions> {
+        let mut prefix = word.as_bytes().to_vec();
+        prefix.push(0);
+
+        let mut positions = WordPositions::default();
+        for (database, values) in [
+            (self.word_fid_docids, &mut positions.fields_ids),
+            (self.word_position_docids, &mut positions.positions),
+        ] {
+            let iter = database
+                .remap_types::<Bytes, Bytes>()
+                .prefix_iter(rtxn, &prefix)?
+                .remap_types::<StrBEU16Codec, CboRoaringBitmapCodec>();
+            for result in iter {
+                let ((_, value), docids) = result?;
+                if docids.contains(docid) {
+                    values.push(value);
+                }
+            }
+        }
+        Ok(positions)
+    }
+
     /* documents */
 
     /// Returns an iterator over the requested documents. The next item will be an error if a document is missing.
@@ -1323,6 +1729,35 @@ impl Index {
         }))
     }
 
+    /* attachments */
+
+    /// Returns the content of an attachment of a document.
+    pub fn attachment<'t>(
+        &self,
+        rtxn: &'t RoTxn<'t>,
+        docid: DocumentId,
+        name: &str,
+    ) -> heed::Result<Option<&'t [u8]>> {
+        self.attachments.get(rtxn, &(docid, name))
+    }
+
+    /// Deletes all the attachments of a document.
+    pub(crate) fn delete_document_attachments(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        docid: DocumentId,
+    ) -> heed::Result<()> {
+        let mut iter = self
+            .attachments
+            .remap_types::<Bytes, DecodeIgnore>()
+            .prefix_iter_mut(wtxn, &docid.to_be_bytes())?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we don't keep any reference to the database entries.
+            unsafe { iter.del_current()? };
+        }
+        Ok(())
+    }
+
     pub fn facets_distribution<'a>(&'a self, rtxn: &'a RoTxn<'a>) -> FacetDistribution<'a> {
         FacetDistribution::new(rtxn, self)
     }
@@ -1386,6 +1821,22 @@ impl Index {
         Ok(())
     }
 
+    /* acl indexed */
+
+    /// Whether the `_acl` field of all the documents is faceted. It isn't for the indexes
+    /// containing documents added before the field was faceted, until their facets are
+    /// indexed again by a settings update.
+    pub fn acl_indexed(&self, txn: &RoTxn<'_>) -> heed::Result<bool> {
+        match self.main.remap_types::<Str, U8>().get(txn, main_key::ACL_INDEXED)? {
+            Some(0) | None => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_acl_indexed(&self, txn: &mut RwTxn<'_>) -> heed::Result<()> {
+        self.main.remap_types::<Str, U8>().put(txn, main_key::ACL_INDEXED, &1)
+    }
+
     pub fn min_word_len_one_typo(&self, txn: &RoTxn<'_>) -> heed::Result<u8> {
         // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
         // identify 0 as being false, and anything else as true. The absence of a value is true,
@@ -1397,91 +1848,620 @@ impl Index {
             .unwrap_or(DEFAULT_MIN_WORD_LEN_ONE_TYPO))
     }
 
-    pub(crate) fn put_min_word_len_one_typo(
+    pub(crate) fn put_min_word_len_one_typo(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: u8,
+    ) -> heed::Result<()> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
+        // identify 0 as being false, and anything else as true. The absence of a value is true,
+        // because by default, we authorize typos.
+        self.main.remap_types::<Str, U8>().put(txn, main_key::ONE_TYPO_WORD_LEN, &val)?;
+        Ok(())
+    }
+
+    pub fn min_word_len_two_typos(&self, txn: &RoTxn<'_>) -> heed::Result<u8> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
+        // identify 0 as being false, and anything else as true. The absence of a value is true,
+        // because by default, we authorize typos.
+        Ok(self
+            .main
+            .remap_types::<Str, U8>()
+            .get(txn, main_key::TWO_TYPOS_WORD_LEN)?
+            .unwrap_or(DEFAULT_MIN_WORD_LEN_TWO_TYPOS))
+    }
+
+    pub(crate) fn put_min_word_len_two_typos(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: u8,
+    ) -> heed::Result<()> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
+        // identify 0 as being false, and anything else as true. The absence of a value is true,
+        // because by default, we authorize typos.
+        self.main.remap_types::<Str, U8>().put(txn, main_key::TWO_TYPOS_WORD_LEN, &val)?;
+        Ok(())
+    }
+
+    /// List the words on which typo are not allowed
+    pub fn exact_words<'t>(&self, txn: &'t RoTxn<'t>) -> Result<Option<fst::Set<Cow<'t, [u8]>>>> {
+        match self.main.remap_types::<Str, Bytes>().get(txn, main_key::EXACT_WORDS)? {
+            Some(bytes) => Ok(Some(fst::Set::new(bytes)?.map_data(Cow::Borrowed)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn put_exact_words<A: AsRef<[u8]>>(
+        &self,
+        txn: &mut RwTxn<'_>,
+        words: &fst::Set<A>,
+    ) -> Result<()> {
+        self.main.remap_types::<Str, Bytes>().put(
+            txn,
+            main_key::EXACT_WORDS,
+            words.as_fst().as_bytes(),
+        )?;
+        Ok(())
+    }
+
+    /// Returns the exact attributes: attributes for which typo is disallowed.
+    pub fn exact_attributes<'t>(&self, txn: &'t RoTxn<'t>) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<Vec<&str>>>()
+            .get(txn, main_key::EXACT_ATTRIBUTES)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the list of exact attributes field ids.
+    pub fn exact_attributes_ids(&self, txn: &RoTxn<'_>) -> Result<HashSet<FieldId>> {
+        let attrs = self.exact_attributes(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(attrs.iter().filter_map(|attr| fid_map.id(attr)).collect())
+    }
+
+    /// Writes the exact attributes to the database.
+    pub(crate) fn put_exact_attributes(&self, txn: &mut RwTxn<'_>, attrs: &[&str]) -> Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<&[&str]>>().put(
+            txn,
+            main_key::EXACT_ATTRIBUTES,
+            &attrs,
+        )?;
+        Ok(())
+    }
+
+    /// Clears the exact attributes from the store.
+    pub(crate) fn delete_exact_attributes(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::EXACT_ATTRIBUTES)
+    }
+
+    /// Returns the presence-only attributes: attributes for which the positions of
+    /// the words and the proximities between them are not stored.
+    pub fn presence_only_attributes<'t>(&self, txn: &'t RoTxn<'t>) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeBincode<Vec<&str>>>()
+            .get(txn, main_key::PRESENCE_ONLY_ATTRIBUTES)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the list of presence-only attributes field ids.
+    pub fn presence_only_attributes_ids(&self, txn: &RoTxn<'_>) -> Result<HashSet<FieldId>> {
+        let attrs = self.presence_only_attributes(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(attrs.iter().filter_map(|attr| fid_map.id(attr)).collect())
+    }
+
+    /// Writes the presence-only attributes to the database.
+    pub(crate) fn put_presence_only_attributes(
+        &self,
+        txn: &mut RwTxn<'_>,
+        attrs: &[&str],
+    ) -> Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<&[&str]>>().put(
+            txn,
+            main_key::PRESENCE_ONLY_ATTRIBUTES,
+            &attrs,
+        )?;
+        Ok(())
+    }
+
+    /// Clears the presence-only attributes from the store.
+    pub(crate) fn delete_presence_only_attributes(
+        &self,
+        txn: &mut RwTxn<'_>,
+    ) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::PRESENCE_ONLY_ATTRIBUTES)
+    }
+
+    /// Returns the chunking of the long searchable fields into passages, if any.
+    pub fn chunking(&self, txn: &RoTxn<'_>) -> heed::Result<Option<Chunking>> {
+        self.main.remap_types::<Str, SerdeJson<Chunking>>().get(txn, main_key::CHUNKING)
+    }
+
+    pub(crate) fn put_chunking(
+        &self,
+        txn: &mut RwTxn<'_>,
+        chunking: &Chunking,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<Chunking>>().put(txn, main_key::CHUNKING, chunking)
+    }
+
+    pub(crate) fn delete_chunking(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::CHUNKING)
+    }
+
+    /// Returns the WebAssembly hook executed on each document at ingestion time, if any.
+    pub fn document_hook(&self, txn: &RoTxn<'_>) -> heed::Result<Option<DocumentHook>> {
+        let module =
+            self.main.remap_types::<Str, Bytes>().get(txn, main_key::DOCUMENT_HOOK_MODULE)?;
+        let fuel = self.main.remap_types::<Str, BEU64>().get(txn, main_key::DOCUMENT_HOOK_FUEL)?;
+        Ok(module.map(|module| DocumentHook {
+            module: module.to_vec(),
+            fuel: fuel.unwrap_or(DEFAULT_DOCUMENT_HOOK_FUEL),
+        }))
+    }
+
+    pub(crate) fn put_document_hook(
+        &self,
+        txn: &mut RwTxn<'_>,
+        hook: &DocumentHook,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, Bytes>().put(
+            txn,
+            main_key::DOCUMENT_HOOK_MODULE,
+            &hook.module,
+        )?;
+        self.main.remap_types::<Str, BEU64>().put(txn, main_key::DOCUMENT_HOOK_FUEL, &hook.fuel)
+    }
+
+    pub(crate) fn delete_document_hook(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        let deleted =
+            self.main.remap_key_type::<Str>().delete(txn, main_key::DOCUMENT_HOOK_MODULE)?;
+        self.main.remap_key_type::<Str>().delete(txn, main_key::DOCUMENT_HOOK_FUEL)?;
+        Ok(deleted)
+    }
+
+    /// Returns the expressions of the fields computed at search time, mapped by the name of the fields.
+    pub fn computed_fields(&self, txn: &RoTxn<'_>) -> heed::Result<BTreeMap<String, String>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(txn, main_key::COMPUTED_FIELDS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_computed_fields(
+        &self,
+        txn: &mut RwTxn<'_>,
+        computed_fields: &BTreeMap<String, String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            txn,
+            main_key::COMPUTED_FIELDS,
+            computed_fields,
+        )
+    }
+
+    pub(crate) fn delete_computed_fields(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::COMPUTED_FIELDS)
+    }
+
+    /// Returns the templates of the prepared filters, mapped by the name of the filters.
+    pub fn prepared_filters(&self, txn: &RoTxn<'_>) -> heed::Result<BTreeMap<String, String>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(txn, main_key::PREPARED_FILTERS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_prepared_filters(
+        &self,
+        txn: &mut RwTxn<'_>,
+        prepared_filters: &BTreeMap<String, String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            txn,
+            main_key::PREPARED_FILTERS,
+            prepared_filters,
+        )
+    }
+
+    pub(crate) fn delete_prepared_filters(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::PREPARED_FILTERS)
+    }
+
+    /// Returns the attributes holding personal information, whose values are stored encrypted.
+    pub fn pii_attributes(&self, txn: &RoTxn<'_>) -> heed::Result<BTreeSet<String>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(txn, main_key::PII_ATTRIBUTES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_pii_attributes(
+        &self,
+        txn: &mut RwTxn<'_>,
+        pii_attributes: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            txn,
+            main_key::PII_ATTRIBUTES,
+            pii_attributes,
+        )
+    }
+
+    pub(crate) fn delete_pii_attributes(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::PII_ATTRIBUTES)
+    }
+
+    /// Returns the limits on the size of the search queries.
+    pub fn query_limits(&self, txn: &RoTxn<'_>) -> heed::Result<QueryLimits> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<QueryLimits>>()
+            .get(txn, main_key::QUERY_LIMITS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_query_limits(
+        &self,
+        txn: &mut RwTxn<'_>,
+        query_limits: &QueryLimits,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<QueryLimits>>().put(
+            txn,
+            main_key::QUERY_LIMITS,
+            query_limits,
+        )
+    }
+
+    pub(crate) fn delete_query_limits(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::QUERY_LIMITS)
+    }
+
+    /// Returns the terms that can't be searched and how the queries containing them are handled.
+    pub fn blocked_terms(&self, txn: &RoTxn<'_>) -> heed::Result<BlockedTerms> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BlockedTerms>>()
+            .get(txn, main_key::BLOCKED_TERMS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_blocked_terms(
+        &self,
+        txn: &mut RwTxn<'_>,
+        blocked_terms: &BlockedTerms,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<BlockedTerms>>().put(
+            txn,
+            main_key::BLOCKED_TERMS,
+            blocked_terms,
+        )
+    }
+
+    pub(crate) fn delete_blocked_terms(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::BLOCKED_TERMS)
+    }
+
+    /// Returns how the fields discovered by the document additions are handled.
+    pub fn new_fields_policy(&self, txn: &RoTxn<'_>) -> heed::Result<NewFieldsPolicy> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<NewFieldsPolicy>>()
+            .get(txn, main_key::NEW_FIELDS_POLICY)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_new_fields_policy(
+        &self,
+        txn: &mut RwTxn<'_>,
+        policy: &NewFieldsPolicy,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<NewFieldsPolicy>>().put(
+            txn,
+            main_key::NEW_FIELDS_POLICY,
+            policy,
+        )
+    }
+
+    pub(crate) fn delete_new_fields_policy(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::NEW_FIELDS_POLICY)
+    }
+
+    /// Returns the rules rewriting the queries before they are tokenized, in order.
+    pub fn query_rewrites(&self, txn: &RoTxn<'_>) -> heed::Result<Vec<QueryRewriteRule>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(txn, main_key::QUERY_REWRITES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_query_rewrites(
+        &self,
+        txn: &mut RwTxn<'_>,
+        rules: &[QueryRewriteRule],
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<&[QueryRewriteRule]>>().put(
+            txn,
+            main_key::QUERY_REWRITES,
+            &rules,
+        )
+    }
+
+    pub(crate) fn delete_query_rewrites(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::QUERY_REWRITES)
+    }
+
+    /// Returns how the cost of the matched words grows with their position in the attributes.
+    pub fn position_decay(&self, txn: &RoTxn<'_>) -> heed::Result<PositionDecay> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<PositionDecay>>()
+            .get(txn, main_key::POSITION_DECAY)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_position_decay(
+        &self,
+        txn: &mut RwTxn<'_>,
+        position_decay: PositionDecay,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<PositionDecay>>().put(
+            txn,
+            main_key::POSITION_DECAY,
+            &position_decay,
+        )
+    }
+
+    pub(crate) fn delete_position_decay(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::POSITION_DECAY)
+    }
+
+    /// Returns the limits of the batches of tasks processed on this index.
+    pub fn batch_limits(&self, txn: &RoTxn<'_>) -> heed::Result<BatchLimits> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BatchLimits>>()
+            .get(txn, main_key::BATCH_LIMITS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_batch_limits(
+        &self,
+        txn: &mut RwTxn<'_>,
+        limits: &BatchLimits,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<BatchLimits>>().put(
+            txn,
+            main_key::BATCH_LIMITS,
+            limits,
+        )
+    }
+
+    pub(crate) fn delete_batch_limits(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::BATCH_LIMITS)
+    }
+
+    /// Returns the locales ordering the string values of the sortable attributes.
+    pub fn sort_collation(&self, txn: &RoTxn<'_>) -> heed::Result<SortCollation> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<SortCollation>>()
+            .get(txn, main_key::SORT_COLLATION)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_sort_collation(
+        &self,
+        txn: &mut RwTxn<'_>,
+        collation: &SortCollation,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<SortCollation>>().put(
+            txn,
+            main_key::SORT_COLLATION,
+            collation,
+        )
+    }
+
+    pub(crate) fn delete_sort_collation(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::SORT_COLLATION)
+    }
+
+    /// Returns the order of the documents that all the ranking rules consider equal,
+    /// `None` when they are returned in the order of their internal ids.
+    pub fn tie_breaker(&self, txn: &RoTxn<'_>) -> heed::Result<Option<TieBreaker>> {
+        self.main.remap_types::<Str, SerdeJson<TieBreaker>>().get(txn, main_key::TIE_BREAKER)
+    }
+
+    pub(crate) fn put_tie_breaker(
+        &self,
+        txn: &mut RwTxn<'_>,
+        tie_breaker: &TieBreaker,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<TieBreaker>>().put(
+            txn,
+            main_key::TIE_BREAKER,
+            tie_breaker,
+        )
+    }
+
+    pub(crate) fn delete_tie_breaker(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::TIE_BREAKER)
+    }
+
+    /// Returns whether the `MATCHES` filter operator is enabled and its limits.
+    pub fn regex_filter(&self, txn: &RoTxn<'_>) -> heed::Result<RegexFilter> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<RegexFilter>>()
+            .get(txn, main_key::REGEX_FILTER)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_regex_filter(
+        &self,
+        txn: &mut RwTxn<'_>,
+        regex_filter: &RegexFilter,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<RegexFilter>>().put(
+            txn,
+            main_key::REGEX_FILTER,
+            regex_filter,
+        )
+    }
+
+    pub(crate) fn delete_regex_filter(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::REGEX_FILTER)
+    }
+
+    /// Returns the single typos that only count as half a typo, if any.
+    pub fn half_typos(&self, txn: &RoTxn<'_>) -> heed::Result<Option<HalfTypos>> {
+        self.main.remap_types::<Str, SerdeJson<HalfTypos>>().get(txn, main_key::HALF_TYPOS)
+    }
+
+    pub(crate) fn put_half_typos(
+        &self,
+        txn: &mut RwTxn<'_>,
+        half_typos: &HalfTypos,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<HalfTypos>>().put(
+            txn,
+            main_key::HALF_TYPOS,
+            half_typos,
+        )
+    }
+
+    pub(crate) fn delete_half_typos(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::HALF_TYPOS)
+    }
+
+    /// Returns `true` if the words of the queries made mostly of digits, like years or product
+    /// codes, must be matched without typos.
+    pub fn disable_typos_on_numbers(&self, txn: &RoTxn<'_>) -> heed::Result<bool> {
+        // The flag is stored as a u8, its absence means that typos are allowed on numbers.
+        match self.main.remap_types::<Str, U8>().get(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)? {
+            Some(0) | None => Ok(false),
+            Some(_) => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_disable_typos_on_numbers(
+        &self,
+        txn: &mut RwTxn<'_>,
+        flag: bool,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, U8>().put(
+            txn,
+            main_key::DISABLE_TYPOS_ON_NUMBERS,
+            &(flag as u8),
+        )
+    }
+
+    pub(crate) fn delete_disable_typos_on_numbers(
+        &self,
+        txn: &mut RwTxn<'_>,
+    ) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)
+    }
+
+    /// Returns `true` if the words of the queries also match the words sounding like them.
+    pub fn enable_phonetic(&self, txn: &RoTxn<'_>) -> heed::Result<bool> {
+        // The flag is stored as a u8, its absence means that the phonetic matching is disabled.
+        match self.main.remap_types::<Str, U8>().get(txn, main_key::ENABLE_PHONETIC)? {
+            Some(0) | None => Ok(false),
+            Some(_) => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_enable_phonetic(&self, txn: &mut RwTxn<'_>, flag: bool) -> heed::Result<()> {
+        self.main.remap_types::<Str, U8>().put(txn, main_key::ENABLE_PHONETIC, &(flag as u8))
+    }
+
+    pub(crate) fn delete_enable_phonetic(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::ENABLE_PHONETIC)
+    }
+
+    pub(crate) fn put_phonetic_words_fst<A: AsRef<[u8]>>(
         &self,
-        txn: &mut RwTxn<'_>,
-        val: u8,
+        wtxn: &mut RwTxn<'_>,
+        fst: &fst::Set<A>,
     ) -> heed::Result<()> {
-        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
-        // identify 0 as being false, and anything else as true. The absence of a value is true,
-        // because by default, we authorize typos.
-        self.main.remap_types::<Str, U8>().put(txn, main_key::ONE_TYPO_WORD_LEN, &val)?;
-        Ok(())
+        self.main.remap_types::<Str, Bytes>().put(
+            wtxn,
+            main_key::PHONETIC_WORDS_FST_KEY,
+            fst.as_fst().as_bytes(),
+        )
     }
 
-    pub fn min_word_len_two_typos(&self, txn: &RoTxn<'_>) -> heed::Result<u8> {
-        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
-        // identify 0 as being false, and anything else as true. The absence of a value is true,
-        // because by default, we authorize typos.
-        Ok(self
-            .main
-            .remap_types::<Str, U8>()
-            .get(txn, main_key::TWO_TYPOS_WORD_LEN)?
-            .unwrap_or(DEFAULT_MIN_WORD_LEN_TWO_TYPOS))
+    pub(crate) fn delete_phonetic_words_fst(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::PHONETIC_WORDS_FST_KEY)
     }
 
-    pub(crate) fn put_min_word_len_two_typos(
+    /// Returns the words of the index grouped by their phonetic key, if the phonetic matching
+    /// is enabled. See [`crate::phonetic`].
+    pub fn phonetic_words_fst<'t>(
         &self,
-        txn: &mut RwTxn<'_>,
-        val: u8,
-    ) -> heed::Result<()> {
-        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
-        // identify 0 as being false, and anything else as true. The absence of a value is true,
-        // because by default, we authorize typos.
-        self.main.remap_types::<Str, U8>().put(txn, main_key::TWO_TYPOS_WORD_LEN, &val)?;
-        Ok(())
-    }
-
-    /// List the words on which typo are not allowed
-    pub fn exact_words<'t>(&self, txn: &'t RoTxn<'t>) -> Result<Option<fst::Set<Cow<'t, [u8]>>>> {
-        match self.main.remap_types::<Str, Bytes>().get(txn, main_key::EXACT_WORDS)? {
+        rtxn: &'t RoTxn<'t>,
+    ) -> Result<Option<fst::Set<Cow<'t, [u8]>>>> {
+        match self.main.remap_types::<Str, Bytes>().get(rtxn, main_key::PHONETIC_WORDS_FST_KEY)? {
             Some(bytes) => Ok(Some(fst::Set::new(bytes)?.map_data(Cow::Borrowed)?)),
             None => Ok(None),
         }
     }
 
-    pub(crate) fn put_exact_words<A: AsRef<[u8]>>(
+    /// Returns how the typos on the first letter of the words are handled.
+    pub fn first_letter_typos(&self, txn: &RoTxn<'_>) -> heed::Result<FirstLetterTypos> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<FirstLetterTypos>>()
+            .get(txn, main_key::FIRST_LETTER_TYPOS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_first_letter_typos(
         &self,
         txn: &mut RwTxn<'_>,
-        words: &fst::Set<A>,
-    ) -> Result<()> {
-        self.main.remap_types::<Str, Bytes>().put(
+        first_letter_typos: FirstLetterTypos,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<FirstLetterTypos>>().put(
             txn,
-            main_key::EXACT_WORDS,
-            words.as_fst().as_bytes(),
-        )?;
-        Ok(())
+            main_key::FIRST_LETTER_TYPOS,
+            &first_letter_typos,
+        )
     }
 
-    /// Returns the exact attributes: attributes for which typo is disallowed.
-    pub fn exact_attributes<'t>(&self, txn: &'t RoTxn<'t>) -> Result<Vec<&'t str>> {
+    pub(crate) fn delete_first_letter_typos(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::FIRST_LETTER_TYPOS)
+    }
+
+    /// Returns the cost of the edges of the typo ranking rule.
+    pub fn typo_costs(&self, txn: &RoTxn<'_>) -> heed::Result<TypoCosts> {
         Ok(self
             .main
-            .remap_types::<Str, SerdeBincode<Vec<&str>>>()
-            .get(txn, main_key::EXACT_ATTRIBUTES)?
+            .remap_types::<Str, SerdeJson<TypoCosts>>()
+            .get(txn, main_key::TYPO_COSTS)?
             .unwrap_or_default())
     }
 
-    /// Returns the list of exact attributes field ids.
-    pub fn exact_attributes_ids(&self, txn: &RoTxn<'_>) -> Result<HashSet<FieldId>> {
-        let attrs = self.exact_attributes(txn)?;
-        let fid_map = self.fields_ids_map(txn)?;
-        Ok(attrs.iter().filter_map(|attr| fid_map.id(attr)).collect())
-    }
-
-    /// Writes the exact attributes to the database.
-    pub(crate) fn put_exact_attributes(&self, txn: &mut RwTxn<'_>, attrs: &[&str]) -> Result<()> {
-        self.main.remap_types::<Str, SerdeBincode<&[&str]>>().put(
+    pub(crate) fn put_typo_costs(
+        &self,
+        txn: &mut RwTxn<'_>,
+        typo_costs: TypoCosts,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<TypoCosts>>().put(
             txn,
-            main_key::EXACT_ATTRIBUTES,
-            &attrs,
-        )?;
-        Ok(())
+            main_key::TYPO_COSTS,
+            &typo_costs,
+        )
     }
 
-    /// Clears the exact attributes from the store.
-    pub(crate) fn delete_exact_attributes(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
-        self.main.remap_key_type::<Str>().delete(txn, main_key::EXACT_ATTRIBUTES)
+    pub(crate) fn delete_typo_costs(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::TYPO_COSTS)
     }
 
     pub fn max_values_per_facet(&self, txn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
@@ -1521,6 +2501,30 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::SORT_FACET_VALUES_BY)
     }
 
+    /// Returns, by field, the facet values merged under each displayed value.
+    pub fn facet_value_aliases(
+        &self,
+        txn: &RoTxn<'_>,
+    ) -> heed::Result<BTreeMap<String, BTreeMap<String, Vec<String>>>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(txn, main_key::FACET_VALUE_ALIASES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_facet_value_aliases(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &BTreeMap<String, BTreeMap<String, Vec<String>>>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(txn, main_key::FACET_VALUE_ALIASES, val)
+    }
+
+    pub(crate) fn delete_facet_value_aliases(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::FACET_VALUE_ALIASES)
+    }
+
     pub fn pagination_max_total_hits(&self, txn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
         self.main.remap_types::<Str, BEU64>().get(txn, main_key::PAGINATION_MAX_TOTAL_HITS)
     }
@@ -1660,6 +2664,177 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(wtxn, main_key::SEARCH_CUTOFF)
     }
 
+    pub(crate) fn put_prefix_cache_size(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        size: u64,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, BEU64>().put(wtxn, main_key::PREFIX_CACHE_SIZE, &size)
+    }
+
+    /// Returns the number of results that are precomputed for the most popular prefixes.
+    pub fn prefix_cache_size(&self, rtxn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
+        self.main.remap_types::<Str, BEU64>().get(rtxn, main_key::PREFIX_CACHE_SIZE)
+    }
+
+    pub(crate) fn delete_prefix_cache_size(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::PREFIX_CACHE_SIZE)
+    }
+
+    pub(crate) fn put_long_query_threshold(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        threshold: u64,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, BEU64>().put(wtxn, main_key::LONG_QUERY_THRESHOLD, &threshold)
+    }
+
+    /// Returns the number of query terms above which the biggest proximities are bucketed together.
+    pub fn long_query_threshold(&self, rtxn: &RoTxn<'_>) -> heed::Result<Option<u64>> {
+        self.main.remap_types::<Str, BEU64>().get(rtxn, main_key::LONG_QUERY_THRESHOLD)
+    }
+
+    pub(crate) fn delete_long_query_threshold(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::LONG_QUERY_THRESHOLD)
+    }
+
+    /* quote characters */
+
+    pub(crate) fn put_quote_characters(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        set: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<_>>().put(wtxn, main_key::QUOTE_CHARACTERS, set)
+    }
+
+    pub(crate) fn delete_quote_characters(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::QUOTE_CHARACTERS)
+    }
+
+    /// Returns the characters delimiting the phrases in the search queries,
+    /// only the double quote when they haven't been customized.
+    pub fn quote_characters(&self, rtxn: &RoTxn<'_>) -> Result<BTreeSet<String>> {
+        let quotes = self
+            .main
+            .remap_types::<Str, SerdeBincode<BTreeSet<String>>>()
+            .get(rtxn, main_key::QUOTE_CHARACTERS)?;
+        Ok(quotes
+            .unwrap_or_else(|| DEFAULT_QUOTE_CHARACTERS.iter().map(|q| q.to_string()).collect()))
+    }
+
+    /* language preset */
+
+    pub(crate) fn put_language_preset(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        preset: LanguagePreset,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<LanguagePreset>>().put(
+            wtxn,
+            main_key::LANGUAGE_PRESET,
+            &preset,
+        )
+    }
+
+    pub(crate) fn delete_language_preset(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::LANGUAGE_PRESET)
+    }
+
+    pub fn language_preset(&self, rtxn: &RoTxn<'_>) -> heed::Result<Option<LanguagePreset>> {
+        self.main
+            .remap_types::<Str, SerdeBincode<LanguagePreset>>()
+            .get(rtxn, main_key::LANGUAGE_PRESET)
+    }
+
+    /* field languages */
+
+    pub(crate) fn put_field_languages(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        field_languages: &BTreeMap<String, LanguagePreset>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<BTreeMap<String, LanguagePreset>>>().put(
+            wtxn,
+            main_key::FIELD_LANGUAGES,
+            field_languages,
+        )
+    }
+
+    pub(crate) fn delete_field_languages(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::FIELD_LANGUAGES)
+    }
+
+    /// Returns the language of the fields whose text is written in a single language,
+    /// mapped by the name of the fields.
+    pub fn field_languages(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<BTreeMap<String, LanguagePreset>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, LanguagePreset>>>()
+            .get(rtxn, main_key::FIELD_LANGUAGES)?
+            .unwrap_or_default())
+    }
+
+    /* min prefix length */
+
+    pub(crate) fn put_min_prefix_length(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        length: u64,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, BEU64>().put(wtxn, main_key::MIN_PREFIX_LENGTH, &length)
+    }
+
+    pub(crate) fn delete_min_prefix_length(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::MIN_PREFIX_LENGTH)
+    }
+
+    /// Returns the number of characters the last word of a query must have
+    /// to be searched as a prefix.
+    pub fn min_prefix_length(&self, rtxn: &RoTxn<'_>) -> heed::Result<u64> {
+        Ok(self
+            .main
+            .remap_types::<Str, BEU64>()
+            .get(rtxn, main_key::MIN_PREFIX_LENGTH)?
+            .unwrap_or(DEFAULT_MIN_PREFIX_LENGTH))
+    }
+
+    pub(crate) fn put_prefix_cache_entry(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        prefix: &str,
+        docids: &[DocumentId],
+    ) -> heed::Result<()> {
+        let key = format!("{}{prefix}", main_key::PREFIX_CACHE_PREFIX);
+        self.main.remap_types::<Str, SerdeJson<&[DocumentId]>>().put(wtxn, &key, &docids)
+    }
+
+    /// Returns the precomputed best results of a search for the given prefix, if it is cached.
+    pub fn prefix_cache_entry(
+        &self,
+        rtxn: &RoTxn<'_>,
+        prefix: &str,
+    ) -> heed::Result<Option<Vec<DocumentId>>> {
+        let key = format!("{}{prefix}", main_key::PREFIX_CACHE_PREFIX);
+        self.main.remap_types::<Str, SerdeJson<Vec<DocumentId>>>().get(rtxn, &key)
+    }
+
+    /// Deletes all the precomputed results of the prefix cache.
+    pub(crate) fn delete_prefix_cache(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<()> {
+        let mut iter = self
+            .main
+            .remap_types::<Str, DecodeIgnore>()
+            .prefix_iter_mut(wtxn, main_key::PREFIX_CACHE_PREFIX)?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we don't keep any reference to the database entries.
+            unsafe { iter.del_current()? };
+        }
+        Ok(())
+    }
+
     pub fn embeddings(
         &self,
         rtxn: &RoTxn<'_>,
@@ -1699,6 +2874,37 @@ impl Index {
     }
 }
 
+/// Associates every field name with its statistics.
+pub type FieldsStats = BTreeMap<String, FieldStats>;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldStats {
+    /// The number of different terms indexed for this field.
+    pub distinct_terms: u64,
+    /// The number of pairs of a term and a document containing it in this field.
+    pub term_document_pairs: u64,
+    /// The number of tokens indexed for this field, in all the documents.
+    ///
+    /// They are counted while indexing the documents, so that the tokens of the documents
+    /// indexed before the statistics existed are not.
+    #[serde(default)]
+    pub total_tokens: u64,
+    /// The number of different facet values of this field, zero if the field is not faceted.
+    pub facet_cardinality: u64,
+    /// The proportion of documents containing this field, between 0 and 1.
+    pub documents_ratio: f64,
+}
+
+/// Where a word is indexed for a document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordPositions {
+    /// The ids of the attributes containing the word.
+    pub fields_ids: Vec<FieldId>,
+    /// The positions of the word in these attributes, bucketed like for the attribute ranking rules.
+    pub positions: Vec<u16>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct IndexEmbeddingConfig {
     pub name: String,
@@ -1718,7 +2924,9 @@ pub(crate) mod tests {
 
     use crate::documents::DocumentsBatchReader;
     use crate::error::{Error, InternalError};
-    use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
+    use crate::index::{
+        FieldStats, WordPositions, DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
+    };
     use crate::update::{
         self, IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig, Setting,
         Settings,
@@ -1940,6 +3148,87 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn fields_stats() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("name") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 1, "name": "kevin" },
+                { "id": 2, "name": "bob bob", "age": 20 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let stats = index.fields_stats(&rtxn).unwrap();
+        assert_eq!(
+            stats["name"],
+            FieldStats {
+                distinct_terms: 2,
+                term_document_pairs: 2,
+                total_tokens: 3,
+                facet_cardinality: 2,
+                documents_ratio: 1.0
+            }
+        );
+        assert_eq!(
+            stats["age"],
+            FieldStats {
+                distinct_terms: 1,
+                term_document_pairs: 1,
+                total_tokens: 1,
+                facet_cardinality: 0,
+                documents_ratio: 0.5
+            }
+        );
+        drop(rtxn);
+
+        index.delete_document("1");
+
+        let rtxn = index.read_txn().unwrap();
+        let stats = index.fields_stats(&rtxn).unwrap();
+        assert_eq!(
+            stats["name"],
+            FieldStats {
+                distinct_terms: 1,
+                term_document_pairs: 1,
+                total_tokens: 2,
+                facet_cardinality: 1,
+                documents_ratio: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn word_positions() {
+        let index = TempIndex::new();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "hello world", "text": "world" },
+                { "id": 1, "title": "world" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let title = fields_ids_map.id("title").unwrap();
+        let text = fields_ids_map.id("text").unwrap();
+
+        let positions = index.word_positions(&rtxn, "world", 0).unwrap();
+        assert_eq!(
+            positions,
+            WordPositions { fields_ids: vec![title, text], positions: vec![0, 1] }
+        );
+        let positions = index.word_positions(&rtxn, "world", 1).unwrap();
+        assert_eq!(positions, WordPositions { fields_ids: vec![title], positions: vec![0] });
+        let positions = index.word_positions(&rtxn, "hello", 1).unwrap();
+        assert_eq!(positions, WordPositions::default());
+    }
+
     #[test]
     fn put_and_retrieve_disable_typo() {
         let index = TempIndex::new();
@@ -2654,6 +3943,11 @@ pub(crate) mod tests {
             mut documents_ids,
             degraded: _,
             used_negative_operator: _,
+            query_truncated: _,
+            query_term_locations: _,
+            query_interpretation: _,
+            relaxed_phrases: _,
+            timings: _,
         } = search.execute().unwrap();
         let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
         documents_ids.sort_unstable();
@@ -2920,4 +4214,56 @@ pub(crate) mod tests {
             .unwrap();
         assert!(results.candidates.is_empty());
     }
+
+    #[test]
+    fn attachments_are_stored_apart_from_the_documents() {
+        let index = TempIndex::new();
+
+        // `kefir` and `doggo` encoded in base64
+        index
+            .add_documents(documents!([
+                { "id": 0, "name": "kefir", "_attachments": { "a.txt": "a2VmaXI=", "b.txt": "ZG9nZ28=" } },
+                { "id": 1, "name": "intel" },
+            ]))
+            .unwrap();
+
+        db_snap!(index, fields_ids_map, @r###"
+        0   id               |
+        1   name             |
+        "###);
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.attachment(&rtxn, 0, "a.txt").unwrap(), Some(&b"kefir"[..]));
+        assert_eq!(index.attachment(&rtxn, 0, "b.txt").unwrap(), Some(&b"doggo"[..]));
+        assert_eq!(index.attachment(&rtxn, 1, "a.txt").unwrap(), None);
+        drop(rtxn);
+
+        // an attachment is removed with `null`, even if the rest of the document didn't change
+        index
+            .add_documents(documents!([
+                { "id": 0, "name": "kefir", "_attachments": { "a.txt": null } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.attachment(&rtxn, 0, "a.txt").unwrap(), None);
+        assert_eq!(index.attachment(&rtxn, 0, "b.txt").unwrap(), Some(&b"doggo"[..]));
+        drop(rtxn);
+
+        // the attachments are removed along with their document
+        index.delete_documents(vec![S("0")]);
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.attachment(&rtxn, 0, "b.txt").unwrap(), None);
+        drop(rtxn);
+
+        let mut index = index;
+        index.indexer_config.attachments_quotas.max_attachment_size = 4;
+        let error = index
+            .add_documents(documents!([
+                { "id": 2, "_attachments": { "a.txt": "a2VmaXI=" } },
+            ]))
+            .unwrap_err();
+        insta::assert_snapshot!(error, @"Invalid `_attachments` field in the document with id: `2`. The attachment `a.txt` weighs 5 bytes, while the maximum size of an attachment is 4 bytes.");
+    }
 }
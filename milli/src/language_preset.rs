@@ -0,0 +1,126 @@
+use std::collections::{BTreeSet, HashMap};
+
+use charabia::{Language, Script};
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// A bundle of tokenization and typo tolerance settings tuned for the documents of a language.
+///
+/// Setting a preset forces the tokenizer to segment and normalize the text of the preset's
+/// script as this language, and fills the stop words and the typo tolerance settings that
+/// were not explicitly given in the same settings update.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum LanguagePreset {
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "ja")]
+    Japanese,
+    #[serde(rename = "zh")]
+    Chinese,
+    #[serde(rename = "ko")]
+    Korean,
+    #[serde(rename = "he")]
+    Hebrew,
+    #[serde(rename = "th")]
+    Thai,
+}
+
+impl LanguagePreset {
+    /// The language and script that the tokenizer must use, instead of detecting them.
+    pub fn script_language(&self) -> (Script, Language) {
+        match self {
+            LanguagePreset::English => (Script::Latin, Language::Eng),
+            LanguagePreset::French => (Script::Latin, Language::Fra),
+            LanguagePreset::German => (Script::Latin, Language::Deu),
+            LanguagePreset::Spanish => (Script::Latin, Language::Spa),
+            LanguagePreset::Japanese => (Script::Cj, Language::Jpn),
+            LanguagePreset::Chinese => (Script::Cj, Language::Cmn),
+            LanguagePreset::Korean => (Script::Hangul, Language::Kor),
+            LanguagePreset::Hebrew => (Script::Hebrew, Language::Heb),
+            LanguagePreset::Thai => (Script::Thai, Language::Tha),
+        }
+    }
+
+    /// The allow list given to the tokenizer.
+    pub fn allow_list(&self) -> HashMap<Script, Vec<Language>> {
+        let (script, language) = self.script_language();
+        HashMap::from([(script, vec![language])])
+    }
+
+    /// The most frequent words of the language.
+    pub fn stop_words(&self) -> BTreeSet<String> {
+        let words: &[&str] = match self {
+            LanguagePreset::English => &[
+                "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it",
+                "of", "on", "or", "that", "the", "this", "to", "was", "with",
+            ],
+            LanguagePreset::French => &[
+                "au", "aux", "ce", "d", "de", "des", "du", "en", "est", "et", "l", "la", "le",
+                "les", "pour", "que", "qui", "un", "une",
+            ],
+            LanguagePreset::German => &[
+                "das", "dem", "den", "der", "die", "ein", "eine", "einen", "es", "ist", "im", "in",
+                "mit", "und", "von", "zu",
+            ],
+            LanguagePreset::Spanish => &[
+                "a", "de", "del", "el", "en", "es", "la", "las", "lo", "los", "por", "que", "se",
+                "un", "una", "y",
+            ],
+            LanguagePreset::Japanese => &["が", "と", "に", "の", "は", "へ", "も", "を"],
+            LanguagePreset::Chinese => &["了", "和", "在", "是", "的"],
+            LanguagePreset::Korean => &["은", "는", "이", "가", "을", "를", "의", "에"],
+            LanguagePreset::Hebrew => &["את", "של", "על", "עם"],
+            LanguagePreset::Thai => &["และ", "ของ", "ที่", "ใน"],
+        };
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    /// The minimum word sizes for one and two typos, or `None` if typos must not be allowed
+    /// because a single character already carries too much meaning.
+    pub fn min_word_len_typos(&self) -> Option<(u8, u8)> {
+        match self {
+            LanguagePreset::English
+            | LanguagePreset::French
+            | LanguagePreset::German
+            | LanguagePreset::Spanish => Some((5, 9)),
+            LanguagePreset::Hebrew => Some((4, 8)),
+            LanguagePreset::Korean => Some((3, 6)),
+            LanguagePreset::Japanese | LanguagePreset::Chinese | LanguagePreset::Thai => None,
+        }
+    }
+}
+
+/// The allow list given to the tokenizer for a text written in any of the languages.
+pub fn languages_allow_list(languages: &[LanguagePreset]) -> HashMap<Script, Vec<Language>> {
+    let mut allow_list: HashMap<Script, Vec<Language>> = HashMap::new();
+    for language in languages {
+        let (script, language) = language.script_language();
+        let languages = allow_list.entry(script).or_default();
+        if !languages.contains(&language) {
+            languages.push(language);
+        }
+    }
+    allow_list
+}
+
+/// Returns the given stop words along with the stop words of the languages.
+pub fn languages_stop_words<D: AsRef<[u8]>>(
+    stop_words: Option<&fst::Set<D>>,
+    languages: &[LanguagePreset],
+) -> Result<fst::Set<Vec<u8>>> {
+    let mut words: BTreeSet<String> = match stop_words {
+        Some(stop_words) => stop_words.stream().into_strs()?.into_iter().collect(),
+        None => BTreeSet::new(),
+    };
+    for language in languages {
+        words.extend(language.stop_words());
+    }
+    Ok(fst::Set::from_iter(words)?)
+}
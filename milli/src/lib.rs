@@ -8,20 +8,41 @@ pub static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 #[macro_use]
 pub mod documents;
 
+pub mod acl;
 mod asc_desc;
+pub mod attachments;
+pub mod batch_limits;
+pub mod blocked_terms;
+pub mod chunking;
+pub mod collation;
+pub mod computed_fields;
 mod criterion;
+pub mod document_hook;
 mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
+pub mod fields_schema;
 pub mod heed_codec;
 pub mod index;
+pub mod language_preset;
+pub mod new_fields;
 pub mod order_by_map;
+pub mod phonetic;
+pub mod pii;
+pub mod position_decay;
+pub mod prepared_filter;
 pub mod prompt;
 pub mod proximity;
+pub mod query_limits;
+pub mod query_rewrites;
+pub mod regex_filter;
 pub mod score_details;
 mod search;
+pub mod sort_expression;
 mod thread_pool_no_abort;
+pub mod tie_breaker;
+pub mod typo;
 pub mod update;
 pub mod vector;
 
@@ -40,15 +61,19 @@ pub use filter_parser::{Condition, FilterCondition, Span, Token};
 use fxhash::{FxHasher32, FxHasher64};
 pub use grenad::CompressionType;
 pub use search::new::{
-    execute_search, filtered_universe, DefaultSearchLogger, GeoSortStrategy, SearchContext,
-    SearchLogger, VisualSearchLogger,
+    execute_search, filtered_universe, ranking_rule_plugin, register_ranking_rule_plugin,
+    DefaultSearchLogger, GeoSortParameter, GeoSortStrategy, InterpretedQueryTerm,
+    QueryInterpretation, QueryTermKind, QueryTermLocation, RankingRulePlugin,
+    RankingRulePluginBuckets, SearchContext, SearchLogger, SearchTimings, VisualSearchLogger,
 };
 use serde_json::Value;
 pub use thread_pool_no_abort::{PanicCatched, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
 pub use {charabia as tokenizer, heed, rhai};
 
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
-pub use self::criterion::{default_criteria, Criterion, CriterionError};
+pub use self::criterion::{
+    default_criteria, Criterion, CriterionError, DecayFunction, DecayOrigin, DecayScale, HalfLife,
+};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
@@ -57,12 +82,15 @@ pub use self::fieldids_weights_map::FieldidsWeightsMap;
 pub use self::fields_ids_map::FieldsIdsMap;
 pub use self::heed_codec::{
     BEU16StrCodec, BEU32StrCodec, BoRoaringBitmapCodec, BoRoaringBitmapLenCodec,
-    CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, FieldIdWordCountCodec, ObkvCodec,
-    RoaringBitmapCodec, RoaringBitmapLenCodec, StrBEU32Codec, U8StrStrCodec,
-    UncheckedU8StrStrCodec,
+    CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, CboRoaringBitmapView, FieldIdBoolCodec,
+    FieldIdWordCountCodec, ObkvCodec, RoaringBitmapCodec, RoaringBitmapLenCodec, StrBEU32Codec,
+    U8StrStrCodec, UncheckedU8StrStrCodec,
 };
 pub use self::index::Index;
-pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
+pub use self::search::facet::{
+    FacetValueHit, FacetValues, FacetValuesPage, SearchForFacetValues,
+    DEFAULT_FACET_VALUES_PAGE_SIZE,
+};
 pub use self::search::similar::Similar;
 pub use self::search::{
     FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
@@ -111,6 +139,9 @@ pub const MAX_WORD_LENGTH: usize = MAX_LMDB_KEY_LENGTH / 2;
 
 pub const MAX_POSITION_PER_ATTRIBUTE: u32 = u16::MAX as u32 + 1;
 
+/// The time budget of a search, in milliseconds, when no `searchCutoffMs` is set.
+pub const DEFAULT_SEARCH_CUTOFF_MS: u64 = 1500;
+
 #[derive(Clone)]
 pub struct TimeBudget {
     started_at: std::time::Instant,
@@ -134,7 +165,7 @@ impl fmt::Debug for TimeBudget {
 
 impl Default for TimeBudget {
     fn default() -> Self {
-        Self::new(std::time::Duration::from_millis(1500))
+        Self::new(std::time::Duration::from_millis(DEFAULT_SEARCH_CUTOFF_MS))
     }
 }
 
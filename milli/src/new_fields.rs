@@ -0,0 +1,33 @@
+//! How the fields discovered by the document additions are handled.
+//!
+//! By default every new field is searchable, through the `*` searchable attributes, and none
+//! is filterable. Indexes receiving documents with arbitrary keys, like logs, can instead keep
+//! their new fields out of the searchable attributes, or make them filterable right away.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NewFieldsPolicy {
+    /// Whether the new fields are searchable when the searchable attributes are `*`.
+    ///
+    /// When they aren't, the `*` is replaced by the fields known before the first addition
+    /// introducing new fields.
+    pub searchable: bool,
+    /// Whether the new fields are added to the filterable attributes.
+    pub filterable: bool,
+}
+
+impl Default for NewFieldsPolicy {
+    fn default() -> Self {
+        NewFieldsPolicy { searchable: true, filterable: false }
+    }
+}
+
+impl NewFieldsPolicy {
+    /// Whether the policy applies to the given field, the reserved fields like `_geo`
+    /// or `_vectors`, starting with an underscore, are left untouched.
+    pub fn applies_to(field: &str) -> bool {
+        !field.starts_with('_')
+    }
+}
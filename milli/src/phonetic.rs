@@ -0,0 +1,126 @@
+//! Phonetic matching, enabled with the `typoTolerance.enablePhonetic` setting.
+//!
+//! The words of the index are grouped by their Soundex key in an FST built at indexing time,
+//! so that the words of a query also match the words sounding like them, e.g. `jon` and `john`,
+//! however many typos apart they are. The phonetic derivations are ranked like two-typo ones.
+
+use std::collections::BTreeSet;
+
+use fst::automaton::Str;
+use fst::{Automaton, IntoStreamer, Streamer};
+
+use crate::Result;
+
+/// The byte separating the phonetic key of a word from the word itself in the phonetic FST.
+pub const PHONETIC_KEY_SEPARATOR: u8 = 0;
+
+/// Returns the American Soundex key of the word, e.g. `J500` for `john`.
+///
+/// Only the words made of ASCII letters have a key.
+pub fn soundex(word: &str) -> Option<String> {
+    if word.is_empty() || !word.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut bytes = word.bytes().map(|b| b.to_ascii_lowercase());
+    let first = bytes.next()?;
+    let mut key = String::with_capacity(4);
+    key.push(first.to_ascii_uppercase() as char);
+
+    let mut last_code = soundex_code(first);
+    for byte in bytes {
+        match byte {
+            // `h` and `w` don't separate two letters with the same code
+            b'h' | b'w' => continue,
+            _ => {
+                let code = soundex_code(byte);
+                if code != 0 && code != last_code {
+                    key.push((b'0' + code) as char);
+                    if key.len() == 4 {
+                        break;
+                    }
+                }
+                last_code = code;
+            }
+        }
+    }
+
+    while key.len() < 4 {
+        key.push('0');
+    }
+    Some(key)
+}
+
+/// The Soundex code of a lowercase letter, the vowels being coded `0`.
+fn soundex_code(letter: u8) -> u8 {
+    match letter {
+        b'b' | b'f' | b'p' | b'v' => 1,
+        b'c' | b'g' | b'j' | b'k' | b'q' | b's' | b'x' | b'z' => 2,
+        b'd' | b't' => 3,
+        b'l' => 4,
+        b'm' | b'n' => 5,
+        b'r' => 6,
+        _ => 0,
+    }
+}
+
+/// Builds the phonetic FST of the given words dictionary.
+///
+/// Each entry is the phonetic key of a word followed by the [`PHONETIC_KEY_SEPARATOR`]
+/// and by the word, so that the words sharing a key are found with a prefix search.
+pub fn build_phonetic_fst<A: AsRef<[u8]>>(words_fst: &fst::Set<A>) -> Result<fst::Set<Vec<u8>>> {
+    let mut entries = BTreeSet::new();
+    let mut stream = words_fst.stream();
+    while let Some(word) = stream.next() {
+        let Ok(word) = std::str::from_utf8(word) else { continue };
+        if let Some(key) = soundex(word) {
+            let mut entry = key.into_bytes();
+            entry.push(PHONETIC_KEY_SEPARATOR);
+            entry.extend_from_slice(word.as_bytes());
+            entries.insert(entry);
+        }
+    }
+    Ok(fst::Set::from_iter(entries)?)
+}
+
+/// Returns the words of the phonetic FST sharing the phonetic key of the given word.
+pub fn phonetic_words<A: AsRef<[u8]>>(phonetic_fst: &fst::Set<A>, word: &str) -> Vec<String> {
+    let Some(key) = soundex(word) else { return Vec::new() };
+    let prefix = format!("{key}{}", PHONETIC_KEY_SEPARATOR as char);
+
+    let mut stream = phonetic_fst.search(Str::new(&prefix).starts_with()).into_stream();
+    let mut words = Vec::new();
+    while let Some(entry) = stream.next() {
+        if let Ok(derived_word) = std::str::from_utf8(&entry[prefix.len()..]) {
+            words.push(derived_word.to_string());
+        }
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn soundex_keys() {
+        assert_eq!(soundex("robert").as_deref(), Some("R163"));
+        assert_eq!(soundex("rupert").as_deref(), Some("R163"));
+        assert_eq!(soundex("ashcraft").as_deref(), Some("A261"));
+        assert_eq!(soundex("tymczak").as_deref(), Some("T522"));
+        assert_eq!(soundex("pfister").as_deref(), Some("P236"));
+        assert_eq!(soundex("jon").as_deref(), Some("J500"));
+        assert_eq!(soundex("john").as_deref(), Some("J500"));
+        assert_eq!(soundex("é"), None);
+        assert_eq!(soundex("42"), None);
+    }
+
+    #[test]
+    fn words_sharing_a_key() {
+        let words = fst::Set::from_iter(["jon", "john", "joan", "jane", "smith", "42"]).unwrap();
+        let phonetic_fst = build_phonetic_fst(&words).unwrap();
+        assert_eq!(phonetic_words(&phonetic_fst, "jon"), vec!["jane", "joan", "john", "jon"]);
+        assert_eq!(phonetic_words(&phonetic_fst, "smyth"), vec!["smith"]);
+        assert!(phonetic_words(&phonetic_fst, "42").is_empty());
+    }
+}
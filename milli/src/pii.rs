@@ -0,0 +1,244 @@
+//! Attributes holding personal information ("PII"), e.g. an email address or a phone number.
+//!
+//! The values of these attributes are encrypted with AES-256-GCM when the documents are added,
+//! with a key given to the engine at startup, so that they are never stored in clear in the
+//! index. They are only decrypted for the API keys allowed to see them, the other keys never
+//! see these attributes. When the PII attributes change, the stored values of the attributes
+//! that became PII are encrypted and the ones of the attributes that stopped being PII are
+//! decrypted, and these attributes are indexed again.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, Seek};
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine as _;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::documents::{obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader};
+use crate::{InternalError, Object, Result};
+
+/// The prefix of the strings holding an encrypted value.
+const ENCRYPTED_PREFIX: &str = "pii:v1:";
+/// The length in bytes of the nonces of AES-256-GCM.
+const NONCE_LEN: usize = 12;
+
+/// Encrypts and decrypts the values of the PII attributes.
+#[derive(Clone)]
+pub struct PiiCipher {
+    cipher: Aes256Gcm,
+}
+
+impl PiiCipher {
+    /// Creates a cipher whose key is derived from the given secret.
+    pub fn new(secret: &str) -> Self {
+        let key = Sha256::digest(secret.as_bytes());
+        PiiCipher { cipher: Aes256Gcm::new(&key) }
+    }
+
+    /// Encrypts a value into a string holding its JSON representation.
+    pub fn encrypt(&self, value: &Value) -> Result<Value> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let plaintext = serde_json::to_vec(value).map_err(InternalError::SerdeJson)?;
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| InternalError::PiiEncryption)?;
+
+        let mut bytes = nonce.to_vec();
+        bytes.extend_from_slice(&ciphertext);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+        Ok(Value::String(format!("{ENCRYPTED_PREFIX}{encoded}")))
+    }
+
+    /// Decrypts a value encrypted by [`Self::encrypt`].
+    ///
+    /// Returns `None` if the value isn't encrypted or was encrypted with another key.
+    pub fn decrypt(&self, value: &Value) -> Option<Value> {
+        let encoded = value.as_str()?.strip_prefix(ENCRYPTED_PREFIX)?;
+        let bytes = base64::engine::general_purpose::STANDARD.decode(encoded).ok()?;
+        if bytes.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plaintext = self.cipher.decrypt(Nonce::from_slice(nonce), ciphertext).ok()?;
+        serde_json::from_slice(&plaintext).ok()
+    }
+
+    /// Encrypts the PII attributes of a document in place.
+    pub fn encrypt_document(
+        &self,
+        document: &mut Object,
+        pii_attributes: &BTreeSet<String>,
+    ) -> Result<()> {
+        for (field, value) in document.iter_mut() {
+            if pii_attributes.contains(field) {
+                *value = self.encrypt(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Decrypts the PII attributes of a document in place, keeping the values that aren't encrypted.
+    pub fn decrypt_document(&self, document: &mut Object, pii_attributes: &BTreeSet<String>) {
+        for (field, value) in document.iter_mut() {
+            if pii_attributes.contains(field) {
+                if let Some(decrypted) = self.decrypt(value) {
+                    *value = decrypted;
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for PiiCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PiiCipher").finish_non_exhaustive()
+    }
+}
+
+/// Removes the PII attributes of a document.
+pub fn remove_pii_attributes(document: &mut Object, pii_attributes: &BTreeSet<String>) {
+    document.retain(|field, _| !pii_attributes.contains(field));
+}
+
+/// Replaces every word of the given attributes of a document by a salted hash of the word.
+///
+/// The anonymized values keep the same number of words, in the same order and with the same
+/// repetitions, so that the documents are ranked like the original ones. A new salt must be used
+/// for every anonymization, so that the common words can't be found back by hashing a dictionary.
+pub fn hash_attributes(document: &mut Object, attributes: &BTreeSet<String>, salt: &[u8]) {
+    for (field, value) in document.iter_mut() {
+        if attributes.contains(field) {
+            hash_value(value, salt);
+        }
+    }
+}
+
+fn hash_value(value: &mut Value, salt: &[u8]) {
+    match value {
+        Value::String(string) => *string = hash_words(string, salt),
+        Value::Number(number) => *value = Value::String(hash_words(&number.to_string(), salt)),
+        Value::Array(values) => values.iter_mut().for_each(|value| hash_value(value, salt)),
+        Value::Object(object) => object.values_mut().for_each(|value| hash_value(value, salt)),
+        Value::Null | Value::Bool(_) => (),
+    }
+}
+
+/// Hashes the alphanumeric words of a string, keeping the characters separating them.
+fn hash_words(string: &str, salt: &[u8]) -> String {
+    let hash = |word: &str| {
+        let digest = Sha256::new().chain_update(salt).chain_update(word.to_lowercase()).finalize();
+        digest[..8].iter().map(|byte| format!("{byte:02x}")).collect::<String>()
+    };
+
+    let mut hashed = String::with_capacity(string.len());
+    let mut word_start = None;
+    for (i, c) in string.char_indices() {
+        match (c.is_alphanumeric(), word_start) {
+            (true, None) => word_start = Some(i),
+            (false, Some(start)) => {
+                hashed.push_str(&hash(&string[start..i]));
+                hashed.push(c);
+                word_start = None;
+            }
+            (true, Some(_)) => (),
+            (false, None) => hashed.push(c),
+        }
+    }
+    if let Some(start) = word_start {
+        hashed.push_str(&hash(&string[start..]));
+    }
+    hashed
+}
+
+/// Encrypts the PII attributes of every document of the batch and returns the resulting batch.
+pub(crate) fn encrypt_pii_attributes<R: Read + Seek>(
+    cipher: &PiiCipher,
+    pii_attributes: &BTreeSet<String>,
+    reader: DocumentsBatchReader<R>,
+) -> Result<DocumentsBatchReader<File>> {
+    let (mut cursor, fields_index) = reader.into_cursor_and_fields_index();
+    let mut builder = tempfile::tempfile().map(DocumentsBatchBuilder::new)?;
+    while let Some(document) = cursor.next_document()? {
+        let mut document = obkv_to_object(&document, &fields_index)?;
+        cipher.encrypt_document(&mut document, pii_attributes)?;
+        builder.append_json_object(&document)?;
+    }
+
+    let file = builder.into_inner()?;
+    Ok(DocumentsBatchReader::from_reader(file)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt() {
+        let cipher = PiiCipher::new("kefir");
+        let pii_attributes = BTreeSet::from(["email".to_string(), "phones".to_string()]);
+
+        let mut document = json!({
+            "id": 1,
+            "email": "kefir@meilisearch.com",
+            "phones": ["+33 6 12 34 56 78"],
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+        let original = document.clone();
+
+        cipher.encrypt_document(&mut document, &pii_attributes).unwrap();
+        assert_eq!(document["id"], json!(1));
+        assert!(document["email"].as_str().unwrap().starts_with(ENCRYPTED_PREFIX));
+        assert!(document["phones"].as_str().unwrap().starts_with(ENCRYPTED_PREFIX));
+
+        // another key can't decrypt the values
+        assert_eq!(PiiCipher::new("intel").decrypt(&document["email"]), None);
+
+        let mut decrypted = document.clone();
+        cipher.decrypt_document(&mut decrypted, &pii_attributes);
+        assert_eq!(decrypted, original);
+
+        remove_pii_attributes(&mut document, &pii_attributes);
+        assert_eq!(document, json!({ "id": 1 }).as_object().unwrap().clone());
+    }
+
+    #[test]
+    fn hashed_attributes_keep_their_structure() {
+        let attributes = BTreeSet::from(["name".to_string(), "contact".to_string()]);
+        let mut document = json!({
+            "id": 1,
+            "name": "Kefir the dog, kefir",
+            "contact": { "email": "kefir@meilisearch.com", "phone": 33612345678u64, "pro": true },
+        })
+        .as_object()
+        .unwrap()
+        .clone();
+
+        let mut hashed = document.clone();
+        hash_attributes(&mut hashed, &attributes, b"salt");
+        assert_eq!(hashed["id"], json!(1));
+        assert_eq!(hashed["contact"]["pro"], json!(true));
+
+        // the separators are kept and the same words get the same hash, whatever their case
+        let name: Vec<_> = hashed["name"].as_str().unwrap().split([' ', ',']).collect();
+        assert_eq!(name.len(), 5);
+        assert_eq!(name[0], name[4]);
+        assert_ne!(name[0], name[1]);
+        assert!(!name[0].contains("efir"));
+        let email = hashed["contact"]["email"].as_str().unwrap();
+        assert_eq!(email.matches(['@', '.']).count(), 2);
+        assert!(hashed["contact"]["phone"].is_string());
+
+        // another salt gives other hashes
+        hash_attributes(&mut document, &attributes, b"pepper");
+        assert_ne!(document["name"], hashed["name"]);
+    }
+}
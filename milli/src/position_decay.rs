@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// The cost of the words found the farthest from their position in the query.
+pub const MAX_POSITION_COST: u32 = 10;
+/// The number of consecutive positions sharing the same cost with the linear decay.
+const LINEAR_STEP: u32 = 8;
+
+/// How the relevancy of a matched word decays as it is found farther in the attribute than
+/// its position in the query, as ranked by the position part of the `attribute` ranking rule.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PositionDecay {
+    /// The cost grows quickly over the first positions, then more and more slowly.
+    #[default]
+    Log,
+    /// The cost grows by one every eight positions.
+    Linear,
+    /// The position of the words doesn't matter.
+    None,
+}
+
+impl PositionDecay {
+    /// Returns the cost of a word found `distance` positions away from its position in the query.
+    pub fn cost(self, distance: u32) -> u32 {
+        match self {
+            PositionDecay::Log => match distance {
+                0 => 0,
+                1 => 1,
+                2..=4 => 2,
+                5..=7 => 3,
+                8..=11 => 4,
+                12..=16 => 5,
+                17..=24 => 6,
+                25..=64 => 7,
+                65..=256 => 8,
+                257..=1024 => 9,
+                _ => MAX_POSITION_COST,
+            },
+            PositionDecay::Linear => distance.div_ceil(LINEAR_STEP).min(MAX_POSITION_COST),
+            PositionDecay::None => 0,
+        }
+    }
+
+    /// Returns the cost of the words found the farthest from their position in the query.
+    pub fn max_cost(self) -> u32 {
+        match self {
+            PositionDecay::Log | PositionDecay::Linear => MAX_POSITION_COST,
+            PositionDecay::None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn costs() {
+        let costs = |decay: PositionDecay| -> Vec<u32> {
+            [0, 1, 4, 8, 9, 30, 100, 2000].into_iter().map(|d| decay.cost(d)).collect()
+        };
+        assert_eq!(costs(PositionDecay::Log), [0, 1, 2, 4, 4, 7, 8, 10]);
+        assert_eq!(costs(PositionDecay::Linear), [0, 1, 1, 1, 2, 4, 10, 10]);
+        assert_eq!(costs(PositionDecay::None), [0; 8]);
+    }
+}
@@ -0,0 +1,153 @@
+//! Named filter templates defined in the settings, e.g. `"mine": "user_id = {uid}"`,
+//! that the search requests reference along with the values of their parameters.
+//!
+//! The values of the parameters are always rendered as quoted strings or numbers,
+//! so that they can't alter the structure of the filter. The placeholders must
+//! therefore not be quoted in the templates.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use filter_parser::FilterCondition;
+use serde_json::Value;
+
+use crate::error::UserError;
+
+/// Returns the names of the parameters of a prepared filter, in the order of their placeholders.
+pub fn prepared_filter_parameters(template: &str) -> Vec<&str> {
+    placeholders(template).map(|(_, parameter)| parameter).collect()
+}
+
+/// Checks that a prepared filter is a valid filter once its parameters are given.
+pub fn validate_prepared_filter(name: &str, template: &str) -> Result<(), UserError> {
+    let filter = substitute(template, |position, _parameter| {
+        // the lists are only expected after the `IN` operator
+        let before = template[..position].trim_end().to_ascii_uppercase();
+        Ok::<_, UserError>(if before.ends_with("IN") { "[0]" } else { "0" }.to_string())
+    })?;
+    match FilterCondition::parse(&filter) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(UserError::InvalidPreparedFilter {
+            name: name.to_string(),
+            error: error.to_string(),
+        }),
+    }
+}
+
+/// Replaces the placeholders of a prepared filter by the values of its parameters.
+pub fn render_prepared_filter(
+    name: &str,
+    template: &str,
+    parameters: &BTreeMap<String, Value>,
+) -> Result<String, UserError> {
+    let invalid = |error: String| UserError::InvalidPreparedFilterParameters {
+        name: name.to_string(),
+        error,
+    };
+
+    let expected: BTreeSet<_> = prepared_filter_parameters(template).into_iter().collect();
+    if let Some(unknown) = parameters.keys().find(|p| !expected.contains(p.as_str())) {
+        let expected = expected.iter().map(|p| format!("`{p}`")).collect::<Vec<_>>().join(", ");
+        return Err(invalid(format!(
+            "Unknown parameter `{unknown}`, expected one of: {expected}."
+        )));
+    }
+
+    substitute(template, |_position, parameter| {
+        let value = parameters
+            .get(parameter)
+            .ok_or_else(|| invalid(format!("Missing the `{parameter}` parameter.")))?;
+        render_value(value).ok_or_else(|| {
+            invalid(format!(
+                "The `{parameter}` parameter must be a string, a number, a boolean or an array of them, but found `{value}`."
+            ))
+        })
+    })
+}
+
+/// Replaces each placeholder of a template by the value returned for its position and parameter.
+fn substitute<E>(
+    template: &str,
+    mut value: impl FnMut(usize, &str) -> Result<String, E>,
+) -> Result<String, E> {
+    let mut output = String::with_capacity(template.len());
+    let mut last = 0;
+    for (position, parameter) in placeholders(template) {
+        output.push_str(&template[last..position]);
+        output.push_str(&value(position, parameter)?);
+        last = position + parameter.len() + 2;
+    }
+    output.push_str(&template[last..]);
+    Ok(output)
+}
+
+/// Returns the byte position and the parameter name of the `{parameter}` placeholders of a template.
+fn placeholders(template: &str) -> impl Iterator<Item = (usize, &str)> {
+    template.match_indices('{').filter_map(move |(start, _)| {
+        let rest = &template[start + 1..];
+        let end = rest.find('}')?;
+        let parameter = &rest[..end];
+        let is_identifier = !parameter.is_empty()
+            && parameter.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        is_identifier.then_some((start, parameter))
+    })
+}
+
+fn render_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(number) => Some(number.to_string()),
+        Value::String(string) => Some(quote(string)),
+        Value::Bool(boolean) => Some(quote(&boolean.to_string())),
+        Value::Array(values) => {
+            let values = values
+                .iter()
+                .map(|value| match value {
+                    Value::Array(_) => None,
+                    value => render_value(value),
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("[{}]", values.join(", ")))
+        }
+        Value::Null | Value::Object(_) => None,
+    }
+}
+
+fn quote(string: &str) -> String {
+    format!("\"{}\"", string.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn render() {
+        let template = "user_id = {uid} AND tag IN {tags} AND NOT title = {uid}";
+        assert_eq!(prepared_filter_parameters(template), vec!["uid", "tags", "uid"]);
+
+        let parameters = BTreeMap::from([
+            ("uid".to_string(), json!("a\" OR \"b")),
+            ("tags".to_string(), json!(["x", 2, true])),
+        ]);
+        insta::assert_snapshot!(
+            render_prepared_filter("mine", template, &parameters).unwrap(),
+            @r###"user_id = "a\" OR \"b" AND tag IN ["x", 2, "true"] AND NOT title = "a\" OR \"b""###
+        );
+
+        let parameters = BTreeMap::from([("uid".to_string(), json!(1))]);
+        insta::assert_snapshot!(
+            render_prepared_filter("mine", template, &parameters).unwrap_err(),
+            @"The parameters of the `mine` prepared filter are invalid. Missing the `tags` parameter."
+        );
+
+        let parameters = BTreeMap::from([("doggo".to_string(), json!(1))]);
+        insta::assert_snapshot!(
+            render_prepared_filter("mine", template, &parameters).unwrap_err(),
+            @"The parameters of the `mine` prepared filter are invalid. Unknown parameter `doggo`, expected one of: `tags`, `uid`."
+        );
+
+        assert!(validate_prepared_filter("mine", template).is_ok());
+        assert!(validate_prepared_filter("broken", "user_id = {uid} AND").is_err());
+    }
+}
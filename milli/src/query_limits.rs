@@ -0,0 +1,49 @@
+//! Limits on the size of the search queries.
+//!
+//! Every word and phrase of a query adds nodes to the query graph, so the very long queries,
+//! whether legitimate or adversarial, are expensive to resolve and to rank. The parts of a
+//! query beyond the limits are ignored and the search result is flagged as truncated.
+
+use serde::{Deserialize, Serialize};
+
+/// The number of words of a query that are searched when no limit is configured.
+pub const DEFAULT_MAX_QUERY_TERMS: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryLimits {
+    /// The number of characters of a query beyond which the end of the query is ignored.
+    #[serde(default)]
+    pub max_length: Option<usize>,
+    /// The number of words and phrases of a query beyond which the next ones are ignored.
+    #[serde(default)]
+    pub max_terms: Option<usize>,
+    /// The number of phrases of a query beyond which the next phrases are ignored.
+    #[serde(default)]
+    pub max_phrases: Option<usize>,
+}
+
+impl QueryLimits {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Returns the query truncated to the maximum length, if it is longer.
+    pub fn truncate_query<'q>(&self, query: &'q str) -> Option<&'q str> {
+        let max_length = self.max_length?;
+        query.char_indices().nth(max_length).map(|(end, _)| &query[..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_query() {
+        let limits = QueryLimits { max_length: Some(5), ..Default::default() };
+        assert_eq!(limits.truncate_query("kéfir"), None);
+        assert_eq!(limits.truncate_query("kéfir intel"), Some("kéfir"));
+        assert_eq!(QueryLimits::default().truncate_query("kéfir intel"), None);
+    }
+}
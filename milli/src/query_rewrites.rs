@@ -0,0 +1,152 @@
+//! Rules rewriting the search queries before they are tokenized, e.g. `tv` → `television`.
+//!
+//! The rules are applied in order, each one to the query rewritten by the previous ones.
+//! A literal rule matches its text case-insensitively wherever it isn't part of a longer
+//! word. A regex rule matches its regular expression as written, and can use its capture
+//! groups in the replacement, e.g. `$1`.
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::error::UserError;
+
+/// The maximum size in bytes of a compiled regular expression.
+const REGEX_SIZE_LIMIT: usize = 1024 * 1024; // 1 MiB
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRewriteRule {
+    /// The text, or the regular expression, to find in the queries.
+    pub find: String,
+    /// The text replacing the matches of `find`.
+    pub replace: String,
+    /// Whether `find` is a regular expression.
+    #[serde(default)]
+    pub regex: bool,
+}
+
+impl QueryRewriteRule {
+    /// Compiles the rule into the regular expression finding its matches.
+    pub fn compile(&self) -> Result<Regex, UserError> {
+        let invalid =
+            |error: String| UserError::InvalidQueryRewriteRule { find: self.find.clone(), error };
+        if self.find.is_empty() {
+            return Err(invalid("The text to find can't be empty.".to_string()));
+        }
+
+        let pattern = if self.regex {
+            self.find.clone()
+        } else {
+            // only bound the sides of the literal starting or ending with a word character
+            let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+            let mut pattern = String::new();
+            if self.find.starts_with(is_word_char) {
+                pattern.push_str(r"\b");
+            }
+            pattern.push_str(&regex::escape(&self.find));
+            if self.find.ends_with(is_word_char) {
+                pattern.push_str(r"\b");
+            }
+            pattern
+        };
+
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.regex)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .build()
+            .map_err(|error| invalid(error.to_string()))
+    }
+}
+
+/// A part of the query replaced by a rewrite rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedQueryRewrite {
+    /// The part of the query matched by the rule.
+    pub original: String,
+    /// The text it was replaced with.
+    pub rewritten: String,
+}
+
+/// Applies the rewrite rules to the query.
+///
+/// Returns the rewritten query along with the rewrites applied, in order,
+/// or `None` if no rule matched the query.
+pub fn rewrite_query(
+    query: &str,
+    rules: &[QueryRewriteRule],
+) -> Result<Option<(String, Vec<AppliedQueryRewrite>)>, UserError> {
+    let mut query = query.to_string();
+    let mut applied = Vec::new();
+
+    for rule in rules {
+        let regex = rule.compile()?;
+        let mut rewritten = String::with_capacity(query.len());
+        let mut last = 0;
+        for captures in regex.captures_iter(&query) {
+            let Some(matched) = captures.get(0).filter(|matched| !matched.is_empty()) else {
+                continue;
+            };
+            let mut replacement = String::new();
+            if rule.regex {
+                captures.expand(&rule.replace, &mut replacement);
+            } else {
+                replacement.push_str(&rule.replace);
+            }
+            rewritten.push_str(&query[last..matched.start()]);
+            rewritten.push_str(&replacement);
+            last = matched.end();
+            applied.push(AppliedQueryRewrite {
+                original: matched.as_str().to_string(),
+                rewritten: replacement,
+            });
+        }
+        rewritten.push_str(&query[last..]);
+        query = rewritten;
+    }
+
+    Ok((!applied.is_empty()).then_some((query, applied)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(find: &str, replace: &str, regex: bool) -> QueryRewriteRule {
+        QueryRewriteRule { find: find.to_string(), replace: replace.to_string(), regex }
+    }
+
+    fn rewrite(query: &str, rules: &[QueryRewriteRule]) -> Option<String> {
+        rewrite_query(query, rules).unwrap().map(|(query, _)| query)
+    }
+
+    #[test]
+    fn literal_rules() {
+        let rules = [rule("tv", "television", false)];
+        assert_eq!(rewrite("TV stand", &rules).as_deref(), Some("television stand"));
+        // the literals are not rewritten inside of the other words
+        assert_eq!(rewrite("atv tvs", &rules), None);
+
+        let rules = [rule("c++", "cplusplus", false), rule("cplusplus", "cpp", false)];
+        assert_eq!(rewrite("learn c++!", &rules).as_deref(), Some("learn cpp!"));
+        let (_, applied) = rewrite_query("learn c++!", &rules).unwrap().unwrap();
+        assert_eq!(
+            applied,
+            vec![
+                AppliedQueryRewrite { original: "c++".into(), rewritten: "cplusplus".into() },
+                AppliedQueryRewrite { original: "cplusplus".into(), rewritten: "cpp".into() },
+            ]
+        );
+    }
+
+    #[test]
+    fn regex_rules() {
+        let rules = [rule(r"(\d+)\s*in(ch(es)?)?\b", "$1 inch", true)];
+        assert_eq!(rewrite("55in tv", &rules).as_deref(), Some("55 inch tv"));
+        assert_eq!(rewrite("a 42 inches tv", &rules).as_deref(), Some("a 42 inch tv"));
+        // the empty matches are ignored
+        assert_eq!(rewrite("tv", &[rule("x*", "y", true)]), None);
+
+        assert!(rule("(", "", true).compile().is_err());
+        assert!(rule("", "nothing", false).compile().is_err());
+    }
+}
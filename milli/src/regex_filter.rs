@@ -0,0 +1,42 @@
+//! The `MATCHES` filter operator, e.g. `sku MATCHES "^AB-[0-9]{4}$"`, selecting the documents
+//! with a string facet value matched by a regular expression.
+//!
+//! Unlike the other operators, it can't use the ordering of the facet databases and must test
+//! every distinct value of the attribute, so it is disabled by default and meant for admin or
+//! analytics use cases. The regular expressions are guaranteed to run in linear time, and like
+//! the other operators they are matched case-insensitively against the normalized facet values.
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// The number of distinct values of an attribute a `MATCHES` condition can be evaluated on
+/// when none is configured.
+pub const DEFAULT_REGEX_FILTER_MAX_CANDIDATES: usize = 10_000;
+/// The maximum size in bytes of a compiled regular expression.
+const REGEX_SIZE_LIMIT: usize = 1024 * 1024; // 1 MiB
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RegexFilter {
+    /// Whether the `MATCHES` operator can be used in the filters.
+    #[serde(default)]
+    pub enabled: bool,
+    /// The number of distinct values of an attribute beyond which a `MATCHES` condition fails.
+    #[serde(default)]
+    pub max_candidates: Option<usize>,
+}
+
+impl RegexFilter {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn max_candidates(&self) -> usize {
+        self.max_candidates.unwrap_or(DEFAULT_REGEX_FILTER_MAX_CANDIDATES)
+    }
+}
+
+/// Compiles the regular expression of a `MATCHES` condition.
+pub fn compile_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).size_limit(REGEX_SIZE_LIMIT).build()
+}
@@ -3,7 +3,7 @@ use std::cmp::Ordering;
 use itertools::Itertools;
 use serde::Serialize;
 
-use crate::distance_between_two_points;
+use crate::{distance_between_two_points, HalfLife};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ScoreDetails {
@@ -17,6 +17,10 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    Plugin(Plugin),
+    Recency(Recency),
+    Decay(Decay),
+    Bm25(Rank),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -53,6 +57,10 @@ impl ScoreDetails {
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::Plugin(details) => Some(details.rank),
+            ScoreDetails::Recency(details) => Some(details.rank),
+            ScoreDetails::Decay(details) => Some(details.rank),
+            ScoreDetails::Bm25(details) => Some(*details),
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -101,6 +109,10 @@ impl ScoreDetails {
             ScoreDetails::Vector(vector) => {
                 RankOrValue::Score(vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64))
             }
+            ScoreDetails::Plugin(p) => RankOrValue::Rank(p.rank),
+            ScoreDetails::Recency(r) => RankOrValue::Rank(r.rank),
+            ScoreDetails::Decay(d) => RankOrValue::Rank(d.rank),
+            ScoreDetails::Bm25(b) => RankOrValue::Rank(*b),
             ScoreDetails::Skipped => RankOrValue::Rank(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -128,12 +140,15 @@ impl ScoreDetails {
                     order += 1;
                 }
                 ScoreDetails::Typo(typo) => {
-                    let typo_details = serde_json::json!({
+                    let mut typo_details = serde_json::json!({
                         "order": order,
                         "typoCount": typo.typo_count,
                         "maxTypoCount": typo.max_typo_count,
                         "score": typo.rank().local_score(),
                     });
+                    if !typo.terms.is_empty() {
+                        typo_details["terms"] = serde_json::json!(typo.terms);
+                    }
                     details_map.insert("typo".into(), typo_details);
                     order += 1;
                 }
@@ -258,6 +273,39 @@ impl ScoreDetails {
                     details_map.insert("vectorSort".into(), details);
                     order += 1;
                 }
+                ScoreDetails::Plugin(details) => {
+                    let plugin_details = serde_json::json!({
+                        "order": order,
+                        "score": details.rank.local_score(),
+                    });
+                    details_map.insert(format!("plugin:{}", details.name), plugin_details);
+                    order += 1;
+                }
+                ScoreDetails::Recency(details) => {
+                    let recency_details = serde_json::json!({
+                        "order": order,
+                        "score": details.rank.local_score(),
+                    });
+                    let rule = format!("recency({}, {})", details.field_name, details.half_life);
+                    details_map.insert(rule, recency_details);
+                    order += 1;
+                }
+                ScoreDetails::Decay(details) => {
+                    let decay_details = serde_json::json!({
+                        "order": order,
+                        "score": details.rank.local_score(),
+                    });
+                    details_map.insert(details.rule.clone(), decay_details);
+                    order += 1;
+                }
+                ScoreDetails::Bm25(bm25) => {
+                    let bm25_details = serde_json::json!({
+                        "order": order,
+                        "score": bm25.local_score(),
+                    });
+                    details_map.insert("relevancy:bm25".into(), bm25_details);
+                    order += 1;
+                }
                 ScoreDetails::Skipped => {
                     details_map
                         .insert("skipped".to_string(), serde_json::json!({ "order": order }));
@@ -329,18 +377,34 @@ impl ExactWords {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Typo {
     pub typo_count: u32,
     pub max_typo_count: u32,
+    /// The rank of the bucket, given by the costs of the typos, which differ from their counts
+    /// when half typos are enabled or the typo costs are configured.
+    pub rank: Rank,
+    /// The typos needed by each matched term of the query, when they are recorded.
+    pub terms: Vec<TermTypos>,
+}
+
+/// The typos a term of the query needed to match a document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TermTypos {
+    /// The word of the query, or the word made of several words of the query.
+    pub term: String,
+    pub typos: u8,
+    /// Whether the term matched a word split in several words, or several words
+    /// of the query concatenated.
+    pub split: bool,
+    /// Whether the term only matched longer words it is a prefix of.
+    pub prefix: bool,
 }
 
 impl Typo {
     pub fn rank(&self) -> Rank {
-        Rank {
-            rank: (self.max_typo_count + 1).saturating_sub(self.typo_count),
-            max_rank: (self.max_typo_count + 1),
-        }
+        self.rank
     }
 
     // max_rank = max_typo + 1
@@ -354,8 +418,39 @@ impl Typo {
         Typo {
             typo_count: rank.max_rank.saturating_sub(rank.rank),
             max_typo_count: rank.max_rank.saturating_sub(1),
+            rank,
+            terms: Vec::new(),
         }
     }
+
+    /// Returns the score of a bucket ranked by the costs of its typos, counting its typos
+    /// independently of these costs.
+    pub fn from_rank_and_count(rank: Rank, typo_count: u32, max_typo_count: u32) -> Typo {
+        Typo { typo_count, max_typo_count, rank, terms: Vec::new() }
+    }
+}
+
+/// The rank given by a ranking rule plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plugin {
+    pub name: String,
+    pub rank: Rank,
+}
+
+/// The rank given by the recency ranking rule, from the decayed boost of the date of the field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recency {
+    pub field_name: String,
+    pub half_life: HalfLife,
+    pub rank: Rank,
+}
+
+/// The rank given by the decay ranking rule, from the decayed boost of the value of the field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decay {
+    /// The ranking rule as written in the settings.
+    pub rule: String,
+    pub rank: Rank,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -9,7 +9,7 @@ use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 
 use crate::error::UserError;
-use crate::facet::FacetType;
+use crate::facet::{FacetType, FacetValueAliases};
 use crate::heed_codec::facet::{
     FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
 };
@@ -93,6 +93,7 @@ impl<'a> FacetDistribution<'a> {
         field_id: FieldId,
         facet_type: FacetType,
         candidates: &RoaringBitmap,
+        aliases: &FacetValueAliases,
         distribution: &mut IndexMap<String, u64>,
     ) -> heed::Result<()> {
         match facet_type {
@@ -139,10 +140,17 @@ impl<'a> FacetDistribution<'a> {
 
                     for result in iter {
                         let ((_, _, normalized_value), original_value) = result?;
-                        let (_, count) = normalized_distribution
+                        let (normalized_value, original_value) = aliases
+                            .displayed_value(normalized_value)
+                            .unwrap_or((normalized_value, original_value));
+                        let (_, count, last_docid) = normalized_distribution
                             .entry(normalized_value)
-                            .or_insert_with(|| (original_value, 0));
-                        *count += 1;
+                            .or_insert_with(|| (original_value, 0, None));
+                        // a document with several merged values is only counted once
+                        if *last_docid != Some(docid) {
+                            *count += 1;
+                            *last_docid = Some(docid);
+                        }
 
                         if normalized_distribution.len() == self.max_values_per_facet {
                             break 'outer;
@@ -152,7 +160,7 @@ impl<'a> FacetDistribution<'a> {
 
                 let iter = normalized_distribution
                     .into_iter()
-                    .map(|(_normalized, (original, count))| (original.to_string(), count));
+                    .map(|(_normalized, (original, count, _))| (original.to_string(), count));
                 distribution.extend(iter);
             }
         }
@@ -196,6 +204,7 @@ impl<'a> FacetDistribution<'a> {
         field_id: FieldId,
         candidates: &RoaringBitmap,
         order_by: OrderBy,
+        aliases: &FacetValueAliases,
         distribution: &mut IndexMap<String, u64>,
     ) -> heed::Result<()> {
         let search_function = match order_by {
@@ -211,28 +220,75 @@ impl<'a> FacetDistribution<'a> {
             |facet_key, nbr_docids, any_docid| {
                 let facet_key = StrRefCodec::bytes_decode(facet_key).unwrap();
 
-                let key: (FieldId, _, &str) = (field_id, any_docid, facet_key);
-                let original_string = self
-                    .index
-                    .field_id_docid_facet_strings
-                    .get(self.rtxn, &key)?
-                    .unwrap()
-                    .to_owned();
+                // the counts of the merged values are summed, a document
+                // with several of them is thus counted more than once
+                if let Some((_, displayed)) = aliases.displayed_value(facet_key) {
+                    *distribution.entry(displayed.to_string()).or_insert(0) += nbr_docids;
+                } else {
+                    let key: (FieldId, _, &str) = (field_id, any_docid, facet_key);
+                    let original_string = self
+                        .index
+                        .field_id_docid_facet_strings
+                        .get(self.rtxn, &key)?
+                        .unwrap()
+                        .to_owned();
+
+                    distribution.insert(original_string, nbr_docids);
+                }
 
-                distribution.insert(original_string, nbr_docids);
                 if distribution.len() == self.max_values_per_facet {
                     Ok(ControlFlow::Break(()))
                 } else {
                     Ok(ControlFlow::Continue(()))
                 }
             },
-        )
+        )?;
+
+        // the merged counts may not be in order anymore
+        if order_by == OrderBy::Count && !aliases.is_empty() {
+            distribution.sort_by(|_, left, _, right| right.cmp(left));
+        }
+
+        Ok(())
+    }
+
+    /// The booleans come after the strings, they are counted with the strings
+    /// they are displayed as.
+    fn facet_bools_distribution(
+        &self,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+        order_by: OrderBy,
+        distribution: &mut IndexMap<String, u64>,
+    ) -> heed::Result<()> {
+        let mut counted = false;
+        for value in [false, true] {
+            let docids = self.index.bool_faceted_documents_ids(self.rtxn, field_id, value)?;
+            let count = docids.intersection_len(candidates);
+            if count == 0 {
+                continue;
+            }
+            let key = value.to_string();
+            if distribution.len() == self.max_values_per_facet && !distribution.contains_key(&key) {
+                break;
+            }
+            *distribution.entry(key).or_insert(0) += count;
+            counted = true;
+        }
+
+        // the booleans are ordered by count along with the strings
+        if counted && order_by == OrderBy::Count {
+            distribution.sort_by(|_, left, _, right| right.cmp(left));
+        }
+
+        Ok(())
     }
 
     fn facet_values(
         &self,
         field_id: FieldId,
         order_by: OrderBy,
+        aliases: &FacetValueAliases,
     ) -> heed::Result<IndexMap<String, u64>> {
         use FacetType::{Number, String};
 
@@ -241,8 +297,10 @@ impl<'a> FacetDistribution<'a> {
             (OrderBy::Lexicographic, Some(cnd)) if cnd.len() <= CANDIDATES_THRESHOLD => {
                 // Classic search, candidates were specified, we must return facet values only related
                 // to those candidates. We also enter here for facet strings for performance reasons.
-                self.facet_distribution_from_documents(field_id, Number, cnd, &mut distribution)?;
-                self.facet_distribution_from_documents(field_id, String, cnd, &mut distribution)?;
+                let dist = &mut distribution;
+                self.facet_distribution_from_documents(field_id, Number, cnd, aliases, dist)?;
+                self.facet_distribution_from_documents(field_id, String, cnd, aliases, dist)?;
+                self.facet_bools_distribution(field_id, cnd, order_by, dist)?;
             }
             _ => {
                 let universe;
@@ -264,8 +322,10 @@ impl<'a> FacetDistribution<'a> {
                     field_id,
                     candidates,
                     order_by,
+                    aliases,
                     &mut distribution,
                 )?;
+                self.facet_bools_distribution(field_id, candidates, order_by, &mut distribution)?;
             }
         };
 
@@ -356,15 +416,18 @@ impl<'a> FacetDistribution<'a> {
             None => filterable_fields,
         };
 
+        let mut aliases = self.index.facet_value_aliases(self.rtxn)?;
         let mut distribution = BTreeMap::new();
         for (fid, name) in fields_ids_map.iter() {
             if crate::is_faceted(name, &fields) {
+                let aliases =
+                    aliases.remove(name).map(|a| FacetValueAliases::new(&a)).unwrap_or_default();
                 let order_by = self
                     .facets
                     .as_ref()
                     .and_then(|facets| facets.get(name).copied())
                     .unwrap_or(self.default_order_by);
-                let values = self.facet_values(fid, order_by)?;
+                let values = self.facet_values(fid, order_by, &aliases)?;
                 distribution.insert(name.to_string(), values);
             }
         }
@@ -398,11 +461,12 @@ mod tests {
     use std::iter;
 
     use big_s::S;
-    use maplit::hashset;
+    use maplit::{btreemap, hashset};
+    use roaring::RoaringBitmap;
 
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, OrderBy};
+    use crate::{milli_snap, FacetDistribution, Filter, OrderBy};
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -479,6 +543,55 @@ mod tests {
         milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 2}}"###);
     }
 
+    #[test]
+    fn facet_value_aliases() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("colour") });
+                settings.set_facet_value_aliases(btreemap! {
+                    S("colour") => btreemap! { S("Blue") => vec![S("navy")] },
+                });
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "colour": "Blue" },
+            { "colour": "Navy" },
+            { "colour": ["blue", "navy"] },
+            { "colour": "RED" }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        // a document with several merged values is only counted once
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .candidates([0, 1, 2, 3].iter().copied().collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 3, "RED": 1}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(iter::once(("colour", OrderBy::default())))
+            .candidates([1, 3].iter().copied().collect())
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 1, "RED": 1}}"###);
+
+        // the filters match all the merged values
+        let filter = Filter::from_str("colour = NAVY").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&txn, &index).unwrap(), RoaringBitmap::from_iter([0, 1, 2]));
+        let filter = Filter::from_str("colour != blue").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&txn, &index).unwrap(), RoaringBitmap::from_iter([3]));
+    }
+
     #[test]
     fn many_candidates_few_facet_values() {
         let mut index = TempIndex::new_with_map_size(4096 * 10_000);
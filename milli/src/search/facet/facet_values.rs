@@ -0,0 +1,130 @@
+use std::ops::Bound::{Included, Unbounded};
+
+use roaring::RoaringBitmap;
+
+use crate::error::UserError;
+use crate::heed_codec::facet::{FacetGroupKey, FacetGroupValue};
+use crate::search::facet::FacetValueHit;
+use crate::{FieldId, Index, Result};
+
+/// The default number of values returned in a page.
+pub const DEFAULT_FACET_VALUES_PAGE_SIZE: usize = 100;
+
+/// Lists the distinct string values of a facet along with their number of documents,
+/// in the lexicographic order of their normalized form, one page at a time.
+pub struct FacetValues<'a> {
+    facet: String,
+    from: Option<String>,
+    limit: usize,
+    candidates: Option<RoaringBitmap>,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+}
+
+/// A page of the values of a facet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetValuesPage {
+    pub values: Vec<FacetValueHit>,
+    /// The cursor to give to get the next page, or `None` if this is the last page.
+    pub next: Option<String>,
+}
+
+impl<'a> FacetValues<'a> {
+    pub fn new(rtxn: &'a heed::RoTxn<'a>, index: &'a Index, facet: String) -> FacetValues<'a> {
+        FacetValues {
+            facet,
+            from: None,
+            limit: DEFAULT_FACET_VALUES_PAGE_SIZE,
+            candidates: None,
+            rtxn,
+            index,
+        }
+    }
+
+    /// The cursor of the page, returned with the previous page.
+    pub fn from(&mut self, from: String) -> &mut Self {
+        self.from = Some(from);
+        self
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Only counts these documents, the values without any of them are skipped.
+    pub fn candidates(&mut self, candidates: RoaringBitmap) -> &mut Self {
+        self.candidates = Some(candidates);
+        self
+    }
+
+    pub fn execute(&self) -> Result<FacetValuesPage> {
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        if !crate::is_faceted(&self.facet, &filterable_fields) {
+            let (valid_fields, hidden_fields) =
+                self.index.remove_hidden_fields(self.rtxn, filterable_fields)?;
+
+            return Err(UserError::InvalidFacetSearchFacetName {
+                field: self.facet.clone(),
+                valid_fields,
+                hidden_fields,
+            }
+            .into());
+        }
+
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let mut page = FacetValuesPage { values: Vec::new(), next: None };
+        let field_id = match fields_ids_map.id(&self.facet) {
+            Some(field_id) => field_id,
+            // no document contains this field (yet).
+            None => return Ok(page),
+        };
+
+        let start = FacetGroupKey {
+            field_id,
+            level: 0,
+            left_bound: self.from.as_deref().unwrap_or_default(),
+        };
+        let range = (Included(&start), Unbounded);
+        for result in self.index.facet_id_string_docids.range(self.rtxn, &range)? {
+            let (key, FacetGroupValue { bitmap, .. }) = result?;
+            // the higher levels and the other fields are stored after the level 0 of the field
+            if key.field_id != field_id || key.level != 0 {
+                break;
+            }
+
+            let count = match &self.candidates {
+                Some(candidates) => candidates.intersection_len(&bitmap),
+                None => bitmap.len(),
+            };
+            if count == 0 {
+                continue;
+            }
+            if page.values.len() == self.limit {
+                page.next = Some(key.left_bound.to_string());
+                break;
+            }
+
+            let value = self
+                .original_value(field_id, key.left_bound, &bitmap)?
+                .unwrap_or_else(|| key.left_bound.to_string());
+            page.values.push(FacetValueHit { value, count });
+        }
+
+        Ok(page)
+    }
+
+    fn original_value(
+        &self,
+        field_id: FieldId,
+        normalized: &str,
+        docids: &RoaringBitmap,
+    ) -> Result<Option<String>> {
+        let docid = match self.candidates.as_ref().and_then(|c| (c & docids).min()) {
+            Some(docid) => docid,
+            None => docids.min().unwrap_or_default(),
+        };
+        let key: (FieldId, _, &str) = (field_id, docid, normalized);
+        Ok(self.index.field_id_docid_facet_strings.get(self.rtxn, &key)?.map(|v| v.to_owned()))
+    }
+}
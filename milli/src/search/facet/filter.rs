@@ -2,16 +2,22 @@ use std::collections::HashSet;
 use std::fmt::{Debug, Display};
 use std::ops::Bound::{self, Excluded, Included};
 
+use charabia::normalizer::{Normalize, NormalizerOption};
 use either::Either;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Token};
+use fst::{IntoStreamer, Streamer};
 use roaring::{MultiOps, RoaringBitmap};
 use serde_json::Value;
 
 use super::facet_range_search;
+use crate::acl::RESERVED_ACL_FIELD_NAME;
 use crate::error::{Error, UserError};
+use crate::facet::FacetValueAliases;
 use crate::heed_codec::facet::{
-    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec, OrderedF64Codec,
 };
+use crate::regex_filter::compile_regex;
+use crate::search::build_dfa;
 use crate::{distance_between_two_points, lat_lng_to_xyz, FieldId, Index, Result};
 
 /// The maximum number of filters the filter AST can process.
@@ -56,6 +62,10 @@ enum FilterError<'a> {
     AttributeNotFilterable { attribute: &'a str, filterable_fields: HashSet<String> },
     ParseGeoError(BadGeoError),
     TooDeep,
+    RegexFilterDisabled,
+    InvalidRegex(regex::Error),
+    TooManyRegexCandidates { attribute: &'a str, max_candidates: usize },
+    BooleanRange(bool),
 }
 impl<'a> std::error::Error for FilterError<'a> {}
 
@@ -96,6 +106,19 @@ impl<'a> Display for FilterError<'a> {
                 MAX_FILTER_DEPTH
             ),
             Self::ParseGeoError(error) => write!(f, "{}", error),
+            Self::RegexFilterDisabled => write!(
+                f,
+                "The `MATCHES` operator is disabled. It can be enabled with the `regexFilter` setting of the index."
+            ),
+            Self::InvalidRegex(error) => write!(f, "Invalid regular expression: {error}"),
+            Self::TooManyRegexCandidates { attribute, max_candidates } => write!(
+                f,
+                "The `MATCHES` operator can't be evaluated on the `{attribute}` attribute, it has more than {max_candidates} distinct values. The limit can be changed with the `regexFilter.maxCandidates` setting of the index."
+            ),
+            Self::BooleanRange(value) => write!(
+                f,
+                "`{value}` is a boolean, which can only be compared with the `=`, `!=` and `IN` operators."
+            ),
         }
     }
 }
@@ -242,18 +265,16 @@ impl<'a> Filter<'a> {
         // field id and the level.
 
         let (left, right) = match operator {
-            Condition::GreaterThan(val) => {
-                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
-            }
+            Condition::GreaterThan(val) => (Excluded(parse_range_bound(val)?), Included(f64::MAX)),
             Condition::GreaterThanOrEqual(val) => {
-                (Included(val.parse_finite_float()?), Included(f64::MAX))
+                (Included(parse_range_bound(val)?), Included(f64::MAX))
             }
-            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
+            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(parse_range_bound(val)?)),
             Condition::LowerThanOrEqual(val) => {
-                (Included(f64::MIN), Included(val.parse_finite_float()?))
+                (Included(f64::MIN), Included(parse_range_bound(val)?))
             }
             Condition::Between { from, to } => {
-                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+                (Included(parse_range_bound(from)?), Included(parse_range_bound(to)?))
             }
             Condition::Null => {
                 let is_null = index.null_faceted_documents_ids(rtxn, field_id)?;
@@ -268,17 +289,17 @@ impl<'a> Filter<'a> {
                 return Ok(exist);
             }
             Condition::Equal(val) => {
-                let string_docids = strings_db
-                    .get(
-                        rtxn,
-                        &FacetGroupKey {
-                            field_id,
-                            level: 0,
-                            left_bound: &crate::normalize_facet(val.value()),
-                        },
-                    )?
-                    .map(|v| v.bitmap)
-                    .unwrap_or_default();
+                let value = crate::normalize_facet(val.value());
+                // the values merged by the aliases of the field are all equal
+                let aliases = FacetValueAliases::of_field(index, rtxn, field_id)?;
+                let values = aliases.merged_values(&value).unwrap_or(std::slice::from_ref(&value));
+                let mut string_docids = RoaringBitmap::new();
+                for value in values {
+                    let key = FacetGroupKey { field_id, level: 0, left_bound: value.as_str() };
+                    if let Some(v) = strings_db.get(rtxn, &key)? {
+                        string_docids |= v.bitmap;
+                    }
+                }
                 let number = val.parse_finite_float().ok();
                 let number_docids = match number {
                     Some(n) => numbers_db
@@ -287,7 +308,12 @@ impl<'a> Filter<'a> {
                         .unwrap_or_default(),
                     None => RoaringBitmap::new(),
                 };
-                return Ok(string_docids | number_docids);
+                // the booleans are stored apart from the strings
+                let bool_docids = match value.parse::<bool>() {
+                    Ok(boolean) => index.bool_faceted_documents_ids(rtxn, field_id, boolean)?,
+                    Err(_) => RoaringBitmap::new(),
+                };
+                return Ok(string_docids | number_docids | bool_docids);
             }
             Condition::NotEqual(val) => {
                 let operator = Condition::Equal(val.clone());
@@ -295,6 +321,67 @@ impl<'a> Filter<'a> {
                 let all_ids = index.documents_ids(rtxn)?;
                 return Ok(all_ids - docids);
             }
+            Condition::FuzzyEqual(val) => {
+                let Some(fst) = index.facet_id_string_fst.get(rtxn, &field_id)? else {
+                    return Ok(RoaringBitmap::new());
+                };
+                // the FST contains the facet values normalized for the facet search
+                let options = NormalizerOption { lossy: true, ..Default::default() };
+                let value = crate::normalize_facet(val.value());
+                let value = value.normalize(&options);
+                let one_typo = index.min_word_len_one_typo(rtxn)?;
+                let typos = if value.chars().count() < one_typo as usize { 0 } else { 1 };
+
+                let mut docids = RoaringBitmap::new();
+                let mut stream = fst.search(build_dfa(&value, typos, false)).into_stream();
+                while let Some(normalized) = stream.next() {
+                    let normalized = std::str::from_utf8(normalized)?;
+                    let key = (field_id, normalized);
+                    let Some(originals) =
+                        index.facet_id_normalized_string_strings.get(rtxn, &key)?
+                    else {
+                        continue;
+                    };
+                    for original in originals {
+                        let key =
+                            FacetGroupKey { field_id, level: 0, left_bound: original.as_str() };
+                        if let Some(v) = strings_db.get(rtxn, &key)? {
+                            docids |= v.bitmap;
+                        }
+                    }
+                }
+                return Ok(docids);
+            }
+            Condition::Matches(val) => {
+                let regex_filter = index.regex_filter(rtxn)?;
+                if !regex_filter.enabled {
+                    return Err(val.as_external_error(FilterError::RegexFilterDisabled).into());
+                }
+                let regex = compile_regex(val.value())
+                    .map_err(|e| val.as_external_error(FilterError::InvalidRegex(e)))?;
+
+                // every distinct value of the attribute is tested, hence the limit
+                let max_candidates = regex_filter.max_candidates();
+                let mut docids = RoaringBitmap::new();
+                let prefix = FacetGroupKey { field_id, level: 0, left_bound: "" };
+                for (i, result) in strings_db.prefix_iter(rtxn, &prefix)?.enumerate() {
+                    if i == max_candidates {
+                        let fields_ids_map = index.fields_ids_map(rtxn)?;
+                        let attribute = fields_ids_map.name(field_id).unwrap_or_default();
+                        let error =
+                            FilterError::TooManyRegexCandidates { attribute, max_candidates };
+                        return Err(val.as_external_error(error).into());
+                    }
+                    let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) =
+                        result?;
+                    if universe.map_or(true, |universe| !universe.is_disjoint(&bitmap))
+                        && regex.is_match(left_bound)
+                    {
+                        docids |= bitmap;
+                    }
+                }
+                return Ok(docids);
+            }
         };
 
         let mut output = RoaringBitmap::new();
@@ -365,7 +452,8 @@ impl<'a> Filter<'a> {
                 }
             }
             FilterCondition::In { fid, els } => {
-                if crate::is_faceted(fid.value(), filterable_fields) {
+                if is_filterable(fid.value(), filterable_fields) {
+                    check_acl_indexed(fid.value(), rtxn, index)?;
                     let field_ids_map = index.fields_ids_map(rtxn)?;
                     if let Some(fid) = field_ids_map.id(fid.value()) {
                         els.iter()
@@ -383,7 +471,8 @@ impl<'a> Filter<'a> {
                 }
             }
             FilterCondition::Condition { fid, op } => {
-                if crate::is_faceted(fid.value(), filterable_fields) {
+                if is_filterable(fid.value(), filterable_fields) {
+                    check_acl_indexed(fid.value(), rtxn, index)?;
                     let field_ids_map = index.fields_ids_map(rtxn)?;
                     if let Some(fid) = field_ids_map.id(fid.value()) {
                         Self::evaluate_operator(rtxn, index, fid, universe, op)
@@ -611,6 +700,28 @@ impl<'a> From<FilterCondition<'a>> for Filter<'a> {
     }
 }
 
+/// Parses the bound of a range, refusing the booleans which are not ordered.
+fn parse_range_bound(val: &Token<'_>) -> Result<f64> {
+    if let Ok(boolean) = crate::normalize_facet(val.value()).parse::<bool>() {
+        return Err(val.as_external_error(FilterError::BooleanRange(boolean)).into());
+    }
+    Ok(val.parse_finite_float()?)
+}
+
+/// Returns `true` if the field can be filtered on, the `_acl` field always can.
+fn is_filterable(field: &str, filterable_fields: &HashSet<String>) -> bool {
+    field == RESERVED_ACL_FIELD_NAME || crate::is_faceted(field, filterable_fields)
+}
+
+/// Refuses to filter on the `_acl` field while some documents may lack its facets, as they
+/// would be considered visible to everyone.
+fn check_acl_indexed(field: &str, rtxn: &heed::RoTxn<'_>, index: &Index) -> Result<()> {
+    if field == RESERVED_ACL_FIELD_NAME && !index.acl_indexed(rtxn)? {
+        return Err(UserError::AclNotIndexed.into());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Write;
@@ -1069,6 +1180,151 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn fuzzy_equal_filter() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("brand") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "brand": "Adidas" },
+                { "id": 1, "brand": "adidas" },
+                { "id": 2, "brand": ["Puma", "Adídas"] },
+                { "id": 3, "brand": "Adidas Originals" },
+                { "id": 4, "brand": "Nike" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        // a missing letter
+        let filter = Filter::from_str("brand ~= adida").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2]));
+
+        // a replaced letter, the whole value must match
+        let filter = Filter::from_str("brand ~= 'Adidas Originels'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([3]));
+
+        // the short values must match exactly
+        let filter = Filter::from_str("brand ~= Nika").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert!(result.is_empty());
+
+        // more than one typo
+        let filter = Filter::from_str("NOT brand ~= adadis").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn regex_filter() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("sku") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "sku": "AB-1234" },
+                { "id": 1, "sku": "ab-99" },
+                { "id": 2, "sku": ["CD-5678", "AB-0000"] },
+                { "id": 3, "sku": "XAB-1234" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("sku MATCHES '^AB-[0-9]{4}$'").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with("The `MATCHES` operator is disabled."));
+        drop(rtxn);
+
+        index.update_settings(|settings| settings.set_regex_filter_enabled(true)).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 2]));
+
+        let filter = Filter::from_str("NOT sku MATCHES '^ab-'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([3]));
+
+        let filter = Filter::from_str("sku MATCHES '(unclosed'").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with("Invalid regular expression:"));
+        drop(rtxn);
+
+        index.update_settings(|settings| settings.set_regex_filter_max_candidates(4)).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("sku MATCHES '^ab-'").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "The `MATCHES` operator can't be evaluated on the `sku` attribute, it has more than 4 distinct values."
+        ));
+    }
+
+    #[test]
+    fn filter_bool() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_owned());
+                settings.set_filterable_fields(hashset! { S("published") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "published": true },
+                { "id": 1, "published": false },
+                { "id": 2, "published": [true, false] },
+                { "id": 3, "published": "True" },
+                { "id": 4 },
+                { "id": 5, "published": "yes" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index)
+        };
+        assert_eq!(evaluate("published = true").unwrap(), RoaringBitmap::from_iter([0, 2, 3]));
+        assert_eq!(evaluate("published = false").unwrap(), RoaringBitmap::from_iter([1, 2]));
+        assert_eq!(evaluate("published != true").unwrap(), RoaringBitmap::from_iter([1, 4, 5]));
+        assert_eq!(
+            evaluate("published IN [false, yes]").unwrap(),
+            RoaringBitmap::from_iter([1, 2, 5])
+        );
+
+        let error = evaluate("published > true").unwrap_err();
+        assert!(error.to_string().starts_with(
+            "`true` is a boolean, which can only be compared with the `=`, `!=` and `IN` operators."
+        ));
+        let error = evaluate("published false TO true").unwrap_err();
+        assert!(error.to_string().starts_with("`false` is a boolean"));
+        drop(rtxn);
+
+        // the booleans removed from the documents are removed from the filters
+        index.add_documents(documents!([{ "id": 0, "published": false }])).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("published = true").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2, 3]));
+        let filter = Filter::from_str("published = false").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2]));
+    }
+
     #[test]
     fn filter_number() {
         let index = TempIndex::new();
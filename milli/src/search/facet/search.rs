@@ -87,10 +87,8 @@ impl<'a> SearchForFacetValues<'a> {
             None => return Ok(Vec::new()),
         };
 
-        let fst = match self.search_query.index.facet_id_string_fst.get(rtxn, &fid)? {
-            Some(fst) => fst,
-            None => return Ok(Vec::new()),
-        };
+        // the fields containing only booleans don't have any string FST
+        let fst = self.search_query.index.facet_id_string_fst.get(rtxn, &fid)?;
 
         let search_candidates = self.search_query.execute_for_candidates(
             self.is_hybrid
@@ -107,8 +105,8 @@ impl<'a> SearchForFacetValues<'a> {
             OrderBy::Count => ValuesCollection::by_count(self.max_values),
         };
 
-        match self.query.as_ref() {
-            Some(query) => {
+        match (self.query.as_ref(), fst) {
+            (Some(query), Some(fst)) => {
                 let options = NormalizerOption { lossy: true, ..Default::default() };
                 let query = query.normalize(&options);
                 let query = query.as_ref();
@@ -179,7 +177,8 @@ impl<'a> SearchForFacetValues<'a> {
                     }
                 }
             }
-            None => {
+            (Some(_), None) => (),
+            (None, _) => {
                 let prefix = FacetGroupKey { field_id: fid, level: 0, left_bound: "" };
                 for result in index.facet_id_string_docids.prefix_iter(rtxn, &prefix)? {
                     let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) =
@@ -197,6 +196,21 @@ impl<'a> SearchForFacetValues<'a> {
             }
         }
 
+        // the booleans come after the strings and are matched as the strings they are displayed as
+        let options = NormalizerOption { lossy: true, ..Default::default() };
+        let query = self.query.as_ref().map(|query| query.normalize(&options).to_string());
+        for value in [false, true] {
+            let displayed = value.to_string();
+            if query.as_ref().map_or(false, |query| !displayed.starts_with(query.as_str())) {
+                continue;
+            }
+            let docids = index.bool_faceted_documents_ids(rtxn, fid, value)?;
+            let count = search_candidates.intersection_len(&docids);
+            if count != 0 && results.insert(FacetValueHit { value: displayed, count }).is_break() {
+                break;
+            }
+        }
+
         Ok(results.into_sorted_vec())
     }
 
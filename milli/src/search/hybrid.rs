@@ -5,7 +5,10 @@ use roaring::RoaringBitmap;
 
 use crate::score_details::{ScoreDetails, ScoreValue, ScoringStrategy};
 use crate::search::SemanticSearch;
-use crate::{MatchingWords, Result, Search, SearchResult};
+use crate::{
+    MatchingWords, QueryInterpretation, QueryTermLocation, Result, Search, SearchResult,
+    SearchTimings,
+};
 
 struct ScoreWithRatioResult {
     matching_words: MatchingWords,
@@ -13,6 +16,11 @@ struct ScoreWithRatioResult {
     document_scores: Vec<(u32, ScoreWithRatio)>,
     degraded: bool,
     used_negative_operator: bool,
+    query_truncated: bool,
+    query_term_locations: Vec<QueryTermLocation>,
+    query_interpretation: Option<QueryInterpretation>,
+    relaxed_phrases: bool,
+    timings: Option<SearchTimings>,
 }
 
 type ScoreWithRatio = (Vec<ScoreDetails>, f32);
@@ -82,6 +90,11 @@ impl ScoreWithRatioResult {
             document_scores,
             degraded: results.degraded,
             used_negative_operator: results.used_negative_operator,
+            query_truncated: results.query_truncated,
+            query_term_locations: results.query_term_locations,
+            query_interpretation: results.query_interpretation,
+            relaxed_phrases: results.relaxed_phrases,
+            timings: results.timings,
         }
     }
 
@@ -99,6 +112,14 @@ impl ScoreWithRatioResult {
         }
         let mut semantic_hit_count = 0;
 
+        let timings = match (keyword_results.timings, vector_results.timings) {
+            (Some(mut timings), Some(vector_timings)) => {
+                timings.merge(vector_timings);
+                Some(timings)
+            }
+            (timings, vector_timings) => timings.or(vector_timings),
+        };
+
         let mut documents_ids = Vec::with_capacity(
             vector_results.document_scores.len() + keyword_results.document_scores.len(),
         );
@@ -145,6 +166,12 @@ impl ScoreWithRatioResult {
                 degraded: vector_results.degraded | keyword_results.degraded,
                 used_negative_operator: vector_results.used_negative_operator
                     | keyword_results.used_negative_operator,
+                query_truncated: keyword_results.query_truncated,
+                // only the keyword search knows about the terms of the query
+                query_term_locations: keyword_results.query_term_locations,
+                query_interpretation: keyword_results.query_interpretation,
+                relaxed_phrases: keyword_results.relaxed_phrases,
+                timings,
             },
             semantic_hit_count,
         )
@@ -163,8 +190,11 @@ impl<'a> Search<'a> {
             limit: self.limit + self.offset,
             sort_criteria: self.sort_criteria.clone(),
             distinct: self.distinct.clone(),
+            diversify: self.diversify.clone(),
             searchable_attributes: self.searchable_attributes,
-            geo_strategy: self.geo_strategy,
+            languages: self.languages.clone(),
+            synonyms: self.synonyms,
+            geo_param: self.geo_param,
             terms_matching_strategy: self.terms_matching_strategy,
             scoring_strategy: ScoringStrategy::Detailed,
             words_limit: self.words_limit,
@@ -174,6 +204,12 @@ impl<'a> Search<'a> {
             semantic: self.semantic.clone(),
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
+            query_term_locations: self.query_term_locations,
+            query_interpretation: self.query_interpretation,
+            phrase_fallback: self.phrase_fallback,
+            regex_terms: self.regex_terms,
+            timings: self.timings,
+            term_typos: self.term_typos,
         };
 
         let semantic = search.semantic.take();
@@ -256,6 +292,11 @@ fn return_keyword_results(
         mut document_scores,
         degraded,
         used_negative_operator,
+        query_truncated,
+        query_term_locations,
+        query_interpretation,
+        relaxed_phrases,
+        timings,
     }: SearchResult,
 ) -> (SearchResult, Option<u32>) {
     let (documents_ids, document_scores) = if offset >= documents_ids.len() ||
@@ -282,6 +323,11 @@ fn return_keyword_results(
             document_scores,
             degraded,
             used_negative_operator,
+            query_truncated,
+            query_term_locations,
+            query_interpretation,
+            relaxed_phrases,
+            timings,
         },
         Some(0),
     )
@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
 use std::fmt;
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::Instant;
 
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
@@ -7,12 +10,18 @@ use roaring::bitmap::RoaringBitmap;
 
 pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
-use self::new::{execute_vector_search, PartialSearchResult};
+use self::new::{
+    execute_vector_search, interpret_query, resolve_query_term_locations, PartialSearchResult,
+};
+use crate::language_preset::LanguagePreset;
+use crate::query_limits::DEFAULT_MAX_QUERY_TERMS;
+use crate::query_rewrites::rewrite_query;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::vector::Embedder;
 use crate::{
     execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, Error, Index,
-    Result, SearchContext, TimeBudget, UserError,
+    QueryInterpretation, QueryTermKind, QueryTermLocation, Result, SearchContext, SearchTimings,
+    TimeBudget, UserError,
 };
 
 // Building these factories is not free.
@@ -41,8 +50,11 @@ pub struct Search<'a> {
     limit: usize,
     sort_criteria: Option<Vec<AscDesc>>,
     distinct: Option<String>,
+    diversify: Option<(String, usize)>,
     searchable_attributes: Option<&'a [String]>,
-    geo_strategy: new::GeoSortStrategy,
+    languages: Option<Vec<LanguagePreset>>,
+    synonyms: Option<&'a BTreeMap<String, Vec<String>>>,
+    geo_param: new::GeoSortParameter,
     terms_matching_strategy: TermsMatchingStrategy,
     scoring_strategy: ScoringStrategy,
     words_limit: usize,
@@ -52,6 +64,12 @@ pub struct Search<'a> {
     semantic: Option<SemanticSearch>,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
+    query_term_locations: bool,
+    query_interpretation: bool,
+    phrase_fallback: bool,
+    regex_terms: bool,
+    timings: bool,
+    term_typos: bool,
 }
 
 impl<'a> Search<'a> {
@@ -63,17 +81,26 @@ impl<'a> Search<'a> {
             limit: 20,
             sort_criteria: None,
             distinct: None,
+            diversify: None,
             searchable_attributes: None,
-            geo_strategy: new::GeoSortStrategy::default(),
+            languages: None,
+            synonyms: None,
+            geo_param: new::GeoSortParameter::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
             scoring_strategy: Default::default(),
             exhaustive_number_hits: false,
-            words_limit: 10,
+            words_limit: DEFAULT_MAX_QUERY_TERMS,
             rtxn,
             index,
             semantic: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
+            query_term_locations: false,
+            query_interpretation: false,
+            phrase_fallback: false,
+            regex_terms: false,
+            timings: false,
+            term_typos: false,
         }
     }
 
@@ -112,11 +139,30 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Limits the number of results sharing the same value in the given filterable attribute.
+    pub fn diversify(&mut self, attribute: String, max_per_value: usize) -> &mut Search<'a> {
+        self.diversify = Some((attribute, max_per_value));
+        self
+    }
+
     pub fn searchable_attributes(&mut self, searchable: &'a [String]) -> &mut Search<'a> {
         self.searchable_attributes = Some(searchable);
         self
     }
 
+    /// Restricts the search to the fields written in one of these languages and to the fields
+    /// without any language.
+    pub fn languages(&mut self, languages: Vec<LanguagePreset>) -> &mut Search<'a> {
+        self.languages = Some(languages);
+        self
+    }
+
+    /// Synonyms added to the ones of the index for this search only.
+    pub fn synonyms(&mut self, synonyms: &'a BTreeMap<String, Vec<String>>) -> &mut Search<'a> {
+        self.synonyms = Some(synonyms);
+        self
+    }
+
     pub fn terms_matching_strategy(&mut self, value: TermsMatchingStrategy) -> &mut Search<'a> {
         self.terms_matching_strategy = value;
         self
@@ -139,7 +185,21 @@ impl<'a> Search<'a> {
 
     #[cfg(test)]
     pub fn geo_sort_strategy(&mut self, strategy: new::GeoSortStrategy) -> &mut Search<'a> {
-        self.geo_strategy = strategy;
+        self.geo_param.strategy = strategy;
+        self
+    }
+
+    /// The documents farther than this distance, in meters, from the `_geoPoint` of the sort
+    /// are not sorted by distance, they are returned after the other ones.
+    pub fn geo_max_distance(&mut self, max_distance: Option<f64>) -> &mut Search<'a> {
+        self.geo_param.max_distance = max_distance;
+        self
+    }
+
+    /// The documents whose distances to the `_geoPoint` of the sort are in the same range
+    /// of this width, in meters, are sorted by the next ranking rules.
+    pub fn geo_bucket_width(&mut self, bucket_width: Option<f64>) -> &mut Search<'a> {
+        self.geo_param.bucket_width = bucket_width;
         self
     }
 
@@ -160,6 +220,47 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Returns the locations of the terms of the query along with the results,
+    /// this requires checking whether the returned documents contain each term.
+    pub fn query_term_locations(&mut self, query_term_locations: bool) -> &mut Search<'a> {
+        self.query_term_locations = query_term_locations;
+        self
+    }
+
+    /// Returns a description of how the query was interpreted along with the results,
+    /// this requires checking whether the returned documents contain each term.
+    pub fn query_interpretation(&mut self, query_interpretation: bool) -> &mut Search<'a> {
+        self.query_interpretation = query_interpretation;
+        self
+    }
+
+    /// When the phrases of the query don't match any document, retries the query with
+    /// the words of its phrases unquoted, all required and allowed a single typo.
+    pub fn phrase_fallback(&mut self, phrase_fallback: bool) -> &mut Search<'a> {
+        self.phrase_fallback = phrase_fallback;
+        self
+    }
+
+    /// Interprets the quoted parts of the query as regular expressions matched against
+    /// the whole words of the index, instead of phrases.
+    pub fn regex_terms(&mut self, regex_terms: bool) -> &mut Search<'a> {
+        self.regex_terms = regex_terms;
+        self
+    }
+
+    /// Measures the time spent in each stage of the search and returns it along with the results.
+    pub fn timings(&mut self, timings: bool) -> &mut Search<'a> {
+        self.timings = timings;
+        self
+    }
+
+    /// Records in the typo score details of the documents the typos needed by each matched
+    /// term of the query. Only useful with the detailed scoring strategy.
+    pub fn term_typos(&mut self, term_typos: bool) -> &mut Search<'a> {
+        self.term_typos = term_typos;
+        self
+    }
+
     pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn)?;
@@ -170,12 +271,47 @@ impl<'a> Search<'a> {
     }
 
     pub fn execute(&self) -> Result<SearchResult> {
+        self.execute_query(self.query.as_deref(), false)
+    }
+
+    /// Executes the search with the given query, relaxing its phrases if asked to.
+    fn execute_query(&self, query: Option<&str>, relaxed_phrases: bool) -> Result<SearchResult> {
         let mut ctx = SearchContext::new(self.index, self.rtxn)?;
+        let terms_matching_strategy = if relaxed_phrases {
+            ctx.max_typos = Some(1);
+            TermsMatchingStrategy::All
+        } else {
+            self.terms_matching_strategy
+        };
+        ctx.regex_terms = self.regex_terms;
+        if self.timings {
+            ctx.timings = Some(SearchTimings::default());
+        }
+        if self.term_typos {
+            ctx.term_typos = Some(Vec::new());
+        }
+
+        // the relaxed queries were already rewritten
+        let rewrites = match query {
+            Some(query) if !relaxed_phrases => {
+                rewrite_query(query, &ctx.index.query_rewrites(ctx.txn)?)?
+            }
+            _ => None,
+        };
+        let query = rewrites.as_ref().map(|(rewritten, _)| rewritten.as_str()).or(query);
 
         if let Some(searchable_attributes) = self.searchable_attributes {
             ctx.attributes_to_search_on(searchable_attributes)?;
         }
 
+        if let Some(languages) = &self.languages {
+            ctx.restrict_to_languages(languages)?;
+        }
+
+        if let Some(synonyms) = self.synonyms {
+            ctx.add_query_synonyms(synonyms)?;
+        }
+
         if let Some(distinct) = &self.distinct {
             let filterable_fields = ctx.index.filterable_fields(ctx.txn)?;
             if !crate::is_faceted(distinct, &filterable_fields) {
@@ -189,14 +325,35 @@ impl<'a> Search<'a> {
             }
         }
 
+        if let Some((attribute, max_per_value)) = &self.diversify {
+            let filterable_fields = ctx.index.filterable_fields(ctx.txn)?;
+            if !crate::is_faceted(attribute, &filterable_fields) {
+                let (valid_fields, hidden_fields) =
+                    ctx.index.remove_hidden_fields(ctx.txn, filterable_fields)?;
+                return Err(Error::UserError(UserError::InvalidDiversifyAttribute {
+                    field: attribute.clone(),
+                    valid_fields,
+                    hidden_fields,
+                }));
+            }
+            ctx.diversify(attribute, *max_per_value)?;
+        }
+
+        let before_filter = Instant::now();
         let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        if let Some(timings) = ctx.timings.as_mut() {
+            timings.filter = before_filter.elapsed();
+        }
         let PartialSearchResult {
             located_query_terms,
+            query_term_locations,
+            removed_stop_words,
             candidates,
             documents_ids,
-            document_scores,
+            mut document_scores,
             degraded,
             used_negative_operator,
+            query_truncated,
         } = match self.semantic.as_ref() {
             Some(SemanticSearch { vector: Some(vector), embedder_name, embedder }) => {
                 execute_vector_search(
@@ -206,7 +363,7 @@ impl<'a> Search<'a> {
                     universe,
                     &self.sort_criteria,
                     &self.distinct,
-                    self.geo_strategy,
+                    self.geo_param,
                     self.offset,
                     self.limit,
                     embedder_name,
@@ -217,14 +374,14 @@ impl<'a> Search<'a> {
             }
             _ => execute_search(
                 &mut ctx,
-                self.query.as_deref(),
-                self.terms_matching_strategy,
+                query,
+                terms_matching_strategy,
                 self.scoring_strategy,
                 self.exhaustive_number_hits,
                 universe,
                 &self.sort_criteria,
                 &self.distinct,
-                self.geo_strategy,
+                self.geo_param,
                 self.offset,
                 self.limit,
                 Some(self.words_limit),
@@ -235,6 +392,55 @@ impl<'a> Search<'a> {
             )?,
         };
 
+        if self.phrase_fallback && !relaxed_phrases && candidates.is_empty() {
+            let phrases = query_term_locations
+                .iter()
+                .filter(|(location, _)| location.kind == QueryTermKind::Phrase)
+                .map(|(location, _)| &location.byte_range);
+            let quotes = ctx.index.quote_characters(ctx.txn)?;
+            let quotes: Vec<char> = quotes.iter().flat_map(|quote| quote.chars()).collect();
+            if let Some(unquoted) = query.and_then(|query| unquote_phrases(query, phrases, &quotes))
+            {
+                return self.execute_query(Some(&unquoted), true);
+            }
+        }
+
+        if let Some(term_typos) = ctx.term_typos.take() {
+            for (docid, scores) in documents_ids.iter().zip(&mut document_scores) {
+                let Some((_, terms)) =
+                    term_typos.iter().find(|(docids, _)| docids.contains(*docid))
+                else {
+                    continue;
+                };
+                for score in scores {
+                    if let ScoreDetails::Typo(typo) = score {
+                        typo.terms = terms.clone();
+                    }
+                }
+            }
+        }
+
+        let documents = RoaringBitmap::from_iter(documents_ids.iter().copied());
+        let query_interpretation = if self.query_interpretation {
+            let mut interpretation =
+                interpret_query(&mut ctx, &query_term_locations, removed_stop_words, &documents)?;
+            if let Some((rewritten_query, rewrites)) = rewrites {
+                interpretation.rewritten_query = Some(rewritten_query);
+                interpretation.rewrites = rewrites;
+            }
+            Some(interpretation)
+        } else {
+            None
+        };
+
+        let query_term_locations = if self.query_term_locations {
+            resolve_query_term_locations(&mut ctx, query_term_locations, &documents)?
+        } else {
+            Vec::new()
+        };
+
+        let timings = ctx.timings.take();
+
         // consume context and located_query_terms to build MatchingWords.
         let matching_words = match located_query_terms {
             Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
@@ -248,10 +454,59 @@ impl<'a> Search<'a> {
             documents_ids,
             degraded,
             used_negative_operator,
+            query_truncated,
+            query_term_locations,
+            query_interpretation,
+            relaxed_phrases,
+            timings,
         })
     }
 }
 
+/// Returns the query with the quotes delimiting its phrases, and the `*` or `~N` following them,
+/// replaced by spaces, or `None` if the query doesn't contain any phrase.
+///
+/// Every removed byte is replaced by a space, so that the locations of the terms of the unquoted
+/// query are also valid in the original one.
+fn unquote_phrases<'q>(
+    query: &str,
+    phrases: impl Iterator<Item = &'q Range<usize>>,
+    quotes: &[char],
+) -> Option<String> {
+    let mut phrases = phrases.peekable();
+    phrases.peek()?;
+
+    let mut removed = Vec::new();
+    for phrase in phrases {
+        let before = &query[..phrase.start];
+        if let Some(quote) = before.chars().next_back().filter(|c| quotes.contains(c)) {
+            removed.push(phrase.start - quote.len_utf8()..phrase.start);
+        }
+        let after = &query[phrase.end..];
+        if let Some(quote) = after.chars().next().filter(|c| quotes.contains(c)) {
+            let rest = &after[quote.len_utf8()..];
+            let suffix = if rest.starts_with('*') {
+                1
+            } else if let Some(digits) = rest.strip_prefix('~') {
+                1 + digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len())
+            } else {
+                0
+            };
+            removed.push(phrase.end..phrase.end + quote.len_utf8() + suffix);
+        }
+    }
+
+    let mut unquoted = String::with_capacity(query.len());
+    let mut last = 0;
+    for range in removed {
+        unquoted.push_str(&query[last..range.start]);
+        unquoted.extend(std::iter::repeat(' ').take(range.len()));
+        last = range.end;
+    }
+    unquoted.push_str(&query[last..]);
+    Some(unquoted)
+}
+
 impl fmt::Debug for Search<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Search {
@@ -261,8 +516,11 @@ impl fmt::Debug for Search<'_> {
             limit,
             sort_criteria,
             distinct,
+            diversify,
             searchable_attributes,
-            geo_strategy: _,
+            languages,
+            synonyms,
+            geo_param: _,
             terms_matching_strategy,
             scoring_strategy,
             words_limit,
@@ -272,6 +530,12 @@ impl fmt::Debug for Search<'_> {
             semantic,
             time_budget,
             ranking_score_threshold,
+            query_term_locations,
+            query_interpretation,
+            phrase_fallback,
+            regex_terms,
+            timings,
+            term_typos,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -281,7 +545,10 @@ impl fmt::Debug for Search<'_> {
             .field("limit", limit)
             .field("sort_criteria", sort_criteria)
             .field("distinct", distinct)
+            .field("diversify", diversify)
             .field("searchable_attributes", searchable_attributes)
+            .field("languages", languages)
+            .field("synonyms", synonyms)
             .field("terms_matching_strategy", terms_matching_strategy)
             .field("scoring_strategy", scoring_strategy)
             .field("exhaustive_number_hits", exhaustive_number_hits)
@@ -292,11 +559,17 @@ impl fmt::Debug for Search<'_> {
             )
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
+            .field("query_term_locations", query_term_locations)
+            .field("query_interpretation", query_interpretation)
+            .field("phrase_fallback", phrase_fallback)
+            .field("regex_terms", regex_terms)
+            .field("timings", timings)
+            .field("term_typos", term_typos)
             .finish()
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct SearchResult {
     pub matching_words: MatchingWords,
     pub candidates: RoaringBitmap,
@@ -304,6 +577,14 @@ pub struct SearchResult {
     pub document_scores: Vec<Vec<ScoreDetails>>,
     pub degraded: bool,
     pub used_negative_operator: bool,
+    /// Whether some parts of the query were ignored because of the query limits of the index.
+    pub query_truncated: bool,
+    pub query_term_locations: Vec<QueryTermLocation>,
+    pub query_interpretation: Option<QueryInterpretation>,
+    /// Whether the phrases of the query were relaxed because they didn't match any document.
+    pub relaxed_phrases: bool,
+    /// The time spent in each stage of the search, if it was measured.
+    pub timings: Option<SearchTimings>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -395,4 +676,16 @@ mod test {
 
         assert_eq!(documents_ids, vec![1]);
     }
+
+    #[test]
+    fn unquote_phrases() {
+        let unquote = |query: &str, phrases: &[Range<usize>]| {
+            super::unquote_phrases(query, phrases.iter(), &['"', '«', '»'])
+        };
+        assert_eq!(unquote("hello world", &[]), None);
+        assert_eq!(unquote(r#"a "b c" d"#, &[3..6]).unwrap(), "a  b c  d");
+        assert_eq!(unquote(r#""b c"~2 d "e"*"#, &[1..4, 11..12]).unwrap(), " b c    d  e  ");
+        // the multi-bytes quotes are replaced by as many spaces
+        assert_eq!(unquote("«b c»", &[2..5]).unwrap(), "  b c  ");
+    }
 }
@@ -0,0 +1,172 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use heed::types::Bytes;
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::query_graph::{QueryGraph, QueryNodeData};
+use super::query_term::ExactTerm;
+use super::{RankingRule, RankingRuleOutput, SearchContext};
+use crate::heed_codec::StrBEU16Codec;
+use crate::score_details::{Rank, ScoreDetails};
+use crate::{CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, DocumentId, Result};
+
+/// The number of buckets the scores of the documents are rounded to.
+///
+/// The documents of similar scores end up in the same bucket, so that the next ranking rules
+/// can still sort them.
+const BM25_MAX_RANK: u32 = 100;
+/// How fast the score of a term saturates as it appears more often in a document.
+const K1: f64 = 1.2;
+/// How much the score of the terms is lowered in the documents longer than the average.
+const B: f64 = 0.75;
+
+/// Ranks the documents by their Okapi BM25 score, which grows with the number of times the
+/// words of the query appear in a document, weighted by the rarity of these words in the index,
+/// and lowered for the longer documents.
+///
+/// The term frequencies and the lengths of the documents are stored at indexing time, only while
+/// the `relevancy:bm25` ranking rule is in the ranking rules of the index. The words are only
+/// counted with their exact spelling, the documents matching the query with typos or prefixes
+/// only are ranked last.
+pub struct Bm25 {
+    original_query: Option<QueryGraph>,
+    buckets: VecDeque<(u32, RoaringBitmap)>,
+}
+
+impl Bm25 {
+    pub fn new() -> Self {
+        Self { original_query: None, buckets: VecDeque::new() }
+    }
+
+    fn score(rank: u32) -> ScoreDetails {
+        ScoreDetails::Bm25(Rank { rank, max_rank: BM25_MAX_RANK })
+    }
+}
+
+/// Returns the words the terms of the query match without typos.
+fn exact_words(ctx: &SearchContext<'_>, query: &QueryGraph) -> BTreeSet<String> {
+    let mut words = BTreeSet::new();
+    for (_, node) in query.nodes.iter() {
+        let QueryNodeData::Term(term) = &node.data else { continue };
+        match term.term_subset.exact_term(ctx) {
+            Some(ExactTerm::Word(word)) => {
+                words.insert(ctx.word_interner.get(word).clone());
+            }
+            Some(ExactTerm::Phrase(phrase)) => {
+                let phrase = ctx.phrase_interner.get(phrase);
+                words.extend(
+                    phrase.words.iter().flatten().map(|w| ctx.word_interner.get(*w)).cloned(),
+                );
+            }
+            None => (),
+        }
+    }
+    words
+}
+
+impl<'ctx> RankingRule<'ctx, QueryGraph> for Bm25 {
+    fn id(&self) -> String {
+        "relevancy:bm25".to_string()
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::bm25")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<QueryGraph>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &QueryGraph,
+    ) -> Result<()> {
+        let index = ctx.index;
+        let txn = ctx.txn;
+
+        // the lengths of the candidates and the average length of the documents
+        let mut lengths = HashMap::<DocumentId, u32>::new();
+        let mut total_length = 0.0;
+        let mut total_documents = 0.0;
+        for result in index.document_length_docids.iter(txn)? {
+            let (length, docids) = result?;
+            total_length += length as f64 * docids.len() as f64;
+            total_documents += docids.len() as f64;
+            for docid in docids & parent_candidates {
+                lengths.insert(docid, length);
+            }
+        }
+
+        let mut scores = HashMap::<DocumentId, f64>::new();
+        let mut max_score = 0.0;
+        if total_documents > 0.0 {
+            let average_length = total_length / total_documents;
+            let word_docids_len = index.word_docids.remap_data_type::<CboRoaringBitmapLenCodec>();
+            let word_frequency_docids =
+                index.word_frequency_docids.remap_types::<Bytes, CboRoaringBitmapCodec>();
+
+            for word in exact_words(ctx, parent_query) {
+                let document_frequency = word_docids_len.get(txn, &word)?.unwrap_or(0) as f64;
+                if document_frequency == 0.0 {
+                    continue;
+                }
+                let idf = (1.0
+                    + (total_documents - document_frequency + 0.5) / (document_frequency + 0.5))
+                    .ln();
+                max_score += idf * (K1 + 1.0);
+
+                let mut prefix = word.into_bytes();
+                prefix.push(0);
+                for result in word_frequency_docids.prefix_iter(txn, &prefix)? {
+                    let (key, docids) = result?;
+                    let (_, frequency) =
+                        StrBEU16Codec::bytes_decode(key).map_err(heed::Error::Decoding)?;
+                    let frequency = frequency as f64;
+                    for docid in docids & parent_candidates {
+                        let length = lengths.get(&docid).copied().unwrap_or_default() as f64;
+                        let norm = K1 * (1.0 - B + B * length / average_length);
+                        *scores.entry(docid).or_default() +=
+                            idf * frequency * (K1 + 1.0) / (frequency + norm);
+                    }
+                }
+            }
+        }
+
+        let mut buckets = BTreeMap::<u32, RoaringBitmap>::new();
+        for (docid, score) in scores {
+            // a rank of 0 is reserved to the documents without any score
+            let rank =
+                ((score / max_score * BM25_MAX_RANK as f64).round() as u32).clamp(1, BM25_MAX_RANK);
+            buckets.entry(rank).or_default().insert(docid);
+        }
+        self.buckets = buckets.into_iter().rev().collect();
+
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::bm25")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<QueryGraph>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<QueryGraph>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        while let Some((rank, bucket)) = self.buckets.pop_front() {
+            let candidates = bucket & universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput { query, candidates, score: Self::score(rank) }));
+            }
+        }
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score: Self::score(0) }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::bm25")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<QueryGraph>,
+    ) {
+        self.original_query = None;
+        self.buckets.clear();
+    }
+}
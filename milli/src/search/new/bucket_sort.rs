@@ -1,10 +1,14 @@
+use std::time::Instant;
+
 use roaring::RoaringBitmap;
 
 use super::logger::SearchLogger;
 use super::ranking_rules::{BoxRankingRule, RankingRuleQueryTrait};
 use super::SearchContext;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
-use crate::search::new::distinct::{apply_distinct_rule, distinct_single_docid, DistinctOutput};
+use crate::search::new::distinct::{
+    apply_distinct_rule, distinct_single_docid, DistinctOutput, Diversification,
+};
 use crate::{Result, TimeBudget};
 
 pub struct BucketSortOutput {
@@ -46,6 +50,9 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         None
     };
 
+    let mut diversification =
+        ctx.diversify.map(|(fid, max_per_value)| Diversification::new(fid, max_per_value));
+
     if universe.len() < from as u64 {
         return Ok(BucketSortOutput {
             docids: vec![],
@@ -55,7 +62,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         });
     }
     if ranking_rules.is_empty() {
-        if let Some(distinct_fid) = distinct_fid {
+        if distinct_fid.is_some() || diversification.is_some() {
             let mut excluded = RoaringBitmap::new();
             let mut results = vec![];
             for docid in universe.iter() {
@@ -66,7 +73,17 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                     continue;
                 }
 
-                distinct_single_docid(ctx.index, ctx.txn, distinct_fid, docid, &mut excluded)?;
+                if let Some(distinct_fid) = distinct_fid {
+                    distinct_single_docid(ctx.index, ctx.txn, distinct_fid, docid, &mut excluded)?;
+                }
+                if let Some(diversification) = diversification.as_mut() {
+                    diversification.diversify_single_docid(
+                        ctx.index,
+                        ctx.txn,
+                        docid,
+                        &mut excluded,
+                    )?;
+                }
                 results.push(docid);
             }
 
@@ -100,9 +117,27 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
 
     let ranking_rules_len = ranking_rules.len();
 
+    if let Some(timings) = ctx.timings.as_mut() {
+        timings.ranking_rules =
+            ranking_rules.iter().map(|rule| (rule.id(), Default::default())).collect();
+    }
+
+    /// Runs the given method of a ranking rule, adding its duration
+    /// to the timings of the rule if they are measured.
+    macro_rules! timed {
+        ($index:expr, $call:expr) => {{
+            let before = ctx.timings.is_some().then(Instant::now);
+            let output = $call;
+            if let (Some(timings), Some(before)) = (ctx.timings.as_mut(), before) {
+                timings.ranking_rules[$index].1 += before.elapsed();
+            }
+            output
+        }};
+    }
+
     logger.start_iteration_ranking_rule(0, ranking_rules[0].as_ref(), query, universe);
 
-    ranking_rules[0].start_iteration(ctx, logger, universe, query)?;
+    timed!(0, ranking_rules[0].start_iteration(ctx, logger, universe, query))?;
 
     let mut ranking_rule_scores: Vec<ScoreDetails> = vec![];
 
@@ -128,7 +163,10 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 &ranking_rule_universes[cur_ranking_rule_index],
             );
             ranking_rule_universes[cur_ranking_rule_index].clear();
-            ranking_rules[cur_ranking_rule_index].end_iteration(ctx, logger);
+            timed!(
+                cur_ranking_rule_index,
+                ranking_rules[cur_ranking_rule_index].end_iteration(ctx, logger)
+            );
             if cur_ranking_rule_index == 0 {
                 break;
             } else {
@@ -160,6 +198,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 cur_ranking_rule_index,
                 &mut cur_offset,
                 distinct_fid,
+                &mut diversification,
                 &ranking_rule_scores,
                 $candidates,
             )?;
@@ -213,10 +252,13 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             continue;
         }
 
-        let Some(next_bucket) = ranking_rules[cur_ranking_rule_index].next_bucket(
-            ctx,
-            logger,
-            &ranking_rule_universes[cur_ranking_rule_index],
+        let Some(next_bucket) = timed!(
+            cur_ranking_rule_index,
+            ranking_rules[cur_ranking_rule_index].next_bucket(
+                ctx,
+                logger,
+                &ranking_rule_universes[cur_ranking_rule_index],
+            )
         )?
         else {
             back!();
@@ -266,11 +308,14 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             &next_bucket.query,
             &ranking_rule_universes[cur_ranking_rule_index],
         );
-        ranking_rules[cur_ranking_rule_index].start_iteration(
-            ctx,
-            logger,
-            &next_bucket.candidates,
-            &next_bucket.query,
+        timed!(
+            cur_ranking_rule_index,
+            ranking_rules[cur_ranking_rule_index].start_iteration(
+                ctx,
+                logger,
+                &next_bucket.candidates,
+                &next_bucket.query,
+            )
         )?;
     }
 
@@ -282,8 +327,8 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     })
 }
 
-/// Add the candidates to the results. Take `distinct`, `from`, `length`, and `cur_offset`
-/// into account and inform the logger.
+/// Add the candidates to the results. Take `distinct`, the diversification, `from`, `length`,
+/// and `cur_offset` into account and inform the logger.
 #[allow(clippy::too_many_arguments)]
 fn maybe_add_to_results<'ctx, Q: RankingRuleQueryTrait>(
     ctx: &mut SearchContext<'ctx>,
@@ -303,6 +348,7 @@ fn maybe_add_to_results<'ctx, Q: RankingRuleQueryTrait>(
     cur_offset: &mut usize,
 
     distinct_fid: Option<u16>,
+    diversification: &mut Option<Diversification>,
     ranking_rule_scores: &[ScoreDetails],
     candidates: RoaringBitmap,
 ) -> Result<()> {
@@ -318,6 +364,17 @@ fn maybe_add_to_results<'ctx, Q: RankingRuleQueryTrait>(
     } else {
         candidates.clone()
     };
+    // Then limit the number of candidates sharing the same value, reducing the universes too
+    let candidates = if let Some(diversification) = diversification {
+        let DistinctOutput { remaining, excluded } = diversification.apply(ctx, &candidates)?;
+        for universe in ranking_rule_universes.iter_mut() {
+            *universe -= &excluded;
+        }
+        *all_candidates -= &excluded;
+        remaining
+    } else {
+        candidates
+    };
     *all_candidates |= &candidates;
 
     // if the candidates are empty, there is nothing to do;
@@ -0,0 +1,209 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use roaring::RoaringBitmap;
+use serde_json::Value;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::computed_fields::ComputedFields;
+use crate::error::InternalError;
+use crate::score_details::{self, ScoreDetails};
+use crate::{DocumentId, FieldId, Object, Result};
+
+/// The maximum number of candidates on which the expression is evaluated during an iteration,
+/// the other candidates are returned unsorted after the sorted ones.
+const MAX_CANDIDATES_TO_EVALUATE: u64 = 10_000;
+
+/// Sorts the documents by the value of a field computed at search time.
+///
+/// Unlike the sort on a regular field, no facet database can be used: the expression of
+/// the field is evaluated on the candidates of the universe when a bucket is asked for, only
+/// decoding the fields it reads, and at most once per candidate. Like the facet sort,
+/// the numbers come before the strings, and the documents without any value come last.
+pub struct ComputedSort<Query> {
+    field_name: String,
+    computed_fields: Rc<ComputedFields>,
+    is_ascending: bool,
+    original_query: Option<Query>,
+    /// The fields of the documents read by the expression.
+    variables: Vec<(FieldId, String)>,
+    /// The candidates on which the expression was evaluated.
+    evaluated: RoaringBitmap,
+    /// The values of the evaluated candidates, in the order of the sort.
+    values: VecDeque<(SortValue, DocumentId)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SortValue {
+    Number(f64),
+    String(String),
+}
+
+impl SortValue {
+    fn into_json(self) -> Value {
+        match self {
+            SortValue::Number(number) => {
+                serde_json::Number::from_f64(number).map_or(Value::Null, Value::Number)
+            }
+            SortValue::String(string) => Value::String(string),
+        }
+    }
+}
+
+impl<Query> ComputedSort<Query> {
+    pub fn new(
+        computed_fields: Rc<ComputedFields>,
+        field_name: String,
+        is_ascending: bool,
+    ) -> Self {
+        Self {
+            field_name,
+            computed_fields,
+            is_ascending,
+            original_query: None,
+            variables: Vec::new(),
+            evaluated: RoaringBitmap::new(),
+            values: VecDeque::new(),
+        }
+    }
+
+    fn score(&self, value: Value) -> ScoreDetails {
+        ScoreDetails::Sort(score_details::Sort {
+            field_name: self.field_name.clone(),
+            ascending: self.is_ascending,
+            redacted: false,
+            value,
+        })
+    }
+
+    /// Compares two values in the order of the sort, the numbers coming before the strings.
+    fn compare(&self, left: &SortValue, right: &SortValue) -> Ordering {
+        let ordering = match (left, right) {
+            (SortValue::Number(left), SortValue::Number(right)) => left.total_cmp(right),
+            (SortValue::String(left), SortValue::String(right)) => left.cmp(right),
+            (SortValue::Number(_), SortValue::String(_)) => return Ordering::Less,
+            (SortValue::String(_), SortValue::Number(_)) => return Ordering::Greater,
+        };
+        if self.is_ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    }
+
+    /// Evaluates the expression on the candidates of the universe it wasn't evaluated on yet,
+    /// up to the maximum number of candidates to evaluate.
+    fn evaluate(&mut self, ctx: &mut SearchContext<'_>, universe: &RoaringBitmap) -> Result<()> {
+        let remaining = MAX_CANDIDATES_TO_EVALUATE.saturating_sub(self.evaluated.len());
+        let candidates: RoaringBitmap =
+            (universe - &self.evaluated).into_iter().take(remaining as usize).collect();
+        if candidates.is_empty() {
+            return Ok(());
+        }
+
+        for result in ctx.index.iter_documents(ctx.txn, candidates.iter())? {
+            let (docid, obkv) = result?;
+            let mut document = Object::new();
+            for (fid, name) in &self.variables {
+                if let Some(value) = obkv.get(*fid) {
+                    let value = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                    document.insert(name.clone(), value);
+                }
+            }
+            let value = match self.computed_fields.compute(&self.field_name, &document) {
+                Some(Value::Number(number)) => number.as_f64().map(SortValue::Number),
+                Some(Value::String(string)) => Some(SortValue::String(string)),
+                _ => None,
+            };
+            if let Some(value) = value {
+                self.values.push_back((value, docid));
+            }
+        }
+        self.evaluated |= candidates;
+
+        let mut values = std::mem::take(&mut self.values);
+        values.make_contiguous().sort_by(|(left, _), (right, _)| self.compare(left, right));
+        self.values = values;
+        Ok(())
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for ComputedSort<Query> {
+    fn id(&self) -> String {
+        let Self { field_name, is_ascending, .. } = self;
+        format!("{field_name}:{}", if *is_ascending { "asc" } else { "desc" })
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::sort")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        _parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+        self.variables = self
+            .computed_fields
+            .variables(&self.field_name)
+            .filter_map(|name| fields_ids_map.id(name).map(|fid| (fid, name.to_string())))
+            .collect();
+        self.evaluated.clear();
+        self.values.clear();
+
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::sort")]
+    fn next_bucket(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        self.evaluate(ctx, universe)?;
+
+        // the universe only shrinks during an iteration, the values outside of it are dropped
+        while let Some((value, docid)) = self.values.pop_front() {
+            if !universe.contains(docid) {
+                continue;
+            }
+            let mut candidates = RoaringBitmap::from_iter([docid]);
+            while let Some((next_value, next_docid)) = self.values.front() {
+                if *next_value != value {
+                    break;
+                }
+                if universe.contains(*next_docid) {
+                    candidates.insert(*next_docid);
+                }
+                self.values.pop_front();
+            }
+            return Ok(Some(RankingRuleOutput {
+                query,
+                candidates,
+                score: self.score(value.into_json()),
+            }));
+        }
+        Ok(Some(RankingRuleOutput {
+            query,
+            candidates: universe.clone(),
+            score: self.score(Value::Null),
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::sort")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.variables.clear();
+        self.evaluated.clear();
+        self.values.clear();
+    }
+}
@@ -12,9 +12,7 @@ use super::Word;
 use crate::heed_codec::{BytesDecodeOwned, StrBEU16Codec};
 use crate::proximity::ProximityPrecision;
 use crate::update::{merge_cbo_roaring_bitmaps, MergeFn};
-use crate::{
-    CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, Result, SearchContext, U8StrStrCodec,
-};
+use crate::{CboRoaringBitmapCodec, CboRoaringBitmapView, Result, SearchContext, U8StrStrCodec};
 
 /// A cache storing pointers to values in the LMDB databases.
 ///
@@ -46,14 +44,13 @@ pub struct DatabaseCache<'ctx> {
     pub word_prefix_fids: FxHashMap<Interned<String>, Vec<u16>>,
 }
 impl<'ctx> DatabaseCache<'ctx> {
-    fn get_value<'v, K1, KC>(
+    fn get_value_bytes<'c, 'v, K1, KC>(
         txn: &'ctx RoTxn<'_>,
         cache_key: K1,
         db_key: &'v KC::EItem,
-        cache: &mut FxHashMap<K1, Option<Cow<'ctx, [u8]>>>,
-        universe: Option<&RoaringBitmap>,
+        cache: &'c mut FxHashMap<K1, Option<Cow<'ctx, [u8]>>>,
         db: Database<KC, Bytes>,
-    ) -> Result<Option<RoaringBitmap>>
+    ) -> Result<Option<&'c [u8]>>
     where
         K1: Copy + Eq + Hash,
         KC: BytesEncode<'v>,
@@ -63,14 +60,32 @@ impl<'ctx> DatabaseCache<'ctx> {
             entry.insert(bitmap_ptr);
         }
 
-        let bitmap_bytes = match cache.get(&cache_key).unwrap() {
-            Some(Cow::Borrowed(bytes)) => bytes,
-            Some(Cow::Owned(bytes)) => bytes.as_slice(),
-            None => return Ok(None),
+        Ok(cache.get(&cache_key).unwrap().as_deref())
+    }
+
+    fn get_value<'v, K1, KC>(
+        txn: &'ctx RoTxn<'_>,
+        cache_key: K1,
+        db_key: &'v KC::EItem,
+        cache: &mut FxHashMap<K1, Option<Cow<'ctx, [u8]>>>,
+        universe: Option<&RoaringBitmap>,
+        db: Database<KC, Bytes>,
+    ) -> Result<Option<RoaringBitmap>>
+    where
+        K1: Copy + Eq + Hash,
+        KC: BytesEncode<'v>,
+    {
+        let Some(bitmap_bytes) = Self::get_value_bytes(txn, cache_key, db_key, cache, db)? else {
+            return Ok(None);
         };
 
         match (bitmap_bytes, universe) {
             (bytes, Some(universe)) => {
+                // Checking the containers is enough to skip most of the postings
+                // when the universe is very selective, without decoding them.
+                if CboRoaringBitmapCodec::is_disjoint_with_serialized(bytes, universe)? {
+                    return Ok(Some(RoaringBitmap::new()));
+                }
                 CboRoaringBitmapCodec::intersection_with_serialized(bytes, universe)
                     .map(Some)
                     .map_err(Into::into)
@@ -82,32 +97,51 @@ impl<'ctx> DatabaseCache<'ctx> {
         }
     }
 
+    /// Returns the number of documents of the value, restricted to the given universe,
+    /// without decoding the whole bitmap.
     fn get_value_length<'v, K1, KC>(
         txn: &'ctx RoTxn<'_>,
         cache_key: K1,
         db_key: &'v KC::EItem,
         cache: &mut FxHashMap<K1, Option<Cow<'ctx, [u8]>>>,
+        universe: Option<&RoaringBitmap>,
         db: Database<KC, Bytes>,
     ) -> Result<Option<u64>>
     where
         K1: Copy + Eq + Hash,
         KC: BytesEncode<'v>,
     {
-        if let Entry::Vacant(entry) = cache.entry(cache_key) {
-            let bitmap_ptr = db.get(txn, db_key)?.map(Cow::Borrowed);
-            entry.insert(bitmap_ptr);
-        }
-
-        let bitmap_bytes = match cache.get(&cache_key).unwrap() {
-            Some(Cow::Borrowed(bytes)) => bytes,
-            Some(Cow::Owned(bytes)) => bytes.as_slice(),
-            None => return Ok(None),
+        let Some(bitmap_bytes) = Self::get_value_bytes(txn, cache_key, db_key, cache, db)? else {
+            return Ok(None);
         };
 
-        CboRoaringBitmapLenCodec::bytes_decode_owned(bitmap_bytes)
-            .map(Some)
-            .map_err(heed::Error::Decoding)
-            .map_err(Into::into)
+        let view = CboRoaringBitmapView::new(bitmap_bytes)?;
+        match universe {
+            Some(universe) => Ok(Some(view.intersection_len(universe)?)),
+            None => Ok(Some(view.len())),
+        }
+    }
+
+    /// Returns `true` if the value doesn't have any document in common with the universe,
+    /// without decoding the whole bitmap.
+    fn get_value_is_disjoint<'v, K1, KC>(
+        txn: &'ctx RoTxn<'_>,
+        cache_key: K1,
+        db_key: &'v KC::EItem,
+        cache: &mut FxHashMap<K1, Option<Cow<'ctx, [u8]>>>,
+        universe: &RoaringBitmap,
+        db: Database<KC, Bytes>,
+    ) -> Result<bool>
+    where
+        K1: Copy + Eq + Hash,
+        KC: BytesEncode<'v>,
+    {
+        match Self::get_value_bytes(txn, cache_key, db_key, cache, db)? {
+            Some(bitmap_bytes) => {
+                Ok(CboRoaringBitmapView::new(bitmap_bytes)?.is_disjoint(universe)?)
+            }
+            None => Ok(true),
+        }
     }
 
     fn get_value_from_keys<'v, K1, KC, DC>(
@@ -195,6 +229,44 @@ impl<'ctx> SearchContext<'ctx> {
         }
     }
 
+    /// Returns `true` if none of the documents of the universe contain the word.
+    ///
+    /// Unlike [`Self::word_docids`], the postings of the word are not decoded.
+    pub fn word_docids_is_disjoint(
+        &mut self,
+        universe: &RoaringBitmap,
+        word: Word,
+    ) -> Result<bool> {
+        if self.restricted_fids.is_some() {
+            let docids = self.word_docids(Some(universe), word)?;
+            return Ok(docids.map_or(true, |docids| docids.is_disjoint(universe)));
+        }
+
+        let (word, exact) = match word {
+            Word::Original(word) => (word, true),
+            Word::Derived(word) => (word, false),
+        };
+        let tolerant_is_disjoint = DatabaseCache::get_value_is_disjoint(
+            self.txn,
+            word,
+            self.word_interner.get(word).as_str(),
+            &mut self.db_cache.word_docids,
+            universe,
+            self.index.word_docids.remap_data_type::<Bytes>(),
+        )?;
+        if !tolerant_is_disjoint || !exact {
+            return Ok(tolerant_is_disjoint);
+        }
+        DatabaseCache::get_value_is_disjoint(
+            self.txn,
+            word,
+            self.word_interner.get(word).as_str(),
+            &mut self.db_cache.exact_word_docids,
+            universe,
+            self.index.exact_word_docids.remap_data_type::<Bytes>(),
+        )
+    }
+
     /// Retrieve or insert the given value in the `word_docids` database.
     fn get_db_word_docids(
         &mut self,
@@ -430,6 +502,7 @@ impl<'ctx> SearchContext<'ctx> {
                     self.word_interner.get(word2).as_str(),
                 ),
                 &mut self.db_cache.word_pair_proximity_docids,
+                universe,
                 self.index.word_pair_proximity_docids.remap_data_type::<Bytes>(),
             ),
         }
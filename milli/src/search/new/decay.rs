@@ -0,0 +1,130 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use roaring::RoaringBitmap;
+use time::OffsetDateTime;
+
+use super::logger::SearchLogger;
+use super::recency::{best_rank_buckets, for_each_number};
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::criterion::{DecayFunction, DecayOrigin, DecayScale};
+use crate::score_details::{self, Rank, ScoreDetails};
+use crate::{Criterion, FieldId, Result};
+
+/// The number of buckets the boosts of the documents are rounded to.
+///
+/// The documents with close values end up in the same bucket, so that the next ranking rules
+/// can still sort them.
+const DECAY_MAX_RANK: u32 = 100;
+
+/// Boosts the documents whose value is close to the origin, with a boost decaying with the
+/// distance between their value and the origin, and halved at one scale from the origin.
+///
+/// The values are either numbers or RFC 3339 dates, read as Unix timestamps in seconds.
+/// The documents without any value, or with a value that can't be parsed, are ranked last.
+pub struct Decay<Query> {
+    /// The ranking rule as written in the settings, e.g. `decay(views, scale=100, type=linear)`.
+    rule: String,
+    field_id: Option<FieldId>,
+    scale: DecayScale,
+    function: DecayFunction,
+    origin: DecayOrigin,
+    original_query: Option<Query>,
+    buckets: VecDeque<(u32, RoaringBitmap)>,
+}
+
+impl<Query> Decay<Query> {
+    pub fn new(
+        ctx: &SearchContext<'_>,
+        field_name: String,
+        scale: DecayScale,
+        function: DecayFunction,
+        origin: DecayOrigin,
+    ) -> Result<Self> {
+        let field_id = ctx.index.fields_ids_map(ctx.txn)?.id(&field_name);
+        let rule = Criterion::Decay { field: field_name, scale, function, origin }.to_string();
+        Ok(Self {
+            rule,
+            field_id,
+            scale,
+            function,
+            origin,
+            original_query: None,
+            buckets: VecDeque::new(),
+        })
+    }
+
+    /// Returns the rank of a document with the given value.
+    fn rank(&self, origin: f64, value: f64) -> u32 {
+        let distance = (origin - value).abs() / self.scale.value();
+        let boost = self.function.boost(distance);
+        // a rank of 0 is reserved to the documents without any value
+        ((boost * DECAY_MAX_RANK as f64).round() as u32).clamp(1, DECAY_MAX_RANK)
+    }
+
+    fn score(&self, rank: u32) -> ScoreDetails {
+        ScoreDetails::Decay(score_details::Decay {
+            rule: self.rule.clone(),
+            rank: Rank { rank, max_rank: DECAY_MAX_RANK },
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Decay<Query> {
+    fn id(&self) -> String {
+        self.rule.clone()
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let origin = match self.origin {
+            DecayOrigin::Now => OffsetDateTime::now_utc().unix_timestamp() as f64,
+            DecayOrigin::Number(number) => number as f64,
+        };
+        let mut buckets = BTreeMap::<u32, RoaringBitmap>::new();
+
+        if let Some(field_id) = self.field_id {
+            for_each_number(ctx, field_id, parent_candidates, |value, docids| {
+                *buckets.entry(self.rank(origin, value)).or_default() |= docids;
+            })?;
+        }
+
+        // the documents with multiple values are ranked by their value closest to the origin
+        self.buckets = best_rank_buckets(buckets);
+
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        while let Some((rank, bucket)) = self.buckets.pop_front() {
+            let candidates = bucket & universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput { query, candidates, score: self.score(rank) }));
+            }
+        }
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score: self.score(0) }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::decay")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets.clear();
+    }
+}
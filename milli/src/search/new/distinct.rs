@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use heed::types::{Bytes, Str, Unit};
 use heed::{Database, RoPrefix, RoTxn};
 use roaring::RoaringBitmap;
@@ -5,6 +7,7 @@ use roaring::RoaringBitmap;
 const FID_SIZE: usize = 2;
 const DOCID_SIZE: usize = 4;
 
+use crate::facet::FacetType;
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetCodec,
 };
@@ -66,9 +69,106 @@ pub fn distinct_single_docid(
             *excluded |= facet_docids;
         }
     }
+    for (_, facet_docids) in facet_bool_values(docid, field_id, index, txn)? {
+        *excluded |= facet_docids;
+    }
     Ok(())
 }
 
+/// Limits the number of results sharing the same value in a field, keeping the first ones.
+///
+/// Unlike the distinct rule, the counts are kept from one bucket to the next, including the
+/// skipped ones, so that the pages of results stay consistent with each other.
+pub struct Diversification {
+    field_id: u16,
+    max_per_value: usize,
+    /// The number of documents kept so far with each value of the field.
+    counts: HashMap<(FacetType, Vec<u8>), usize>,
+    kept: RoaringBitmap,
+}
+
+impl Diversification {
+    pub fn new(field_id: u16, max_per_value: usize) -> Self {
+        Self { field_id, max_per_value, counts: HashMap::new(), kept: RoaringBitmap::new() }
+    }
+
+    /// Return a [`DistinctOutput`] containing:
+    /// - `remaining`: the candidates kept, in order, as long as none of their values in the field
+    /// was already kept `max_per_value` times.
+    /// - `excluded`: the set of document ids that can't be kept anymore because one of their
+    /// values was kept `max_per_value` times.
+    pub fn apply(
+        &mut self,
+        ctx: &mut SearchContext<'_>,
+        candidates: &RoaringBitmap,
+    ) -> Result<DistinctOutput> {
+        let mut excluded = RoaringBitmap::new();
+        let mut remaining = RoaringBitmap::new();
+        for docid in candidates {
+            if excluded.contains(docid) {
+                continue;
+            }
+            self.diversify_single_docid(ctx.index, ctx.txn, docid, &mut excluded)?;
+            remaining.push(docid);
+        }
+        // the documents kept in the previous buckets must stay in the results
+        excluded -= &self.kept;
+        Ok(DistinctOutput { remaining, excluded })
+    }
+
+    /// Keep a single document id, as defined by [`Self::apply`].
+    pub fn diversify_single_docid(
+        &mut self,
+        index: &Index,
+        txn: &RoTxn<'_>,
+        docid: u32,
+        excluded: &mut RoaringBitmap,
+    ) -> Result<()> {
+        for item in facet_string_values(docid, self.field_id, index, txn)? {
+            let ((_, _, facet_value), _) = item?;
+            let database = index.facet_id_string_docids.remap_types();
+            self.count(database, txn, FacetType::String, facet_value, excluded)?;
+        }
+        for item in facet_number_values(docid, self.field_id, index, txn)? {
+            let ((_, _, facet_value), _) = item?;
+            let database = index.facet_id_f64_docids.remap_types();
+            self.count(database, txn, FacetType::Number, facet_value, excluded)?;
+        }
+        for (value, facet_docids) in facet_bool_values(docid, self.field_id, index, txn)? {
+            // the booleans are counted with the strings they are displayed as
+            let key = (FacetType::String, value.to_string().into_bytes());
+            let count = self.counts.entry(key).or_default();
+            *count += 1;
+            if *count >= self.max_per_value {
+                *excluded |= facet_docids;
+            }
+        }
+        self.kept.insert(docid);
+        Ok(())
+    }
+
+    /// Count a kept value, excluding all its documents once it was kept `max_per_value` times.
+    fn count(
+        &mut self,
+        database: Database<FacetGroupKeyCodec<BytesRefCodec>, FacetGroupValueCodec>,
+        txn: &RoTxn<'_>,
+        facet_type: FacetType,
+        facet_value: &[u8],
+        excluded: &mut RoaringBitmap,
+    ) -> Result<()> {
+        let count = self.counts.entry((facet_type, facet_value.to_vec())).or_default();
+        *count += 1;
+        if *count >= self.max_per_value {
+            if let Some(facet_docids) =
+                facet_value_docids(database, txn, self.field_id, facet_value)?
+            {
+                *excluded |= facet_docids;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Return all the docids containing the given value in the given field
 fn facet_value_docids(
     database: Database<FacetGroupKeyCodec<BytesRefCodec>, FacetGroupValueCodec>,
@@ -81,8 +181,26 @@ fn facet_value_docids(
         .map(|opt| opt.map(|v| v.bitmap))
 }
 
+/// Return each boolean value in the given field of the given document,
+/// along with all the docids containing this value.
+fn facet_bool_values(
+    docid: u32,
+    field_id: u16,
+    index: &Index,
+    txn: &RoTxn<'_>,
+) -> Result<Vec<(bool, RoaringBitmap)>> {
+    let mut values = Vec::new();
+    for value in [false, true] {
+        let docids = index.bool_faceted_documents_ids(txn, field_id, value)?;
+        if docids.contains(docid) {
+            values.push((value, docids));
+        }
+    }
+    Ok(values)
+}
+
 /// Return an iterator over each number value in the given field of the given document.
-fn facet_number_values<'a>(
+pub fn facet_number_values<'a>(
     docid: u32,
     field_id: u16,
     index: &Index,
@@ -0,0 +1,148 @@
+use std::collections::{HashMap, VecDeque};
+
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+use serde_json::Value;
+
+use super::distinct::facet_number_values;
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::heed_codec::facet::OrderedF64Codec;
+use crate::score_details::{self, ScoreDetails};
+use crate::sort_expression::SortExpression;
+use crate::{FieldId, Result};
+
+/// Sorts the documents by the value of an arithmetic expression over their numeric fields.
+///
+/// The expression is evaluated lazily, only on the candidates of the bucket the ranking rule
+/// starts iterating on, with the smallest number value of each field of the documents.
+/// The documents for which the expression has no value, e.g. because they miss one of the
+/// fields, come last.
+pub struct ExpressionSort<Query> {
+    expression: SortExpression,
+    field_ids: HashMap<String, Option<FieldId>>,
+    is_ascending: bool,
+    original_query: Option<Query>,
+    buckets: VecDeque<(RoaringBitmap, Value)>,
+}
+
+impl<Query> ExpressionSort<Query> {
+    pub fn new(
+        ctx: &SearchContext<'_>,
+        expression: SortExpression,
+        is_ascending: bool,
+    ) -> Result<Self> {
+        let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
+        let field_ids = expression
+            .fields()
+            .into_iter()
+            .map(|name| (name.to_string(), fields_ids_map.id(name)))
+            .collect();
+
+        Ok(Self {
+            expression,
+            field_ids,
+            is_ascending,
+            original_query: None,
+            buckets: VecDeque::new(),
+        })
+    }
+
+    fn score(&self, value: Value) -> ScoreDetails {
+        ScoreDetails::Sort(score_details::Sort {
+            field_name: self.expression.to_string(),
+            ascending: self.is_ascending,
+            redacted: false,
+            value,
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for ExpressionSort<Query> {
+    fn id(&self) -> String {
+        let Self { expression, is_ascending, .. } = self;
+        format!("{expression}:{}", if *is_ascending { "asc" } else { "desc" })
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::sort")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let mut numbers = Vec::new();
+        for docid in parent_candidates {
+            let mut values = HashMap::new();
+            for (name, field_id) in &self.field_ids {
+                let Some(field_id) = *field_id else { continue };
+                // the values are ordered, the first one is the smallest
+                if let Some(result) =
+                    facet_number_values(docid, field_id, ctx.index, ctx.txn)?.next()
+                {
+                    let ((_, _, bytes), ()) = result?;
+                    let number =
+                        OrderedF64Codec::bytes_decode(bytes).map_err(heed::Error::Decoding)?;
+                    values.insert(name.as_str(), number);
+                }
+            }
+            if let Some(number) =
+                self.expression.evaluate(&mut |name: &str| values.get(name).copied())
+            {
+                numbers.push((number, docid));
+            }
+        }
+
+        numbers.sort_by(|(left, _), (right, _)| left.total_cmp(right));
+        if !self.is_ascending {
+            numbers.reverse();
+        }
+
+        // group the consecutive documents with the same value in a single bucket
+        self.buckets.clear();
+        for (number, docid) in numbers {
+            let value = serde_json::Number::from_f64(number).map_or(Value::Null, Value::Number);
+            match self.buckets.back_mut() {
+                Some((bucket, last)) if *last == value => {
+                    bucket.insert(docid);
+                }
+                _ => self.buckets.push_back((RoaringBitmap::from_iter([docid]), value)),
+            }
+        }
+
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::sort")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        while let Some((bucket, value)) = self.buckets.pop_front() {
+            let candidates = bucket & universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput { query, candidates, score: self.score(value) }));
+            }
+        }
+        Ok(Some(RankingRuleOutput {
+            query,
+            candidates: universe.clone(),
+            score: self.score(Value::Null),
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::sort")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets.clear();
+    }
+}
@@ -73,10 +73,26 @@ impl Strategy {
     }
 }
 
+/// The parameters of the geo sort.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Parameter {
+    /// The strategy used to fetch the sorted documents.
+    pub strategy: Strategy,
+    /// The documents farther than this distance, in meters, are not sorted by distance
+    /// and are returned after the others, along with the documents without a geo point.
+    pub max_distance: Option<f64>,
+    /// The width, in meters, of the distance ranges in which the documents are considered
+    /// equally distant, and are thus sorted by the next ranking rules.
+    /// By default, every document has its own bucket.
+    pub bucket_width: Option<f64>,
+}
+
 pub struct GeoSort<Q: RankingRuleQueryTrait> {
     query: Option<Q>,
 
     strategy: Strategy,
+    max_distance: Option<f64>,
+    bucket_width: Option<f64>,
     ascending: bool,
     point: [f64; 2],
     field_ids: Option<[u16; 2]>,
@@ -88,14 +104,17 @@ pub struct GeoSort<Q: RankingRuleQueryTrait> {
 
 impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
     pub fn new(
-        strategy: Strategy,
+        parameter: Parameter,
         geo_faceted_docids: RoaringBitmap,
         point: [f64; 2],
         ascending: bool,
     ) -> Result<Self> {
+        let Parameter { strategy, max_distance, bucket_width } = parameter;
         Ok(Self {
             query: None,
             strategy,
+            max_distance,
+            bucket_width,
             ascending,
             point,
             geo_candidates: geo_faceted_docids,
@@ -171,6 +190,12 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
 
         Ok(())
     }
+
+    /// Returns the index of the distance range of the given distance,
+    /// or `None` if every document has its own bucket.
+    fn bucket_of(&self, distance: f64) -> Option<u64> {
+        self.bucket_width.filter(|width| *width > 0.).map(|width| (distance / width) as u64)
+    }
 }
 
 /// Extracts the lat and long values from a single document.
@@ -236,28 +261,15 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
     }
 
     #[tracing::instrument(level = "trace", skip_all, target = "search::geo_sort")]
-    #[allow(clippy::only_used_in_recursion)]
     fn next_bucket(
         &mut self,
         ctx: &mut SearchContext<'ctx>,
-        logger: &mut dyn SearchLogger<Q>,
+        _logger: &mut dyn SearchLogger<Q>,
         universe: &RoaringBitmap,
     ) -> Result<Option<RankingRuleOutput<Q>>> {
         let query = self.query.as_ref().unwrap().clone();
 
-        let geo_candidates = &self.geo_candidates & universe;
-
-        if geo_candidates.is_empty() {
-            return Ok(Some(RankingRuleOutput {
-                query,
-                candidates: universe.clone(),
-                score: ScoreDetails::GeoSort(score_details::GeoSort {
-                    target_point: self.point,
-                    ascending: self.ascending,
-                    value: None,
-                }),
-            }));
-        }
+        let mut geo_candidates = &self.geo_candidates & universe;
 
         let ascending = self.ascending;
         let next = |cache: &mut VecDeque<_>| {
@@ -267,24 +279,84 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
                 cache.pop_back()
             }
         };
-        while let Some((id, point)) = next(&mut self.cached_sorted_docids) {
-            if geo_candidates.contains(id) {
-                return Ok(Some(RankingRuleOutput {
-                    query,
-                    candidates: RoaringBitmap::from_iter([id]),
-                    score: ScoreDetails::GeoSort(score_details::GeoSort {
-                        target_point: self.point,
-                        ascending: self.ascending,
-                        value: Some(point),
-                    }),
-                }));
+        let put_back = |cache: &mut VecDeque<_>, value| {
+            if ascending {
+                cache.push_front(value)
+            } else {
+                cache.push_back(value)
+            }
+        };
+
+        // the documents in the same distance range as the first one,
+        // along with the first point and its distance range.
+        let mut bucket = RoaringBitmap::new();
+        let mut first: Option<([f64; 2], Option<u64>)> = None;
+        'fill: while !(&geo_candidates - &bucket).is_empty() {
+            while let Some((id, point)) = next(&mut self.cached_sorted_docids) {
+                if !geo_candidates.contains(id) || bucket.contains(id) {
+                    continue;
+                }
+
+                let distance = distance_between_two_points(&self.point, &point);
+                if self.max_distance.map_or(false, |max| distance > max) {
+                    // the documents too far away are returned with the ones without a geo point.
+                    if ascending {
+                        // all the remaining documents are even farther
+                        let too_far = &geo_candidates - &bucket;
+                        self.geo_candidates -= &too_far;
+                        geo_candidates -= too_far;
+                        self.cached_sorted_docids.clear();
+                        break 'fill;
+                    }
+                    self.geo_candidates.remove(id);
+                    geo_candidates.remove(id);
+                    continue;
+                }
+
+                let bucket_index = self.bucket_of(distance);
+                match first {
+                    None => first = Some((point, bucket_index)),
+                    Some((_, first_index)) if first_index != bucket_index => {
+                        put_back(&mut self.cached_sorted_docids, (id, point));
+                        break 'fill;
+                    }
+                    Some(_) => (),
+                }
+                bucket.insert(id);
+                if bucket_index.is_none() {
+                    break 'fill;
+                }
+            }
+
+            // if we got out of this loop it means we've exhausted our cache.
+            // we need to refill it and continue to fill the bucket.
+            let remaining = &geo_candidates - &bucket;
+            if !remaining.is_empty() {
+                self.fill_buffer(ctx, &remaining)?;
             }
         }
 
-        // if we got out of this loop it means we've exhausted our cache.
-        // we need to refill it and run the function again.
-        self.fill_buffer(ctx, &geo_candidates)?;
-        self.next_bucket(ctx, logger, universe)
+        match first {
+            Some((point, _)) => Ok(Some(RankingRuleOutput {
+                query,
+                candidates: bucket,
+                score: ScoreDetails::GeoSort(score_details::GeoSort {
+                    target_point: self.point,
+                    ascending: self.ascending,
+                    value: Some(point),
+                }),
+            })),
+            // no more documents to sort by distance, the remaining ones are returned together.
+            None => Ok(Some(RankingRuleOutput {
+                query,
+                candidates: universe.clone(),
+                score: ScoreDetails::GeoSort(score_details::GeoSort {
+                    target_point: self.point,
+                    ascending: self.ascending,
+                    value: None,
+                }),
+            })),
+        }
     }
 
     #[tracing::instrument(level = "trace", skip_all, target = "search::geo_sort")]
@@ -121,6 +121,9 @@ pub struct GraphBasedRankingRuleState<G: RankingRuleGraphTrait> {
     cur_cost: u64,
     /// One above the highest possible cost for this rule
     next_max_cost: u64,
+    /// The highest possible score count for this rule, when the details of its score don't count
+    /// the costs of the paths
+    max_score_count: Option<u64>,
 }
 
 impl<'ctx, G: RankingRuleGraphTrait> RankingRule<'ctx, QueryGraph> for GraphBasedRankingRule<G> {
@@ -196,6 +199,7 @@ impl<'ctx, G: RankingRuleGraphTrait> RankingRule<'ctx, QueryGraph> for GraphBase
 
         next_max_cost +=
             all_costs.get(graph.query_graph.root_node).iter().copied().max().unwrap_or(0);
+        let max_score_count = graph.find_max_score_count();
 
         let state = GraphBasedRankingRuleState {
             graph,
@@ -204,6 +208,7 @@ impl<'ctx, G: RankingRuleGraphTrait> RankingRule<'ctx, QueryGraph> for GraphBase
             all_costs,
             cur_cost: 0,
             next_max_cost,
+            max_score_count,
         };
 
         self.state = Some(state);
@@ -239,11 +244,9 @@ impl<'ctx, G: RankingRuleGraphTrait> RankingRule<'ctx, QueryGraph> for GraphBase
             all_costs,
             cur_cost: _,
             next_max_cost,
+            max_score_count,
         } = &mut state;
 
-        let rank = *next_max_cost - cost;
-        let score = G::rank_to_score(Rank { rank: rank as u32, max_rank: *next_max_cost as u32 });
-
         let mut universe = universe.clone();
 
         let mut used_conditions = SmallBitmap::for_interned_values_in(&graph.conditions_interner);
@@ -312,6 +315,7 @@ impl<'ctx, G: RankingRuleGraphTrait> RankingRule<'ctx, QueryGraph> for GraphBase
             for &condition in path {
                 used_conditions.insert(condition);
             }
+            G::record_path(ctx, graph, path, &path_docids)?;
             bucket |= &path_docids;
             // Reduce the size of the universe so that we can more optimistically discard candidate paths
             universe -= &path_docids;
@@ -328,6 +332,28 @@ impl<'ctx, G: RankingRuleGraphTrait> RankingRule<'ctx, QueryGraph> for GraphBase
         logger.log_internal_state(graph);
         logger.log_internal_state(&good_paths);
 
+        // the rank always comes from the cost, so that the scores follow the order of the buckets
+        let rank = Rank { rank: (*next_max_cost - cost) as u32, max_rank: *next_max_cost as u32 };
+        let score = match *max_score_count {
+            // the paths of the bucket share the same cost but not necessarily the same count,
+            // the bucket is given the lowest one
+            Some(max_score_count) => {
+                let score_count = good_paths
+                    .iter()
+                    .map(|path| {
+                        path.iter()
+                            .map(|&condition| graph.conditions_interner.get(condition))
+                            .filter_map(G::score_count)
+                            .map(|count| count as u64)
+                            .sum::<u64>()
+                    })
+                    .min()
+                    .unwrap_or(0);
+                G::rank_and_count_to_score(rank, score_count as u32, max_score_count as u32)
+            }
+            None => G::rank_to_score(rank),
+        };
+
         // We modify the next query graph so that it only contains the subgraph
         // that was used to compute this bucket
 
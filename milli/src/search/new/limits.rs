@@ -8,6 +8,12 @@ pub const MAX_ONE_TYPO_COUNT: usize = 150;
 /// Maximum number of words that can be derived from a single word with a distance of two to that word.
 pub const MAX_TWO_TYPOS_COUNT: usize = 50;
 
+/// Maximum number of extra positions allowed between the words of a phrase, e.g. `"quick fox"~2`.
+pub const MAX_PHRASE_SLOP: u8 = 8;
+
+/// Maximum factor a term of the query can be boosted by, e.g. `rust^2.5`.
+pub const MAX_TERM_BOOST: f32 = 10.0;
+
 /// Maximum amount of synonym phrases that can be derived from a single word.
 pub const MAX_SYNONYM_PHRASE_COUNT: usize = 50;
 
@@ -452,6 +452,7 @@ fill: \"#B6E2D3\"
                 term_subset,
                 positions: _,
                 term_ids: _,
+                boost: _,
             }) => {
                 writeln!(
                     file,
@@ -462,9 +463,16 @@ fill: \"#B6E2D3\"
                     term_subset.max_typo_cost(ctx)
                 )?;
 
-                for w in term_subset.all_single_words_except_prefix_db(ctx)? {
+                let words = term_subset.all_single_words_except_prefix_db(ctx)?;
+                let transpositions = term_subset.transpositions(ctx);
+                for w in words {
+                    let kind = if transpositions.contains(&w.interned()) {
+                        "transposition"
+                    } else {
+                        "word"
+                    };
                     let w = ctx.word_interner.get(w.interned());
-                    writeln!(file, "{w}: word")?;
+                    writeln!(file, "{w}: {kind}")?;
                 }
                 for p in term_subset.all_phrases(ctx)? {
                     writeln!(file, "{}: phrase", p.description(ctx))?;
@@ -9,11 +9,13 @@ use super::super::query_term::LocatedQueryTerm;
 use super::super::{DedupInterner, Phrase};
 use crate::SearchContext;
 
+#[derive(Clone)]
 pub struct LocatedMatchingPhrase {
     pub value: Interned<Phrase>,
     pub positions: RangeInclusive<WordId>,
 }
 
+#[derive(Clone)]
 pub struct LocatedMatchingWords {
     pub value: Vec<Interned<String>>,
     pub positions: RangeInclusive<WordId>,
@@ -23,7 +25,7 @@ pub struct LocatedMatchingWords {
 
 /// Structure created from a query tree
 /// referencing words that match the given query tree.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MatchingWords {
     word_interner: DedupInterner<String>,
     phrase_interner: DedupInterner<Phrase>,
@@ -130,7 +132,8 @@ impl<'a> Iterator for MatchesIter<'a, '_> {
                         word.map(|word| self.matching_words.word_interner.get(word).as_str())
                     })
                     .collect();
-                let partial = PartialMatch { matching_words: words, ids, char_len: 0 };
+                let is_prefix = phrase.is_prefix;
+                let partial = PartialMatch { matching_words: words, ids, char_len: 0, is_prefix };
 
                 partial.match_token(self.token).or_else(|| self.next())
             }
@@ -159,6 +162,8 @@ pub struct PartialMatch<'a> {
     matching_words: Vec<Option<&'a str>>,
     ids: &'a RangeInclusive<WordId>,
     char_len: usize,
+    /// Whether the last word to match is a prefix.
+    is_prefix: bool,
 }
 
 impl<'a> PartialMatch<'a> {
@@ -167,21 +172,30 @@ impl<'a> PartialMatch<'a> {
     /// - Partial if the given token matches the partial match but doesn't complete it
     /// - Full if the given token completes the partial match
     pub fn match_token(self, token: &Token<'_>) -> Option<MatchType<'a>> {
-        let Self { mut matching_words, ids, .. } = self;
+        let Self { mut matching_words, ids, is_prefix, .. } = self;
 
+        let mut char_len = token.char_end - token.char_start;
         let is_matching = match matching_words.first()? {
+            // the last word of a prefix phrase only has to start the token,
+            // only this beginning of the token is highlighted.
+            Some(word) if is_prefix && matching_words.len() == 1 => {
+                let is_matching = token.lemma().starts_with(word);
+                if is_matching {
+                    char_len = token.original_lengths(word.len()).0;
+                }
+                is_matching
+            }
             Some(word) => &token.lemma() == word,
             // a None value in the phrase corresponds to a stop word,
             // the walue is considered a match if the current token is categorized as a stop word.
             None => token.is_stopword(),
         };
 
-        let char_len = token.char_end - token.char_start;
         // if there are remaining words to match in the phrase and the current token is matching,
         // return a new Partial match allowing the highlighter to continue.
         if is_matching && matching_words.len() > 1 {
             matching_words.remove(0);
-            Some(MatchType::Partial(PartialMatch { matching_words, ids, char_len }))
+            Some(MatchType::Partial(PartialMatch { matching_words, ids, char_len, is_prefix }))
         // if there is no remaining word to match in the phrase and the current token is matching,
         // return a Full match.
         } else if is_matching {
@@ -263,7 +277,7 @@ pub(crate) mod tests {
         let tokenizer = builder.build();
         let tokens = tokenizer.tokenize("split this world");
         let ExtractedTokens { query_terms, .. } =
-            located_query_terms_from_tokens(&mut ctx, tokens, None).unwrap();
+            located_query_terms_from_tokens(&mut ctx, "split this world", tokens, None).unwrap();
         let matching_words = MatchingWords::new(ctx, query_terms);
 
         assert_eq!(
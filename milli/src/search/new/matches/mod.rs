@@ -18,6 +18,8 @@ pub struct MatcherBuilder<'m> {
     crop_marker: Option<String>,
     highlight_prefix: Option<String>,
     highlight_suffix: Option<String>,
+    max_fragments: Option<usize>,
+    fragment_separator: Option<String>,
 }
 
 impl<'m> MatcherBuilder<'m> {
@@ -28,6 +30,8 @@ impl<'m> MatcherBuilder<'m> {
             crop_marker: None,
             highlight_prefix: None,
             highlight_suffix: None,
+            max_fragments: None,
+            fragment_separator: None,
         }
     }
 
@@ -46,6 +50,19 @@ impl<'m> MatcherBuilder<'m> {
         self
     }
 
+    /// Crops the texts to their best `max_fragments` windows instead of a single one.
+    pub fn max_fragments(&mut self, max_fragments: usize) -> &Self {
+        // a cropped text always keeps at least one window
+        self.max_fragments = Some(max_fragments.max(1));
+        self
+    }
+
+    /// The text inserted between two fragments, the crop marker by default.
+    pub fn fragment_separator(&mut self, separator: String) -> &Self {
+        self.fragment_separator = Some(separator);
+        self
+    }
+
     pub fn build<'t>(&'m self, text: &'t str) -> Matcher<'t, 'm> {
         let crop_marker = match &self.crop_marker {
             Some(marker) => marker.as_str(),
@@ -60,6 +77,10 @@ impl<'m> MatcherBuilder<'m> {
             Some(marker) => marker.as_str(),
             None => DEFAULT_HIGHLIGHT_SUFFIX,
         };
+        let fragment_separator = match &self.fragment_separator {
+            Some(separator) => separator.as_str(),
+            None => crop_marker,
+        };
         Matcher {
             text,
             matching_words: &self.matching_words,
@@ -67,6 +88,8 @@ impl<'m> MatcherBuilder<'m> {
             crop_marker,
             highlight_prefix,
             highlight_suffix,
+            max_fragments: self.max_fragments,
+            fragment_separator,
             matches: None,
         }
     }
@@ -114,6 +137,8 @@ pub struct Matcher<'t, 'm> {
     crop_marker: &'m str,
     highlight_prefix: &'m str,
     highlight_suffix: &'m str,
+    max_fragments: Option<usize>,
+    fragment_separator: &'m str,
     matches: Option<(Vec<Token<'t>>, Vec<Match>)>,
 }
 
@@ -419,6 +444,60 @@ impl<'t> Matcher<'t, '_> {
         }
     }
 
+    /// Returns the bounds in byte index of the best crop windows that don't overlap,
+    /// in the order of the text.
+    ///
+    /// The windows are ranked by the number of distinct query words they match,
+    /// then by their number of matches, and then like the single crop window.
+    fn fragments_bounds(
+        &self,
+        tokens: &[Token<'_>],
+        matches: &[Match],
+        crop_size: usize,
+        max_fragments: usize,
+    ) -> Vec<(usize, usize)> {
+        // each match starts a window containing the next matches fitting in the crop size.
+        let mut windows: Vec<_> = (0..matches.len())
+            .map(|first| {
+                let start = matches[first].word_position;
+                let len = matches[first..]
+                    .iter()
+                    .take_while(|m| m.word_position - start < crop_size)
+                    .count();
+                let window = &matches[first..first + len];
+                let (uniq_score, distance_score, order_score) = self.match_interval_score(window);
+                ((uniq_score, len, distance_score, order_score), window)
+            })
+            .collect();
+        // the sort is stable, the first windows of the text come first on equal scores.
+        windows.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        let mut fragments: Vec<&[Match]> = Vec::new();
+        for (_, window) in windows {
+            if fragments.len() == max_fragments {
+                break;
+            }
+            let start = window[0].word_position;
+            if fragments.iter().all(|f| f[0].word_position.abs_diff(start) >= crop_size) {
+                fragments.push(window);
+            }
+        }
+        fragments.sort_unstable_by_key(|f| f[0].word_position);
+
+        // the windows are grown around their matches, the ones that end up overlapping are merged.
+        let mut bounds: Vec<(usize, usize)> = Vec::new();
+        for fragment in fragments {
+            let (byte_start, byte_end) = self.crop_bounds(tokens, fragment, crop_size);
+            match bounds.last_mut() {
+                Some((_, last_end)) if byte_start <= *last_end => {
+                    *last_end = byte_end.max(*last_end)
+                }
+                _ => bounds.push((byte_start, byte_end)),
+            }
+        }
+        bounds
+    }
+
     // Returns the formatted version of the original text.
     pub fn format(&mut self, format_options: FormatOptions) -> Cow<'t, str> {
         if !format_options.highlight && format_options.crop.is_none() {
@@ -428,61 +507,72 @@ impl<'t> Matcher<'t, '_> {
             match &self.matches {
                 Some((tokens, matches)) => {
                     // If the text has to be cropped,
-                    // crop around the best interval.
-                    let (byte_start, byte_end) = match format_options.crop {
-                        Some(crop_size) if crop_size > 0 => {
-                            let matches = self.find_best_match_interval(matches, crop_size);
-                            self.crop_bounds(tokens, matches, crop_size)
-                        }
-                        _ => (0, self.text.len()),
+                    // crop around the best interval, or the best intervals when fragmented.
+                    let bounds = match format_options.crop {
+                        Some(crop_size) if crop_size > 0 => match self.max_fragments {
+                            Some(max_fragments) if !matches.is_empty() => {
+                                self.fragments_bounds(tokens, matches, crop_size, max_fragments)
+                            }
+                            _ => {
+                                let matches = self.find_best_match_interval(matches, crop_size);
+                                vec![self.crop_bounds(tokens, matches, crop_size)]
+                            }
+                        },
+                        _ => vec![(0, self.text.len())],
                     };
 
                     let mut formatted = Vec::new();
 
-                    // push crop marker if it's not the start of the text.
-                    if byte_start > 0 && !self.crop_marker.is_empty() {
-                        formatted.push(self.crop_marker);
-                    }
+                    for (i, &(byte_start, byte_end)) in bounds.iter().enumerate() {
+                        if i > 0 {
+                            formatted.push(self.fragment_separator);
+                        } else if byte_start > 0 && !self.crop_marker.is_empty() {
+                            // push crop marker if it's not the start of the text.
+                            formatted.push(self.crop_marker);
+                        }
 
-                    let mut byte_index = byte_start;
+                        let mut byte_index = byte_start;
 
-                    if format_options.highlight {
-                        // insert highlight markers around matches.
-                        for m in matches {
-                            let token = &tokens[m.token_position];
+                        if format_options.highlight {
+                            // insert highlight markers around matches.
+                            for m in matches {
+                                let token = &tokens[m.token_position];
 
-                            // skip matches out of the crop window.
-                            if token.byte_start < byte_start || token.byte_end > byte_end {
-                                continue;
-                            }
+                                // skip matches out of the crop window.
+                                if token.byte_start < byte_start || token.byte_end > byte_end {
+                                    continue;
+                                }
 
-                            if byte_index < token.byte_start {
-                                formatted.push(&self.text[byte_index..token.byte_start]);
-                            }
+                                if byte_index < token.byte_start {
+                                    formatted.push(&self.text[byte_index..token.byte_start]);
+                                }
 
-                            let highlight_byte_index = self.text[token.byte_start..]
-                                .char_indices()
-                                .enumerate()
-                                .find(|(i, _)| *i == m.match_len)
-                                .map_or(token.byte_end, |(_, (i, _))| i + token.byte_start);
-                            formatted.push(self.highlight_prefix);
-                            formatted.push(&self.text[token.byte_start..highlight_byte_index]);
-                            formatted.push(self.highlight_suffix);
-                            // if it's a prefix highlight, we put the end of the word after the highlight marker.
-                            if highlight_byte_index < token.byte_end {
-                                formatted.push(&self.text[highlight_byte_index..token.byte_end]);
-                            }
+                                let highlight_byte_index = self.text[token.byte_start..]
+                                    .char_indices()
+                                    .enumerate()
+                                    .find(|(i, _)| *i == m.match_len)
+                                    .map_or(token.byte_end, |(_, (i, _))| i + token.byte_start);
+                                formatted.push(self.highlight_prefix);
+                                formatted.push(&self.text[token.byte_start..highlight_byte_index]);
+                                formatted.push(self.highlight_suffix);
+                                // if it's a prefix highlight, we put the end of the word after the highlight marker.
+                                if highlight_byte_index < token.byte_end {
+                                    formatted
+                                        .push(&self.text[highlight_byte_index..token.byte_end]);
+                                }
 
-                            byte_index = token.byte_end;
+                                byte_index = token.byte_end;
+                            }
                         }
-                    }
 
-                    // push the rest of the text between last match and the end of crop.
-                    if byte_index < byte_end {
-                        formatted.push(&self.text[byte_index..byte_end]);
+                        // push the rest of the text between last match and the end of crop.
+                        if byte_index < byte_end {
+                            formatted.push(&self.text[byte_index..byte_end]);
+                        }
                     }
 
                     // push crop marker if it's not the end of the text.
+                    let (byte_start, byte_end) = (bounds[0].0, bounds[bounds.len() - 1].1);
                     if byte_end < self.text.len() && !self.crop_marker.is_empty() {
                         formatted.push(self.crop_marker);
                     }
@@ -522,7 +612,7 @@ mod tests {
                 universe,
                 &None,
                 &None,
-                crate::search::new::GeoSortStrategy::default(),
+                crate::search::new::GeoSortParameter::default(),
                 0,
                 100,
                 Some(10),
@@ -840,6 +930,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_fragments() {
+        let temp_index = temp_index_with_documents();
+        let rtxn = temp_index.read_txn().unwrap();
+        let mut builder = MatcherBuilder::new_test(&rtxn, &temp_index, "world");
+        builder.max_fragments(2);
+
+        let format_options = FormatOptions { highlight: true, crop: Some(3) };
+        let text =
+            "one world two three four five six world world seven eight nine ten world eleven";
+
+        let mut matcher = builder.build(text);
+        // the densest window comes first, then the first one of the text that doesn't overlap it.
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"one <em>world</em> two…six <em>world</em> <em>world</em>…"
+        );
+
+        builder.max_fragments(1);
+        builder.fragment_separator(" [...] ".to_string());
+        let mut matcher = builder.build(text);
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"…six <em>world</em> <em>world</em>…"
+        );
+
+        builder.max_fragments(3);
+        let mut matcher = builder.build(text);
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"one <em>world</em> two [...] six <em>world</em> <em>world</em> [...] ten <em>world</em> eleven"
+        );
+    }
+
     #[test]
     fn smaller_crop_size() {
         //! testing: https://github.com/meilisearch/specifications/pull/120#discussion_r836536295
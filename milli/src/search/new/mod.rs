@@ -1,5 +1,8 @@
+mod bm25;
 mod bucket_sort;
+mod computed_sort;
 mod db_cache;
+mod decay;
 mod distinct;
 mod geo_sort;
 mod graph_based_ranking_rule;
@@ -7,50 +10,82 @@ mod interner;
 mod limits;
 mod logger;
 pub mod matches;
+mod primary_key_sort;
 mod query_graph;
+mod query_interpretation;
 mod query_term;
 mod ranking_rule_graph;
+mod ranking_rule_plugin;
 mod ranking_rules;
+mod recency;
 mod resolve_query_graph;
 mod small_bitmap;
+mod timings;
 
 mod exact_attribute;
+mod expression_sort;
 mod sort;
 mod vector_sort;
 
 #[cfg(test)]
 mod tests;
 
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::Instant;
 
+use bm25::Bm25;
 use bucket_sort::{bucket_sort, BucketSortOutput};
+use charabia::separators::DEFAULT_SEPARATORS;
 use charabia::TokenizerBuilder;
+use computed_sort::ComputedSort;
 use db_cache::DatabaseCache;
+use decay::Decay;
 use exact_attribute::ExactAttribute;
+use expression_sort::ExpressionSort;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
 use heed::RoTxn;
 use interner::{DedupInterner, Interner};
 pub use logger::visual::VisualSearchLogger;
 pub use logger::{DefaultSearchLogger, SearchLogger};
+use primary_key_sort::PrimaryKeySort;
 use query_graph::{QueryGraph, QueryNode};
+pub use query_interpretation::{InterpretedQueryTerm, QueryInterpretation};
 use query_term::{
     located_query_terms_from_tokens, ExtractedTokens, LocatedQueryTerm, Phrase, QueryTerm,
+    QueryTermSubset,
+};
+pub use query_term::{QueryTermKind, QueryTermLocation};
+use ranking_rule_plugin::PluginRankingRule;
+pub use ranking_rule_plugin::{
+    ranking_rule_plugin, register_ranking_rule_plugin, RankingRulePlugin, RankingRulePluginBuckets,
 };
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
 };
-use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
+use recency::Recency;
+use resolve_query_graph::{
+    compute_query_graph_docids, compute_query_term_subset_docids,
+    compute_query_term_subset_docids_within_field_id, PhraseDocIdsCache,
+};
 use roaring::RoaringBitmap;
 use sort::Sort;
+pub use timings::SearchTimings;
 
 use self::distinct::facet_string_values;
 use self::geo_sort::GeoSort;
-pub use self::geo_sort::Strategy as GeoSortStrategy;
+pub use self::geo_sort::{Parameter as GeoSortParameter, Strategy as GeoSortStrategy};
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
 use self::vector_sort::VectorSort;
-use crate::score_details::{ScoreDetails, ScoringStrategy};
-use crate::search::new::distinct::apply_distinct_rule;
+use crate::computed_fields::ComputedFields;
+use crate::language_preset::{languages_allow_list, languages_stop_words, LanguagePreset};
+use crate::score_details::{ScoreDetails, ScoringStrategy, TermTypos};
+use crate::search::new::distinct::{apply_distinct_rule, Diversification};
+use crate::tie_breaker::TieBreaker;
+use crate::update::normalize_synonyms;
 use crate::vector::Embedder;
 use crate::{
     AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, TimeBudget,
@@ -67,6 +102,24 @@ pub struct SearchContext<'ctx> {
     pub term_interner: Interner<QueryTerm>,
     pub phrase_docids: PhraseDocIdsCache,
     pub restricted_fids: Option<RestrictedFids>,
+    /// Whether the query is long enough for its biggest proximities to be bucketed together.
+    pub bucket_proximities: bool,
+    /// The normalized synonyms given with the query, added to the ones of the index.
+    pub query_synonyms: HashMap<Vec<String>, Vec<Vec<String>>>,
+    /// The languages the query is written in, if the search is restricted to them.
+    pub query_languages: Vec<LanguagePreset>,
+    /// The field and the maximum number of results sharing one of its values, if the results
+    /// are diversified.
+    pub diversify: Option<(FieldId, usize)>,
+    /// The maximum number of typos allowed on any word of the query, on top of the settings.
+    pub max_typos: Option<u8>,
+    /// Whether the quoted parts of the query are regular expressions instead of phrases.
+    pub regex_terms: bool,
+    /// The time spent in each stage of the search, if it is measured.
+    pub timings: Option<SearchTimings>,
+    /// The typos needed by the terms of the query, along with the documents matched
+    /// through the same path of the typo ranking rule, if they are recorded.
+    pub term_typos: Option<Vec<(RoaringBitmap, Vec<TermTypos>)>>,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -93,9 +146,48 @@ impl<'ctx> SearchContext<'ctx> {
             term_interner: <_>::default(),
             phrase_docids: <_>::default(),
             restricted_fids: None,
+            bucket_proximities: false,
+            query_synonyms: HashMap::new(),
+            query_languages: Vec::new(),
+            diversify: None,
+            max_typos: None,
+            regex_terms: false,
+            timings: None,
+            term_typos: None,
         })
     }
 
+    /// Adds synonyms to the ones of the index for this search only.
+    pub fn add_query_synonyms(&mut self, synonyms: &BTreeMap<String, Vec<String>>) -> Result<()> {
+        let synonyms = normalize_synonyms(self.index, self.txn, synonyms)?;
+        for (words, synonyms) in synonyms {
+            self.query_synonyms.entry(words).or_default().extend(synonyms);
+        }
+        Ok(())
+    }
+
+    /// Limits the number of results sharing the same value in the given field.
+    pub fn diversify(&mut self, field: &str, max_per_value: usize) -> Result<()> {
+        let fields_ids_map = self.index.fields_ids_map(self.txn)?;
+        // when no document has the field, the results are all different
+        self.diversify = fields_ids_map.id(field).map(|fid| (fid, max_per_value));
+        Ok(())
+    }
+
+    /// Returns the synonyms of the given words, both the ones of the index and the
+    /// ones given with the query, except the ones containing a blocked word.
+    pub fn synonyms_of(&self, words: &[String]) -> Result<Vec<Vec<String>>> {
+        let mut synonyms = self.index.synonyms(self.txn)?.remove(words).unwrap_or_default();
+        if let Some(query_synonyms) = self.query_synonyms.get(words) {
+            synonyms.extend(query_synonyms.iter().cloned());
+            synonyms.sort_unstable();
+            synonyms.dedup();
+        }
+        let blocked_terms = self.index.blocked_terms(self.txn)?;
+        synonyms.retain(|words| !words.iter().any(|word| blocked_terms.contains(word)));
+        Ok(synonyms)
+    }
+
     pub fn attributes_to_search_on(
         &mut self,
         attributes_to_search_on: &'ctx [String],
@@ -152,6 +244,41 @@ impl<'ctx> SearchContext<'ctx> {
 
         Ok(())
     }
+
+    /// Restricts the search to the fields written in one of the given languages
+    /// and to the fields without any language, the query being tokenized in these languages.
+    pub fn restrict_to_languages(&mut self, languages: &[LanguagePreset]) -> Result<()> {
+        let fields_ids_map = self.index.fields_ids_map(self.txn)?;
+        let field_languages = self.index.field_languages(self.txn)?;
+        let is_in_languages = |fid: &FieldId| {
+            fields_ids_map
+                .name(*fid)
+                .and_then(|name| field_languages.get(name))
+                .map_or(true, |language| languages.contains(language))
+        };
+
+        let mut restricted_fids = match self.restricted_fids.take() {
+            Some(restricted_fids) => restricted_fids,
+            None => {
+                let exact_attributes_ids = self.index.exact_attributes_ids(self.txn)?;
+                let mut restricted_fids = RestrictedFids::default();
+                for (_name, fid, weight) in self.index.searchable_fields_and_weights(self.txn)? {
+                    if exact_attributes_ids.contains(&fid) {
+                        restricted_fids.exact.push((fid, weight));
+                    } else {
+                        restricted_fids.tolerant.push((fid, weight));
+                    }
+                }
+                restricted_fids
+            }
+        };
+        restricted_fids.exact.retain(|(fid, _)| is_in_languages(fid));
+        restricted_fids.tolerant.retain(|(fid, _)| is_in_languages(fid));
+
+        self.restricted_fids = Some(restricted_fids);
+        self.query_languages = languages.to_vec();
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
@@ -259,11 +386,27 @@ fn resolve_negative_phrases(
     Ok(negative_bitmap)
 }
 
+#[tracing::instrument(level = "trace", skip_all, target = "search::query")]
+fn resolve_field_scoped_terms(
+    ctx: &mut SearchContext<'_>,
+    universe: &RoaringBitmap,
+    field_scoped_terms: &[(Interned<QueryTerm>, FieldId)],
+) -> Result<RoaringBitmap> {
+    let mut docids = universe.clone();
+    for &(term, fid) in field_scoped_terms {
+        let subset = QueryTermSubset::full(term);
+        let term_docids =
+            compute_query_term_subset_docids_within_field_id(ctx, Some(&docids), &subset, fid)?;
+        docids &= term_docids;
+    }
+    Ok(docids)
+}
+
 /// Return the list of initialised ranking rules to be used for a placeholder search.
 fn get_ranking_rules_for_placeholder_search<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
-    geo_strategy: geo_sort::Strategy,
+    geo_param: geo_sort::Parameter,
 ) -> Result<Vec<BoxRankingRule<'ctx, PlaceholderQuery>>> {
     let mut sort = false;
     let mut sorted_fields = HashSet::new();
@@ -277,7 +420,8 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
             | crate::Criterion::Typo
             | crate::Criterion::Attribute
             | crate::Criterion::Proximity
-            | crate::Criterion::Exactness => continue,
+            | crate::Criterion::Exactness
+            | crate::Criterion::Bm25 => continue,
             crate::Criterion::Sort => {
                 if sort {
                     continue;
@@ -288,7 +432,7 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                     &mut ranking_rules,
                     &mut sorted_fields,
                     &mut geo_sorted,
-                    geo_strategy,
+                    geo_param,
                 )?;
                 sort = true;
             }
@@ -306,15 +450,25 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Plugin(name) => {
+                ranking_rules.push(Box::new(PluginRankingRule::new(name)?));
+            }
+            crate::Criterion::Recency { field, half_life } => {
+                ranking_rules.push(Box::new(Recency::new(ctx, field, half_life)?));
+            }
+            crate::Criterion::Decay { field, scale, function, origin } => {
+                ranking_rules.push(Box::new(Decay::new(ctx, field, scale, function, origin)?));
+            }
         }
     }
+    push_tie_breaker(ctx, &mut ranking_rules, &sorted_fields)?;
     Ok(ranking_rules)
 }
 
 fn get_ranking_rules_for_vector<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
-    geo_strategy: geo_sort::Strategy,
+    geo_param: geo_sort::Parameter,
     limit_plus_offset: usize,
     target: &[f32],
     embedder_name: &str,
@@ -336,7 +490,8 @@ fn get_ranking_rules_for_vector<'ctx>(
             | crate::Criterion::Typo
             | crate::Criterion::Proximity
             | crate::Criterion::Attribute
-            | crate::Criterion::Exactness => {
+            | crate::Criterion::Exactness
+            | crate::Criterion::Bm25 => {
                 if !vector {
                     let vector_candidates = ctx.index.documents_ids(ctx.txn)?;
                     let vector_sort = VectorSort::new(
@@ -361,7 +516,7 @@ fn get_ranking_rules_for_vector<'ctx>(
                     &mut ranking_rules,
                     &mut sorted_fields,
                     &mut geo_sorted,
-                    geo_strategy,
+                    geo_param,
                 )?;
                 sort = true;
             }
@@ -379,9 +534,19 @@ fn get_ranking_rules_for_vector<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Plugin(name) => {
+                ranking_rules.push(Box::new(PluginRankingRule::new(name)?));
+            }
+            crate::Criterion::Recency { field, half_life } => {
+                ranking_rules.push(Box::new(Recency::new(ctx, field, half_life)?));
+            }
+            crate::Criterion::Decay { field, scale, function, origin } => {
+                ranking_rules.push(Box::new(Decay::new(ctx, field, scale, function, origin)?));
+            }
         }
     }
 
+    push_tie_breaker(ctx, &mut ranking_rules, &sorted_fields)?;
     Ok(ranking_rules)
 }
 
@@ -389,7 +554,7 @@ fn get_ranking_rules_for_vector<'ctx>(
 fn get_ranking_rules_for_query_graph_search<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
-    geo_strategy: geo_sort::Strategy,
+    geo_param: geo_sort::Parameter,
     terms_matching_strategy: TermsMatchingStrategy,
 ) -> Result<Vec<BoxRankingRule<'ctx, QueryGraph>>> {
     // query graph search
@@ -399,6 +564,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     let mut sort = false;
     let mut attribute = false;
     let mut exactness = false;
+    let mut bm25 = false;
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
 
@@ -463,7 +629,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                     &mut ranking_rules,
                     &mut sorted_fields,
                     &mut geo_sorted,
-                    geo_strategy,
+                    geo_param,
                 )?;
                 sort = true;
             }
@@ -489,36 +655,94 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Plugin(name) => {
+                ranking_rules.push(Box::new(PluginRankingRule::new(name)?));
+            }
+            crate::Criterion::Recency { field, half_life } => {
+                ranking_rules.push(Box::new(Recency::new(ctx, field, half_life)?));
+            }
+            crate::Criterion::Decay { field, scale, function, origin } => {
+                ranking_rules.push(Box::new(Decay::new(ctx, field, scale, function, origin)?));
+            }
+            crate::Criterion::Bm25 => {
+                if bm25 {
+                    continue;
+                }
+                bm25 = true;
+                ranking_rules.push(Box::new(Bm25::new()));
+            }
         }
     }
+    push_tie_breaker(ctx, &mut ranking_rules, &sorted_fields)?;
     Ok(ranking_rules)
 }
 
+/// Appends the tie-breaker of the index, ordering the documents that all the other ranking
+/// rules consider equal, unless the documents are already sorted by the same field.
+fn push_tie_breaker<'ctx, Query: RankingRuleQueryTrait>(
+    ctx: &SearchContext<'ctx>,
+    ranking_rules: &mut Vec<BoxRankingRule<'ctx, Query>>,
+    sorted_fields: &HashSet<String>,
+) -> Result<()> {
+    match ctx.index.tie_breaker(ctx.txn)? {
+        Some(TieBreaker::PrimaryKey { ascending }) => {
+            let primary_key = ctx.index.primary_key(ctx.txn)?;
+            if primary_key.map_or(false, |primary_key| sorted_fields.contains(primary_key)) {
+                return Ok(());
+            }
+            ranking_rules.push(Box::new(PrimaryKeySort::new(ctx.index, ctx.txn, ascending)?));
+        }
+        Some(TieBreaker::Field { field, ascending }) => {
+            if sorted_fields.contains(&field) {
+                return Ok(());
+            }
+            ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field, ascending)?));
+        }
+        None => (),
+    }
+    Ok(())
+}
+
 fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
     sort_criteria: &Option<Vec<AscDesc>>,
     ctx: &SearchContext<'ctx>,
     ranking_rules: &mut Vec<BoxRankingRule<'ctx, Query>>,
     sorted_fields: &mut HashSet<String>,
     geo_sorted: &mut bool,
-    geo_strategy: geo_sort::Strategy,
+    geo_param: geo_sort::Parameter,
 ) -> Result<()> {
     let sort_criteria = sort_criteria.clone().unwrap_or_default();
     ranking_rules.reserve(sort_criteria.len());
+    let computed_fields = Rc::new(ComputedFields::new(&ctx.index.computed_fields(ctx.txn)?)?);
     for criterion in sort_criteria {
+        let is_ascending = matches!(criterion, AscDesc::Asc(_));
         match criterion {
-            AscDesc::Asc(Member::Field(field_name)) => {
+            AscDesc::Asc(Member::Field(field_name)) | AscDesc::Desc(Member::Field(field_name)) => {
                 if sorted_fields.contains(&field_name) {
                     continue;
                 }
                 sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, true)?));
+                if computed_fields.contains(&field_name) {
+                    ranking_rules.push(Box::new(ComputedSort::new(
+                        computed_fields.clone(),
+                        field_name,
+                        is_ascending,
+                    )));
+                } else {
+                    ranking_rules.push(Box::new(Sort::new(
+                        ctx.index,
+                        ctx.txn,
+                        field_name,
+                        is_ascending,
+                    )?));
+                }
             }
-            AscDesc::Desc(Member::Field(field_name)) => {
-                if sorted_fields.contains(&field_name) {
+            AscDesc::Asc(Member::Expression(expression))
+            | AscDesc::Desc(Member::Expression(expression)) => {
+                if !sorted_fields.insert(expression.to_string()) {
                     continue;
                 }
-                sorted_fields.insert(field_name.clone());
-                ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
+                ranking_rules.push(Box::new(ExpressionSort::new(ctx, expression, is_ascending)?));
             }
             AscDesc::Asc(Member::Geo(point)) => {
                 if *geo_sorted {
@@ -526,7 +750,7 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                 }
                 let geo_faceted_docids = ctx.index.geo_faceted_documents_ids(ctx.txn)?;
                 ranking_rules.push(Box::new(GeoSort::new(
-                    geo_strategy,
+                    geo_param,
                     geo_faceted_docids,
                     point,
                     true,
@@ -538,7 +762,7 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                 }
                 let geo_faceted_docids = ctx.index.geo_faceted_documents_ids(ctx.txn)?;
                 ranking_rules.push(Box::new(GeoSort::new(
-                    geo_strategy,
+                    geo_param,
                     geo_faceted_docids,
                     point,
                     false,
@@ -570,7 +794,7 @@ pub fn execute_vector_search(
     universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
     distinct: &Option<String>,
-    geo_strategy: geo_sort::Strategy,
+    geo_param: geo_sort::Parameter,
     from: usize,
     length: usize,
     embedder_name: &str,
@@ -585,7 +809,7 @@ pub fn execute_vector_search(
     let ranking_rules = get_ranking_rules_for_vector(
         ctx,
         sort_criteria,
-        geo_strategy,
+        geo_param,
         from + length,
         vector,
         embedder_name,
@@ -615,8 +839,11 @@ pub fn execute_vector_search(
         document_scores: scores,
         documents_ids: docids,
         located_query_terms: None,
+        query_term_locations: Vec::new(),
+        removed_stop_words: Vec::new(),
         degraded,
         used_negative_operator: false,
+        query_truncated: false,
     })
 }
 
@@ -631,7 +858,7 @@ pub fn execute_search(
     mut universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
     distinct: &Option<String>,
-    geo_strategy: geo_sort::Strategy,
+    geo_param: geo_sort::Parameter,
     from: usize,
     length: usize,
     words_limit: Option<usize>,
@@ -642,9 +869,38 @@ pub fn execute_search(
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
+    // the precomputed results of the popular prefixes can only be
+    // used when nothing but the query can change them
+    let prefix_cache_usable = ctx.index.prefix_cache_size(ctx.txn)?.map_or(false, |size| size > 0)
+        && sort_criteria.as_ref().map_or(true, Vec::is_empty)
+        && distinct.is_none()
+        && ctx.index.distinct_field(ctx.txn)?.is_none()
+        && scoring_strategy == ScoringStrategy::Skip
+        && ranking_score_threshold.is_none()
+        && ctx.restricted_fids.is_none()
+        && ctx.query_synonyms.is_empty()
+        && ctx.query_languages.is_empty()
+        && ctx.diversify.is_none()
+        && ctx.max_typos.is_none()
+        && universe.len() == ctx.index.number_of_documents(ctx.txn)?;
+
     let mut used_negative_operator = false;
+    let mut used_field_scopes = false;
+    let mut query_truncated = false;
     let mut located_query_terms = None;
+    let mut query_term_locations = Vec::new();
+    let mut removed_stop_words = Vec::new();
     let query_terms = if let Some(query) = query {
+        let query_limits = ctx.index.query_limits(ctx.txn)?;
+        let query = match query_limits.truncate_query(query) {
+            Some(truncated) => {
+                query_truncated = true;
+                truncated
+            }
+            None => query,
+        };
+
+        let before_parsing = Instant::now();
         let span = tracing::trace_span!(target: "search::tokens", "tokenizer_builder");
         let entered = span.enter();
 
@@ -652,25 +908,48 @@ pub fn execute_search(
         // this ensures that the query builder is able to properly remove them.
         let mut tokbuilder = TokenizerBuilder::new();
         let stop_words = ctx.index.stop_words(ctx.txn)?;
+        // the stop words of the languages of the query were removed from their fields
+        let stop_words = if ctx.query_languages.is_empty() {
+            stop_words.map(|stop_words| stop_words.map_data(Cow::Borrowed)).transpose()?
+        } else {
+            let stop_words = languages_stop_words(stop_words.as_ref(), &ctx.query_languages)?;
+            Some(stop_words.map_data(Cow::Owned)?)
+        };
         if let Some(ref stop_words) = stop_words {
             tokbuilder.stop_words(stop_words);
         }
 
-        let separators = ctx.index.allowed_separators(ctx.txn)?;
+        let mut separators = ctx.index.allowed_separators(ctx.txn)?;
+        // the quotes must be tokenized as separators to delimit the phrases
+        let quotes = ctx.index.quote_characters(ctx.txn)?;
+        let is_separator = |quote: &String| match &separators {
+            Some(separators) => separators.contains(quote),
+            None => DEFAULT_SEPARATORS.contains(&quote.as_str()),
+        };
+        if !quotes.iter().all(is_separator) {
+            separators
+                .get_or_insert_with(|| DEFAULT_SEPARATORS.iter().map(|s| s.to_string()).collect())
+                .extend(quotes);
+        }
         let separators: Option<Vec<_>> =
             separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
         if let Some(ref separators) = separators {
             tokbuilder.separators(separators);
         }
 
-        let dictionary = ctx.index.dictionary(ctx.txn)?;
+        let dictionary = ctx.index.tokenizer_dictionary(ctx.txn)?;
         let dictionary: Option<Vec<_>> =
             dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
         if let Some(ref dictionary) = dictionary {
             tokbuilder.words_dict(dictionary);
         }
 
-        let script_lang_map = ctx.index.script_language(ctx.txn)?;
+        // the query is tokenized in the language forced by the preset, like the documents
+        let script_lang_map = match ctx.index.language_preset(ctx.txn)? {
+            _ if !ctx.query_languages.is_empty() => languages_allow_list(&ctx.query_languages),
+            Some(preset) => preset.allow_list(),
+            None => ctx.index.script_language(ctx.txn)?,
+        };
         if !script_lang_map.is_empty() {
             tokbuilder.allow_list(&script_lang_map);
         }
@@ -683,8 +962,27 @@ pub fn execute_search(
         let tokens = tokenizer.tokenize(query);
         drop(entered);
 
-        let ExtractedTokens { query_terms, negative_words, negative_phrases } =
-            located_query_terms_from_tokens(ctx, tokens, words_limit)?;
+        let words_limit = query_limits.max_terms.or(words_limit);
+        let mut extracted_tokens =
+            located_query_terms_from_tokens(ctx, query, tokens, words_limit)?;
+        if let Some(timings) = ctx.timings.as_mut() {
+            timings.query_parsing = before_parsing.elapsed();
+        }
+        if let Some(max_phrases) = query_limits.max_phrases {
+            query_truncated |= extracted_tokens.limit_phrases(max_phrases);
+        }
+        let ExtractedTokens {
+            query_terms,
+            negative_words,
+            negative_phrases,
+            field_scoped_terms,
+            term_locations,
+            removed_stop_words: stop_words,
+            truncated,
+        } = extracted_tokens;
+        query_truncated |= truncated;
+        query_term_locations = term_locations;
+        removed_stop_words = stop_words;
         used_negative_operator = !negative_words.is_empty() || !negative_phrases.is_empty();
 
         let ignored_documents = resolve_negative_words(ctx, Some(&universe), &negative_words)?;
@@ -693,6 +991,9 @@ pub fn execute_search(
         universe -= ignored_documents;
         universe -= ignored_phrases;
 
+        used_field_scopes = !field_scoped_terms.is_empty();
+        universe = resolve_field_scoped_terms(ctx, &universe, &field_scoped_terms)?;
+
         if query_terms.is_empty() {
             // Do a placeholder search instead
             None
@@ -704,35 +1005,64 @@ pub fn execute_search(
     };
 
     let bucket_sort_output = if let Some(query_terms) = query_terms {
+        if let Some(threshold) = ctx.index.long_query_threshold(ctx.txn)? {
+            ctx.bucket_proximities = query_terms.len() as u64 > threshold;
+        }
+
+        let before_graph_building = Instant::now();
         let (graph, new_located_query_terms) = QueryGraph::from_query(ctx, &query_terms)?;
         located_query_terms = Some(new_located_query_terms);
 
+        let cached_docids = if prefix_cache_usable && !used_negative_operator && !used_field_scopes
+        {
+            cached_prefix_results(ctx, &query_terms)?
+        } else {
+            None
+        };
+
         let ranking_rules = get_ranking_rules_for_query_graph_search(
             ctx,
             sort_criteria,
-            geo_strategy,
+            geo_param,
             terms_matching_strategy,
         )?;
 
         universe &=
             resolve_universe(ctx, &universe, &graph, terms_matching_strategy, query_graph_logger)?;
+        if let Some(timings) = ctx.timings.as_mut() {
+            timings.graph_building = before_graph_building.elapsed();
+        }
 
-        bucket_sort(
-            ctx,
-            ranking_rules,
-            &graph,
-            distinct.as_deref(),
-            &universe,
-            from,
-            length,
-            scoring_strategy,
-            query_graph_logger,
-            time_budget,
-            ranking_score_threshold,
-        )?
+        match cached_docids {
+            // the cache can only be used if it contains the whole requested page
+            Some(docids)
+                if from + length <= docids.len() || docids.len() as u64 == universe.len() =>
+            {
+                let docids: Vec<_> = docids.into_iter().skip(from).take(length).collect();
+                BucketSortOutput {
+                    scores: vec![Default::default(); docids.len()],
+                    docids,
+                    all_candidates: universe.clone(),
+                    degraded: false,
+                }
+            }
+            _ => bucket_sort(
+                ctx,
+                ranking_rules,
+                &graph,
+                distinct.as_deref(),
+                &universe,
+                from,
+                length,
+                scoring_strategy,
+                query_graph_logger,
+                time_budget,
+                ranking_score_threshold,
+            )?,
+        }
     } else {
         let ranking_rules =
-            get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_strategy)?;
+            get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_param)?;
         bucket_sort(
             ctx,
             ranking_rules,
@@ -751,8 +1081,8 @@ pub fn execute_search(
     let BucketSortOutput { docids, scores, mut all_candidates, degraded } = bucket_sort_output;
     let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
 
-    // The candidates is the universe unless the exhaustive number of hits
-    // is requested and a distinct attribute is set.
+    // The candidates is the universe unless the exhaustive number of hits is
+    // requested and a distinct attribute is set or the results are diversified.
     if exhaustive_number_hits {
         let distinct_field = match distinct.as_deref() {
             Some(distinct) => Some(distinct),
@@ -764,6 +1094,11 @@ pub fn execute_search(
                 all_candidates = apply_distinct_rule(ctx, distinct_fid, &all_candidates)?.remaining;
             }
         }
+
+        if let Some((fid, max_per_value)) = ctx.diversify {
+            let mut diversification = Diversification::new(fid, max_per_value);
+            all_candidates = diversification.apply(ctx, &all_candidates)?.remaining;
+        }
     }
 
     Ok(PartialSearchResult {
@@ -771,11 +1106,85 @@ pub fn execute_search(
         document_scores: scores,
         documents_ids: docids,
         located_query_terms,
+        query_term_locations,
+        removed_stop_words,
         degraded,
         used_negative_operator,
+        query_truncated,
     })
 }
 
+/// Returns the locations of the terms of the query, marking the
+/// ones that are contained by at least one of the given documents.
+pub fn resolve_query_term_locations(
+    ctx: &mut SearchContext<'_>,
+    term_locations: Vec<(QueryTermLocation, Option<Interned<QueryTerm>>)>,
+    documents: &RoaringBitmap,
+) -> Result<Vec<QueryTermLocation>> {
+    term_locations
+        .into_iter()
+        .map(|(mut location, term)| {
+            // negative terms are never contained by the returned documents
+            if let Some(term) = term {
+                let subset = QueryTermSubset::full(term);
+                let docids = compute_query_term_subset_docids(ctx, Some(documents), &subset)?;
+                location.matched = !docids.is_empty();
+            }
+            Ok(location)
+        })
+        .collect()
+}
+
+/// Describes how the query was interpreted, marking the terms that the
+/// matching strategy made optional for some of the given documents.
+pub fn interpret_query(
+    ctx: &mut SearchContext<'_>,
+    term_locations: &[(QueryTermLocation, Option<Interned<QueryTerm>>)],
+    removed_stop_words: Vec<Range<usize>>,
+    documents: &RoaringBitmap,
+) -> Result<QueryInterpretation> {
+    let mut terms = Vec::with_capacity(term_locations.len());
+    for (location, term) in term_locations {
+        let mut interpreted = InterpretedQueryTerm {
+            byte_range: location.byte_range.clone(),
+            kind: location.kind,
+            max_typos: 0,
+            synonyms: Vec::new(),
+            dropped: false,
+        };
+        // negative terms have no derivations and are never part of the returned documents
+        if let Some(term) = *term {
+            let query_term = ctx.term_interner.get(term);
+            interpreted.max_typos = query_term.max_typos();
+            let synonyms: Vec<_> = query_term.synonyms().collect();
+            interpreted.synonyms =
+                synonyms.into_iter().map(|synonym| synonym.description(ctx)).collect();
+
+            let subset = QueryTermSubset::full(term);
+            let docids = compute_query_term_subset_docids(ctx, Some(documents), &subset)?;
+            interpreted.dropped = docids.len() < documents.len();
+        }
+        terms.push(interpreted);
+    }
+
+    Ok(QueryInterpretation { terms, removed_stop_words, ..Default::default() })
+}
+
+/// Returns the precomputed results of the query if it
+/// is made of a single prefix whose results are cached.
+fn cached_prefix_results(
+    ctx: &SearchContext<'_>,
+    query_terms: &[LocatedQueryTerm],
+) -> Result<Option<Vec<DocumentId>>> {
+    let [term] = query_terms else { return Ok(None) };
+    let term = ctx.term_interner.get(term.value);
+    // the cached results are the ones of a regular prefix search, allowing typos
+    if !term.is_prefix() || term.is_wildcard() {
+        return Ok(None);
+    }
+    Ok(ctx.index.prefix_cache_entry(ctx.txn, &term.original_word(ctx))?)
+}
+
 fn check_sort_criteria(
     ctx: &SearchContext<'_>,
     sort_criteria: Option<&Vec<AscDesc>>,
@@ -800,9 +1209,14 @@ fn check_sort_criteria(
     // We check that we are allowed to use the sort criteria, we check
     // that they are declared in the sortable fields.
     let sortable_fields = ctx.index.sortable_fields(ctx.txn)?;
+    // the computed fields are evaluated at search time and are always sortable
+    let computed_fields = ctx.index.computed_fields(ctx.txn)?;
     for asc_desc in sort_criteria {
         match asc_desc.member() {
-            Member::Field(ref field) if !crate::is_faceted(field, &sortable_fields) => {
+            Member::Field(ref field)
+                if !crate::is_faceted(field, &sortable_fields)
+                    && !computed_fields.contains_key(field) =>
+            {
                 let (valid_fields, hidden_fields) =
                     ctx.index.remove_hidden_fields(ctx.txn, sortable_fields)?;
 
@@ -813,6 +1227,23 @@ fn check_sort_criteria(
                 }
                 .into());
             }
+            Member::Expression(expression) => {
+                let unsortable = expression
+                    .fields()
+                    .into_iter()
+                    .find(|field| !crate::is_faceted(field, &sortable_fields));
+                if let Some(field) = unsortable {
+                    let (valid_fields, hidden_fields) =
+                        ctx.index.remove_hidden_fields(ctx.txn, sortable_fields)?;
+
+                    return Err(UserError::InvalidSortableAttribute {
+                        field: field.to_string(),
+                        valid_fields,
+                        hidden_fields,
+                    }
+                    .into());
+                }
+            }
             Member::Geo(_) if !sortable_fields.contains("_geo") => {
                 let (valid_fields, hidden_fields) =
                     ctx.index.remove_hidden_fields(ctx.txn, sortable_fields)?;
@@ -833,10 +1264,13 @@ fn check_sort_criteria(
 
 pub struct PartialSearchResult {
     pub located_query_terms: Option<Vec<LocatedQueryTerm>>,
+    pub query_term_locations: Vec<(QueryTermLocation, Option<Interned<QueryTerm>>)>,
+    pub removed_stop_words: Vec<Range<usize>>,
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
 
     pub degraded: bool,
     pub used_negative_operator: bool,
+    pub query_truncated: bool,
 }
@@ -0,0 +1,132 @@
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::score_details::{self, ScoreDetails};
+use crate::{DocumentId, Index, Result};
+
+/// Up to this number of candidates, their primary keys are read from the documents and sorted,
+/// beyond it the external ids, already sorted in their database, are scanned instead.
+const MAX_CANDIDATES_TO_READ: u64 = 1000;
+
+type ExternalIdsIter<'ctx> = Box<dyn Iterator<Item = Result<(String, DocumentId)>> + 'ctx>;
+
+/// Sorts the documents by their primary key, returning a single document per bucket.
+///
+/// It is the last ranking rule when the tie-breaker of the index is the primary key, so that
+/// the documents that all the other ranking rules consider equal keep the same order across
+/// reindexing. The primary keys are compared as strings.
+pub struct PrimaryKeySort<'ctx, Query> {
+    primary_key: String,
+    is_ascending: bool,
+    must_redact: bool,
+    original_query: Option<Query>,
+    iter: Option<ExternalIdsIter<'ctx>>,
+}
+
+impl<'ctx, Query> PrimaryKeySort<'ctx, Query> {
+    pub fn new(index: &Index, rtxn: &'ctx heed::RoTxn<'ctx>, is_ascending: bool) -> Result<Self> {
+        let primary_key = index.primary_key(rtxn)?.unwrap_or_default().to_string();
+        let must_redact = match index.displayed_fields(rtxn)? {
+            Some(displayed_fields) => !displayed_fields.iter().any(|&field| field == primary_key),
+            None => false,
+        };
+
+        Ok(Self { primary_key, is_ascending, must_redact, original_query: None, iter: None })
+    }
+
+    fn score(&self, value: serde_json::Value) -> ScoreDetails {
+        ScoreDetails::Sort(score_details::Sort {
+            field_name: self.primary_key.clone(),
+            ascending: self.is_ascending,
+            redacted: self.must_redact,
+            value,
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for PrimaryKeySort<'ctx, Query> {
+    fn id(&self) -> String {
+        let Self { primary_key, is_ascending, .. } = self;
+        format!("{primary_key}:{}", if *is_ascending { "asc" } else { "desc" })
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::primary_key_sort")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let iter: ExternalIdsIter<'ctx> = if parent_candidates.is_empty() {
+            Box::new(std::iter::empty())
+        } else if parent_candidates.len() <= MAX_CANDIDATES_TO_READ {
+            let external_ids = ctx.index.external_id_of(ctx.txn, parent_candidates.iter())?;
+            let mut external_ids: Vec<_> = external_ids
+                .into_iter()
+                .zip(parent_candidates.iter())
+                .map(|(external_id, docid)| external_id.map(|external_id| (external_id, docid)))
+                .collect::<Result<_>>()?;
+            if self.is_ascending {
+                external_ids.sort_unstable();
+            } else {
+                external_ids.sort_unstable_by(|left, right| right.cmp(left));
+            }
+            Box::new(external_ids.into_iter().map(Ok))
+        } else {
+            let external_documents_ids = ctx.index.external_documents_ids();
+            let iter = if self.is_ascending {
+                itertools::Either::Left(external_documents_ids.iter(ctx.txn)?)
+            } else {
+                itertools::Either::Right(external_documents_ids.rev_iter(ctx.txn)?)
+            };
+            let candidates = parent_candidates.clone();
+            Box::new(iter.filter_map(move |result| match result {
+                Ok((external_id, docid)) => {
+                    candidates.contains(docid).then(|| Ok((external_id.to_string(), docid)))
+                }
+                Err(error) => Some(Err(error.into())),
+            }))
+        };
+
+        self.original_query = Some(parent_query.clone());
+        self.iter = Some(iter);
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::primary_key_sort")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        while let Some(result) = self.iter.as_mut().unwrap().next() {
+            let (external_id, docid) = result?;
+            if universe.contains(docid) {
+                return Ok(Some(RankingRuleOutput {
+                    query,
+                    candidates: RoaringBitmap::from_iter([docid]),
+                    score: self.score(serde_json::Value::String(external_id)),
+                }));
+            }
+        }
+        Ok(Some(RankingRuleOutput {
+            query,
+            candidates: universe.clone(),
+            score: self.score(serde_json::Value::Null),
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::primary_key_sort")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.iter = None;
+    }
+}
@@ -113,12 +113,17 @@ impl QueryGraph {
         for term_idx in 0..original_terms_len {
             let mut new_nodes = vec![];
 
+            let mut term_subset = QueryTermSubset::full(terms[term_idx].value);
+            if terms[term_idx].mandatory {
+                term_subset.make_mandatory();
+            }
             let new_node_idx = add_node(
                 &mut nodes_data,
                 QueryNodeData::Term(LocatedQueryTermSubset {
-                    term_subset: QueryTermSubset::full(terms[term_idx].value),
+                    term_subset,
                     positions: terms[term_idx].positions.clone(),
                     term_ids: term_idx as u8..=term_idx as u8,
+                    boost: terms[term_idx].boost,
                 }),
             );
             new_nodes.push(new_node_idx);
@@ -134,6 +139,7 @@ impl QueryGraph {
                             term_subset: QueryTermSubset::full(ngram.value),
                             positions: ngram.positions,
                             term_ids: term_idx as u8 - 1..=term_idx as u8,
+                            boost: ngram.boost,
                         }),
                     );
                     new_nodes.push(ngram_idx);
@@ -150,6 +156,7 @@ impl QueryGraph {
                             term_subset: QueryTermSubset::full(ngram.value),
                             positions: ngram.positions,
                             term_ids: term_idx as u8 - 2..=term_idx as u8,
+                            boost: ngram.boost,
                         }),
                     );
                     new_nodes.push(ngram_idx);
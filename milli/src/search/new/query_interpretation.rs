@@ -0,0 +1,32 @@
+use std::ops::Range;
+
+use super::QueryTermKind;
+use crate::query_rewrites::AppliedQueryRewrite;
+
+/// How the engine interpreted a search query.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryInterpretation {
+    /// The terms of the query, in the order they appear.
+    pub terms: Vec<InterpretedQueryTerm>,
+    /// The byte offsets of the stop words that were removed from the query.
+    pub removed_stop_words: Vec<Range<usize>>,
+    /// The query rewritten by the `queryRewrites` rules of the index, if any of them matched.
+    /// The byte offsets of the terms and stop words are then given in this query.
+    pub rewritten_query: Option<String>,
+    /// The rewrites applied to the query, in order.
+    pub rewrites: Vec<AppliedQueryRewrite>,
+}
+
+/// How a single term of the query was interpreted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterpretedQueryTerm {
+    /// The byte offsets of the term in the query, without the quotes of the phrases.
+    pub byte_range: Range<usize>,
+    pub kind: QueryTermKind,
+    /// The maximum number of typos allowed on the term.
+    pub max_typos: u8,
+    /// The synonyms of the term that were searched for along with it.
+    pub synonyms: Vec<String>,
+    /// Whether the matching strategy made the term optional for some of the returned documents.
+    pub dropped: bool,
+}
@@ -7,12 +7,15 @@ use fst::{Automaton, IntoStreamer, Streamer};
 use heed::types::DecodeIgnore;
 
 use super::{OneTypoTerm, Phrase, QueryTerm, ZeroTypoTerm};
+use crate::phonetic::phonetic_words;
+use crate::regex_filter::compile_regex;
 use crate::search::fst_utils::{Complement, Intersection, StartsWith, Union};
 use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::{Lazy, TwoTypoTerm};
 use crate::search::new::{limits, SearchContext};
 use crate::search::{build_dfa, get_first};
-use crate::{Result, MAX_WORD_LENGTH};
+use crate::typo::{is_transposition, swap_first_characters, FirstLetterTypos};
+use crate::{Result, UserError, MAX_WORD_LENGTH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NumberOfTypos {
@@ -108,12 +111,36 @@ fn find_zero_one_typo_derivations(
             }
         }
     }
+
+    // the words starting with the first two characters of the word swapped are not
+    // found by the automaton, which only looks for the words starting like the word
+    if let Some(swapped) = swap_first_characters(word) {
+        let mut derived_words = Vec::new();
+        if is_prefix {
+            let mut stream = fst.search(Str::new(&swapped).starts_with()).into_stream();
+            while let Some(derived_word) = stream.next() {
+                derived_words.push(std::str::from_utf8(derived_word)?.to_owned());
+                if derived_words.len() >= limits::MAX_ONE_TYPO_COUNT {
+                    break;
+                }
+            }
+        } else if fst.contains(&swapped) {
+            derived_words.push(swapped);
+        }
+        for derived_word in derived_words {
+            let derived_word = ctx.word_interner.insert(derived_word);
+            if visit(derived_word, ZeroOrOneTypo::One)?.is_break() {
+                break;
+            }
+        }
+    }
     Ok(())
 }
 
 fn find_zero_one_two_typo_derivations(
     word_interned: Interned<String>,
     is_prefix: bool,
+    first_letter_typos: FirstLetterTypos,
     fst: fst::Set<Cow<'_, [u8]>>,
     word_interner: &mut DedupInterner<String>,
     mut visit: impl FnMut(Interned<String>, NumberOfTypos) -> Result<ControlFlow<()>>,
@@ -133,8 +160,18 @@ fn find_zero_one_two_typo_derivations(
         let derived_word = std::str::from_utf8(derived_word)?;
         let derived_word_interned = word_interner.insert(derived_word.to_owned());
         // in the case the typo is on the first letter, we know the number of typo
-        // is two
+        // is two, unless the first two letters are only swapped
         if get_first(derived_word) != get_first(word) {
+            if is_transposition(word, derived_word, is_prefix) {
+                let cf = visit(derived_word_interned, NumberOfTypos::One)?;
+                if cf.is_break() {
+                    break;
+                }
+                continue;
+            }
+            if first_letter_typos == FirstLetterTypos::Disallowed {
+                continue;
+            }
             let cf = visit(derived_word_interned, NumberOfTypos::Two)?;
             if cf.is_break() {
                 break;
@@ -186,17 +223,21 @@ pub fn partially_initialized_term_from_word(
                 original: ctx.word_interner.insert(word.to_owned()),
                 ngram_words: None,
                 is_prefix: false,
+                is_wildcard: false,
                 max_levenshtein_distance: 0,
                 zero_typo: <_>::default(),
                 one_typo: Lazy::Init(<_>::default()),
                 two_typo: Lazy::Init(<_>::default()),
+                phonetic: BTreeSet::new(),
             }
         });
     }
 
     let fst = ctx.index.words_fst(ctx.txn)?;
 
+    // the prefix databases also hold the documents of the blocked words starting with the prefix
     let use_prefix_db = is_prefix
+        && !ctx.index.blocked_terms(ctx.txn)?.terms.iter().any(|term| term.starts_with(word))
         && (ctx
             .index
             .word_prefix_docids
@@ -234,12 +275,11 @@ pub fn partially_initialized_term_from_word(
             },
         )?;
     }
-    let synonyms = ctx.index.synonyms(ctx.txn)?;
+    remove_underivable_words(ctx, &mut prefix_of)?;
+
+    let synonyms = ctx.synonyms_of(&[word.to_owned()])?;
     let mut synonym_word_count = 0;
     let synonyms = synonyms
-        .get(&vec![word.to_owned()])
-        .cloned()
-        .unwrap_or_default()
         .into_iter()
         .take(limits::MAX_SYNONYM_PHRASE_COUNT)
         .filter_map(|words| {
@@ -248,26 +288,205 @@ pub fn partially_initialized_term_from_word(
             }
             synonym_word_count += words.len();
             let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            Some(ctx.phrase_interner.insert(Phrase { words }))
+            Some(ctx.phrase_interner.insert(Phrase { words, is_prefix: false, slop: 0 }))
         })
         .collect();
     let zero_typo =
         ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, use_prefix_db };
 
+    let phonetic = if is_ngram { BTreeSet::new() } else { find_phonetic_derivations(ctx, word)? };
+
     Ok(QueryTerm {
         original: word_interned,
         ngram_words: None,
         max_levenshtein_distance: max_typo,
         is_prefix,
+        is_wildcard: false,
         zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        phonetic,
+    })
+}
+
+/// Returns the words of the index sounding like the given word, when the phonetic matching
+/// is enabled. Like the typos, it is disabled on the words listed in `disableOnWords`.
+fn find_phonetic_derivations(
+    ctx: &mut SearchContext<'_>,
+    word: &str,
+) -> Result<BTreeSet<Interned<String>>> {
+    let mut derivations = BTreeSet::new();
+    let Some(phonetic_fst) = ctx.index.phonetic_words_fst(ctx.txn)? else {
+        return Ok(derivations);
+    };
+    if !ctx.index.authorize_typos(ctx.txn)?
+        || ctx.index.exact_words(ctx.txn)?.map_or(false, |exact| exact.contains(word))
+    {
+        return Ok(derivations);
+    }
+
+    for derived_word in phonetic_words(&phonetic_fst, word) {
+        if derived_word != word {
+            derivations.insert(ctx.word_interner.insert(derived_word));
+            if derivations.len() >= limits::MAX_TWO_TYPOS_COUNT {
+                break;
+            }
+        }
+    }
+    remove_underivable_words(ctx, &mut derivations)?;
+    Ok(derivations)
+}
+
+/// Where the `*` wildcards surrounding a word of the query are, e.g. `run*`, `*ing` or `*un*`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wildcards {
+    pub leading: bool,
+    pub trailing: bool,
+}
+
+/// Builds the term of a word surrounded by `*` wildcards, wherever it is in the query.
+///
+/// Its derivations are all the words of the index starting, ending with, or containing the
+/// word, without any typo. They are always expanded, the prefix databases are never used.
+pub fn wildcard_term_from_word(
+    ctx: &mut SearchContext<'_>,
+    word: &str,
+    wildcards: Wildcards,
+) -> Result<QueryTerm> {
+    let word_interned = ctx.word_interner.insert(word.to_owned());
+    let fst = ctx.index.words_fst(ctx.txn)?;
+    let exact = if fst.contains(word) { Some(word_interned) } else { None };
+
+    let mut derivations = BTreeSet::new();
+    if wildcards.leading {
+        // the words can't be looked up by their end, the whole dictionary is scanned
+        let mut stream = fst.stream();
+        while let Some(derived_word) = stream.next() {
+            let derived_word = std::str::from_utf8(derived_word)?;
+            let matches = if wildcards.trailing {
+                derived_word.contains(word)
+            } else {
+                derived_word.ends_with(word)
+            };
+            if matches && derived_word != word {
+                derivations.insert(ctx.word_interner.insert(derived_word.to_owned()));
+                if derivations.len() >= limits::MAX_PREFIX_COUNT {
+                    break;
+                }
+            }
+        }
+    } else {
+        find_zero_typo_prefix_derivations(
+            word_interned,
+            fst,
+            &mut ctx.word_interner,
+            |derived_word| {
+                if derivations.len() < limits::MAX_PREFIX_COUNT {
+                    derivations.insert(derived_word);
+                    Ok(ControlFlow::Continue(()))
+                } else {
+                    Ok(ControlFlow::Break(()))
+                }
+            },
+        )?;
+    }
+    remove_underivable_words(ctx, &mut derivations)?;
+
+    Ok(QueryTerm {
+        original: word_interned,
+        ngram_words: None,
+        max_levenshtein_distance: 0,
+        // the words only ending with, or containing the word, aren't highlighted as prefixes
+        is_prefix: !wildcards.leading,
+        is_wildcard: true,
+        zero_typo: ZeroTypoTerm {
+            phrase: None,
+            exact,
+            prefix_of: derivations,
+            synonyms: BTreeSet::new(),
+            use_prefix_db: None,
+        },
+        one_typo: Lazy::Uninit,
+        two_typo: Lazy::Uninit,
+        phonetic: BTreeSet::new(),
+    })
+}
+
+/// Builds the term of a quoted regular expression, when the query is searched with the
+/// `regex` terms matching strategy.
+///
+/// Its derivations are all the words of the index entirely matched by the regular expression,
+/// without any typo. The words can't be looked up by a regular expression, the whole dictionary
+/// is scanned.
+pub fn regex_term_from_pattern(ctx: &mut SearchContext<'_>, pattern: &str) -> Result<QueryTerm> {
+    let regex = compile_regex(&format!("^(?:{pattern})$")).map_err(|error| {
+        UserError::InvalidQueryRegex { pattern: pattern.to_owned(), error: error.to_string() }
+    })?;
+    let pattern_interned = ctx.word_interner.insert(pattern.to_owned());
+    let fst = ctx.index.words_fst(ctx.txn)?;
+
+    let mut derivations = BTreeSet::new();
+    let mut stream = fst.stream();
+    while let Some(derived_word) = stream.next() {
+        let derived_word = std::str::from_utf8(derived_word)?;
+        if regex.is_match(derived_word) {
+            derivations.insert(ctx.word_interner.insert(derived_word.to_owned()));
+            if derivations.len() >= limits::MAX_PREFIX_COUNT {
+                break;
+            }
+        }
+    }
+    remove_underivable_words(ctx, &mut derivations)?;
+
+    Ok(QueryTerm {
+        original: pattern_interned,
+        ngram_words: None,
+        max_levenshtein_distance: 0,
+        is_prefix: false,
+        is_wildcard: true,
+        zero_typo: ZeroTypoTerm {
+            phrase: None,
+            exact: None,
+            prefix_of: derivations,
+            synonyms: BTreeSet::new(),
+            use_prefix_db: None,
+        },
+        one_typo: Lazy::Uninit,
+        two_typo: Lazy::Uninit,
+        phonetic: BTreeSet::new(),
     })
 }
 
+/// Remove the literal words from the given derivations, as literal words only match themselves,
+/// and the blocked words, which are never searched.
+fn remove_underivable_words(
+    ctx: &SearchContext<'_>,
+    derivations: &mut BTreeSet<Interned<String>>,
+) -> Result<()> {
+    if derivations.is_empty() {
+        return Ok(());
+    }
+    if let Some(literal_words) = ctx.index.literal_words_fst(ctx.txn)? {
+        derivations.retain(|word| !literal_words.contains(ctx.word_interner.get(*word)));
+    }
+    let blocked_terms = ctx.index.blocked_terms(ctx.txn)?;
+    if !blocked_terms.terms.is_empty() {
+        derivations.retain(|word| !blocked_terms.contains(ctx.word_interner.get(*word)));
+    }
+    Ok(())
+}
+
 fn find_split_words(ctx: &mut SearchContext<'_>, word: &str) -> Result<Option<Interned<Phrase>>> {
+    if ctx.index.literal_words_fst(ctx.txn)?.map_or(false, |fst| fst.contains(word)) {
+        return Ok(None);
+    }
     if let Some((l, r)) = split_best_frequency(ctx, word)? {
-        Ok(Some(ctx.phrase_interner.insert(Phrase { words: vec![Some(l), Some(r)] })))
+        let blocked_terms = ctx.index.blocked_terms(ctx.txn)?;
+        if [l, r].iter().any(|word| blocked_terms.contains(ctx.word_interner.get(*word))) {
+            return Ok(None);
+        }
+        let words = vec![Some(l), Some(r)];
+        Ok(Some(ctx.phrase_interner.insert(Phrase { words, is_prefix: false, slop: 0 })))
     } else {
         Ok(None)
     }
@@ -310,6 +529,8 @@ impl Interned<QueryTerm> {
             })?;
         }
 
+        remove_underivable_words(ctx, &mut one_typo_words)?;
+
         let split_words = if allows_split_words {
             let original_str = ctx.word_interner.get(original).to_owned();
             find_split_words(ctx, original_str.as_str())?
@@ -317,6 +538,7 @@ impl Interned<QueryTerm> {
             None
         };
 
+        let transpositions = find_transpositions(ctx, original, is_prefix, &one_typo_words);
         let self_mut = ctx.term_interner.get_mut(self);
 
         // Only add the split words to the derivations if:
@@ -325,7 +547,7 @@ impl Interned<QueryTerm> {
         let split_words = if let Some((ngram_words, split_words)) =
             self_mut.ngram_words.as_ref().zip(split_words.as_ref())
         {
-            let Phrase { words } = ctx.phrase_interner.get(*split_words);
+            let Phrase { words, .. } = ctx.phrase_interner.get(*split_words);
             if ngram_words.iter().ne(words.iter().flatten()) {
                 Some(*split_words)
             } else {
@@ -334,7 +556,7 @@ impl Interned<QueryTerm> {
         } else {
             split_words
         };
-        let one_typo = OneTypoTerm { split_words, one_typo: one_typo_words };
+        let one_typo = OneTypoTerm { split_words, one_typo: one_typo_words, transpositions };
 
         self_mut.one_typo = Lazy::Init(one_typo);
 
@@ -360,6 +582,7 @@ impl Interned<QueryTerm> {
             find_zero_one_two_typo_derivations(
                 *original,
                 *is_prefix,
+                ctx.index.first_letter_typos(ctx.txn)?,
                 ctx.index.words_fst(ctx.txn)?,
                 &mut ctx.word_interner,
                 |derived_word, nbr_typos| {
@@ -387,10 +610,14 @@ impl Interned<QueryTerm> {
             )?;
         }
 
+        remove_underivable_words(ctx, &mut one_typo_words)?;
+        remove_underivable_words(ctx, &mut two_typo_words)?;
+
         let split_words = find_split_words(ctx, original_str.as_str())?;
+        let transpositions = find_transpositions(ctx, original, is_prefix, &one_typo_words);
         let self_mut = ctx.term_interner.get_mut(self);
 
-        let one_typo = OneTypoTerm { one_typo: one_typo_words, split_words };
+        let one_typo = OneTypoTerm { one_typo: one_typo_words, split_words, transpositions };
 
         let two_typo = TwoTypoTerm { two_typos: two_typo_words };
 
@@ -401,6 +628,21 @@ impl Interned<QueryTerm> {
     }
 }
 
+/// Returns the one-typo derivations that only swap two adjacent characters of the original word.
+fn find_transpositions(
+    ctx: &SearchContext<'_>,
+    original: Interned<String>,
+    is_prefix: bool,
+    one_typo_words: &BTreeSet<Interned<String>>,
+) -> BTreeSet<Interned<String>> {
+    let original = ctx.word_interner.get(original);
+    one_typo_words
+        .iter()
+        .copied()
+        .filter(|&word| is_transposition(original, ctx.word_interner.get(word), is_prefix))
+        .collect()
+}
+
 /// Split the original word into the two words that appear the
 /// most next to each other in the index.
 ///
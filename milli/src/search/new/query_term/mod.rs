@@ -11,11 +11,14 @@ use either::Either;
 pub use ntypo_subset::NTypoTermSubset;
 pub use parse_query::{
     located_query_terms_from_tokens, make_ngram, number_of_typos_allowed, ExtractedTokens,
+    QueryTermKind, QueryTermLocation,
 };
 pub use phrase::Phrase;
 
 use super::interner::{DedupInterner, Interned};
 use super::{limits, SearchContext, Word};
+use crate::search::get_first;
+use crate::typo::HalfTypos;
 use crate::Result;
 
 /// A set of word derivations attached to a location in the search query.
@@ -24,6 +27,7 @@ pub struct LocatedQueryTermSubset {
     pub term_subset: QueryTermSubset,
     pub positions: RangeInclusive<u16>,
     pub term_ids: RangeInclusive<u8>,
+    pub boost: Boost,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -46,11 +50,15 @@ pub struct QueryTerm {
     ngram_words: Option<Vec<Interned<String>>>,
     max_levenshtein_distance: u8,
     is_prefix: bool,
+    /// Whether the term is a word surrounded by `*` wildcards, e.g. `*ing`
+    is_wildcard: bool,
     zero_typo: ZeroTypoTerm,
     // May not be computed yet
     one_typo: Lazy<OneTypoTerm>,
     // May not be computed yet
     two_typo: Lazy<TwoTypoTerm>,
+    /// Words sounding like the original word, ranked like the two-typo derivations
+    phonetic: BTreeSet<Interned<String>>,
 }
 
 // SubTerms will be in a dedup interner
@@ -60,7 +68,7 @@ struct ZeroTypoTerm {
     phrase: Option<Interned<Phrase>>,
     /// A single word equivalent to the original term, with zero typos
     exact: Option<Interned<String>>,
-    /// All the words that contain the original word as prefix
+    /// All the words that contain the original word as prefix, or that match its wildcards
     prefix_of: BTreeSet<Interned<String>>,
     /// All the synonyms of the original word or phrase
     synonyms: BTreeSet<Interned<Phrase>>,
@@ -73,6 +81,9 @@ struct OneTypoTerm {
     split_words: Option<Interned<Phrase>>,
     /// Words that are 1 typo away from the original word
     one_typo: BTreeSet<Interned<String>>,
+    /// The words of `one_typo` that only swap two adjacent characters of the original word,
+    /// kept apart for debugging purposes
+    transpositions: BTreeSet<Interned<String>>,
 }
 #[derive(Default, Clone, PartialEq, Eq, Hash)]
 struct TwoTypoTerm {
@@ -142,6 +153,11 @@ impl QueryTermSubset {
         }
     }
 
+    /// Returns `true` if the term matches, without typos, the words it is a prefix of.
+    pub fn is_prefix(&self, ctx: &SearchContext<'_>) -> bool {
+        ctx.term_interner.get(self.original).is_prefix()
+    }
+
     pub fn empty(for_term: Interned<QueryTerm>) -> Self {
         Self {
             original: for_term,
@@ -260,14 +276,16 @@ impl QueryTermSubset {
 
         match &self.one_typo_subset {
             NTypoTermSubset::All => {
-                let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words: _, one_typo, transpositions: _ }) =
+                    &original.one_typo
                 else {
                     panic!()
                 };
                 result.extend(one_typo.iter().copied().map(Word::Derived))
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
-                let Lazy::Init(OneTypoTerm { split_words: _, one_typo }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words: _, one_typo, transpositions: _ }) =
+                    &original.one_typo
                 else {
                     panic!()
                 };
@@ -280,10 +298,12 @@ impl QueryTermSubset {
             NTypoTermSubset::All => {
                 let Lazy::Init(TwoTypoTerm { two_typos }) = &original.two_typo else { panic!() };
                 result.extend(two_typos.iter().copied().map(Word::Derived));
+                result.extend(original.phonetic.iter().copied().map(Word::Derived));
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
                 let Lazy::Init(TwoTypoTerm { two_typos }) = &original.two_typo else { panic!() };
                 result.extend(two_typos.intersection(words).copied().map(Word::Derived));
+                result.extend(original.phonetic.intersection(words).copied().map(Word::Derived));
             }
             NTypoTermSubset::Nothing => {}
         };
@@ -305,14 +325,16 @@ impl QueryTermSubset {
 
         match &self.one_typo_subset {
             NTypoTermSubset::All => {
-                let Lazy::Init(OneTypoTerm { split_words, one_typo: _ }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words, one_typo: _, transpositions: _ }) =
+                    &original.one_typo
                 else {
                     panic!();
                 };
                 result.extend(split_words.iter().copied());
             }
             NTypoTermSubset::Subset { phrases, .. } => {
-                let Lazy::Init(OneTypoTerm { split_words, one_typo: _ }) = &original.one_typo
+                let Lazy::Init(OneTypoTerm { split_words, one_typo: _, transpositions: _ }) =
+                    &original.one_typo
                 else {
                     panic!();
                 };
@@ -327,6 +349,19 @@ impl QueryTermSubset {
 
         Ok(result)
     }
+    /// The one-typo words of the subset that only swap two adjacent characters of the
+    /// original word, if the one-typo derivations of the term were computed.
+    pub fn transpositions(&self, ctx: &SearchContext<'_>) -> BTreeSet<Interned<String>> {
+        let original = ctx.term_interner.get(self.original);
+        let Lazy::Init(OneTypoTerm { transpositions, .. }) = &original.one_typo else {
+            return BTreeSet::new();
+        };
+        transpositions
+            .iter()
+            .copied()
+            .filter(|&word| self.one_typo_subset.contains_word(word))
+            .collect()
+    }
 
     pub fn original_phrase(&self, ctx: &SearchContext<'_>) -> Option<Interned<Phrase>> {
         let t = ctx.term_interner.get(self.original);
@@ -339,6 +374,10 @@ impl QueryTermSubset {
     }
     pub fn max_typo_cost(&self, ctx: &SearchContext<'_>) -> u8 {
         let t = ctx.term_interner.get(self.original);
+        // the phonetic derivations cost two typos, whatever the typos allowed on the word
+        if !t.phonetic.is_empty() && !self.two_typo_subset.is_empty() {
+            return 2;
+        }
         match t.max_levenshtein_distance {
             0 => {
                 if t.allows_split_words() {
@@ -368,6 +407,159 @@ impl QueryTermSubset {
             _ => panic!(),
         }
     }
+    /// Split the one-typo derivations of the subset between the half typos and the regular typos.
+    ///
+    /// Returns the subset containing only the half-typo derivations and the subset containing
+    /// the remaining one-typo derivations, or `None` if none of the derivations is a half typo.
+    pub fn split_half_typos(
+        &self,
+        ctx: &mut SearchContext<'_>,
+        half_typos: &HalfTypos,
+    ) -> Result<Option<(Self, Self)>> {
+        if !half_typos.is_enabled() || self.one_typo_subset.is_empty() {
+            return Ok(None);
+        }
+        self.original.compute_fully_if_needed(ctx)?;
+
+        let original = ctx.term_interner.get(self.original);
+        let Lazy::Init(OneTypoTerm { split_words, one_typo, transpositions: _ }) =
+            &original.one_typo
+        else {
+            panic!()
+        };
+        let original_word = ctx.word_interner.get(original.original);
+
+        let mut half_typo_words = BTreeSet::new();
+        let mut full_typo_words = BTreeSet::new();
+        for &word in one_typo {
+            if !self.one_typo_subset.contains_word(word) {
+                continue;
+            }
+            let derivation = ctx.word_interner.get(word);
+            if half_typos.is_half_typo(original_word, derivation, original.is_prefix) {
+                half_typo_words.insert(word);
+            } else {
+                full_typo_words.insert(word);
+            }
+        }
+        if half_typo_words.is_empty() {
+            return Ok(None);
+        }
+        let full_typo_phrases = split_words
+            .iter()
+            .copied()
+            .filter(|&phrase| self.one_typo_subset.contains_phrase(phrase))
+            .collect();
+
+        let mut half = self.clone();
+        half.clear_zero_typo_subset();
+        half.clear_two_typo_subset();
+        half.one_typo_subset =
+            NTypoTermSubset::Subset { words: half_typo_words, phrases: BTreeSet::new() };
+
+        let mut full = half.clone();
+        full.one_typo_subset =
+            NTypoTermSubset::Subset { words: full_typo_words, phrases: full_typo_phrases };
+
+        Ok(Some((half, full)))
+    }
+    /// Split the one-typo derivations of the subset between the words with one typo and the
+    /// split words, i.e. the original word split into consecutive words.
+    ///
+    /// Returns the subset containing the words with one typo, if any, and the subset containing
+    /// the split words, or `None` if the subset contains no split words.
+    pub fn split_split_words(
+        &self,
+        ctx: &mut SearchContext<'_>,
+    ) -> Result<Option<(Option<Self>, Self)>> {
+        if self.one_typo_subset.is_empty() {
+            return Ok(None);
+        }
+        self.original.compute_fully_if_needed(ctx)?;
+
+        let original = ctx.term_interner.get(self.original);
+        let Lazy::Init(OneTypoTerm { split_words, one_typo, transpositions: _ }) =
+            &original.one_typo
+        else {
+            panic!()
+        };
+        let Some(split_words) =
+            split_words.filter(|&phrase| self.one_typo_subset.contains_phrase(phrase))
+        else {
+            return Ok(None);
+        };
+        let one_typo_words: BTreeSet<_> = one_typo
+            .iter()
+            .copied()
+            .filter(|&word| self.one_typo_subset.contains_word(word))
+            .collect();
+
+        let mut split = self.clone();
+        split.clear_zero_typo_subset();
+        split.clear_two_typo_subset();
+        split.one_typo_subset = NTypoTermSubset::Subset {
+            words: BTreeSet::new(),
+            phrases: BTreeSet::from_iter([split_words]),
+        };
+
+        let typos = if one_typo_words.is_empty() {
+            None
+        } else {
+            let mut typos = split.clone();
+            typos.one_typo_subset =
+                NTypoTermSubset::Subset { words: one_typo_words, phrases: BTreeSet::new() };
+            Some(typos)
+        };
+
+        Ok(Some((typos, split)))
+    }
+    /// Split the two-typo derivations of the subset between the ones with a typo on the
+    /// first letter and the other ones.
+    ///
+    /// Returns the subset containing the two-typo derivations with a correct first letter and
+    /// the subset containing the remaining ones, or `None` if the first letter of every
+    /// derivation is correct.
+    pub fn split_first_letter_typos(
+        &self,
+        ctx: &mut SearchContext<'_>,
+    ) -> Result<Option<(Self, Self)>> {
+        if self.two_typo_subset.is_empty() {
+            return Ok(None);
+        }
+        self.original.compute_fully_if_needed(ctx)?;
+
+        let original = ctx.term_interner.get(self.original);
+        let Lazy::Init(TwoTypoTerm { two_typos }) = &original.two_typo else { panic!() };
+        let original_first = get_first(ctx.word_interner.get(original.original));
+
+        let mut first_letter_words = BTreeSet::new();
+        let mut other_words = BTreeSet::new();
+        for &word in two_typos.iter().chain(&original.phonetic) {
+            if !self.two_typo_subset.contains_word(word) {
+                continue;
+            }
+            if get_first(ctx.word_interner.get(word)) == original_first {
+                other_words.insert(word);
+            } else {
+                first_letter_words.insert(word);
+            }
+        }
+        if first_letter_words.is_empty() {
+            return Ok(None);
+        }
+
+        let mut others = self.clone();
+        others.clear_zero_typo_subset();
+        others.clear_one_typo_subset();
+        others.two_typo_subset =
+            NTypoTermSubset::Subset { words: other_words, phrases: BTreeSet::new() };
+
+        let mut first_letter = others.clone();
+        first_letter.two_typo_subset =
+            NTypoTermSubset::Subset { words: first_letter_words, phrases: BTreeSet::new() };
+
+        Ok(Some((others, first_letter)))
+    }
     pub fn keep_only_exact_term(&mut self, ctx: &SearchContext<'_>) {
         if let Some(term) = self.exact_term(ctx) {
             match term {
@@ -417,7 +609,7 @@ impl ZeroTypoTerm {
 }
 impl OneTypoTerm {
     fn is_empty(&self) -> bool {
-        let OneTypoTerm { split_words, one_typo } = self;
+        let OneTypoTerm { split_words, one_typo, transpositions: _ } = self;
         one_typo.is_empty() && split_words.is_none()
     }
 }
@@ -437,10 +629,13 @@ impl QueryTerm {
             return false;
         };
 
-        self.zero_typo.is_empty() && one_typo.is_empty() && two_typo.is_empty()
+        self.zero_typo.is_empty()
+            && one_typo.is_empty()
+            && two_typo.is_empty()
+            && self.phonetic.is_empty()
     }
     fn allows_split_words(&self) -> bool {
-        self.zero_typo.phrase.is_none()
+        self.zero_typo.phrase.is_none() && !self.is_wildcard
     }
 }
 
@@ -461,6 +656,39 @@ impl Interned<QueryTerm> {
 pub struct LocatedQueryTerm {
     pub value: Interned<QueryTerm>,
     pub positions: RangeInclusive<u16>,
+    /// Whether the term can't be removed by the terms matching strategy,
+    /// e.g. because it is joined to another term with the `AND` operator.
+    pub mandatory: bool,
+    pub boost: Boost,
+}
+
+/// The factor the ranking costs of a term are multiplied by, e.g. `2.5` for `rust^2.5`,
+/// so that the attribute and proximity of a boosted term weigh more than the ones of the others.
+///
+/// It is stored in hundredths so that the terms can still be hashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Boost(u16);
+
+impl Boost {
+    /// Returns the boost of the given factor, if it is strictly positive.
+    pub fn new(factor: f32) -> Option<Self> {
+        if !factor.is_finite() || factor <= 0.0 {
+            return None;
+        }
+        let hundredths = (factor.min(limits::MAX_TERM_BOOST) * 100.0).round().max(1.0);
+        Some(Boost(hundredths as u16))
+    }
+
+    /// Returns the given ranking cost multiplied by the boost, rounded to the nearest integer.
+    pub fn apply(&self, cost: u32) -> u32 {
+        ((cost as u64 * self.0 as u64 + 50) / 100) as u32
+    }
+}
+
+impl Default for Boost {
+    fn default() -> Self {
+        Boost(100)
+    }
 }
 
 impl LocatedQueryTerm {
@@ -477,6 +705,9 @@ impl QueryTerm {
     pub fn is_prefix(&self) -> bool {
         self.is_prefix
     }
+    pub fn is_wildcard(&self) -> bool {
+        self.is_wildcard
+    }
     pub fn original_word(&self, ctx: &SearchContext<'_>) -> String {
         ctx.word_interner.get(self.original).clone()
     }
@@ -485,6 +716,16 @@ impl QueryTerm {
         self.zero_typo.phrase
     }
 
+    /// The maximum number of typos allowed on the term.
+    pub fn max_typos(&self) -> u8 {
+        self.max_levenshtein_distance
+    }
+
+    /// The synonyms of the term, as defined in the settings or given with the query.
+    pub fn synonyms(&self) -> impl Iterator<Item = Interned<Phrase>> + '_ {
+        self.zero_typo.synonyms.iter().copied()
+    }
+
     pub fn all_computed_derivations(&self) -> (Vec<Interned<String>>, Vec<Interned<Phrase>>) {
         let mut words = BTreeSet::new();
         let mut phrases = BTreeSet::new();
@@ -496,7 +737,8 @@ impl QueryTerm {
         phrases.extend(phrase.iter().copied());
         phrases.extend(synonyms.iter().copied());
 
-        if let Lazy::Init(OneTypoTerm { split_words, one_typo }) = &self.one_typo {
+        if let Lazy::Init(OneTypoTerm { split_words, one_typo, transpositions: _ }) = &self.one_typo
+        {
             words.extend(one_typo.iter().copied());
             phrases.extend(split_words.iter().copied());
         };
@@ -504,6 +746,7 @@ impl QueryTerm {
         if let Lazy::Init(TwoTypoTerm { two_typos }) = &self.two_typo {
             words.extend(two_typos.iter().copied());
         };
+        words.extend(self.phonetic.iter().copied());
 
         (words.into_iter().collect(), phrases.into_iter().collect())
     }
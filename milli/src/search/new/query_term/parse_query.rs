@@ -1,13 +1,19 @@
+use std::borrow::Cow;
 use std::collections::BTreeSet;
+use std::ops::Range;
 
 use charabia::normalizer::NormalizedTokenIter;
 use charabia::{SeparatorKind, TokenKind};
 
-use super::compute_derivations::partially_initialized_term_from_word;
-use super::{LocatedQueryTerm, ZeroTypoTerm};
+use super::compute_derivations::{
+    partially_initialized_term_from_word, regex_term_from_pattern, wildcard_term_from_word,
+    Wildcards,
+};
+use super::{Boost, LocatedQueryTerm, ZeroTypoTerm};
+use crate::blocked_terms::BlockedTermsMode;
 use crate::search::new::query_term::{Lazy, Phrase, QueryTerm};
-use crate::search::new::Word;
-use crate::{Result, SearchContext, MAX_WORD_LENGTH};
+use crate::search::new::{Interned, Word};
+use crate::{FieldId, Result, SearchContext, UserError, Weight, MAX_WORD_LENGTH};
 
 #[derive(Clone)]
 /// Extraction of the content of a query.
@@ -18,16 +24,262 @@ pub struct ExtractedTokens {
     pub negative_words: Vec<Word>,
     /// The phrases that must not appear in the results.
     pub negative_phrases: Vec<LocatedQueryTerm>,
+    /// The terms prefixed by the name of a searchable attribute, e.g. `title:rust`,
+    /// along with the attribute that must contain them.
+    pub field_scoped_terms: Vec<(Interned<QueryTerm>, FieldId)>,
+    /// The location of every term in the original query, in the order they appear,
+    /// along with the term to search for when it isn't a negative one.
+    pub term_locations: Vec<(QueryTermLocation, Option<Interned<QueryTerm>>)>,
+    /// The byte offsets of the stop words that were removed from the query.
+    pub removed_stop_words: Vec<Range<usize>>,
+    /// Whether some words of the query were ignored because of the limit on the number of terms.
+    pub truncated: bool,
+}
+
+impl ExtractedTokens {
+    /// Ignores the phrases of the query beyond the given number of phrases.
+    ///
+    /// Returns `true` if some phrases were ignored.
+    pub fn limit_phrases(&mut self, max_phrases: usize) -> bool {
+        let ignored: BTreeSet<_> = self
+            .term_locations
+            .iter()
+            .filter(|(location, _)| location.kind == QueryTermKind::Phrase)
+            .filter_map(|(_, term)| *term)
+            .skip(max_phrases)
+            .collect();
+        if ignored.is_empty() {
+            return false;
+        }
+
+        self.query_terms.retain(|term| !ignored.contains(&term.value));
+        self.field_scoped_terms.retain(|(term, _)| !ignored.contains(term));
+        self.term_locations.retain(|(_, term)| term.map_or(true, |term| !ignored.contains(&term)));
+        true
+    }
+}
+
+/// The kind of a term of the query, as written by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryTermKind {
+    Word,
+    Prefix,
+    Phrase,
+    NegativeWord,
+    NegativePhrase,
+    /// A word surrounded by `*` wildcards, e.g. `run*`, `*ing` or `*un*`.
+    Wildcard,
+    /// A quoted regular expression, when the quoted parts of the query are regular expressions.
+    Regex,
+}
+
+/// The location of a term in the original search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryTermLocation {
+    /// The byte offsets of the term in the query, without the quotes of the phrases.
+    pub byte_range: Range<usize>,
+    pub kind: QueryTermKind,
+    /// Whether at least one of the returned documents contains the term.
+    pub matched: bool,
+}
+
+impl QueryTermLocation {
+    fn new(byte_range: Range<usize>, kind: QueryTermKind) -> Self {
+        QueryTermLocation { byte_range, kind, matched: false }
+    }
+}
+
+/// A boolean operator written in uppercase between the terms of the query, e.g. `red AND shoes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueryOperator {
+    /// Both the previous and the next terms must be contained by the documents.
+    And,
+    /// The next term is an alternative to the previous one.
+    Or,
+    /// The next term must not be contained by the documents, like with the `-` prefix.
+    Not,
+}
+
+impl QueryOperator {
+    fn from_word(word: &str) -> Option<Self> {
+        match word {
+            "AND" => Some(QueryOperator::And),
+            "OR" => Some(QueryOperator::Or),
+            "NOT" => Some(QueryOperator::Not),
+            _ => None,
+        }
+    }
+}
+
+/// A `field:` prefix restricting the next term of the query to a searchable attribute.
+struct FieldScope {
+    /// The byte offsets of the name of the attribute and of the colon.
+    byte_range: Range<usize>,
+    fid: FieldId,
+}
+
+/// Returns the `field:` prefixes of the query naming a searchable attribute.
+///
+/// A prefix must be at the start of a word of the query and be directly followed by the term
+/// it scopes, e.g. `title:rust` or `title:"dark tower"`, but not `note: rust`.
+fn field_scopes(query: &str, searchable: &[(Cow<'_, str>, FieldId, Weight)]) -> Vec<FieldScope> {
+    query
+        .match_indices(':')
+        .filter_map(|(colon, _)| {
+            let start = query[..colon]
+                .char_indices()
+                .rev()
+                .find(|(_, c)| c.is_whitespace())
+                .map_or(0, |(i, c)| i + c.len_utf8());
+            let followed_by_term =
+                query[colon + 1..].chars().next().map_or(false, |c| !c.is_whitespace());
+            let field = &query[start..colon];
+            let (_, fid, _) = searchable.iter().find(|(name, _, _)| name == field)?;
+            followed_by_term.then_some(FieldScope { byte_range: start..colon + 1, fid: *fid })
+        })
+        .collect()
+}
+
+/// A word of the query surrounded by `*` wildcards.
+struct WildcardWord {
+    /// The byte offsets of the word, including its wildcards.
+    byte_range: Range<usize>,
+    wildcards: Wildcards,
+}
+
+/// Returns the words of the query starting or ending with a `*` wildcard, e.g. `run*` or `*ing`.
+///
+/// The wildcards must surround a single alphanumeric word, so that neither the phrases,
+/// e.g. `"harry pot"*`, nor the negative terms are concerned.
+fn wildcard_words(query: &str) -> Vec<WildcardWord> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let end_of_query = std::iter::once((query.len(), ' '));
+    for (end, c) in query.char_indices().chain(end_of_query) {
+        if !c.is_whitespace() {
+            continue;
+        }
+        let word = &query[start..end];
+        let wildcards = Wildcards { leading: word.starts_with('*'), trailing: word.ends_with('*') };
+        let inner = word.trim_matches('*');
+        if (wildcards.leading || wildcards.trailing)
+            && !inner.is_empty()
+            && inner.chars().all(char::is_alphanumeric)
+        {
+            words.push(WildcardWord { byte_range: start..end, wildcards });
+        }
+        start = end + c.len_utf8();
+    }
+    words
+}
+
+/// A quoted regular expression of the query.
+struct RegexTerm {
+    /// The byte offsets of the regular expression, including its quotes.
+    byte_range: Range<usize>,
+    /// The byte offsets of the regular expression, without its quotes.
+    pattern: Range<usize>,
+}
+
+/// Returns the quoted parts of the query, which are regular expressions instead of phrases.
+///
+/// A quote escaped with a backslash is part of the regular expression, and a quote that is
+/// never closed extends the regular expression to the end of the query.
+fn regex_terms(query: &str, quotes: &[char]) -> Vec<RegexTerm> {
+    let mut terms = Vec::new();
+    let mut opening: Option<Range<usize>> = None;
+    let mut escaped = false;
+    for (i, c) in query.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if quotes.contains(&c) {
+            match opening.take() {
+                Some(quote) => terms.push(RegexTerm {
+                    byte_range: quote.start..i + c.len_utf8(),
+                    pattern: quote.end..i,
+                }),
+                None => opening = Some(i..i + c.len_utf8()),
+            }
+        }
+    }
+    if let Some(quote) = opening {
+        terms.push(RegexTerm {
+            byte_range: quote.start..query.len(),
+            pattern: quote.end..query.len(),
+        });
+    }
+    // the empty quotes are left to the phrases, which ignore them
+    terms.retain(|term| !term.pattern.is_empty());
+    terms
+}
+
+/// The `^` boosts written right after the words of the query, e.g. `rust^2.5`.
+struct Boosts {
+    /// The byte offsets of each boost in the query, from the `^` to the end of the factor.
+    boosts: Vec<(Range<usize>, Boost)>,
+}
+
+impl Boosts {
+    /// Finds the boosts of the query.
+    ///
+    /// A boost must directly follow a word and be followed by a whitespace or the end of the query.
+    fn new(query: &str) -> Self {
+        let boosts = query
+            .match_indices('^')
+            .filter_map(|(caret, _)| {
+                let follows_word =
+                    query[..caret].chars().next_back().map_or(false, |c| !c.is_whitespace());
+                let factor = &query[caret + 1..];
+                let len = factor.find(char::is_whitespace).unwrap_or(factor.len());
+                let boost = factor[..len].parse().ok().and_then(Boost::new)?;
+                follows_word.then_some((caret..caret + 1 + len, boost))
+            })
+            .collect();
+        Boosts { boosts }
+    }
+
+    /// Whether the token is part of a boost, which is not searched for.
+    fn contains(&self, token: &charabia::Token<'_>) -> bool {
+        self.boosts.iter().any(|(range, _)| range.contains(&token.byte_start))
+    }
+
+    /// Returns the boost written right after the given word.
+    fn boost_of(&self, token: &charabia::Token<'_>) -> Boost {
+        let boost = self.boosts.iter().find(|(range, _)| range.start == token.byte_end);
+        boost.map_or_else(Boost::default, |(_, boost)| *boost)
+    }
 }
 
 /// Convert the tokenised search query into a list of located query terms.
+///
+/// The original query is used to recognize the boolean operators, which must be written in uppercase,
+/// the `field:` prefixes scoping a term to a searchable attribute, the `*` wildcards and the `^` boosts.
+/// When [`SearchContext::regex_terms`] is set, it also gives the quoted regular expressions.
 #[tracing::instrument(level = "trace", skip_all, target = "search::query")]
 pub fn located_query_terms_from_tokens(
     ctx: &mut SearchContext<'_>,
+    original_query: &str,
     query: NormalizedTokenIter<'_, '_>,
     words_limit: Option<usize>,
 ) -> Result<ExtractedTokens> {
     let nbr_typos = number_of_typos_allowed(ctx)?;
+    let literal_words = ctx.index.literal_words_fst(ctx.txn)?;
+    let min_prefix_length = ctx.index.min_prefix_length(ctx.txn)? as usize;
+    let blocked_terms = ctx.index.blocked_terms(ctx.txn)?;
+    let quotes: Vec<char> =
+        ctx.index.quote_characters(ctx.txn)?.iter().filter_map(|q| q.chars().next()).collect();
+    let field_scopes = if original_query.contains(':') {
+        field_scopes(original_query, &ctx.index.searchable_fields_and_weights(ctx.txn)?)
+    } else {
+        Vec::new()
+    };
+    let wildcard_words = wildcard_words(original_query);
+    let regex_terms =
+        if ctx.regex_terms { regex_terms(original_query, &quotes) } else { Vec::new() };
+    let mut next_regex_term = 0;
+    let boosts = Boosts::new(original_query);
 
     let mut query_terms = Vec::new();
 
@@ -35,8 +287,18 @@ pub fn located_query_terms_from_tokens(
     let mut phrase: Option<PhraseBuilder> = None;
     let mut encountered_whitespace = true;
     let mut negative_next_token = false;
+    let mut required_next_token = false;
+    let mut operator: Option<QueryOperator> = None;
+    let mut phrase_operator: Option<QueryOperator> = None;
+    let mut pending_scope: Option<FieldId> = None;
+    let mut phrase_scope: Option<FieldId> = None;
     let mut negative_words = Vec::new();
     let mut negative_phrases = Vec::new();
+    let mut field_scoped_terms = Vec::new();
+    let mut term_locations = Vec::new();
+    let mut removed_stop_words = Vec::new();
+    // the end of the slop of the last closed phrase, e.g. `~2`
+    let mut slop_end = 0;
 
     let parts_limit = words_limit.unwrap_or(usize::MAX);
 
@@ -51,7 +313,94 @@ pub fn located_query_terms_from_tokens(
 
         // early return if word limit is exceeded
         if query_terms.len() >= parts_limit {
-            return Ok(ExtractedTokens { query_terms, negative_words, negative_phrases });
+            let truncated = std::iter::once(token).chain(peekable).any(|token| token.is_word());
+            return Ok(ExtractedTokens {
+                query_terms,
+                negative_words,
+                negative_phrases,
+                field_scoped_terms,
+                term_locations,
+                removed_stop_words,
+                truncated,
+            });
+        }
+
+        // the `field:` prefixes are not searched, they only scope the next term
+        let scope = field_scopes.iter().find(|scope| scope.byte_range.contains(&token.byte_start));
+        if let Some(scope) = scope.filter(|_| phrase.is_none()) {
+            match token.kind {
+                TokenKind::Word | TokenKind::StopWord => {
+                    pending_scope = Some(scope.fid);
+                    continue;
+                }
+                _ if token.byte_end <= scope.byte_range.end => continue,
+                _ => (),
+            }
+        }
+
+        // a quoted regular expression is a single term, its tokens are not searched
+        while let Some(regex_term) =
+            regex_terms.get(next_regex_term).filter(|term| term.byte_range.start < token.byte_end)
+        {
+            next_regex_term += 1;
+            position = position.wrapping_add(1);
+            let term = regex_term_from_pattern(ctx, &original_query[regex_term.pattern.clone()])?;
+            let mut located_term = LocatedQueryTerm {
+                value: ctx.term_interner.push(term),
+                positions: position..=position,
+                mandatory: required_next_token,
+                boost: Boost::default(),
+            };
+            let mut operator = operator.take();
+            apply_field_scope(
+                &mut located_term,
+                &mut operator,
+                pending_scope.take(),
+                &mut field_scoped_terms,
+            );
+            push_query_term(
+                ctx,
+                &mut query_terms,
+                &mut term_locations,
+                QueryTermLocation::new(regex_term.pattern.clone(), QueryTermKind::Regex),
+                located_term,
+                operator,
+            );
+            negative_next_token = false;
+            required_next_token = false;
+        }
+        if regex_terms.iter().any(|term| {
+            term.byte_range.start < token.byte_end && token.byte_start < term.byte_range.end
+        }) {
+            encountered_whitespace = token.lemma().ends_with(char::is_whitespace);
+            continue;
+        }
+
+        // the `*` wildcards are not searched, they only mark the word they surround
+        let wildcard = wildcard_words
+            .iter()
+            .find(|wildcard| wildcard.byte_range.contains(&token.byte_start))
+            .filter(|_| phrase.is_none());
+        if wildcard.is_some()
+            && token.is_word()
+            && token.lemma().chars().all(|c| c == '*' || c.is_whitespace())
+        {
+            continue;
+        }
+
+        // the boosts are not searched, they only weigh the word they follow
+        if boosts.contains(&token) {
+            continue;
+        }
+
+        // the slop of a phrase is not searched, it only loosens the phrase
+        if token.is_word() && token.byte_start < slop_end {
+            continue;
+        }
+
+        // the backslashes escaping the quotes are not searched either
+        if token.is_word() && token.lemma() == "\\" {
+            continue;
         }
 
         match token.kind {
@@ -59,50 +408,177 @@ pub fn located_query_terms_from_tokens(
                 // On first loop, goes from u16::MAX to 0, then normal increment.
                 position = position.wrapping_add(1);
 
+                // An operator is only recognized between two terms, and `AND` and `OR` need a
+                // previous term to combine the next one with, otherwise it is searched as a word.
+                let prefixed = negative_next_token || required_next_token;
+                let operator_word = if phrase.is_none() && !prefixed && peekable.peek().is_some() {
+                    original_query
+                        .get(token.byte_start..token.byte_end)
+                        .and_then(QueryOperator::from_word)
+                } else {
+                    None
+                };
+                let has_previous_term = term_locations.last().map_or(false, |(_, t)| t.is_some());
+                let new_operator = match (operator, operator_word) {
+                    // `AND NOT` is the same as `NOT`, the negative terms being always excluded
+                    (None | Some(QueryOperator::And), Some(QueryOperator::Not)) => {
+                        Some(QueryOperator::Not)
+                    }
+                    (None, Some(operator_word)) if has_previous_term => Some(operator_word),
+                    _ => None,
+                };
+                if new_operator.is_some() {
+                    operator = new_operator;
+                    encountered_whitespace = false;
+                    continue;
+                }
+
+                // the blocked words are never searched, nor derived into other words
+                let word = unescape(token.lemma()).trim_matches('*');
+                if blocked_terms.contains(word) {
+                    if blocked_terms.mode == BlockedTermsMode::Reject {
+                        return Err(UserError::BlockedSearchTerm(word.to_string()).into());
+                    }
+                    match &mut phrase {
+                        // the other words of the phrase keep their positions
+                        Some(phrase) => phrase.push_word(ctx, &token, position, true),
+                        None => {
+                            negative_next_token = false;
+                            operator = None;
+                        }
+                    }
+                    pending_scope = None;
+                    required_next_token = false;
+                    continue;
+                }
+
                 // 1. if the word is quoted we push it in a phrase-buffer waiting for the ending quote,
                 // 2. if the word is not the last token of the query and is not a stop_word we push it as a non-prefix word,
                 // 3. if the word is the last token of the query we push it as a prefix word.
                 if let Some(phrase) = &mut phrase {
-                    phrase.push_word(ctx, &token, position)
-                } else if negative_next_token {
+                    phrase.push_word(ctx, &token, position, false)
+                } else if negative_next_token || operator == Some(QueryOperator::Not) {
                     let word = token.lemma().to_string();
                     let word = Word::Original(ctx.word_interner.insert(word));
                     negative_words.push(word);
+                    term_locations.push((
+                        QueryTermLocation::new(
+                            token.byte_start..token.byte_end,
+                            QueryTermKind::NegativeWord,
+                        ),
+                        None,
+                    ));
                     negative_next_token = false;
+                    operator = None;
                 } else if peekable.peek().is_some() {
                     match token.kind {
-                        TokenKind::Word => {
-                            let word = token.lemma();
-                            let term = partially_initialized_term_from_word(
-                                ctx,
-                                word,
-                                nbr_typos(word),
-                                false,
-                                false,
-                            )?;
-                            let located_term = LocatedQueryTerm {
+                        // a required stop word is searched like any other word
+                        TokenKind::StopWord if !required_next_token => {
+                            removed_stop_words.push(token.byte_start..token.byte_end)
+                        }
+                        TokenKind::Word | TokenKind::StopWord => {
+                            let word = unescape(token.lemma()).trim_matches('*');
+                            let term = match wildcard {
+                                Some(wildcard) => {
+                                    wildcard_term_from_word(ctx, word, wildcard.wildcards)?
+                                }
+                                None => partially_initialized_term_from_word(
+                                    ctx,
+                                    word,
+                                    nbr_typos(word),
+                                    false,
+                                    false,
+                                )?,
+                            };
+                            let mut located_term = LocatedQueryTerm {
                                 value: ctx.term_interner.push(term),
                                 positions: position..=position,
+                                mandatory: required_next_token,
+                                boost: boosts.boost_of(&token),
                             };
-                            query_terms.push(located_term);
+                            let mut operator = operator.take();
+                            apply_field_scope(
+                                &mut located_term,
+                                &mut operator,
+                                pending_scope,
+                                &mut field_scoped_terms,
+                            );
+                            let location = match wildcard {
+                                Some(wildcard) => QueryTermLocation::new(
+                                    wildcard.byte_range.clone(),
+                                    QueryTermKind::Wildcard,
+                                ),
+                                None => QueryTermLocation::new(
+                                    token.byte_start..token.byte_end,
+                                    QueryTermKind::Word,
+                                ),
+                            };
+                            push_query_term(
+                                ctx,
+                                &mut query_terms,
+                                &mut term_locations,
+                                location,
+                                located_term,
+                                operator,
+                            );
                         }
-                        TokenKind::StopWord | TokenKind::Separator(_) | TokenKind::Unknown => (),
+                        TokenKind::Separator(_) | TokenKind::Unknown => (),
                     }
                 } else {
-                    let word = token.lemma();
-                    let term = partially_initialized_term_from_word(
-                        ctx,
-                        word,
-                        nbr_typos(word),
-                        true,
-                        false,
-                    )?;
-                    let located_term = LocatedQueryTerm {
+                    let word = unescape(token.lemma()).trim_matches('*');
+                    // literal words are never expanded, even as the last word of the query,
+                    // and neither are the words too short to be worth expanding
+                    let is_prefix = !literal_words.as_ref().map_or(false, |fst| fst.contains(word))
+                        && word.chars().count() >= min_prefix_length;
+                    let term = match wildcard {
+                        Some(wildcard) => wildcard_term_from_word(ctx, word, wildcard.wildcards)?,
+                        None => partially_initialized_term_from_word(
+                            ctx,
+                            word,
+                            nbr_typos(word),
+                            is_prefix,
+                            false,
+                        )?,
+                    };
+                    let mut located_term = LocatedQueryTerm {
                         value: ctx.term_interner.push(term),
                         positions: position..=position,
+                        mandatory: required_next_token,
+                        boost: boosts.boost_of(&token),
                     };
-                    query_terms.push(located_term);
+                    let mut operator = operator.take();
+                    apply_field_scope(
+                        &mut located_term,
+                        &mut operator,
+                        pending_scope,
+                        &mut field_scoped_terms,
+                    );
+                    let location = match wildcard {
+                        Some(wildcard) => QueryTermLocation::new(
+                            wildcard.byte_range.clone(),
+                            QueryTermKind::Wildcard,
+                        ),
+                        None if is_prefix => QueryTermLocation::new(
+                            token.byte_start..token.byte_end,
+                            QueryTermKind::Prefix,
+                        ),
+                        None => QueryTermLocation::new(
+                            token.byte_start..token.byte_end,
+                            QueryTermKind::Word,
+                        ),
+                    };
+                    push_query_term(
+                        ctx,
+                        &mut query_terms,
+                        &mut term_locations,
+                        location,
+                        located_term,
+                        operator,
+                    );
                 }
+                // a prefix only scopes the word directly following it
+                pending_scope = None;
+                required_next_token = false;
             }
             TokenKind::Separator(separator_kind) => {
                 // add penalty for hard separators
@@ -116,14 +592,36 @@ pub fn located_query_terms_from_tokens(
                     // If we have a hard separator inside a phrase, we immediately start a new phrase
                     let phrase = if separator_kind == SeparatorKind::Hard {
                         if let Some(phrase) = phrase {
-                            if let Some(located_query_term) = phrase.build(ctx) {
+                            if let Some((mut located_query_term, byte_range)) = phrase.build(ctx)? {
                                 // as we are evaluating a negative operator we put the phrase
                                 // in the negative one *but* we don't reset the negative operator
                                 // as we are immediately starting a new negative phrase.
                                 if negative_phrase {
+                                    term_locations.push((
+                                        QueryTermLocation::new(
+                                            byte_range,
+                                            QueryTermKind::NegativePhrase,
+                                        ),
+                                        None,
+                                    ));
                                     negative_phrases.push(located_query_term);
                                 } else {
-                                    query_terms.push(located_query_term);
+                                    // the scope also applies to the next part of the phrase
+                                    let mut operator = phrase_operator.take();
+                                    apply_field_scope(
+                                        &mut located_query_term,
+                                        &mut operator,
+                                        phrase_scope,
+                                        &mut field_scoped_terms,
+                                    );
+                                    push_query_term(
+                                        ctx,
+                                        &mut query_terms,
+                                        &mut term_locations,
+                                        QueryTermLocation::new(byte_range, QueryTermKind::Phrase),
+                                        located_query_term,
+                                        operator,
+                                    );
                                 }
                             }
                             Some(PhraseBuilder::empty())
@@ -134,31 +632,83 @@ pub fn located_query_terms_from_tokens(
                         phrase
                     };
 
-                    // We close and start a new phrase depending on the number of double quotes
-                    let mut quote_count = token.lemma().chars().filter(|&s| s == '"').count();
+                    // We close and start a new phrase depending on the number of quotes
+                    let escaped = preceded_by_backslash(original_query, &token);
+                    let mut quote_count = count_quotes(
+                        token.lemma(),
+                        &quotes,
+                        escaped,
+                        phrase.is_some(),
+                        encountered_whitespace,
+                        peekable.peek(),
+                    );
                     if quote_count == 0 {
                         break 'phrase phrase;
                     }
 
                     // Consume the closing quote and the phrase
-                    if let Some(phrase) = phrase {
+                    if let Some(mut phrase) = phrase {
                         // Per the check above, quote_count > 0
                         quote_count -= 1;
-                        if let Some(located_query_term) = phrase.build(ctx) {
+                        if closing_quote_followed_by_star(
+                            token.lemma(),
+                            &quotes,
+                            escaped,
+                            peekable.peek(),
+                        ) {
+                            phrase.is_prefix = true;
+                            // the star is only a marker, it must not be searched for
+                            if peekable.peek().map_or(false, |next| {
+                                next.lemma() == "*"
+                                    && matches!(next.kind, TokenKind::Word | TokenKind::StopWord)
+                            }) {
+                                peekable.next();
+                            }
+                        } else if let Some((slop, end)) =
+                            closing_quote_slop(original_query, &token, &quotes, escaped)
+                        {
+                            phrase.slop = slop;
+                            slop_end = end;
+                        }
+                        if let Some((mut located_query_term, byte_range)) = phrase.build(ctx)? {
                             // we were evaluating a negative operator so we
                             // put the phrase in the negative phrases
                             if negative_phrase {
+                                term_locations.push((
+                                    QueryTermLocation::new(
+                                        byte_range,
+                                        QueryTermKind::NegativePhrase,
+                                    ),
+                                    None,
+                                ));
                                 negative_phrases.push(located_query_term);
                                 negative_phrase = false;
                             } else {
-                                query_terms.push(located_query_term);
+                                let mut operator = phrase_operator.take();
+                                apply_field_scope(
+                                    &mut located_query_term,
+                                    &mut operator,
+                                    phrase_scope.take(),
+                                    &mut field_scoped_terms,
+                                );
+                                push_query_term(
+                                    ctx,
+                                    &mut query_terms,
+                                    &mut term_locations,
+                                    QueryTermLocation::new(byte_range, QueryTermKind::Phrase),
+                                    located_query_term,
+                                    operator,
+                                );
                             }
                         }
                     }
 
                     // Start new phrase if the token ends with an opening quote
                     if quote_count % 2 == 1 {
-                        negative_phrase = negative_next_token;
+                        phrase_operator = operator.take();
+                        phrase_scope = pending_scope.take();
+                        negative_phrase =
+                            negative_next_token || phrase_operator == Some(QueryOperator::Not);
                         Some(PhraseBuilder::empty())
                     } else {
                         None
@@ -167,6 +717,9 @@ pub fn located_query_terms_from_tokens(
 
                 negative_next_token =
                     phrase.is_none() && token.lemma() == "-" && encountered_whitespace;
+                // the phrases are always required, the `+` prefix is only meaningful for words
+                required_next_token =
+                    phrase.is_none() && token.lemma() == "+" && encountered_whitespace;
             }
             _ => (),
         }
@@ -177,17 +730,203 @@ pub fn located_query_terms_from_tokens(
 
     // If a quote is never closed, we consider all of the end of the query as a phrase.
     if let Some(phrase) = phrase.take() {
-        if let Some(located_query_term) = phrase.build(ctx) {
+        if let Some((mut located_query_term, byte_range)) = phrase.build(ctx)? {
             // put the phrase in the negative set if we are evaluating a negative operator.
             if negative_phrase {
+                term_locations.push((
+                    QueryTermLocation::new(byte_range, QueryTermKind::NegativePhrase),
+                    None,
+                ));
                 negative_phrases.push(located_query_term);
             } else {
-                query_terms.push(located_query_term);
+                let mut operator = phrase_operator.take();
+                apply_field_scope(
+                    &mut located_query_term,
+                    &mut operator,
+                    phrase_scope,
+                    &mut field_scoped_terms,
+                );
+                push_query_term(
+                    ctx,
+                    &mut query_terms,
+                    &mut term_locations,
+                    QueryTermLocation::new(byte_range, QueryTermKind::Phrase),
+                    located_query_term,
+                    operator,
+                );
+            }
+        }
+    }
+
+    Ok(ExtractedTokens {
+        query_terms,
+        negative_words,
+        negative_phrases,
+        field_scoped_terms,
+        term_locations,
+        removed_stop_words,
+        truncated: false,
+    })
+}
+
+/// Restricts a positive term to the attribute of its `field:` prefix, if any.
+///
+/// A scoped term is mandatory, and is never an alternative to the previous term
+/// as it couldn't be restricted to its attribute anymore.
+fn apply_field_scope(
+    located_term: &mut LocatedQueryTerm,
+    operator: &mut Option<QueryOperator>,
+    scope: Option<FieldId>,
+    field_scoped_terms: &mut Vec<(Interned<QueryTerm>, FieldId)>,
+) {
+    if let Some(fid) = scope {
+        located_term.mandatory = true;
+        if *operator == Some(QueryOperator::Or) {
+            *operator = None;
+        }
+        field_scoped_terms.push((located_term.value, fid));
+    }
+}
+
+/// Pushes a positive term of the query, combining it with the previous term
+/// when they are separated by an `AND` or `OR` operator.
+fn push_query_term(
+    ctx: &mut SearchContext<'_>,
+    query_terms: &mut Vec<LocatedQueryTerm>,
+    term_locations: &mut Vec<(QueryTermLocation, Option<Interned<QueryTerm>>)>,
+    location: QueryTermLocation,
+    mut located_term: LocatedQueryTerm,
+    operator: Option<QueryOperator>,
+) {
+    let previous = term_locations.last().and_then(|(_, term)| *term);
+    let previous =
+        previous.and_then(|previous| query_terms.iter_mut().rev().find(|t| t.value == previous));
+
+    match (operator, previous) {
+        (Some(QueryOperator::And), Some(previous)) => {
+            previous.mandatory = true;
+            located_term.mandatory = true;
+        }
+        (Some(QueryOperator::Or), Some(previous)) => {
+            // the alternative is matched like a synonym of the previous term
+            let term = ctx.term_interner.get(located_term.value);
+            let phrase = match term.original_phrase() {
+                Some(phrase) => phrase,
+                None => ctx.phrase_interner.insert(Phrase {
+                    words: vec![Some(term.original)],
+                    is_prefix: term.is_prefix,
+                    slop: 0,
+                }),
+            };
+            ctx.term_interner.get_mut(previous.value).zero_typo.synonyms.insert(phrase);
+            term_locations.push((location, Some(previous.value)));
+            return;
+        }
+        _ => (),
+    }
+
+    term_locations.push((location, Some(located_term.value)));
+    query_terms.push(located_term);
+}
+
+/// Returns the number of quotes of the given separator that open or close a phrase.
+///
+/// The double quote always counts, the other quote characters are also used as apostrophes
+/// and only count when they are on the outer side of a word, e.g. `'harry'` but not `don't`.
+/// The quotes escaped by a backslash never count, e.g. `"he said \"hi\""`, `escaped`
+/// telling whether the separator directly follows a backslash.
+fn count_quotes(
+    lemma: &str,
+    quotes: &[char],
+    escaped: bool,
+    mut in_phrase: bool,
+    preceded_by_whitespace: bool,
+    next: Option<&charabia::Token<'_>>,
+) -> usize {
+    let mut count = 0;
+    let mut previous = escaped.then_some('\\');
+    let mut chars = lemma.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quotes.contains(&c) && previous != Some('\\') {
+            let is_delimiter = c == '"'
+                || if in_phrase {
+                    match chars.peek() {
+                        Some(after) => !after.is_alphanumeric(),
+                        None => {
+                            next.map_or(true, |token| matches!(token.kind, TokenKind::Separator(_)))
+                        }
+                    }
+                } else {
+                    previous
+                        .map_or(preceded_by_whitespace, |before: char| !before.is_alphanumeric())
+                };
+            if is_delimiter {
+                count += 1;
+                in_phrase = !in_phrase;
             }
         }
+        previous = Some(c);
+    }
+    count
+}
+
+/// Returns whether the closing quote of the given separator is directly followed by a star,
+/// either in the separator itself or at the beginning of the next token, e.g. `"harry pot"*`.
+fn closing_quote_followed_by_star(
+    lemma: &str,
+    quotes: &[char],
+    escaped: bool,
+    next: Option<&charabia::Token<'_>>,
+) -> bool {
+    let Some((quote, c)) = first_unescaped_quote(lemma, quotes, escaped) else {
+        return false;
+    };
+    match lemma[quote + c.len_utf8()..].chars().next() {
+        Some(c) => c == '*',
+        None => next.map_or(false, |token| token.lemma().starts_with('*')),
     }
+}
+
+/// Returns the slop written right after the closing quote of the given separator,
+/// e.g. `"quick fox"~2`, along with the byte offset of its end in the query.
+fn closing_quote_slop(
+    original_query: &str,
+    separator: &charabia::Token<'_>,
+    quotes: &[char],
+    escaped: bool,
+) -> Option<(u8, usize)> {
+    let raw = original_query.get(separator.byte_start..)?;
+    let (quote, c) = first_unescaped_quote(raw, quotes, escaped)?;
+    let after_quote = quote + c.len_utf8();
+    let digits = raw[after_quote..].strip_prefix('~')?;
+    let len = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+    let slop: u32 = digits[..len].parse().ok()?;
+    let slop = slop.min(super::limits::MAX_PHRASE_SLOP as u32) as u8;
+    Some((slop, separator.byte_start + after_quote + 1 + len))
+}
+
+/// Returns the byte offset and the character of the first quote of the given text that isn't
+/// escaped by a backslash, `escaped` telling whether the text directly follows a backslash.
+fn first_unescaped_quote(text: &str, quotes: &[char], escaped: bool) -> Option<(usize, char)> {
+    let mut previous_is_backslash = escaped;
+    for (i, c) in text.char_indices() {
+        if quotes.contains(&c) && !previous_is_backslash {
+            return Some((i, c));
+        }
+        previous_is_backslash = c == '\\';
+    }
+    None
+}
+
+/// Returns whether the given token directly follows a backslash in the query.
+fn preceded_by_backslash(original_query: &str, token: &charabia::Token<'_>) -> bool {
+    original_query.get(..token.byte_start).map_or(false, |before| before.ends_with('\\'))
+}
 
-    Ok(ExtractedTokens { query_terms, negative_words, negative_phrases })
+/// Removes the backslash escaping the quote following a word, e.g. `hi\"`, when the backslash
+/// isn't a separator.
+fn unescape(word: &str) -> &str {
+    word.strip_suffix('\\').filter(|word| !word.is_empty()).unwrap_or(word)
 }
 
 pub fn number_of_typos_allowed<'ctx>(
@@ -196,23 +935,36 @@ pub fn number_of_typos_allowed<'ctx>(
     let authorize_typos = ctx.index.authorize_typos(ctx.txn)?;
     let min_len_one_typo = ctx.index.min_word_len_one_typo(ctx.txn)?;
     let min_len_two_typos = ctx.index.min_word_len_two_typos(ctx.txn)?;
+    let disable_typos_on_numbers = ctx.index.disable_typos_on_numbers(ctx.txn)?;
 
     let exact_words = ctx.index.exact_words(ctx.txn)?;
+    let literal_words = ctx.index.literal_words_fst(ctx.txn)?;
+    let max_typos = ctx.max_typos.unwrap_or(2);
 
     Ok(Box::new(move |word: &str| {
-        if !authorize_typos
+        let typos = if !authorize_typos
             || word.len() < min_len_one_typo as usize
             || exact_words.as_ref().map_or(false, |fst| fst.contains(word))
+            || literal_words.as_ref().map_or(false, |fst| fst.contains(word))
+            // "2024" must not match "2014", nor "b2024" match "b2014"
+            || (disable_typos_on_numbers && is_mostly_digits(word))
         {
             0
         } else if word.len() < min_len_two_typos as usize {
             1
         } else {
             2
-        }
+        };
+        typos.min(max_typos)
     }))
 }
 
+/// Whether most of the characters of the word are digits, like in years or product codes.
+fn is_mostly_digits(word: &str) -> bool {
+    let digits = word.chars().filter(char::is_ascii_digit).count();
+    digits * 2 > word.chars().count()
+}
+
 pub fn make_ngram(
     ctx: &mut SearchContext<'_>,
     terms: &[LocatedQueryTerm],
@@ -220,7 +972,9 @@ pub fn make_ngram(
 ) -> Result<Option<LocatedQueryTerm>> {
     assert!(!terms.is_empty());
     for t in terms {
-        if ctx.term_interner.get(t.value).zero_typo.phrase.is_some() {
+        let term = ctx.term_interner.get(t.value);
+        // the wildcards are patterns, they can't be concatenated with their neighbours
+        if term.zero_typo.phrase.is_some() || term.is_wildcard {
             return Ok(None);
         }
     }
@@ -241,11 +995,18 @@ pub fn make_ngram(
     let words =
         words_interned.iter().map(|&i| ctx.word_interner.get(i).to_owned()).collect::<Vec<_>>();
 
+    // literal words are never concatenated with their neighbours
+    if let Some(literal_words) = ctx.index.literal_words_fst(ctx.txn)? {
+        if words.iter().any(|word| literal_words.contains(word)) {
+            return Ok(None);
+        }
+    }
+
     let start = *terms.first().as_ref().unwrap().positions.start();
     let end = *terms.last().as_ref().unwrap().positions.end();
     let is_prefix = ctx.term_interner.get(terms.last().as_ref().unwrap().value).is_prefix;
     let ngram_str = words.join("");
-    if ngram_str.len() > MAX_WORD_LENGTH {
+    if ngram_str.len() > MAX_WORD_LENGTH || ctx.index.blocked_terms(ctx.txn)?.contains(&ngram_str) {
         return Ok(None);
     }
     let ngram_str_interned = ctx.word_interner.insert(ngram_str.clone());
@@ -257,26 +1018,32 @@ pub fn make_ngram(
         partially_initialized_term_from_word(ctx, &ngram_str, max_nbr_typos, is_prefix, true)?;
 
     // Now add the synonyms
-    let index_synonyms = ctx.index.synonyms(ctx.txn)?;
+    let synonyms = ctx.synonyms_of(&words)?;
 
-    term.zero_typo.synonyms.extend(
-        index_synonyms.get(&words).cloned().unwrap_or_default().into_iter().map(|words| {
-            let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
-            ctx.phrase_interner.insert(Phrase { words })
-        }),
-    );
+    term.zero_typo.synonyms.extend(synonyms.into_iter().map(|words| {
+        let words = words.into_iter().map(|w| Some(ctx.word_interner.insert(w))).collect();
+        ctx.phrase_interner.insert(Phrase { words, is_prefix: false, slop: 0 })
+    }));
 
     let term = QueryTerm {
         original: ngram_str_interned,
         ngram_words: Some(words_interned),
         is_prefix,
+        is_wildcard: false,
         max_levenshtein_distance: max_nbr_typos,
         zero_typo: term.zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        phonetic: BTreeSet::new(),
     };
 
-    let term = LocatedQueryTerm { value: ctx.term_interner.push(term), positions: start..=end };
+    let term = LocatedQueryTerm {
+        value: ctx.term_interner.push(term),
+        positions: start..=end,
+        mandatory: false,
+        // an ngram is as boosted as the most boosted of its words
+        boost: terms.iter().map(|term| term.boost).max().unwrap_or_default(),
+    };
 
     Ok(Some(term))
 }
@@ -285,11 +1052,25 @@ struct PhraseBuilder {
     words: Vec<Option<crate::search::new::Interned<String>>>,
     start: u16,
     end: u16,
+    byte_start: usize,
+    byte_end: usize,
+    /// Whether the last word of the phrase is a prefix.
+    is_prefix: bool,
+    /// The number of extra positions allowed between the words of the phrase.
+    slop: u8,
 }
 
 impl PhraseBuilder {
     fn empty() -> Self {
-        Self { words: Default::default(), start: u16::MAX, end: u16::MAX }
+        Self {
+            words: Default::default(),
+            start: u16::MAX,
+            end: u16::MAX,
+            byte_start: usize::MAX,
+            byte_end: usize::MAX,
+            is_prefix: false,
+            slop: 0,
+        }
     }
 
     fn is_empty(&self) -> bool {
@@ -302,33 +1083,64 @@ impl PhraseBuilder {
         ctx: &mut SearchContext<'_>,
         token: &charabia::Token<'_>,
         position: u16,
+        blocked: bool,
     ) {
         if self.is_empty() {
             self.start = position;
         }
+        if self.words.is_empty() {
+            self.byte_start = token.byte_start;
+        }
         self.end = position;
-        if let TokenKind::StopWord = token.kind {
+        self.byte_end = token.byte_end;
+        if blocked || matches!(token.kind, TokenKind::StopWord) {
             self.words.push(None);
         } else {
             // token has kind Word
-            let word = ctx.word_interner.insert(token.lemma().to_string());
+            let word = ctx.word_interner.insert(unescape(token.lemma()).to_string());
             self.words.push(Some(word));
         }
     }
 
-    fn build(self, ctx: &mut SearchContext<'_>) -> Option<LocatedQueryTerm> {
+    /// Returns the phrase along with its byte offsets in the query.
+    fn build(
+        self,
+        ctx: &mut SearchContext<'_>,
+    ) -> Result<Option<(LocatedQueryTerm, Range<usize>)>> {
         if self.is_empty() {
-            return None;
+            return Ok(None);
         }
-        Some(LocatedQueryTerm {
+        let byte_range = self.byte_start..self.byte_end;
+
+        // a prefix phrase made of a single word is an exact prefix, e.g. `"pot"*`
+        if self.is_prefix && self.words.iter().flatten().count() == 1 {
+            if let Some(Some(word)) = self.words.last() {
+                let word = ctx.word_interner.get(*word).clone();
+                let term = partially_initialized_term_from_word(ctx, &word, 0, true, false)?;
+                let located_term = LocatedQueryTerm {
+                    value: ctx.term_interner.push(term),
+                    positions: self.end..=self.end,
+                    mandatory: false,
+                    boost: Boost::default(),
+                };
+                return Ok(Some((located_term, byte_range)));
+            }
+        }
+
+        let located_term = LocatedQueryTerm {
             value: ctx.term_interner.push({
-                let phrase = ctx.phrase_interner.insert(Phrase { words: self.words });
+                let phrase = ctx.phrase_interner.insert(Phrase {
+                    words: self.words,
+                    is_prefix: self.is_prefix,
+                    slop: self.slop,
+                });
                 let phrase_desc = phrase.description(ctx);
                 QueryTerm {
                     original: ctx.word_interner.insert(phrase_desc),
                     ngram_words: None,
                     max_levenshtein_distance: 0,
                     is_prefix: false,
+                    is_wildcard: false,
                     zero_typo: ZeroTypoTerm {
                         phrase: Some(phrase),
                         exact: None,
@@ -338,10 +1150,14 @@ impl PhraseBuilder {
                     },
                     one_typo: Lazy::Uninit,
                     two_typo: Lazy::Uninit,
+                    phonetic: BTreeSet::new(),
                 }
             }),
             positions: self.start..=self.end,
-        })
+            mandatory: false,
+            boost: Boost::default(),
+        };
+        Ok(Some((located_term, byte_range)))
     }
 }
 
@@ -374,7 +1190,7 @@ mod tests {
         let mut ctx = SearchContext::new(&index, &rtxn)?;
         // panics with `attempt to add with overflow` before <https://github.com/meilisearch/meilisearch/issues/3785>
         let ExtractedTokens { query_terms, .. } =
-            located_query_terms_from_tokens(&mut ctx, tokens, None)?;
+            located_query_terms_from_tokens(&mut ctx, ".", tokens, None)?;
         assert!(query_terms.is_empty());
 
         Ok(())
@@ -8,6 +8,10 @@ use crate::SearchContext;
 #[derive(Default, Clone, PartialEq, Eq, Hash)]
 pub struct Phrase {
     pub words: Vec<Option<Interned<String>>>,
+    /// Whether the last word of the phrase is a prefix, e.g. `"harry pot"*`.
+    pub is_prefix: bool,
+    /// The number of extra positions allowed between the words of the phrase, e.g. `"quick fox"~2`.
+    pub slop: u8,
 }
 impl Interned<Phrase> {
     pub fn description(self, ctx: &SearchContext<'_>) -> String {
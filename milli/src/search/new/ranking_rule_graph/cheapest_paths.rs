@@ -305,6 +305,32 @@ impl<G: RankingRuleGraphTrait> RankingRuleGraph<G> {
         costs_to_end
     }
 
+    /// Returns the highest sum of the score counts of the conditions of a path from the root
+    /// node to the end node, if the ranking rule counts something else than the costs.
+    pub fn find_max_score_count(&self) -> Option<u64> {
+        let mut max_counts = self.query_graph.nodes.map(|_| Some(0));
+
+        self.traverse_breadth_first_backward(self.query_graph.end_node, |cur_node| {
+            if cur_node == self.query_graph.end_node {
+                return;
+            }
+            let mut max_count = Some(0);
+            for edge_idx in self.edges_of_node.get(cur_node).iter() {
+                let edge = self.edges_store.get(edge_idx).as_ref().unwrap();
+                let count = match edge.condition {
+                    Some(condition) => G::score_count(self.conditions_interner.get(condition)),
+                    None => Some(0),
+                };
+                let succ_count = *max_counts.get(edge.dest_node);
+                max_count = max_count
+                    .zip(count.zip(succ_count))
+                    .map(|(max, (count, succ))| max.max(count as u64 + succ));
+            }
+            *max_counts.get_mut(cur_node) = max_count;
+        });
+        *max_counts.get(self.query_graph.root_node)
+    }
+
     pub fn update_all_costs_before_node(
         &self,
         node_with_removed_outgoing_conditions: Interned<QueryNode>,
@@ -82,7 +82,7 @@ impl RankingRuleGraphTrait for FidGraph {
                 .weight(fid)
                 .ok_or(InternalError::FieldidsWeightsMapMissingEntry { key: fid })?;
             edges.push((
-                weight as u32 * term.term_ids.len() as u32,
+                term.boost.apply(weight as u32 * term.term_ids.len() as u32),
                 conditions_interner.insert(FidCondition { term: term.clone(), fid: Some(fid) }),
             ));
         }
@@ -93,7 +93,8 @@ impl RankingRuleGraphTrait for FidGraph {
         if let Some(max_weight) = max_weight {
             if !all_fields.contains(&max_weight) {
                 edges.push((
-                    max_weight as u32 * term.term_ids.len() as u32, // TODO improve the fid score i.e. fid^10.
+                    // TODO improve the fid score i.e. fid^10.
+                    term.boost.apply(max_weight as u32 * term.term_ids.len() as u32),
                     conditions_interner.insert(FidCondition {
                         term: term.clone(), // TODO remove this ugly clone
                         fid: None,
@@ -114,6 +114,30 @@ pub trait RankingRuleGraphTrait: Sized + 'static {
 
     /// Convert the rank of a path to its corresponding score for the ranking rule
     fn rank_to_score(rank: Rank) -> ScoreDetails;
+
+    /// Returns what the details of the score of the ranking rule count for the condition, when
+    /// it isn't the cost of its edges, e.g. because the costs of the edges are configurable.
+    ///
+    /// The rank of a path is still derived from its cost, only the details use these counts.
+    fn score_count(_condition: &Self::Condition) -> Option<u32> {
+        None
+    }
+
+    /// Convert the rank of a path to its corresponding score for the ranking rule, given the
+    /// sum of the score counts of its conditions and the highest possible sum
+    fn rank_and_count_to_score(rank: Rank, _count: u32, _max_count: u32) -> ScoreDetails {
+        Self::rank_to_score(rank)
+    }
+
+    /// Records the documents resolved by a path of the graph, does nothing by default.
+    fn record_path(
+        _ctx: &mut SearchContext<'_>,
+        _graph: &RankingRuleGraph<Self>,
+        _path: &[Interned<Self::Condition>],
+        _docids: &RoaringBitmap,
+    ) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// The graph used by graph-based ranking rules.
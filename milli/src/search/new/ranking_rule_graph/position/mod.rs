@@ -54,6 +54,7 @@ impl RankingRuleGraphTrait for PositionGraph {
         to_term: &LocatedQueryTermSubset,
     ) -> Result<Vec<(u32, Interned<Self::Condition>)>> {
         let term = to_term;
+        let decay = ctx.index.position_decay(ctx.txn)?;
 
         let mut all_positions = FxHashSet::default();
         for word in term.term_subset.all_single_words_except_prefix_db(ctx)? {
@@ -90,20 +91,20 @@ impl RankingRuleGraphTrait for PositionGraph {
                     // Because if two words are in the same bucketed position (e.g. 32) and consecutive,
                     // then their position cost will be 32+32=64, but an ngram of these two words at the
                     // same position will have a cost of 32+32+1=65
-                    cost += cost_from_distance(distance as u32 + i as u32);
+                    cost += decay.cost(distance as u32 + i as u32);
                 }
                 cost
             };
             positions_for_costs.entry(cost).or_default().push(position);
         }
 
-        let max_cost = term.term_ids.len() as u32 * 10;
+        let max_cost = term.term_ids.len() as u32 * decay.max_cost();
         let max_cost_exists = positions_for_costs.contains_key(&max_cost);
 
         let mut edges = vec![];
         for (cost, positions) in positions_for_costs {
             edges.push((
-                cost,
+                term.boost.apply(cost),
                 conditions_interner.insert(PositionCondition { term: term.clone(), positions }),
             ));
         }
@@ -111,7 +112,7 @@ impl RankingRuleGraphTrait for PositionGraph {
         if !max_cost_exists {
             // artificial empty condition for computing max cost
             edges.push((
-                max_cost,
+                term.boost.apply(max_cost),
                 conditions_interner
                     .insert(PositionCondition { term: term.clone(), positions: Vec::default() }),
             ));
@@ -125,19 +126,3 @@ impl RankingRuleGraphTrait for PositionGraph {
         ScoreDetails::Position(rank)
     }
 }
-
-fn cost_from_distance(distance: u32) -> u32 {
-    match distance {
-        0 => 0,
-        1 => 1,
-        2..=4 => 2,
-        5..=7 => 3,
-        8..=11 => 4,
-        12..=16 => 5,
-        17..=24 => 6,
-        25..=64 => 7,
-        65..=256 => 8,
-        257..=1024 => 9,
-        _ => 10,
-    }
-}
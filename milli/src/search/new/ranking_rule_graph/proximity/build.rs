@@ -8,7 +8,7 @@ use crate::search::new::SearchContext;
 use crate::Result;
 
 pub fn build_edges(
-    _ctx: &mut SearchContext<'_>,
+    ctx: &mut SearchContext<'_>,
     conditions_interner: &mut DedupInterner<ProximityCondition>,
     left_term: Option<&LocatedQueryTermSubset>,
     right_term: &LocatedQueryTermSubset,
@@ -17,7 +17,7 @@ pub fn build_edges(
 
     let Some(left_term) = left_term else {
         return Ok(vec![(
-            right_ngram_max as u32,
+            right_term.boost.apply(right_ngram_max as u32),
             conditions_interner.insert(ProximityCondition::Term { term: right_term.clone() }),
         )]);
     };
@@ -30,15 +30,31 @@ pub fn build_edges(
         // The remaining query graph represents `the sun .. are beautiful`
         // but `sun` and `are` have no proximity condition between them
         return Ok(vec![(
-            right_ngram_max as u32,
+            right_term.boost.apply(right_ngram_max as u32),
             conditions_interner.insert(ProximityCondition::Term { term: right_term.clone() }),
         )]);
     }
 
+    // the proximity of a pair of terms is as boosted as the most boosted of the two
+    let boost = left_term.boost.max(right_term.boost);
+
     let mut conditions = vec![];
-    for cost in right_ngram_max..(((MAX_DISTANCE as usize) - 1) + right_ngram_max) {
+    let max_cost = ((MAX_DISTANCE as usize) - 1) + right_ngram_max;
+    for cost in right_ngram_max..max_cost {
+        // For long queries, the proximities from 3 to 7 are merged in a single edge.
+        if ctx.bucket_proximities && cost == right_ngram_max + 2 {
+            conditions.push((
+                boost.apply(cost as u32),
+                conditions_interner.insert(ProximityCondition::Bucketed {
+                    left_term: left_term.clone(),
+                    right_term: right_term.clone(),
+                    costs: (cost + 1) as u8..=max_cost as u8,
+                }),
+            ));
+            break;
+        }
         conditions.push((
-            cost as u32,
+            boost.apply(cost as u32),
             conditions_interner.insert(ProximityCondition::Uninit {
                 left_term: left_term.clone(),
                 right_term: right_term.clone(),
@@ -48,7 +64,7 @@ pub fn build_edges(
     }
 
     conditions.push((
-        ((MAX_DISTANCE - 1) + (right_ngram_max as u32)),
+        boost.apply(max_cost as u32),
         conditions_interner.insert(ProximityCondition::Term { term: right_term.clone() }),
     ));
 
@@ -6,7 +6,7 @@ use roaring::RoaringBitmap;
 
 use super::ProximityCondition;
 use crate::search::new::interner::Interned;
-use crate::search::new::query_term::{Phrase, QueryTermSubset};
+use crate::search::new::query_term::{LocatedQueryTermSubset, Phrase, QueryTermSubset};
 use crate::search::new::ranking_rule_graph::ComputedCondition;
 use crate::search::new::resolve_query_graph::compute_query_term_subset_docids;
 use crate::search::new::{SearchContext, Word};
@@ -17,9 +17,12 @@ pub fn compute_docids(
     condition: &ProximityCondition,
     universe: &RoaringBitmap,
 ) -> Result<ComputedCondition> {
-    let (left_term, right_term, cost) = match condition {
+    let (left_term, right_term, costs) = match condition {
         ProximityCondition::Uninit { left_term, right_term, cost } => {
-            (left_term, right_term, *cost)
+            (left_term, right_term, *cost..=*cost)
+        }
+        ProximityCondition::Bucketed { left_term, right_term, costs } => {
+            (left_term, right_term, costs.clone())
         }
         ProximityCondition::Term { term } => {
             return Ok(ComputedCondition {
@@ -31,6 +34,35 @@ pub fn compute_docids(
         }
     };
 
+    let universe_len = universe.len();
+    // Every pair of words must be found in the documents containing the right term.
+    // Restricting the universe to them early lets the lookups below skip the
+    // postings, or parts of postings, that can't contribute to the condition.
+    let universe = &compute_query_term_subset_docids(ctx, Some(universe), &right_term.term_subset)?;
+
+    let mut docids = RoaringBitmap::new();
+    if !universe.is_empty() {
+        for cost in costs {
+            compute_cost_docids(ctx, left_term, right_term, cost, &mut docids, universe)?;
+        }
+    }
+
+    Ok(ComputedCondition {
+        docids,
+        universe_len,
+        start_term_subset: Some(left_term.clone()),
+        end_term_subset: right_term.clone(),
+    })
+}
+
+fn compute_cost_docids(
+    ctx: &mut SearchContext<'_>,
+    left_term: &LocatedQueryTermSubset,
+    right_term: &LocatedQueryTermSubset,
+    cost: u8,
+    docids: &mut RoaringBitmap,
+    universe: &RoaringBitmap,
+) -> Result<()> {
     let right_term_ngram_len = right_term.term_ids.len() as u8;
 
     // e.g. for the simple words `sun .. flower`
@@ -45,8 +77,6 @@ pub fn compute_docids(
     let forward_proximity = 1 + cost - right_term_ngram_len;
     let backward_proximity = cost - right_term_ngram_len;
 
-    let mut docids = RoaringBitmap::new();
-
     if let Some(right_prefix) = right_term.term_subset.use_prefix_db(ctx) {
         for (left_phrase, left_word) in last_words_of_term_derivations(ctx, &left_term.term_subset)?
         {
@@ -57,7 +87,7 @@ pub fn compute_docids(
                 left_phrase,
                 forward_proximity,
                 backward_proximity,
-                &mut docids,
+                docids,
                 universe,
             )?;
         }
@@ -77,10 +107,8 @@ pub fn compute_docids(
                 if universe.is_disjoint(ctx.get_phrase_docids(left_phrase)?) {
                     continue;
                 }
-            } else if let Some(left_word_docids) = ctx.word_docids(Some(universe), left_word)? {
-                if left_word_docids.is_empty() {
-                    continue;
-                }
+            } else if ctx.word_docids_is_disjoint(universe, left_word)? {
+                continue;
             }
         }
 
@@ -93,18 +121,13 @@ pub fn compute_docids(
                 right_phrase,
                 forward_proximity,
                 backward_proximity,
-                &mut docids,
+                docids,
                 universe,
             )?;
         }
     }
 
-    Ok(ComputedCondition {
-        docids,
-        universe_len: universe.len(),
-        start_term_subset: Some(left_term.clone()),
-        end_term_subset: right_term.clone(),
-    })
+    Ok(())
 }
 
 fn compute_prefix_edges(
@@ -1,6 +1,8 @@
 pub mod build;
 pub mod compute_docids;
 
+use std::ops::RangeInclusive;
+
 use roaring::RoaringBitmap;
 
 use super::{ComputedCondition, RankingRuleGraphTrait};
@@ -12,8 +14,21 @@ use crate::Result;
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub enum ProximityCondition {
-    Uninit { left_term: LocatedQueryTermSubset, right_term: LocatedQueryTermSubset, cost: u8 },
-    Term { term: LocatedQueryTermSubset },
+    Uninit {
+        left_term: LocatedQueryTermSubset,
+        right_term: LocatedQueryTermSubset,
+        cost: u8,
+    },
+    /// Several proximity costs merged into a single edge, used for long queries
+    /// to keep the number of edges, and thus of paths, tractable.
+    Bucketed {
+        left_term: LocatedQueryTermSubset,
+        right_term: LocatedQueryTermSubset,
+        costs: RangeInclusive<u8>,
+    },
+    Term {
+        term: LocatedQueryTermSubset,
+    },
 }
 
 pub enum ProximityGraph {}
@@ -1,17 +1,31 @@
 use roaring::RoaringBitmap;
 
-use super::{ComputedCondition, RankingRuleGraphTrait};
-use crate::score_details::{self, Rank, ScoreDetails};
+use super::{ComputedCondition, RankingRuleGraph, RankingRuleGraphTrait};
+use crate::score_details::{self, Rank, ScoreDetails, TermTypos};
 use crate::search::new::interner::{DedupInterner, Interned};
-use crate::search::new::query_term::LocatedQueryTermSubset;
+use crate::search::new::query_term::{ExactTerm, LocatedQueryTermSubset};
 use crate::search::new::resolve_query_graph::compute_query_term_subset_docids;
-use crate::search::new::SearchContext;
+use crate::search::new::{SearchContext, Word};
+use crate::typo::FirstLetterTypos;
 use crate::Result;
 
+/// The kind of derivation an edge of the typo graph stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypoEdge {
+    /// The derivations with the given number of typos.
+    Typos(u8),
+    /// The original word split into consecutive words, e.g. `whitehorse` → `white horse`,
+    /// or consecutive words of the query concatenated, e.g. `data base` → `database`.
+    Split,
+}
+
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct TypoCondition {
     term: LocatedQueryTermSubset,
-    nbr_typos: u8,
+    edge: TypoEdge,
+    /// The number of typos counted in the score, which doesn't depend on the half typos
+    /// and the configured costs of the typos like the cost of the edge does.
+    typo_count: u32,
 }
 
 pub enum TypoGraph {}
@@ -46,11 +60,20 @@ impl RankingRuleGraphTrait for TypoGraph {
     ) -> Result<Vec<(u32, Interned<Self::Condition>)>> {
         let term = to_term;
 
+        // When half typos are enabled, all the costs are doubled so that the half typos
+        // can be given an odd cost, in between no typo and a full typo.
+        let half_typos = ctx.index.half_typos(ctx.txn)?.unwrap_or_default();
+        let scale = if half_typos.is_enabled() { 2 } else { 1 };
+        let first_letter_typos = ctx.index.first_letter_typos(ctx.txn)?;
+        let costs = ctx.index.typo_costs(ctx.txn)?;
+
         let mut edges = vec![];
-        // Ngrams have a base typo cost
+        // Ngrams are concatenations of the words of the query, they have a base split cost
         // 2-gram -> equivalent to 1 typo
         // 3-gram -> equivalent to 2 typos
-        let base_cost = if term.term_ids.len() == 1 { 0 } else { term.term_ids.len() as u32 };
+        let is_ngram = term.term_ids.len() > 1;
+        let base_count = if is_ngram { term.term_ids.len() as u32 } else { 0 };
+        let base_cost = base_count * costs.split * scale;
 
         for nbr_typos in 0..=term.term_subset.max_typo_cost(ctx) {
             let mut term = term.clone();
@@ -62,17 +85,84 @@ impl RankingRuleGraphTrait for TypoGraph {
                 1 => {
                     term.term_subset.clear_zero_typo_subset();
                     term.term_subset.clear_two_typo_subset();
+                    // the split words get their own edge
+                    if let Some((typos, split)) = term.term_subset.split_split_words(ctx)? {
+                        let split = LocatedQueryTermSubset { term_subset: split, ..term.clone() };
+                        edges.push((
+                            costs.split * scale + base_cost,
+                            conditions_interner.insert(TypoCondition {
+                                term: split,
+                                edge: TypoEdge::Split,
+                                typo_count: 1 + base_count,
+                            }),
+                        ));
+                        match typos {
+                            Some(typos) => term.term_subset = typos,
+                            None => continue,
+                        }
+                    }
+                    if let Some((half, full)) =
+                        term.term_subset.split_half_typos(ctx, &half_typos)?
+                    {
+                        let half = LocatedQueryTermSubset { term_subset: half, ..term.clone() };
+                        // the costs are doubled, a half typo costs half of a full typo
+                        // but is still counted as a typo
+                        edges.push((
+                            costs.one_typo + base_cost,
+                            conditions_interner.insert(TypoCondition {
+                                term: half,
+                                edge: TypoEdge::Typos(1),
+                                typo_count: 1 + base_count,
+                            }),
+                        ));
+                        term.term_subset = full;
+                    }
                 }
                 2 => {
                     term.term_subset.clear_zero_typo_subset();
                     term.term_subset.clear_one_typo_subset();
+                    // the typos on the first letter get their own, more expensive, edge
+                    if first_letter_typos == FirstLetterTypos::Penalized {
+                        if let Some((others, first_letter)) =
+                            term.term_subset.split_first_letter_typos(ctx)?
+                        {
+                            let first_letter = LocatedQueryTermSubset {
+                                term_subset: first_letter,
+                                ..term.clone()
+                            };
+                            edges.push((
+                                (costs.two_typos + 1) * scale + base_cost,
+                                conditions_interner.insert(TypoCondition {
+                                    term: first_letter,
+                                    edge: TypoEdge::Typos(2),
+                                    typo_count: 2 + base_count,
+                                }),
+                            ));
+                            term.term_subset = others;
+                        }
+                    }
                 }
                 _ => panic!(),
             };
 
+            // an ngram without typos is only a concatenation of the words of the query
+            let edge = if is_ngram && nbr_typos == 0 {
+                TypoEdge::Split
+            } else {
+                TypoEdge::Typos(nbr_typos)
+            };
+            let cost = match nbr_typos {
+                0 => 0,
+                1 => costs.one_typo,
+                _ => costs.two_typos,
+            };
             edges.push((
-                nbr_typos as u32 + base_cost,
-                conditions_interner.insert(TypoCondition { term, nbr_typos }),
+                cost * scale + base_cost,
+                conditions_interner.insert(TypoCondition {
+                    term,
+                    edge,
+                    typo_count: nbr_typos as u32 + base_count,
+                }),
             ));
         }
         Ok(edges)
@@ -80,6 +170,73 @@ impl RankingRuleGraphTrait for TypoGraph {
 
     #[tracing::instrument(level = "trace", skip_all, target = "search::typo")]
     fn rank_to_score(rank: Rank) -> ScoreDetails {
+        // the typo count is the sum of the costs of the edges, expressed in half typos
+        // when half typos are enabled
         ScoreDetails::Typo(score_details::Typo::from_rank(rank))
     }
+
+    fn rank_and_count_to_score(rank: Rank, count: u32, max_count: u32) -> ScoreDetails {
+        // the rank is derived from the costs of the conditions, the details count their typos
+        ScoreDetails::Typo(score_details::Typo::from_rank_and_count(rank, count, max_count))
+    }
+
+    fn score_count(condition: &Self::Condition) -> Option<u32> {
+        Some(condition.typo_count)
+    }
+
+    fn record_path(
+        ctx: &mut SearchContext<'_>,
+        graph: &RankingRuleGraph<Self>,
+        path: &[Interned<Self::Condition>],
+        docids: &RoaringBitmap,
+    ) -> Result<()> {
+        if ctx.term_typos.is_none() {
+            return Ok(());
+        }
+        // the documents of the path share the same terms, except for the prefix terms
+        // matched without typos that split them between exact and prefix matches
+        let mut records: Vec<(RoaringBitmap, Vec<TermTypos>)> = vec![(docids.clone(), vec![])];
+        for &condition in path {
+            let TypoCondition { term, edge, .. } = graph.conditions_interner.get(condition);
+            // the ngrams are always made of several words of the query
+            let is_ngram = term.term_ids.len() > 1;
+            let (typos, split) = match *edge {
+                TypoEdge::Typos(typos) => (typos, is_ngram),
+                TypoEdge::Split => (0, true),
+            };
+            let term_typos =
+                TermTypos { term: term.term_subset.description(ctx), typos, split, prefix: false };
+
+            if *edge != TypoEdge::Typos(0) || !term.term_subset.is_prefix(ctx) {
+                for (_, terms) in records.iter_mut() {
+                    terms.push(term_typos.clone());
+                }
+                continue;
+            }
+
+            let exact_docids = match term.term_subset.exact_term(ctx) {
+                Some(ExactTerm::Word(word)) => {
+                    ctx.word_docids(Some(docids), Word::Original(word))?.unwrap_or_default()
+                }
+                Some(ExactTerm::Phrase(_)) | None => RoaringBitmap::new(),
+            };
+            let mut split_records = Vec::with_capacity(records.len() * 2);
+            for (docids, terms) in records {
+                let prefix_docids = &docids - &exact_docids;
+                let exact_docids = docids & &exact_docids;
+                for (docids, prefix) in [(exact_docids, false), (prefix_docids, true)] {
+                    if !docids.is_empty() {
+                        let mut terms = terms.clone();
+                        terms.push(TermTypos { prefix, ..term_typos.clone() });
+                        split_records.push((docids, terms));
+                    }
+                }
+            }
+            records = split_records;
+        }
+        if let Some(term_typos) = ctx.term_typos.as_mut() {
+            term_typos.extend(records);
+        }
+        Ok(())
+    }
 }
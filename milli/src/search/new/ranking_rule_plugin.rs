@@ -0,0 +1,183 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, RwLock};
+
+use heed::RoTxn;
+use once_cell::sync::Lazy;
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::score_details::{self, Rank, ScoreDetails};
+use crate::{Index, Result, UserError};
+
+/// A ranking rule implemented outside of the engine, e.g. to apply business rules.
+///
+/// It is referenced in the ranking rules of an index as `plugin:<name>`,
+/// where `name` is the name it was registered with.
+pub trait RankingRulePlugin: Send + Sync {
+    /// Splits the given universe into buckets, from the most relevant to the least relevant.
+    ///
+    /// The documents of the universe that are not part of any bucket are ranked after all the buckets.
+    fn buckets(
+        &self,
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        universe: &RoaringBitmap,
+    ) -> Result<Vec<RoaringBitmap>>;
+
+    /// Starts ranking the given universe, returning its buckets to be computed one at a time.
+    ///
+    /// The search only asks for the buckets it needs to fill the requested page, so a plugin
+    /// whose buckets are expensive to compute can compute them lazily by implementing this
+    /// method. By default, all the buckets are computed upfront by [`Self::buckets`].
+    fn start_iteration(
+        &self,
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        universe: &RoaringBitmap,
+    ) -> Result<Box<dyn RankingRulePluginBuckets>> {
+        let buckets = self.buckets(index, rtxn, universe)?;
+        Ok(Box::new(ComputedBuckets(buckets.into())))
+    }
+}
+
+/// The buckets of a ranking rule plugin, from the most relevant to the least relevant.
+pub trait RankingRulePluginBuckets {
+    /// Returns the number of buckets, used to rank the documents in their score details.
+    ///
+    /// It is called once, before the first bucket. The buckets returned after this
+    /// number of buckets are ignored.
+    fn bucket_count(&self) -> usize;
+
+    /// Returns the next bucket, or `None` if there are no more buckets.
+    ///
+    /// Only the documents of the given universe are ranked, it shrinks from one call to the next.
+    /// The documents of the universe that are not part of any bucket are ranked after all the buckets.
+    fn next_bucket(
+        &mut self,
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RoaringBitmap>>;
+}
+
+/// The buckets computed upfront by [`RankingRulePlugin::buckets`].
+struct ComputedBuckets(VecDeque<RoaringBitmap>);
+
+impl RankingRulePluginBuckets for ComputedBuckets {
+    fn bucket_count(&self) -> usize {
+        self.0.len()
+    }
+
+    fn next_bucket(
+        &mut self,
+        _index: &Index,
+        _rtxn: &RoTxn<'_>,
+        _universe: &RoaringBitmap,
+    ) -> Result<Option<RoaringBitmap>> {
+        Ok(self.0.pop_front())
+    }
+}
+
+static RANKING_RULE_PLUGINS: Lazy<RwLock<BTreeMap<String, Arc<dyn RankingRulePlugin>>>> =
+    Lazy::new(Default::default);
+
+/// Registers a ranking rule plugin under the given name, replacing the one already registered under this name.
+pub fn register_ranking_rule_plugin(name: impl Into<String>, plugin: Arc<dyn RankingRulePlugin>) {
+    RANKING_RULE_PLUGINS.write().unwrap().insert(name.into(), plugin);
+}
+
+/// Returns the ranking rule plugin registered under the given name.
+pub fn ranking_rule_plugin(name: &str) -> Option<Arc<dyn RankingRulePlugin>> {
+    RANKING_RULE_PLUGINS.read().unwrap().get(name).cloned()
+}
+
+pub struct PluginRankingRule<Query> {
+    name: String,
+    plugin: Arc<dyn RankingRulePlugin>,
+    original_query: Option<Query>,
+    buckets: Option<Box<dyn RankingRulePluginBuckets>>,
+    /// The rank of the next bucket, the documents left out by the plugin are ranked 1.
+    next_rank: u32,
+    max_rank: u32,
+}
+
+impl<Query> PluginRankingRule<Query> {
+    pub fn new(name: String) -> Result<Self> {
+        match ranking_rule_plugin(&name) {
+            Some(plugin) => Ok(Self {
+                name,
+                plugin,
+                original_query: None,
+                buckets: None,
+                next_rank: 1,
+                max_rank: 1,
+            }),
+            None => Err(UserError::UnknownRankingRulePlugin { name }.into()),
+        }
+    }
+
+    fn score(&self, rank: u32) -> ScoreDetails {
+        ScoreDetails::Plugin(score_details::Plugin {
+            name: self.name.clone(),
+            rank: Rank { rank, max_rank: self.max_rank },
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for PluginRankingRule<Query> {
+    fn id(&self) -> String {
+        format!("plugin:{}", self.name)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::plugin")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let buckets = self.plugin.start_iteration(ctx.index, ctx.txn, universe)?;
+        // the documents left out by the plugin are ranked last, with a rank of 1
+        self.max_rank = (buckets.bucket_count() as u32).saturating_add(1);
+        self.next_rank = self.max_rank;
+        self.buckets = Some(buckets);
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::plugin")]
+    fn next_bucket(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        let buckets = self.buckets.as_mut().unwrap();
+        while self.next_rank > 1 {
+            let rank = self.next_rank;
+            let Some(bucket) = buckets.next_bucket(ctx.index, ctx.txn, universe)? else {
+                self.next_rank = 1;
+                break;
+            };
+            self.next_rank -= 1;
+            let candidates = bucket & universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput { query, candidates, score: self.score(rank) }));
+            }
+        }
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score: self.score(1) }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::plugin")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets = None;
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use heed::types::Bytes;
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::heed_codec::facet::{FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec};
+use crate::heed_codec::StrRefCodec;
+use crate::score_details::{self, Rank, ScoreDetails};
+use crate::{FieldId, HalfLife, Result};
+
+/// The number of buckets the boosts of the documents are rounded to.
+///
+/// The documents of similar dates end up in the same bucket, so that the next ranking rules
+/// can still sort them.
+const RECENCY_MAX_RANK: u32 = 100;
+
+/// Boosts the recent documents with a boost decaying exponentially with the age of their date,
+/// halving every half-life.
+///
+/// The dates are either numbers, read as Unix timestamps in seconds, or RFC 3339 strings.
+/// The documents without any date, or with a date that can't be parsed, are ranked last.
+pub struct Recency<Query> {
+    field_name: String,
+    field_id: Option<FieldId>,
+    half_life: HalfLife,
+    original_query: Option<Query>,
+    buckets: VecDeque<(u32, RoaringBitmap)>,
+}
+
+impl<Query> Recency<Query> {
+    pub fn new(ctx: &SearchContext<'_>, field_name: String, half_life: HalfLife) -> Result<Self> {
+        let field_id = ctx.index.fields_ids_map(ctx.txn)?.id(&field_name);
+        Ok(Self { field_name, field_id, half_life, original_query: None, buckets: VecDeque::new() })
+    }
+
+    /// Returns the rank of a document whose date is the given Unix timestamp.
+    fn rank(&self, now: f64, timestamp: f64) -> u32 {
+        let age = (now - timestamp).max(0.0);
+        let boost = 0.5f64.powf(age / self.half_life.seconds as f64);
+        // a rank of 0 is reserved to the documents without any date
+        ((boost * RECENCY_MAX_RANK as f64).round() as u32).clamp(1, RECENCY_MAX_RANK)
+    }
+
+    fn score(&self, rank: u32) -> ScoreDetails {
+        ScoreDetails::Recency(score_details::Recency {
+            field_name: self.field_name.clone(),
+            half_life: self.half_life,
+            rank: Rank { rank, max_rank: RECENCY_MAX_RANK },
+        })
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Recency<Query> {
+    fn id(&self) -> String {
+        format!("recency({}, {})", self.field_name, self.half_life)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::recency")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let now = OffsetDateTime::now_utc().unix_timestamp() as f64;
+        let mut buckets = BTreeMap::<u32, RoaringBitmap>::new();
+
+        if let Some(field_id) = self.field_id {
+            for_each_number(ctx, field_id, parent_candidates, |timestamp, docids| {
+                *buckets.entry(self.rank(now, timestamp)).or_default() |= docids;
+            })?;
+        }
+
+        // the documents with multiple dates are ranked by their most recent date
+        self.buckets = best_rank_buckets(buckets);
+
+        self.original_query = Some(parent_query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::recency")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        while let Some((rank, bucket)) = self.buckets.pop_front() {
+            let candidates = bucket & universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput { query, candidates, score: self.score(rank) }));
+            }
+        }
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score: self.score(0) }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::recency")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets.clear();
+    }
+}
+
+/// Calls `f` with every number value of the field along with the candidates having it.
+///
+/// The RFC 3339 string values are read as dates and converted to Unix timestamps in seconds,
+/// the other string values are ignored.
+pub(super) fn for_each_number(
+    ctx: &mut SearchContext<'_>,
+    field_id: FieldId,
+    candidates: &RoaringBitmap,
+    mut f: impl FnMut(f64, RoaringBitmap),
+) -> Result<()> {
+    let mut level0prefix = field_id.to_be_bytes().to_vec();
+    level0prefix.push(0);
+
+    let numbers = ctx.index.facet_id_f64_docids.remap_types::<Bytes, FacetGroupValueCodec>();
+    for result in numbers.prefix_iter(ctx.txn, &level0prefix)? {
+        let (key, value) = result?;
+        let docids = value.bitmap & candidates;
+        if docids.is_empty() {
+            continue;
+        }
+        let key = FacetGroupKeyCodec::<OrderedF64Codec>::bytes_decode(key)
+            .map_err(heed::Error::Decoding)?;
+        f(key.left_bound, docids);
+    }
+
+    let strings = ctx.index.facet_id_string_docids.remap_types::<Bytes, FacetGroupValueCodec>();
+    for result in strings.prefix_iter(ctx.txn, &level0prefix)? {
+        let (key, value) = result?;
+        let docids = value.bitmap & candidates;
+        if docids.is_empty() {
+            continue;
+        }
+        let key =
+            FacetGroupKeyCodec::<StrRefCodec>::bytes_decode(key).map_err(heed::Error::Decoding)?;
+        // the facet strings are normalized, i.e. lowercased
+        let date = key.left_bound.to_ascii_uppercase();
+        if let Ok(date) = OffsetDateTime::parse(&date, &Rfc3339) {
+            f(date.unix_timestamp() as f64, docids);
+        }
+    }
+
+    Ok(())
+}
+
+/// Orders the buckets from the highest rank, keeping the documents with multiple values
+/// in the bucket of their highest rank only.
+pub(super) fn best_rank_buckets(
+    buckets: BTreeMap<u32, RoaringBitmap>,
+) -> VecDeque<(u32, RoaringBitmap)> {
+    let mut ranked = RoaringBitmap::new();
+    buckets
+        .into_iter()
+        .rev()
+        .filter_map(|(rank, docids)| {
+            let docids = docids - &ranked;
+            ranked |= &docids;
+            (!docids.is_empty()).then_some((rank, docids))
+        })
+        .collect()
+}
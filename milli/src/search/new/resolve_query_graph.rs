@@ -2,6 +2,8 @@
 
 use std::collections::VecDeque;
 
+use fst::automaton::Str;
+use fst::{Automaton, IntoStreamer, Streamer};
 use fxhash::FxHashMap;
 use roaring::{MultiOps, RoaringBitmap};
 
@@ -9,7 +11,8 @@ use super::interner::Interned;
 use super::query_graph::QueryNodeData;
 use super::query_term::{Phrase, QueryTermSubset};
 use super::small_bitmap::SmallBitmap;
-use super::{QueryGraph, SearchContext, Word};
+use super::{limits, QueryGraph, SearchContext, Word};
+use crate::proximity::MAX_DISTANCE;
 use crate::search::new::query_term::LocatedQueryTermSubset;
 use crate::Result;
 
@@ -157,6 +160,7 @@ pub fn compute_query_graph_docids(
                 term_subset,
                 positions: _,
                 term_ids: _,
+                boost: _,
             }) => compute_query_term_subset_docids(ctx, Some(&predecessors_docids), term_subset)?,
             QueryNodeData::Deleted => {
                 panic!()
@@ -188,11 +192,77 @@ pub fn compute_phrase_docids(
     ctx: &mut SearchContext<'_>,
     phrase: Interned<Phrase>,
 ) -> Result<RoaringBitmap> {
-    let Phrase { words } = ctx.phrase_interner.get(phrase).clone();
+    let Phrase { words, is_prefix, slop } = ctx.phrase_interner.get(phrase).clone();
 
     if words.is_empty() {
         return Ok(RoaringBitmap::new());
     }
+
+    let slop = slop as usize;
+    match words.last() {
+        Some(Some(prefix)) if is_prefix => compute_prefix_phrase_docids(ctx, &words, *prefix, slop),
+        _ => compute_words_phrase_docids(ctx, &words, slop),
+    }
+}
+
+fn compute_words_phrase_docids(
+    ctx: &mut SearchContext<'_>,
+    words: &[Option<Interned<String>>],
+    slop: usize,
+) -> Result<RoaringBitmap> {
+    let candidates = compute_phrase_proximity_candidates(ctx, words, slop)?;
+    if candidates.is_empty() {
+        return Ok(candidates);
+    }
+    retain_phrase_positional_matches(ctx, words, slop, candidates)
+}
+
+/// Returns the documents containing the phrase in which the last word
+/// is replaced by any of the words starting with it.
+fn compute_prefix_phrase_docids(
+    ctx: &mut SearchContext<'_>,
+    words: &[Option<Interned<String>>],
+    prefix: Interned<String>,
+    slop: usize,
+) -> Result<RoaringBitmap> {
+    let (_, head) = words.split_last().unwrap();
+    // no need to look at the derivations if the beginning of the phrase can't be found
+    if head.iter().any(Option::is_some)
+        && compute_phrase_proximity_candidates(ctx, head, slop)?.is_empty()
+    {
+        return Ok(RoaringBitmap::new());
+    }
+
+    let prefix = ctx.word_interner.get(prefix).clone();
+    let fst = ctx.get_words_fst()?;
+    let mut derived_words = Vec::new();
+    let mut stream = fst.search(Str::new(&prefix).starts_with()).into_stream();
+    while let Some(derived_word) = stream.next() {
+        if derived_words.len() >= limits::MAX_PREFIX_COUNT {
+            break;
+        }
+        derived_words.push(std::str::from_utf8(derived_word)?.to_owned());
+    }
+
+    let mut docids = RoaringBitmap::new();
+    let mut words = words.to_vec();
+    for derived_word in derived_words {
+        *words.last_mut().unwrap() = Some(ctx.word_interner.insert(derived_word));
+        docids |= compute_words_phrase_docids(ctx, &words, slop)?;
+    }
+    Ok(docids)
+}
+
+/// Returns the documents in which every pair of words of the phrase is close enough.
+///
+/// This is only an approximation of the phrase: the pairs can be found
+/// in different places of the document. The pairs further apart than the
+/// proximities stored in the database, because of the slop, aren't checked.
+fn compute_phrase_proximity_candidates(
+    ctx: &mut SearchContext<'_>,
+    words: &[Option<Interned<String>>],
+    slop: usize,
+) -> Result<RoaringBitmap> {
     let mut candidates = RoaringBitmap::new();
     for word in words.iter().flatten().copied() {
         if let Some(word_docids) = ctx.word_docids(None, Word::Original(word))? {
@@ -218,7 +288,11 @@ pub fn compute_phrase_docids(
                 .enumerate()
                 .filter_map(|(index, word)| word.as_ref().map(|word| (index, word)))
             {
-                if dist == 0 {
+                let max_proximity = dist + 1 + slop;
+                if max_proximity >= MAX_DISTANCE as usize {
+                    continue;
+                }
+                if max_proximity == 1 {
                     match ctx.get_db_word_pair_proximity_docids(None, s1, s2, 1)? {
                         Some(m) => bitmaps.push(m),
                         // If there are no documents for this pair, there will be no
@@ -227,9 +301,9 @@ pub fn compute_phrase_docids(
                     }
                 } else {
                     let mut bitmap = RoaringBitmap::new();
-                    for dist in 0..=dist {
+                    for proximity in 1..=max_proximity {
                         if let Some(m) =
-                            ctx.get_db_word_pair_proximity_docids(None, s1, s2, dist as u8 + 1)?
+                            ctx.get_db_word_pair_proximity_docids(None, s1, s2, proximity as u8)?
                         {
                             bitmap |= m;
                         }
@@ -258,3 +332,110 @@ pub fn compute_phrase_docids(
     }
     Ok(candidates)
 }
+
+/// The relative positions smaller than this one are stored as is in the word position database,
+/// the bigger ones are bucketed.
+const EXACT_POSITIONS: usize = 16;
+
+/// Removes the candidates that don't contain the words of the phrase right after each other,
+/// or with at most `slop` extra positions between them.
+///
+/// For every position of the first word of the phrase, the position postings of the following
+/// words are intersected, smallest first, which lets the intersection skip most of the bigger
+/// postings. This is only possible when the whole phrase fits in the exact positions, the
+/// candidates in which the phrase could be further in an attribute are kept as is: the buckets
+/// are too coarse to prove that the words aren't next to each other.
+///
+/// The positions aren't scoped to an attribute, so this only tightens the candidates given by
+/// the word pair proximities, it can't replace them.
+fn retain_phrase_positional_matches(
+    ctx: &mut SearchContext<'_>,
+    words: &[Option<Interned<String>>],
+    slop: usize,
+    candidates: RoaringBitmap,
+) -> Result<RoaringBitmap> {
+    // the removed stop words still take a position in the phrase
+    let Some((anchor_offset, anchor)) =
+        words.iter().enumerate().find_map(|(offset, word)| word.map(|word| (offset, word)))
+    else {
+        return Ok(candidates);
+    };
+
+    let mut verified = RoaringBitmap::new();
+    let mut unverified = RoaringBitmap::new();
+    for position in ctx.get_db_word_positions(anchor)? {
+        // the phrase can't start before the beginning of the attribute
+        let Some(start) = (position as usize).checked_sub(anchor_offset) else { continue };
+        let Some(anchor_docids) =
+            ctx.get_db_word_position_docids(Some(&candidates), anchor, position)?
+        else {
+            continue;
+        };
+
+        if start + words.len() + slop > EXACT_POSITIONS {
+            unverified |= anchor_docids;
+            continue;
+        }
+
+        if slop > 0 {
+            let following = &words[anchor_offset + 1..];
+            verified |=
+                sloppy_phrase_docids(ctx, following, position as usize, slop, anchor_docids)?;
+            continue;
+        }
+
+        let mut postings = vec![anchor_docids];
+        for (offset, word) in words.iter().enumerate().skip(anchor_offset + 1) {
+            let Some(word) = *word else { continue };
+            match ctx.get_db_word_position_docids(
+                Some(&candidates),
+                word,
+                (start + offset) as u16,
+            )? {
+                Some(docids) if !docids.is_empty() => postings.push(docids),
+                _ => {
+                    postings.clear();
+                    break;
+                }
+            }
+        }
+
+        if !postings.is_empty() {
+            postings.sort_unstable_by_key(RoaringBitmap::len);
+            verified |= postings.intersection();
+        }
+    }
+
+    Ok(candidates & (verified | unverified))
+}
+
+/// Returns the documents, among the given ones, in which the following words of the phrase come
+/// after the word at the given position, in order, with at most `slop` extra positions in total.
+fn sloppy_phrase_docids(
+    ctx: &mut SearchContext<'_>,
+    following: &[Option<Interned<String>>],
+    previous_position: usize,
+    slop: usize,
+    docids: RoaringBitmap,
+) -> Result<RoaringBitmap> {
+    let Some((word, following)) = following.split_first() else {
+        return Ok(docids);
+    };
+    let Some(word) = *word else {
+        // the removed stop words still take a position in the phrase
+        return sloppy_phrase_docids(ctx, following, previous_position + 1, slop, docids);
+    };
+
+    let mut matching = RoaringBitmap::new();
+    for extra in 0..=slop {
+        let position = previous_position + 1 + extra;
+        match ctx.get_db_word_position_docids(Some(&docids), word, position as u16)? {
+            Some(word_docids) if !word_docids.is_empty() => {
+                matching |=
+                    sloppy_phrase_docids(ctx, following, position, slop - extra, word_docids)?;
+            }
+            _ => (),
+        }
+    }
+    Ok(matching)
+}
@@ -1,13 +1,18 @@
+use std::cmp::Reverse;
+
 use heed::BytesDecode;
 use roaring::RoaringBitmap;
 
+use super::distinct::facet_string_values;
 use super::logger::SearchLogger;
 use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::collation::CollationLocale;
 use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
-use crate::heed_codec::{BytesRefCodec, StrRefCodec};
+use crate::heed_codec::{BytesRefCodec, NumericSortIndexView, StrRefCodec};
+use crate::index::{db_name, main_key};
 use crate::score_details::{self, ScoreDetails};
 use crate::search::facet::{ascending_facet_sort, descending_facet_sort};
-use crate::{FieldId, Index, Result};
+use crate::{FieldId, Index, InternalError, Result, SerializationError};
 
 pub trait RankingRuleOutputIter<'ctx, Query> {
     fn next_bucket(&mut self) -> Result<Option<RankingRuleOutput<Query>>>;
@@ -52,6 +57,8 @@ pub struct Sort<'ctx, Query> {
     original_query: Option<Query>,
     iter: Option<RankingRuleOutputIterWrapper<'ctx, Query>>,
     must_redact: bool,
+    /// The locale ordering the string values, if they are not sorted by their facet keys.
+    collation: Option<CollationLocale>,
 }
 impl<'ctx, Query> Sort<'ctx, Query> {
     pub fn new(
@@ -63,6 +70,7 @@ impl<'ctx, Query> Sort<'ctx, Query> {
         let fields_ids_map = index.fields_ids_map(rtxn)?;
         let field_id = fields_ids_map.id(&field_name);
         let must_redact = Self::must_redact(index, rtxn, &field_name)?;
+        let collation = index.sort_collation(rtxn)?.locale(&field_name);
 
         Ok(Self {
             field_name,
@@ -71,6 +79,7 @@ impl<'ctx, Query> Sort<'ctx, Query> {
             original_query: None,
             iter: None,
             must_redact,
+            collation,
         })
     }
 
@@ -141,45 +150,82 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
                 };
                 let number_iter = number_iter.map(|r| -> Result<_> {
                     let (docids, bytes) = r?;
-                    Ok((
-                        docids,
-                        serde_json::Value::Number(
-                            serde_json::Number::from_f64(
-                                OrderedF64Codec::bytes_decode(bytes).expect("some number"),
-                            )
-                            .expect("too big float"),
-                        ),
-                    ))
+                    let number = OrderedF64Codec::bytes_decode(bytes).map_err(|_| {
+                        SerializationError::Decoding { db_name: Some(db_name::FACET_ID_F64_DOCIDS) }
+                    })?;
+                    Ok((docids, serde_json::Value::Number(json_number(number)?)))
                 });
+                let number_iter = match ctx.index.numeric_sort_index_view(ctx.txn, field_id)? {
+                    // the dense sort index directly gives the rank of every candidate
+                    Some(sort_index) => itertools::Either::Left(
+                        numeric_sort_index_buckets(
+                            sort_index,
+                            parent_candidates,
+                            self.is_ascending,
+                        )?
+                        .into_iter()
+                        .map(Ok),
+                    ),
+                    None => itertools::Either::Right(number_iter),
+                };
                 let string_iter = string_iter.map(|r| -> Result<_> {
                     let (docids, bytes) = r?;
-                    Ok((
-                        docids,
-                        serde_json::Value::String(
-                            StrRefCodec::bytes_decode(bytes).expect("some string").to_owned(),
-                        ),
-                    ))
+                    let string = StrRefCodec::bytes_decode(bytes).map_err(|_| {
+                        SerializationError::Decoding {
+                            db_name: Some(db_name::FACET_ID_STRING_DOCIDS),
+                        }
+                    })?;
+                    Ok((docids, serde_json::Value::String(string.to_owned())))
                 });
+                let string_iter = match self.collation {
+                    // the collation compares the original values instead of the facet keys
+                    Some(locale) => itertools::Either::Left(
+                        collated_string_buckets(
+                            ctx.index,
+                            ctx.txn,
+                            field_id,
+                            parent_candidates,
+                            locale,
+                            self.is_ascending,
+                        )?
+                        .into_iter()
+                        .map(Ok),
+                    ),
+                    None => itertools::Either::Right(string_iter),
+                };
+                let bool_iter = bool_buckets(
+                    ctx.index,
+                    ctx.txn,
+                    field_id,
+                    parent_candidates,
+                    self.is_ascending,
+                )?
+                .into_iter()
+                .map(Ok);
+                // the booleans sort after the strings
+                let values_iter = if self.is_ascending {
+                    itertools::Either::Left(number_iter.chain(string_iter).chain(bool_iter))
+                } else {
+                    itertools::Either::Right(number_iter.chain(bool_iter).chain(string_iter))
+                };
 
                 let query_graph = parent_query.clone();
                 let ascending = self.is_ascending;
                 let field_name = self.field_name.clone();
                 let must_redact = self.must_redact;
-                RankingRuleOutputIterWrapper::new(Box::new(number_iter.chain(string_iter).map(
-                    move |r| {
-                        let (docids, value) = r?;
-                        Ok(RankingRuleOutput {
-                            query: query_graph.clone(),
-                            candidates: docids,
-                            score: ScoreDetails::Sort(score_details::Sort {
-                                field_name: field_name.clone(),
-                                ascending,
-                                redacted: must_redact,
-                                value,
-                            }),
-                        })
-                    },
-                )))
+                RankingRuleOutputIterWrapper::new(Box::new(values_iter.map(move |r| {
+                    let (docids, value) = r?;
+                    Ok(RankingRuleOutput {
+                        query: query_graph.clone(),
+                        candidates: docids,
+                        score: ScoreDetails::Sort(score_details::Sort {
+                            field_name: field_name.clone(),
+                            ascending,
+                            redacted: must_redact,
+                            value,
+                        }),
+                    })
+                })))
             }
             None => RankingRuleOutputIterWrapper::new(Box::new(std::iter::empty())),
         };
@@ -224,3 +270,116 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
         self.iter = None;
     }
 }
+
+/// Groups the candidates by their numeric value using the dense sort index of the field,
+/// in the order of the sort.
+///
+/// Like the facet sort, a document with several values is only returned with the first
+/// of its values in the order of the sort. The candidates without any numeric value are ignored.
+/// Only the ordinals of the candidates are read from the index.
+fn numeric_sort_index_buckets(
+    sort_index: NumericSortIndexView<'_>,
+    candidates: &RoaringBitmap,
+    is_ascending: bool,
+) -> Result<Vec<(RoaringBitmap, serde_json::Value)>> {
+    let ordinal = |docid| {
+        if is_ascending {
+            sort_index.min_ordinal(docid)
+        } else {
+            sort_index.max_ordinal(docid)
+        }
+    };
+    let mut ordinals: Vec<(u32, u32)> = candidates
+        .iter()
+        .filter_map(|docid| ordinal(docid).map(|ordinal| (ordinal, docid)))
+        .collect();
+    if is_ascending {
+        ordinals.sort_unstable();
+    } else {
+        ordinals.sort_unstable_by_key(|&(ordinal, docid)| (Reverse(ordinal), docid));
+    }
+
+    ordinals
+        .chunk_by(|(left, _), (right, _)| left == right)
+        .map(|chunk| -> Result<_> {
+            let docids = chunk.iter().map(|&(_, docid)| docid);
+            let docids = RoaringBitmap::from_sorted_iter(docids).unwrap();
+            let value =
+                sort_index.value(chunk[0].0).ok_or(InternalError::DatabaseMissingEntry {
+                    db_name: db_name::MAIN,
+                    key: Some(main_key::NUMERIC_SORT_INDEX_PREFIX),
+                })?;
+            Ok((docids, serde_json::Value::Number(json_number(value)?)))
+        })
+        .collect()
+}
+
+/// Converts a sorted float to a JSON number, failing on the non-finite floats.
+fn json_number(value: f64) -> Result<serde_json::Number> {
+    serde_json::Number::from_f64(value).ok_or_else(|| InternalError::NonFiniteFloat(value).into())
+}
+
+/// Groups the candidates by their string value, in the order of the collation of the locale.
+///
+/// Like the facet sort, a document with several values is only returned with the first
+/// of its values in the order of the sort. The original values of every candidate are read
+/// and compared, as the facet keys lost their case.
+fn collated_string_buckets(
+    index: &Index,
+    rtxn: &heed::RoTxn<'_>,
+    field_id: FieldId,
+    candidates: &RoaringBitmap,
+    locale: CollationLocale,
+    is_ascending: bool,
+) -> Result<Vec<(RoaringBitmap, serde_json::Value)>> {
+    let mut keys = Vec::new();
+    for docid in candidates {
+        let mut first: Option<(Vec<u32>, &str)> = None;
+        for result in facet_string_values(docid, field_id, index, rtxn)? {
+            let (_, original) = result?;
+            let key = locale.sort_key(original);
+            let is_first = match &first {
+                Some((first_key, _)) if is_ascending => key < *first_key,
+                Some((first_key, _)) => key > *first_key,
+                None => true,
+            };
+            if is_first {
+                first = Some((key, original));
+            }
+        }
+        if let Some((key, original)) = first {
+            keys.push((key, original, docid));
+        }
+    }
+    keys.sort_unstable_by(|(left, ..), (right, ..)| left.cmp(right));
+    if !is_ascending {
+        keys.reverse();
+    }
+
+    Ok(keys
+        .chunk_by(|(left, ..), (right, ..)| left == right)
+        .map(|chunk| {
+            let docids = RoaringBitmap::from_iter(chunk.iter().map(|&(_, _, docid)| docid));
+            (docids, serde_json::Value::String(chunk[0].1.to_string()))
+        })
+        .collect())
+}
+
+/// Groups the candidates by their boolean value, `false` before `true` in the ascending order.
+fn bool_buckets(
+    index: &Index,
+    rtxn: &heed::RoTxn<'_>,
+    field_id: FieldId,
+    candidates: &RoaringBitmap,
+    is_ascending: bool,
+) -> Result<Vec<(RoaringBitmap, serde_json::Value)>> {
+    let values = if is_ascending { [false, true] } else { [true, false] };
+    let mut buckets = Vec::new();
+    for value in values {
+        let docids = index.bool_faceted_documents_ids(rtxn, field_id, value)? & candidates;
+        if !docids.is_empty() {
+            buckets.push((docids, serde_json::Value::Bool(value)));
+        }
+    }
+    Ok(buckets)
+}
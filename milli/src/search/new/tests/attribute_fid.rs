@@ -157,3 +157,38 @@ fn test_attribute_fid_ngrams() {
     let document_ids_scores: Vec<_> = documents_ids.iter().zip(document_scores).collect();
     insta::assert_snapshot!(format!("{document_ids_scores:#?}"));
 }
+
+#[test]
+fn test_attribute_fid_weights() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_weighted_searchable_fields(vec![
+                ("title".to_owned(), None),
+                ("description".to_owned(), None),
+                ("plot".to_owned(), Some(3)),
+            ]);
+        })
+        .unwrap();
+
+    // the plot is now as important as the title
+    db_snap!(index, searchable_fields, @r###"["title", "description", "plot"]"###);
+    db_snap!(index, fieldids_weights_map, @r###"
+    fid weight
+    1   0   |
+    2   3   |
+    3   0   |
+    "###);
+
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("the quick brown fox jumps over the lazy dog");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+
+    // the documents matching in the title and in the plot only come first
+    let mut first_documents_ids = documents_ids[..3].to_vec();
+    first_documents_ids.sort_unstable();
+    assert_eq!(first_documents_ids, vec![0, 2, 11]);
+}
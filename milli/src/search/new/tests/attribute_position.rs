@@ -1,4 +1,5 @@
 use crate::index::tests::TempIndex;
+use crate::position_decay::PositionDecay;
 use crate::{db_snap, Criterion, Search, SearchResult, TermsMatchingStrategy};
 
 fn create_index() -> TempIndex {
@@ -194,3 +195,42 @@ fn test_attribute_position_ngrams() {
     let document_ids_scores: Vec<_> = documents_ids.iter().zip(document_scores).collect();
     insta::assert_snapshot!(format!("{document_ids_scores:#?}"));
 }
+
+#[test]
+fn test_attribute_position_decay() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Attribute]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "a b c d e f g h i j fox" },
+            { "id": 1, "text": "a b c d e fox" },
+            { "id": 2, "text": "a b c fox" },
+            { "id": 3, "text": "fox" },
+        ]))
+        .unwrap();
+
+    let search = |index: &TempIndex| {
+        let txn = index.read_txn().unwrap();
+        let mut s = Search::new(&txn, index);
+        s.query("fox");
+        s.execute().unwrap().documents_ids
+    };
+
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[3, 2, 1, 0]");
+
+    // the words at the positions 3 and 5 share the same cost with the linear decay
+    index.update_settings(|s| s.set_position_decay(PositionDecay::Linear)).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[3, 1, 2, 0]");
+
+    index.update_settings(|s| s.set_position_decay(PositionDecay::None)).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[0, 1, 2, 3]");
+
+    index.update_settings(|s| s.reset_position_decay()).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[3, 2, 1, 0]");
+}
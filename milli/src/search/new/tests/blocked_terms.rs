@@ -0,0 +1,94 @@
+/*!
+This module tests the `blockedTerms` setting:
+
+1. the blocked words are dropped from the queries, or reject them in the `reject` mode
+2. the blocked words keep their position in the phrases
+3. the blocked words can't be found through a typo, a prefix, a split, an ngram or a synonym
+4. the blocked words are normalized like the words of the queries
+*/
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use meili_snap::insta;
+
+use crate::blocked_terms::BlockedTermsMode;
+use crate::index::tests::TempIndex;
+use crate::{Result, Search};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_blocked_terms(BTreeSet::from(["Scoundrel".to_owned()]));
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "a scoundrel stole it" },
+            { "id": 1, "text": "a scout stole it" },
+            { "id": 2, "text": "a scout found it" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Result<Vec<u32>> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    let mut documents_ids = s.execute()?.documents_ids;
+    documents_ids.sort_unstable();
+    Ok(documents_ids)
+}
+
+#[test]
+fn blocked_words_are_dropped() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "scoundrel stole").unwrap()), @"[0, 1]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "SCOUNDREL stole").unwrap()), @"[0, 1]");
+    // like a query made of stop words, a query made of blocked words is a placeholder search
+    insta::assert_snapshot!(format!("{:?}", search(&index, "scoundrel").unwrap()), @"[0, 1, 2]");
+    // the other words of the phrase keep their positions
+    insta::assert_snapshot!(format!("{:?}", search(&index, "\"a scoundrel stole\"").unwrap()), @"[0, 1]");
+
+    index.update_settings(|s| s.reset_blocked_terms()).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "scoundrel stole").unwrap()), @"[0]");
+}
+
+#[test]
+fn blocked_words_reject_the_query() {
+    let index = create_index();
+    index.update_settings(|s| s.set_blocked_terms_mode(BlockedTermsMode::Reject)).unwrap();
+
+    let error = search(&index, "scoundrel stole").unwrap_err();
+    insta::assert_snapshot!(error, @"The query contains the blocked term `scoundrel`.");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "scout stole").unwrap()), @"[1]");
+}
+
+#[test]
+fn blocked_words_are_not_derived() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_synonyms(BTreeMap::from([("rogue".to_owned(), vec!["scoundrel".to_owned()])]))
+        })
+        .unwrap();
+
+    // a typo
+    insta::assert_snapshot!(format!("{:?}", search(&index, "scoundrl").unwrap()), @"[]");
+    // a prefix
+    insta::assert_snapshot!(format!("{:?}", search(&index, "scoundr").unwrap()), @"[]");
+    // a split of the word
+    insta::assert_snapshot!(format!("{:?}", search(&index, "scoundrelstole").unwrap()), @"[]");
+    // an ngram of the words
+    insta::assert_snapshot!(format!("{:?}", search(&index, "scoundr el").unwrap()), @"[]");
+    // a synonym
+    insta::assert_snapshot!(format!("{:?}", search(&index, "rogue").unwrap()), @"[]");
+    // the other words are still derived
+    insta::assert_snapshot!(format!("{:?}", search(&index, "scoot").unwrap()), @"[1, 2]");
+}
@@ -0,0 +1,85 @@
+/*!
+This module tests the `relevancy:bm25` ranking rule:
+
+1. the documents containing the words of the query more often are ranked first
+2. the longer documents are ranked after the shorter ones for the same number of occurrences
+3. the frequencies of the existing documents are indexed when the ranking rule is added
+4. the frequencies are deleted when the ranking rule is removed
+*/
+
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{Rank, ScoreDetails, ScoringStrategy};
+use crate::{Criterion, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the cat" },
+            { "id": 1, "text": "cat cat cat dog" },
+            { "id": 2, "text": "dog" },
+            { "id": 3, "text": "cat and a very long text about many other things and animals" },
+            { "id": 4, "text": "kitten" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn bm25() {
+    let index = create_index();
+    index.update_settings(|s| s.set_criteria(vec![Criterion::Bm25])).unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let rank = |rank| vec![ScoreDetails::Bm25(Rank { rank, max_rank: 100 })];
+
+    let mut s = Search::new(&txn, &index);
+    s.query("cat");
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 0, 3]");
+    assert_eq!(document_scores, vec![rank(71), rank(57), rank(25)]);
+
+    // the placeholder search ignores the ranking rule
+    let mut s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4]");
+}
+
+#[test]
+fn bm25_frequencies_follow_the_ranking_rules() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    assert!(index.word_frequency_docids.is_empty(&txn).unwrap());
+    assert!(index.document_length_docids.is_empty(&txn).unwrap());
+    drop(txn);
+
+    index.update_settings(|s| s.set_criteria(vec![Criterion::Bm25])).unwrap();
+    let txn = index.read_txn().unwrap();
+    let cats = index.word_frequency_docids.get(&txn, &("cat", 3)).unwrap().unwrap();
+    assert_eq!(cats.iter().collect::<Vec<_>>(), vec![1]);
+    let lengths = index.document_length_docids.get(&txn, &12).unwrap().unwrap();
+    assert_eq!(lengths.iter().collect::<Vec<_>>(), vec![3]);
+    drop(txn);
+
+    index.delete_documents(vec!["1".to_owned()]);
+    let txn = index.read_txn().unwrap();
+    assert!(index.word_frequency_docids.get(&txn, &("cat", 3)).unwrap().is_none());
+    drop(txn);
+
+    index.update_settings(|s| s.reset_criteria()).unwrap();
+    let txn = index.read_txn().unwrap();
+    assert!(index.word_frequency_docids.is_empty(&txn).unwrap());
+    assert!(index.document_length_docids.is_empty(&txn).unwrap());
+}
@@ -0,0 +1,92 @@
+//! This module tests the boolean operators written in the search query:
+//! 1. `AND` makes the terms on both of its sides mandatory, whatever the terms matching strategy
+//! 2. `OR` makes the next term an alternative to the previous one
+//! 3. `NOT` excludes the documents containing the next word or phrase, like the `-` prefix
+//! 4. The operators must be written in uppercase, otherwise they are searched as words
+//! 5. The `+` prefix makes a word mandatory, whatever the terms matching strategy
+
+use crate::index::tests::TempIndex;
+use crate::{Search, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "red leather shoes" },
+            { "id": 1, "text": "red shoes in canvas" },
+            { "id": 2, "text": "blue shoes" },
+            { "id": 3, "text": "red hat" },
+            { "id": 4, "text": "red shoes and a leather bag" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str, strategy: TermsMatchingStrategy) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(strategy);
+    let mut docids = s.execute().unwrap().documents_ids;
+    docids.sort_unstable();
+    docids
+}
+
+#[test]
+fn test_and_operator() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "red shoes", TermsMatchingStrategy::Last), vec![0, 1, 3, 4]);
+    assert_eq!(search(&index, "red AND shoes", TermsMatchingStrategy::Last), vec![0, 1, 4]);
+    assert_eq!(search(&index, "red AND shoes", TermsMatchingStrategy::All), vec![0, 1, 4]);
+}
+
+#[test]
+fn test_or_operator() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "hat OR canvas", TermsMatchingStrategy::All), vec![1, 3]);
+    assert_eq!(search(&index, "red hat OR bag", TermsMatchingStrategy::All), vec![3, 4]);
+    assert_eq!(search(&index, "hat OR \"leather bag\"", TermsMatchingStrategy::All), vec![3, 4]);
+    assert_eq!(search(&index, "hat OR canvas OR blue", TermsMatchingStrategy::All), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_not_operator() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "\"red shoes\" NOT leather", TermsMatchingStrategy::All), vec![1]);
+    assert_eq!(search(&index, "shoes AND NOT leather", TermsMatchingStrategy::All), vec![1, 2]);
+    assert_eq!(
+        search(&index, "shoes NOT \"leather bag\"", TermsMatchingStrategy::All),
+        vec![0, 1, 2]
+    );
+}
+
+#[test]
+fn test_lowercase_operators_are_words() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "red and shoes", TermsMatchingStrategy::All), vec![4]);
+    // an operator without any term to combine is searched as a word
+    assert_eq!(search(&index, "AND shoes", TermsMatchingStrategy::All), vec![4]);
+}
+
+#[test]
+fn test_required_words() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "red leather shoes", TermsMatchingStrategy::Last), vec![0, 1, 3, 4]);
+    assert_eq!(search(&index, "red +leather shoes", TermsMatchingStrategy::Last), vec![0, 4]);
+    assert_eq!(search(&index, "+red +shoes", TermsMatchingStrategy::Last), vec![0, 1, 4]);
+    // the `+` must start a word
+    assert_eq!(search(&index, "red leather+shoes", TermsMatchingStrategy::Last), vec![0, 1, 3, 4]);
+}
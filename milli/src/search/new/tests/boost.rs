@@ -0,0 +1,90 @@
+/*!
+This module tests the `^` boosts of the query terms:
+
+1. the boost is not searched for, it only weighs the word it follows
+2. the attribute of a boosted term weighs more than the ones of the other terms
+3. the proximity of a pair of terms is as boosted as the most boosted of the two
+*/
+
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index(criteria: Vec<Criterion>) -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "description".to_owned()]);
+            s.set_criteria(criteria);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "rust", "description": "game" },
+            { "id": 1, "title": "game", "description": "rust" },
+            { "id": 2, "title": "quick fox and then a long way to the lazy dog" },
+            { "id": 3, "title": "quick and then a long way to the fox dog" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn boost_is_not_searched() {
+    let index = create_index(vec![Criterion::Words]);
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("rust^3 game");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+}
+
+#[test]
+fn boost_attribute() {
+    let index = create_index(vec![Criterion::Words, Criterion::Attribute]);
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("rust game");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+
+    // the documents with the boosted term in the title come first
+    let mut s = Search::new(&txn, &index);
+    s.query("rust game^2.5");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 0]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("rust^2.5 game");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+}
+
+#[test]
+fn boost_proximity() {
+    let index = create_index(vec![Criterion::Words, Criterion::Proximity]);
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick fox dog");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 3]");
+
+    // the documents where the boosted term is close to its neighbour come first
+    let mut s = Search::new(&txn, &index);
+    s.query("quick fox dog^3");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("quick^3 fox dog");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 3]");
+}
@@ -0,0 +1,60 @@
+/*!
+This module tests the sort on the fields computed at search time:
+
+1. the documents are sorted by the value of the expression, in both directions
+2. the documents on which the expression can't be evaluated are returned last
+3. a computed field can be sorted on without being a sortable attribute
+*/
+
+use maplit::btreemap;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{AscDesc, Criterion, Member, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Sort]);
+            s.set_computed_fields(btreemap! {
+                "finalPrice".to_owned() => "price * (1 - discount)".to_owned(),
+            });
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "price": 100, "discount": 0.5 },
+            { "id": 1, "price": 80, "discount": 0 },
+            { "id": 2, "price": 60, "discount": 0.1 },
+            { "id": 3, "price": 40 },
+            { "id": 4, "price": 200, "discount": 0.75 },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn sort_on_computed_field() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field("finalPrice".to_owned()))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    // 50.0, 50.0, 54.0, 80.0, then the document without discount
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 4, 2, 1, 3]");
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field("finalPrice".to_owned()))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 2, 0, 4, 3]");
+
+    // the regular fields must still be declared sortable
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field("price".to_owned()))]);
+    assert!(s.execute().is_err());
+}
@@ -116,6 +116,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 4,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -130,6 +135,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 1,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 3,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -144,6 +154,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 2,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 2,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -158,6 +173,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 2,
+                    rank: Rank {
+                        rank: 3,
+                        max_rank: 3,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -226,6 +246,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 4,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -273,6 +298,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 4,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -287,6 +317,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 1,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 3,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -326,6 +361,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 4,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -340,6 +380,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 1,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 3,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -354,6 +399,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 2,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 2,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -384,6 +434,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 4,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -398,6 +453,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 1,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 3,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -412,6 +472,11 @@ fn degraded_search_and_score_details() {
                 Typo {
                     typo_count: 2,
                     max_typo_count: 3,
+                    rank: Rank {
+                        rank: 2,
+                        max_rank: 4,
+                    },
+                    terms: [],
                 },
             ),
         ],
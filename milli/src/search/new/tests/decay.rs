@@ -0,0 +1,100 @@
+/*!
+This module tests the decay ranking rule:
+
+1. the documents are ranked by the decayed boost of the distance between their value and the origin
+2. the boost decays with the function of the rule, and is halved at one scale from the origin
+3. the origin defaults to the current date, to which the dates are compared
+4. the documents without a value, or with an invalid one, come last
+*/
+
+use meili_snap::insta;
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, Rank, ScoreDetails, ScoringStrategy};
+use crate::{Criterion, DecayFunction, DecayOrigin, DecayScale, HalfLife, Search, SearchResult};
+
+fn create_index(criterion: Criterion) -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![criterion]);
+        })
+        .unwrap();
+
+    let now = OffsetDateTime::now_utc();
+    let timestamp = |age: Duration| (now - age).unix_timestamp();
+    let rfc3339 = |age: Duration| (now - age).format(&Rfc3339).unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "hello", "views": 1000, "date": timestamp(Duration::days(2)) },
+            { "id": 1, "text": "hello", "views": 900, "date": rfc3339(Duration::days(1)) },
+            { "id": 2, "text": "hello", "views": 500, "date": timestamp(Duration::ZERO) },
+            { "id": 3, "text": "hello" },
+            { "id": 4, "text": "hello", "views": 1100, "date": "yesterday" },
+            { "id": 5, "text": "hello", "views": "a lot", "date": rfc3339(Duration::days(30)) },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn decay_on_numbers() {
+    let criterion = Criterion::Decay {
+        field: "views".to_owned(),
+        scale: DecayScale::Number(100),
+        function: DecayFunction::Linear,
+        origin: DecayOrigin::Number(1000),
+    };
+    let index = create_index(criterion);
+    let txn = index.read_txn().unwrap();
+
+    let rank = |rank| {
+        vec![ScoreDetails::Decay(score_details::Decay {
+            rule: "decay(views, scale=100, type=linear, origin=1000)".to_owned(),
+            rank: Rank { rank, max_rank: 100 },
+        })]
+    };
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 4, 2, 3, 5]");
+    assert_eq!(document_scores, vec![rank(100), rank(50), rank(50), rank(1), rank(0), rank(0)]);
+
+    let mut s = Search::new(&txn, &index);
+    s.query("hello");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 4, 2, 3, 5]");
+}
+
+#[test]
+fn decay_on_dates() {
+    let criterion = Criterion::Decay {
+        field: "date".to_owned(),
+        scale: DecayScale::Duration(HalfLife { seconds: 24 * 60 * 60 }),
+        function: DecayFunction::Gauss,
+        origin: DecayOrigin::Now,
+    };
+    let index = create_index(criterion);
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 1, 0, 5, 3, 4]");
+    let ranks: Vec<_> = document_scores
+        .iter()
+        .map(|scores| match scores.as_slice() {
+            [ScoreDetails::Decay(decay)] => decay.rank.rank,
+            _ => panic!("unexpected score details: {scores:?}"),
+        })
+        .collect();
+    // the boost at two days is 0.5^(2^2) with the gauss function
+    assert_eq!(ranks, vec![100, 50, 6, 1, 0, 0]);
+}
@@ -0,0 +1,109 @@
+/*!
+This module tests the diversification of the results:
+
+1. at most `max_per_value` results share the same value of the attribute
+2. the best documents for each value are kept
+3. the documents without the attribute are not limited
+4. the pages of results are consistent with each other
+5. the attribute must be filterable
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{AscDesc, Criterion, Member, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(hashset! { S("domain") });
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_criteria(vec![Criterion::Words, Criterion::Sort]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "hello", "domain": "a.com", "rank": 0 },
+            { "id": 1, "text": "hello", "domain": "a.com", "rank": 1 },
+            { "id": 2, "text": "hello", "domain": "a.com", "rank": 2 },
+            { "id": 3, "text": "hello", "domain": "b.com", "rank": 3 },
+            { "id": 4, "text": "hello", "domain": "a.com", "rank": 4 },
+            { "id": 5, "text": "hello", "domain": "b.com", "rank": 5 },
+            { "id": 6, "text": "hello", "domain": "c.com", "rank": 6 },
+            { "id": 7, "text": "hello", "rank": 7 },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn diversify_placeholder() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    // without any ranking rule, the documents are returned in the order of their ids
+    let mut s = Search::new(&txn, &index);
+    s.diversify(S("domain"), 2);
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 3, 5, 6, 7]");
+    insta::assert_snapshot!(format!("{candidates:?}"), @"RoaringBitmap<[0, 1, 3, 5, 6, 7]>");
+
+    let mut s = Search::new(&txn, &index);
+    s.diversify(S("domain"), 2);
+    s.offset(2);
+    s.limit(2);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 5]");
+}
+
+#[test]
+fn diversify_sorted() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("hello");
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank")))]);
+    s.diversify(S("domain"), 2);
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[7, 6, 5, 4, 3, 2]");
+    insta::assert_snapshot!(format!("{candidates:?}"), @"RoaringBitmap<[2, 3, 4, 5, 6, 7]>");
+
+    // the counts of the previous pages are kept
+    let mut pages = vec![];
+    for offset in [0, 2, 4] {
+        let mut s = Search::new(&txn, &index);
+        s.query("hello");
+        s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank")))]);
+        s.diversify(S("domain"), 2);
+        s.offset(offset);
+        s.limit(2);
+        pages.extend(s.execute().unwrap().documents_ids);
+    }
+    insta::assert_snapshot!(format!("{pages:?}"), @"[7, 6, 5, 4, 3, 2]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("hello");
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank")))]);
+    s.diversify(S("domain"), 1);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[7, 6, 5, 4]");
+}
+
+#[test]
+fn diversify_not_filterable() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.diversify(S("rank"), 2);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `rank` is not filterable and thus, cannot be used to diversify the results. Available filterable attributes are: `domain`.");
+}
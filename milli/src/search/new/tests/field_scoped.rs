@@ -0,0 +1,80 @@
+//! This module tests the terms scoped to a searchable attribute in the search query:
+//! 1. `title:rust` only matches the documents containing `rust` in their `title`
+//! 2. a phrase can be scoped too, e.g. `title:"dark tower"`
+//! 3. the scoped terms are mandatory, whatever the terms matching strategy
+//! 4. a prefix that doesn't name a searchable attribute is searched as a word
+
+use crate::index::tests::TempIndex;
+use crate::{Search, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["title".to_owned(), "author".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "the dark tower", "author": "stephen king" },
+            { "id": 1, "title": "a king in the dark", "author": "tower publishing" },
+            { "id": 2, "title": "the rust book", "author": "steve klabnik" },
+            { "id": 3, "title": "rusted tower", "author": "rust king" },
+            { "id": 4, "title": "title note", "author": "nobody" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str, strategy: TermsMatchingStrategy) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(strategy);
+    let mut docids = s.execute().unwrap().documents_ids;
+    docids.sort_unstable();
+    docids
+}
+
+#[test]
+fn test_scoped_word() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "king", TermsMatchingStrategy::All), vec![0, 1, 3]);
+    assert_eq!(search(&index, "author:king", TermsMatchingStrategy::All), vec![0, 3]);
+    assert_eq!(search(&index, "title:king", TermsMatchingStrategy::All), vec![1]);
+    // the last word is still a prefix
+    assert_eq!(search(&index, "title:rust", TermsMatchingStrategy::All), vec![2, 3]);
+}
+
+#[test]
+fn test_scoped_phrase() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "title:\"dark tower\"", TermsMatchingStrategy::All), vec![0]);
+    assert_eq!(
+        search(&index, "title:\"dark tower\" author:king", TermsMatchingStrategy::All),
+        vec![0]
+    );
+    assert_eq!(search(&index, "author:\"dark tower\"", TermsMatchingStrategy::All), vec![]);
+}
+
+#[test]
+fn test_scoped_terms_are_mandatory() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "tower author:king", TermsMatchingStrategy::Last), vec![0, 3]);
+    assert_eq!(search(&index, "tower king", TermsMatchingStrategy::Last), vec![0, 1, 3]);
+}
+
+#[test]
+fn test_unknown_field_is_searched() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "note:nobody", TermsMatchingStrategy::All), vec![4]);
+    // a whitespace after the colon doesn't scope anything
+    assert_eq!(search(&index, "title: nobody", TermsMatchingStrategy::All), vec![4]);
+}
@@ -307,3 +307,47 @@ fn geo_sort_without_any_geo_faceted_documents() {
     insta::assert_snapshot!(format!("{ids:?}"), @"[0, 2, 3]");
     insta::assert_snapshot!(format!("{scores:#?}"));
 }
+
+#[test]
+fn geo_sort_with_bucket_width_and_max_distance() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_sortable_fields(hashset! { S("_geo"), S("rank") });
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "rank": 3, "_geo": { "lat": 0, "lng": 0 } },
+            { "id": 1, "rank": 1, "_geo": { "lat": 0.001, "lng": 0 } },
+            { "id": 2, "rank": 2, "_geo": { "lat": 0.002, "lng": 0 } },
+            { "id": 3, "rank": 0, "_geo": { "lat": 0.01, "lng": 0 } },
+            { "id": 4, "rank": 0, "_geo": { "lat": 1, "lng": 0 } },
+            { "id": 5, "rank": -1 },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    let rank = AscDesc::Asc(Member::Field(S("rank")));
+    s.sort_criteria(vec![AscDesc::Asc(Member::Geo([0., 0.])), rank.clone()]);
+    let (ids, _) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[0, 1, 2, 3, 4, 5]");
+
+    // the documents less than 500m away are sorted by rank
+    s.geo_bucket_width(Some(500.));
+    let (ids, _) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[1, 2, 0, 3, 4, 5]");
+
+    // the documents more than 2km away are sorted with the ones without a geo point
+    s.geo_max_distance(Some(2000.));
+    let (ids, _) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[1, 2, 0, 3, 5, 4]");
+
+    s.geo_bucket_width(None);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Geo([0., 0.])), rank]);
+    let (ids, _) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3, 2, 1, 0, 5, 4]");
+}
@@ -1,4 +1,8 @@
+use std::collections::BTreeMap;
+
 use crate::index::tests::TempIndex;
+use crate::index::DEFAULT_MIN_WORD_LEN_ONE_TYPO;
+use crate::language_preset::LanguagePreset;
 use crate::{Search, SearchResult};
 
 #[test]
@@ -21,3 +25,97 @@ fn test_kanji_language_detection() {
 
     insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
 }
+
+#[test]
+fn test_language_preset() {
+    let index = TempIndex::new();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "東京のお寿司。" },
+            { "id": 1, "title": "大阪のたこ焼き。" }
+        ]))
+        .unwrap();
+
+    // the settings given in the same update take precedence over the preset
+    index
+        .update_settings(|s| {
+            s.set_language_preset(LanguagePreset::Japanese);
+            s.set_min_word_len_one_typo(4);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    assert_eq!(index.language_preset(&txn).unwrap(), Some(LanguagePreset::Japanese));
+    let stop_words = index.stop_words(&txn).unwrap().unwrap();
+    assert!(stop_words.contains("の"));
+    assert!(!index.authorize_typos(&txn).unwrap());
+    assert_eq!(index.min_word_len_one_typo(&txn).unwrap(), 4);
+
+    let mut search = Search::new(&txn, &index);
+    search.query("東京");
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+    drop(txn);
+
+    // resetting the preset resets the settings it bundles
+    index.update_settings(|s| s.reset_language_preset()).unwrap();
+
+    let txn = index.read_txn().unwrap();
+    assert_eq!(index.language_preset(&txn).unwrap(), None);
+    assert!(index.stop_words(&txn).unwrap().is_none());
+    assert!(index.authorize_typos(&txn).unwrap());
+    assert_eq!(index.min_word_len_one_typo(&txn).unwrap(), DEFAULT_MIN_WORD_LEN_ONE_TYPO);
+}
+
+#[test]
+fn test_field_languages() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_field_languages(BTreeMap::from([
+                ("title_en".to_string(), LanguagePreset::English),
+                ("title_fr".to_string(), LanguagePreset::French),
+            ]));
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title_en": "the best of the city", "title_fr": "le meilleur de la ville" },
+            { "id": 1, "title_en": "le mans race", "title_fr": "la course du mans" }
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    assert_eq!(index.field_languages(&txn).unwrap().len(), 2);
+
+    // `le` is a french stop word, it is only removed from the french field
+    let mut search = Search::new(&txn, &index);
+    search.query("le");
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    let mut search = Search::new(&txn, &index);
+    search.query("ville");
+    search.languages(vec![LanguagePreset::English]);
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    let mut search = Search::new(&txn, &index);
+    search.query("ville");
+    search.languages(vec![LanguagePreset::French]);
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+    drop(txn);
+
+    // the fields are tokenized like the others once their language is removed
+    index.update_settings(|s| s.reset_field_languages()).unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut search = Search::new(&txn, &index);
+    search.query("le");
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 0]");
+}
@@ -0,0 +1,93 @@
+/*!
+This module tests the `minPrefixLength` setting:
+
+1. by default, the last word of the query is searched as a prefix whatever its length
+2. the last words shorter than the minimum length are only searched as words
+3. the explicit `*` wildcards are always expanded
+4. the prefixes shorter than the minimum length are not cached
+*/
+
+use meili_snap::insta;
+
+use crate::documents::documents_batch_reader_from_objects;
+use crate::index::tests::TempIndex;
+use crate::Search;
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "hello world" },
+            { "id": 1, "text": "help me" },
+            { "id": 2, "text": "he said" },
+            { "id": 3, "text": "helicopter" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    let mut documents_ids = s.execute().unwrap().documents_ids;
+    documents_ids.sort_unstable();
+    documents_ids
+}
+
+#[test]
+fn default_min_prefix_length() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "h")), @"[0, 1, 2, 3]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "hel")), @"[0, 1, 3]");
+}
+
+#[test]
+fn short_words_are_not_expanded() {
+    let index = create_index();
+    index.update_settings(|s| s.set_min_prefix_length(3)).unwrap();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "h")), @"[]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "he")), @"[2]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "hel")), @"[0, 1, 3]");
+    // only the last word of the query can be a prefix
+    insta::assert_snapshot!(format!("{:?}", search(&index, "he said")), @"[2]");
+    // the wildcards are explicit and always expanded
+    insta::assert_snapshot!(format!("{:?}", search(&index, "he*")), @"[0, 1, 2, 3]");
+
+    index.update_settings(|s| s.reset_min_prefix_length()).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "he")), @"[0, 1, 2, 3]");
+}
+
+#[test]
+fn short_prefixes_are_not_cached() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_prefix_cache_size(20);
+            s.set_min_prefix_length(2);
+        })
+        .unwrap();
+
+    // the words prefixes are only computed when enough words share them
+    let documents = (0..150u8).map(|i| {
+        let word = format!("ha{}{}", char::from(b'a' + i / 26), char::from(b'a' + i % 26));
+        serde_json::json!({ "id": i, "text": word }).as_object().unwrap().clone()
+    });
+    index.add_documents(documents_batch_reader_from_objects(documents)).unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    assert_eq!(index.prefix_cache_entry(&rtxn, "h").unwrap(), None);
+    assert!(index.prefix_cache_entry(&rtxn, "ha").unwrap().is_some());
+}
@@ -1,19 +1,44 @@
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod blocked_terms;
+pub mod bm25;
+pub mod boolean_operators;
+pub mod boost;
+pub mod computed_fields;
 pub mod cutoff;
+pub mod decay;
 pub mod distinct;
+pub mod diversify;
 pub mod exactness;
+pub mod field_scoped;
 pub mod geo_sort;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 pub mod language;
+pub mod min_prefix_length;
 pub mod ngram_split_words;
+pub mod phonetic;
+pub mod phrase;
+pub mod phrase_fallback;
+pub mod prefix_cache;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod query_interpretation;
+pub mod query_limits;
+pub mod query_rewrites;
+pub mod query_synonyms;
+pub mod query_term_locations;
+pub mod ranking_rule_plugin;
+pub mod recency;
+pub mod regex_terms;
 pub mod sort;
+pub mod sort_expression;
 pub mod stop_words;
+pub mod tie_breaker;
+pub mod timings;
 pub mod typo;
 pub mod typo_proximity;
+pub mod wildcard;
 pub mod words_tms;
 
 fn collect_field_values(
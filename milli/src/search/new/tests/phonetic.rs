@@ -0,0 +1,87 @@
+/*!
+This module tests the phonetic matching, enabled with the `enablePhonetic` typo setting:
+
+1. the words of the query also match the words of the index sharing their Soundex key
+2. even when the word is too short to allow any typo
+3. the phonetic derivations are ranked like two-typo derivations
+4. the phonetic FST is rebuilt when the setting changes and when documents are added
+5. the phonetic matching is disabled with the typo tolerance and on the `disableOnWords` words
+*/
+
+use std::collections::BTreeSet;
+
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "john smith" },
+            { "id": 1, "text": "jon snow" },
+            { "id": 2, "text": "joan of arc" },
+            { "id": 3, "text": "jane doe" },
+            { "id": 4, "text": "mike" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    documents_ids
+}
+
+fn sorted(documents_ids: Vec<u32>) -> BTreeSet<u32> {
+    documents_ids.into_iter().collect()
+}
+
+#[test]
+fn phonetic_derivations() {
+    let index = create_index();
+    assert_eq!(search(&index, "jon"), vec![1]);
+
+    index.update_settings(|s| s.set_enable_phonetic(true)).unwrap();
+    let documents_ids = search(&index, "jon");
+    // the exact match comes first
+    assert_eq!(documents_ids[0], 1);
+    assert_eq!(sorted(documents_ids), BTreeSet::from([0, 1, 2, 3]));
+    assert_eq!(search(&index, "smyth"), vec![0]);
+
+    // the documents added afterwards are matched too
+    index.add_documents(documents!([{ "id": 5, "text": "jonny" }])).unwrap();
+    assert_eq!(sorted(search(&index, "jon")), BTreeSet::from([0, 1, 2, 3, 5]));
+
+    // `jonny` is still matched as a prefix
+    index.update_settings(|s| s.set_enable_phonetic(false)).unwrap();
+    assert_eq!(sorted(search(&index, "jon")), BTreeSet::from([1, 5]));
+}
+
+#[test]
+fn phonetic_disabled_with_typos() {
+    let index = create_index();
+    index.update_settings(|s| s.set_enable_phonetic(true)).unwrap();
+
+    index.update_settings(|s| s.set_exact_words(BTreeSet::from(["jon".to_owned()]))).unwrap();
+    assert_eq!(search(&index, "jon"), vec![1]);
+
+    index
+        .update_settings(|s| {
+            s.reset_exact_words();
+            s.set_autorize_typos(false);
+        })
+        .unwrap();
+    assert_eq!(search(&index, "jon"), vec![1]);
+}
@@ -0,0 +1,144 @@
+/*!
+This module tests the resolution of the phrases:
+
+1. The words of a phrase must be right after each other, it isn't enough
+that every pair of words is close in different places of the document.
+
+2. The removed stop words still take a position in the phrase.
+
+3. The phrases that are further in an attribute than the exact positions
+are still found.
+
+4. A star right after the closing quote makes the last word of the phrase a prefix.
+
+5. A prefix phrase made of a single word is an exact prefix.
+
+6. The phrases can be delimited by the configured quote characters, which are
+ignored when used as apostrophes inside the words.
+
+7. A `~N` slop right after the closing quote allows up to N extra positions
+between the words of the phrase, which must still be in order.
+
+8. The quotes escaped by a backslash don't delimit the phrases.
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words]);
+            s.set_stop_words(["the".to_owned()].into_iter().collect());
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "text": "hello world kefir doggo world kefir puppy"
+            },
+            {
+                "id": 1,
+                "text": "hello world kefir puppy"
+            },
+            {
+                "id": 2,
+                "text": "one two three four five six seven eight nine ten eleven twelve thirteen fourteen fifteen sixteen hello world kefir puppy"
+            },
+            {
+                "id": 3,
+                "text": "kefir the puppy"
+            },
+            {
+                "id": 4,
+                "text": "kefir puppy"
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search_phrase(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query(query);
+    let mut docids = s.execute().unwrap().documents_ids;
+    docids.sort_unstable();
+    docids
+}
+
+#[test]
+fn test_phrase_words_must_be_consecutive() {
+    let index = create_index();
+    assert_eq!(search_phrase(&index, "\"hello world kefir puppy\""), vec![1, 2]);
+}
+
+#[test]
+fn test_phrase_with_stop_words() {
+    let index = create_index();
+    assert_eq!(search_phrase(&index, "\"kefir the puppy\""), vec![3]);
+}
+
+#[test]
+fn test_prefix_phrase() {
+    let index = create_index();
+    assert_eq!(search_phrase(&index, "\"hello world kef\"*"), vec![0, 1, 2]);
+    assert_eq!(search_phrase(&index, "\"kefir pup\"*"), vec![0, 1, 2, 4]);
+    // without the star the last word must be complete
+    assert_eq!(search_phrase(&index, "\"kefir pup\""), Vec::<u32>::new());
+    // the beginning of the phrase must still be consecutive
+    assert_eq!(search_phrase(&index, "\"hello kefir pup\"*"), Vec::<u32>::new());
+}
+
+#[test]
+fn test_single_word_prefix_phrase() {
+    let index = create_index();
+    assert_eq!(search_phrase(&index, "\"pup\"*"), vec![0, 1, 2, 3, 4]);
+    assert_eq!(search_phrase(&index, "\"doggo\"*"), vec![0]);
+}
+
+#[test]
+fn test_custom_quote_characters() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_quote_characters(
+                ["\"", "'", "“", "”"].into_iter().map(|q| q.to_owned()).collect(),
+            )
+        })
+        .unwrap();
+
+    assert_eq!(search_phrase(&index, "“hello world kefir puppy”"), vec![1, 2]);
+    assert_eq!(search_phrase(&index, "'kefir puppy'"), vec![0, 1, 2, 4]);
+    assert_eq!(search_phrase(&index, "'world kefir' doggo"), vec![0]);
+    assert_eq!(search_phrase(&index, "\"kefir the puppy\""), vec![3]);
+    // the apostrophe isn't the beginning of a "world hello" phrase
+    assert_eq!(search_phrase(&index, "puppy'world hello"), vec![0, 1, 2]);
+}
+
+#[test]
+fn test_phrase_slop() {
+    let index = create_index();
+    assert_eq!(search_phrase(&index, "\"hello kefir\""), Vec::<u32>::new());
+    assert_eq!(search_phrase(&index, "\"hello kefir\"~1"), vec![0, 1, 2]);
+    assert_eq!(search_phrase(&index, "\"hello puppy\"~1"), Vec::<u32>::new());
+    assert_eq!(search_phrase(&index, "\"hello puppy\"~2"), vec![1, 2]);
+    assert_eq!(search_phrase(&index, "\"puppy hello\"~2"), Vec::<u32>::new());
+    // the slop isn't searched as a word
+    assert_eq!(search_phrase(&index, "\"hello kefir\"~1 doggo"), vec![0]);
+}
+
+#[test]
+fn test_escaped_quotes() {
+    let index = create_index();
+    assert_eq!(search_phrase(&index, r#""hello \"world\" kefir puppy""#), vec![1, 2]);
+    assert_eq!(search_phrase(&index, r#""hello \"kefir\"""#), Vec::<u32>::new());
+    assert_eq!(search_phrase(&index, r#""hello \"kefir\""~1"#), vec![0, 1, 2]);
+}
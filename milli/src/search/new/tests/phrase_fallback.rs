@@ -0,0 +1,88 @@
+/*!
+This module tests the fallback of the phrases without any result:
+
+1. the phrases matching some documents are never relaxed
+2. otherwise, the words of the phrases are searched unquoted, all required and with one typo
+3. the relaxed results favor the documents where the words are adjacent
+4. the queries without any phrase are never relaxed
+*/
+
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox" },
+            { "id": 1, "text": "the brown and quick fox" },
+            { "id": 2, "text": "the quikc brown fox" },
+            { "id": 3, "text": "the lazy dog" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn phrase_with_results() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.phrase_fallback(true);
+    s.query("\"quick brown\" fox");
+    let SearchResult { documents_ids, relaxed_phrases, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+    assert!(!relaxed_phrases);
+}
+
+#[test]
+fn phrase_without_results() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"quick browm\"");
+    let SearchResult { documents_ids, relaxed_phrases, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+    assert!(!relaxed_phrases);
+
+    // `quikc` is two typos away from `quick`, the adjacent words come first
+    let mut s = Search::new(&txn, &index);
+    s.phrase_fallback(true);
+    s.query("\"quick browm\"");
+    let SearchResult { documents_ids, relaxed_phrases, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+    assert!(relaxed_phrases);
+
+    // the slop of the phrase is ignored
+    let mut s = Search::new(&txn, &index);
+    s.phrase_fallback(true);
+    s.query("\"quick browm\"~1 fox");
+    let SearchResult { documents_ids, relaxed_phrases, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+    assert!(relaxed_phrases);
+}
+
+#[test]
+fn query_without_phrase() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.phrase_fallback(true);
+    s.query("zebra");
+    let SearchResult { documents_ids, relaxed_phrases, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+    assert!(!relaxed_phrases);
+}
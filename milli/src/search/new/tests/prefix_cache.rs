@@ -0,0 +1,115 @@
+//! This module tests the precomputed results of the popular prefixes and ensure a few things:
+//! 1. The cache is only filled when a prefix cache size is set
+//! 2. The cached results are the ones the search would have returned
+//! 3. The cache is ignored as soon as something else than the query can change the results
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::documents::documents_batch_reader_from_objects;
+use crate::index::tests::TempIndex;
+use crate::score_details::ScoringStrategy;
+use crate::{Filter, Search};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(hashset! { S("id") });
+        })
+        .unwrap();
+
+    // the words prefixes are only computed when enough words share them
+    let documents = (0..150u8).map(|i| {
+        let word = format!("ha{}{}", char::from(b'a' + i / 26), char::from(b'a' + i % 26));
+        serde_json::json!({ "id": i, "text": format!("{word} kefir") }).as_object().unwrap().clone()
+    });
+    index.add_documents(documents_batch_reader_from_objects(documents)).unwrap();
+    index
+}
+
+fn search_ha(index: &TempIndex, txn: &heed::RoTxn<'_>, limit: usize) -> Vec<u32> {
+    let mut search = Search::new(txn, index);
+    search.query("ha");
+    search.limit(limit);
+    search.execute().unwrap().documents_ids
+}
+
+/// Computing the detailed scores prevents the search from using the cache.
+fn uncached_search_ha(index: &TempIndex, txn: &heed::RoTxn<'_>, limit: usize) -> Vec<u32> {
+    let mut search = Search::new(txn, index);
+    search.query("ha");
+    search.limit(limit);
+    search.scoring_strategy(ScoringStrategy::Detailed);
+    search.execute().unwrap().documents_ids
+}
+
+#[test]
+fn prefix_cache_follows_the_setting() {
+    let index = create_index();
+
+    let rtxn = index.read_txn().unwrap();
+    assert_eq!(index.prefix_cache_entry(&rtxn, "ha").unwrap(), None);
+    drop(rtxn);
+
+    index.update_settings(|s| s.set_prefix_cache_size(20)).unwrap();
+    let rtxn = index.read_txn().unwrap();
+    let cached = index.prefix_cache_entry(&rtxn, "ha").unwrap().unwrap();
+    assert_eq!(cached.len(), 20);
+    assert_eq!(cached, uncached_search_ha(&index, &rtxn, 20));
+    assert!(index.prefix_cache_entry(&rtxn, "h").unwrap().is_some());
+    // only the short prefixes are cached
+    assert_eq!(index.prefix_cache_entry(&rtxn, "hab").unwrap(), None);
+    drop(rtxn);
+
+    index.update_settings(|s| s.reset_prefix_cache_size()).unwrap();
+    let rtxn = index.read_txn().unwrap();
+    assert_eq!(index.prefix_cache_entry(&rtxn, "ha").unwrap(), None);
+}
+
+#[test]
+fn prefix_cache_is_only_used_by_plain_searches() {
+    let index = create_index();
+    index.update_settings(|s| s.set_prefix_cache_size(20)).unwrap();
+
+    // we tamper with the cache to make sure the results come from it
+    let mut wtxn = index.write_txn().unwrap();
+    index.put_prefix_cache_entry(&mut wtxn, "ha", &[42, 43]).unwrap();
+
+    assert_eq!(search_ha(&index, &wtxn, 2), vec![42, 43]);
+    // the requested page isn't fully cached
+    assert_eq!(search_ha(&index, &wtxn, 3), uncached_search_ha(&index, &wtxn, 3));
+
+    // a filter can change the results
+    let mut search = Search::new(&wtxn, &index);
+    search.query("ha");
+    search.limit(2);
+    search.filter(Filter::from_str("id >= 1").unwrap().unwrap());
+    assert_ne!(search.execute().unwrap().documents_ids, vec![42, 43]);
+}
+
+#[test]
+fn prefix_cache_is_updated_incrementally() {
+    let index = create_index();
+    index.update_settings(|s| s.set_prefix_cache_size(20)).unwrap();
+
+    // we tamper with the cache to make sure the results are not searched again
+    let mut wtxn = index.write_txn().unwrap();
+    index.put_prefix_cache_entry(&mut wtxn, "ha", &[42, 43]).unwrap();
+    wtxn.commit().unwrap();
+
+    // the new document doesn't match the prefix
+    index.add_documents(documents!([{ "id": 200, "text": "kefir" }])).unwrap();
+    let rtxn = index.read_txn().unwrap();
+    assert_eq!(index.prefix_cache_entry(&rtxn, "ha").unwrap(), Some(vec![42, 43]));
+    drop(rtxn);
+
+    // but this one does
+    index.add_documents(documents!([{ "id": 201, "text": "hat" }])).unwrap();
+    let rtxn = index.read_txn().unwrap();
+    let cached = index.prefix_cache_entry(&rtxn, "ha").unwrap().unwrap();
+    assert_eq!(cached, uncached_search_ha(&index, &rtxn, 20));
+}
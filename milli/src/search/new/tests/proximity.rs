@@ -13,6 +13,9 @@ e.g. `sunflower` equivalent to `sun flower`.
 4. The prefix databases can be used to find the sprximity between two words, but
 they store fewer sprximities than the regular word sprximity DB.
 
+5. When the query has more terms than the long query threshold, the proximities
+from 3 to 7 have the same cost.
+
 */
 
 use std::collections::BTreeMap;
@@ -488,3 +491,45 @@ fn test_proximity_prefix_db() {
     ]
     "###);
 }
+
+#[test]
+fn test_proximity_long_query_buckets() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Proximity]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "hello a b c d e world" },
+            { "id": 1, "text": "hello a b world" },
+            { "id": 2, "text": "hello world" },
+            { "id": 3, "text": "hello a world" },
+            { "id": 4, "text": "hello a b c d e f g h i world" },
+        ]))
+        .unwrap();
+
+    let search = |index: &TempIndex| {
+        let txn = index.read_txn().unwrap();
+        let mut s = Search::new(&txn, index);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query("hello world");
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        documents_ids
+    };
+
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[2, 3, 1, 0, 4]");
+
+    // the query is not longer than the threshold
+    index.update_settings(|s| s.set_long_query_threshold(2)).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[2, 3, 1, 0, 4]");
+
+    // the proximities 3 and 6 end up in the same bucket, sorted by document id
+    index.update_settings(|s| s.set_long_query_threshold(1)).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[2, 3, 0, 1, 4]");
+}
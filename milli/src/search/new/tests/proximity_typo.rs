@@ -76,6 +76,11 @@ fn test_trap_basic() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 2,
+                    rank: Rank {
+                        rank: 3,
+                        max_rank: 3,
+                    },
+                    terms: [],
                 },
             ),
         ],
@@ -90,6 +95,11 @@ fn test_trap_basic() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 2,
+                    rank: Rank {
+                        rank: 3,
+                        max_rank: 3,
+                    },
+                    terms: [],
                 },
             ),
         ],
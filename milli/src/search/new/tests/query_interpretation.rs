@@ -0,0 +1,80 @@
+//! This module tests the description of how the query was interpreted:
+//! 1. The stop words removed from the query are located
+//! 2. The typo budget and synonyms of each term are returned
+//! 3. The terms dropped by the terms matching strategy for some documents are marked
+//! 4. Nothing is returned unless requested
+
+use big_s::S;
+use maplit::btreemap;
+
+use crate::index::tests::TempIndex;
+use crate::{InterpretedQueryTerm, QueryTermKind, Search, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_stop_words(["the".to_owned()].into_iter().collect());
+            s.set_synonyms(btreemap! { S("brown") => vec![S("tan")] });
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fast" },
+            { "id": 1, "text": "the quick brown" },
+            { "id": 2, "text": "the quick brown fox" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn term(
+    start: usize,
+    end: usize,
+    kind: QueryTermKind,
+    max_typos: u8,
+    synonyms: &[&str],
+    dropped: bool,
+) -> InterpretedQueryTerm {
+    InterpretedQueryTerm {
+        byte_range: start..end,
+        kind,
+        max_typos,
+        synonyms: synonyms.iter().map(|s| s.to_string()).collect(),
+        dropped,
+    }
+}
+
+#[test]
+fn test_query_interpretation() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("the brown fox slow");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query_interpretation(true);
+    let result = s.execute().unwrap();
+    assert_eq!(result.documents_ids.len(), 3);
+
+    let interpretation = result.query_interpretation.unwrap();
+    assert_eq!(interpretation.removed_stop_words, vec![0..3]);
+    assert_eq!(
+        interpretation.terms,
+        vec![
+            term(4, 9, QueryTermKind::Word, 1, &["tan"], false),
+            term(10, 13, QueryTermKind::Word, 0, &[], true),
+            term(14, 18, QueryTermKind::Prefix, 0, &[], true),
+        ]
+    );
+
+    let mut s = Search::new(&txn, &index);
+    s.query("the brown fox slow");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let result = s.execute().unwrap();
+    assert!(result.query_interpretation.is_none());
+}
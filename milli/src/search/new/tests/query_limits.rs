@@ -0,0 +1,128 @@
+/*!
+This module tests the limits on the size of the queries:
+
+1. the end of a query longer than the maximum length is ignored
+2. the words beyond the maximum number of terms are ignored
+3. the phrases beyond the maximum number of phrases are ignored
+4. the search result is flagged when a part of the query was ignored
+*/
+
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox jumps over the lazy dog" },
+            { "id": 1, "text": "the quick brown cat" },
+            { "id": 2, "text": "the lazy dog" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> (Vec<u32>, bool) {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let SearchResult { mut documents_ids, query_truncated, .. } = s.execute().unwrap();
+    documents_ids.sort_unstable();
+    (documents_ids, query_truncated)
+}
+
+#[test]
+fn query_max_length() {
+    let index = create_index();
+    insta::assert_debug_snapshot!(search(&index, "quick brown fox"), @r###"
+    (
+        [
+            0,
+        ],
+        false,
+    )
+    "###);
+
+    index.update_settings(|s| s.set_query_max_length(11)).unwrap();
+    insta::assert_debug_snapshot!(search(&index, "quick brown fox"), @r###"
+    (
+        [
+            0,
+            1,
+        ],
+        true,
+    )
+    "###);
+    insta::assert_debug_snapshot!(search(&index, "quick brown"), @r###"
+    (
+        [
+            0,
+            1,
+        ],
+        false,
+    )
+    "###);
+}
+
+#[test]
+fn query_max_terms() {
+    let index = create_index();
+
+    index.update_settings(|s| s.set_query_max_terms(2)).unwrap();
+    insta::assert_debug_snapshot!(search(&index, "quick brown fox"), @r###"
+    (
+        [
+            0,
+            1,
+        ],
+        true,
+    )
+    "###);
+
+    // the stop words after the limit are not worth flagging the query
+    index.update_settings(|s| s.set_stop_words(["the".to_owned()].into())).unwrap();
+    insta::assert_debug_snapshot!(search(&index, "quick brown the"), @r###"
+    (
+        [
+            0,
+            1,
+        ],
+        false,
+    )
+    "###);
+}
+
+#[test]
+fn query_max_phrases() {
+    let index = create_index();
+    insta::assert_debug_snapshot!(search(&index, "\"quick brown\" \"lazy dog\""), @r###"
+    (
+        [
+            0,
+        ],
+        false,
+    )
+    "###);
+
+    index.update_settings(|s| s.set_query_max_phrases(1)).unwrap();
+    insta::assert_debug_snapshot!(search(&index, "\"quick brown\" \"lazy dog\""), @r###"
+    (
+        [
+            0,
+            1,
+        ],
+        true,
+    )
+    "###);
+}
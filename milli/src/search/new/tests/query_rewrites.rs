@@ -0,0 +1,101 @@
+/*!
+This module tests the `queryRewrites` setting:
+
+1. the literal rules replace the whole words of the query, case-insensitively
+2. the regex rules can use their capture groups in the replacement
+3. the rewrites are reported in the interpretation of the query, whose offsets
+refer to the rewritten query
+4. the invalid rules are rejected when the settings are updated
+*/
+
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::query_rewrites::{AppliedQueryRewrite, QueryRewriteRule};
+use crate::Search;
+
+fn rule(find: &str, replace: &str, regex: bool) -> QueryRewriteRule {
+    QueryRewriteRule { find: find.to_owned(), replace: replace.to_owned(), regex }
+}
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_query_rewrites(vec![
+                rule("tv", "television", false),
+                rule(r"(\d+)\s*in\b", "$1 inch", true),
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "a 55 inch television" },
+            { "id": 1, "text": "a 42 inch television" },
+            { "id": 2, "text": "a tv remote" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    let mut documents_ids = s.execute().unwrap().documents_ids;
+    documents_ids.sort_unstable();
+    documents_ids
+}
+
+#[test]
+fn literal_and_regex_rewrites() {
+    let index = create_index();
+
+    insta::assert_snapshot!(format!("{:?}", search(&index, "TV")), @"[0, 1]");
+    insta::assert_snapshot!(format!("{:?}", search(&index, "55in tv")), @"[0]");
+    // the literals are not rewritten inside of the other words
+    insta::assert_snapshot!(format!("{:?}", search(&index, "tvs")), @"[]");
+
+    index.update_settings(|s| s.reset_query_rewrites()).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "TV")), @"[2]");
+}
+
+#[test]
+fn rewrites_are_interpreted() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("55in tv");
+    s.query_interpretation(true);
+    let interpretation = s.execute().unwrap().query_interpretation.unwrap();
+    assert_eq!(interpretation.rewritten_query.as_deref(), Some("55 inch television"));
+    assert_eq!(
+        interpretation.rewrites,
+        vec![
+            AppliedQueryRewrite { original: "tv".to_owned(), rewritten: "television".to_owned() },
+            AppliedQueryRewrite { original: "55in".to_owned(), rewritten: "55 inch".to_owned() },
+        ]
+    );
+    let ranges: Vec<_> = interpretation.terms.iter().map(|term| term.byte_range.clone()).collect();
+    assert_eq!(ranges, vec![0..2, 3..7, 8..18]);
+
+    let mut s = Search::new(&txn, &index);
+    s.query("remote");
+    s.query_interpretation(true);
+    let interpretation = s.execute().unwrap().query_interpretation.unwrap();
+    assert_eq!(interpretation.rewritten_query, None);
+    assert!(interpretation.rewrites.is_empty());
+}
+
+#[test]
+fn invalid_rules_are_rejected() {
+    let index = create_index();
+
+    index.update_settings(|s| s.set_query_rewrites(vec![rule("(", "", true)])).unwrap_err();
+    index.update_settings(|s| s.set_query_rewrites(vec![rule("", "nothing", false)])).unwrap_err();
+}
@@ -0,0 +1,88 @@
+//! This module tests the synonyms given with a search query:
+//! 1. They are added to the synonyms of the index, for this search only
+//! 2. They are normalized like the synonyms of the index
+//! 3. Multi-word synonyms are applied to the ngrams of the query
+
+use std::collections::BTreeMap;
+
+use big_s::S;
+use maplit::btreemap;
+
+use crate::index::tests::TempIndex;
+use crate::{Search, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_synonyms(btreemap! { S("car") => vec![S("automobile")] });
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "a red car" },
+            { "id": 1, "text": "a red automobile" },
+            { "id": 2, "text": "a red vehicle" },
+            { "id": 3, "text": "a red motor vehicle" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(
+    index: &TempIndex,
+    query: &str,
+    synonyms: Option<&BTreeMap<String, Vec<String>>>,
+) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    if let Some(synonyms) = synonyms {
+        s.synonyms(synonyms);
+    }
+    let mut docids = s.execute().unwrap().documents_ids;
+    docids.sort_unstable();
+    docids
+}
+
+#[test]
+fn test_query_synonyms_are_added_to_the_index_ones() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "car", None), vec![0, 1]);
+
+    let synonyms = btreemap! { S("car") => vec![S("vehicle")] };
+    assert_eq!(search(&index, "car", Some(&synonyms)), vec![0, 1, 2, 3]);
+
+    // the synonyms of the index are left untouched
+    let txn = index.read_txn().unwrap();
+    assert_eq!(
+        index.user_defined_synonyms(&txn).unwrap(),
+        btreemap! { S("car") => vec![S("automobile")] }
+    );
+    drop(txn);
+    assert_eq!(search(&index, "car", None), vec![0, 1]);
+}
+
+#[test]
+fn test_query_synonyms_are_normalized() {
+    let index = create_index();
+
+    let synonyms = btreemap! { S("CAR") => vec![S("Motor  Vehicle")] };
+    assert_eq!(search(&index, "car", Some(&synonyms)), vec![0, 1, 3]);
+}
+
+#[test]
+fn test_query_synonyms_of_ngrams() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "red car", None), vec![0, 1]);
+
+    let synonyms = btreemap! { S("red car") => vec![S("vehicle")] };
+    assert_eq!(search(&index, "red car", Some(&synonyms)), vec![0, 1, 2, 3]);
+}
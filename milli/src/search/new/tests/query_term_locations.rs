@@ -0,0 +1,79 @@
+//! This module tests the locations of the query terms returned with the search results:
+//! 1. The byte offsets of the words, prefixes and phrases, without the quotes
+//! 2. The negative terms are located but never matched
+//! 3. The terms dropped by the terms matching strategy aren't matched
+//! 4. Nothing is returned unless requested
+
+use crate::index::tests::TempIndex;
+use crate::{QueryTermKind, QueryTermLocation, Search, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fast" },
+            { "id": 1, "text": "the quick brown" },
+            { "id": 2, "text": "the quick brown fox" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn location(start: usize, end: usize, kind: QueryTermKind, matched: bool) -> QueryTermLocation {
+    QueryTermLocation { byte_range: start..end, kind, matched }
+}
+
+#[test]
+fn test_query_term_locations() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"the quick\" brown -fox fast");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query_term_locations(true);
+    let result = s.execute().unwrap();
+    assert_eq!(
+        result.query_term_locations,
+        vec![
+            location(1, 10, QueryTermKind::Phrase, true),
+            location(12, 17, QueryTermKind::Word, true),
+            location(19, 22, QueryTermKind::NegativeWord, false),
+            location(23, 27, QueryTermKind::Prefix, true),
+        ]
+    );
+}
+
+#[test]
+fn test_dropped_query_terms_are_not_matched() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("brown slow");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.query_term_locations(true);
+    let result = s.execute().unwrap();
+    assert_eq!(result.documents_ids.len(), 3);
+    assert_eq!(
+        result.query_term_locations,
+        vec![
+            location(0, 5, QueryTermKind::Word, true),
+            location(6, 10, QueryTermKind::Prefix, false),
+        ]
+    );
+
+    let mut s = Search::new(&txn, &index);
+    s.query("brown slow");
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    let result = s.execute().unwrap();
+    assert!(result.query_term_locations.is_empty());
+}
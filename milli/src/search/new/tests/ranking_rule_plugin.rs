@@ -0,0 +1,167 @@
+/*!
+This module tests the ranking rule plugins:
+
+1. the buckets returned by the plugin are ranked in order, followed by the documents left out
+2. the plugin works for both placeholder and query searches
+3. the plugin gives a rank to the documents of each bucket
+4. an error is returned if the plugin of a ranking rule isn't registered
+5. the buckets of a plugin computing them lazily are only computed while the search needs them
+*/
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use heed::RoTxn;
+use meili_snap::insta;
+use roaring::RoaringBitmap;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, Rank, ScoreDetails, ScoringStrategy};
+use crate::{
+    register_ranking_rule_plugin, Criterion, Index, RankingRulePlugin, RankingRulePluginBuckets,
+    Result, Search, SearchResult,
+};
+
+/// Ranks the given documents first, in the order of the buckets.
+struct Promote(Vec<RoaringBitmap>);
+
+impl RankingRulePlugin for Promote {
+    fn buckets(
+        &self,
+        _index: &Index,
+        _rtxn: &RoTxn<'_>,
+        universe: &RoaringBitmap,
+    ) -> Result<Vec<RoaringBitmap>> {
+        Ok(self.0.iter().map(|bucket| bucket & universe).collect())
+    }
+}
+
+/// Ranks the documents by decreasing id, one document per bucket, computed lazily.
+struct ByDecreasingId {
+    computed_buckets: Arc<AtomicUsize>,
+}
+
+struct ByDecreasingIdBuckets {
+    next_id: u32,
+    computed_buckets: Arc<AtomicUsize>,
+}
+
+impl RankingRulePlugin for ByDecreasingId {
+    fn buckets(
+        &self,
+        _index: &Index,
+        _rtxn: &RoTxn<'_>,
+        universe: &RoaringBitmap,
+    ) -> Result<Vec<RoaringBitmap>> {
+        Ok(universe.iter().rev().map(|docid| RoaringBitmap::from_iter([docid])).collect())
+    }
+
+    fn start_iteration(
+        &self,
+        _index: &Index,
+        _rtxn: &RoTxn<'_>,
+        universe: &RoaringBitmap,
+    ) -> Result<Box<dyn RankingRulePluginBuckets>> {
+        Ok(Box::new(ByDecreasingIdBuckets {
+            next_id: universe.max().unwrap_or_default(),
+            computed_buckets: self.computed_buckets.clone(),
+        }))
+    }
+}
+
+impl RankingRulePluginBuckets for ByDecreasingIdBuckets {
+    fn bucket_count(&self) -> usize {
+        self.next_id as usize + 1
+    }
+
+    fn next_bucket(
+        &mut self,
+        _index: &Index,
+        _rtxn: &RoTxn<'_>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RoaringBitmap>> {
+        self.computed_buckets.fetch_add(1, Ordering::Relaxed);
+        let bucket = universe.iter().rev().find(|&docid| docid <= self.next_id);
+        Ok(bucket.map(|docid| {
+            self.next_id = docid.saturating_sub(1);
+            RoaringBitmap::from_iter([docid])
+        }))
+    }
+}
+
+fn create_index(criteria: Vec<Criterion>) -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(criteria);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "hello world" },
+            { "id": 1, "text": "hello" },
+            { "id": 2, "text": "hello world" },
+            { "id": 3, "text": "hello" },
+            { "id": 4, "text": "hello world" },
+            { "id": 5, "text": "hello" },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn ranking_rule_plugin() {
+    let promote = Promote(vec![RoaringBitmap::from_iter([4]), RoaringBitmap::from_iter([1, 3])]);
+    register_ranking_rule_plugin("promote", Arc::new(promote));
+
+    let index = create_index(vec![Criterion::Plugin("promote".to_owned()), Criterion::Words]);
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 1, 3, 0, 2, 5]");
+    let rank = |rank| {
+        vec![ScoreDetails::Plugin(score_details::Plugin {
+            name: "promote".to_owned(),
+            rank: Rank { rank, max_rank: 3 },
+        })]
+    };
+    assert_eq!(document_scores, vec![rank(3), rank(2), rank(2), rank(1), rank(1), rank(1)]);
+
+    let mut s = Search::new(&txn, &index);
+    s.query("hello world");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 1, 3, 0, 2, 5]");
+}
+
+#[test]
+fn unknown_ranking_rule_plugin() {
+    let index = create_index(vec![Criterion::Plugin("missing".to_owned())]);
+    let txn = index.read_txn().unwrap();
+
+    let s = Search::new(&txn, &index);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"The `plugin:missing` ranking rule is not available, no ranking rule plugin is registered under the name `missing`.");
+}
+
+#[test]
+fn lazy_ranking_rule_plugin() {
+    let computed_buckets = Arc::new(AtomicUsize::new(0));
+    let plugin = ByDecreasingId { computed_buckets: computed_buckets.clone() };
+    register_ranking_rule_plugin("by_decreasing_id", Arc::new(plugin));
+
+    let index = create_index(vec![Criterion::Plugin("by_decreasing_id".to_owned())]);
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.limit(2);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5, 4]");
+    // only the buckets of the requested documents were computed
+    assert_eq!(computed_buckets.load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,76 @@
+/*!
+This module tests the recency ranking rule:
+
+1. the documents are ranked by the decayed boost of their date, the most recent first
+2. the dates can be either Unix timestamps in seconds or RFC 3339 strings
+3. the documents whose dates are close enough share the same bucket
+4. the documents without a date, or with an invalid one, come last
+*/
+
+use meili_snap::insta;
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{self, Rank, ScoreDetails, ScoringStrategy};
+use crate::{Criterion, HalfLife, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Recency {
+                field: "date".to_owned(),
+                half_life: HalfLife { seconds: 24 * 60 * 60 },
+            }]);
+        })
+        .unwrap();
+
+    let now = OffsetDateTime::now_utc();
+    let timestamp = |age: Duration| (now - age).unix_timestamp();
+    let rfc3339 = |age: Duration| (now - age).format(&Rfc3339).unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "hello", "date": timestamp(Duration::days(2)) },
+            { "id": 1, "text": "hello" },
+            { "id": 2, "text": "hello", "date": rfc3339(Duration::days(1)) },
+            { "id": 3, "text": "hello", "date": timestamp(Duration::ZERO) },
+            { "id": 4, "text": "hello", "date": "yesterday" },
+            { "id": 5, "text": "hello", "date": timestamp(Duration::days(1)) },
+            { "id": 6, "text": "hello", "date": rfc3339(Duration::days(30)) },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn recency() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let rank = |rank| {
+        vec![ScoreDetails::Recency(score_details::Recency {
+            field_name: "date".to_owned(),
+            half_life: HalfLife { seconds: 24 * 60 * 60 },
+            rank: Rank { rank, max_rank: 100 },
+        })]
+    };
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 2, 5, 0, 6, 1, 4]");
+    assert_eq!(
+        document_scores,
+        vec![rank(100), rank(50), rank(50), rank(25), rank(1), rank(0), rank(0)]
+    );
+
+    let mut s = Search::new(&txn, &index);
+    s.query("hello");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[3, 2, 5, 0, 6, 1, 4]");
+}
@@ -0,0 +1,100 @@
+//! This module tests the quoted regular expressions of the search query, when the quoted
+//! parts of the query are regular expressions instead of phrases:
+//! 1. `"colou?r"` matches the words of the index entirely matched by the regular expression
+//! 2. the regular expressions are mixed with the other words of the query
+//! 3. the regular expressions never allow any typo
+//! 4. an invalid regular expression is a user error
+//! 5. the regular expressions are located without their quotes
+
+use crate::index::tests::TempIndex;
+use crate::{Error, QueryTermKind, QueryTermLocation, Search, TermsMatchingStrategy, UserError};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the color red" },
+            { "id": 1, "text": "the colour blue" },
+            { "id": 2, "text": "a colorful painting" },
+            { "id": 3, "text": "error 404" },
+            { "id": 4, "text": "error 500" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> crate::Result<Vec<u32>> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.regex_terms(true);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let mut docids = s.execute()?.documents_ids;
+    docids.sort_unstable();
+    Ok(docids)
+}
+
+#[test]
+fn test_regex_term() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "\"colou?r\"").unwrap(), vec![0, 1]);
+    assert_eq!(search(&index, "\"colou?r.*\"").unwrap(), vec![0, 1, 2]);
+    assert_eq!(search(&index, "\"4[0-9]{2}\"").unwrap(), vec![3]);
+    // without the option, the quotes are a phrase
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("\"colou?r\"");
+    assert!(s.execute().unwrap().documents_ids.is_empty());
+}
+
+#[test]
+fn test_regex_term_with_words() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "\"colou?r\" blue").unwrap(), vec![1]);
+    assert_eq!(search(&index, "error \"5.*\"").unwrap(), vec![4]);
+    assert_eq!(search(&index, "\"colou?r\" \"re.\"").unwrap(), vec![0]);
+}
+
+#[test]
+fn test_regex_term_without_typos() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "\"colr\"").unwrap(), vec![]);
+}
+
+#[test]
+fn test_invalid_regex_term() {
+    let index = create_index();
+
+    let error = search(&index, "\"colo(r\"").unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::InvalidQueryRegex { .. })));
+}
+
+#[test]
+fn test_regex_term_location() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("\"colou?r\" blue");
+    s.regex_terms(true);
+    s.query_term_locations(true);
+    let result = s.execute().unwrap();
+    assert_eq!(
+        result.query_term_locations,
+        vec![
+            QueryTermLocation { byte_range: 1..8, kind: QueryTermKind::Regex, matched: true },
+            QueryTermLocation { byte_range: 10..14, kind: QueryTermKind::Prefix, matched: true },
+        ]
+    );
+}
@@ -7,16 +7,22 @@ This module tests the `sort` ranking rule:
 4. custom sort ranking rules can be added to the settings, they interact with the generic `sort` ranking rule as expected
 5. numbers appear before strings
 6. documents with either: (1) no value, (2) null, or (3) an object for the field-to-sort appear at the end of the bucket
-7. boolean values are translated to strings
+7. boolean values sort after the strings, `false` before `true`
 8. if a field contains an array, it is sorted by the best value in the array according to the sort rule
+9. the dense numeric sort index of a field gives the same order as the facet databases
+10. the collation of a locale sorts the original strings, accents and case included, in the order of the locale
 */
 
+use std::collections::BTreeSet;
+
 use big_s::S;
 use maplit::hashset;
 use meili_snap::insta;
 
+use crate::collation::CollationLocale;
 use crate::index::tests::TempIndex;
 use crate::search::new::tests::collect_field_values;
+use crate::update::{DerivedDatabase, RebuildDatabases};
 use crate::{
     score_details, AscDesc, Criterion, Member, Search, SearchResult, TermsMatchingStrategy,
 };
@@ -325,6 +331,83 @@ fn test_sort() {
     "###);
 }
 
+#[test]
+fn test_dense_sort_index() {
+    let index = create_index();
+    index
+        .update_settings(|s| {
+            s.set_dense_sortable_fields(hashset! { S("rank"), S("vague") });
+        })
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let fields_ids_map = index.fields_ids_map(&txn).unwrap();
+    let letter = fields_ids_map.id("letter").unwrap();
+    assert!(index.numeric_sort_index(&txn, letter).unwrap().is_none());
+    let vague = fields_ids_map.id("vague").unwrap();
+    let sort_index = index.numeric_sort_index(&txn, vague).unwrap().unwrap();
+    insta::assert_debug_snapshot!(sort_index.values, @r###"
+    [
+        0.0,
+        1.0,
+        1.1367,
+        1.2367,
+        1.5673,
+        2.0,
+    ]
+    "###);
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank")))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[14, 13, 12, 4, 7, 11, 17, 23, 1, 3, 6, 10, 16, 19, 22, 0, 2, 5, 8, 9]");
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("vague")))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 4, 5, 22, 23, 13, 1, 3, 12, 21, 11, 20, 6, 7, 8, 9, 10, 14, 15]");
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("vague")))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 13, 23, 22, 2, 5, 0, 11, 20, 12, 21, 3, 1, 6, 7, 8, 9, 10, 14, 15]");
+    drop(txn);
+
+    // the sort index is kept up to date with the documents
+    index.delete_documents(vec![S("4"), S("13")]);
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("vague")))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[23, 22, 2, 5, 0, 11, 20, 12, 21, 3, 1, 6, 7, 8, 9, 10, 14, 15, 16, 17]");
+    drop(txn);
+
+    // the values added and removed shift the ordinals like a full rebuild does
+    index
+        .add_documents(documents!([
+            { "id": 30, "rank": 5, "vague": 1.05 },
+            { "id": 31, "rank": 5, "vague": [-1, 3] },
+            { "id": 0, "rank": 5, "vague": 1.1367 },
+        ]))
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+    let updated = index.numeric_sort_index(&txn, vague).unwrap().unwrap();
+    drop(txn);
+
+    let mut wtxn = index.write_txn().unwrap();
+    let databases = BTreeSet::from([DerivedDatabase::SortIndexes]);
+    RebuildDatabases::new(&mut wtxn, &index, &index.indexer_config, databases)
+        .execute(|| false)
+        .unwrap();
+    wtxn.commit().unwrap();
+    let txn = index.read_txn().unwrap();
+    assert_eq!(index.numeric_sort_index(&txn, vague).unwrap().unwrap(), updated);
+}
+
 #[test]
 fn test_redacted() {
     let index = create_index();
@@ -354,3 +437,48 @@ fn test_redacted() {
     insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 4, 5, 22, 23, 13, 1, 3, 12, 21, 11, 20, 6, 7, 8, 9, 10, 14, 15]");
     insta::assert_json_snapshot!(document_scores_json);
 }
+
+#[test]
+fn test_collation() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_sortable_fields(hashset! { S("name"), S("city") });
+            s.set_criteria(vec![Criterion::Sort]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "ez", "city": "Zürich" },
+            { "id": 1, "name": "éa", "city": "Örebro" },
+            { "id": 2, "name": "Eb", "city": "Arvika" },
+            { "id": 3, "name": "eb", "city": "Åre" },
+            { "id": 4, "name": ["f", "ea"] },
+            { "id": 5, "name": 2 },
+        ]))
+        .unwrap();
+
+    let search = |field: &str, ascending: bool| {
+        let txn = index.read_txn().unwrap();
+        let mut s = Search::new(&txn, &index);
+        let member = Member::Field(field.to_owned());
+        s.sort_criteria(vec![if ascending { AscDesc::Asc(member) } else { AscDesc::Desc(member) }]);
+        let SearchResult { documents_ids, .. } = s.execute().unwrap();
+        format!("{documents_ids:?}")
+    };
+
+    // without any collation the accented letters sort after all the unaccented ones
+    insta::assert_snapshot!(search("name", true), @"[5, 4, 2, 3, 0, 1]");
+
+    index
+        .update_settings(|s| {
+            s.set_collation_locale(CollationLocale::French);
+            s.set_collation_attributes([(S("city"), CollationLocale::Swedish)].into());
+        })
+        .unwrap();
+
+    insta::assert_snapshot!(search("name", true), @"[5, 4, 1, 3, 2, 0]");
+    insta::assert_snapshot!(search("name", false), @"[5, 4, 0, 2, 3, 1]");
+    insta::assert_snapshot!(search("city", true), @"[2, 0, 3, 1, 4, 5]");
+}
@@ -0,0 +1,71 @@
+/*!
+This module tests the sort on an arithmetic expression over the sortable fields:
+
+1. the documents are sorted by the value of the expression, in both directions
+2. the documents on which the expression can't be evaluated are returned last
+3. all the fields of the expression must be sortable
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{AscDesc, Criterion, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_criteria(vec![Criterion::Sort]);
+            s.set_sortable_fields(hashset! { S("price"), S("weight") });
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "price": 100, "weight": 4, "stock": 1 },
+            { "id": 1, "price": 30, "weight": 3 },
+            { "id": 2, "price": 60, "weight": 2 },
+            { "id": 3, "price": 10 },
+            { "id": 4, "price": 50, "weight": 0 },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn sort_on_expression() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec!["price / weight:asc".parse::<AscDesc>().unwrap()]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    // 10.0, 25.0, 30.0, then the documents without weight or with a division by zero
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 0, 2, 3, 4]");
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec!["price / weight:desc".parse::<AscDesc>().unwrap()]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 0, 1, 3, 4]");
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec!["(price + 20) * weight:asc".parse::<AscDesc>().unwrap()]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    // 0.0, 150.0, 160.0, 480.0, then the document without weight
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 1, 2, 0, 3]");
+}
+
+#[test]
+fn sort_on_expression_with_unsortable_field() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec!["price / stock:asc".parse::<AscDesc>().unwrap()]);
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `stock` is not sortable. Available sortable attributes are: `price, weight`.");
+}
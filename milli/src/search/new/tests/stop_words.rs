@@ -98,6 +98,11 @@ fn test_ignore_stop_words() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 1,
+                    rank: Rank {
+                        rank: 2,
+                        max_rank: 2,
+                    },
+                    terms: [],
                 },
             ),
             Proximity(
@@ -151,6 +156,11 @@ fn test_ignore_stop_words() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 1,
+                    rank: Rank {
+                        rank: 2,
+                        max_rank: 2,
+                    },
+                    terms: [],
                 },
             ),
             Proximity(
@@ -204,6 +214,11 @@ fn test_ignore_stop_words() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 1,
+                    rank: Rank {
+                        rank: 2,
+                        max_rank: 2,
+                    },
+                    terms: [],
                 },
             ),
             Proximity(
@@ -256,6 +271,11 @@ fn test_ignore_stop_words() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 2,
+                    rank: Rank {
+                        rank: 3,
+                        max_rank: 3,
+                    },
+                    terms: [],
                 },
             ),
             Proximity(
@@ -315,6 +335,11 @@ fn test_stop_words_in_phrase() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 0,
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    terms: [],
                 },
             ),
             Proximity(
@@ -356,6 +381,11 @@ fn test_stop_words_in_phrase() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 0,
+                    rank: Rank {
+                        rank: 1,
+                        max_rank: 1,
+                    },
+                    terms: [],
                 },
             ),
             Proximity(
@@ -408,6 +438,11 @@ fn test_stop_words_in_phrase() {
                 Typo {
                     typo_count: 0,
                     max_typo_count: 2,
+                    rank: Rank {
+                        rank: 3,
+                        max_rank: 3,
+                    },
+                    terms: [],
                 },
             ),
             Proximity(
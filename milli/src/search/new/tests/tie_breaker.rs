@@ -0,0 +1,157 @@
+/*!
+This module tests the tie-breaker of the index:
+
+1. without a tie-breaker, the documents the ranking rules consider equal are in the order of their internal ids
+2. the primary key tie-breaker sorts them by their primary key, ascending or descending
+3. the field tie-breaker sorts them like an `asc` or `desc` ranking rule on the field
+4. the tie-breaker only applies after all the ranking rules of the index
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::tie_breaker::TieBreaker;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": "c", "text": "hello", "rank": 2 },
+            { "id": "a", "text": "hello world", "rank": 1 },
+            { "id": "d", "text": "hello", "rank": 2 },
+            { "id": "b", "text": "hello", "rank": 1 },
+            { "id": "e", "text": "hello world", "rank": 3 },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Vec<String> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.terms_matching_strategy(TermsMatchingStrategy::Last);
+    if !query.is_empty() {
+        s.query(query);
+    }
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    collect_field_values(index, &txn, "id", &documents_ids)
+}
+
+#[test]
+fn no_tie_breaker() {
+    let index = create_index();
+
+    insta::assert_debug_snapshot!(search(&index, ""), @r###"
+    [
+        "\"c\"",
+        "\"a\"",
+        "\"d\"",
+        "\"b\"",
+        "\"e\"",
+    ]
+    "###);
+    insta::assert_debug_snapshot!(search(&index, "hello world"), @r###"
+    [
+        "\"a\"",
+        "\"e\"",
+        "\"c\"",
+        "\"d\"",
+        "\"b\"",
+    ]
+    "###);
+}
+
+#[test]
+fn primary_key_tie_breaker() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| s.set_tie_breaker(TieBreaker::PrimaryKey { ascending: true }))
+        .unwrap();
+    insta::assert_debug_snapshot!(search(&index, ""), @r###"
+    [
+        "\"a\"",
+        "\"b\"",
+        "\"c\"",
+        "\"d\"",
+        "\"e\"",
+    ]
+    "###);
+    insta::assert_debug_snapshot!(search(&index, "hello world"), @r###"
+    [
+        "\"a\"",
+        "\"e\"",
+        "\"b\"",
+        "\"c\"",
+        "\"d\"",
+    ]
+    "###);
+
+    index
+        .update_settings(|s| s.set_tie_breaker(TieBreaker::PrimaryKey { ascending: false }))
+        .unwrap();
+    insta::assert_debug_snapshot!(search(&index, "hello world"), @r###"
+    [
+        "\"e\"",
+        "\"a\"",
+        "\"d\"",
+        "\"c\"",
+        "\"b\"",
+    ]
+    "###);
+
+    index.update_settings(|s| s.reset_tie_breaker()).unwrap();
+    insta::assert_debug_snapshot!(search(&index, ""), @r###"
+    [
+        "\"c\"",
+        "\"a\"",
+        "\"d\"",
+        "\"b\"",
+        "\"e\"",
+    ]
+    "###);
+}
+
+#[test]
+fn field_tie_breaker() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_tie_breaker(TieBreaker::Field { field: S("rank"), ascending: false })
+        })
+        .unwrap();
+    // the documents with the same rank keep the order of their internal ids
+    insta::assert_debug_snapshot!(search(&index, ""), @r###"
+    [
+        "\"e\"",
+        "\"c\"",
+        "\"d\"",
+        "\"a\"",
+        "\"b\"",
+    ]
+    "###);
+    insta::assert_debug_snapshot!(search(&index, "hello world"), @r###"
+    [
+        "\"e\"",
+        "\"a\"",
+        "\"c\"",
+        "\"d\"",
+        "\"b\"",
+    ]
+    "###);
+}
@@ -0,0 +1,69 @@
+//! This module tests the timings of the stages of a search:
+//! 1. Every ranking rule is timed, in the order they are applied
+//! 2. The placeholder searches only time their own ranking rules
+//! 3. Nothing is returned unless requested
+
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_sortable_fields(["rank".to_owned()].into_iter().collect());
+            s.set_criteria(vec![
+                Criterion::Words,
+                Criterion::Typo,
+                Criterion::Desc("rank".to_owned()),
+            ]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox", "rank": 1 },
+            { "id": 1, "text": "the quick brown dog", "rank": 2 },
+            { "id": 2, "text": "the slow brown fox", "rank": 3 },
+        ]))
+        .unwrap();
+    index
+}
+
+fn ranking_rules(index: &TempIndex, query: Option<&str>) -> Vec<String> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    if let Some(query) = query {
+        s.query(query);
+    }
+    s.timings(true);
+    let SearchResult { timings, .. } = s.execute().unwrap();
+    timings.unwrap().ranking_rules.into_iter().map(|(rule, _)| rule).collect()
+}
+
+#[test]
+fn timings() {
+    let index = create_index();
+
+    insta::assert_debug_snapshot!(ranking_rules(&index, Some("quick fox")), @r###"
+    [
+        "words",
+        "typo",
+        "rank:desc",
+    ]
+    "###);
+    insta::assert_debug_snapshot!(ranking_rules(&index, None), @r###"
+    [
+        "rank:desc",
+    ]
+    "###);
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("quick fox");
+    assert!(s.execute().unwrap().timings.is_none());
+}
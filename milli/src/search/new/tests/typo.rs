@@ -16,12 +16,22 @@ if `words` doesn't exist before it.
 12. Prefix tolerance costs nothing according to the typo ranking rule
 13. Split words cost 1 typo according to the typo ranking rule
 14. Synonyms cost nothing according to the typo ranking rule
+15. Half typos are ranked between exact matches and regular typos
+16. Literal words only match themselves
+17. The words made mostly of digits only match themselves when typos on numbers are disabled
+18. Typos on the first letter can be ranked after the other typos, or disallowed
+19. The typos needed by each term can be recorded in the score details of the documents
+20. The cost of one typo, two typos, and a split word can be configured
+21. A transposition of the first two letters counts as a single typo
+22. The terms only matching the words they are a prefix of are recorded as prefix matches
 */
 
 use std::collections::BTreeMap;
 
 use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::tests::collect_field_values;
+use crate::typo::{FirstLetterTypos, KeyboardLayout};
 use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
 
 fn create_index() -> TempIndex {
@@ -318,6 +328,32 @@ fn test_typo_exact_word() {
     "###);
 }
 
+#[test]
+fn test_literal_words() {
+    let index = create_index();
+
+    index
+        .update_settings(|s| {
+            s.set_literal_words(["sunflower"].iter().map(ToString::to_string).collect())
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    // literal words are neither typo tolerant, nor expanded as a prefix, nor split
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("network interconnection sunflower");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[16]");
+    let texts = collect_field_values(&index, &txn, "text", &documents_ids);
+    insta::assert_debug_snapshot!(texts, @r###"
+    [
+        "\"network interconnection sunflower\"",
+    ]
+    "###);
+}
+
 #[test]
 fn test_typo_exact_attribute() {
     let index = create_index();
@@ -635,3 +671,338 @@ fn test_typo_synonyms() {
     ]
     "###);
 }
+
+#[test]
+fn test_half_typos() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Typo]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "sunflowar" },
+            { "id": 1, "text": "sunflowet" },
+            { "id": 2, "text": "sunflower" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("sunflower");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 0, 1]");
+    drop(txn);
+
+    // `r` and `t` are neighbours on a qwerty keyboard, `e` and `a` are not
+    index
+        .update_settings(|s| {
+            s.set_half_typos_keyboard_layout(KeyboardLayout::Qwerty);
+        })
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.query("sunflower");
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 1, 0]");
+
+    // the half typo is ranked before the full typo but is still counted as a typo
+    let typo_counts: Vec<_> = document_scores
+        .iter()
+        .flat_map(|scores| {
+            scores.iter().filter_map(|score| match score {
+                ScoreDetails::Typo(typo) => Some((typo.typo_count, typo.max_typo_count)),
+                _ => None,
+            })
+        })
+        .collect();
+    insta::assert_snapshot!(format!("{typo_counts:?}"), @"[(0, 2), (1, 2), (1, 2)]");
+}
+
+#[test]
+fn test_typos_on_numbers() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_min_word_len_one_typo(4);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "released in 2014" },
+            { "id": 1, "text": "released in 2024" },
+            { "id": 2, "text": "model b2014" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("2024");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 0]");
+    drop(txn);
+
+    index.update_settings(|s| s.set_disable_typos_on_numbers(true)).unwrap();
+
+    // the words made mostly of digits only match themselves
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("2024");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+
+    let mut s = Search::new(&txn, &index);
+    s.query("b2024");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[]");
+
+    // but the other words are still typo tolerant
+    let mut s = Search::new(&txn, &index);
+    s.query("relesed");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1]");
+}
+
+#[test]
+fn test_first_letter_typos() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Typo]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "dunflower" },
+            { "id": 1, "text": "sunflawar" },
+            { "id": 2, "text": "sunflower" },
+        ]))
+        .unwrap();
+
+    let search = |index: &TempIndex| {
+        let txn = index.read_txn().unwrap();
+        let mut s = Search::new(&txn, index);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query("sunflower");
+        s.execute().unwrap().documents_ids
+    };
+
+    // by default a typo on the first letter counts as two typos
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[2, 0, 1]");
+
+    index.update_settings(|s| s.set_first_letter_typos(FirstLetterTypos::Penalized)).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[2, 1, 0]");
+
+    index.update_settings(|s| s.set_first_letter_typos(FirstLetterTypos::Disallowed)).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[2, 1]");
+}
+
+#[test]
+fn test_term_typos() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Typo]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "white horse" },
+            { "id": 1, "text": "whitehorse" },
+            { "id": 2, "text": "whitehorsa" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.term_typos(true);
+    s.query("whitehorse");
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 0, 2]");
+
+    let term_typos: Vec<Vec<_>> = document_scores
+        .iter()
+        .flat_map(|scores| {
+            scores.iter().filter_map(|score| match score {
+                ScoreDetails::Typo(typo) => {
+                    Some(typo.terms.iter().map(|t| (t.term.clone(), t.typos, t.split)).collect())
+                }
+                _ => None,
+            })
+        })
+        .collect();
+    insta::assert_snapshot!(format!("{term_typos:?}"), @r###"[[("whitehorse", 0, false)], [("whitehorse", 0, true)], [("whitehorse", 1, false)]]"###);
+}
+
+#[test]
+fn test_term_typos_prefix() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Typo]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "black horse" },
+            { "id": 1, "text": "black horses" },
+            { "id": 2, "text": "black horsa" },
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.term_typos(true);
+    // only the last word of the query is a prefix
+    s.query("black horse");
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+
+    let term_typos: Vec<Vec<_>> = document_scores
+        .iter()
+        .flat_map(|scores| {
+            scores.iter().filter_map(|score| match score {
+                ScoreDetails::Typo(typo) => {
+                    Some(typo.terms.iter().map(|t| (t.term.clone(), t.typos, t.prefix)).collect())
+                }
+                _ => None,
+            })
+        })
+        .collect();
+    insta::assert_snapshot!(format!("{term_typos:?}"), @r###"[[("black", 0, false), ("horse", 0, false)], [("black", 0, false), ("horse", 0, true)], [("black", 0, false), ("horse", 1, false)]]"###);
+}
+
+#[test]
+fn test_typo_costs() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Typo]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "sun flower" },
+            { "id": 1, "text": "sunflowar" },
+            { "id": 2, "text": "sunflawar" },
+            { "id": 3, "text": "sunflower" },
+        ]))
+        .unwrap();
+
+    let search = |index: &TempIndex| {
+        let txn = index.read_txn().unwrap();
+        let mut s = Search::new(&txn, index);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query("sunflower");
+        s.execute().unwrap().documents_ids
+    };
+
+    // by default a split word costs as much as one typo
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[3, 0, 1, 2]");
+
+    index.update_settings(|s| s.set_typo_cost_split(3)).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[3, 1, 2, 0]");
+
+    index.update_settings(|s| s.set_typo_cost_two_typos(5)).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[3, 1, 0, 2]");
+
+    // the costs don't change the number of typos counted in the score
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    s.query("sunflower");
+    let SearchResult { document_scores, .. } = s.execute().unwrap();
+    let typo_counts: Vec<_> = document_scores
+        .iter()
+        .flat_map(|scores| {
+            scores.iter().filter_map(|score| match score {
+                ScoreDetails::Typo(typo) => Some((typo.typo_count, typo.max_typo_count)),
+                _ => None,
+            })
+        })
+        .collect();
+    insta::assert_snapshot!(format!("{typo_counts:?}"), @"[(0, 2), (1, 2), (1, 2), (2, 2)]");
+
+    // but the ranks still come from the costs, so that the scores follow the order of the buckets
+    let typo_ranks: Vec<_> = document_scores
+        .iter()
+        .flat_map(|scores| {
+            scores.iter().filter_map(|score| match score {
+                ScoreDetails::Typo(typo) => Some(typo.rank()),
+                _ => None,
+            })
+        })
+        .collect();
+    assert!(typo_ranks.windows(2).all(|ranks| ranks[0].local_score() > ranks[1].local_score()));
+    drop(txn);
+
+    // two typos can't cost less than one typo
+    index.update_settings(|s| s.set_typo_cost_one_typo(6)).unwrap_err();
+
+    index
+        .update_settings(|s| {
+            s.reset_typo_cost_two_typos();
+            s.reset_typo_cost_split();
+        })
+        .unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index)), @"[3, 0, 1, 2]");
+}
+
+#[test]
+fn test_first_letters_transposition() {
+    let index = TempIndex::new();
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Typo]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "sunflower" },
+            { "id": 1, "text": "usnflowar" },
+            { "id": 2, "text": "usnflawar" },
+            { "id": 3, "text": "house" },
+        ]))
+        .unwrap();
+
+    let search = |index: &TempIndex, query: &str| {
+        let txn = index.read_txn().unwrap();
+        let mut s = Search::new(&txn, index);
+        s.terms_matching_strategy(TermsMatchingStrategy::All);
+        s.query(query);
+        s.execute().unwrap().documents_ids
+    };
+
+    // `sunflower` is in the same bucket as `usnflowar`, with a single typo
+    insta::assert_snapshot!(format!("{:?}", search(&index, "usnflower")), @"[0, 1, 2]");
+    // the words allowing a single typo also match their transpositions
+    insta::assert_snapshot!(format!("{:?}", search(&index, "ohuse")), @"[3]");
+
+    // a transposition is not a typo on the first letter
+    index.update_settings(|s| s.set_first_letter_typos(FirstLetterTypos::Disallowed)).unwrap();
+    insta::assert_snapshot!(format!("{:?}", search(&index, "usnflower")), @"[0, 1, 2]");
+}
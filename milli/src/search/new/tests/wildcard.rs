@@ -0,0 +1,86 @@
+//! This module tests the `*` wildcards surrounding the words of the search query:
+//! 1. `run*` matches the words starting with `run`, wherever it is in the query
+//! 2. `*ing` matches the words ending with `ing`, and `*run*` the words containing `run`
+//! 3. the wildcards never allow any typo
+//! 4. the wildcards are located with the word they surround
+
+use crate::index::tests::TempIndex;
+use crate::{QueryTermKind, QueryTermLocation, Search, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "running fast" },
+            { "id": 1, "text": "the runner" },
+            { "id": 2, "text": "singing birds" },
+            { "id": 3, "text": "rerun the tests" },
+            { "id": 4, "text": "brunch" },
+        ]))
+        .unwrap();
+    index
+}
+
+fn search(index: &TempIndex, query: &str) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    let mut docids = s.execute().unwrap().documents_ids;
+    docids.sort_unstable();
+    docids
+}
+
+#[test]
+fn test_trailing_wildcard() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "run*"), vec![0, 1]);
+    // only the last word of the query is a prefix without the wildcard
+    assert_eq!(search(&index, "run fast"), vec![]);
+    assert_eq!(search(&index, "run* fast"), vec![0]);
+}
+
+#[test]
+fn test_leading_wildcard() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "*ing"), vec![0, 2]);
+    assert_eq!(search(&index, "*run"), vec![3]);
+    assert_eq!(search(&index, "*run*"), vec![0, 1, 3, 4]);
+    assert_eq!(search(&index, "*ing birds"), vec![2]);
+}
+
+#[test]
+fn test_wildcard_without_typos() {
+    let index = create_index();
+
+    assert_eq!(search(&index, "runnimg"), vec![0]);
+    assert_eq!(search(&index, "runnimg*"), vec![]);
+}
+
+#[test]
+fn test_wildcard_location() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.query("*ing fast");
+    s.query_term_locations(true);
+    let result = s.execute().unwrap();
+    assert_eq!(
+        result.query_term_locations,
+        vec![
+            QueryTermLocation { byte_range: 0..4, kind: QueryTermKind::Wildcard, matched: true },
+            QueryTermLocation { byte_range: 5..9, kind: QueryTermKind::Prefix, matched: true },
+        ]
+    );
+}
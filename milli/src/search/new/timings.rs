@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+/// The time spent in each stage of a search, computed on demand to investigate slow queries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchTimings {
+    /// The resolution of the filter into the universe of the search.
+    pub filter: Duration,
+    /// The tokenization of the query and the parsing of its terms.
+    pub query_parsing: Duration,
+    /// The building of the query graph and the resolution of the documents it matches.
+    pub graph_building: Duration,
+    /// The time spent in each ranking rule, identified by its id, in the order they are applied.
+    pub ranking_rules: Vec<(String, Duration)>,
+}
+
+impl SearchTimings {
+    /// Adds the timings of another search run for the same query, e.g. the semantic part
+    /// of a hybrid search.
+    pub fn merge(&mut self, other: SearchTimings) {
+        let SearchTimings { filter, query_parsing, graph_building, ranking_rules } = other;
+        self.filter += filter;
+        self.query_parsing += query_parsing;
+        self.graph_building += graph_building;
+        self.ranking_rules.extend(ranking_rules);
+    }
+}
@@ -141,6 +141,11 @@ impl<'a> Similar<'a> {
             document_scores,
             degraded: false,
             used_negative_operator: false,
+            query_truncated: false,
+            query_term_locations: Vec::new(),
+            query_interpretation: None,
+            relaxed_phrases: false,
+            timings: None,
         })
     }
 }
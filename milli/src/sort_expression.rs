@@ -0,0 +1,283 @@
+//! Arithmetic expressions over the sortable numeric fields, used to sort the documents at search
+//! time without indexing a precomputed field, e.g. `price / weight:asc`.
+//!
+//! The operators must be separated from their operands by whitespace, so that the field names
+//! containing an operator, like `a-b`, are still sorted as regular fields.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::error::is_reserved_keyword;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum SortExpressionError {
+    #[error("the expression ends unexpectedly")]
+    UnexpectedEnd,
+    #[error("unexpected `{0}`")]
+    UnexpectedToken(String),
+    #[error("`{0}` is a reserved keyword and can't be used in an expression")]
+    ReservedKeyword(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operator {
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "+" => Some(Operator::Add),
+            "-" => Some(Operator::Sub),
+            "*" => Some(Operator::Mul),
+            "/" => Some(Operator::Div),
+            _ => None,
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Operator::Add | Operator::Sub => 0,
+            Operator::Mul | Operator::Div => 1,
+        }
+    }
+
+    fn apply(&self, left: f64, right: f64) -> f64 {
+        match self {
+            Operator::Add => left + right,
+            Operator::Sub => left - right,
+            Operator::Mul => left * right,
+            Operator::Div => left / right,
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::Add => f.write_str("+"),
+            Operator::Sub => f.write_str("-"),
+            Operator::Mul => f.write_str("*"),
+            Operator::Div => f.write_str("/"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SortExpression {
+    Number(f64),
+    Field(String),
+    Operation { operator: Operator, left: Box<SortExpression>, right: Box<SortExpression> },
+}
+
+impl SortExpression {
+    /// Returns `true` if the text contains an operator and must be parsed as an expression
+    /// rather than as a field name.
+    pub fn is_expression(text: &str) -> bool {
+        text.split_whitespace().any(|token| Operator::from_token(token).is_some())
+    }
+
+    pub fn parse(text: &str) -> Result<Self, SortExpressionError> {
+        let mut tokens = tokenize(text).into_iter().peekable();
+        let expression = parse_sum(&mut tokens)?;
+        match tokens.next() {
+            Some(token) => Err(SortExpressionError::UnexpectedToken(token.to_string())),
+            None => Ok(expression),
+        }
+    }
+
+    /// Returns the names of the fields used by the expression.
+    pub fn fields(&self) -> Vec<&str> {
+        match self {
+            SortExpression::Number(_) => Vec::new(),
+            SortExpression::Field(name) => vec![name.as_str()],
+            SortExpression::Operation { left, right, .. } => {
+                let mut fields = left.fields();
+                fields.extend(right.fields());
+                fields
+            }
+        }
+    }
+
+    /// Evaluates the expression with the values of the fields given by `value`.
+    ///
+    /// Returns `None` if a field has no value or if the result isn't a finite number,
+    /// e.g. on a division by zero.
+    pub fn evaluate(&self, value: &mut impl FnMut(&str) -> Option<f64>) -> Option<f64> {
+        let result = match self {
+            SortExpression::Number(number) => *number,
+            SortExpression::Field(name) => value(name)?,
+            SortExpression::Operation { operator, left, right } => {
+                operator.apply(left.evaluate(value)?, right.evaluate(value)?)
+            }
+        };
+        result.is_finite().then_some(result)
+    }
+}
+
+impl fmt::Display for SortExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortExpression::Number(number) => write!(f, "{number}"),
+            SortExpression::Field(name) => f.write_str(name),
+            SortExpression::Operation { operator, left, right } => {
+                let precedence = operator.precedence();
+                match left.as_ref() {
+                    SortExpression::Operation { operator: left_operator, .. }
+                        if left_operator.precedence() < precedence =>
+                    {
+                        write!(f, "({left})")?
+                    }
+                    _ => write!(f, "{left}")?,
+                }
+                write!(f, " {operator} ")?;
+                match right.as_ref() {
+                    SortExpression::Operation { operator: right_operator, .. }
+                        if right_operator.precedence() <= precedence =>
+                    {
+                        write!(f, "({right})")
+                    }
+                    _ => write!(f, "{right}"),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    LeftParenthesis,
+    RightParenthesis,
+    Operator(Operator),
+    Operand(&'a str),
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::LeftParenthesis => f.write_str("("),
+            Token::RightParenthesis => f.write_str(")"),
+            Token::Operator(operator) => write!(f, "{operator}"),
+            Token::Operand(operand) => f.write_str(operand),
+        }
+    }
+}
+
+/// Splits the expression on whitespace, then splits the parentheses at the edges of the words.
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    for word in text.split_whitespace() {
+        if let Some(operator) = Operator::from_token(word) {
+            tokens.push(Token::Operator(operator));
+            continue;
+        }
+
+        let operand = word.trim_start_matches('(');
+        tokens.extend((0..word.len() - operand.len()).map(|_| Token::LeftParenthesis));
+        let trimmed = operand.trim_end_matches(')');
+        if !trimmed.is_empty() {
+            tokens.push(Token::Operand(trimmed));
+        }
+        tokens.extend((0..operand.len() - trimmed.len()).map(|_| Token::RightParenthesis));
+    }
+    tokens
+}
+
+type Tokens<'a> = std::iter::Peekable<std::vec::IntoIter<Token<'a>>>;
+
+fn parse_sum(tokens: &mut Tokens<'_>) -> Result<SortExpression, SortExpressionError> {
+    let mut left = parse_product(tokens)?;
+    while let Some(&Token::Operator(operator @ (Operator::Add | Operator::Sub))) = tokens.peek() {
+        tokens.next();
+        let right = parse_product(tokens)?;
+        left = SortExpression::Operation { operator, left: Box::new(left), right: Box::new(right) };
+    }
+    Ok(left)
+}
+
+fn parse_product(tokens: &mut Tokens<'_>) -> Result<SortExpression, SortExpressionError> {
+    let mut left = parse_operand(tokens)?;
+    while let Some(&Token::Operator(operator @ (Operator::Mul | Operator::Div))) = tokens.peek() {
+        tokens.next();
+        let right = parse_operand(tokens)?;
+        left = SortExpression::Operation { operator, left: Box::new(left), right: Box::new(right) };
+    }
+    Ok(left)
+}
+
+fn parse_operand(tokens: &mut Tokens<'_>) -> Result<SortExpression, SortExpressionError> {
+    match tokens.next() {
+        Some(Token::LeftParenthesis) => {
+            let expression = parse_sum(tokens)?;
+            match tokens.next() {
+                Some(Token::RightParenthesis) => Ok(expression),
+                Some(token) => Err(SortExpressionError::UnexpectedToken(token.to_string())),
+                None => Err(SortExpressionError::UnexpectedEnd),
+            }
+        }
+        Some(Token::Operand(operand)) => match operand.parse::<f64>() {
+            Ok(number) if number.is_finite() => Ok(SortExpression::Number(number)),
+            _ if is_reserved_keyword(operand) || operand.starts_with("_geo") => {
+                Err(SortExpressionError::ReservedKeyword(operand.to_string()))
+            }
+            _ => Ok(SortExpression::Field(operand.to_string())),
+        },
+        Some(token) => Err(SortExpressionError::UnexpectedToken(token.to_string())),
+        None => Err(SortExpressionError::UnexpectedEnd),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn parse_and_evaluate() {
+        let values = HashMap::from([("price", 12.0), ("weight", 4.0), ("tax", 3.0)]);
+        let mut value = |name: &str| values.get(name).copied();
+
+        let expression = SortExpression::parse("price / weight").unwrap();
+        assert_eq!(expression.fields(), ["price", "weight"]);
+        assert_eq!(expression.evaluate(&mut value), Some(3.0));
+
+        let expression = SortExpression::parse("(price + tax) / weight * 2").unwrap();
+        assert_eq!(expression.to_string(), "(price + tax) / weight * 2");
+        assert_eq!(expression.evaluate(&mut value), Some(7.5));
+
+        let expression = SortExpression::parse("price - (tax - 1)").unwrap();
+        assert_eq!(expression.to_string(), "price - (tax - 1)");
+        assert_eq!(expression.evaluate(&mut value), Some(10.0));
+
+        // a missing field and a division by zero don't give any value
+        assert_eq!(SortExpression::parse("price * stock").unwrap().evaluate(&mut value), None);
+        assert_eq!(SortExpression::parse("price / 0").unwrap().evaluate(&mut value), None);
+    }
+
+    #[test]
+    fn detect_and_reject_expressions() {
+        assert!(SortExpression::is_expression("price / weight"));
+        assert!(!SortExpression::is_expression("a-b"));
+        assert!(!SortExpression::is_expression("truc(12, 13)"));
+
+        assert_eq!(SortExpression::parse("price /"), Err(SortExpressionError::UnexpectedEnd));
+        assert_eq!(
+            SortExpression::parse("(price / weight"),
+            Err(SortExpressionError::UnexpectedEnd)
+        );
+        assert_eq!(
+            SortExpression::parse("price weight / 2"),
+            Err(SortExpressionError::UnexpectedToken(String::from("weight")))
+        );
+        assert_eq!(
+            SortExpression::parse("_geo * 2"),
+            Err(SortExpressionError::ReservedKeyword(String::from("_geo")))
+        );
+    }
+}
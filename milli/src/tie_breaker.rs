@@ -0,0 +1,21 @@
+//! The final ordering of the documents that all the ranking rules consider equal.
+//!
+//! Without a tie-breaker the documents of the last bucket are returned in the order of their
+//! internal ids, which depends on the order in which they were indexed and changes when the
+//! index is rebuilt. A tie-breaker orders them by a value of the documents instead, so that
+//! the results keep the same order across reindexing.
+//!
+//! The primary keys are compared as strings, `"10"` sorting before `"2"`. The field
+//! tie-breaker sorts the documents like an `asc` or `desc` ranking rule on a sortable
+//! attribute, the documents sharing the same value keeping the order of their internal ids.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TieBreaker {
+    /// Sorts the documents by their primary key.
+    PrimaryKey { ascending: bool },
+    /// Sorts the documents by the values of a sortable attribute.
+    Field { field: String, ascending: bool },
+}
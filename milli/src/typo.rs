@@ -0,0 +1,226 @@
+use charabia::normalizer::{CharNormalizer, CompatibilityDecompositionNormalizer};
+use serde::{Deserialize, Serialize};
+
+/// Describes which single typos only count as "half" a typo in the typo ranking rule.
+///
+/// Half typos are derived exactly like regular one-typo derivations, they only get a
+/// cheaper edge in the typo graph so that documents matching them are ranked before
+/// the documents matching a full typo.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HalfTypos {
+    /// Substituting a character by one of its neighbouring keys on this layout is a half typo.
+    #[serde(default)]
+    pub keyboard_layout: Option<KeyboardLayout>,
+    /// Substituting a character by the same character with different diacritics is a half typo.
+    #[serde(default)]
+    pub accents: bool,
+}
+
+impl HalfTypos {
+    pub fn is_enabled(&self) -> bool {
+        self.keyboard_layout.is_some() || self.accents
+    }
+
+    /// Returns `true` if `derivation` only differs from `original` by a single substitution
+    /// that is considered a half typo.
+    ///
+    /// When `is_prefix` is `true` the derivation may be longer than the original word,
+    /// only the first characters of the derivation are compared.
+    pub fn is_half_typo(&self, original: &str, derivation: &str, is_prefix: bool) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+
+        let mut original = original.chars();
+        let mut derivation = derivation.chars();
+        let mut substitution = None;
+        loop {
+            match (original.next(), derivation.next()) {
+                (Some(o), Some(d)) if o == d => continue,
+                (Some(o), Some(d)) => {
+                    if substitution.replace((o, d)).is_some() {
+                        return false;
+                    }
+                }
+                (None, None) => break,
+                (None, Some(_)) if is_prefix => break,
+                _ => return false,
+            }
+        }
+
+        match substitution {
+            Some((o, d)) => {
+                (self.accents && fold_diacritics(o) == fold_diacritics(d))
+                    || self.keyboard_layout.map_or(false, |layout| layout.are_neighbours(o, d))
+            }
+            None => false,
+        }
+    }
+}
+
+/// Returns `true` if `derivation` only differs from `original` by two swapped adjacent
+/// characters, e.g. `hte` → `the`, which counts as a single typo.
+///
+/// When `is_prefix` is `true` the derivation may be longer than the original word,
+/// only the first characters of the derivation are compared.
+pub fn is_transposition(original: &str, derivation: &str, is_prefix: bool) -> bool {
+    let original: Vec<char> = original.chars().collect();
+    let derivation: Vec<char> = derivation.chars().collect();
+    let Some(i) = original.iter().zip(&derivation).position(|(o, d)| o != d) else {
+        return false;
+    };
+    if i + 1 >= original.len() || i + 1 >= derivation.len() {
+        return false;
+    }
+    if original[i] != derivation[i + 1] || original[i + 1] != derivation[i] {
+        return false;
+    }
+    let (original_rest, derivation_rest) = (&original[i + 2..], &derivation[i + 2..]);
+    if is_prefix {
+        derivation_rest.starts_with(original_rest)
+    } else {
+        derivation_rest == original_rest
+    }
+}
+
+/// Returns the word with its first two characters swapped, if they are different.
+pub(crate) fn swap_first_characters(word: &str) -> Option<String> {
+    let mut chars = word.chars();
+    match (chars.next(), chars.next()) {
+        (Some(first), Some(second)) if first != second => {
+            Some(format!("{second}{first}{}", chars.as_str()))
+        }
+        _ => None,
+    }
+}
+
+/// How the typos on the first letter of a word are handled, as users rarely mistype it.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FirstLetterTypos {
+    /// A typo on the first letter counts as two typos.
+    #[default]
+    CountAsTwo,
+    /// A typo on the first letter counts as two typos, but the documents matching it
+    /// are ranked after the ones matching any other two typos.
+    Penalized,
+    /// The first letter must be typed correctly.
+    Disallowed,
+}
+
+/// The cost of the edges of the typo graph, the documents matching the cheapest edges
+/// being ranked first.
+///
+/// The costs only order the documents, the typo score keeps counting the typos.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TypoCosts {
+    /// The cost of a derivation with one typo.
+    pub one_typo: u32,
+    /// The cost of a derivation with two typos, must not be lower than `one_typo`.
+    pub two_typos: u32,
+    /// The cost of a missing or an extra space in the query, i.e. of a split word
+    /// or of a word made of several words of the query.
+    pub split: u32,
+}
+
+impl Default for TypoCosts {
+    fn default() -> Self {
+        TypoCosts { one_typo: 1, two_typos: 2, split: 1 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyboardLayout {
+    Qwerty,
+    Azerty,
+    Qwertz,
+}
+
+impl KeyboardLayout {
+    fn rows(&self) -> &'static [&'static str] {
+        match self {
+            KeyboardLayout::Qwerty => &["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"],
+            KeyboardLayout::Azerty => &["1234567890", "azertyuiop", "qsdfghjklm", "wxcvbn"],
+            KeyboardLayout::Qwertz => &["1234567890", "qwertzuiop", "asdfghjkl", "yxcvbnm"],
+        }
+    }
+
+    fn position(&self, c: char) -> Option<(usize, usize)> {
+        self.rows()
+            .iter()
+            .enumerate()
+            .find_map(|(row, keys)| keys.chars().position(|k| k == c).map(|column| (row, column)))
+    }
+
+    /// Returns `true` if the two characters are different keys touching each other on this layout.
+    pub fn are_neighbours(&self, a: char, b: char) -> bool {
+        match (self.position(a), self.position(b)) {
+            (Some((row_a, col_a)), Some((row_b, col_b))) => {
+                a != b && row_a.abs_diff(row_b) <= 1 && col_a.abs_diff(col_b) <= 1
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Returns the base character of `c`, stripped from its diacritics.
+fn fold_diacritics(c: char) -> char {
+    let mut buffer = [0; 4];
+    let decomposed = CompatibilityDecompositionNormalizer.normalize_str(c.encode_utf8(&mut buffer));
+    decomposed.chars().next().unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyboard_neighbours() {
+        let half_typos =
+            HalfTypos { keyboard_layout: Some(KeyboardLayout::Qwerty), accents: false };
+        assert!(half_typos.is_half_typo("hello", "jello", false));
+        assert!(half_typos.is_half_typo("hello", "hrllo", false));
+        assert!(!half_typos.is_half_typo("hello", "hallo", false));
+        // two substitutions are never a half typo
+        assert!(!half_typos.is_half_typo("hello", "jrllo", false));
+        // neither are insertions or deletions
+        assert!(!half_typos.is_half_typo("hello", "helo", false));
+        assert!(half_typos.is_half_typo("hel", "jello", true));
+        assert!(!half_typos.is_half_typo("hel", "jello", false));
+
+        let half_typos =
+            HalfTypos { keyboard_layout: Some(KeyboardLayout::Azerty), accents: false };
+        assert!(half_typos.is_half_typo("arbre", "qrbre", false));
+        // `m` and `n` are only neighbours on qwerty-like layouts
+        assert!(!half_typos.is_half_typo("maison", "naison", false));
+    }
+
+    #[test]
+    fn transpositions() {
+        assert!(is_transposition("hte", "the", false));
+        assert!(is_transposition("hoeu", "houe", false));
+        assert!(!is_transposition("the", "the", false));
+        // the swapped characters must be adjacent
+        assert!(!is_transposition("abcd", "cbad", false));
+        // and the rest of the words must be the same
+        assert!(!is_transposition("hte", "thee", false));
+        assert!(is_transposition("hte", "thee", true));
+        assert!(!is_transposition("ht", "the", false));
+
+        assert_eq!(swap_first_characters("hte").as_deref(), Some("the"));
+        assert_eq!(swap_first_characters("aab"), None);
+        assert_eq!(swap_first_characters("a"), None);
+    }
+
+    #[test]
+    fn accents() {
+        let half_typos = HalfTypos { keyboard_layout: None, accents: true };
+        assert!(half_typos.is_half_typo("cafe", "café", false));
+        assert!(half_typos.is_half_typo("über", "uber", false));
+        assert!(!half_typos.is_half_typo("cafe", "cafo", false));
+        assert!(!HalfTypos::default().is_half_typo("cafe", "café", false));
+    }
+}
@@ -2,6 +2,8 @@ use heed::RwTxn;
 use roaring::RoaringBitmap;
 use time::OffsetDateTime;
 
+use crate::fields_schema::FieldsSchema;
+use crate::index::FieldsStats;
 use crate::{FieldDistribution, Index, Result};
 
 pub struct ClearDocuments<'t, 'i> {
@@ -22,6 +24,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
     )]
     pub fn execute(self) -> Result<u64> {
         self.index.set_updated_at(self.wtxn, &OffsetDateTime::now_utc())?;
+        self.index.put_acl_indexed(self.wtxn)?;
         let Index {
             env: _env,
             main: _main,
@@ -34,6 +37,8 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             word_position_docids,
             word_fid_docids,
             field_id_word_count_docids,
+            word_frequency_docids,
+            document_length_docids,
             word_prefix_position_docids,
             word_prefix_fid_docids,
             script_language_docids,
@@ -44,11 +49,13 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             facet_id_exists_docids,
             facet_id_is_null_docids,
             facet_id_is_empty_docids,
+            facet_id_bool_docids,
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
             embedder_category_id: _,
             documents,
+            attachments,
         } = self.index;
 
         let empty_roaring = RoaringBitmap::default();
@@ -61,6 +68,11 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         self.index.put_words_prefixes_fst(self.wtxn, &fst::Set::default())?;
         self.index.put_documents_ids(self.wtxn, &empty_roaring)?;
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
+        self.index.put_fields_stats(self.wtxn, &FieldsStats::default())?;
+        self.index.put_fields_schema(self.wtxn, &FieldsSchema::default())?;
+        self.index.delete_numeric_sort_indexes(self.wtxn)?;
+        self.index.delete_prefix_cache(self.wtxn)?;
+        self.index.delete_phonetic_words_fst(self.wtxn)?;
         self.index.delete_geo_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
 
@@ -81,6 +93,8 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         word_position_docids.clear(self.wtxn)?;
         word_fid_docids.clear(self.wtxn)?;
         field_id_word_count_docids.clear(self.wtxn)?;
+        word_frequency_docids.clear(self.wtxn)?;
+        document_length_docids.clear(self.wtxn)?;
         word_prefix_position_docids.clear(self.wtxn)?;
         word_prefix_fid_docids.clear(self.wtxn)?;
         script_language_docids.clear(self.wtxn)?;
@@ -90,6 +104,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         facet_id_exists_docids.clear(self.wtxn)?;
         facet_id_is_null_docids.clear(self.wtxn)?;
         facet_id_is_empty_docids.clear(self.wtxn)?;
+        facet_id_bool_docids.clear(self.wtxn)?;
         facet_id_string_docids.clear(self.wtxn)?;
         field_id_docid_facet_f64s.clear(self.wtxn)?;
         field_id_docid_facet_strings.clear(self.wtxn)?;
@@ -97,6 +112,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         vector_arroy.clear(self.wtxn)?;
 
         documents.clear(self.wtxn)?;
+        attachments.clear(self.wtxn)?;
 
         Ok(number_of_documents)
     }
@@ -135,6 +151,8 @@ mod tests {
         assert!(index.external_documents_ids().is_empty(&rtxn).unwrap());
         assert!(index.documents_ids(&rtxn).unwrap().is_empty());
         assert!(index.field_distribution(&rtxn).unwrap().is_empty());
+        assert!(index.fields_stats(&rtxn).unwrap().is_empty());
+        assert!(index.fields_schema(&rtxn).unwrap().is_empty());
         assert!(index.geo_rtree(&rtxn).unwrap().is_none());
         assert!(index.geo_faceted_documents_ids(&rtxn).unwrap().is_empty());
 
@@ -142,6 +160,8 @@ mod tests {
         assert!(index.word_prefix_docids.is_empty(&rtxn).unwrap());
         assert!(index.word_pair_proximity_docids.is_empty(&rtxn).unwrap());
         assert!(index.field_id_word_count_docids.is_empty(&rtxn).unwrap());
+        assert!(index.word_frequency_docids.is_empty(&rtxn).unwrap());
+        assert!(index.document_length_docids.is_empty(&rtxn).unwrap());
         assert!(index.facet_id_f64_docids.is_empty(&rtxn).unwrap());
         assert!(index.facet_id_string_docids.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_f64s.is_empty(&rtxn).unwrap());
@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{RoTxn, RwTxn};
+
+use crate::index::{FieldStats, FieldsStats};
+use crate::{CboRoaringBitmapLenCodec, FieldId, Index, Result};
+
+/// The changes made to the statistics of the fields by an indexing operation.
+///
+/// They are recorded while the entries of the extractors are written into the databases,
+/// so that the stats can be updated without scanning the whole index.
+#[derive(Debug, Default)]
+pub(crate) struct FieldsStatsDelta {
+    fields: BTreeMap<FieldId, FieldStatsDelta>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct FieldStatsDelta {
+    distinct_terms: i64,
+    term_document_pairs: i64,
+    total_tokens: i64,
+    facet_cardinality: i64,
+}
+
+impl FieldsStatsDelta {
+    /// Records the change of the number of documents containing a term in a field.
+    pub fn record_term(&mut self, fid: FieldId, previous_len: u64, len: u64) {
+        let delta = self.fields.entry(fid).or_default();
+        delta.distinct_terms += (len != 0) as i64 - (previous_len != 0) as i64;
+        delta.term_document_pairs += len as i64 - previous_len as i64;
+    }
+
+    /// Records tokens added to a field, or removed from it when `count` is negative.
+    pub fn record_tokens(&mut self, fid: FieldId, count: i64) {
+        self.fields.entry(fid).or_default().total_tokens += count;
+    }
+
+    /// Records facet values added to a field, or removed from it when `count` is negative.
+    pub fn record_facet_values(&mut self, fid: FieldId, count: i64) {
+        self.fields.entry(fid).or_default().facet_cardinality += count;
+    }
+}
+
+/// Updates the statistics of every field with the changes made by an indexing operation.
+///
+/// The stats of the indexes created before they existed are computed from scratch once.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::fields_stats")]
+pub(crate) fn update_fields_stats(
+    index: &Index,
+    wtxn: &mut RwTxn<'_>,
+    mut delta: FieldsStatsDelta,
+) -> Result<()> {
+    let fields_ids_map = index.fields_ids_map(wtxn)?;
+    let Some(mut fields_stats) = index.stored_fields_stats(wtxn)? else {
+        let mut fields_stats = compute_fields_stats(index, wtxn)?;
+        // the tokens can only be counted while extracting the documents
+        for (fid, name) in fields_ids_map.iter() {
+            if let (Some(delta), Some(field_stats)) =
+                (delta.fields.get(&fid), fields_stats.get_mut(name))
+            {
+                field_stats.total_tokens = delta.total_tokens.max(0) as u64;
+            }
+        }
+        index.put_fields_stats(wtxn, &fields_stats)?;
+        return Ok(());
+    };
+    let field_distribution = index.field_distribution(wtxn)?;
+    let number_of_documents = index.number_of_documents(wtxn)?;
+
+    let fields_stats = fields_ids_map
+        .iter()
+        .map(|(fid, name)| {
+            let mut field_stats = fields_stats.remove(name).unwrap_or_default();
+            if let Some(delta) = delta.fields.remove(&fid) {
+                field_stats.distinct_terms =
+                    field_stats.distinct_terms.saturating_add_signed(delta.distinct_terms);
+                field_stats.term_document_pairs = field_stats
+                    .term_document_pairs
+                    .saturating_add_signed(delta.term_document_pairs);
+                field_stats.total_tokens =
+                    field_stats.total_tokens.saturating_add_signed(delta.total_tokens);
+                field_stats.facet_cardinality =
+                    field_stats.facet_cardinality.saturating_add_signed(delta.facet_cardinality);
+            }
+            let documents = field_distribution.get(name).copied().unwrap_or_default();
+            field_stats.documents_ratio = if number_of_documents != 0 {
+                documents as f64 / number_of_documents as f64
+            } else {
+                0.0
+            };
+            (name.to_string(), field_stats)
+        })
+        .collect();
+
+    index.put_fields_stats(wtxn, &fields_stats)?;
+    Ok(())
+}
+
+/// Computes the statistics of every field of the index from the content of its databases.
+///
+/// The tokens are not stored in the databases and are left uncounted.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::fields_stats")]
+pub(crate) fn compute_fields_stats(index: &Index, rtxn: &RoTxn<'_>) -> Result<FieldsStats> {
+    let fields_ids_map = index.fields_ids_map(rtxn)?;
+    let field_distribution = index.field_distribution(rtxn)?;
+    let number_of_documents = index.number_of_documents(rtxn)?;
+
+    let mut stats: BTreeMap<FieldId, FieldStats> = BTreeMap::new();
+
+    let word_fid_docids = index.word_fid_docids.remap_data_type::<CboRoaringBitmapLenCodec>();
+    for result in word_fid_docids.iter(rtxn)? {
+        let ((_word, fid), len) = result?;
+        let field_stats = stats.entry(fid).or_default();
+        if len != 0 {
+            field_stats.distinct_terms += 1;
+            field_stats.term_document_pairs += len;
+        }
+    }
+
+    for fid in index.faceted_fields_ids(rtxn)? {
+        // the level 0 of the facet databases contains exactly one entry per facet value
+        let mut prefix = fid.to_be_bytes().to_vec();
+        prefix.push(0);
+        let mut cardinality = 0;
+        for db in [
+            index.facet_id_f64_docids.remap_types::<Bytes, DecodeIgnore>(),
+            index.facet_id_string_docids.remap_types::<Bytes, DecodeIgnore>(),
+        ] {
+            for result in db.prefix_iter(rtxn, &prefix)? {
+                result?;
+                cardinality += 1;
+            }
+        }
+        stats.entry(fid).or_default().facet_cardinality = cardinality;
+    }
+
+    Ok(fields_ids_map
+        .iter()
+        .map(|(fid, name)| {
+            let mut field_stats = stats.remove(&fid).unwrap_or_default();
+            let documents = field_distribution.get(name).copied().unwrap_or_default();
+            if number_of_documents != 0 {
+                field_stats.documents_ratio = documents as f64 / number_of_documents as f64;
+            }
+            (name.to_string(), field_stats)
+        })
+        .collect())
+}
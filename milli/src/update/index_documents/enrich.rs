@@ -5,6 +5,7 @@ use std::result::Result as StdResult;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::attachments::{parse_attachments, AttachmentsQuotas, RESERVED_ATTACHMENTS_FIELD_NAME};
 use crate::documents::{
     DocumentIdExtractionError, DocumentsBatchIndex, DocumentsBatchReader,
     EnrichedDocumentsBatchReader, PrimaryKey, DEFAULT_PRIMARY_KEY,
@@ -17,7 +18,8 @@ use crate::{FieldId, Index, Result};
 ///  - we can infer a primary key,
 ///  - all the documents id exist and are extracted,
 ///  - the validity of them but also,
-///  - the validity of the `_geo` field depending on the settings.
+///  - the validity of the `_geo` field depending on the settings,
+///  - the validity and the size of the `_attachments` field.
 ///
 /// # Panics
 ///
@@ -27,6 +29,7 @@ pub fn enrich_documents_batch<R: Read + Seek>(
     rtxn: &heed::RoTxn<'_>,
     index: &Index,
     autogenerate_docids: bool,
+    attachments_quotas: AttachmentsQuotas,
     reader: DocumentsBatchReader<R>,
 ) -> Result<StdResult<EnrichedDocumentsBatchReader<R>, UserError>> {
     let (mut cursor, mut documents_batch_index) = reader.into_cursor_and_fields_index();
@@ -103,6 +106,8 @@ pub fn enrich_documents_batch<R: Read + Seek>(
         _otherwise => None,
     };
 
+    let attachments_field_id = documents_batch_index.id(RESERVED_ATTACHMENTS_FIELD_NAME);
+
     let mut count = 0;
     while let Some(document) = cursor.next_document()? {
         let document_id = match fetch_or_generate_document_id(
@@ -123,6 +128,16 @@ pub fn enrich_documents_batch<R: Read + Seek>(
             }
         }
 
+        if let Some(attachments) = attachments_field_id.and_then(|fid| document.get(fid)) {
+            let attachments =
+                serde_json::from_slice(attachments).map_err(InternalError::SerdeJson)?;
+            if let Err(user_error) =
+                parse_attachments(document_id.value(), &attachments, attachments_quotas)
+            {
+                return Ok(Err(user_error));
+            }
+        }
+
         let document_id = serde_json::to_vec(&document_id).map_err(InternalError::SerdeJson)?;
         external_ids.insert(count.to_be_bytes(), document_id)?;
 
@@ -1,16 +1,19 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::File;
 use std::io::BufReader;
 use std::{io, mem, str};
 
 use charabia::{Language, Script, SeparatorKind, Token, TokenKind, Tokenizer, TokenizerBuilder};
-use obkv::{KvReader, KvWriterU16};
+use obkv::{KvReader, KvWriterU16, KvWriterU32};
 use roaring::RoaringBitmap;
 use serde_json::Value;
 
 use super::helpers::{create_sorter, keep_latest_obkv, sorter_into_reader, GrenadParameters};
+use crate::chunking::passage_position;
 use crate::error::{InternalError, SerializationError};
+use crate::language_preset::{languages_stop_words, LanguagePreset};
+use crate::proximity::MAX_DISTANCE;
 use crate::update::del_add::{del_add_from_two_obkvs, DelAdd, KvReaderDelAdd};
 use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
 use crate::{FieldId, Result, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH};
@@ -61,13 +64,31 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         .map(|s| s.iter().map(String::as_str).collect());
     let old_dictionary: Option<Vec<_>> =
         settings_diff.old.dictionary.as_ref().map(|s| s.iter().map(String::as_str).collect());
+    let old_allow_list = settings_diff.old.language_preset.map(|preset| preset.allow_list());
     let mut del_builder = tokenizer_builder(
         old_stop_words,
         old_separators.as_deref(),
         old_dictionary.as_deref(),
-        None,
+        old_allow_list.as_ref(),
     );
     let del_tokenizer = del_builder.build();
+    let old_languages = field_languages_tokenization(&settings_diff.old)?;
+    let mut old_language_builders: Vec<_> = old_languages
+        .iter()
+        .map(|(language, stop_words, allow_list)| {
+            let builder = tokenizer_builder(
+                Some(stop_words),
+                old_separators.as_deref(),
+                old_dictionary.as_deref(),
+                Some(allow_list),
+            );
+            (*language, builder)
+        })
+        .collect();
+    let del_language_tokenizers: HashMap<_, _> = old_language_builders
+        .iter_mut()
+        .map(|(language, builder)| (*language, builder.build()))
+        .collect();
 
     let new_stop_words = settings_diff.new.stop_words.as_ref();
     let new_separators: Option<Vec<_>> = settings_diff
@@ -77,13 +98,31 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         .map(|s| s.iter().map(String::as_str).collect());
     let new_dictionary: Option<Vec<_>> =
         settings_diff.new.dictionary.as_ref().map(|s| s.iter().map(String::as_str).collect());
+    let new_allow_list = settings_diff.new.language_preset.map(|preset| preset.allow_list());
     let mut add_builder = tokenizer_builder(
         new_stop_words,
         new_separators.as_deref(),
         new_dictionary.as_deref(),
-        None,
+        new_allow_list.as_ref(),
     );
     let add_tokenizer = add_builder.build();
+    let new_languages = field_languages_tokenization(&settings_diff.new)?;
+    let mut new_language_builders: Vec<_> = new_languages
+        .iter()
+        .map(|(language, stop_words, allow_list)| {
+            let builder = tokenizer_builder(
+                Some(stop_words),
+                new_separators.as_deref(),
+                new_dictionary.as_deref(),
+                Some(allow_list),
+            );
+            (*language, builder)
+        })
+        .collect();
+    let add_language_tokenizers: HashMap<_, _> = new_language_builders
+        .iter_mut()
+        .map(|(language, builder)| (*language, builder.build()))
+        .collect();
 
     // iterate over documents.
     let mut cursor = obkv_documents.into_cursor()?;
@@ -113,6 +152,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     &settings_diff.old,
                     &del_tokenizer,
+                    &del_language_tokenizers,
                     max_positions_per_attributes,
                     DelAdd::Deletion,
                     &mut del_buffers,
@@ -124,6 +164,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     &settings_diff.new,
                     &add_tokenizer,
+                    &add_language_tokenizers,
                     max_positions_per_attributes,
                     DelAdd::Addition,
                     &mut add_buffers,
@@ -135,7 +176,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         let (add_obkv, add_script_language_word_count) = add?;
 
         // merge deletions and additions.
-        // transforming two KV<FieldId, KV<u16, String>> into one KV<FieldId, KV<DelAdd, KV<u16, String>>>
+        // transforming two KV<FieldId, KV<u32, String>> into one KV<FieldId, KV<DelAdd, KV<u32, String>>>
         value_buffer.clear();
         del_add_from_two_obkvs(
             &KvReader::<FieldId>::new(del_obkv),
@@ -143,7 +184,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
             &mut value_buffer,
         )?;
 
-        // write each KV<DelAdd, KV<u16, String>> into the sorter, field by field.
+        // write each KV<DelAdd, KV<u32, String>> into the sorter, field by field.
         let obkv = KvReader::<FieldId>::new(&value_buffer);
         for (field_id, value) in obkv.iter() {
             key_buffer.truncate(mem::size_of::<u32>());
@@ -172,7 +213,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         }
     }
 
-    // the returned sorter is serialized as: key: (DocId, FieldId), value: KV<DelAdd, KV<u16, String>>.
+    // the returned sorter is serialized as: key: (DocId, FieldId), value: KV<DelAdd, KV<u32, String>>.
     sorter_into_reader(docid_word_positions_sorter, indexer)
         .map(|reader| (reader, script_language_docids))
 }
@@ -225,12 +266,28 @@ fn tokenizer_builder<'a>(
     tokenizer_builder
 }
 
+/// Returns the stop words and the allow list given to the tokenizer of each language
+/// of the fields, the stop words of the language being added to the ones of the index.
+fn field_languages_tokenization(
+    settings: &InnerIndexSettings,
+) -> Result<Vec<(LanguagePreset, fst::Set<Vec<u8>>, HashMap<Script, Vec<Language>>)>> {
+    let languages: HashSet<_> = settings.field_languages.values().copied().collect();
+    languages
+        .into_iter()
+        .map(|language| {
+            let stop_words = languages_stop_words(settings.stop_words.as_ref(), &[language])?;
+            Ok((language, stop_words, language.allow_list()))
+        })
+        .collect()
+}
+
 /// Extract words mapped with their positions of a document,
 /// ensuring no Language detection mistakes was made.
 fn lang_safe_tokens_from_document<'a>(
     obkv: &KvReader<'_, FieldId>,
     settings: &InnerIndexSettings,
     tokenizer: &Tokenizer<'_>,
+    language_tokenizers: &HashMap<LanguagePreset, Tokenizer<'_>>,
     max_positions_per_attributes: u32,
     del_add: DelAdd,
     buffers: &'a mut Buffers,
@@ -239,8 +296,9 @@ fn lang_safe_tokens_from_document<'a>(
 
     tokens_from_document(
         obkv,
-        &settings.searchable_fields_ids,
+        settings,
         tokenizer,
+        language_tokenizers,
         max_positions_per_attributes,
         del_add,
         buffers,
@@ -250,10 +308,12 @@ fn lang_safe_tokens_from_document<'a>(
     // if we detect a potetial mistake in the language detection,
     // we rerun the extraction forcing the tokenizer to detect the most frequently detected Languages.
     // context: https://github.com/meilisearch/meilisearch/issues/3565
-    if script_language_word_count
-        .values()
-        .map(Vec::as_slice)
-        .any(potential_language_detection_error)
+    // There is nothing to detect when a language preset forces the language.
+    if settings.language_preset.is_none()
+        && script_language_word_count
+            .values()
+            .map(Vec::as_slice)
+            .any(potential_language_detection_error)
     {
         // build an allow list with the most frequent detected languages in the document.
         let script_language: HashMap<_, _> =
@@ -279,8 +339,9 @@ fn lang_safe_tokens_from_document<'a>(
             // rerun the extraction.
             tokens_from_document(
                 obkv,
-                &settings.searchable_fields_ids,
+                settings,
                 &tokenizer,
+                language_tokenizers,
                 max_positions_per_attributes,
                 del_add,
                 buffers,
@@ -289,25 +350,31 @@ fn lang_safe_tokens_from_document<'a>(
         }
     }
 
-    // returns a (KV<FieldId, KV<u16, String>>, HashMap<Script, Vec<(Language, usize)>>)
+    // returns a (KV<FieldId, KV<u32, String>>, HashMap<Script, Vec<(Language, usize)>>)
     Ok((&buffers.obkv_buffer, script_language_word_count))
 }
 
 /// Extract words mapped with their positions of a document.
+///
+/// The positions are made of the passage of the word and of its relative position in the passage,
+/// a field is made of a single passage when no chunking is defined.
 fn tokens_from_document<'a>(
     obkv: &KvReader<'a, FieldId>,
-    searchable_fields: &[FieldId],
+    settings: &InnerIndexSettings,
     tokenizer: &Tokenizer<'_>,
+    language_tokenizers: &HashMap<LanguagePreset, Tokenizer<'_>>,
     max_positions_per_attributes: u32,
     del_add: DelAdd,
     buffers: &'a mut Buffers,
     script_language_word_count: &mut HashMap<Script, Vec<(Language, usize)>>,
 ) -> Result<&'a [u8]> {
+    let searchable_fields = &settings.searchable_fields_ids;
+    let chunking = settings.chunking.as_ref();
     buffers.obkv_buffer.clear();
     let mut document_writer = KvWriterU16::new(&mut buffers.obkv_buffer);
     for (field_id, field_bytes) in obkv.iter() {
         // if field is searchable.
-        if searchable_fields.as_ref().contains(&field_id) {
+        if searchable_fields.contains(&field_id) {
             // extract deletion or addition only.
             if let Some(field_bytes) = KvReaderDelAdd::new(field_bytes).get(del_add) {
                 // parse json.
@@ -316,15 +383,26 @@ fn tokens_from_document<'a>(
 
                 // prepare writing destination.
                 buffers.obkv_positions_buffer.clear();
-                let mut writer = KvWriterU16::new(&mut buffers.obkv_positions_buffer);
+                let mut writer = KvWriterU32::new(&mut buffers.obkv_positions_buffer);
+
+                // the fields written in a single language are tokenized in this language.
+                let tokenizer = settings
+                    .fields_ids_map
+                    .name(field_id)
+                    .and_then(|name| settings.field_languages.get(name))
+                    .and_then(|language| language_tokenizers.get(language))
+                    .unwrap_or(tokenizer);
 
                 // convert json into a unique string.
                 buffers.field_buffer.clear();
                 if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
-                    // create an iterator of token with their positions.
-                    let tokens = process_tokens(tokenizer.tokenize(field))
-                        .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
+                    // create an iterator of token with their positions,
+                    // the passages are only cut once all the words of the field are known.
+                    let tokens = process_tokens(tokenizer.tokenize(field)).take_while(|(p, _)| {
+                        chunking.is_some() || (*p as u32) < max_positions_per_attributes
+                    });
 
+                    buffers.words.clear();
                     for (index, token) in tokens {
                         // if a language has been detected for the token, we update the counter.
                         if let Some(language) = token.language {
@@ -339,10 +417,34 @@ fn tokens_from_document<'a>(
                         // keep a word only if it is not empty and fit in a LMDB key.
                         let token = token.lemma().trim();
                         if !token.is_empty() && token.len() <= MAX_WORD_LENGTH {
-                            let position: u16 = index
-                                .try_into()
-                                .map_err(|_| SerializationError::InvalidNumberSerialization)?;
-                            writer.insert(position, token.as_bytes())?;
+                            match chunking {
+                                Some(_) => buffers.words.push((index, token.to_string())),
+                                None => {
+                                    let position: u16 = index.try_into().map_err(|_| {
+                                        SerializationError::InvalidNumberSerialization
+                                    })?;
+                                    writer
+                                        .insert(passage_position(0, position), token.as_bytes())?;
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(chunking) = chunking {
+                        // the last words of a passage must never be close to the next passage.
+                        let max_positions = max_positions_per_attributes
+                            .min(MAX_POSITION_PER_ATTRIBUTE - MAX_DISTANCE);
+                        for (passage, range) in chunking.passages(buffers.words.len()) {
+                            let words = &buffers.words[range];
+                            let first_index = words.first().map_or(0, |(index, _)| *index);
+                            for (index, word) in words {
+                                // the positions restart at the beginning of each passage.
+                                let position = index - first_index;
+                                if position as u32 >= max_positions {
+                                    break;
+                                }
+                                writer.insert(passage_position(passage, position as u16), word)?;
+                            }
                         }
                     }
 
@@ -354,7 +456,7 @@ fn tokens_from_document<'a>(
         }
     }
 
-    // returns a KV<FieldId, KV<u16, String>>
+    // returns a KV<FieldId, KV<u32, String>>
     Ok(document_writer.into_inner().map(|v| v.as_slice())?)
 }
 
@@ -464,4 +566,6 @@ struct Buffers {
     obkv_buffer: Vec<u8>,
     // buffer used to store the value data containing an obkv of tokens with their positions.
     obkv_positions_buffer: Vec<u8>,
+    // the words of a field with their positions, when the field is cut into passages.
+    words: Vec<(usize, String)>,
 }
@@ -33,6 +33,7 @@ pub struct ExtractedFacetValues {
     pub fid_facet_is_null_docids_chunk: grenad::Reader<BufReader<File>>,
     pub fid_facet_is_empty_docids_chunk: grenad::Reader<BufReader<File>>,
     pub fid_facet_exists_docids_chunk: grenad::Reader<BufReader<File>>,
+    pub fid_facet_bool_docids_chunk: grenad::Reader<BufReader<File>>,
 }
 
 /// Extracts the facet values of each faceted field of each document.
@@ -70,6 +71,7 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     let mut facet_exists_docids = BTreeMap::<FieldId, (RoaringBitmap, RoaringBitmap)>::new();
     let mut facet_is_null_docids = BTreeMap::<FieldId, (RoaringBitmap, RoaringBitmap)>::new();
     let mut facet_is_empty_docids = BTreeMap::<FieldId, (RoaringBitmap, RoaringBitmap)>::new();
+    let mut facet_bool_docids = BTreeMap::<(FieldId, bool), (RoaringBitmap, RoaringBitmap)>::new();
 
     // We create two buffers for mutable ref issues with closures.
     let mut numbers_key_buffer = Vec::new();
@@ -80,7 +82,10 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     let new_faceted_fids: BTreeSet<_> =
         settings_diff.new.faceted_fields_ids.iter().copied().collect();
 
-    if !settings_diff.settings_update_only || old_faceted_fids != new_faceted_fids {
+    if !settings_diff.settings_update_only
+        || old_faceted_fids != new_faceted_fids
+        || settings_diff.reencrypt_pii()
+    {
         let mut cursor = obkv_documents.into_cursor()?;
         while let Some((docid_bytes, value)) = cursor.move_on_next()? {
             let obkv = obkv::KvReader::new(value);
@@ -113,7 +118,9 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                     }
                     EitherOrBoth::Both(&field_id, _) => {
                         // during settings update, recompute the changing settings only.
-                        if settings_diff.settings_update_only {
+                        if settings_diff.settings_update_only
+                            && !settings_diff.pii_changed_id(field_id)
+                        {
                             continue;
                         }
 
@@ -168,6 +175,26 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                     let add_filterable_values =
                         add_value.map(|value| extract_facet_values(&value, add_geo_support));
 
+                    // The booleans are stored in their own bitmaps rather than with the strings.
+                    for value in [false, true] {
+                        let contains = |values: &Option<FilterableValues>| {
+                            values.as_ref().map_or(false, |values| values.contains_bool(value))
+                        };
+                        match (contains(&del_filterable_values), contains(&add_filterable_values)) {
+                            (true, false) => {
+                                let (del_bool, _) =
+                                    facet_bool_docids.entry((field_id, value)).or_default();
+                                del_bool.insert(document);
+                            }
+                            (false, true) => {
+                                let (_, add_bool) =
+                                    facet_bool_docids.entry((field_id, value)).or_default();
+                                add_bool.insert(document);
+                            }
+                            _ => (),
+                        }
+                    }
+
                     // Those closures are just here to simplify things a bit.
                     let mut insert_numbers_diff = |del_numbers, add_numbers| {
                         insert_numbers_diff(
@@ -195,7 +222,7 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                             Empty => {
                                 del_is_empty.insert(document);
                             }
-                            Values { numbers, strings } => {
+                            Values { numbers, strings, .. } => {
                                 insert_numbers_diff(numbers, vec![])?;
                                 insert_strings_diff(strings, vec![])?;
                             }
@@ -207,7 +234,7 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                             Empty => {
                                 add_is_empty.insert(document);
                             }
-                            Values { numbers, strings } => {
+                            Values { numbers, strings, .. } => {
                                 insert_numbers_diff(vec![], numbers)?;
                                 insert_strings_diff(vec![], strings)?;
                             }
@@ -223,29 +250,29 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                                     del_is_empty.insert(document);
                                     add_is_null.insert(document);
                                 }
-                                (Null, Values { numbers, strings }) => {
+                                (Null, Values { numbers, strings, .. }) => {
                                     insert_numbers_diff(vec![], numbers)?;
                                     insert_strings_diff(vec![], strings)?;
                                     del_is_null.insert(document);
                                 }
-                                (Empty, Values { numbers, strings }) => {
+                                (Empty, Values { numbers, strings, .. }) => {
                                     insert_numbers_diff(vec![], numbers)?;
                                     insert_strings_diff(vec![], strings)?;
                                     del_is_empty.insert(document);
                                 }
-                                (Values { numbers, strings }, Null) => {
+                                (Values { numbers, strings, .. }, Null) => {
                                     add_is_null.insert(document);
                                     insert_numbers_diff(numbers, vec![])?;
                                     insert_strings_diff(strings, vec![])?;
                                 }
-                                (Values { numbers, strings }, Empty) => {
+                                (Values { numbers, strings, .. }, Empty) => {
                                     add_is_empty.insert(document);
                                     insert_numbers_diff(numbers, vec![])?;
                                     insert_strings_diff(strings, vec![])?;
                                 }
                                 (
-                                    Values { numbers: del_numbers, strings: del_strings },
-                                    Values { numbers: add_numbers, strings: add_strings },
+                                    Values { numbers: del_numbers, strings: del_strings, .. },
+                                    Values { numbers: add_numbers, strings: add_strings, .. },
                                 ) => {
                                     insert_numbers_diff(del_numbers, add_numbers)?;
                                     insert_strings_diff(del_strings, add_strings)?;
@@ -292,12 +319,27 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     }
     let facet_is_empty_docids_reader = writer_into_reader(facet_is_empty_docids_writer)?;
 
+    let mut facet_bool_docids_writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+    for ((fid, value), (del_bitmap, add_bitmap)) in facet_bool_docids.into_iter() {
+        deladd_obkv_cbo_roaring_bitmaps(&mut buffer, &del_bitmap, &add_bitmap)?;
+        // Note: this encoding is consistent with FieldIdBoolCodec
+        let mut key = fid.to_be_bytes().to_vec();
+        key.push(u8::from(value));
+        facet_bool_docids_writer.insert(key, &buffer)?;
+    }
+    let facet_bool_docids_reader = writer_into_reader(facet_bool_docids_writer)?;
+
     Ok(ExtractedFacetValues {
         fid_docid_facet_numbers_chunk: sorter_into_reader(fid_docid_facet_numbers_sorter, indexer)?,
         fid_docid_facet_strings_chunk: sorter_into_reader(fid_docid_facet_strings_sorter, indexer)?,
         fid_facet_is_null_docids_chunk: facet_is_null_docids_reader,
         fid_facet_is_empty_docids_chunk: facet_is_empty_docids_reader,
         fid_facet_exists_docids_chunk: facet_exists_docids_reader,
+        fid_facet_bool_docids_chunk: facet_bool_docids_reader,
     })
 }
 
@@ -442,8 +484,17 @@ enum FilterableValues {
     Null,
     /// Corresponds to either, an empty string `""`, an empty array `[]`, or an empty object `{}`.
     Empty,
-    /// Represents all the numbers and strings values found in this document field.
-    Values { numbers: Vec<f64>, strings: Vec<(String, String)> },
+    /// Represents all the numbers, strings and booleans values found in this document field.
+    Values { numbers: Vec<f64>, strings: Vec<(String, String)>, bools: Vec<bool> },
+}
+
+impl FilterableValues {
+    fn contains_bool(&self, value: bool) -> bool {
+        match self {
+            Values { bools, .. } => bools.contains(&value),
+            Null | Empty => false,
+        }
+    }
 }
 
 /// Extracts the facet values of a JSON field.
@@ -453,11 +504,12 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
         can_recurse: bool,
         output_numbers: &mut Vec<f64>,
         output_strings: &mut Vec<(String, String)>,
+        output_bools: &mut Vec<bool>,
         geo_field: bool,
     ) {
         match value {
             Value::Null => (),
-            Value::Bool(b) => output_strings.push((b.to_string(), b.to_string())),
+            Value::Bool(b) => output_bools.push(*b),
             Value::Number(number) => {
                 if let Some(float) = number.as_f64() {
                     output_numbers.push(float);
@@ -486,6 +538,7 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                             false,
                             output_numbers,
                             output_strings,
+                            output_bools,
                             geo_field,
                         );
                     }
@@ -503,8 +556,16 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
         otherwise => {
             let mut numbers = Vec::new();
             let mut strings = Vec::new();
-            inner_extract_facet_values(otherwise, true, &mut numbers, &mut strings, geo_field);
-            FilterableValues::Values { numbers, strings }
+            let mut bools = Vec::new();
+            inner_extract_facet_values(
+                otherwise,
+                true,
+                &mut numbers,
+                &mut strings,
+                &mut bools,
+                geo_field,
+            );
+            FilterableValues::Values { numbers, strings, bools }
         }
     }
 }
@@ -1,17 +1,19 @@
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{self, BufReader};
 
-use obkv::KvReaderU16;
+use obkv::KvReaderU32;
 
 use super::helpers::{
     create_sorter, merge_deladd_cbo_roaring_bitmaps, sorter_into_reader, try_split_array_at,
     GrenadParameters,
 };
+use crate::chunking::{passage_of, Chunking};
 use crate::error::SerializationError;
 use crate::index::db_name::DOCID_WORD_POSITIONS;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::settings::InnerIndexSettingsDiff;
-use crate::Result;
+use crate::{FieldId, Result};
 
 const MAX_COUNTED_WORDS: usize = 30;
 
@@ -19,13 +21,14 @@ const MAX_COUNTED_WORDS: usize = 30;
 /// this field id with this amount of words appear.
 ///
 /// Returns a grenad reader with the list of extracted field id word counts
-/// and documents ids from the given chunk of docid word positions.
+/// and documents ids from the given chunk of docid word positions, along with
+/// the number of tokens added to each field, negative when tokens were removed.
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
 pub fn extract_fid_word_count_docids<R: io::Read + io::Seek>(
     docid_word_positions: grenad::Reader<R>,
     indexer: GrenadParameters,
-    _settings_diff: &InnerIndexSettingsDiff,
-) -> Result<grenad::Reader<BufReader<File>>> {
+    settings_diff: &InnerIndexSettingsDiff,
+) -> Result<(grenad::Reader<BufReader<File>>, BTreeMap<FieldId, i64>)> {
     let max_memory = indexer.max_memory_by_thread();
 
     let mut fid_word_count_docids_sorter = create_sorter(
@@ -37,6 +40,7 @@ pub fn extract_fid_word_count_docids<R: io::Read + io::Seek>(
         max_memory,
     );
 
+    let mut fields_tokens_delta: BTreeMap<FieldId, i64> = BTreeMap::new();
     let mut key_buffer = Vec::new();
     let mut value_buffer = Vec::new();
     let mut cursor = docid_word_positions.into_cursor()?;
@@ -44,22 +48,30 @@ pub fn extract_fid_word_count_docids<R: io::Read + io::Seek>(
         let (document_id_bytes, fid_bytes) = try_split_array_at(key)
             .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
         let document_id = u32::from_be_bytes(document_id_bytes);
+        let (fid_bytes, _) = try_split_array_at(fid_bytes)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let fid = FieldId::from_be_bytes(fid_bytes);
 
         let del_add_reader = KvReaderDelAdd::new(value);
         let deletion = del_add_reader
             // get deleted words
             .get(DelAdd::Deletion)
             // count deleted words
-            .map(|deletion| KvReaderU16::new(deletion).iter().take(MAX_COUNTED_WORDS + 1).count())
-            // keep the count if under or equal to MAX_COUNTED_WORDS
-            .filter(|&word_count| word_count <= MAX_COUNTED_WORDS);
+            .map(|deletion| count_words(KvReaderU32::new(deletion), settings_diff.old.chunking));
         let addition = del_add_reader
             // get added words
             .get(DelAdd::Addition)
             // count added words
-            .map(|addition| KvReaderU16::new(addition).iter().take(MAX_COUNTED_WORDS + 1).count())
-            // keep the count if under or equal to MAX_COUNTED_WORDS
-            .filter(|&word_count| word_count <= MAX_COUNTED_WORDS);
+            .map(|addition| count_words(KvReaderU32::new(addition), settings_diff.new.chunking));
+
+        let tokens_delta = addition.unwrap_or(0) as i64 - deletion.unwrap_or(0) as i64;
+        if tokens_delta != 0 {
+            *fields_tokens_delta.entry(fid).or_default() += tokens_delta;
+        }
+
+        // keep the counts if under or equal to MAX_COUNTED_WORDS
+        let deletion = deletion.filter(|&word_count| word_count <= MAX_COUNTED_WORDS);
+        let addition = addition.filter(|&word_count| word_count <= MAX_COUNTED_WORDS);
 
         if deletion != addition {
             // Insert deleted word count in sorter if exist.
@@ -68,7 +80,7 @@ pub fn extract_fid_word_count_docids<R: io::Read + io::Seek>(
                 let mut value_writer = KvWriterDelAdd::new(&mut value_buffer);
                 value_writer.insert(DelAdd::Deletion, document_id.to_ne_bytes()).unwrap();
                 key_buffer.clear();
-                key_buffer.extend_from_slice(fid_bytes);
+                key_buffer.extend_from_slice(&fid_bytes);
                 key_buffer.push(word_count as u8);
                 fid_word_count_docids_sorter
                     .insert(&key_buffer, value_writer.into_inner().unwrap())?;
@@ -79,7 +91,7 @@ pub fn extract_fid_word_count_docids<R: io::Read + io::Seek>(
                 let mut value_writer = KvWriterDelAdd::new(&mut value_buffer);
                 value_writer.insert(DelAdd::Addition, document_id.to_ne_bytes()).unwrap();
                 key_buffer.clear();
-                key_buffer.extend_from_slice(fid_bytes);
+                key_buffer.extend_from_slice(&fid_bytes);
                 key_buffer.push(word_count as u8);
                 fid_word_count_docids_sorter
                     .insert(&key_buffer, value_writer.into_inner().unwrap())?;
@@ -87,5 +99,24 @@ pub fn extract_fid_word_count_docids<R: io::Read + io::Seek>(
         }
     }
 
-    sorter_into_reader(fid_word_count_docids_sorter, indexer)
+    Ok((sorter_into_reader(fid_word_count_docids_sorter, indexer)?, fields_tokens_delta))
+}
+
+/// Counts the words of a field, the words repeated at the start of the passages are only counted once.
+fn count_words(words: KvReaderU32<'_>, chunking: Option<Chunking>) -> usize {
+    let overlap = chunking.map_or(0, |chunking| chunking.overlap);
+    let mut current_passage = 0;
+    let mut index_in_passage = 0;
+    words
+        .iter()
+        .filter(|(position, _)| {
+            let passage = passage_of(*position);
+            if passage != current_passage {
+                current_passage = passage;
+                index_in_passage = 0;
+            }
+            index_in_passage += 1;
+            passage == 0 || index_in_passage > overlap
+        })
+        .count()
 }
@@ -13,6 +13,7 @@ use roaring::RoaringBitmap;
 use serde_json::Value;
 
 use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
+use crate::chunking::Chunking;
 use crate::index::IndexEmbeddingConfig;
 use crate::prompt::Prompt;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
@@ -107,6 +108,8 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
 
     let old_fields_ids_map = &settings_diff.old.fields_ids_map;
     let new_fields_ids_map = &settings_diff.new.fields_ids_map;
+    let old_chunking = settings_diff.old.chunking.as_ref();
+    let new_chunking = settings_diff.new.chunking.as_ref();
     // the vector field id may have changed
     let old_vectors_fid = old_fields_ids_map.id(RESERVED_VECTORS_FIELD_NAME);
 
@@ -282,7 +285,9 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                     // this happens only when an existing embedder changed. We cannot regenerate userProvided vectors
                     VectorState::Manual => VectorStateDelta::NoChange,
                     // generated vectors must be regenerated
-                    VectorState::Generated => regenerate_prompt(obkv, prompt, new_fields_ids_map)?,
+                    VectorState::Generated => {
+                        regenerate_prompt(obkv, prompt, new_fields_ids_map, new_chunking)?
+                    }
                 },
                 // prompt regeneration is only triggered for existing embedders
                 ExtractionAction::SettingsRegeneratePrompts { old_prompt } => {
@@ -291,6 +296,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                             obkv,
                             (old_prompt, prompt),
                             (old_fields_ids_map, new_fields_ids_map),
+                            (old_chunking, new_chunking),
                         )?
                     } else {
                         // we can simply ignore user provided vectors as they are not regenerated and are
@@ -307,6 +313,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                     (add_to_user_provided, remove_from_user_provided),
                     (old, new),
                     (old_fields_ids_map, new_fields_ids_map),
+                    (old_chunking, new_chunking),
                     document_id,
                 )?,
             };
@@ -358,6 +365,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     Ok(results)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn extract_vector_document_diff(
     docid: DocumentId,
     obkv: obkv::KvReader<'_, FieldId>,
@@ -365,6 +373,7 @@ fn extract_vector_document_diff(
     (add_to_user_provided, remove_from_user_provided): (&mut RoaringBitmap, &mut RoaringBitmap),
     (old, new): (VectorState, VectorState),
     (old_fields_ids_map, new_fields_ids_map): (&FieldsIdsMap, &FieldsIdsMap),
+    (old_chunking, new_chunking): (Option<&Chunking>, Option<&Chunking>),
     document_id: impl Fn() -> Value,
 ) -> Result<VectorStateDelta> {
     match (old.must_regenerate(), new.must_regenerate()) {
@@ -405,9 +414,12 @@ fn extract_vector_document_diff(
             if document_is_kept {
                 // Don't give up if the old prompt was failing
                 let old_prompt = Some(&prompt).map(|p| {
-                    p.render(obkv, DelAdd::Deletion, old_fields_ids_map).unwrap_or_default()
+                    let old_prompt =
+                        p.render(obkv, DelAdd::Deletion, old_fields_ids_map).unwrap_or_default();
+                    bounded_prompt(old_prompt, old_chunking)
                 });
                 let new_prompt = prompt.render(obkv, DelAdd::Addition, new_fields_ids_map)?;
+                let new_prompt = bounded_prompt(new_prompt, new_chunking);
                 if old_prompt.as_ref() != Some(&new_prompt) {
                     let old_prompt = old_prompt.unwrap_or_default();
                     tracing::trace!(
@@ -435,11 +447,8 @@ fn extract_vector_document_diff(
                 .any(|deladd| deladd.get(DelAdd::Addition).is_some());
             if document_is_kept {
                 // becomes autogenerated
-                VectorStateDelta::NowGenerated(prompt.render(
-                    obkv,
-                    DelAdd::Addition,
-                    new_fields_ids_map,
-                )?)
+                let prompt = prompt.render(obkv, DelAdd::Addition, new_fields_ids_map)?;
+                VectorStateDelta::NowGenerated(bounded_prompt(prompt, new_chunking))
             } else {
                 // make sure the document is always removed from user provided on removal
                 remove_from_user_provided.insert(docid);
@@ -474,10 +483,13 @@ fn regenerate_if_prompt_changed(
     obkv: obkv::KvReader<'_, FieldId>,
     (old_prompt, new_prompt): (&Prompt, &Prompt),
     (old_fields_ids_map, new_fields_ids_map): (&FieldsIdsMap, &FieldsIdsMap),
+    (old_chunking, new_chunking): (Option<&Chunking>, Option<&Chunking>),
 ) -> Result<VectorStateDelta> {
     let old_prompt =
         old_prompt.render(obkv, DelAdd::Deletion, old_fields_ids_map).unwrap_or(Default::default());
+    let old_prompt = bounded_prompt(old_prompt, old_chunking);
     let new_prompt = new_prompt.render(obkv, DelAdd::Addition, new_fields_ids_map)?;
+    let new_prompt = bounded_prompt(new_prompt, new_chunking);
 
     if new_prompt == old_prompt {
         return Ok(VectorStateDelta::NoChange);
@@ -489,10 +501,20 @@ fn regenerate_prompt(
     obkv: obkv::KvReader<'_, FieldId>,
     prompt: &Prompt,
     new_fields_ids_map: &FieldsIdsMap,
+    new_chunking: Option<&Chunking>,
 ) -> Result<VectorStateDelta> {
     let prompt = prompt.render(obkv, DelAdd::Addition, new_fields_ids_map)?;
 
-    Ok(VectorStateDelta::NowGenerated(prompt))
+    Ok(VectorStateDelta::NowGenerated(bounded_prompt(prompt, new_chunking)))
+}
+
+/// Only keeps the first passage of the rendered prompt when the documents are cut into passages,
+/// so that the embedders receive bounded inputs.
+fn bounded_prompt(prompt: String, chunking: Option<&Chunking>) -> String {
+    match chunking {
+        Some(chunking) => chunking.first_passage(&prompt).to_string(),
+        None => prompt,
+    }
 }
 
 /// We cannot compute the diff between both Del and Add vectors.
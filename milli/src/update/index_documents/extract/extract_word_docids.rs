@@ -3,7 +3,7 @@ use std::fs::File;
 use std::io::{self, BufReader};
 
 use heed::{BytesDecode, BytesEncode};
-use obkv::KvReaderU16;
+use obkv::KvReaderU32;
 use roaring::RoaringBitmap;
 
 use super::helpers::{
@@ -61,14 +61,14 @@ pub fn extract_word_docids<R: io::Read + io::Seek>(
         let del_add_reader = KvReaderDelAdd::new(value);
         // extract all unique words to remove.
         if let Some(deletion) = del_add_reader.get(DelAdd::Deletion) {
-            for (_pos, word) in KvReaderU16::new(deletion).iter() {
+            for (_pos, word) in KvReaderU32::new(deletion).iter() {
                 del_words.insert(word.to_vec());
             }
         }
 
         // extract all unique additional words.
         if let Some(addition) = del_add_reader.get(DelAdd::Addition) {
-            for (_pos, word) in KvReaderU16::new(addition).iter() {
+            for (_pos, word) in KvReaderU32::new(addition).iter() {
                 add_words.insert(word.to_vec());
             }
         }
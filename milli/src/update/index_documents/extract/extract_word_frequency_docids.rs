@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader};
+
+use obkv::KvReaderU32;
+
+use super::helpers::{
+    create_sorter, merge_deladd_cbo_roaring_bitmaps, sorter_into_reader, try_split_array_at,
+    GrenadParameters,
+};
+use crate::error::SerializationError;
+use crate::index::db_name::DOCID_WORD_POSITIONS;
+use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
+use crate::update::settings::InnerIndexSettingsDiff;
+use crate::update::MergeFn;
+use crate::{DocumentId, Result};
+
+/// Extracts the number of times each word appears in the documents and the number of words
+/// of the documents, used by the `relevancy:bm25` ranking rule.
+///
+/// Returns a grenad reader with the list of extracted words and frequencies with the documents
+/// ids, and a grenad reader with the lengths of the documents with the documents ids.
+/// Nothing is extracted when the ranking rule isn't in the ranking rules of the index.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
+pub fn extract_word_frequency_docids<R: io::Read + io::Seek>(
+    docid_word_positions: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    settings_diff: &InnerIndexSettingsDiff,
+) -> Result<(grenad::Reader<BufReader<File>>, grenad::Reader<BufReader<File>>)> {
+    let max_memory = indexer.max_memory_by_thread();
+
+    let mut word_frequency_docids_sorter = create_sorter(
+        grenad::SortAlgorithm::Unstable,
+        merge_deladd_cbo_roaring_bitmaps,
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        indexer.max_nb_chunks,
+        max_memory.map(|m| m / 2),
+    );
+
+    let mut document_length_docids_sorter = create_sorter(
+        grenad::SortAlgorithm::Unstable,
+        merge_deladd_cbo_roaring_bitmaps,
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        indexer.max_nb_chunks,
+        max_memory.map(|m| m / 2),
+    );
+
+    let mut del_word_frequencies = WordFrequencies::default();
+    let mut add_word_frequencies = WordFrequencies::default();
+    let mut current_document_id: Option<u32> = None;
+    let mut cursor = docid_word_positions.into_cursor()?;
+    while let Some((key, value)) = cursor.move_on_next()? {
+        let (document_id_bytes, _fid_bytes) = try_split_array_at(key)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let document_id = DocumentId::from_be_bytes(document_id_bytes);
+
+        if current_document_id.map_or(false, |id| document_id != id) {
+            words_frequency_into_sorters(
+                current_document_id.unwrap(),
+                &del_word_frequencies,
+                &add_word_frequencies,
+                &mut word_frequency_docids_sorter,
+                &mut document_length_docids_sorter,
+            )?;
+            del_word_frequencies.clear();
+            add_word_frequencies.clear();
+        }
+
+        current_document_id = Some(document_id);
+
+        let del_add_reader = KvReaderDelAdd::new(value);
+        // count the words of the old version of the document.
+        if settings_diff.old.bm25 {
+            if let Some(deletion) = del_add_reader.get(DelAdd::Deletion) {
+                for (_position, word_bytes) in KvReaderU32::new(deletion).iter() {
+                    del_word_frequencies.insert(word_bytes);
+                }
+            }
+        }
+
+        // count the words of the new version of the document.
+        if settings_diff.new.bm25 {
+            if let Some(addition) = del_add_reader.get(DelAdd::Addition) {
+                for (_position, word_bytes) in KvReaderU32::new(addition).iter() {
+                    add_word_frequencies.insert(word_bytes);
+                }
+            }
+        }
+    }
+
+    if let Some(document_id) = current_document_id {
+        words_frequency_into_sorters(
+            document_id,
+            &del_word_frequencies,
+            &add_word_frequencies,
+            &mut word_frequency_docids_sorter,
+            &mut document_length_docids_sorter,
+        )?;
+    }
+
+    let word_frequency_docids_reader = sorter_into_reader(word_frequency_docids_sorter, indexer)?;
+    let document_length_docids_reader = sorter_into_reader(document_length_docids_sorter, indexer)?;
+
+    Ok((word_frequency_docids_reader, document_length_docids_reader))
+}
+
+/// The number of times each word appears in a document, and its total number of words.
+#[derive(Default)]
+struct WordFrequencies {
+    frequencies: BTreeMap<Vec<u8>, u32>,
+    length: u32,
+}
+
+impl WordFrequencies {
+    fn insert(&mut self, word_bytes: &[u8]) {
+        match self.frequencies.get_mut(word_bytes) {
+            Some(frequency) => *frequency = frequency.saturating_add(1),
+            None => {
+                self.frequencies.insert(word_bytes.to_vec(), 1);
+            }
+        }
+        self.length = self.length.saturating_add(1);
+    }
+
+    fn clear(&mut self) {
+        self.frequencies.clear();
+        self.length = 0;
+    }
+
+    /// Returns the words with their frequency, which is capped to fit the keys of the database.
+    fn iter(&self) -> impl Iterator<Item = (&[u8], u16)> {
+        self.frequencies
+            .iter()
+            .map(|(word, frequency)| (word.as_slice(), (*frequency).min(u16::MAX as u32) as u16))
+    }
+}
+
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
+fn words_frequency_into_sorters(
+    document_id: DocumentId,
+    del_word_frequencies: &WordFrequencies,
+    add_word_frequencies: &WordFrequencies,
+    word_frequency_docids_sorter: &mut grenad::Sorter<MergeFn>,
+    document_length_docids_sorter: &mut grenad::Sorter<MergeFn>,
+) -> Result<()> {
+    use itertools::merge_join_by;
+    use itertools::EitherOrBoth::{Both, Left, Right};
+
+    let mut buffer = Vec::new();
+    let mut key_buffer = Vec::new();
+    for eob in
+        merge_join_by(del_word_frequencies.iter(), add_word_frequencies.iter(), |d, a| d.cmp(a))
+    {
+        buffer.clear();
+        let mut value_writer = KvWriterDelAdd::new(&mut buffer);
+        let (word_bytes, frequency) = match eob {
+            Left(key) => {
+                value_writer.insert(DelAdd::Deletion, document_id.to_ne_bytes()).unwrap();
+                key
+            }
+            Right(key) => {
+                value_writer.insert(DelAdd::Addition, document_id.to_ne_bytes()).unwrap();
+                key
+            }
+            Both(key, _) => {
+                value_writer.insert(DelAdd::Deletion, document_id.to_ne_bytes()).unwrap();
+                value_writer.insert(DelAdd::Addition, document_id.to_ne_bytes()).unwrap();
+                key
+            }
+        };
+
+        key_buffer.clear();
+        key_buffer.extend_from_slice(word_bytes);
+        key_buffer.push(0);
+        key_buffer.extend_from_slice(&frequency.to_be_bytes());
+        word_frequency_docids_sorter.insert(&key_buffer, value_writer.into_inner().unwrap())?;
+    }
+
+    let mut insert_length = |length: u32, operations: &[DelAdd]| -> Result<()> {
+        buffer.clear();
+        let mut value_writer = KvWriterDelAdd::new(&mut buffer);
+        for &operation in operations {
+            value_writer.insert(operation, document_id.to_ne_bytes()).unwrap();
+        }
+        document_length_docids_sorter
+            .insert(length.to_be_bytes(), value_writer.into_inner().unwrap())?;
+        Ok(())
+    };
+
+    match (del_word_frequencies.length, add_word_frequencies.length) {
+        (0, 0) => (),
+        (del, add) if del == add => insert_length(del, &[DelAdd::Deletion, DelAdd::Addition])?,
+        (del, add) => {
+            if del != 0 {
+                insert_length(del, &[DelAdd::Deletion])?;
+            }
+            if add != 0 {
+                insert_length(add, &[DelAdd::Addition])?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ use std::fs::File;
 use std::io::BufReader;
 use std::{cmp, io};
 
-use obkv::KvReaderU16;
+use obkv::KvReaderU32;
 
 use super::helpers::{
     create_sorter, create_writer, merge_deladd_cbo_roaring_bitmaps, try_split_array_at,
@@ -14,7 +14,7 @@ use crate::index::db_name::DOCID_WORD_POSITIONS;
 use crate::proximity::{index_proximity, ProximityPrecision, MAX_DISTANCE};
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::settings::InnerIndexSettingsDiff;
-use crate::{DocumentId, Result};
+use crate::{DocumentId, FieldId, Result};
 
 /// Extracts the best proximity between pairs of words and the documents ids where this pair appear.
 ///
@@ -53,9 +53,9 @@ pub fn extract_word_pair_proximity_docids<R: io::Read + io::Seek>(
         })
         .collect();
 
-    let mut del_word_positions: VecDeque<(String, u16)> =
+    let mut del_word_positions: VecDeque<(String, u32)> =
         VecDeque::with_capacity(MAX_DISTANCE as usize);
-    let mut add_word_positions: VecDeque<(String, u16)> =
+    let mut add_word_positions: VecDeque<(String, u32)> =
         VecDeque::with_capacity(MAX_DISTANCE as usize);
     let mut del_word_pair_proximity = BTreeMap::new();
     let mut add_word_pair_proximity = BTreeMap::new();
@@ -63,9 +63,17 @@ pub fn extract_word_pair_proximity_docids<R: io::Read + io::Seek>(
 
     let mut cursor = docid_word_positions.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
-        let (document_id_bytes, _fid_bytes) = try_split_array_at(key)
+        let (document_id_bytes, fid_bytes) = try_split_array_at(key)
             .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
         let document_id = u32::from_be_bytes(document_id_bytes);
+        let (fid_bytes, _) = try_split_array_at(fid_bytes)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let fid = FieldId::from_be_bytes(fid_bytes);
+        // the proximities of the presence-only attributes are not stored.
+        let any_deletion =
+            any_deletion && !settings_diff.old.presence_only_attributes.contains(&fid);
+        let any_addition =
+            any_addition && !settings_diff.new.presence_only_attributes.contains(&fid);
 
         // if we change document, we fill the sorter
         if current_document_id.map_or(false, |id| id != document_id) {
@@ -93,10 +101,10 @@ pub fn extract_word_pair_proximity_docids<R: io::Read + io::Seek>(
 
                 // deletions
                 if let Some(deletion) = KvReaderDelAdd::new(value).get(DelAdd::Deletion) {
-                    for (position, word) in KvReaderU16::new(deletion).iter() {
+                    for (position, word) in KvReaderU32::new(deletion).iter() {
                         // drain the proximity window until the head word is considered close to the word we are inserting.
                         while del_word_positions.front().map_or(false, |(_w, p)| {
-                            index_proximity(*p as u32, position as u32) >= MAX_DISTANCE
+                            index_proximity(*p, position) >= MAX_DISTANCE
                         }) {
                             word_positions_into_word_pair_proximity(
                                 &mut del_word_positions,
@@ -126,10 +134,10 @@ pub fn extract_word_pair_proximity_docids<R: io::Read + io::Seek>(
 
                 // additions
                 if let Some(addition) = KvReaderDelAdd::new(value).get(DelAdd::Addition) {
-                    for (position, word) in KvReaderU16::new(addition).iter() {
+                    for (position, word) in KvReaderU32::new(addition).iter() {
                         // drain the proximity window until the head word is considered close to the word we are inserting.
                         while add_word_positions.front().map_or(false, |(_w, p)| {
-                            index_proximity(*p as u32, position as u32) >= MAX_DISTANCE
+                            index_proximity(*p, position) >= MAX_DISTANCE
                         }) {
                             word_positions_into_word_pair_proximity(
                                 &mut add_word_positions,
@@ -241,12 +249,12 @@ fn document_word_positions_into_sorter(
 }
 
 fn word_positions_into_word_pair_proximity(
-    word_positions: &mut VecDeque<(String, u16)>,
+    word_positions: &mut VecDeque<(String, u32)>,
     word_pair_proximity: &mut BTreeMap<(String, String), u8>,
 ) -> Result<()> {
     let (head_word, head_position) = word_positions.pop_front().unwrap();
     for (word, position) in word_positions.iter() {
-        let prox = index_proximity(head_position as u32, *position as u32) as u8;
+        let prox = index_proximity(head_position, *position) as u8;
         if prox > 0 && prox < MAX_DISTANCE as u8 {
             word_pair_proximity
                 .entry((head_word.clone(), word.clone()))
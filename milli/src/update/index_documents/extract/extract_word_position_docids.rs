@@ -2,18 +2,23 @@ use std::collections::BTreeSet;
 use std::fs::File;
 use std::io::{self, BufReader};
 
-use obkv::KvReaderU16;
+use obkv::KvReaderU32;
 
 use super::helpers::{
     create_sorter, merge_deladd_cbo_roaring_bitmaps, sorter_into_reader, try_split_array_at,
     GrenadParameters,
 };
+use crate::chunking::position_in_passage;
 use crate::error::SerializationError;
 use crate::index::db_name::DOCID_WORD_POSITIONS;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::settings::InnerIndexSettingsDiff;
 use crate::update::MergeFn;
-use crate::{bucketed_position, DocumentId, Result};
+use crate::{bucketed_position, DocumentId, FieldId, Result};
+
+/// The position at which all the words of the presence-only attributes are stored,
+/// far enough to get the maximum cost in the position ranking rule.
+const PRESENCE_ONLY_POSITION: u16 = 2048;
 
 /// Extracts the word positions and the documents ids where this word appear.
 ///
@@ -23,7 +28,7 @@ use crate::{bucketed_position, DocumentId, Result};
 pub fn extract_word_position_docids<R: io::Read + io::Seek>(
     docid_word_positions: grenad::Reader<R>,
     indexer: GrenadParameters,
-    _settings_diff: &InnerIndexSettingsDiff,
+    settings_diff: &InnerIndexSettingsDiff,
 ) -> Result<grenad::Reader<BufReader<File>>> {
     let max_memory = indexer.max_memory_by_thread();
 
@@ -42,9 +47,12 @@ pub fn extract_word_position_docids<R: io::Read + io::Seek>(
     let mut key_buffer = Vec::new();
     let mut cursor = docid_word_positions.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
-        let (document_id_bytes, _fid_bytes) = try_split_array_at(key)
+        let (document_id_bytes, fid_bytes) = try_split_array_at(key)
             .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
         let document_id = DocumentId::from_be_bytes(document_id_bytes);
+        let (fid_bytes, _) = try_split_array_at(fid_bytes)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let fid = FieldId::from_be_bytes(fid_bytes);
 
         if current_document_id.map_or(false, |id| document_id != id) {
             words_position_into_sorter(
@@ -63,16 +71,26 @@ pub fn extract_word_position_docids<R: io::Read + io::Seek>(
         let del_add_reader = KvReaderDelAdd::new(value);
         // extract all unique words to remove.
         if let Some(deletion) = del_add_reader.get(DelAdd::Deletion) {
-            for (position, word_bytes) in KvReaderU16::new(deletion).iter() {
-                let position = bucketed_position(position);
+            let presence_only = settings_diff.old.presence_only_attributes.contains(&fid);
+            for (position, word_bytes) in KvReaderU32::new(deletion).iter() {
+                let position = if presence_only {
+                    PRESENCE_ONLY_POSITION
+                } else {
+                    bucketed_position(position_in_passage(position))
+                };
                 del_word_positions.insert((position, word_bytes.to_vec()));
             }
         }
 
         // extract all unique additional words.
         if let Some(addition) = del_add_reader.get(DelAdd::Addition) {
-            for (position, word_bytes) in KvReaderU16::new(addition).iter() {
-                let position = bucketed_position(position);
+            let presence_only = settings_diff.new.presence_only_attributes.contains(&fid);
+            for (position, word_bytes) in KvReaderU32::new(addition).iter() {
+                let position = if presence_only {
+                    PRESENCE_ONLY_POSITION
+                } else {
+                    bucketed_position(position_in_passage(position))
+                };
                 add_word_positions.insert((position, word_bytes.to_vec()));
             }
         }
@@ -6,9 +6,11 @@ mod extract_fid_word_count_docids;
 mod extract_geo_points;
 mod extract_vector_points;
 mod extract_word_docids;
+mod extract_word_frequency_docids;
 mod extract_word_pair_proximity_docids;
 mod extract_word_position_docids;
 
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::BufReader;
 use std::sync::{Arc, OnceLock};
@@ -26,6 +28,7 @@ use self::extract_vector_points::{
     extract_embeddings, extract_vector_points, ExtractedVectorPoints,
 };
 use self::extract_word_docids::extract_word_docids;
+use self::extract_word_frequency_docids::extract_word_frequency_docids;
 use self::extract_word_pair_proximity_docids::extract_word_pair_proximity_docids;
 use self::extract_word_position_docids::extract_word_position_docids;
 use super::helpers::{as_cloneable_grenad, CursorClonableMmap, GrenadParameters};
@@ -82,13 +85,22 @@ pub(crate) fn data_from_obkv_documents(
                         (ref fid_docid_facet_numbers_chunk, ref fid_docid_facet_strings_chunk),
                     )) = result
                     {
-                        run_extraction_task::<_, _, grenad::Reader<BufReader<File>>>(
+                        run_extraction_task::<
+                            _,
+                            _,
+                            (grenad::Reader<BufReader<File>>, BTreeMap<FieldId, i64>),
+                        >(
                             docid_word_positions_chunk.clone(),
                             indexer,
                             settings_diff.clone(),
                             lmdb_writer_sx.clone(),
                             extract_fid_word_count_docids,
-                            TypedChunk::FieldIdWordCountDocids,
+                            |(fid_word_count_docids_reader, fields_tokens_delta)| {
+                                TypedChunk::FieldIdWordCountDocids {
+                                    fid_word_count_docids_reader,
+                                    fields_tokens_delta,
+                                }
+                            },
                         );
                         run_extraction_task::<
                             _,
@@ -126,6 +138,26 @@ pub(crate) fn data_from_obkv_documents(
                             TypedChunk::WordPositionDocids,
                         );
 
+                        if settings_diff.old.bm25 || settings_diff.new.bm25 {
+                            run_extraction_task::<
+                                _,
+                                _,
+                                (grenad::Reader<BufReader<File>>, grenad::Reader<BufReader<File>>),
+                            >(
+                                docid_word_positions_chunk.clone(),
+                                indexer,
+                                settings_diff.clone(),
+                                lmdb_writer_sx.clone(),
+                                extract_word_frequency_docids,
+                                |(word_frequency_docids_reader, document_length_docids_reader)| {
+                                    TypedChunk::WordFrequencyDocids {
+                                        word_frequency_docids_reader,
+                                        document_length_docids_reader,
+                                    }
+                                },
+                            );
+                        }
+
                         run_extraction_task::<
                             _,
                             _,
@@ -362,6 +394,7 @@ fn send_and_extract_flattened_documents_data(
                     fid_facet_is_null_docids_chunk,
                     fid_facet_is_empty_docids_chunk,
                     fid_facet_exists_docids_chunk,
+                    fid_facet_bool_docids_chunk,
                 } = extract_fid_docid_facet_values(
                     flattened_documents_chunk.clone(),
                     indexer,
@@ -394,6 +427,9 @@ fn send_and_extract_flattened_documents_data(
                 let _ = lmdb_writer_sx
                     .send(Ok(TypedChunk::FieldIdFacetExistsDocids(fid_facet_exists_docids_chunk)));
 
+                let _ = lmdb_writer_sx
+                    .send(Ok(TypedChunk::FieldIdFacetBoolDocids(fid_facet_bool_docids_chunk)));
+
                 Ok((fid_docid_facet_numbers_chunk, fid_docid_facet_strings_chunk))
             },
         );
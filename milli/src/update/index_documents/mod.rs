@@ -6,6 +6,7 @@ mod transform;
 mod typed_chunk;
 
 use std::collections::{HashMap, HashSet};
+use std::fs::File;
 use std::io::{Read, Seek};
 use std::iter;
 use std::num::NonZeroU32;
@@ -33,13 +34,21 @@ pub use self::helpers::{
     merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap, merge_roaring_bitmaps,
     valid_lmdb_key, write_sorter_into_database, writer_into_reader, MergeFn,
 };
-use self::helpers::{grenad_obkv_into_chunks, GrenadParameters};
+use self::helpers::{grenad_obkv_into_chunks, try_split_array_at, GrenadParameters};
 pub use self::transform::{Transform, TransformOutput};
+use crate::acl::RESERVED_ACL_FIELD_NAME;
+use crate::document_hook::apply_document_hook;
 use crate::documents::{obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader};
-use crate::error::{Error, InternalError, UserError};
+use crate::error::{Error, InternalError, SerializationError, UserError};
+use crate::index::db_name;
+use crate::pii::encrypt_pii_attributes;
 use crate::thread_pool_no_abort::ThreadPoolNoAbortBuilder;
+use crate::update::fields_stats::{update_fields_stats, FieldsStatsDelta};
 pub use crate::update::index_documents::helpers::CursorClonableMmap;
 use crate::update::index_documents::parallel::ImmutableObkvs;
+use crate::update::numeric_sort_index::{update_numeric_sort_indexes, NumericSortIndexesDelta};
+use crate::update::phonetic_words::compute_phonetic_words_fst;
+use crate::update::prefix_cache::update_prefix_cache;
 use crate::update::{
     IndexerConfig, UpdateIndexingStep, WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
 };
@@ -76,6 +85,25 @@ impl Default for IndexDocumentsMethod {
     }
 }
 
+/// Whether documents are allowed to create or to overwrite a document.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DocumentsWriteMode {
+    /// Create the missing documents and update the existing ones.
+    #[default]
+    Upsert,
+    /// Only create documents, the documents that already exist are rejected.
+    FailIfExists,
+    /// Only update documents, the documents that do not exist yet are rejected.
+    FailIfMissing,
+}
+
+impl DocumentsWriteMode {
+    pub fn is_upsert(&self) -> bool {
+        matches!(self, Self::Upsert)
+    }
+}
+
 pub struct IndexDocuments<'t, 'i, 'a, FP, FA> {
     wtxn: &'t mut heed::RwTxn<'i>,
     index: &'i Index,
@@ -112,6 +140,13 @@ where
         progress: FP,
         should_abort: FA,
     ) -> Result<IndexDocuments<'t, 'i, 'a, FP, FA>> {
+        // While no document has an `_acl` field, the field can't have been left unfaceted.
+        if !index.acl_indexed(wtxn)?
+            && index.fields_ids_map(wtxn)?.id(RESERVED_ACL_FIELD_NAME).is_none()
+        {
+            index.put_acl_indexed(wtxn)?;
+        }
+
         let transform = Some(Transform::new(
             wtxn,
             index,
@@ -142,7 +177,7 @@ where
     /// Returns the number of documents added to the builder.
     #[tracing::instrument(level = "trace", skip_all, target = "indexing::documents")]
     pub fn add_documents<R: Read + Seek>(
-        mut self,
+        self,
         reader: DocumentsBatchReader<R>,
     ) -> Result<(Self, StdResult<u64, UserError>)> {
         // Early return when there is no document to add
@@ -150,6 +185,39 @@ where
             return Ok((self, Ok(0)));
         }
 
+        match self.index.document_hook(self.wtxn)? {
+            Some(hook) => match apply_document_hook(&hook, reader)? {
+                Ok(reader) => self.add_protected_documents(reader),
+                Err(user_error) => Ok((self, Err(user_error))),
+            },
+            None => self.add_protected_documents(reader),
+        }
+    }
+
+    /// Encrypts the PII attributes of the documents before they are stored.
+    fn add_protected_documents<R: Read + Seek>(
+        self,
+        reader: DocumentsBatchReader<R>,
+    ) -> Result<(Self, StdResult<u64, UserError>)> {
+        let pii_attributes = self.index.pii_attributes(self.wtxn)?;
+        if pii_attributes.is_empty() {
+            return self.add_enriched_documents(reader);
+        }
+
+        let indexer_config = self.indexer_config;
+        match &indexer_config.pii_cipher {
+            Some(cipher) => {
+                let reader = encrypt_pii_attributes(cipher, &pii_attributes, reader)?;
+                self.add_enriched_documents(reader)
+            }
+            None => Ok((self, Err(UserError::MissingPiiEncryptionKey))),
+        }
+    }
+
+    fn add_enriched_documents<R: Read + Seek>(
+        mut self,
+        reader: DocumentsBatchReader<R>,
+    ) -> Result<(Self, StdResult<u64, UserError>)> {
         // We check for user errors in this validator and if there is one, we can return
         // the `IndexDocument` struct as it is valid to send more documents into it.
         // However, if there is an internal error we throw it away!
@@ -157,6 +225,7 @@ where
             self.wtxn,
             self.index,
             self.config.autogenerate_docids,
+            self.indexer_config.attachments_quotas,
             reader,
         )? {
             Ok(reader) => reader,
@@ -176,6 +245,14 @@ where
         Ok((self, Ok(indexed_documents)))
     }
 
+    /// Returns the number of documents that were skipped by this builder because their
+    /// version was lower than the version of the document they were replacing.
+    ///
+    /// Those documents are not counted in the result of [`Self::add_documents`].
+    pub fn outdated_documents_count(&self) -> u64 {
+        self.transform.as_ref().map_or(0, |transform| transform.outdated_documents_count())
+    }
+
     #[tracing::instrument(level = "trace", skip_all, target = "indexing::documents")]
     pub fn edit_documents(
         self,
@@ -316,6 +393,29 @@ where
         self
     }
 
+    /// Sets the write mode used by the next calls to [`Self::add_documents`].
+    pub fn with_write_mode(mut self, write_mode: DocumentsWriteMode) -> Self {
+        if let Some(transform) = self.transform.as_mut() {
+            transform.write_mode = write_mode;
+        }
+        self
+    }
+
+    /// Returns the number of documents that were rejected by this builder
+    /// because of its [`DocumentsWriteMode`].
+    ///
+    /// Those documents are not counted in the result of [`Self::add_documents`].
+    pub fn rejected_documents_count(&self) -> u64 {
+        self.transform.as_ref().map_or(0, |transform| transform.rejected_documents_count())
+    }
+
+    /// Returns the external ids of the documents that were rejected by this builder
+    /// because of its [`DocumentsWriteMode`], only the first hundred of each call to
+    /// [`Self::add_documents`] are kept.
+    pub fn rejected_documents(&self) -> &[String] {
+        self.transform.as_ref().map_or(&[], |transform| transform.rejected_documents())
+    }
+
     /// Remove a batch of documents from the current builder.
     ///
     /// Returns the number of documents deleted from the builder.
@@ -417,8 +517,19 @@ where
             documents_count,
             original_documents,
             flattened_documents,
+            attachments,
+            known_fields_ids_map,
         } = output;
 
+        // the fields discovered by this addition may be made filterable or not searchable
+        if let Some(known_fields_ids_map) = known_fields_ids_map {
+            settings_diff.new.apply_new_fields_policy(
+                self.wtxn,
+                self.index,
+                &known_fields_ids_map,
+            )?;
+        }
+
         // update the internal facet and searchable list,
         // because they might have changed due to the nested documents flattening.
         settings_diff.new.recompute_facets(self.wtxn, self.index)?;
@@ -503,6 +614,9 @@ where
         let mut word_docids = None;
         let mut exact_word_docids = None;
         let mut chunk_accumulator = ChunkAccumulator::default();
+        let mut fields_stats_delta = FieldsStatsDelta::default();
+        let mut sort_indexes_delta = NumericSortIndexesDelta::default();
+        let mut modified_documents = RoaringBitmap::new();
         let mut dimension = HashMap::new();
 
         let current_span = tracing::Span::current();
@@ -567,7 +681,7 @@ where
                     Err(status) => {
                         if let Some(typed_chunks) = chunk_accumulator.pop_longest() {
                             let (docids, is_merged_database) =
-                                write_typed_chunk_into_index(self.wtxn, self.index, &settings_diff, typed_chunks)?;
+                                write_typed_chunk_into_index(self.wtxn, self.index, &settings_diff, typed_chunks, &mut fields_stats_delta, &mut sort_indexes_delta, &mut modified_documents)?;
                             if !docids.is_empty() {
                                 final_documents_ids |= docids;
                                 let documents_seen_count = final_documents_ids.len();
@@ -672,6 +786,10 @@ where
 
         // We write the primary key field id into the main database
         self.index.put_primary_key(self.wtxn, &primary_key)?;
+
+        if let Some(attachments) = attachments {
+            write_attachments_into_index(self.wtxn, self.index, attachments)?;
+        }
         let number_of_documents = self.index.number_of_documents(self.wtxn)?;
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
 
@@ -704,6 +822,11 @@ where
             word_fid_docids.map(MergerBuilder::build),
         )?;
 
+        update_fields_stats(self.index, self.wtxn, fields_stats_delta)?;
+        update_numeric_sort_indexes(self.index, self.wtxn, sort_indexes_delta)?;
+        compute_phonetic_words_fst(self.index, self.wtxn)?;
+        update_prefix_cache(self.index, self.wtxn, &modified_documents)?;
+
         Ok(number_of_documents)
     }
 
@@ -915,6 +1038,35 @@ fn execute_word_prefix_docids(
     Ok(())
 }
 
+/// Puts or deletes the attachments of the documents that are still in the index.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::documents")]
+fn write_attachments_into_index(
+    wtxn: &mut heed::RwTxn<'_>,
+    index: &Index,
+    attachments: File,
+) -> Result<()> {
+    let documents_ids = index.documents_ids(wtxn)?;
+    let mut cursor = grenad::Reader::new(attachments)?.into_cursor()?;
+    while let Some((key, value)) = cursor.move_on_next()? {
+        let (docid_bytes, name_bytes) = try_split_array_at(key)
+            .ok_or(SerializationError::Decoding { db_name: Some(db_name::ATTACHMENTS) })?;
+        let docid = crate::DocumentId::from_be_bytes(docid_bytes);
+        // the document may have been deleted after its attachments were given
+        if !documents_ids.contains(docid) {
+            continue;
+        }
+
+        let name = std::str::from_utf8(name_bytes)?;
+        match value.split_first() {
+            Some((1, content)) => index.attachments.put(wtxn, &(docid, name), content)?,
+            _ => {
+                index.attachments.delete(wtxn, &(docid, name))?;
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -2388,6 +2540,147 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn skip_outdated_documents() {
+        let index = TempIndex::new();
+        index.update_settings(|settings| settings.set_version_field(S("version"))).unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "doggo": "kevin", "version": 2 },
+                { "id": 2, "doggo": "bob", "version": 2 },
+            ]))
+            .unwrap();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+
+        // the first document is older than the stored one, the second one is newer
+        let documents = documents!([
+            { "id": 1, "doggo": "old kevin", "version": 1 },
+            { "id": 2, "doggo": "new bob", "version": 3 },
+            { "id": 3, "doggo": "jean" },
+        ]);
+        let (builder, added) = builder.add_documents(documents).unwrap();
+        insta::assert_snapshot!(added.unwrap(), @"2");
+        insta::assert_snapshot!(builder.outdated_documents_count(), @"1");
+
+        // versions are also compared with the documents of the same batch
+        let documents = documents!([
+            { "id": 2, "doggo": "replayed bob", "version": 2 },
+            { "id": 3, "doggo": "jean", "version": 1 },
+        ]);
+        let (builder, added) = builder.add_documents(documents).unwrap();
+        insta::assert_snapshot!(added.unwrap(), @"1");
+        insta::assert_snapshot!(builder.outdated_documents_count(), @"2");
+
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        db_snap!(index, documents, @r###"
+        {"id":1,"doggo":"kevin","version":2}
+        {"id":2,"doggo":"new bob","version":3}
+        {"id":3,"doggo":"jean","version":1}
+        "###);
+    }
+
+    #[test]
+    fn write_modes() {
+        let index = TempIndex::new();
+        index.add_documents(documents!([{ "id": 1, "doggo": "kevin" }])).unwrap();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+
+        let builder = builder.with_write_mode(DocumentsWriteMode::FailIfExists);
+        let documents = documents!([
+            { "id": 1, "doggo": "replaced kevin" },
+            { "id": 2, "doggo": "bob" },
+        ]);
+        let (builder, added) = builder.add_documents(documents).unwrap();
+        insta::assert_snapshot!(added.unwrap(), @"1");
+        insta::assert_debug_snapshot!(builder.rejected_documents(), @r###"
+        [
+            "1",
+        ]
+        "###);
+        insta::assert_snapshot!(builder.rejected_documents_count(), @"1");
+
+        // the documents added by the previous call are considered as existing
+        let builder = builder.with_write_mode(DocumentsWriteMode::FailIfMissing);
+        let documents = documents!([
+            { "id": 2, "doggo": "updated bob" },
+            { "id": 3, "doggo": "jean" },
+        ]);
+        let (builder, added) = builder.add_documents(documents).unwrap();
+        insta::assert_snapshot!(added.unwrap(), @"1");
+        insta::assert_debug_snapshot!(builder.rejected_documents(), @r###"
+        [
+            "1",
+            "3",
+        ]
+        "###);
+        insta::assert_snapshot!(builder.rejected_documents_count(), @"2");
+
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        db_snap!(index, documents, @r###"
+        {"id":1,"doggo":"kevin"}
+        {"id":2,"doggo":"updated bob"}
+        "###);
+    }
+
+    #[test]
+    fn write_modes_rejected_documents_sample() {
+        let index = TempIndex::new();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+
+        let builder = builder.with_write_mode(DocumentsWriteMode::FailIfMissing);
+        let documents = (0..150).map(|id| {
+            let mut object = serde_json::Map::new();
+            object.insert(S("id"), serde_json::json!(id));
+            object
+        });
+        let documents = documents_batch_reader_from_objects(documents);
+        let (builder, added) = builder.add_documents(documents).unwrap();
+        insta::assert_snapshot!(added.unwrap(), @"0");
+        insta::assert_snapshot!(builder.rejected_documents_count(), @"150");
+        insta::assert_snapshot!(builder.rejected_documents().len(), @"100");
+
+        // each addition keeps its own sample
+        let documents = documents!([{ "id": 150 }]);
+        let (builder, _) = builder.add_documents(documents).unwrap();
+        insta::assert_snapshot!(builder.rejected_documents_count(), @"151");
+        insta::assert_snapshot!(builder.rejected_documents()[100..].join(", "), @"150");
+    }
+
     #[test]
     fn add_update_and_delete_documents_in_single_transform() {
         let mut index = TempIndex::new();
@@ -3471,4 +3764,43 @@ mod tests {
         let crate::SearchResult { documents_ids, .. } = s.execute().unwrap();
         insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
     }
+
+    #[test]
+    fn new_fields_policy() {
+        let index = TempIndex::new();
+
+        index.add_documents(documents!([{ "id": 0, "text": "hello" }])).unwrap();
+        index
+            .update_settings(|settings| {
+                settings.set_new_fields_searchable(false);
+                settings.set_new_fields_filterable(true);
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 1, "text": "hello", "level": "warning" },
+                { "id": 2, "text": "world", "nested": { "level": "error" } }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        // the `*` was replaced by the fields known before the second addition
+        let searchable = index.user_defined_searchable_fields(&rtxn).unwrap();
+        insta::assert_snapshot!(format!("{searchable:?}"), @r###"Some(["id", "text"])"###);
+        let mut filterable: Vec<_> = index.filterable_fields(&rtxn).unwrap().into_iter().collect();
+        filterable.sort_unstable();
+        insta::assert_snapshot!(format!("{filterable:?}"), @r###"["level", "nested", "nested.level"]"###);
+
+        let search = |query: &str, filter: Option<&str>| {
+            let mut s = Search::new(&rtxn, &index);
+            s.query(query);
+            if let Some(filter) = filter {
+                s.filter(Filter::from_str(filter).unwrap().unwrap());
+            }
+            s.execute().unwrap().documents_ids
+        };
+        insta::assert_snapshot!(format!("{:?}", search("warning", None)), @"[]");
+        insta::assert_snapshot!(format!("{:?}", search("hello", Some("level = warning"))), @"[1]");
+        insta::assert_snapshot!(format!("{:?}", search("", Some("nested.level = error"))), @"[2]");
+    }
 }
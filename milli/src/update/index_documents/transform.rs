@@ -13,16 +13,19 @@ use serde_json::Value;
 use smartstring::SmartString;
 
 use super::helpers::{
-    create_sorter, create_writer, keep_first, obkvs_keep_last_addition_merge_deletions,
-    obkvs_merge_additions_and_deletions, sorter_into_reader, MergeFn,
+    create_sorter, create_writer, keep_first, keep_latest_obkv,
+    obkvs_keep_last_addition_merge_deletions, obkvs_merge_additions_and_deletions,
+    sorter_into_reader, MergeFn,
 };
-use super::{IndexDocumentsMethod, IndexerConfig};
+use super::{DocumentsWriteMode, IndexDocumentsMethod, IndexerConfig};
+use crate::attachments::{parse_attachments, RESERVED_ATTACHMENTS_FIELD_NAME};
 use crate::documents::{DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchReader};
 use crate::error::{Error, InternalError, UserError};
 use crate::index::{db_name, main_key};
+use crate::pii::PiiCipher;
 use crate::update::del_add::{
-    into_del_add_obkv, into_del_add_obkv_conditional_operation, DelAdd, DelAddOperation,
-    KvReaderDelAdd,
+    del_add_from_two_obkvs, into_del_add_obkv, into_del_add_obkv_conditional_operation, DelAdd,
+    DelAddOperation, KvReaderDelAdd,
 };
 use crate::update::index_documents::GrenadParameters;
 use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
@@ -33,6 +36,9 @@ use crate::{
     is_faceted_by, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index, Result,
 };
 
+/// The number of rejected external ids kept by each call to [`Transform::read_documents`].
+const MAX_REJECTED_DOCUMENTS_SAMPLE: usize = 100;
+
 pub struct TransformOutput {
     pub primary_key: String,
     pub settings_diff: InnerIndexSettingsDiff,
@@ -40,6 +46,10 @@ pub struct TransformOutput {
     pub documents_count: usize,
     pub original_documents: Option<File>,
     pub flattened_documents: Option<File>,
+    /// The attachments to put or delete, keyed by the internal document id and their name.
+    pub attachments: Option<File>,
+    /// The fields known before the documents were added, if any were.
+    pub known_fields_ids_map: Option<FieldsIdsMap>,
 }
 
 /// Extract the external ids, deduplicate and compute the new internal documents ids
@@ -51,9 +61,12 @@ pub struct TransformOutput {
 pub struct Transform<'a, 'i> {
     pub index: &'i Index,
     fields_ids_map: FieldsIdsMap,
+    /// The fields of the index before the documents were added.
+    known_fields_ids_map: FieldsIdsMap,
 
     indexer_settings: &'a IndexerConfig,
     pub index_documents_method: IndexDocumentsMethod,
+    pub write_mode: DocumentsWriteMode,
     available_documents_ids: AvailableDocumentsIds,
 
     // Both grenad follows the same format:
@@ -61,12 +74,22 @@ pub struct Transform<'a, 'i> {
     // u32 | 1 byte for the Operation byte, the rest is the obkv of the document stored
     original_sorter: grenad::Sorter<MergeFn>,
     flattened_sorter: grenad::Sorter<MergeFn>,
+    // The attachments grenad follows this format:
+    // key                    | value
+    // u32 ++ attachment name | 1 byte telling if the attachment is put, followed by its content
+    attachments_sorter: grenad::Sorter<MergeFn>,
 
     replaced_documents_ids: RoaringBitmap,
     new_documents_ids: RoaringBitmap,
     // To increase the cache locality and decrease the heap usage we use compact smartstring.
     new_external_documents_ids_builder: FxHashMap<SmartString<smartstring::Compact>, u64>,
     documents_count: usize,
+    // The versions of the documents added by this transform, only filled when a version field is set.
+    documents_versions: HashMap<u32, f64>,
+    outdated_documents_count: u64,
+    rejected_documents_count: u64,
+    // The first external ids rejected by each call to `read_documents`.
+    rejected_documents: Vec<String>,
 }
 
 /// This enum is specific to the grenad sorter stored in the transform.
@@ -90,6 +113,8 @@ fn create_fields_mapping(
         // we sort by id here to ensure a deterministic mapping of the fields, that preserves
         // the original ordering.
         .sorted_by_key(|(&id, _)| id)
+        // the attachments are stored apart from the documents
+        .filter(|(_, name)| name.as_str() != RESERVED_ATTACHMENTS_FIELD_NAME)
         .map(|(field, name)| match index_field_map.id(name) {
             Some(id) => Ok((*field, id)),
             None => index_field_map
@@ -134,20 +159,37 @@ impl<'a, 'i> Transform<'a, 'i> {
             indexer_settings.max_nb_chunks,
             indexer_settings.max_memory.map(|mem| mem / 2),
         );
+        let attachments_sorter = create_sorter(
+            grenad::SortAlgorithm::Stable,
+            keep_latest_obkv,
+            indexer_settings.chunk_compression_type,
+            indexer_settings.chunk_compression_level,
+            indexer_settings.max_nb_chunks,
+            None,
+        );
         let documents_ids = index.documents_ids(wtxn)?;
 
+        let fields_ids_map = index.fields_ids_map(wtxn)?;
+
         Ok(Transform {
             index,
-            fields_ids_map: index.fields_ids_map(wtxn)?,
+            known_fields_ids_map: fields_ids_map.clone(),
+            fields_ids_map,
             indexer_settings,
             available_documents_ids: AvailableDocumentsIds::from_documents_ids(&documents_ids),
             original_sorter,
             flattened_sorter,
+            attachments_sorter,
             index_documents_method,
+            write_mode: DocumentsWriteMode::default(),
             replaced_documents_ids: RoaringBitmap::new(),
             new_documents_ids: RoaringBitmap::new(),
             new_external_documents_ids_builder: FxHashMap::default(),
             documents_count: 0,
+            documents_versions: HashMap::new(),
+            outdated_documents_count: 0,
+            rejected_documents_count: 0,
+            rejected_documents: Vec::new(),
         })
     }
 
@@ -167,10 +209,15 @@ impl<'a, 'i> Transform<'a, 'i> {
         let (mut cursor, fields_index) = reader.into_cursor_and_fields_index();
         let external_documents_ids = self.index.external_documents_ids();
         let mapping = create_fields_mapping(&mut self.fields_ids_map, &fields_index)?;
+        let attachments_field_id = fields_index.id(RESERVED_ATTACHMENTS_FIELD_NAME);
 
         let primary_key = cursor.primary_key().to_string();
         let primary_key_id =
             self.fields_ids_map.insert(&primary_key).ok_or(UserError::AttributeLimitReached)?;
+        let version_field_id = match self.index.version_field(wtxn)? {
+            Some(name) => self.fields_ids_map.id(name),
+            None => None,
+        };
 
         let mut obkv_buffer = Vec::new();
         let mut document_sorter_value_buffer = Vec::new();
@@ -178,6 +225,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         let mut documents_count = 0;
         let mut docid_buffer: Vec<u8> = Vec::new();
         let mut field_buffer: Vec<(u16, Cow<'_, [u8]>)> = Vec::new();
+        let rejected_documents_start = self.rejected_documents.len();
         while let Some(enriched_document) = cursor.next_enriched_document()? {
             let EnrichedDocument { document, document_id } = enriched_document;
 
@@ -197,13 +245,43 @@ impl<'a, 'i> Transform<'a, 'i> {
             // When the document id has been auto-generated by the `enrich_documents_batch`
             // we must insert this document id into the remaped document.
             let external_id = document_id.value();
+            if self.write_mode != DocumentsWriteMode::Upsert {
+                let exists = match self.new_external_documents_ids_builder.get(external_id) {
+                    Some(_) => true,
+                    // a document removed by this transform is in the replaced documents ids
+                    None => external_documents_ids
+                        .get(wtxn, external_id)?
+                        .map_or(false, |docid| !self.replaced_documents_ids.contains(docid)),
+                };
+                let rejected = match self.write_mode {
+                    DocumentsWriteMode::Upsert => false,
+                    DocumentsWriteMode::FailIfExists => exists,
+                    DocumentsWriteMode::FailIfMissing => !exists,
+                };
+                if rejected {
+                    self.rejected_documents_count += 1;
+                    if self.rejected_documents.len() - rejected_documents_start
+                        < MAX_REJECTED_DOCUMENTS_SAMPLE
+                    {
+                        self.rejected_documents.push(external_id.to_string());
+                    }
+                    field_buffer = drop_and_reuse(field_buffer_cache);
+                    continue;
+                }
+            }
+
             if document_id.is_generated() {
                 serde_json::to_writer(&mut docid_buffer, external_id)
                     .map_err(InternalError::SerdeJson)?;
                 field_buffer_cache.push((primary_key_id, Cow::from(&docid_buffer)));
             }
 
+            let mut attachments = None;
             for (k, v) in document.iter() {
+                if Some(k) == attachments_field_id {
+                    attachments = Some(v);
+                    continue;
+                }
                 let mapped_id =
                     *mapping.get(&k).ok_or(InternalError::FieldIdMappingMissingEntry { key: k })?;
                 field_buffer_cache.push((mapped_id, Cow::from(v)));
@@ -244,6 +322,74 @@ impl<'a, 'i> Transform<'a, 'i> {
                 }
             };
 
+            if let Some(version_field_id) = version_field_id {
+                let new_version =
+                    document_version(KvReaderU16::new(&obkv_buffer), version_field_id);
+                let current_version = match (self.documents_versions.get(&docid), original_docid) {
+                    (Some(version), _) => Some(*version),
+                    (None, Some(original_docid)) => {
+                        let base_obkv = self
+                            .index
+                            .documents
+                            .remap_data_type::<heed::types::Bytes>()
+                            .get(wtxn, &original_docid)?
+                            .ok_or(InternalError::DatabaseMissingEntry {
+                                db_name: db_name::DOCUMENTS,
+                                key: None,
+                            })?;
+                        document_version(KvReaderU16::new(base_obkv), version_field_id)
+                    }
+                    (None, None) => None,
+                };
+
+                match (new_version, current_version) {
+                    // the document is older than the one we already know, we skip it entirely
+                    (Some(new), Some(current)) if new < current => {
+                        if let Some(original_docid) = original_docid {
+                            self.replaced_documents_ids.remove(original_docid);
+                            self.new_external_documents_ids_builder.remove(external_id);
+                        }
+                        self.outdated_documents_count += 1;
+
+                        field_buffer = drop_and_reuse(field_buffer_cache);
+                        docid_buffer.clear();
+                        obkv_buffer.clear();
+                        continue;
+                    }
+                    (Some(new), _) => {
+                        self.documents_versions.insert(docid, new);
+                    }
+                    (None, _) => (),
+                }
+            }
+
+            // The attachments were validated when enriching the documents, they are written
+            // even if the rest of the document didn't change.
+            if let Some(attachments) = attachments {
+                let attachments =
+                    serde_json::from_slice(attachments).map_err(InternalError::SerdeJson)?;
+                let attachments = parse_attachments(
+                    external_id,
+                    &attachments,
+                    self.indexer_settings.attachments_quotas,
+                )?;
+                for (name, content) in attachments {
+                    document_sorter_key_buffer.clear();
+                    document_sorter_key_buffer.extend_from_slice(&docid.to_be_bytes());
+                    document_sorter_key_buffer.extend_from_slice(name.as_bytes());
+                    document_sorter_value_buffer.clear();
+                    match content {
+                        Some(content) => {
+                            document_sorter_value_buffer.push(1);
+                            document_sorter_value_buffer.extend_from_slice(&content);
+                        }
+                        None => document_sorter_value_buffer.push(0),
+                    }
+                    self.attachments_sorter
+                        .insert(&document_sorter_key_buffer, &document_sorter_value_buffer)?;
+                }
+            }
+
             let mut skip_insertion = false;
             if let Some(original_docid) = original_docid {
                 let original_key = original_docid;
@@ -356,6 +502,23 @@ impl<'a, 'i> Transform<'a, 'i> {
         Ok(documents_count)
     }
 
+    /// The number of documents that were not added because their version
+    /// was lower than the version of the document they were replacing.
+    pub fn outdated_documents_count(&self) -> u64 {
+        self.outdated_documents_count
+    }
+
+    /// The number of documents that were not added because of the write mode.
+    pub fn rejected_documents_count(&self) -> u64 {
+        self.rejected_documents_count
+    }
+
+    /// The external ids of the documents that were not added because of the write mode,
+    /// limited to the first hundred of each call to `read_documents`.
+    pub fn rejected_documents(&self) -> &[String] {
+        &self.rejected_documents
+    }
+
     /// The counter part of `read_documents` that removes documents either from the transform or the database.
     /// It can be called before, after or in between two calls of the `read_documents`.
     ///
@@ -414,6 +577,7 @@ impl<'a, 'i> Transform<'a, 'i> {
                         // 2. It wasn't in it because the document was created by a previous batch and since
                         //    we're removing it there is nothing to do.
                         self.new_documents_ids.remove(docid);
+                        self.documents_versions.remove(&docid);
                         entry.remove_entry();
                         true
                     }
@@ -792,6 +956,15 @@ impl<'a, 'i> Transform<'a, 'i> {
         let mut flattened_documents = writer.into_inner()?;
         flattened_documents.rewind()?;
 
+        let mut writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+        self.attachments_sorter.write_into_stream_writer(&mut writer)?;
+        let mut attachments = writer.into_inner()?;
+        attachments.rewind()?;
+
         let mut new_external_documents_ids_builder: Vec<_> =
             self.new_external_documents_ids_builder.into_iter().collect();
 
@@ -829,6 +1002,8 @@ impl<'a, 'i> Transform<'a, 'i> {
             flattened_documents: Some(
                 flattened_documents.into_inner().map_err(|err| err.into_error())?,
             ),
+            attachments: Some(attachments.into_inner().map_err(|err| err.into_error())?),
+            known_fields_ids_map: Some(self.known_fields_ids_map),
         })
     }
 
@@ -842,6 +1017,8 @@ impl<'a, 'i> Transform<'a, 'i> {
         modified_faceted_fields: &HashSet<String>,
         mut injected_vectors: serde_json::Map<String, serde_json::Value>,
         old_vectors_fid: Option<FieldId>,
+        pii_cipher: Option<&PiiCipher>,
+        fields_ids_map: &mut FieldsIdsMap,
         original_obkv_buffer: Option<&mut Vec<u8>>,
         flattened_obkv_buffer: Option<&mut Vec<u8>>,
     ) -> Result<()> {
@@ -862,6 +1039,11 @@ impl<'a, 'i> Transform<'a, 'i> {
         // we need the fields for the prompt/templating.
         let reindex_vectors = settings_diff.reindex_vectors();
 
+        // The whole documents are written again with the values of the attributes that became
+        // or stopped being PII encrypted or decrypted, the previous values are deleted.
+        let reencrypt_pii = settings_diff.reencrypt_pii();
+        let mut previous_obkv_writer = KvWriter::<_, FieldId>::memory();
+
         // The operations that we must perform on the different fields.
         let mut operations = HashMap::new();
         let mut error_seen = false;
@@ -899,15 +1081,25 @@ impl<'a, 'i> Transform<'a, 'i> {
                     existing_vectors.append(&mut injected_vectors);
 
                     operations.insert(vectors_fid, DelAddOperation::DeletionAndAddition);
-                    obkv_writer
-                        .insert(vectors_fid, serde_json::to_vec(&existing_vectors).unwrap())?;
+                    let existing_vectors = serde_json::to_vec(&existing_vectors).unwrap();
+                    if reencrypt_pii {
+                        previous_obkv_writer.insert(vectors_fid, &existing_vectors)?;
+                    }
+                    obkv_writer.insert(vectors_fid, existing_vectors)?;
                     if id == vectors_fid {
                         continue 'write_fid;
                     }
                 }
             }
 
-            if is_primary_key(id) || necessary_faceted_field(id) || reindex_vectors {
+            if reencrypt_pii {
+                operations.insert(id, DelAddOperation::DeletionAndAddition);
+                previous_obkv_writer.insert(id, val)?;
+                match Self::reencrypt_pii_value(id, val, settings_diff, pii_cipher)? {
+                    Some(value) => obkv_writer.insert(id, value)?,
+                    None => obkv_writer.insert(id, val)?,
+                }
+            } else if is_primary_key(id) || necessary_faceted_field(id) || reindex_vectors {
                 operations.insert(id, DelAddOperation::DeletionAndAddition);
                 obkv_writer.insert(id, val)?;
             } else if let Some(operation) = settings_diff.reindex_searchable_id(id) {
@@ -920,33 +1112,81 @@ impl<'a, 'i> Transform<'a, 'i> {
                 let Some(vectors_fid) = old_vectors_fid else { break 'inject_vectors };
 
                 operations.insert(vectors_fid, DelAddOperation::DeletionAndAddition);
-                obkv_writer.insert(vectors_fid, serde_json::to_vec(&injected_vectors).unwrap())?;
+                let injected_vectors = serde_json::to_vec(&injected_vectors).unwrap();
+                if reencrypt_pii {
+                    previous_obkv_writer.insert(vectors_fid, &injected_vectors)?;
+                }
+                obkv_writer.insert(vectors_fid, injected_vectors)?;
             }
         }
 
         let data = obkv_writer.into_inner()?;
         let obkv = KvReader::<FieldId>::new(&data);
+        let previous_data = previous_obkv_writer.into_inner()?;
+        let previous_obkv = KvReader::<FieldId>::new(&previous_data);
 
         if let Some(original_obkv_buffer) = original_obkv_buffer {
             original_obkv_buffer.clear();
-            into_del_add_obkv(obkv, DelAddOperation::DeletionAndAddition, original_obkv_buffer)?;
+            if reencrypt_pii {
+                del_add_from_two_obkvs(&previous_obkv, &obkv, original_obkv_buffer)?;
+            } else {
+                into_del_add_obkv(
+                    obkv,
+                    DelAddOperation::DeletionAndAddition,
+                    original_obkv_buffer,
+                )?;
+            }
         }
 
         if let Some(flattened_obkv_buffer) = flattened_obkv_buffer {
             // take the non-flattened version if flatten_from_fields_ids_map returns None.
-            let mut fields_ids_map = settings_diff.new.fields_ids_map.clone();
-            let flattened = Self::flatten_from_fields_ids_map(&obkv, &mut fields_ids_map)?;
+            let flattened = Self::flatten_from_fields_ids_map(&obkv, fields_ids_map)?;
             let flattened = flattened.as_deref().map_or(obkv, KvReader::new);
 
             flattened_obkv_buffer.clear();
-            into_del_add_obkv_conditional_operation(flattened, flattened_obkv_buffer, |id| {
-                operations.get(&id).copied().unwrap_or(DelAddOperation::DeletionAndAddition)
-            })?;
+            if reencrypt_pii {
+                let previous_flattened =
+                    Self::flatten_from_fields_ids_map(&previous_obkv, fields_ids_map)?;
+                let previous_flattened =
+                    previous_flattened.as_deref().map_or(previous_obkv, KvReader::new);
+                del_add_from_two_obkvs(&previous_flattened, &flattened, flattened_obkv_buffer)?;
+            } else {
+                into_del_add_obkv_conditional_operation(flattened, flattened_obkv_buffer, |id| {
+                    operations.get(&id).copied().unwrap_or(DelAddOperation::DeletionAndAddition)
+                })?;
+            }
         }
 
         Ok(())
     }
 
+    /// Encrypts the value of an attribute that became PII and decrypts the value of an attribute
+    /// that stopped being PII. Returns `None` if the value doesn't change.
+    fn reencrypt_pii_value(
+        id: FieldId,
+        value: &[u8],
+        settings_diff: &InnerIndexSettingsDiff,
+        pii_cipher: Option<&PiiCipher>,
+    ) -> Result<Option<Vec<u8>>> {
+        let field_name = settings_diff.new.fields_ids_map.name(id).unwrap();
+        if !settings_diff.pii_changed(field_name) {
+            return Ok(None);
+        }
+
+        let cipher = pii_cipher.ok_or(UserError::MissingPiiEncryptionKey)?;
+        let value: Value = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+        let value = if settings_diff.new.pii_attributes.contains(field_name) {
+            cipher.encrypt(&value)?
+        } else {
+            match cipher.decrypt(&value) {
+                Some(value) => value,
+                // the value was stored before the attribute became PII
+                None => return Ok(None),
+            }
+        };
+        Ok(Some(serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?))
+    }
+
     /// Clear all databases. Returns a `TransformOutput` with a file that contains the documents
     /// of the index with the attributes reordered accordingly to the `FieldsIdsMap` given as argument.
     ///
@@ -959,7 +1199,7 @@ impl<'a, 'i> Transform<'a, 'i> {
     pub fn prepare_for_documents_reindexing(
         self,
         wtxn: &mut heed::RwTxn<'i>,
-        settings_diff: InnerIndexSettingsDiff,
+        mut settings_diff: InnerIndexSettingsDiff,
     ) -> Result<TransformOutput> {
         // There already has been a document addition, the primary key should be set by now.
         let primary_key = self
@@ -976,18 +1216,19 @@ impl<'a, 'i> Transform<'a, 'i> {
         let documents_count = documents_ids.len() as usize;
 
         // We initialize the sorter with the user indexing settings.
-        let mut original_sorter = if settings_diff.reindex_vectors() {
-            Some(create_sorter(
-                grenad::SortAlgorithm::Stable,
-                keep_first,
-                self.indexer_settings.chunk_compression_type,
-                self.indexer_settings.chunk_compression_level,
-                self.indexer_settings.max_nb_chunks,
-                self.indexer_settings.max_memory.map(|mem| mem / 2),
-            ))
-        } else {
-            None
-        };
+        let mut original_sorter =
+            if settings_diff.reindex_vectors() || settings_diff.reencrypt_pii() {
+                Some(create_sorter(
+                    grenad::SortAlgorithm::Stable,
+                    keep_first,
+                    self.indexer_settings.chunk_compression_type,
+                    self.indexer_settings.chunk_compression_level,
+                    self.indexer_settings.max_nb_chunks,
+                    self.indexer_settings.max_memory.map(|mem| mem / 2),
+                ))
+            } else {
+                None
+            };
 
         let readers: Result<
             BTreeMap<&str, (Vec<arroy::Reader<'_, arroy::distances::Angular>>, &RoaringBitmap)>,
@@ -1019,20 +1260,24 @@ impl<'a, 'i> Transform<'a, 'i> {
             .id(crate::vector::parsed_vectors::RESERVED_VECTORS_FIELD_NAME);
 
         // We initialize the sorter with the user indexing settings.
-        let mut flattened_sorter =
-            if settings_diff.reindex_searchable() || settings_diff.reindex_facets() {
-                Some(create_sorter(
-                    grenad::SortAlgorithm::Stable,
-                    keep_first,
-                    self.indexer_settings.chunk_compression_type,
-                    self.indexer_settings.chunk_compression_level,
-                    self.indexer_settings.max_nb_chunks,
-                    self.indexer_settings.max_memory.map(|mem| mem / 2),
-                ))
-            } else {
-                None
-            };
+        let mut flattened_sorter = if settings_diff.reindex_searchable()
+            || settings_diff.reindex_facets()
+            || settings_diff.reencrypt_pii()
+        {
+            Some(create_sorter(
+                grenad::SortAlgorithm::Stable,
+                keep_first,
+                self.indexer_settings.chunk_compression_type,
+                self.indexer_settings.chunk_compression_level,
+                self.indexer_settings.max_nb_chunks,
+                self.indexer_settings.max_memory.map(|mem| mem / 2),
+            ))
+        } else {
+            None
+        };
 
+        // the decrypted values can contain nested fields that aren't known yet
+        let mut fields_ids_map = settings_diff.new.fields_ids_map.clone();
         if original_sorter.is_some() || flattened_sorter.is_some() {
             let modified_faceted_fields = settings_diff.modified_faceted_fields();
             let mut original_obkv_buffer = Vec::new();
@@ -1088,6 +1333,8 @@ impl<'a, 'i> Transform<'a, 'i> {
                     &modified_faceted_fields,
                     injected_vectors,
                     old_vectors_fid,
+                    self.indexer_settings.pii_cipher.as_ref(),
+                    &mut fields_ids_map,
                     Some(&mut original_obkv_buffer).filter(|_| original_sorter.is_some()),
                     Some(&mut flattened_obkv_buffer).filter(|_| flattened_sorter.is_some()),
                 )?;
@@ -1104,6 +1351,12 @@ impl<'a, 'i> Transform<'a, 'i> {
             }
         }
 
+        if fields_ids_map.len() != settings_diff.new.fields_ids_map.len() {
+            self.index.put_fields_ids_map(wtxn, &fields_ids_map)?;
+            settings_diff.new = InnerIndexSettings::from_index(self.index, wtxn)?;
+            settings_diff.new.recompute_facets(wtxn, self.index)?;
+        }
+
         let mut writers = Vec::new();
 
         // delete all vectors from the embedders that need removal
@@ -1145,10 +1398,18 @@ impl<'a, 'i> Transform<'a, 'i> {
             documents_count,
             original_documents: original_documents.map(|od| od.into_inner().into_inner()),
             flattened_documents: flattened_documents.map(|fd| fd.into_inner().into_inner()),
+            attachments: None,
+            known_fields_ids_map: None,
         })
     }
 }
 
+/// Returns the version of the document, only numbers are considered as valid versions.
+fn document_version(obkv: KvReaderU16<'_>, version_field_id: FieldId) -> Option<f64> {
+    let value = obkv.get(version_field_id)?;
+    serde_json::from_slice::<Value>(value).ok()?.as_f64()
+}
+
 /// Drops all the value of type `U` in vec, and reuses the allocation to create a `Vec<T>`.
 ///
 /// The size and alignment of T and U must match.
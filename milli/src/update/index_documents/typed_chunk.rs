@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::convert::TryInto;
 use std::fs::File;
 use std::io::{self, BufReader};
@@ -6,8 +6,8 @@ use std::io::{self, BufReader};
 use bytemuck::allocation::pod_collect_to_vec;
 use charabia::{Language, Script};
 use grenad::{Merger, MergerBuilder};
-use heed::types::Bytes;
-use heed::{BytesDecode, RwTxn};
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{BytesDecode, RoTxn, RwTxn};
 use obkv::{KvReader, KvWriter};
 use roaring::RoaringBitmap;
 
@@ -19,18 +19,22 @@ use super::helpers::{
 use super::MergeFn;
 use crate::external_documents_ids::{DocumentOperation, DocumentOperationKind};
 use crate::facet::FacetType;
+use crate::fields_schema::update_field_schema;
+use crate::heed_codec::StrBEU16Codec;
 use crate::index::db_name::DOCUMENTS;
 use crate::index::IndexEmbeddingConfig;
 use crate::proximity::MAX_DISTANCE;
 use crate::update::del_add::{deladd_serialize_add_side, DelAdd, KvReaderDelAdd};
 use crate::update::facet::FacetsUpdate;
+use crate::update::fields_stats::FieldsStatsDelta;
 use crate::update::index_documents::helpers::{
     as_cloneable_grenad, keep_latest_obkv, try_split_array_at,
 };
+use crate::update::numeric_sort_index::NumericSortIndexesDelta;
 use crate::update::settings::InnerIndexSettingsDiff;
 use crate::{
-    lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, FieldId, GeoPoint, Index, InternalError,
-    Result, SerializationError, U8StrStrCodec,
+    lat_lng_to_xyz, CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, DocumentId, FieldId, GeoPoint,
+    Index, InternalError, Result, SerializationError, U8StrStrCodec,
 };
 
 /// This struct accumulates and group the TypedChunks
@@ -71,19 +75,28 @@ pub(crate) enum TypedChunk {
     FieldIdDocidFacetStrings(grenad::Reader<CursorClonableMmap>),
     FieldIdDocidFacetNumbers(grenad::Reader<CursorClonableMmap>),
     Documents(grenad::Reader<CursorClonableMmap>),
-    FieldIdWordCountDocids(grenad::Reader<BufReader<File>>),
+    FieldIdWordCountDocids {
+        fid_word_count_docids_reader: grenad::Reader<BufReader<File>>,
+        /// The number of tokens added to each field, negative when tokens were removed.
+        fields_tokens_delta: BTreeMap<FieldId, i64>,
+    },
     WordDocids {
         word_docids_reader: grenad::Reader<BufReader<File>>,
         exact_word_docids_reader: grenad::Reader<BufReader<File>>,
         word_fid_docids_reader: grenad::Reader<BufReader<File>>,
     },
     WordPositionDocids(grenad::Reader<BufReader<File>>),
+    WordFrequencyDocids {
+        word_frequency_docids_reader: grenad::Reader<BufReader<File>>,
+        document_length_docids_reader: grenad::Reader<BufReader<File>>,
+    },
     WordPairProximityDocids(grenad::Reader<BufReader<File>>),
     FieldIdFacetStringDocids((grenad::Reader<BufReader<File>>, grenad::Reader<BufReader<File>>)),
     FieldIdFacetNumberDocids(grenad::Reader<BufReader<File>>),
     FieldIdFacetExistsDocids(grenad::Reader<BufReader<File>>),
     FieldIdFacetIsNullDocids(grenad::Reader<BufReader<File>>),
     FieldIdFacetIsEmptyDocids(grenad::Reader<BufReader<File>>),
+    FieldIdFacetBoolDocids(grenad::Reader<BufReader<File>>),
     GeoPoints(grenad::Reader<BufReader<File>>),
     VectorPoints {
         remove_vectors: grenad::Reader<BufReader<File>>,
@@ -104,15 +117,17 @@ impl TypedChunk {
             (FieldIdDocidFacetStrings(_), FieldIdDocidFacetStrings(_))
             | (FieldIdDocidFacetNumbers(_), FieldIdDocidFacetNumbers(_))
             | (Documents(_), Documents(_))
-            | (FieldIdWordCountDocids(_), FieldIdWordCountDocids(_))
+            | (FieldIdWordCountDocids { .. }, FieldIdWordCountDocids { .. })
             | (WordDocids { .. }, WordDocids { .. })
             | (WordPositionDocids(_), WordPositionDocids(_))
+            | (WordFrequencyDocids { .. }, WordFrequencyDocids { .. })
             | (WordPairProximityDocids(_), WordPairProximityDocids(_))
             | (FieldIdFacetStringDocids(_), FieldIdFacetStringDocids(_))
             | (FieldIdFacetNumberDocids(_), FieldIdFacetNumberDocids(_))
             | (FieldIdFacetExistsDocids(_), FieldIdFacetExistsDocids(_))
             | (FieldIdFacetIsNullDocids(_), FieldIdFacetIsNullDocids(_))
             | (FieldIdFacetIsEmptyDocids(_), FieldIdFacetIsEmptyDocids(_))
+            | (FieldIdFacetBoolDocids(_), FieldIdFacetBoolDocids(_))
             | (GeoPoints(_), GeoPoints(_))
             | (ScriptLanguageDocids(_), ScriptLanguageDocids(_)) => true,
             (
@@ -125,13 +140,17 @@ impl TypedChunk {
 }
 
 /// Write typed chunk in the corresponding LMDB database of the provided index.
-/// Return new documents seen.
+/// Return new documents seen, the ids of the documents written or deleted are added
+/// to `modified_documents`.
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::write_db")]
 pub(crate) fn write_typed_chunk_into_index(
     wtxn: &mut RwTxn<'_>,
     index: &Index,
     settings_diff: &InnerIndexSettingsDiff,
     typed_chunks: Vec<TypedChunk>,
+    fields_stats_delta: &mut FieldsStatsDelta,
+    sort_indexes_delta: &mut NumericSortIndexesDelta,
+    modified_documents: &mut RoaringBitmap,
 ) -> Result<(RoaringBitmap, bool)> {
     let mut is_merged_database = false;
     match typed_chunks[0] {
@@ -155,6 +174,7 @@ pub(crate) fn write_typed_chunk_into_index(
             let mut operations: Vec<DocumentOperation> = Default::default();
 
             let mut docids = index.documents_ids(wtxn)?;
+            let mut fields_schema = index.fields_schema(wtxn)?;
             let mut iter = merger.into_stream_merger_iter()?;
 
             let embedders: BTreeSet<_> = index
@@ -171,10 +191,22 @@ pub(crate) fn write_typed_chunk_into_index(
                     .ok_or(SerializationError::Decoding { db_name: Some(DOCUMENTS) })?;
                 let docid = DocumentId::from_be_bytes(document_id_bytes);
                 let external_id = std::str::from_utf8(external_id_bytes)?;
+                modified_documents.insert(docid);
 
                 for (field_id, value) in reader.iter() {
                     let del_add_reader = KvReaderDelAdd::new(value);
 
+                    if vectors_fid != Some(field_id) {
+                        if let Some(field_name) = fields_ids_map.name(field_id) {
+                            update_field_schema(
+                                &mut fields_schema,
+                                field_name,
+                                del_add_reader.get(DelAdd::Deletion),
+                                del_add_reader.get(DelAdd::Addition),
+                            );
+                        }
+                    }
+
                     if let Some(addition) = del_add_reader.get(DelAdd::Addition) {
                         let addition = if vectors_fid == Some(field_id) {
                             'vectors: {
@@ -220,6 +252,7 @@ pub(crate) fn write_typed_chunk_into_index(
                     docids.insert(docid);
                 } else {
                     db.delete(wtxn, &docid)?;
+                    index.delete_document_attachments(wtxn, docid)?;
                     operations.push(DocumentOperation {
                         external_id: external_id.to_string(),
                         internal_id: docid,
@@ -231,19 +264,27 @@ pub(crate) fn write_typed_chunk_into_index(
             let external_documents_docids = index.external_documents_ids();
             external_documents_docids.apply(wtxn, operations)?;
             index.put_documents_ids(wtxn, &docids)?;
+            index.put_fields_schema(wtxn, &fields_schema)?;
         }
-        TypedChunk::FieldIdWordCountDocids(_) => {
+        TypedChunk::FieldIdWordCountDocids { .. } => {
             let span =
                 tracing::trace_span!(target: "indexing::write_db", "field_id_word_count_docids");
             let _entered = span.enter();
 
             let mut builder = MergerBuilder::new(merge_deladd_cbo_roaring_bitmaps as MergeFn);
             for typed_chunk in typed_chunks {
-                let TypedChunk::FieldIdWordCountDocids(chunk) = typed_chunk else {
+                let TypedChunk::FieldIdWordCountDocids {
+                    fid_word_count_docids_reader,
+                    fields_tokens_delta,
+                } = typed_chunk
+                else {
                     unreachable!();
                 };
 
-                builder.push(chunk.into_cursor()?);
+                for (fid, tokens) in fields_tokens_delta {
+                    fields_stats_delta.record_tokens(fid, tokens);
+                }
+                builder.push(fid_word_count_docids_reader.into_cursor()?);
             }
             let merger = builder.build();
 
@@ -306,12 +347,11 @@ pub(crate) fn write_typed_chunk_into_index(
             )?;
 
             let word_fid_docids_merger = word_fid_docids_builder.build();
-            write_entries_into_database(
+            write_word_fid_entries_into_database(
                 word_fid_docids_merger,
-                &index.word_fid_docids,
+                index,
                 wtxn,
-                deladd_serialize_add_side,
-                merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap,
+                fields_stats_delta,
             )?;
 
             // create fst from word docids
@@ -350,26 +390,84 @@ pub(crate) fn write_typed_chunk_into_index(
             )?;
             is_merged_database = true;
         }
+        TypedChunk::WordFrequencyDocids { .. } => {
+            let span = tracing::trace_span!(target: "indexing::write_db", "word_frequency_docids");
+            let _entered = span.enter();
+
+            let mut word_frequency_docids_builder =
+                MergerBuilder::new(merge_deladd_cbo_roaring_bitmaps as MergeFn);
+            let mut document_length_docids_builder =
+                MergerBuilder::new(merge_deladd_cbo_roaring_bitmaps as MergeFn);
+            for typed_chunk in typed_chunks {
+                let TypedChunk::WordFrequencyDocids {
+                    word_frequency_docids_reader,
+                    document_length_docids_reader,
+                } = typed_chunk
+                else {
+                    unreachable!();
+                };
+
+                word_frequency_docids_builder.push(word_frequency_docids_reader.into_cursor()?);
+                document_length_docids_builder.push(document_length_docids_reader.into_cursor()?);
+            }
+            let word_frequency_docids_merger = word_frequency_docids_builder.build();
+            let document_length_docids_merger = document_length_docids_builder.build();
+
+            write_entries_into_database(
+                word_frequency_docids_merger,
+                &index.word_frequency_docids,
+                wtxn,
+                deladd_serialize_add_side,
+                merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap,
+            )?;
+            write_entries_into_database(
+                document_length_docids_merger,
+                &index.document_length_docids,
+                wtxn,
+                deladd_serialize_add_side,
+                merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap,
+            )?;
+            is_merged_database = true;
+        }
         TypedChunk::FieldIdFacetNumberDocids(_) => {
             let span =
                 tracing::trace_span!(target: "indexing::write_db","field_id_facet_number_docids");
             let _entered = span.enter();
 
             let mut builder = MergerBuilder::new(merge_deladd_cbo_roaring_bitmaps as MergeFn);
+            let mut values_before_builder = MergerBuilder::new(merge_ignore_values as MergeFn);
+            let mut values_after_builder =
+                MergerBuilder::new(merge_deladd_cbo_roaring_bitmaps as MergeFn);
             let mut data_size = 0;
             for typed_chunk in typed_chunks {
                 let TypedChunk::FieldIdFacetNumberDocids(facet_id_number_docids) = typed_chunk
                 else {
                     unreachable!();
                 };
+                let clonable_facet_id_number_docids =
+                    unsafe { as_cloneable_grenad(&facet_id_number_docids) }?;
 
                 data_size += facet_id_number_docids.len();
                 builder.push(facet_id_number_docids.into_cursor()?);
+                values_before_builder.push(clonable_facet_id_number_docids.clone().into_cursor()?);
+                values_after_builder.push(clonable_facet_id_number_docids.into_cursor()?);
             }
             let merger = builder.build();
+            let values_before_merger = values_before_builder.build();
+            let values_after_merger = values_after_builder.build();
+            let database = index.facet_id_f64_docids.remap_types::<Bytes, DecodeIgnore>();
 
+            let existed_before = existing_facet_values(values_before_merger, database, wtxn)?;
             let indexer = FacetsUpdate::new(index, FacetType::Number, merger, None, data_size);
             indexer.execute(wtxn)?;
+            record_facet_values_changes(
+                values_after_merger,
+                database,
+                wtxn,
+                existed_before,
+                fields_stats_delta,
+                Some(sort_indexes_delta),
+            )?;
             is_merged_database = true;
         }
         TypedChunk::FieldIdFacetStringDocids(_) => {
@@ -381,6 +479,9 @@ pub(crate) fn write_typed_chunk_into_index(
                 MergerBuilder::new(merge_deladd_cbo_roaring_bitmaps as MergeFn);
             let mut normalized_facet_id_string_builder =
                 MergerBuilder::new(merge_deladd_btreeset_string as MergeFn);
+            let mut values_before_builder = MergerBuilder::new(merge_ignore_values as MergeFn);
+            let mut values_after_builder =
+                MergerBuilder::new(merge_deladd_cbo_roaring_bitmaps as MergeFn);
             let mut data_size = 0;
             for typed_chunk in typed_chunks {
                 let TypedChunk::FieldIdFacetStringDocids((
@@ -391,13 +492,21 @@ pub(crate) fn write_typed_chunk_into_index(
                     unreachable!();
                 };
 
+                let clonable_facet_id_string_docids =
+                    unsafe { as_cloneable_grenad(&facet_id_string_docids) }?;
+
                 data_size += facet_id_string_docids.len();
                 facet_id_string_builder.push(facet_id_string_docids.into_cursor()?);
                 normalized_facet_id_string_builder
                     .push(normalized_facet_id_string_docids.into_cursor()?);
+                values_before_builder.push(clonable_facet_id_string_docids.clone().into_cursor()?);
+                values_after_builder.push(clonable_facet_id_string_docids.into_cursor()?);
             }
             let facet_id_string_merger = facet_id_string_builder.build();
             let normalized_facet_id_string_merger = normalized_facet_id_string_builder.build();
+            let values_before_merger = values_before_builder.build();
+            let values_after_merger = values_after_builder.build();
+            let database = index.facet_id_string_docids.remap_types::<Bytes, DecodeIgnore>();
 
             let indexer = FacetsUpdate::new(
                 index,
@@ -406,7 +515,16 @@ pub(crate) fn write_typed_chunk_into_index(
                 Some(normalized_facet_id_string_merger),
                 data_size,
             );
+            let existed_before = existing_facet_values(values_before_merger, database, wtxn)?;
             indexer.execute(wtxn)?;
+            record_facet_values_changes(
+                values_after_merger,
+                database,
+                wtxn,
+                existed_before,
+                fields_stats_delta,
+                None,
+            )?;
             is_merged_database = true;
         }
         TypedChunk::FieldIdFacetExistsDocids(_) => {
@@ -480,6 +598,30 @@ pub(crate) fn write_typed_chunk_into_index(
             )?;
             is_merged_database = true;
         }
+        TypedChunk::FieldIdFacetBoolDocids(_) => {
+            let span =
+                tracing::trace_span!(target: "indexing::write_db", "field_id_facet_bool_docids");
+            let _entered = span.enter();
+
+            let mut builder = MergerBuilder::new(merge_deladd_cbo_roaring_bitmaps as MergeFn);
+            for typed_chunk in typed_chunks {
+                let TypedChunk::FieldIdFacetBoolDocids(chunk) = typed_chunk else {
+                    unreachable!();
+                };
+
+                builder.push(chunk.into_cursor()?);
+            }
+            let merger = builder.build();
+
+            write_entries_into_database(
+                merger,
+                &index.facet_id_bool_docids,
+                wtxn,
+                deladd_serialize_add_side,
+                merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap,
+            )?;
+            is_merged_database = true;
+        }
         TypedChunk::WordPairProximityDocids(_) => {
             let span =
                 tracing::trace_span!(target: "indexing::write_db", "word_pair_proximity_docids");
@@ -866,6 +1008,110 @@ where
     Ok(())
 }
 
+/// Akin to the `write_entries_into_database` function but specialized for the
+/// word fid docids, recording the changes of the statistics of the fields.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::write_db")]
+fn write_word_fid_entries_into_database<R>(
+    merger: Merger<R, MergeFn>,
+    index: &Index,
+    wtxn: &mut RwTxn<'_>,
+    fields_stats_delta: &mut FieldsStatsDelta,
+) -> Result<()>
+where
+    R: io::Read + io::Seek,
+{
+    let mut buffer = Vec::new();
+    let database = index.word_fid_docids.remap_types::<Bytes, Bytes>();
+
+    let mut iter = merger.into_stream_merger_iter()?;
+    while let Some((key, value)) = iter.next()? {
+        if valid_lmdb_key(key) {
+            let (_word, fid) = StrBEU16Codec::bytes_decode(key).map_err(heed::Error::Decoding)?;
+            buffer.clear();
+            let (previous_len, value) = match database.get(wtxn, key)? {
+                Some(prev_value) => (
+                    CboRoaringBitmapLenCodec::bytes_decode(prev_value)
+                        .map_err(heed::Error::Decoding)?,
+                    merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap(
+                        value,
+                        prev_value,
+                        &mut buffer,
+                    )?,
+                ),
+                None => (0, Some(deladd_serialize_add_side(value, &mut buffer)?)),
+            };
+            let len = match value {
+                Some(value) => {
+                    database.put(wtxn, key, value)?;
+                    CboRoaringBitmapLenCodec::bytes_decode(value).map_err(heed::Error::Decoding)?
+                }
+                None => {
+                    database.delete(wtxn, key)?;
+                    0
+                }
+            };
+            fields_stats_delta.record_term(fid, previous_len, len);
+        }
+    }
+    Ok(())
+}
+
+/// Returns, for every facet value of the merger, whether it is in the level 0 of the facet database.
+fn existing_facet_values<R>(
+    merger: Merger<R, MergeFn>,
+    database: heed::Database<Bytes, DecodeIgnore>,
+    rtxn: &RoTxn<'_>,
+) -> Result<Vec<bool>>
+where
+    R: io::Read + io::Seek,
+{
+    let mut existing = Vec::new();
+    let mut iter = merger.into_stream_merger_iter()?;
+    while let Some((key, _)) = iter.next()? {
+        existing.push(valid_lmdb_key(key) && database.get(rtxn, key)?.is_some());
+    }
+    Ok(existing)
+}
+
+/// Records the facet values of the merger added to the level 0 of the facet database or removed
+/// from it since `existing_facet_values` was called, and the documents whose values changed.
+fn record_facet_values_changes<R>(
+    merger: Merger<R, MergeFn>,
+    database: heed::Database<Bytes, DecodeIgnore>,
+    rtxn: &RoTxn<'_>,
+    existed_before: Vec<bool>,
+    fields_stats_delta: &mut FieldsStatsDelta,
+    mut sort_indexes_delta: Option<&mut NumericSortIndexesDelta>,
+) -> Result<()>
+where
+    R: io::Read + io::Seek,
+{
+    let mut existed_before = existed_before.into_iter();
+    let mut iter = merger.into_stream_merger_iter()?;
+    while let Some((key, value)) = iter.next()? {
+        let existed_before = existed_before.next().unwrap_or_default();
+        let exists = valid_lmdb_key(key) && database.get(rtxn, key)?.is_some();
+        let (fid, _) =
+            try_split_array_at(key).ok_or(SerializationError::Decoding { db_name: None })?;
+        let fid = FieldId::from_be_bytes(fid);
+        fields_stats_delta.record_facet_values(fid, exists as i64 - existed_before as i64);
+
+        if let Some(sort_indexes_delta) = sort_indexes_delta.as_deref_mut() {
+            if exists != existed_before {
+                sort_indexes_delta.record_values_change(fid);
+            }
+            let value = KvReaderDelAdd::new(value);
+            for side in [DelAdd::Deletion, DelAdd::Addition] {
+                if let Some(docids) = value.get(side) {
+                    let docids = CboRoaringBitmapCodec::deserialize_from(docids)?;
+                    sort_indexes_delta.record_documents(fid, &docids);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Akin to the `write_entries_into_database` function but specialized
 /// for the case when we only index additional searchable fields only.
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::write_db")]
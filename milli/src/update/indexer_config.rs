@@ -1,5 +1,7 @@
 use grenad::CompressionType;
 
+use crate::attachments::AttachmentsQuotas;
+use crate::pii::PiiCipher;
 use crate::thread_pool_no_abort::ThreadPoolNoAbort;
 
 #[derive(Debug)]
@@ -13,6 +15,8 @@ pub struct IndexerConfig {
     pub thread_pool: Option<ThreadPoolNoAbort>,
     pub max_positions_per_attributes: Option<u32>,
     pub skip_index_budget: bool,
+    pub attachments_quotas: AttachmentsQuotas,
+    pub pii_cipher: Option<PiiCipher>,
 }
 
 impl Default for IndexerConfig {
@@ -27,6 +31,8 @@ impl Default for IndexerConfig {
             thread_pool: None,
             max_positions_per_attributes: None,
             skip_index_budget: false,
+            attachments_quotas: AttachmentsQuotas::default(),
+            pii_cipher: None,
         }
     }
 }
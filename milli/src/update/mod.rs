@@ -4,9 +4,11 @@ pub use self::facet::bulk::FacetsUpdateBulk;
 pub use self::facet::incremental::FacetsUpdateIncrementalInner;
 pub use self::index_documents::{
     merge_cbo_roaring_bitmaps, merge_roaring_bitmaps, DocumentAdditionResult, DocumentId,
-    IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod, MergeFn,
+    DocumentsWriteMode, IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod, MergeFn,
 };
 pub use self::indexer_config::IndexerConfig;
+pub use self::rebuild::{DerivedDatabase, RebuildDatabases};
+pub(crate) use self::settings::normalize_synonyms;
 pub use self::settings::{validate_embedding_settings, Setting, Settings};
 pub use self::update_step::UpdateIndexingStep;
 pub use self::word_prefix_docids::WordPrefixDocids;
@@ -17,8 +19,13 @@ mod available_documents_ids;
 mod clear_documents;
 pub(crate) mod del_add;
 pub(crate) mod facet;
+mod fields_stats;
 mod index_documents;
 mod indexer_config;
+mod numeric_sort_index;
+mod phonetic_words;
+mod prefix_cache;
+mod rebuild;
 mod settings;
 mod update_step;
 mod word_prefix_docids;
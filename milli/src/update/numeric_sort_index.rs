@@ -0,0 +1,241 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use heed::types::{Bytes, DecodeIgnore};
+use heed::{RoTxn, RwTxn};
+use roaring::RoaringBitmap;
+
+use crate::heed_codec::facet::{FacetGroupKeyCodec, FieldDocIdFacetF64Codec, OrderedF64Codec};
+use crate::heed_codec::NumericSortIndex;
+use crate::{DocumentId, FieldId, Index, Result};
+
+/// The changes made to the numeric facet values by an indexing operation.
+///
+/// They are recorded while the numeric facets are written into the databases,
+/// so that the dense numeric sort indexes can be updated without being rebuilt.
+#[derive(Debug, Default)]
+pub(crate) struct NumericSortIndexesDelta {
+    fields: BTreeMap<FieldId, NumericSortIndexDelta>,
+}
+
+#[derive(Debug, Default)]
+struct NumericSortIndexDelta {
+    /// The documents whose values changed.
+    docids: RoaringBitmap,
+    /// Whether values were added to the field or removed from it, shifting the ordinals.
+    values_changed: bool,
+}
+
+impl NumericSortIndexesDelta {
+    /// Records documents whose values of the field changed.
+    pub fn record_documents(&mut self, fid: FieldId, docids: &RoaringBitmap) {
+        self.fields.entry(fid).or_default().docids |= docids;
+    }
+
+    /// Records that values were added to the field or removed from it.
+    pub fn record_values_change(&mut self, fid: FieldId) {
+        self.fields.entry(fid).or_default().values_changed = true;
+    }
+}
+
+/// Updates the dense numeric sort index of every dense sortable field with the changes
+/// made by an indexing operation.
+///
+/// The indexes of the fields that are no longer dense sortable are deleted and the missing
+/// ones are computed from scratch. It must be called once the facet databases are up to date.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::numeric_sort_index")]
+pub(crate) fn update_numeric_sort_indexes(
+    index: &Index,
+    wtxn: &mut RwTxn<'_>,
+    mut delta: NumericSortIndexesDelta,
+) -> Result<()> {
+    let fields_ids = dense_sortable_fields_ids(index, wtxn)?;
+    for field_id in index.fields_ids_map(wtxn)?.ids() {
+        if !fields_ids.contains(&field_id) {
+            index.delete_numeric_sort_index(wtxn, field_id)?;
+        }
+    }
+
+    for field_id in fields_ids {
+        let sort_index = match index.numeric_sort_index(wtxn, field_id)? {
+            Some(sort_index) => match delta.fields.remove(&field_id) {
+                Some(field_delta) => {
+                    update_numeric_sort_index(index, wtxn, field_id, sort_index, field_delta)?
+                }
+                None => continue,
+            },
+            None => compute_numeric_sort_index(index, wtxn, field_id)?,
+        };
+        index.put_numeric_sort_index(wtxn, field_id, &sort_index)?;
+    }
+
+    Ok(())
+}
+
+/// Rebuilds the dense numeric sort index of every dense sortable field.
+///
+/// The ordinals are computed from the level 0 of the numeric facet database,
+/// which is why it must be called once the facet databases are up to date.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::numeric_sort_index")]
+pub(crate) fn compute_numeric_sort_indexes(index: &Index, wtxn: &mut RwTxn<'_>) -> Result<()> {
+    index.delete_numeric_sort_indexes(wtxn)?;
+
+    for field_id in dense_sortable_fields_ids(index, wtxn)? {
+        let sort_index = compute_numeric_sort_index(index, wtxn, field_id)?;
+        index.put_numeric_sort_index(wtxn, field_id, &sort_index)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the ids of the dense sortable fields that are faceted.
+fn dense_sortable_fields_ids(index: &Index, rtxn: &RoTxn<'_>) -> Result<BTreeSet<FieldId>> {
+    let dense_sortable_fields = index.dense_sortable_fields(rtxn)?;
+    if dense_sortable_fields.is_empty() {
+        return Ok(BTreeSet::new());
+    }
+
+    let fields_ids_map = index.fields_ids_map(rtxn)?;
+    let faceted_fields_ids = index.faceted_fields_ids(rtxn)?;
+    Ok(dense_sortable_fields
+        .iter()
+        .filter_map(|name| fields_ids_map.id(name))
+        .filter(|field_id| faceted_fields_ids.contains(field_id))
+        .collect())
+}
+
+fn compute_numeric_sort_index(
+    index: &Index,
+    rtxn: &RoTxn<'_>,
+    field_id: FieldId,
+) -> Result<NumericSortIndex> {
+    let documents_len = index.documents_ids(rtxn)?.max().map_or(0, |max| max as usize + 1);
+    let mut sort_index = NumericSortIndex {
+        values: Vec::new(),
+        min_ordinals: vec![NumericSortIndex::MISSING; documents_len],
+        max_ordinals: vec![NumericSortIndex::MISSING; documents_len],
+    };
+
+    // the level 0 of the facet database contains exactly one entry per facet value
+    let mut prefix = field_id.to_be_bytes().to_vec();
+    prefix.push(0);
+    let iter = index
+        .facet_id_f64_docids
+        .remap_key_type::<Bytes>()
+        .prefix_iter(rtxn, &prefix)?
+        .remap_key_type::<FacetGroupKeyCodec<OrderedF64Codec>>();
+    for result in iter {
+        let (key, value) = result?;
+
+        // the values are iterated in ascending order
+        let ordinal = sort_index.values.len() as u32;
+        sort_index.values.push(key.left_bound);
+        for docid in value.bitmap {
+            let docid = docid as usize;
+            if sort_index.min_ordinals[docid] == NumericSortIndex::MISSING {
+                sort_index.min_ordinals[docid] = ordinal;
+            }
+            sort_index.max_ordinals[docid] = ordinal;
+        }
+    }
+
+    Ok(sort_index)
+}
+
+/// Applies the changes of the numeric values of a field to its sort index.
+///
+/// When values were added or removed, the ordinals of all the documents are shifted,
+/// then the ordinals of the documents whose values changed are read from their facet values.
+fn update_numeric_sort_index(
+    index: &Index,
+    rtxn: &RoTxn<'_>,
+    field_id: FieldId,
+    mut sort_index: NumericSortIndex,
+    delta: NumericSortIndexDelta,
+) -> Result<NumericSortIndex> {
+    if delta.values_changed {
+        let values = field_values(index, rtxn, field_id)?;
+
+        // the ordinals of the removed values are only used by the documents that changed
+        let mut new_ordinal = 0;
+        let ordinals: Vec<u32> = sort_index
+            .values
+            .iter()
+            .map(|value| {
+                new_ordinal += values[new_ordinal..].partition_point(|v| v < value);
+                match values.get(new_ordinal) {
+                    Some(v) if v == value => new_ordinal as u32,
+                    _ => NumericSortIndex::MISSING,
+                }
+            })
+            .collect();
+        for ordinal in sort_index.min_ordinals.iter_mut().chain(&mut sort_index.max_ordinals) {
+            if *ordinal != NumericSortIndex::MISSING {
+                *ordinal = ordinals[*ordinal as usize];
+            }
+        }
+        sort_index.values = values;
+    }
+
+    let documents_len = delta.docids.max().map_or(0, |max| max as usize + 1);
+    if sort_index.min_ordinals.len() < documents_len {
+        sort_index.min_ordinals.resize(documents_len, NumericSortIndex::MISSING);
+        sort_index.max_ordinals.resize(documents_len, NumericSortIndex::MISSING);
+    }
+
+    for docid in delta.docids {
+        let (min, max) = document_ordinals(index, rtxn, field_id, docid, &sort_index.values)?;
+        sort_index.min_ordinals[docid as usize] = min;
+        sort_index.max_ordinals[docid as usize] = max;
+    }
+
+    Ok(sort_index)
+}
+
+/// Returns the numeric values of the field, in ascending order.
+fn field_values(index: &Index, rtxn: &RoTxn<'_>, field_id: FieldId) -> Result<Vec<f64>> {
+    // the level 0 of the facet database contains exactly one entry per facet value
+    let mut prefix = field_id.to_be_bytes().to_vec();
+    prefix.push(0);
+    let iter = index
+        .facet_id_f64_docids
+        .remap_types::<Bytes, DecodeIgnore>()
+        .prefix_iter(rtxn, &prefix)?
+        .remap_key_type::<FacetGroupKeyCodec<OrderedF64Codec>>();
+    iter.map(|result| Ok(result?.0.left_bound)).collect()
+}
+
+/// Returns the ordinals of the smallest and the biggest values of the field in the document.
+fn document_ordinals(
+    index: &Index,
+    rtxn: &RoTxn<'_>,
+    field_id: FieldId,
+    docid: DocumentId,
+    values: &[f64],
+) -> Result<(u32, u32)> {
+    let mut prefix = field_id.to_be_bytes().to_vec();
+    prefix.extend_from_slice(&docid.to_be_bytes());
+    let iter = index
+        .field_id_docid_facet_f64s
+        .remap_key_type::<Bytes>()
+        .prefix_iter(rtxn, &prefix)?
+        .remap_key_type::<FieldDocIdFacetF64Codec>();
+
+    let ordinal = |value: f64| {
+        let ordinal = values.partition_point(|v| *v < value);
+        match values.get(ordinal) {
+            Some(v) if *v == value => ordinal as u32,
+            _ => NumericSortIndex::MISSING,
+        }
+    };
+
+    // the values of the document are iterated in ascending order
+    let mut ordinals = (NumericSortIndex::MISSING, NumericSortIndex::MISSING);
+    for result in iter {
+        let ((_, _, value), ()) = result?;
+        if ordinals.0 == NumericSortIndex::MISSING {
+            ordinals.0 = ordinal(value);
+        }
+        ordinals.1 = ordinal(value);
+    }
+    Ok(ordinals)
+}
@@ -0,0 +1,20 @@
+use heed::RwTxn;
+
+use crate::phonetic::build_phonetic_fst;
+use crate::{Index, Result};
+
+/// Rebuilds the phonetic FST from the words dictionary of the index, or deletes it
+/// when the phonetic matching is disabled.
+///
+/// It must be called once the words FST is up to date.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::phonetic")]
+pub(crate) fn compute_phonetic_words_fst(index: &Index, wtxn: &mut RwTxn<'_>) -> Result<()> {
+    if !index.enable_phonetic(wtxn)? {
+        index.delete_phonetic_words_fst(wtxn)?;
+        return Ok(());
+    }
+
+    let phonetic_fst = build_phonetic_fst(&index.words_fst(wtxn)?)?;
+    index.put_phonetic_words_fst(wtxn, &phonetic_fst)?;
+    Ok(())
+}
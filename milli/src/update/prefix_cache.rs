@@ -0,0 +1,113 @@
+use fst::Streamer;
+use heed::RwTxn;
+use roaring::RoaringBitmap;
+
+use crate::heed_codec::CboRoaringBitmapLenCodec;
+use crate::{Criterion, Index, Result, Search, TimeBudget};
+
+/// The maximum number of prefixes for which the results are precomputed.
+const MAX_CACHED_PREFIXES: usize = 100;
+
+/// The maximum length, in chars, of the prefixes for which the results are precomputed.
+const MAX_CACHED_PREFIX_LEN: usize = 2;
+
+/// Precomputes the best results of the most frequent short prefixes,
+/// so that the first keystrokes of an as-you-type search don't have to
+/// run the bucket sort on a very large number of candidates.
+///
+/// It must be called once all the other databases are up to date,
+/// as the cached results are computed by running a search on the index.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::prefix_cache")]
+pub(crate) fn compute_prefix_cache(index: &Index, wtxn: &mut RwTxn<'_>) -> Result<()> {
+    write_prefix_cache(index, wtxn, None)
+}
+
+/// Updates the prefix cache after an indexing that only modified the given documents.
+///
+/// The results of a prefix are only searched again when the prefix matches one of these
+/// documents, or when they were part of its cached results.
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::prefix_cache")]
+pub(crate) fn update_prefix_cache(
+    index: &Index,
+    wtxn: &mut RwTxn<'_>,
+    modified_documents: &RoaringBitmap,
+) -> Result<()> {
+    write_prefix_cache(index, wtxn, Some(modified_documents))
+}
+
+fn write_prefix_cache(
+    index: &Index,
+    wtxn: &mut RwTxn<'_>,
+    modified_documents: Option<&RoaringBitmap>,
+) -> Result<()> {
+    let size = match index.prefix_cache_size(wtxn)? {
+        Some(size) if size > 0 => size as usize,
+        _ => {
+            index.delete_prefix_cache(wtxn)?;
+            return Ok(());
+        }
+    };
+
+    // the bm25 scores and the synonyms make the results of a prefix
+    // depend on documents that don't contain it
+    let modified_documents = match modified_documents {
+        Some(modified_documents)
+            if !index.criteria(wtxn)?.contains(&Criterion::Bm25)
+                && index.synonyms(wtxn)?.is_empty() =>
+        {
+            Some(modified_documents)
+        }
+        _ => None,
+    };
+
+    // the shorter prefixes are searched as words and never use the cache
+    let min_prefix_length = index.min_prefix_length(wtxn)? as usize;
+    let word_prefix_docids = index.word_prefix_docids.remap_data_type::<CboRoaringBitmapLenCodec>();
+    let mut prefixes = Vec::new();
+    {
+        let words_prefixes_fst = index.words_prefixes_fst(wtxn)?;
+        let mut stream = words_prefixes_fst.stream();
+        while let Some(bytes) = stream.next() {
+            let Ok(prefix) = std::str::from_utf8(bytes) else { continue };
+            let len = prefix.chars().count();
+            if len > MAX_CACHED_PREFIX_LEN || len < min_prefix_length {
+                continue;
+            }
+            let count = word_prefix_docids.get(wtxn, prefix)?.unwrap_or_default();
+            prefixes.push((count, prefix.to_string()));
+        }
+    }
+
+    // the most frequent prefixes are the most expensive ones to search for
+    prefixes.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+    prefixes.truncate(MAX_CACHED_PREFIXES);
+
+    let mut entries = Vec::with_capacity(prefixes.len());
+    for (_, prefix) in prefixes {
+        if let Some(modified_documents) = modified_documents {
+            if let Some(cached) = index.prefix_cache_entry(wtxn, &prefix)? {
+                let prefix_docids =
+                    index.word_prefix_docids.get(wtxn, &prefix)?.unwrap_or_default();
+                if prefix_docids.is_disjoint(modified_documents)
+                    && cached.iter().all(|docid| !modified_documents.contains(*docid))
+                {
+                    entries.push((prefix, cached));
+                    continue;
+                }
+            }
+        }
+
+        let mut search = Search::new(wtxn, index);
+        search.query(prefix.as_str()).limit(size).time_budget(TimeBudget::max());
+        let result = search.execute()?;
+        entries.push((prefix, result.documents_ids));
+    }
+
+    // the prefixes that are not frequent enough anymore are removed from the cache
+    index.delete_prefix_cache(wtxn)?;
+    for (prefix, docids) in entries {
+        index.put_prefix_cache_entry(wtxn, &prefix, &docids)?;
+    }
+
+    Ok(())
+}
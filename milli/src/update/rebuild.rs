@@ -0,0 +1,191 @@
+//! Rebuilds the databases of an index that are entirely derived from the other ones.
+//!
+//! When a settings change only affects one of these structures, or when one of them must
+//! be compacted after many small updates, it can be rebuilt on its own without reindexing
+//! the documents.
+
+use std::collections::BTreeSet;
+
+use deserr::Deserr;
+use grenad::MergerBuilder;
+use heed::RwTxn;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, InternalError};
+use crate::facet::FacetType;
+use crate::update::index_documents::merge_deladd_cbo_roaring_bitmaps;
+use crate::update::numeric_sort_index::compute_numeric_sort_indexes;
+use crate::update::phonetic_words::compute_phonetic_words_fst;
+use crate::update::prefix_cache::compute_prefix_cache;
+use crate::update::{
+    FacetsUpdateBulk, IndexDocuments, IndexDocumentsConfig, IndexerConfig, MergeFn,
+};
+use crate::{Index, Result};
+
+/// A database that can be rebuilt from the other databases of the index.
+///
+/// The variants are declared in the order they must be rebuilt in.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Deserr,
+)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum DerivedDatabase {
+    /// The words prefixes FST and the prefix databases, computed from the words databases.
+    Prefixes,
+    /// The upper levels of the facet databases, computed from their level 0.
+    Facets,
+    /// The dense numeric sort indexes, computed from the level 0 of the numeric facets.
+    SortIndexes,
+    /// The phonetic FST, computed from the words FST.
+    Phonetic,
+    /// The cached results of the most frequent prefixes.
+    PrefixCache,
+}
+
+pub struct RebuildDatabases<'t, 'i, 'a> {
+    wtxn: &'t mut RwTxn<'i>,
+    index: &'i Index,
+    indexer_config: &'a IndexerConfig,
+    databases: BTreeSet<DerivedDatabase>,
+}
+
+impl<'t, 'i, 'a> RebuildDatabases<'t, 'i, 'a> {
+    pub fn new(
+        wtxn: &'t mut RwTxn<'i>,
+        index: &'i Index,
+        indexer_config: &'a IndexerConfig,
+        databases: BTreeSet<DerivedDatabase>,
+    ) -> RebuildDatabases<'t, 'i, 'a> {
+        RebuildDatabases { wtxn, index, indexer_config, databases }
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "indexing::rebuild")]
+    pub fn execute<FA>(self, should_abort: FA) -> Result<()>
+    where
+        FA: Fn() -> bool + Sync + Send,
+    {
+        let RebuildDatabases { wtxn, index, indexer_config, databases } = self;
+
+        for database in databases {
+            if should_abort() {
+                return Err(Error::InternalError(InternalError::AbortedIndexation));
+            }
+
+            match database {
+                DerivedDatabase::Prefixes => {
+                    rebuild_prefixes(wtxn, index, indexer_config, &should_abort)?
+                }
+                DerivedDatabase::Facets => rebuild_facet_levels(wtxn, index)?,
+                DerivedDatabase::SortIndexes => compute_numeric_sort_indexes(index, wtxn)?,
+                DerivedDatabase::Phonetic => compute_phonetic_words_fst(index, wtxn)?,
+                DerivedDatabase::PrefixCache => compute_prefix_cache(index, wtxn)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Clears the prefix databases and computes them again as if all the prefixes were new.
+fn rebuild_prefixes<'i, FA>(
+    wtxn: &mut RwTxn<'i>,
+    index: &'i Index,
+    indexer_config: &IndexerConfig,
+    should_abort: &FA,
+) -> Result<()>
+where
+    FA: Fn() -> bool + Sync + Send,
+{
+    index.put_words_prefixes_fst(wtxn, &fst::Set::default())?;
+    index.word_prefix_docids.clear(wtxn)?;
+    index.exact_word_prefix_docids.clear(wtxn)?;
+    index.word_prefix_position_docids.clear(wtxn)?;
+    index.word_prefix_fid_docids.clear(wtxn)?;
+
+    // the docids of the new prefixes are read from the words databases,
+    // there is no change to merge into the existing prefixes
+    let no_changes =
+        || Some(MergerBuilder::new(merge_deladd_cbo_roaring_bitmaps as MergeFn).build());
+    let builder = IndexDocuments::new(
+        wtxn,
+        index,
+        indexer_config,
+        IndexDocumentsConfig::default(),
+        |_| (),
+        should_abort,
+    )?;
+    builder.execute_prefix_databases(no_changes(), no_changes(), no_changes(), no_changes())
+}
+
+/// Recomputes the upper levels of the facet databases of every faceted field.
+fn rebuild_facet_levels(wtxn: &mut RwTxn<'_>, index: &Index) -> Result<()> {
+    let mut field_ids: Vec<_> = index.faceted_fields_ids(wtxn)?.into_iter().collect();
+    field_ids.sort_unstable();
+
+    for facet_type in [FacetType::String, FacetType::Number] {
+        FacetsUpdateBulk::new_not_updating_level_0(index, field_ids.clone(), facet_type)
+            .execute(wtxn)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use big_s::S;
+    use heed::RoTxn;
+    use serde_json::{json, Value};
+
+    use super::{DerivedDatabase, RebuildDatabases};
+    use crate::documents::documents_batch_reader_from_objects;
+    use crate::index::tests::TempIndex;
+    use crate::Index;
+
+    fn prefixes_content(index: &Index, rtxn: &RoTxn<'_>) -> Vec<(String, Vec<u32>)> {
+        let words_prefixes_fst = index.words_prefixes_fst(rtxn).unwrap();
+        let mut content = Vec::new();
+        for prefix in words_prefixes_fst.stream().into_strs().unwrap() {
+            let docids = index.word_prefix_docids.get(rtxn, &prefix).unwrap().unwrap_or_default();
+            content.push((prefix, docids.into_iter().collect()));
+        }
+        content
+    }
+
+    #[test]
+    fn rebuild_the_prefixes_and_the_facets() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|s| s.set_filterable_fields([S("genre")].into_iter().collect()))
+            .unwrap();
+        // enough words sharing the same prefixes for them to be part of the prefixes FST
+        let documents = (0..120u8).filter_map(|i| {
+            let word = format!("help{}{}", (b'a' + i / 26) as char, (b'a' + i % 26) as char);
+            match json!({ "id": i, "title": word, "genre": i % 3 }) {
+                Value::Object(object) => Some(object),
+                _ => None,
+            }
+        });
+        index.add_documents(documents_batch_reader_from_objects(documents)).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let prefixes = prefixes_content(&index, &rtxn);
+        assert!(!prefixes.is_empty());
+        let facets = index.facet_id_f64_docids.len(&rtxn).unwrap();
+        drop(rtxn);
+
+        let mut wtxn = index.write_txn().unwrap();
+        index.word_prefix_docids.clear(&mut wtxn).unwrap();
+        let databases = BTreeSet::from([DerivedDatabase::Facets, DerivedDatabase::Prefixes]);
+        RebuildDatabases::new(&mut wtxn, &index, &index.indexer_config, databases)
+            .execute(|| false)
+            .unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(prefixes_content(&index, &rtxn), prefixes);
+        assert_eq!(index.facet_id_f64_docids.len(&rtxn).unwrap(), facets);
+    }
+}
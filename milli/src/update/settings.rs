@@ -12,14 +12,33 @@ use time::OffsetDateTime;
 
 use super::del_add::DelAddOperation;
 use super::index_documents::{IndexDocumentsConfig, Transform};
+use super::numeric_sort_index::compute_numeric_sort_indexes;
+use super::phonetic_words::compute_phonetic_words_fst;
+use super::prefix_cache::compute_prefix_cache;
 use super::IndexerConfig;
+use crate::acl::RESERVED_ACL_FIELD_NAME;
+use crate::batch_limits::BatchLimits;
+use crate::blocked_terms::BlockedTermsMode;
+use crate::chunking::Chunking;
+use crate::collation::{CollationLocale, SortCollation};
+use crate::computed_fields::ComputedFields;
 use crate::criterion::Criterion;
+use crate::document_hook::{DocumentHook, DocumentHookRunner, DEFAULT_DOCUMENT_HOOK_FUEL};
 use crate::error::UserError;
 use crate::index::{
     IndexEmbeddingConfig, DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
 };
+use crate::language_preset::LanguagePreset;
+use crate::new_fields::NewFieldsPolicy;
 use crate::order_by_map::OrderByMap;
+use crate::position_decay::PositionDecay;
+use crate::prepared_filter::validate_prepared_filter;
 use crate::proximity::ProximityPrecision;
+use crate::query_limits::QueryLimits;
+use crate::query_rewrites::QueryRewriteRule;
+use crate::regex_filter::RegexFilter;
+use crate::tie_breaker::TieBreaker;
+use crate::typo::{FirstLetterTypos, HalfTypos, KeyboardLayout, TypoCosts};
 use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
 use crate::vector::parsed_vectors::RESERVED_VECTORS_FIELD_NAME;
@@ -28,7 +47,7 @@ use crate::vector::settings::{
     WriteBackToDocuments,
 };
 use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
-use crate::{FieldId, FieldsIdsMap, Index, Result};
+use crate::{FieldId, FieldsIdsMap, Index, Result, Weight};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -136,15 +155,20 @@ pub struct Settings<'a, 't, 'i> {
     indexer_config: &'a IndexerConfig,
 
     searchable_fields: Setting<Vec<String>>,
+    /// The weights of the searchable fields, only used when the searchable fields are set.
+    searchable_weights: BTreeMap<String, Weight>,
     displayed_fields: Setting<Vec<String>>,
     filterable_fields: Setting<HashSet<String>>,
     sortable_fields: Setting<HashSet<String>>,
+    dense_sortable_fields: Setting<HashSet<String>>,
     criteria: Setting<Vec<Criterion>>,
     stop_words: Setting<BTreeSet<String>>,
     non_separator_tokens: Setting<BTreeSet<String>>,
     separator_tokens: Setting<BTreeSet<String>>,
     dictionary: Setting<BTreeSet<String>>,
+    literal_words: Setting<BTreeSet<String>>,
     distinct_field: Setting<String>,
+    version_field: Setting<String>,
     synonyms: Setting<BTreeMap<String, Vec<String>>>,
     primary_key: Setting<String>,
     authorize_typos: Setting<bool>,
@@ -153,12 +177,52 @@ pub struct Settings<'a, 't, 'i> {
     exact_words: Setting<BTreeSet<String>>,
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
+    presence_only_attributes: Setting<HashSet<String>>,
+    chunking_max_words: Setting<usize>,
+    chunking_overlap: Setting<usize>,
+    document_hook_module: Setting<Vec<u8>>,
+    document_hook_fuel: Setting<u64>,
+    computed_fields: Setting<BTreeMap<String, String>>,
+    prepared_filters: Setting<BTreeMap<String, String>>,
+    pii_attributes: Setting<BTreeSet<String>>,
+    query_max_length: Setting<usize>,
+    query_max_terms: Setting<usize>,
+    query_max_phrases: Setting<usize>,
+    regex_filter_enabled: Setting<bool>,
+    regex_filter_max_candidates: Setting<usize>,
+    half_typos_keyboard_layout: Setting<KeyboardLayout>,
+    half_typos_on_accents: Setting<bool>,
+    disable_typos_on_numbers: Setting<bool>,
+    first_letter_typos: Setting<FirstLetterTypos>,
+    typo_cost_one_typo: Setting<u32>,
+    typo_cost_two_typos: Setting<u32>,
+    typo_cost_split: Setting<u32>,
+    enable_phonetic: Setting<bool>,
     max_values_per_facet: Setting<usize>,
     sort_facet_values_by: Setting<OrderByMap>,
+    /// The facet values merged under each displayed value, by field.
+    facet_value_aliases: Setting<BTreeMap<String, BTreeMap<String, Vec<String>>>>,
     pagination_max_total_hits: Setting<usize>,
     proximity_precision: Setting<ProximityPrecision>,
     embedder_settings: Setting<BTreeMap<String, Setting<EmbeddingSettings>>>,
     search_cutoff: Setting<u64>,
+    prefix_cache_size: Setting<usize>,
+    long_query_threshold: Setting<usize>,
+    quote_characters: Setting<BTreeSet<String>>,
+    language_preset: Setting<LanguagePreset>,
+    field_languages: Setting<BTreeMap<String, LanguagePreset>>,
+    min_prefix_length: Setting<usize>,
+    blocked_terms: Setting<BTreeSet<String>>,
+    blocked_terms_mode: Setting<BlockedTermsMode>,
+    new_fields_searchable: Setting<bool>,
+    new_fields_filterable: Setting<bool>,
+    query_rewrites: Setting<Vec<QueryRewriteRule>>,
+    position_decay: Setting<PositionDecay>,
+    max_batch_size: Setting<u64>,
+    max_batch_payload: Setting<u64>,
+    collation_locale: Setting<CollationLocale>,
+    collation_attributes: Setting<BTreeMap<String, CollationLocale>>,
+    tie_breaker: Setting<TieBreaker>,
 }
 
 impl<'a, 't, 'i> Settings<'a, 't, 'i> {
@@ -171,15 +235,19 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             wtxn,
             index,
             searchable_fields: Setting::NotSet,
+            searchable_weights: BTreeMap::new(),
             displayed_fields: Setting::NotSet,
             filterable_fields: Setting::NotSet,
             sortable_fields: Setting::NotSet,
+            dense_sortable_fields: Setting::NotSet,
             criteria: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
             separator_tokens: Setting::NotSet,
             dictionary: Setting::NotSet,
+            literal_words: Setting::NotSet,
             distinct_field: Setting::NotSet,
+            version_field: Setting::NotSet,
             synonyms: Setting::NotSet,
             primary_key: Setting::NotSet,
             authorize_typos: Setting::NotSet,
@@ -187,22 +255,75 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             min_word_len_two_typos: Setting::NotSet,
             min_word_len_one_typo: Setting::NotSet,
             exact_attributes: Setting::NotSet,
+            presence_only_attributes: Setting::NotSet,
+            chunking_max_words: Setting::NotSet,
+            chunking_overlap: Setting::NotSet,
+            document_hook_module: Setting::NotSet,
+            document_hook_fuel: Setting::NotSet,
+            computed_fields: Setting::NotSet,
+            prepared_filters: Setting::NotSet,
+            pii_attributes: Setting::NotSet,
+            query_max_length: Setting::NotSet,
+            query_max_terms: Setting::NotSet,
+            query_max_phrases: Setting::NotSet,
+            regex_filter_enabled: Setting::NotSet,
+            regex_filter_max_candidates: Setting::NotSet,
+            half_typos_keyboard_layout: Setting::NotSet,
+            half_typos_on_accents: Setting::NotSet,
+            disable_typos_on_numbers: Setting::NotSet,
+            first_letter_typos: Setting::NotSet,
+            typo_cost_one_typo: Setting::NotSet,
+            typo_cost_two_typos: Setting::NotSet,
+            typo_cost_split: Setting::NotSet,
+            enable_phonetic: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
             sort_facet_values_by: Setting::NotSet,
+            facet_value_aliases: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
             proximity_precision: Setting::NotSet,
             embedder_settings: Setting::NotSet,
             search_cutoff: Setting::NotSet,
+            prefix_cache_size: Setting::NotSet,
+            long_query_threshold: Setting::NotSet,
+            quote_characters: Setting::NotSet,
+            language_preset: Setting::NotSet,
+            field_languages: Setting::NotSet,
+            min_prefix_length: Setting::NotSet,
+            blocked_terms: Setting::NotSet,
+            blocked_terms_mode: Setting::NotSet,
+            new_fields_searchable: Setting::NotSet,
+            new_fields_filterable: Setting::NotSet,
+            query_rewrites: Setting::NotSet,
+            position_decay: Setting::NotSet,
+            max_batch_size: Setting::NotSet,
+            max_batch_payload: Setting::NotSet,
+            collation_locale: Setting::NotSet,
+            collation_attributes: Setting::NotSet,
+            tie_breaker: Setting::NotSet,
             indexer_config,
         }
     }
 
     pub fn reset_searchable_fields(&mut self) {
         self.searchable_fields = Setting::Reset;
+        self.searchable_weights.clear();
     }
 
     pub fn set_searchable_fields(&mut self, names: Vec<String>) {
         self.searchable_fields = Setting::Set(names);
+        self.searchable_weights.clear();
+    }
+
+    /// Sets the searchable fields along with the weights scaling their importance in the
+    /// attribute ranking rule, the fields without any weight have a weight of 1.
+    pub fn set_weighted_searchable_fields(&mut self, fields: Vec<(String, Option<Weight>)>) {
+        // only the first occurrence of a field is taken into account
+        self.searchable_weights = fields
+            .iter()
+            .rev()
+            .filter_map(|(name, weight)| Some((name.clone(), (*weight)?)))
+            .collect();
+        self.searchable_fields = Setting::Set(fields.into_iter().map(|(name, _)| name).collect());
     }
 
     pub fn reset_displayed_fields(&mut self) {
@@ -279,6 +400,23 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             if dictionary.is_empty() { Setting::Reset } else { Setting::Set(dictionary) }
     }
 
+    pub fn reset_literal_words(&mut self) {
+        self.literal_words = Setting::Reset;
+    }
+
+    pub fn set_literal_words(&mut self, literal_words: BTreeSet<String>) {
+        self.literal_words =
+            if literal_words.is_empty() { Setting::Reset } else { Setting::Set(literal_words) }
+    }
+
+    pub fn reset_dense_sortable_fields(&mut self) {
+        self.dense_sortable_fields = Setting::Reset;
+    }
+
+    pub fn set_dense_sortable_fields(&mut self, names: HashSet<String>) {
+        self.dense_sortable_fields = Setting::Set(names);
+    }
+
     pub fn reset_distinct_field(&mut self) {
         self.distinct_field = Setting::Reset;
     }
@@ -287,6 +425,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.distinct_field = Setting::Set(distinct_field);
     }
 
+    pub fn reset_version_field(&mut self) {
+        self.version_field = Setting::Reset;
+    }
+
+    pub fn set_version_field(&mut self, version_field: String) {
+        self.version_field = Setting::Set(version_field);
+    }
+
     pub fn reset_synonyms(&mut self) {
         self.synonyms = Setting::Reset;
     }
@@ -343,6 +489,174 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.exact_attributes = Setting::Reset;
     }
 
+    pub fn set_presence_only_attributes(&mut self, attrs: HashSet<String>) {
+        self.presence_only_attributes = Setting::Set(attrs);
+    }
+
+    pub fn reset_presence_only_attributes(&mut self) {
+        self.presence_only_attributes = Setting::Reset;
+    }
+
+    pub fn set_chunking_max_words(&mut self, max_words: usize) {
+        self.chunking_max_words = Setting::Set(max_words);
+    }
+
+    pub fn reset_chunking_max_words(&mut self) {
+        self.chunking_max_words = Setting::Reset;
+    }
+
+    pub fn set_chunking_overlap(&mut self, overlap: usize) {
+        self.chunking_overlap = Setting::Set(overlap);
+    }
+
+    pub fn reset_chunking_overlap(&mut self) {
+        self.chunking_overlap = Setting::Reset;
+    }
+
+    pub fn set_document_hook_module(&mut self, module: Vec<u8>) {
+        self.document_hook_module = Setting::Set(module);
+    }
+
+    pub fn reset_document_hook_module(&mut self) {
+        self.document_hook_module = Setting::Reset;
+    }
+
+    pub fn set_document_hook_fuel(&mut self, fuel: u64) {
+        self.document_hook_fuel = Setting::Set(fuel);
+    }
+
+    pub fn reset_document_hook_fuel(&mut self) {
+        self.document_hook_fuel = Setting::Reset;
+    }
+
+    pub fn set_computed_fields(&mut self, computed_fields: BTreeMap<String, String>) {
+        self.computed_fields = Setting::Set(computed_fields);
+    }
+
+    pub fn reset_computed_fields(&mut self) {
+        self.computed_fields = Setting::Reset;
+    }
+
+    pub fn set_prepared_filters(&mut self, prepared_filters: BTreeMap<String, String>) {
+        self.prepared_filters = Setting::Set(prepared_filters);
+    }
+
+    pub fn reset_prepared_filters(&mut self) {
+        self.prepared_filters = Setting::Reset;
+    }
+
+    pub fn set_pii_attributes(&mut self, pii_attributes: BTreeSet<String>) {
+        self.pii_attributes = Setting::Set(pii_attributes);
+    }
+
+    pub fn reset_pii_attributes(&mut self) {
+        self.pii_attributes = Setting::Reset;
+    }
+
+    pub fn set_query_max_length(&mut self, max_length: usize) {
+        self.query_max_length = Setting::Set(max_length);
+    }
+
+    pub fn reset_query_max_length(&mut self) {
+        self.query_max_length = Setting::Reset;
+    }
+
+    pub fn set_query_max_terms(&mut self, max_terms: usize) {
+        self.query_max_terms = Setting::Set(max_terms);
+    }
+
+    pub fn reset_query_max_terms(&mut self) {
+        self.query_max_terms = Setting::Reset;
+    }
+
+    pub fn set_query_max_phrases(&mut self, max_phrases: usize) {
+        self.query_max_phrases = Setting::Set(max_phrases);
+    }
+
+    pub fn reset_query_max_phrases(&mut self) {
+        self.query_max_phrases = Setting::Reset;
+    }
+
+    pub fn set_regex_filter_enabled(&mut self, enabled: bool) {
+        self.regex_filter_enabled = Setting::Set(enabled);
+    }
+
+    pub fn reset_regex_filter_enabled(&mut self) {
+        self.regex_filter_enabled = Setting::Reset;
+    }
+
+    pub fn set_regex_filter_max_candidates(&mut self, max_candidates: usize) {
+        self.regex_filter_max_candidates = Setting::Set(max_candidates);
+    }
+
+    pub fn reset_regex_filter_max_candidates(&mut self) {
+        self.regex_filter_max_candidates = Setting::Reset;
+    }
+
+    pub fn set_half_typos_keyboard_layout(&mut self, layout: KeyboardLayout) {
+        self.half_typos_keyboard_layout = Setting::Set(layout);
+    }
+
+    pub fn reset_half_typos_keyboard_layout(&mut self) {
+        self.half_typos_keyboard_layout = Setting::Reset;
+    }
+
+    pub fn set_half_typos_on_accents(&mut self, value: bool) {
+        self.half_typos_on_accents = Setting::Set(value);
+    }
+
+    pub fn reset_half_typos_on_accents(&mut self) {
+        self.half_typos_on_accents = Setting::Reset;
+    }
+
+    pub fn set_disable_typos_on_numbers(&mut self, value: bool) {
+        self.disable_typos_on_numbers = Setting::Set(value);
+    }
+
+    pub fn reset_disable_typos_on_numbers(&mut self) {
+        self.disable_typos_on_numbers = Setting::Reset;
+    }
+
+    pub fn set_first_letter_typos(&mut self, value: FirstLetterTypos) {
+        self.first_letter_typos = Setting::Set(value);
+    }
+
+    pub fn reset_first_letter_typos(&mut self) {
+        self.first_letter_typos = Setting::Reset;
+    }
+
+    pub fn set_typo_cost_one_typo(&mut self, cost: u32) {
+        self.typo_cost_one_typo = Setting::Set(cost);
+    }
+
+    pub fn reset_typo_cost_one_typo(&mut self) {
+        self.typo_cost_one_typo = Setting::Reset;
+    }
+
+    pub fn set_typo_cost_two_typos(&mut self, cost: u32) {
+        self.typo_cost_two_typos = Setting::Set(cost);
+    }
+
+    pub fn reset_typo_cost_two_typos(&mut self) {
+        self.typo_cost_two_typos = Setting::Reset;
+    }
+
+    pub fn set_typo_cost_split(&mut self, cost: u32) {
+        self.typo_cost_split = Setting::Set(cost);
+    }
+
+    pub fn reset_typo_cost_split(&mut self) {
+        self.typo_cost_split = Setting::Reset;
+    }
+
+    pub fn set_enable_phonetic(&mut self, value: bool) {
+        self.enable_phonetic = Setting::Set(value);
+    }
+
+    pub fn reset_enable_phonetic(&mut self) {
+        self.enable_phonetic = Setting::Reset;
+    }
+
     pub fn set_max_values_per_facet(&mut self, value: usize) {
         self.max_values_per_facet = Setting::Set(value);
     }
@@ -359,6 +673,17 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.sort_facet_values_by = Setting::Reset;
     }
 
+    pub fn set_facet_value_aliases(
+        &mut self,
+        value: BTreeMap<String, BTreeMap<String, Vec<String>>>,
+    ) {
+        self.facet_value_aliases = Setting::Set(value);
+    }
+
+    pub fn reset_facet_value_aliases(&mut self) {
+        self.facet_value_aliases = Setting::Reset;
+    }
+
     pub fn set_pagination_max_total_hits(&mut self, value: usize) {
         self.pagination_max_total_hits = Setting::Set(value);
     }
@@ -391,6 +716,142 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.search_cutoff = Setting::Reset;
     }
 
+    pub fn set_prefix_cache_size(&mut self, value: usize) {
+        self.prefix_cache_size = Setting::Set(value);
+    }
+
+    pub fn reset_prefix_cache_size(&mut self) {
+        self.prefix_cache_size = Setting::Reset;
+    }
+
+    pub fn set_long_query_threshold(&mut self, value: usize) {
+        self.long_query_threshold = Setting::Set(value);
+    }
+
+    pub fn reset_long_query_threshold(&mut self) {
+        self.long_query_threshold = Setting::Reset;
+    }
+
+    pub fn set_quote_characters(&mut self, value: BTreeSet<String>) {
+        self.quote_characters = Setting::Set(value);
+    }
+
+    pub fn reset_quote_characters(&mut self) {
+        self.quote_characters = Setting::Reset;
+    }
+
+    pub fn set_language_preset(&mut self, value: LanguagePreset) {
+        self.language_preset = Setting::Set(value);
+    }
+
+    pub fn reset_language_preset(&mut self) {
+        self.language_preset = Setting::Reset;
+    }
+
+    pub fn set_field_languages(&mut self, value: BTreeMap<String, LanguagePreset>) {
+        self.field_languages = Setting::Set(value);
+    }
+
+    pub fn reset_field_languages(&mut self) {
+        self.field_languages = Setting::Reset;
+    }
+
+    pub fn set_min_prefix_length(&mut self, value: usize) {
+        self.min_prefix_length = Setting::Set(value);
+    }
+
+    pub fn reset_min_prefix_length(&mut self) {
+        self.min_prefix_length = Setting::Reset;
+    }
+
+    pub fn set_blocked_terms(&mut self, terms: BTreeSet<String>) {
+        self.blocked_terms = Setting::Set(terms);
+    }
+
+    pub fn reset_blocked_terms(&mut self) {
+        self.blocked_terms = Setting::Reset;
+    }
+
+    pub fn set_blocked_terms_mode(&mut self, mode: BlockedTermsMode) {
+        self.blocked_terms_mode = Setting::Set(mode);
+    }
+
+    pub fn reset_blocked_terms_mode(&mut self) {
+        self.blocked_terms_mode = Setting::Reset;
+    }
+
+    pub fn set_new_fields_searchable(&mut self, value: bool) {
+        self.new_fields_searchable = Setting::Set(value);
+    }
+
+    pub fn reset_new_fields_searchable(&mut self) {
+        self.new_fields_searchable = Setting::Reset;
+    }
+
+    pub fn set_new_fields_filterable(&mut self, value: bool) {
+        self.new_fields_filterable = Setting::Set(value);
+    }
+
+    pub fn reset_new_fields_filterable(&mut self) {
+        self.new_fields_filterable = Setting::Reset;
+    }
+
+    pub fn set_query_rewrites(&mut self, rules: Vec<QueryRewriteRule>) {
+        self.query_rewrites = Setting::Set(rules);
+    }
+
+    pub fn reset_query_rewrites(&mut self) {
+        self.query_rewrites = Setting::Reset;
+    }
+
+    pub fn set_position_decay(&mut self, value: PositionDecay) {
+        self.position_decay = Setting::Set(value);
+    }
+
+    pub fn reset_position_decay(&mut self) {
+        self.position_decay = Setting::Reset;
+    }
+
+    pub fn set_max_batch_size(&mut self, value: u64) {
+        self.max_batch_size = Setting::Set(value);
+    }
+
+    pub fn reset_max_batch_size(&mut self) {
+        self.max_batch_size = Setting::Reset;
+    }
+
+    pub fn set_max_batch_payload(&mut self, value: u64) {
+        self.max_batch_payload = Setting::Set(value);
+    }
+
+    pub fn reset_max_batch_payload(&mut self) {
+        self.max_batch_payload = Setting::Reset;
+    }
+
+    pub fn set_collation_locale(&mut self, value: CollationLocale) {
+        self.collation_locale = Setting::Set(value);
+    }
+
+    pub fn reset_collation_locale(&mut self) {
+        self.collation_locale = Setting::Reset;
+    }
+
+    pub fn set_collation_attributes(&mut self, value: BTreeMap<String, CollationLocale>) {
+        self.collation_attributes = Setting::Set(value);
+    }
+
+    pub fn reset_collation_attributes(&mut self) {
+        self.collation_attributes = Setting::Reset;
+    }
+
+    pub fn set_tie_breaker(&mut self, value: TieBreaker) {
+        self.tie_breaker = Setting::Set(value);
+    }
+
+    pub fn reset_tie_breaker(&mut self) {
+        self.tie_breaker = Setting::Reset;
+    }
+
     #[tracing::instrument(
         level = "trace"
         skip(self, progress_callback, should_abort, settings_diff),
@@ -467,20 +928,40 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(true)
     }
 
+    fn update_version_field(&mut self) -> Result<bool> {
+        match self.version_field {
+            Setting::Set(ref attr) => {
+                self.index.put_version_field(self.wtxn, attr)?;
+            }
+            Setting::Reset => {
+                self.index.delete_version_field(self.wtxn)?;
+            }
+            Setting::NotSet => return Ok(false),
+        }
+        Ok(true)
+    }
+
     /// Updates the index's searchable attributes.
     fn update_searchable(&mut self) -> Result<bool> {
         match self.searchable_fields {
             Setting::Set(ref fields) => {
                 // Check to see if the searchable fields changed before doing anything else
                 let old_fields = self.index.searchable_fields(self.wtxn)?;
+                let old_weights = self.index.searchable_weights(self.wtxn)?;
                 let did_change = {
                     let new_fields = fields.iter().map(String::as_str).collect::<Vec<_>>();
-                    new_fields != old_fields
+                    new_fields != old_fields || self.searchable_weights != old_weights
                 };
                 if !did_change {
                     return Ok(false);
                 }
 
+                if self.searchable_weights.is_empty() {
+                    self.index.delete_searchable_weights(self.wtxn)?;
+                } else {
+                    self.index.put_searchable_weights(self.wtxn, &self.searchable_weights)?;
+                }
+
                 // Since we're updating the settings we can only add new fields at the end of the field id map
                 let mut fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
                 // fields are deduplicated, only the first occurrence is taken into account
@@ -614,67 +1095,61 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(changes)
     }
 
-    fn update_synonyms(&mut self) -> Result<bool> {
-        match self.synonyms {
-            Setting::Set(ref user_synonyms) => {
-                fn normalize(tokenizer: &Tokenizer<'_>, text: &str) -> Vec<String> {
-                    tokenizer
-                        .tokenize(text)
-                        .filter_map(|token| {
-                            if token.is_word() && !token.lemma().is_empty() {
-                                Some(token.lemma().to_string())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>()
-                }
+    fn update_literal_words(&mut self) -> Result<bool> {
+        let changes = match self.literal_words {
+            Setting::Set(ref literal_words) => {
+                let current = self.index.literal_words(self.wtxn)?;
 
-                let mut builder = TokenizerBuilder::new();
-                let stop_words = self.index.stop_words(self.wtxn)?;
-                if let Some(ref stop_words) = stop_words {
-                    builder.stop_words(stop_words);
-                }
+                // Does the new list differ from the previous one?
+                if current.map_or(true, |current| &current != literal_words) {
+                    // The literal words are part of the tokenizer dictionary,
+                    // so that they are normalized without ever being split.
+                    let dictionary: Vec<_> = literal_words.iter().map(String::as_str).collect();
+                    let mut builder = TokenizerBuilder::default();
+                    builder.words_dict(&dictionary);
+                    let tokenizer = builder.build();
+
+                    let mut words: Vec<String> = literal_words
+                        .iter()
+                        .map(|word| {
+                            tokenizer
+                                .tokenize(word)
+                                .map(|token| token.lemma().to_string())
+                                .collect()
+                        })
+                        .collect();
 
-                let separators = self.index.allowed_separators(self.wtxn)?;
-                let separators: Option<Vec<_>> =
-                    separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
-                if let Some(ref separators) = separators {
-                    builder.separators(separators);
-                }
+                    // normalization could reorder words
+                    words.sort_unstable();
+                    words.dedup();
 
-                let dictionary = self.index.dictionary(self.wtxn)?;
-                let dictionary: Option<Vec<_>> =
-                    dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
-                if let Some(ref dictionary) = dictionary {
-                    builder.words_dict(dictionary);
+                    let words = fst::Set::from_iter(words.iter())?;
+                    self.index.put_literal_words(self.wtxn, literal_words)?;
+                    self.index.put_literal_words_fst(self.wtxn, &words)?;
+                    true
+                } else {
+                    false
                 }
+            }
+            Setting::Reset => {
+                self.index.delete_literal_words_fst(self.wtxn)?;
+                self.index.delete_literal_words(self.wtxn)?
+            }
+            Setting::NotSet => false,
+        };
 
-                let tokenizer = builder.build();
-
-                let mut new_synonyms = HashMap::new();
-                for (word, synonyms) in user_synonyms {
-                    // Normalize both the word and associated synonyms.
-                    let normalized_word = normalize(&tokenizer, word);
-                    let normalized_synonyms: Vec<_> = synonyms
-                        .iter()
-                        .map(|synonym| normalize(&tokenizer, synonym))
-                        .filter(|synonym| !synonym.is_empty())
-                        .collect();
+        // the synonyms must be updated if the tokenizer dictionary has been updated.
+        if changes && self.synonyms == Setting::NotSet {
+            self.synonyms = Setting::Set(self.index.user_defined_synonyms(self.wtxn)?);
+        }
 
-                    // Store the normalized synonyms under the normalized word,
-                    // merging the possible duplicate words.
-                    if !normalized_word.is_empty() && !normalized_synonyms.is_empty() {
-                        let entry = new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
-                        entry.extend(normalized_synonyms.into_iter());
-                    }
-                }
+        Ok(changes)
+    }
 
-                // Make sure that we don't have duplicate synonyms.
-                new_synonyms.iter_mut().for_each(|(_, synonyms)| {
-                    synonyms.sort_unstable();
-                    synonyms.dedup();
-                });
+    fn update_synonyms(&mut self) -> Result<bool> {
+        match self.synonyms {
+            Setting::Set(ref user_synonyms) => {
+                let new_synonyms = normalize_synonyms(self.index, self.wtxn, user_synonyms)?;
 
                 let old_synonyms = self.index.synonyms(self.wtxn)?;
 
@@ -709,21 +1184,212 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         }
     }
 
-    fn update_filterable(&mut self) -> Result<()> {
-        match self.filterable_fields {
-            Setting::Set(ref fields) => {
-                let mut new_facets = HashSet::new();
-                for name in fields {
-                    new_facets.insert(name.clone());
+    fn update_presence_only_attributes(&mut self) -> Result<bool> {
+        match self.presence_only_attributes {
+            Setting::Set(ref attrs) => {
+                let old_attrs = self.index.presence_only_attributes(self.wtxn)?;
+                let old_attrs = old_attrs.into_iter().map(String::from).collect::<HashSet<_>>();
+
+                if attrs != &old_attrs {
+                    let attrs = attrs.iter().map(String::as_str).collect::<Vec<_>>();
+                    self.index.put_presence_only_attributes(self.wtxn, &attrs)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
                 }
-                self.index.put_filterable_fields(self.wtxn, &new_facets)?;
             }
-            Setting::Reset => {
-                self.index.delete_filterable_fields(self.wtxn)?;
+            Setting::Reset => Ok(self.index.delete_presence_only_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_chunking(&mut self) -> Result<bool> {
+        if self.chunking_max_words.is_not_set() && self.chunking_overlap.is_not_set() {
+            return Ok(false);
+        }
+
+        let old = self.index.chunking(self.wtxn)?;
+        let max_words = match self.chunking_max_words {
+            Setting::Set(max_words) => Some(max_words),
+            Setting::Reset => None,
+            Setting::NotSet => old.map(|chunking| chunking.max_words),
+        };
+        let overlap = match self.chunking_overlap {
+            Setting::Set(overlap) => overlap,
+            Setting::Reset => 0,
+            Setting::NotSet => old.map_or(0, |chunking| chunking.overlap),
+        };
+
+        let new = match max_words {
+            Some(max_words) if overlap >= max_words => {
+                return Err(UserError::InvalidChunkingOverlap { max_words, overlap }.into());
+            }
+            Some(max_words) => Some(Chunking { max_words, overlap }),
+            None => None,
+        };
+
+        match &new {
+            Some(chunking) => self.index.put_chunking(self.wtxn, chunking)?,
+            None => {
+                self.index.delete_chunking(self.wtxn)?;
             }
-            Setting::NotSet => (),
         }
-        Ok(())
+
+        Ok(old != new)
+    }
+
+    /// The hook is only executed on the documents added afterward,
+    /// the documents already in the index are left as is.
+    fn update_document_hook(&mut self) -> Result<()> {
+        if self.document_hook_module.is_not_set() && self.document_hook_fuel.is_not_set() {
+            return Ok(());
+        }
+
+        let old = self.index.document_hook(self.wtxn)?;
+        let module = match &mut self.document_hook_module {
+            Setting::Set(module) => Some(std::mem::take(module)),
+            Setting::Reset => None,
+            Setting::NotSet => old.as_ref().map(|hook| hook.module.clone()),
+        };
+        let fuel = match self.document_hook_fuel {
+            Setting::Set(fuel) => fuel,
+            Setting::Reset => DEFAULT_DOCUMENT_HOOK_FUEL,
+            Setting::NotSet => old.map_or(DEFAULT_DOCUMENT_HOOK_FUEL, |hook| hook.fuel),
+        };
+
+        match module {
+            Some(module) => {
+                let hook = DocumentHook { module, fuel };
+                DocumentHookRunner::new(&hook).map_err(UserError::InvalidDocumentHook)?;
+                self.index.put_document_hook(self.wtxn, &hook)?;
+            }
+            None => {
+                self.index.delete_document_hook(self.wtxn)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The computed fields are evaluated at search time, they don't require any reindexing.
+    fn update_computed_fields(&mut self) -> Result<()> {
+        match self.computed_fields {
+            Setting::Set(ref computed_fields) if !computed_fields.is_empty() => {
+                ComputedFields::new(computed_fields)?;
+                self.index.put_computed_fields(self.wtxn, computed_fields)?;
+            }
+            Setting::Set(_) | Setting::Reset => {
+                self.index.delete_computed_fields(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
+    /// The prepared filters are rendered and parsed at search time, they don't require any reindexing.
+    fn update_prepared_filters(&mut self) -> Result<()> {
+        match self.prepared_filters {
+            Setting::Set(ref prepared_filters) if !prepared_filters.is_empty() => {
+                for (name, template) in prepared_filters {
+                    validate_prepared_filter(name, template)?;
+                }
+                self.index.put_prepared_filters(self.wtxn, prepared_filters)?;
+            }
+            Setting::Set(_) | Setting::Reset => {
+                self.index.delete_prepared_filters(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
+    /// The PII attributes are encrypted when the documents are added,
+    /// the documents already in the index are left untouched.
+    ///
+    /// Only the top-level attributes can be PII, their values are encrypted as a whole.
+    fn update_pii_attributes(&mut self) -> Result<()> {
+        match self.pii_attributes {
+            Setting::Set(ref pii_attributes) if !pii_attributes.is_empty() => {
+                // the values are encrypted as a whole, the nested attributes would be stored in clear
+                if let Some((name, parent)) = pii_attributes
+                    .iter()
+                    .find_map(|name| name.split_once('.').map(|(parent, _)| (name, parent)))
+                {
+                    let (name, parent) = (name.clone(), parent.to_string());
+                    return Err(UserError::InvalidPiiAttribute { name, parent }.into());
+                }
+                self.index.put_pii_attributes(self.wtxn, pii_attributes)?;
+            }
+            Setting::Set(_) | Setting::Reset => {
+                self.index.delete_pii_attributes(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
+    fn update_query_limits(&mut self) -> Result<()> {
+        fn apply(setting: Setting<usize>, old: Option<usize>) -> Option<usize> {
+            match setting {
+                Setting::Set(value) => Some(value),
+                Setting::Reset => None,
+                Setting::NotSet => old,
+            }
+        }
+
+        let old = self.index.query_limits(self.wtxn)?;
+        let new = QueryLimits {
+            max_length: apply(self.query_max_length, old.max_length),
+            max_terms: apply(self.query_max_terms, old.max_terms),
+            max_phrases: apply(self.query_max_phrases, old.max_phrases),
+        };
+
+        if new.is_empty() {
+            self.index.delete_query_limits(self.wtxn)?;
+        } else if new != old {
+            self.index.put_query_limits(self.wtxn, &new)?;
+        }
+        Ok(())
+    }
+
+    fn update_regex_filter(&mut self) -> Result<()> {
+        let old = self.index.regex_filter(self.wtxn)?;
+        let new = RegexFilter {
+            enabled: match self.regex_filter_enabled {
+                Setting::Set(enabled) => enabled,
+                Setting::Reset => false,
+                Setting::NotSet => old.enabled,
+            },
+            max_candidates: match self.regex_filter_max_candidates {
+                Setting::Set(max_candidates) => Some(max_candidates),
+                Setting::Reset => None,
+                Setting::NotSet => old.max_candidates,
+            },
+        };
+
+        if new.is_empty() {
+            self.index.delete_regex_filter(self.wtxn)?;
+        } else if new != old {
+            self.index.put_regex_filter(self.wtxn, &new)?;
+        }
+        Ok(())
+    }
+
+    fn update_filterable(&mut self) -> Result<()> {
+        match self.filterable_fields {
+            Setting::Set(ref fields) => {
+                let mut new_facets = HashSet::new();
+                for name in fields {
+                    new_facets.insert(name.clone());
+                }
+                self.index.put_filterable_fields(self.wtxn, &new_facets)?;
+            }
+            Setting::Reset => {
+                self.index.delete_filterable_fields(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
     }
 
     fn update_sortable(&mut self) -> Result<()> {
@@ -743,6 +1409,20 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_dense_sortable_fields(&mut self) -> Result<bool> {
+        match self.dense_sortable_fields {
+            Setting::Set(ref fields) => {
+                if self.index.dense_sortable_fields(self.wtxn)? == *fields {
+                    return Ok(false);
+                }
+                self.index.put_dense_sortable_fields(self.wtxn, fields)?;
+            }
+            Setting::Reset => return Ok(self.index.delete_dense_sortable_fields(self.wtxn)?),
+            Setting::NotSet => return Ok(false),
+        }
+        Ok(true)
+    }
+
     fn update_criteria(&mut self) -> Result<()> {
         match &self.criteria {
             Setting::Set(criteria) => {
@@ -867,6 +1547,110 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_half_typos(&mut self) -> Result<bool> {
+        if self.half_typos_keyboard_layout.is_not_set() && self.half_typos_on_accents.is_not_set() {
+            return Ok(false);
+        }
+
+        let old = self.index.half_typos(self.wtxn)?.unwrap_or_default();
+        let mut new = old.clone();
+        match self.half_typos_keyboard_layout {
+            Setting::Set(layout) => new.keyboard_layout = Some(layout),
+            Setting::Reset => new.keyboard_layout = None,
+            Setting::NotSet => (),
+        }
+        match self.half_typos_on_accents {
+            Setting::Set(accents) => new.accents = accents,
+            Setting::Reset => new.accents = false,
+            Setting::NotSet => (),
+        }
+
+        if new == HalfTypos::default() {
+            self.index.delete_half_typos(self.wtxn)?;
+        } else {
+            self.index.put_half_typos(self.wtxn, &new)?;
+        }
+
+        Ok(old != new)
+    }
+
+    fn update_disable_typos_on_numbers(&mut self) -> Result<()> {
+        match self.disable_typos_on_numbers {
+            Setting::Set(flag) => self.index.put_disable_typos_on_numbers(self.wtxn, flag)?,
+            Setting::Reset => {
+                self.index.delete_disable_typos_on_numbers(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_first_letter_typos(&mut self) -> Result<()> {
+        match self.first_letter_typos {
+            Setting::Set(value) => self.index.put_first_letter_typos(self.wtxn, value)?,
+            Setting::Reset => {
+                self.index.delete_first_letter_typos(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_typo_costs(&mut self) -> Result<()> {
+        if self.typo_cost_one_typo.is_not_set()
+            && self.typo_cost_two_typos.is_not_set()
+            && self.typo_cost_split.is_not_set()
+        {
+            return Ok(());
+        }
+
+        let default = TypoCosts::default();
+        let mut costs = self.index.typo_costs(self.wtxn)?;
+        match self.typo_cost_one_typo {
+            Setting::Set(cost) => costs.one_typo = cost,
+            Setting::Reset => costs.one_typo = default.one_typo,
+            Setting::NotSet => (),
+        }
+        match self.typo_cost_two_typos {
+            Setting::Set(cost) => costs.two_typos = cost,
+            Setting::Reset => costs.two_typos = default.two_typos,
+            Setting::NotSet => (),
+        }
+        match self.typo_cost_split {
+            Setting::Set(cost) => costs.split = cost,
+            Setting::Reset => costs.split = default.split,
+            Setting::NotSet => (),
+        }
+
+        if costs.one_typo > costs.two_typos {
+            return Err(UserError::InvalidTypoCostsSetting(costs.one_typo, costs.two_typos).into());
+        }
+
+        if costs == default {
+            self.index.delete_typo_costs(self.wtxn)?;
+        } else {
+            self.index.put_typo_costs(self.wtxn, costs)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the phonetic matching was enabled or disabled.
+    fn update_enable_phonetic(&mut self) -> Result<bool> {
+        let old = self.index.enable_phonetic(self.wtxn)?;
+        match self.enable_phonetic {
+            Setting::Set(flag) => self.index.put_enable_phonetic(self.wtxn, flag)?,
+            Setting::Reset => {
+                self.index.delete_enable_phonetic(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(old != self.index.enable_phonetic(self.wtxn)?)
+    }
+
     fn update_max_values_per_facet(&mut self) -> Result<()> {
         match self.max_values_per_facet {
             Setting::Set(max) => {
@@ -881,6 +1665,20 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_facet_value_aliases(&mut self) -> Result<()> {
+        match self.facet_value_aliases.as_ref() {
+            Setting::Set(value) => {
+                self.index.put_facet_value_aliases(self.wtxn, value)?;
+            }
+            Setting::Reset => {
+                self.index.delete_facet_value_aliases(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     fn update_sort_facet_values_by(&mut self) -> Result<()> {
         match self.sort_facet_values_by.as_ref() {
             Setting::Set(value) => {
@@ -1078,44 +1876,372 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                 EmbedderAction::WriteBackToDocuments(_) => { /* already removed */ }
             }
         }
-        let updated_configs: Vec<IndexEmbeddingConfig> = updated_configs
-            .into_iter()
-            .filter_map(|(name, (config, user_provided))| match config {
-                Setting::Set(config) => {
-                    Some(IndexEmbeddingConfig { name, config: config.into(), user_provided })
-                }
-                Setting::Reset => None,
-                Setting::NotSet => Some(IndexEmbeddingConfig {
-                    name,
-                    config: EmbeddingSettings::default().into(),
-                    user_provided,
-                }),
-            })
-            .collect();
-        if updated_configs.is_empty() {
-            self.index.delete_embedding_configs(self.wtxn)?;
+        let updated_configs: Vec<IndexEmbeddingConfig> = updated_configs
+            .into_iter()
+            .filter_map(|(name, (config, user_provided))| match config {
+                Setting::Set(config) => {
+                    Some(IndexEmbeddingConfig { name, config: config.into(), user_provided })
+                }
+                Setting::Reset => None,
+                Setting::NotSet => Some(IndexEmbeddingConfig {
+                    name,
+                    config: EmbeddingSettings::default().into(),
+                    user_provided,
+                }),
+            })
+            .collect();
+        if updated_configs.is_empty() {
+            self.index.delete_embedding_configs(self.wtxn)?;
+        } else {
+            self.index.put_embedding_configs(self.wtxn, updated_configs)?;
+        }
+        Ok(embedder_actions)
+    }
+
+    fn update_search_cutoff(&mut self) -> Result<bool> {
+        let changed = match self.search_cutoff {
+            Setting::Set(new) => {
+                let old = self.index.search_cutoff(self.wtxn)?;
+                if old == Some(new) {
+                    false
+                } else {
+                    self.index.put_search_cutoff(self.wtxn, new)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_search_cutoff(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_prefix_cache_size(&mut self) -> Result<bool> {
+        let changed = match self.prefix_cache_size {
+            Setting::Set(new) => {
+                let old = self.index.prefix_cache_size(self.wtxn)?;
+                if old == Some(new as u64) {
+                    false
+                } else {
+                    self.index.put_prefix_cache_size(self.wtxn, new as u64)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_prefix_cache_size(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_long_query_threshold(&mut self) -> Result<bool> {
+        let changed = match self.long_query_threshold {
+            Setting::Set(new) => {
+                let old = self.index.long_query_threshold(self.wtxn)?;
+                if old == Some(new as u64) {
+                    false
+                } else {
+                    self.index.put_long_query_threshold(self.wtxn, new as u64)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_long_query_threshold(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_quote_characters(&mut self) -> Result<bool> {
+        let changed = match self.quote_characters {
+            Setting::Set(ref quotes) => {
+                // a quote must be a single punctuation character to be recognized in the queries
+                if let Some(invalid) = quotes.iter().find(|quote| {
+                    let mut chars = quote.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => c.is_alphanumeric() || c.is_whitespace(),
+                        _ => true,
+                    }
+                }) {
+                    return Err(UserError::InvalidQuoteCharacter(invalid.clone()).into());
+                }
+
+                if &self.index.quote_characters(self.wtxn)? != quotes {
+                    self.index.put_quote_characters(self.wtxn, quotes)?;
+                    true
+                } else {
+                    false
+                }
+            }
+            Setting::Reset => self.index.delete_quote_characters(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    /// Fills the stop words and typo tolerance settings bundled in the language preset
+    /// that are not explicitly given in this update, so that they are all applied together.
+    ///
+    /// Resetting the preset resets these settings too, unless they are explicitly given.
+    fn apply_language_preset(&mut self) -> Result<()> {
+        let preset = match self.language_preset {
+            Setting::Set(preset) => Some(preset),
+            Setting::Reset if self.index.language_preset(self.wtxn)?.is_some() => None,
+            Setting::Reset | Setting::NotSet => return Ok(()),
+        };
+
+        if self.stop_words.is_not_set() {
+            self.stop_words = match preset {
+                Some(preset) => Setting::Set(preset.stop_words()),
+                None => Setting::Reset,
+            };
+        }
+
+        let min_word_len_typos = preset.and_then(|preset| preset.min_word_len_typos());
+        if self.authorize_typos.is_not_set() {
+            self.authorize_typos = match preset {
+                Some(_) if min_word_len_typos.is_none() => Setting::Set(false),
+                _ => Setting::Reset,
+            };
+        }
+        if self.min_word_len_one_typo.is_not_set() {
+            self.min_word_len_one_typo = match min_word_len_typos {
+                Some((one_typo, _)) => Setting::Set(one_typo),
+                None => Setting::Reset,
+            };
+        }
+        if self.min_word_len_two_typos.is_not_set() {
+            self.min_word_len_two_typos = match min_word_len_typos {
+                Some((_, two_typos)) => Setting::Set(two_typos),
+                None => Setting::Reset,
+            };
+        }
+
+        Ok(())
+    }
+
+    fn update_language_preset(&mut self) -> Result<bool> {
+        let changed = match self.language_preset {
+            Setting::Set(preset) => {
+                if self.index.language_preset(self.wtxn)? != Some(preset) {
+                    self.index.put_language_preset(self.wtxn, preset)?;
+                    true
+                } else {
+                    false
+                }
+            }
+            Setting::Reset => self.index.delete_language_preset(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_field_languages(&mut self) -> Result<bool> {
+        let changed = match self.field_languages {
+            Setting::Set(ref field_languages) if field_languages.is_empty() => {
+                self.index.delete_field_languages(self.wtxn)?
+            }
+            Setting::Set(ref field_languages) => {
+                if &self.index.field_languages(self.wtxn)? != field_languages {
+                    self.index.put_field_languages(self.wtxn, field_languages)?;
+                    true
+                } else {
+                    false
+                }
+            }
+            Setting::Reset => self.index.delete_field_languages(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_min_prefix_length(&mut self) -> Result<bool> {
+        let changed = match self.min_prefix_length {
+            Setting::Set(new) => {
+                let old = self.index.min_prefix_length(self.wtxn)?;
+                if old == new as u64 {
+                    false
+                } else {
+                    self.index.put_min_prefix_length(self.wtxn, new as u64)?;
+                    true
+                }
+            }
+            Setting::Reset => self.index.delete_min_prefix_length(self.wtxn)?,
+            Setting::NotSet => false,
+        };
+
+        Ok(changed)
+    }
+
+    fn update_blocked_terms(&mut self) -> Result<()> {
+        if self.blocked_terms.is_not_set() && self.blocked_terms_mode.is_not_set() {
+            return Ok(());
+        }
+
+        let old = self.index.blocked_terms(self.wtxn)?;
+        let mut new = old.clone();
+        match self.blocked_terms {
+            Setting::Set(ref terms) => {
+                // the terms are compared to the normalized words of the queries
+                let tokenizer = TokenizerBuilder::default().build();
+                new.terms = terms
+                    .iter()
+                    .map(|term| {
+                        let term: String = tokenizer
+                            .tokenize(term)
+                            .map(|token| token.lemma().to_string())
+                            .collect();
+                        term.trim().to_string()
+                    })
+                    .filter(|term| !term.is_empty())
+                    .collect();
+            }
+            Setting::Reset => new.terms.clear(),
+            Setting::NotSet => (),
+        }
+        match self.blocked_terms_mode {
+            Setting::Set(mode) => new.mode = mode,
+            Setting::Reset => new.mode = BlockedTermsMode::default(),
+            Setting::NotSet => (),
+        }
+
+        if new.is_empty() {
+            self.index.delete_blocked_terms(self.wtxn)?;
+        } else if new != old {
+            self.index.put_blocked_terms(self.wtxn, &new)?;
+        }
+        Ok(())
+    }
+
+    fn update_new_fields_policy(&mut self) -> Result<()> {
+        if self.new_fields_searchable.is_not_set() && self.new_fields_filterable.is_not_set() {
+            return Ok(());
+        }
+
+        let old = self.index.new_fields_policy(self.wtxn)?;
+        let mut new = old;
+        let default = NewFieldsPolicy::default();
+        match self.new_fields_searchable {
+            Setting::Set(searchable) => new.searchable = searchable,
+            Setting::Reset => new.searchable = default.searchable,
+            Setting::NotSet => (),
+        }
+        match self.new_fields_filterable {
+            Setting::Set(filterable) => new.filterable = filterable,
+            Setting::Reset => new.filterable = default.filterable,
+            Setting::NotSet => (),
+        }
+
+        // the policy only applies to the next document additions
+        if new == default {
+            self.index.delete_new_fields_policy(self.wtxn)?;
+        } else if new != old {
+            self.index.put_new_fields_policy(self.wtxn, &new)?;
+        }
+        Ok(())
+    }
+
+    /// The rewrite rules are applied at search time, they don't require any reindexing.
+    fn update_query_rewrites(&mut self) -> Result<()> {
+        match self.query_rewrites {
+            Setting::Set(ref rules) if !rules.is_empty() => {
+                for rule in rules {
+                    rule.compile()?;
+                }
+                self.index.put_query_rewrites(self.wtxn, rules)?;
+            }
+            Setting::Set(_) | Setting::Reset => {
+                self.index.delete_query_rewrites(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
+    /// The position decay only changes the costs of the `attribute` ranking rule,
+    /// it doesn't require any reindexing.
+    fn update_position_decay(&mut self) -> Result<()> {
+        match self.position_decay {
+            Setting::Set(value) if value != PositionDecay::default() => {
+                self.index.put_position_decay(self.wtxn, value)?;
+            }
+            Setting::Set(_) | Setting::Reset => {
+                self.index.delete_position_decay(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
+    /// The collation is applied to the original string values when the documents are sorted,
+    /// it doesn't require any reindexing.
+    fn update_sort_collation(&mut self) -> Result<()> {
+        if self.collation_locale.is_not_set() && self.collation_attributes.is_not_set() {
+            return Ok(());
+        }
+
+        let mut collation = self.index.sort_collation(self.wtxn)?;
+        match self.collation_locale {
+            Setting::Set(locale) => collation.locale = Some(locale),
+            Setting::Reset => collation.locale = None,
+            Setting::NotSet => (),
+        }
+        match self.collation_attributes {
+            Setting::Set(ref attributes) => collation.attributes = attributes.clone(),
+            Setting::Reset => collation.attributes.clear(),
+            Setting::NotSet => (),
+        }
+
+        if collation == SortCollation::default() {
+            self.index.delete_sort_collation(self.wtxn)?;
         } else {
-            self.index.put_embedding_configs(self.wtxn, updated_configs)?;
+            self.index.put_sort_collation(self.wtxn, &collation)?;
         }
-        Ok(embedder_actions)
+        Ok(())
     }
 
-    fn update_search_cutoff(&mut self) -> Result<bool> {
-        let changed = match self.search_cutoff {
-            Setting::Set(new) => {
-                let old = self.index.search_cutoff(self.wtxn)?;
-                if old == Some(new) {
-                    false
-                } else {
-                    self.index.put_search_cutoff(self.wtxn, new)?;
-                    true
-                }
+    /// The tie-breaker is applied when the documents are ranked, it doesn't require
+    /// any reindexing.
+    fn update_tie_breaker(&mut self) -> Result<()> {
+        match self.tie_breaker {
+            Setting::Set(ref tie_breaker) => {
+                self.index.put_tie_breaker(self.wtxn, tie_breaker)?;
             }
-            Setting::Reset => self.index.delete_search_cutoff(self.wtxn)?,
-            Setting::NotSet => false,
-        };
+            Setting::Reset => {
+                self.index.delete_tie_breaker(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
 
-        Ok(changed)
+    /// The batch limits are read by the task scheduler, they don't require any reindexing.
+    fn update_batch_limits(&mut self) -> Result<()> {
+        if self.max_batch_size.is_not_set() && self.max_batch_payload.is_not_set() {
+            return Ok(());
+        }
+
+        let mut limits = self.index.batch_limits(self.wtxn)?;
+        match self.max_batch_size {
+            Setting::Set(size) => limits.max_batch_size = Some(size),
+            Setting::Reset => limits.max_batch_size = None,
+            Setting::NotSet => (),
+        }
+        match self.max_batch_payload {
+            Setting::Set(payload) => limits.max_batch_payload = Some(payload),
+            Setting::Reset => limits.max_batch_payload = None,
+            Setting::NotSet => (),
+        }
+
+        if limits == BatchLimits::default() {
+            self.index.delete_batch_limits(self.wtxn)?;
+        } else {
+            self.index.put_batch_limits(self.wtxn, &limits)?;
+        }
+        Ok(())
     }
 
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
@@ -1125,20 +2251,47 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
     {
         self.index.set_updated_at(self.wtxn, &OffsetDateTime::now_utc())?;
 
-        let old_inner_settings = InnerIndexSettings::from_index(self.index, self.wtxn)?;
+        let mut old_inner_settings = InnerIndexSettings::from_index(self.index, self.wtxn)?;
+        // the `_acl` facets of the documents added before the field was faceted are missing,
+        // considering it wasn't faceted makes the reindexing extract them for all the documents
+        let acl_indexed = self.index.acl_indexed(self.wtxn)?;
+        if !acl_indexed {
+            old_inner_settings.forget_acl_facets();
+        }
+
+        self.apply_language_preset()?;
 
         // never trigger re-indexing
         self.update_displayed()?;
         self.update_distinct_field()?;
+        self.update_version_field()?;
+        let dense_sortable_fields_changed = self.update_dense_sortable_fields()?;
         self.update_criteria()?;
         self.update_primary_key()?;
         self.update_authorize_typos()?;
         self.update_min_typo_word_len()?;
         self.update_exact_words()?;
+        self.update_half_typos()?;
+        self.update_disable_typos_on_numbers()?;
+        self.update_first_letter_typos()?;
+        self.update_typo_costs()?;
+        let phonetic_changed = self.update_enable_phonetic()?;
         self.update_max_values_per_facet()?;
         self.update_sort_facet_values_by()?;
+        self.update_facet_value_aliases()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_prefix_cache_size()?;
+        self.update_long_query_threshold()?;
+        self.update_min_prefix_length()?;
+        self.update_blocked_terms()?;
+        self.update_new_fields_policy()?;
+        self.update_query_rewrites()?;
+        self.update_position_decay()?;
+        self.update_batch_limits()?;
+        self.update_sort_collation()?;
+        self.update_tie_breaker()?;
+        self.update_quote_characters()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
@@ -1147,12 +2300,31 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_non_separator_tokens()?;
         self.update_separator_tokens()?;
         self.update_dictionary()?;
+        self.update_literal_words()?;
         self.update_synonyms()?;
         self.update_searchable()?;
         self.update_exact_attributes()?;
+        self.update_presence_only_attributes()?;
+        let chunking_changed = self.update_chunking()?;
+        self.update_document_hook()?;
+        self.update_computed_fields()?;
+        self.update_prepared_filters()?;
+        self.update_pii_attributes()?;
+        self.update_query_limits()?;
+        self.update_regex_filter()?;
         self.update_proximity_precision()?;
-
-        let embedding_config_updates = self.update_embedding_configs()?;
+        self.update_language_preset()?;
+        self.update_field_languages()?;
+
+        let mut embedding_config_updates = self.update_embedding_configs()?;
+        // the embedders receive the first passage of the documents, it must be embedded again
+        if chunking_changed {
+            for IndexEmbeddingConfig { name, .. } in self.index.embedding_configs(self.wtxn)? {
+                embedding_config_updates
+                    .entry(name)
+                    .or_insert(EmbedderAction::Reindex(ReindexAction::RegeneratePrompts));
+            }
+        }
 
         let mut new_inner_settings = InnerIndexSettings::from_index(self.index, self.wtxn)?;
         new_inner_settings.recompute_facets(self.wtxn, self.index)?;
@@ -1171,7 +2343,23 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         );
 
         if inner_settings_diff.any_reindexing_needed() {
+            // the numeric sort indexes and the prefix cache are updated at the end of the reindexing
             self.reindex(&progress_callback, &should_abort, inner_settings_diff)?;
+        } else {
+            if dense_sortable_fields_changed {
+                compute_numeric_sort_indexes(self.index, self.wtxn)?;
+            }
+            // the phonetic FST only depends on the words dictionary, it is rebuilt without reindexing
+            if phonetic_changed {
+                compute_phonetic_words_fst(self.index, self.wtxn)?;
+            }
+            // most settings, like the ranking rules or the typo tolerance,
+            // can change the results of the cached prefixes
+            compute_prefix_cache(self.index, self.wtxn)?;
+        }
+
+        if !acl_indexed {
+            self.index.put_acl_indexed(self.wtxn)?;
         }
 
         Ok(())
@@ -1189,12 +2377,20 @@ pub struct InnerIndexSettingsDiff {
     pub(crate) only_additional_fields: Option<HashSet<String>>,
 
     // Cache the check to see if all the stop_words, allowed_separators, dictionary,
-    // exact_attributes, proximity_precision are different.
+    // exact_attributes, proximity_precision, language_preset, field_languages are different.
     pub(crate) cache_reindex_searchable_without_user_defined: bool,
     // Cache the check to see if the user_defined_searchables are different.
     pub(crate) cache_user_defined_searchables: bool,
     // Cache the check to see if the exact_attributes are different.
     pub(crate) cache_exact_attributes: bool,
+    // Cache the check to see if the presence_only_attributes are different.
+    pub(crate) cache_presence_only_attributes: bool,
+    // Cache the check to see if the chunking is different.
+    pub(crate) cache_chunking: bool,
+    // Cache the check to see if the `relevancy:bm25` ranking rule was added or removed.
+    pub(crate) cache_bm25: bool,
+    // Cache the attributes that became or stopped being PII.
+    pub(crate) cache_pii_attributes: BTreeSet<String>,
 }
 
 impl InnerIndexSettingsDiff {
@@ -1210,6 +2406,8 @@ impl InnerIndexSettingsDiff {
             &old_settings.user_defined_searchable_fields,
             &new_settings.user_defined_searchable_fields,
         ) {
+            // the term frequencies are computed from all the searchable fields of the documents
+            _ if old_settings.bm25 || new_settings.bm25 => None,
             (None, None) | (Some(_), None) | (None, Some(_)) => None, // None means *
             (Some(old), Some(new)) => {
                 let old: HashSet<_> = old.iter().cloned().collect();
@@ -1229,10 +2427,21 @@ impl InnerIndexSettingsDiff {
                 || old_settings.allowed_separators != new_settings.allowed_separators
                 || old_settings.dictionary != new_settings.dictionary
                 || old_settings.proximity_precision != new_settings.proximity_precision
+                || old_settings.language_preset != new_settings.language_preset
+                || old_settings.field_languages != new_settings.field_languages
         };
 
         let cache_exact_attributes = old_settings.exact_attributes != new_settings.exact_attributes;
 
+        let cache_presence_only_attributes =
+            old_settings.presence_only_attributes != new_settings.presence_only_attributes;
+
+        let cache_chunking = old_settings.chunking != new_settings.chunking;
+
+        let cache_bm25 = old_settings.bm25 != new_settings.bm25;
+
+        let cache_pii_attributes = &old_settings.pii_attributes ^ &new_settings.pii_attributes;
+
         let cache_user_defined_searchables = old_settings.user_defined_searchable_fields
             != new_settings.user_defined_searchable_fields;
 
@@ -1246,17 +2455,27 @@ impl InnerIndexSettingsDiff {
             cache_reindex_searchable_without_user_defined,
             cache_user_defined_searchables,
             cache_exact_attributes,
+            cache_presence_only_attributes,
+            cache_chunking,
+            cache_bm25,
+            cache_pii_attributes,
         }
     }
 
     pub fn any_reindexing_needed(&self) -> bool {
-        self.reindex_searchable() || self.reindex_facets() || self.reindex_vectors()
+        self.reindex_searchable()
+            || self.reindex_facets()
+            || self.reindex_vectors()
+            || self.reencrypt_pii()
     }
 
     pub fn reindex_searchable(&self) -> bool {
         self.cache_reindex_searchable_without_user_defined
             || self.cache_exact_attributes
+            || self.cache_presence_only_attributes
+            || self.cache_chunking
             || self.cache_user_defined_searchables
+            || self.cache_bm25
     }
 
     pub fn reindex_proximities(&self) -> bool {
@@ -1265,10 +2484,24 @@ impl InnerIndexSettingsDiff {
         // and if any settings needs the proximity database created
             && (self.old.proximity_precision == ProximityPrecision::ByAttribute
                 || self.new.proximity_precision == ProximityPrecision::ByAttribute)
+        // or if the proximities of some fields must be created or deleted
+            || self.cache_presence_only_attributes
+        // or if the passages of the fields changed
+            || self.cache_chunking
     }
 
     pub fn reindex_searchable_id(&self, id: FieldId) -> Option<DelAddOperation> {
-        if self.cache_reindex_searchable_without_user_defined || self.cache_exact_attributes {
+        // the term frequencies and lengths of the documents are computed from all their fields
+        let reindex_bm25 = (self.old.bm25 || self.new.bm25) && self.reindex_searchable();
+        if self.cache_reindex_searchable_without_user_defined
+            || self.cache_exact_attributes
+            || self.cache_chunking
+            || reindex_bm25
+        {
+            Some(DelAddOperation::DeletionAndAddition)
+        } else if self.old.presence_only_attributes.contains(&id)
+            != self.new.presence_only_attributes.contains(&id)
+        {
             Some(DelAddOperation::DeletionAndAddition)
         } else if let Some(only_additional_fields) = &self.only_additional_fields {
             let additional_field = self.new.fields_ids_map.name(id).unwrap();
@@ -1310,6 +2543,24 @@ impl InnerIndexSettingsDiff {
         !self.embedding_config_updates.is_empty()
     }
 
+    /// Whether some attributes became or stopped being PII, their stored values must then be
+    /// encrypted or decrypted and indexed again.
+    pub fn reencrypt_pii(&self) -> bool {
+        !self.cache_pii_attributes.is_empty()
+    }
+
+    /// Whether the attribute became or stopped being PII.
+    pub fn pii_changed(&self, field_name: &str) -> bool {
+        self.cache_pii_attributes.contains(field_name)
+    }
+
+    /// Whether the field is, or is nested in, an attribute that became or stopped being PII.
+    pub fn pii_changed_id(&self, id: FieldId) -> bool {
+        self.new.fields_ids_map.name(id).map_or(false, |name| {
+            self.cache_pii_attributes.iter().any(|attribute| crate::is_faceted_by(name, attribute))
+        })
+    }
+
     pub fn settings_update_only(&self) -> bool {
         self.settings_update_only
     }
@@ -1335,12 +2586,20 @@ pub(crate) struct InnerIndexSettings {
     pub faceted_fields_ids: HashSet<FieldId>,
     pub searchable_fields_ids: Vec<FieldId>,
     pub exact_attributes: HashSet<FieldId>,
+    pub presence_only_attributes: HashSet<FieldId>,
+    pub chunking: Option<Chunking>,
     pub proximity_precision: ProximityPrecision,
+    pub language_preset: Option<LanguagePreset>,
+    pub field_languages: BTreeMap<String, LanguagePreset>,
     pub embedding_configs: EmbeddingConfigs,
     pub existing_fields: HashSet<String>,
+    pub pii_attributes: BTreeSet<String>,
     pub geo_fields_ids: Option<(FieldId, FieldId)>,
     pub non_searchable_fields_ids: Vec<FieldId>,
     pub non_faceted_fields_ids: Vec<FieldId>,
+    /// Whether the term frequencies of the documents are stored for the `relevancy:bm25`
+    /// ranking rule.
+    pub bm25: bool,
 }
 
 impl InnerIndexSettings {
@@ -1348,7 +2607,7 @@ impl InnerIndexSettings {
         let stop_words = index.stop_words(rtxn)?;
         let stop_words = stop_words.map(|sw| sw.map_data(Vec::from).unwrap());
         let allowed_separators = index.allowed_separators(rtxn)?;
-        let dictionary = index.dictionary(rtxn)?;
+        let dictionary = index.tokenizer_dictionary(rtxn)?;
         let mut fields_ids_map = index.fields_ids_map(rtxn)?;
         let user_defined_searchable_fields = index.user_defined_searchable_fields(rtxn)?;
         let user_defined_searchable_fields =
@@ -1357,13 +2616,19 @@ impl InnerIndexSettings {
         let mut searchable_fields_ids = index.searchable_fields_ids(rtxn)?;
         let mut faceted_fields_ids = index.faceted_fields_ids(rtxn)?;
         let exact_attributes = index.exact_attributes_ids(rtxn)?;
+        let presence_only_attributes = index.presence_only_attributes_ids(rtxn)?;
+        let chunking = index.chunking(rtxn)?;
         let proximity_precision = index.proximity_precision(rtxn)?.unwrap_or_default();
+        let language_preset = index.language_preset(rtxn)?;
+        let field_languages = index.field_languages(rtxn)?;
+        let bm25 = index.criteria(rtxn)?.contains(&Criterion::Bm25);
         let embedding_configs = embedders(index.embedding_configs(rtxn)?)?;
         let existing_fields: HashSet<_> = index
             .field_distribution(rtxn)?
             .into_iter()
             .filter_map(|(field, count)| (count != 0).then_some(field))
             .collect();
+        let pii_attributes = index.pii_attributes(rtxn)?;
         // index.fields_ids_map($a)? ==>> fields_ids_map
         let geo_fields_ids = match fields_ids_map.id("_geo") {
             Some(gfid) => {
@@ -1397,15 +2662,29 @@ impl InnerIndexSettings {
             faceted_fields_ids,
             searchable_fields_ids,
             exact_attributes,
+            presence_only_attributes,
+            chunking,
             proximity_precision,
+            language_preset,
+            field_languages,
             embedding_configs,
             existing_fields,
+            pii_attributes,
             geo_fields_ids,
             non_searchable_fields_ids: vectors_fids.clone(),
             non_faceted_fields_ids: vectors_fids.clone(),
+            bm25,
         })
     }
 
+    /// Removes the `_acl` field from the faceted fields.
+    fn forget_acl_facets(&mut self) {
+        self.user_defined_faceted_fields.remove(RESERVED_ACL_FIELD_NAME);
+        if let Some(fid) = self.fields_ids_map.id(RESERVED_ACL_FIELD_NAME) {
+            self.faceted_fields_ids.remove(&fid);
+        }
+    }
+
     // find and insert the new field ids
     pub fn recompute_facets(&mut self, wtxn: &mut heed::RwTxn<'_>, index: &Index) -> Result<()> {
         let new_facets = self
@@ -1421,6 +2700,51 @@ impl InnerIndexSettings {
         Ok(())
     }
 
+    /// Applies the new fields policy of the index to the fields missing from the given map,
+    /// i.e. the fields discovered by a document addition.
+    pub fn apply_new_fields_policy(
+        &mut self,
+        wtxn: &mut heed::RwTxn<'_>,
+        index: &Index,
+        old_fields_ids_map: &FieldsIdsMap,
+    ) -> Result<()> {
+        let policy = index.new_fields_policy(wtxn)?;
+        if policy == NewFieldsPolicy::default() {
+            return Ok(());
+        }
+
+        let new_fields: Vec<_> = self
+            .fields_ids_map
+            .names()
+            .filter(|name| old_fields_ids_map.id(name).is_none())
+            .filter(|name| NewFieldsPolicy::applies_to(name))
+            .map(String::from)
+            .collect();
+        if new_fields.is_empty() {
+            return Ok(());
+        }
+
+        // the `*` would make the new fields searchable, it is replaced by the known fields
+        if !policy.searchable && self.user_defined_searchable_fields.is_none() {
+            let known_fields = old_fields_ids_map
+                .names()
+                .filter(|name| !crate::is_faceted_by(name, RESERVED_VECTORS_FIELD_NAME))
+                .filter(|name| !crate::is_faceted_by(name, RESERVED_ACL_FIELD_NAME))
+                .map(String::from)
+                .collect();
+            self.user_defined_searchable_fields = Some(known_fields);
+        }
+
+        if policy.filterable {
+            let mut filterable_fields = index.filterable_fields(wtxn)?;
+            filterable_fields.extend(new_fields.iter().cloned());
+            index.put_filterable_fields(wtxn, &filterable_fields)?;
+            self.user_defined_faceted_fields.extend(new_fields);
+        }
+
+        Ok(())
+    }
+
     // find and insert the new field ids
     pub fn recompute_searchables(
         &mut self,
@@ -1448,6 +2772,75 @@ impl InnerIndexSettings {
     }
 }
 
+/// Normalizes the synonyms like the words of the documents, returning them
+/// as the lists of words of each synonym indexed by the words of the synonymized text.
+pub(crate) fn normalize_synonyms(
+    index: &Index,
+    rtxn: &heed::RoTxn<'_>,
+    user_synonyms: &BTreeMap<String, Vec<String>>,
+) -> Result<HashMap<Vec<String>, Vec<Vec<String>>>> {
+    fn normalize(tokenizer: &Tokenizer<'_>, text: &str) -> Vec<String> {
+        tokenizer
+            .tokenize(text)
+            .filter_map(|token| {
+                if token.is_word() && !token.lemma().is_empty() {
+                    Some(token.lemma().to_string())
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>()
+    }
+
+    let mut builder = TokenizerBuilder::new();
+    let stop_words = index.stop_words(rtxn)?;
+    if let Some(ref stop_words) = stop_words {
+        builder.stop_words(stop_words);
+    }
+
+    let separators = index.allowed_separators(rtxn)?;
+    let separators: Option<Vec<_>> =
+        separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref separators) = separators {
+        builder.separators(separators);
+    }
+
+    let dictionary = index.tokenizer_dictionary(rtxn)?;
+    let dictionary: Option<Vec<_>> =
+        dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref dictionary) = dictionary {
+        builder.words_dict(dictionary);
+    }
+
+    let tokenizer = builder.build();
+
+    let mut new_synonyms = HashMap::new();
+    for (word, synonyms) in user_synonyms {
+        // Normalize both the word and associated synonyms.
+        let normalized_word = normalize(&tokenizer, word);
+        let normalized_synonyms: Vec<_> = synonyms
+            .iter()
+            .map(|synonym| normalize(&tokenizer, synonym))
+            .filter(|synonym| !synonym.is_empty())
+            .collect();
+
+        // Store the normalized synonyms under the normalized word,
+        // merging the possible duplicate words.
+        if !normalized_word.is_empty() && !normalized_synonyms.is_empty() {
+            let entry = new_synonyms.entry(normalized_word).or_insert_with(Vec::new);
+            entry.extend(normalized_synonyms.into_iter());
+        }
+    }
+
+    // Make sure that we don't have duplicate synonyms.
+    new_synonyms.iter_mut().for_each(|(_, synonyms)| {
+        synonyms.sort_unstable();
+        synonyms.dedup();
+    });
+
+    Ok(new_synonyms)
+}
+
 fn embedders(embedding_configs: Vec<IndexEmbeddingConfig>) -> Result<EmbeddingConfigs> {
     let res: Result<_> = embedding_configs
         .into_iter()
@@ -1726,6 +3119,7 @@ mod tests {
     use super::*;
     use crate::error::Error;
     use crate::index::tests::TempIndex;
+    use crate::pii::PiiCipher;
     use crate::update::ClearDocuments;
     use crate::{db_snap, Criterion, Filter, SearchResult};
 
@@ -2596,6 +3990,319 @@ mod tests {
         }
     }
 
+    #[test]
+    fn presence_only_attributes_skip_positions_and_proximities() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "hello world", "description": "big red dog" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.word_pair_proximity_docids.get(&rtxn, &(1, "big", "red")).unwrap().is_some());
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_presence_only_attributes(hashset! { S("description") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        // the other attributes are untouched
+        assert!(index
+            .word_pair_proximity_docids
+            .get(&rtxn, &(1, "hello", "world"))
+            .unwrap()
+            .is_some());
+        assert!(index.word_position_docids.get(&rtxn, &("world", 1)).unwrap().is_some());
+
+        assert!(index.word_pair_proximity_docids.get(&rtxn, &(1, "big", "red")).unwrap().is_none());
+        assert!(index.word_position_docids.get(&rtxn, &("red", 1)).unwrap().is_none());
+        assert!(index.word_position_docids.get(&rtxn, &("red", 2048)).unwrap().is_some());
+        // the words can still be found
+        assert!(index.word_docids.get(&rtxn, "red").unwrap().is_some());
+        drop(rtxn);
+
+        index.update_settings(|settings| settings.reset_presence_only_attributes()).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.word_pair_proximity_docids.get(&rtxn, &(1, "big", "red")).unwrap().is_some());
+        assert!(index.word_position_docids.get(&rtxn, &("red", 2048)).unwrap().is_none());
+    }
+
+    #[test]
+    fn chunking_indexes_the_tail_of_long_fields() {
+        let mut index = TempIndex::new();
+        index.indexer_config.max_positions_per_attributes = Some(4);
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "description": "the quick brown fox jumps over the lazy dog" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        // the tail of the field is dropped
+        assert!(index.word_docids.get(&rtxn, "lazy").unwrap().is_none());
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_chunking_max_words(4);
+                settings.set_chunking_overlap(1);
+            })
+            .unwrap();
+
+        // the passages are: "the quick brown fox", "fox jumps over the" and "the lazy dog"
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.word_docids.get(&rtxn, "lazy").unwrap().is_some());
+        // the positions restart at the beginning of each passage
+        assert!(index.word_position_docids.get(&rtxn, &("lazy", 1)).unwrap().is_some());
+        // the overlapping words are close to the words of both passages
+        assert!(index
+            .word_pair_proximity_docids
+            .get(&rtxn, &(1, "fox", "jumps"))
+            .unwrap()
+            .is_some());
+        drop(rtxn);
+
+        let error = index.update_settings(|settings| settings.set_chunking_overlap(4)).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::InvalidChunkingOverlap { max_words: 4, overlap: 4 })
+        ));
+
+        index.update_settings(|settings| settings.reset_chunking_max_words()).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.chunking(&rtxn).unwrap(), None);
+        assert!(index.word_docids.get(&rtxn, "lazy").unwrap().is_none());
+    }
+
+    #[test]
+    fn document_hook_transforms_the_added_documents() {
+        // lowercases the ASCII letters of the JSON object of the document
+        let lowercase = wat::parse_str(
+            r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "alloc") (param $len i32) (result i32) (i32.const 0))
+              (func (export "transform") (param $ptr i32) (param $len i32) (result i64)
+                (local $i i32) (local $c i32)
+                (block $done
+                  (loop $next
+                    (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                    (local.set $c (i32.load8_u (i32.add (local.get $ptr) (local.get $i))))
+                    (if (i32.and (i32.ge_u (local.get $c) (i32.const 65))
+                                 (i32.le_u (local.get $c) (i32.const 90)))
+                      (then (i32.store8 (i32.add (local.get $ptr) (local.get $i))
+                                        (i32.add (local.get $c) (i32.const 32)))))
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br $next)))
+                (i64.or (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+                        (i64.extend_i32_u (local.get $len)))))
+            "#,
+        )
+        .unwrap();
+        let never_ending = wat::parse_str(
+            r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "alloc") (param $len i32) (result i32) (i32.const 0))
+              (func (export "transform") (param $ptr i32) (param $len i32) (result i64)
+                (loop $forever (br $forever))
+                (unreachable)))
+            "#,
+        )
+        .unwrap();
+
+        let index = TempIndex::new();
+        index.update_settings(|settings| settings.set_document_hook_module(lowercase)).unwrap();
+        index.add_documents(documents!([{ "id": 0, "title": "Hello WORLD" }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let (_docid, obkv) = index.documents(&rtxn, [0]).unwrap()[0];
+        let document = crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap();
+        assert_eq!(document["title"], "hello world");
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_document_hook_module(never_ending);
+                settings.set_document_hook_fuel(1000);
+            })
+            .unwrap();
+        let error = index.add_documents(documents!([{ "id": 1, "title": "Hello" }])).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::DocumentHookFailed { position: 0, .. })
+        ));
+
+        let error = index
+            .update_settings(|settings| settings.set_document_hook_module(b"not wasm".to_vec()))
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidDocumentHook(_))));
+
+        index.update_settings(|settings| settings.reset_document_hook_module()).unwrap();
+        index.add_documents(documents!([{ "id": 1, "title": "Hello" }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.document_hook(&rtxn).unwrap(), None);
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 2);
+    }
+
+    #[test]
+    fn document_hook_is_bounded() {
+        // returns an output larger than its memory
+        let out_of_bounds = wat::parse_str(
+            r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "alloc") (param $len i32) (result i32) (i32.const 0))
+              (func (export "transform") (param $ptr i32) (param $len i32) (result i64)
+                (i64.const 0xffffffff)))
+            "#,
+        )
+        .unwrap();
+        // asks for 512MiB of memory
+        let too_much_memory = wat::parse_str(
+            r#"
+            (module
+              (memory (export "memory") 8192)
+              (func (export "alloc") (param $len i32) (result i32) (i32.const 0))
+              (func (export "transform") (param $ptr i32) (param $len i32) (result i64)
+                (i64.const 0)))
+            "#,
+        )
+        .unwrap();
+
+        let index = TempIndex::new();
+        index.update_settings(|settings| settings.set_document_hook_module(out_of_bounds)).unwrap();
+        let error = index.add_documents(documents!([{ "id": 0, "title": "Hello" }])).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::DocumentHookFailed { position: 0, .. })
+        ));
+
+        let error = index
+            .update_settings(|settings| settings.set_document_hook_module(too_much_memory))
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidDocumentHook(_))));
+    }
+
+    #[test]
+    fn pii_attributes_are_stored_encrypted() {
+        let mut index = TempIndex::new();
+        index
+            .update_settings(|settings| settings.set_pii_attributes(btreeset! { S("email") }))
+            .unwrap();
+
+        let error = index
+            .add_documents(documents!([{ "id": 0, "email": "kefir@meilisearch.com" }]))
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::MissingPiiEncryptionKey)));
+
+        let cipher = PiiCipher::new("secret");
+        index.indexer_config.pii_cipher = Some(cipher.clone());
+        index
+            .add_documents(
+                documents!([{ "id": 0, "name": "kefir", "email": "kefir@meilisearch.com" }]),
+            )
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let (_docid, obkv) = index.documents(&rtxn, [0]).unwrap()[0];
+        let document = crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap();
+        assert_eq!(document["name"], "kefir");
+        assert_ne!(document["email"], "kefir@meilisearch.com");
+        assert_eq!(cipher.decrypt(&document["email"]).unwrap(), "kefir@meilisearch.com");
+    }
+
+    #[test]
+    fn pii_attributes_update_reencrypts_the_documents() {
+        let mut index = TempIndex::new();
+        let cipher = PiiCipher::new("secret");
+        index.indexer_config.pii_cipher = Some(cipher.clone());
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key(S("id"));
+                settings.set_filterable_fields(hashset! { S("email"), S("name") });
+            })
+            .unwrap();
+        index
+            .add_documents(
+                documents!([{ "id": 0, "name": "kefir", "email": "kefir@meilisearch.com" }]),
+            )
+            .unwrap();
+
+        let document = |index: &TempIndex| {
+            let rtxn = index.read_txn().unwrap();
+            let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+            let (_docid, obkv) = index.documents(&rtxn, [0]).unwrap()[0];
+            crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap()
+        };
+        let count = |index: &TempIndex, filter: &str| {
+            let rtxn = index.read_txn().unwrap();
+            let filter = crate::Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, index).unwrap().len()
+        };
+
+        // the stored email is encrypted and its clear value isn't indexed anymore
+        index
+            .update_settings(|settings| settings.set_pii_attributes(btreeset! { S("email") }))
+            .unwrap();
+        let encrypted = document(&index);
+        assert_eq!(encrypted["name"], "kefir");
+        assert_eq!(cipher.decrypt(&encrypted["email"]).unwrap(), "kefir@meilisearch.com");
+        assert_eq!(count(&index, "email = \"kefir@meilisearch.com\""), 0);
+        assert_eq!(count(&index, "name = kefir"), 1);
+
+        // the stored email is decrypted and indexed again
+        index.update_settings(|settings| settings.reset_pii_attributes()).unwrap();
+        assert_eq!(document(&index)["email"], "kefir@meilisearch.com");
+        assert_eq!(count(&index, "email = \"kefir@meilisearch.com\""), 1);
+
+        // without a key the documents can't be encrypted
+        index.indexer_config.pii_cipher = None;
+        let error = index
+            .update_settings(|settings| settings.set_pii_attributes(btreeset! { S("email") }))
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::MissingPiiEncryptionKey)));
+    }
+
+    #[test]
+    fn nested_pii_attributes_are_refused() {
+        let mut index = TempIndex::new();
+        index.indexer_config.pii_cipher = Some(PiiCipher::new("secret"));
+        let error = index
+            .update_settings(|settings| {
+                settings.set_pii_attributes(btreeset! { S("name"), S("contact.email") })
+            })
+            .unwrap_err();
+        insta::assert_snapshot!(error, @"`piiAttributes` setting is invalid. The `contact.email` attribute is nested in another one, only the top-level attributes can be PII: make the whole `contact` attribute PII instead.");
+
+        // the whole object is encrypted instead
+        index
+            .update_settings(|settings| settings.set_pii_attributes(btreeset! { S("contact") }))
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "contact": { "email": "kefir@meilisearch.com" } }
+            ]))
+            .unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let (_docid, obkv) = index.documents(&rtxn, [0]).unwrap()[0];
+        let document = crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap();
+        assert!(document["contact"].is_string());
+        assert!(!document["contact"].to_string().contains("kefir"));
+    }
+
     #[test]
     fn test_correct_settings_init() {
         let index = TempIndex::new();
@@ -2608,15 +4315,19 @@ mod tests {
                     index: _,
                     indexer_config: _,
                     searchable_fields,
+                    searchable_weights,
                     displayed_fields,
                     filterable_fields,
                     sortable_fields,
+                    dense_sortable_fields,
                     criteria,
                     stop_words,
                     non_separator_tokens,
                     separator_tokens,
                     dictionary,
+                    literal_words,
                     distinct_field,
+                    version_field,
                     synonyms,
                     primary_key,
                     authorize_typos,
@@ -2624,23 +4335,66 @@ mod tests {
                     min_word_len_one_typo,
                     exact_words,
                     exact_attributes,
+                    presence_only_attributes,
+                    chunking_max_words,
+                    chunking_overlap,
+                    document_hook_module,
+                    document_hook_fuel,
+                    computed_fields,
+                    prepared_filters,
+                    pii_attributes,
+                    query_max_length,
+                    query_max_terms,
+                    query_max_phrases,
+                    regex_filter_enabled,
+                    regex_filter_max_candidates,
+                    half_typos_keyboard_layout,
+                    half_typos_on_accents,
+                    disable_typos_on_numbers,
+                    first_letter_typos,
+                    typo_cost_one_typo,
+                    typo_cost_two_typos,
+                    typo_cost_split,
+                    enable_phonetic,
                     max_values_per_facet,
                     sort_facet_values_by,
+                    facet_value_aliases,
                     pagination_max_total_hits,
                     proximity_precision,
                     embedder_settings,
                     search_cutoff,
+                    prefix_cache_size,
+                    long_query_threshold,
+                    quote_characters,
+                    language_preset,
+                    field_languages,
+                    min_prefix_length,
+                    blocked_terms,
+                    blocked_terms_mode,
+                    new_fields_searchable,
+                    new_fields_filterable,
+                    query_rewrites,
+                    position_decay,
+                    max_batch_size,
+                    max_batch_payload,
+                    collation_locale,
+                    collation_attributes,
+                    tie_breaker,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
+                assert!(searchable_weights.is_empty());
                 assert!(matches!(displayed_fields, Setting::NotSet));
                 assert!(matches!(filterable_fields, Setting::NotSet));
                 assert!(matches!(sortable_fields, Setting::NotSet));
+                assert!(matches!(dense_sortable_fields, Setting::NotSet));
                 assert!(matches!(criteria, Setting::NotSet));
                 assert!(matches!(stop_words, Setting::NotSet));
                 assert!(matches!(non_separator_tokens, Setting::NotSet));
                 assert!(matches!(separator_tokens, Setting::NotSet));
                 assert!(matches!(dictionary, Setting::NotSet));
+                assert!(matches!(literal_words, Setting::NotSet));
                 assert!(matches!(distinct_field, Setting::NotSet));
+                assert!(matches!(version_field, Setting::NotSet));
                 assert!(matches!(synonyms, Setting::NotSet));
                 assert!(matches!(primary_key, Setting::NotSet));
                 assert!(matches!(authorize_typos, Setting::NotSet));
@@ -2648,12 +4402,52 @@ mod tests {
                 assert!(matches!(min_word_len_one_typo, Setting::NotSet));
                 assert!(matches!(exact_words, Setting::NotSet));
                 assert!(matches!(exact_attributes, Setting::NotSet));
+                assert!(matches!(presence_only_attributes, Setting::NotSet));
+                assert!(matches!(chunking_max_words, Setting::NotSet));
+                assert!(matches!(chunking_overlap, Setting::NotSet));
+                assert!(matches!(document_hook_module, Setting::NotSet));
+                assert!(matches!(document_hook_fuel, Setting::NotSet));
+                assert!(matches!(computed_fields, Setting::NotSet));
+                assert!(matches!(prepared_filters, Setting::NotSet));
+                assert!(matches!(pii_attributes, Setting::NotSet));
+                assert!(matches!(query_max_length, Setting::NotSet));
+                assert!(matches!(query_max_terms, Setting::NotSet));
+                assert!(matches!(query_max_phrases, Setting::NotSet));
+                assert!(matches!(regex_filter_enabled, Setting::NotSet));
+                assert!(matches!(regex_filter_max_candidates, Setting::NotSet));
+                assert!(matches!(half_typos_keyboard_layout, Setting::NotSet));
+                assert!(matches!(half_typos_on_accents, Setting::NotSet));
+                assert!(matches!(disable_typos_on_numbers, Setting::NotSet));
+                assert!(matches!(first_letter_typos, Setting::NotSet));
+                assert!(matches!(typo_cost_one_typo, Setting::NotSet));
+                assert!(matches!(typo_cost_two_typos, Setting::NotSet));
+                assert!(matches!(typo_cost_split, Setting::NotSet));
+                assert!(matches!(enable_phonetic, Setting::NotSet));
                 assert!(matches!(max_values_per_facet, Setting::NotSet));
                 assert!(matches!(sort_facet_values_by, Setting::NotSet));
+                assert!(matches!(facet_value_aliases, Setting::NotSet));
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
                 assert!(matches!(proximity_precision, Setting::NotSet));
                 assert!(matches!(embedder_settings, Setting::NotSet));
                 assert!(matches!(search_cutoff, Setting::NotSet));
+                assert!(matches!(prefix_cache_size, Setting::NotSet));
+                assert!(matches!(long_query_threshold, Setting::NotSet));
+                assert!(matches!(quote_characters, Setting::NotSet));
+                assert!(matches!(language_preset, Setting::NotSet));
+                assert!(matches!(field_languages, Setting::NotSet));
+                assert!(matches!(field_languages, Setting::NotSet));
+                assert!(matches!(min_prefix_length, Setting::NotSet));
+                assert!(matches!(blocked_terms, Setting::NotSet));
+                assert!(matches!(blocked_terms_mode, Setting::NotSet));
+                assert!(matches!(new_fields_searchable, Setting::NotSet));
+                assert!(matches!(new_fields_filterable, Setting::NotSet));
+                assert!(matches!(query_rewrites, Setting::NotSet));
+                assert!(matches!(position_decay, Setting::NotSet));
+                assert!(matches!(max_batch_size, Setting::NotSet));
+                assert!(matches!(max_batch_payload, Setting::NotSet));
+                assert!(matches!(collation_locale, Setting::NotSet));
+                assert!(matches!(collation_attributes, Setting::NotSet));
+                assert!(matches!(tie_breaker, Setting::NotSet));
             })
             .unwrap();
     }